@@ -0,0 +1,259 @@
+//! Cavebot Detection Module
+//!
+//! Cavebots walk a fixed loop of waypoints over and over. Humans repeating
+//! a hunting route drift off the path and never lap it in exactly the same
+//! time. The detector looks for a period in the recent position history
+//! at which the path repeats itself tile for tile, and flags it when the
+//! loop is large, repeats several times, and every lap takes the same time.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::detection::{DetectionMetrics, DetectionResult};
+use crate::{CheatType, PlayerMonitor, ViolationSeverity};
+
+/// Cavebot detection settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CavebotConfig {
+    /// Enable loop detection
+    pub enabled: bool,
+    /// Shortest loop, in steps, worth considering
+    pub min_loop_length: usize,
+    /// Distinct tiles a loop must cover, so pacing between two tiles is ignored
+    pub min_distinct_tiles: usize,
+    /// Laps that must be observed back to back
+    pub min_repetitions: usize,
+    /// Fraction of steps that must land on the same tile as one lap earlier
+    pub min_similarity: f64,
+    /// Tiles a step may be off and still count as the same
+    pub tile_tolerance: i32,
+    /// Highest coefficient of variation of lap times considered machine-precise
+    pub max_lap_time_variation: f64,
+}
+
+impl Default for CavebotConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_loop_length: 12,
+            min_distinct_tiles: 8,
+            min_repetitions: 3,
+            min_similarity: 0.95,
+            tile_tolerance: 0,
+            max_lap_time_variation: 0.05,
+        }
+    }
+}
+
+/// A repeating path found in a player's movement
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathLoop {
+    /// Steps per lap
+    pub period: usize,
+    /// Consecutive laps observed
+    pub laps: usize,
+    /// Fraction of steps repeated one lap later
+    pub similarity: f64,
+    /// Mean lap time in milliseconds
+    pub lap_ms: f64,
+    /// Coefficient of variation of the lap times
+    pub lap_variation: f64,
+    /// The most recent lap
+    pub positions: Vec<(i32, i32, i32, DateTime<Utc>)>,
+}
+
+/// Waypoint loop detector
+pub struct CavebotDetector {
+    config: CavebotConfig,
+}
+
+impl CavebotDetector {
+    /// Create a new cavebot detector
+    pub fn new(config: CavebotConfig) -> Self {
+        Self { config }
+    }
+
+    /// Check the player's recent movement for a cavebot loop
+    pub fn check(&self, monitor: &PlayerMonitor) -> Option<DetectionResult> {
+        if !self.config.enabled {
+            return None;
+        }
+
+        let path_loop = self.find_loop(&monitor.position_history)?;
+        let severity = if path_loop.laps > self.config.min_repetitions {
+            ViolationSeverity::High
+        } else {
+            ViolationSeverity::Medium
+        };
+
+        Some(DetectionResult {
+            cheat_type: CheatType::Botting,
+            severity,
+            confidence: path_loop.similarity,
+            description: format!(
+                "Cavebot loop: {} steps repeated {} times, {:.0}ms per lap",
+                path_loop.period, path_loop.laps, path_loop.lap_ms
+            ),
+            metrics: DetectionMetrics {
+                time_window_ms: Some((path_loop.lap_ms * path_loop.laps as f64) as u64),
+                pattern_score: Some(path_loop.similarity),
+                path_loop: path_loop.positions,
+                ..Default::default()
+            },
+        })
+    }
+
+    /// Find the shortest loop the end of `history` repeats with machine precision
+    pub fn find_loop(&self, history: &[(i32, i32, i32, DateTime<Utc>)]) -> Option<PathLoop> {
+        let config = &self.config;
+        let repetitions = config.min_repetitions.max(2);
+        let len = history.len();
+        let mut best: Option<PathLoop> = None;
+
+        for period in config.min_loop_length.max(1)..=len / repetitions {
+            let window = &history[len - period * repetitions..];
+            let pairs = period * (repetitions - 1);
+            let similarity = self.similarity(window, period);
+            if similarity < config.min_similarity {
+                continue;
+            }
+
+            let distinct: HashSet<(i32, i32, i32)> = window[..period].iter().map(|&(x, y, z, _)| (x, y, z)).collect();
+            if distinct.len() < config.min_distinct_tiles {
+                continue;
+            }
+
+            let laps: Vec<f64> = (0..pairs)
+                .map(|i| (window[i + period].3 - window[i].3).num_milliseconds() as f64)
+                .collect();
+            let mean = laps.iter().sum::<f64>() / laps.len() as f64;
+            if mean <= 0.0 {
+                continue;
+            }
+            let variance = laps.iter().map(|lap| (lap - mean).powi(2)).sum::<f64>() / laps.len() as f64;
+            let variation = variance.sqrt() / mean;
+            if variation > config.max_lap_time_variation {
+                continue;
+            }
+
+            if best.as_ref().is_none_or(|b| similarity > b.similarity) {
+                best = Some(PathLoop {
+                    period,
+                    laps: self.count_laps(history, period),
+                    similarity,
+                    lap_ms: mean,
+                    lap_variation: variation,
+                    positions: history[len - period..].to_vec(),
+                });
+            }
+        }
+
+        best
+    }
+
+    /// Fraction of steps in `window` on the same tile as one lap later
+    fn similarity(&self, window: &[(i32, i32, i32, DateTime<Utc>)], period: usize) -> f64 {
+        let pairs = window.len() - period;
+        let matching = (0..pairs)
+            .filter(|&i| {
+                let (x1, y1, z1, _) = window[i];
+                let (x2, y2, z2, _) = window[i + period];
+                z1 == z2 && (x1 - x2).abs().max((y1 - y2).abs()) <= self.config.tile_tolerance
+            })
+            .count();
+        matching as f64 / pairs as f64
+    }
+
+    /// Consecutive laps of `period` steps at the end of `history`
+    fn count_laps(&self, history: &[(i32, i32, i32, DateTime<Utc>)], period: usize) -> usize {
+        let mut laps = 1;
+        while (laps + 1) * period <= history.len() {
+            let window = &history[history.len() - (laps + 1) * period..];
+            if self.similarity(window, period) < self.config.min_similarity {
+                break;
+            }
+            laps += 1;
+        }
+        laps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporter::ViolationReporter;
+    use crate::ViolationAction;
+    use chrono::Duration;
+    use uuid::Uuid;
+
+    /// Perimeter of a 5x5 room, 16 steps
+    fn waypoints() -> Vec<(i32, i32)> {
+        let mut path = Vec::new();
+        path.extend((0..4).map(|i| (100 + i, 100)));
+        path.extend((0..4).map(|i| (104, 100 + i)));
+        path.extend((0..4).map(|i| (104 - i, 104)));
+        path.extend((0..4).map(|i| (100, 104 - i)));
+        path
+    }
+
+    #[test]
+    fn test_looping_cavebot_flagged_with_loop_evidence() {
+        let detector = CavebotDetector::new(CavebotConfig::default());
+        let mut monitor = PlayerMonitor::new(Uuid::new_v4());
+        let start = Utc::now();
+        for (step, (x, y)) in waypoints().into_iter().cycle().take(80).enumerate() {
+            monitor.position_history.push((x, y, 7, start + Duration::milliseconds(step as i64 * 200)));
+        }
+
+        let path_loop = detector.find_loop(&monitor.position_history).unwrap();
+        assert_eq!((path_loop.period, path_loop.laps), (16, 5));
+        assert_eq!(path_loop.lap_ms, 3_200.0);
+
+        let detection = detector.check(&monitor).unwrap();
+        assert_eq!(detection.cheat_type, CheatType::Botting);
+        assert_eq!(detection.severity, ViolationSeverity::High);
+        let violation = ViolationReporter::new().report(
+            Uuid::new_v4(),
+            monitor.character_id,
+            "Bot",
+            detection,
+            ViolationAction::FlagForReview,
+        );
+        assert_eq!(violation.evidence.position_history.len(), 16);
+        assert_eq!(violation.evidence.position_history[15].0, 100);
+    }
+
+    #[test]
+    fn test_organic_movement_not_flagged() {
+        let detector = CavebotDetector::new(CavebotConfig::default());
+        let start = Utc::now();
+
+        // Wandering around with uneven pacing
+        let mut monitor = PlayerMonitor::new(Uuid::new_v4());
+        let (mut x, mut y, mut seed) = (100i32, 100i32, 7u64);
+        let mut time = start;
+        for _ in 0..100 {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            match (seed >> 33) % 4 {
+                0 => x += 1,
+                1 => x -= 1,
+                2 => y += 1,
+                _ => y -= 1,
+            }
+            time += Duration::milliseconds(150 + ((seed >> 40) % 300) as i64);
+            monitor.position_history.push((x, y, 7, time));
+        }
+        assert!(detector.check(&monitor).is_none());
+
+        // The same route walked by hand takes a different time every lap
+        let mut monitor = PlayerMonitor::new(Uuid::new_v4());
+        let mut time = start;
+        for (step, (x, y)) in waypoints().into_iter().cycle().take(80).enumerate() {
+            time += Duration::milliseconds(if (step / 16) % 2 == 0 { 200 } else { 260 });
+            monitor.position_history.push((x, y, 7, time));
+        }
+        assert!(detector.find_loop(&monitor.position_history).is_none());
+    }
+}
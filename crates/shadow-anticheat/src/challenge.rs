@@ -0,0 +1,273 @@
+//! In-game Challenge Module
+//!
+//! Human-verification prompts issued to players flagged by behavior
+//! analysis. Each challenge has a unique ID and a single accepted answer;
+//! it must be answered within the response window, is consumed by the
+//! first answer, and cannot be answered before it was issued. Wrong answers
+//! and timeouts escalate the action taken against the session.
+
+use chrono::{DateTime, Duration, Utc};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::ViolationAction;
+
+/// Challenge configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChallengeConfig {
+    /// Seconds the player has to answer
+    pub response_window_secs: i64,
+    /// Actions applied for the 1st, 2nd, ... failure; the last one repeats
+    pub escalation: Vec<ViolationAction>,
+}
+
+impl Default for ChallengeConfig {
+    fn default() -> Self {
+        Self {
+            response_window_secs: 60,
+            escalation: vec![
+                ViolationAction::Warn,
+                ViolationAction::Kick,
+                ViolationAction::TempBan { hours: 24 },
+            ],
+        }
+    }
+}
+
+/// A challenge as sent to the client (without the answer)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Challenge {
+    pub id: Uuid,
+    pub character_id: Uuid,
+    pub prompt: String,
+    pub issued_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// A player's answer to a challenge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChallengeResponse {
+    pub challenge_id: Uuid,
+    pub answer: String,
+}
+
+/// Result of answering or timing out a challenge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChallengeOutcome {
+    /// Correct answer; the flag is cleared
+    Passed,
+    /// Wrong answer or timeout; the action to apply
+    Failed(ViolationAction),
+}
+
+/// Challenge errors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChallengeError {
+    /// No challenge is pending for the character
+    NoPendingChallenge,
+    /// Response refers to a different (old or unknown) challenge
+    WrongChallenge,
+}
+
+impl std::fmt::Display for ChallengeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChallengeError::NoPendingChallenge => write!(f, "No challenge pending"),
+            ChallengeError::WrongChallenge => write!(f, "Response does not match the pending challenge"),
+        }
+    }
+}
+
+impl std::error::Error for ChallengeError {}
+
+#[derive(Debug, Clone)]
+struct PendingChallenge {
+    challenge: Challenge,
+    answer: String,
+}
+
+/// Tracks pending challenges and failure counts per character
+pub struct ChallengeManager {
+    config: ChallengeConfig,
+    pending: HashMap<Uuid, PendingChallenge>,
+    failures: HashMap<Uuid, u32>,
+}
+
+impl ChallengeManager {
+    pub fn new(config: ChallengeConfig) -> Self {
+        Self {
+            config,
+            pending: HashMap::new(),
+            failures: HashMap::new(),
+        }
+    }
+
+    /// Issue a new challenge, replacing any pending one
+    pub fn issue(&mut self, character_id: Uuid, now: DateTime<Utc>) -> Challenge {
+        let mut rng = rand::thread_rng();
+        let (a, b) = (rng.gen_range(2..20), rng.gen_range(2..20));
+
+        let challenge = Challenge {
+            id: Uuid::new_v4(),
+            character_id,
+            prompt: format!("What is {} + {}?", a, b),
+            issued_at: now,
+            expires_at: now + Duration::seconds(self.config.response_window_secs),
+        };
+        self.pending.insert(
+            character_id,
+            PendingChallenge { challenge: challenge.clone(), answer: (a + b).to_string() },
+        );
+        challenge
+    }
+
+    /// Answer the pending challenge. The challenge is consumed either way.
+    pub fn answer(
+        &mut self,
+        character_id: Uuid,
+        response: &ChallengeResponse,
+        now: DateTime<Utc>,
+    ) -> Result<ChallengeOutcome, ChallengeError> {
+        let pending = self.pending.get(&character_id).ok_or(ChallengeError::NoPendingChallenge)?;
+        if pending.challenge.id != response.challenge_id {
+            return Err(ChallengeError::WrongChallenge);
+        }
+        let pending = self.pending.remove(&character_id).expect("pending challenge exists");
+
+        if now <= pending.challenge.expires_at && response.answer.trim() == pending.answer {
+            self.failures.remove(&character_id);
+            Ok(ChallengeOutcome::Passed)
+        } else {
+            Ok(ChallengeOutcome::Failed(self.escalate(character_id)))
+        }
+    }
+
+    /// Fail every challenge whose window has passed
+    pub fn expire(&mut self, now: DateTime<Utc>) -> Vec<(Uuid, ViolationAction)> {
+        let expired: Vec<Uuid> = self
+            .pending
+            .iter()
+            .filter(|(_, p)| now > p.challenge.expires_at)
+            .map(|(id, _)| *id)
+            .collect();
+
+        expired
+            .into_iter()
+            .map(|character_id| {
+                self.pending.remove(&character_id);
+                (character_id, self.escalate(character_id))
+            })
+            .collect()
+    }
+
+    fn escalate(&mut self, character_id: Uuid) -> ViolationAction {
+        let failures = self.failures.entry(character_id).or_insert(0);
+        *failures += 1;
+        let idx = (*failures as usize - 1).min(self.config.escalation.len().saturating_sub(1));
+        self.config
+            .escalation
+            .get(idx)
+            .copied()
+            .unwrap_or(ViolationAction::FlagForReview)
+    }
+
+    /// Whether the character has a challenge pending
+    pub fn is_pending(&self, character_id: Uuid) -> bool {
+        self.pending.contains_key(&character_id)
+    }
+
+    /// Consecutive failures for a character
+    pub fn failures(&self, character_id: Uuid) -> u32 {
+        self.failures.get(&character_id).copied().unwrap_or(0)
+    }
+}
+
+impl Default for ChallengeManager {
+    fn default() -> Self {
+        Self::new(ChallengeConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_known_answer(manager: &mut ChallengeManager, character_id: Uuid, now: DateTime<Utc>) -> Uuid {
+        let challenge = manager.issue(character_id, now);
+        manager.pending.get_mut(&character_id).unwrap().answer = "12".to_string();
+        challenge.id
+    }
+
+    #[test]
+    fn test_challenge_cannot_be_pre_answered_or_replayed() {
+        let mut manager = ChallengeManager::default();
+        let player = Uuid::new_v4();
+        let now = Utc::now();
+
+        // Answering before a challenge exists is rejected
+        let early = ChallengeResponse { challenge_id: Uuid::new_v4(), answer: "12".into() };
+        assert_eq!(manager.answer(player, &early, now), Err(ChallengeError::NoPendingChallenge));
+
+        let id = with_known_answer(&mut manager, player, now);
+        assert_eq!(manager.answer(player, &early, now), Err(ChallengeError::WrongChallenge));
+
+        let response = ChallengeResponse { challenge_id: id, answer: "12".into() };
+        assert_eq!(manager.answer(player, &response, now), Ok(ChallengeOutcome::Passed));
+        // Replaying the same answer fails: the challenge was consumed
+        assert_eq!(manager.answer(player, &response, now), Err(ChallengeError::NoPendingChallenge));
+    }
+
+    #[test]
+    fn test_failures_escalate() {
+        let mut manager = ChallengeManager::default();
+        let player = Uuid::new_v4();
+        let now = Utc::now();
+
+        let id = with_known_answer(&mut manager, player, now);
+        let wrong = ChallengeResponse { challenge_id: id, answer: "13".into() };
+        assert_eq!(
+            manager.answer(player, &wrong, now),
+            Ok(ChallengeOutcome::Failed(ViolationAction::Warn))
+        );
+
+        // A correct answer after the window counts as a failure
+        let id = with_known_answer(&mut manager, player, now);
+        let late = ChallengeResponse { challenge_id: id, answer: "12".into() };
+        assert_eq!(
+            manager.answer(player, &late, now + Duration::seconds(61)),
+            Ok(ChallengeOutcome::Failed(ViolationAction::Kick))
+        );
+        assert_eq!(manager.failures(player), 2);
+    }
+
+    fn solve(prompt: &str) -> String {
+        let numbers: Vec<u32> = prompt
+            .trim_start_matches("What is ")
+            .trim_end_matches('?')
+            .split(" + ")
+            .map(|n| n.parse().unwrap())
+            .collect();
+        numbers.iter().sum::<u32>().to_string()
+    }
+
+    #[test]
+    fn test_system_challenge_flow() {
+        let mut system = crate::AntiCheatSystem::new(crate::AntiCheatConfig::default());
+        let (human, bot) = (Uuid::new_v4(), Uuid::new_v4());
+
+        // Correct answer clears the flag
+        let challenge = system.issue_challenge(human);
+        assert!(system.is_flagged(human));
+        let response = ChallengeResponse { challenge_id: challenge.id, answer: solve(&challenge.prompt) };
+        assert_eq!(system.answer_challenge(human, &response), Ok(ChallengeOutcome::Passed));
+        assert!(!system.is_flagged(human));
+
+        // Timeout escalates and keeps the flag
+        let challenge = system.issue_challenge(bot);
+        let escalated = system.expire_challenges(challenge.expires_at + Duration::seconds(1));
+        assert_eq!(escalated, vec![(bot, ViolationAction::Warn)]);
+        assert!(system.is_flagged(bot));
+    }
+}
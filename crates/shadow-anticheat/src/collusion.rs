@@ -0,0 +1,293 @@
+//! Market Collusion Module
+//!
+//! Detects bids and sales between accounts that are likely owned by the
+//! same person: shill bidding on your own alt's auction to inflate the
+//! price, or wash-trading items back and forth. Accounts are linked by a
+//! shared login IP, a shared payment method or a history of gold
+//! transfers. Linked trades are never blocked, only flagged so staff can
+//! review them.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use uuid::Uuid;
+
+/// Collusion detection configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CollusionConfig {
+    /// Link accounts that logged in from the same IP
+    pub link_by_ip: bool,
+    /// Link accounts that paid with the same payment method
+    pub link_by_payment: bool,
+    /// Transfers between two accounts before they count as linked (0 = off)
+    pub min_linking_transfers: u32,
+}
+
+impl Default for CollusionConfig {
+    fn default() -> Self {
+        Self {
+            link_by_ip: true,
+            link_by_payment: true,
+            min_linking_transfers: 3,
+        }
+    }
+}
+
+/// Kind of market trade
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MarketTradeKind {
+    /// Bid on a character or item auction
+    AuctionBid,
+    /// Completed market sale
+    MarketSale,
+}
+
+/// A bid or sale between two accounts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketTrade {
+    /// Auction or offer id
+    pub id: Uuid,
+    pub kind: MarketTradeKind,
+    /// Bidding or buying account
+    pub buyer_account_id: Uuid,
+    /// Auction owner or selling account
+    pub seller_account_id: Uuid,
+    pub amount: u64,
+    pub at: DateTime<Utc>,
+}
+
+/// Why two accounts are considered linked
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AccountLink {
+    /// Both accounts logged in from this IP
+    SharedIp(IpAddr),
+    /// Both accounts used the same payment method
+    SharedPaymentMethod,
+    /// The accounts sent each other gold this many times
+    TransferHistory { transfers: u32, total: u64 },
+}
+
+/// A trade flagged for review
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollusionFlag {
+    pub id: Uuid,
+    pub trade: MarketTrade,
+    pub links: Vec<AccountLink>,
+    pub flagged_at: DateTime<Utc>,
+    /// Was reviewed by staff
+    pub reviewed: bool,
+}
+
+/// Summary of flagged trades for staff
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollusionReport {
+    pub generated_at: DateTime<Utc>,
+    /// Unreviewed flags, most recent first
+    pub flags: Vec<CollusionFlag>,
+    /// Gold moved through flagged trades
+    pub total_amount: u64,
+    /// Accounts involved, with how many flagged trades each took part in
+    pub accounts: HashMap<Uuid, usize>,
+}
+
+/// Links accounts and flags market trades between linked accounts
+pub struct CollusionDetector {
+    config: CollusionConfig,
+    /// Login IPs by account
+    ips: HashMap<Uuid, HashSet<IpAddr>>,
+    /// Payment method fingerprints by account
+    payment_methods: HashMap<Uuid, HashSet<String>>,
+    /// Transfer count and total by unordered account pair
+    transfers: HashMap<(Uuid, Uuid), (u32, u64)>,
+    flags: Vec<CollusionFlag>,
+}
+
+impl CollusionDetector {
+    pub fn new(config: CollusionConfig) -> Self {
+        Self {
+            config,
+            ips: HashMap::new(),
+            payment_methods: HashMap::new(),
+            transfers: HashMap::new(),
+            flags: Vec::new(),
+        }
+    }
+
+    /// Record a login IP of an account
+    pub fn record_login(&mut self, account_id: Uuid, ip: IpAddr) {
+        self.ips.entry(account_id).or_default().insert(ip);
+    }
+
+    /// Record a payment method fingerprint (e.g. a hashed card number) of an account
+    pub fn record_payment_method(&mut self, account_id: Uuid, fingerprint: &str) {
+        self.payment_methods
+            .entry(account_id)
+            .or_default()
+            .insert(fingerprint.to_string());
+    }
+
+    /// Record a gold transfer between two accounts
+    pub fn record_transfer(&mut self, from: Uuid, to: Uuid, amount: u64) {
+        let entry = self.transfers.entry(pair(from, to)).or_default();
+        entry.0 += 1;
+        entry.1 += amount;
+    }
+
+    /// Everything linking two accounts
+    pub fn links(&self, a: Uuid, b: Uuid) -> Vec<AccountLink> {
+        let mut links = Vec::new();
+        if a == b {
+            return links;
+        }
+
+        if self.config.link_by_ip {
+            if let (Some(ips_a), Some(ips_b)) = (self.ips.get(&a), self.ips.get(&b)) {
+                links.extend(ips_a.intersection(ips_b).map(|ip| AccountLink::SharedIp(*ip)));
+            }
+        }
+        if self.config.link_by_payment {
+            if let (Some(pay_a), Some(pay_b)) = (self.payment_methods.get(&a), self.payment_methods.get(&b)) {
+                if !pay_a.is_disjoint(pay_b) {
+                    links.push(AccountLink::SharedPaymentMethod);
+                }
+            }
+        }
+        if self.config.min_linking_transfers > 0 {
+            if let Some(&(transfers, total)) = self.transfers.get(&pair(a, b)) {
+                if transfers >= self.config.min_linking_transfers {
+                    links.push(AccountLink::TransferHistory { transfers, total });
+                }
+            }
+        }
+        links
+    }
+
+    /// Check a bid or sale. Returns the flag when the accounts are linked;
+    /// the trade itself goes through either way.
+    pub fn check_trade(&mut self, trade: MarketTrade) -> Option<&CollusionFlag> {
+        let links = self.links(trade.buyer_account_id, trade.seller_account_id);
+        if links.is_empty() {
+            return None;
+        }
+
+        tracing::warn!(
+            "Possible market collusion: {:?} {} between accounts {} and {} ({} links)",
+            trade.kind,
+            trade.id,
+            trade.buyer_account_id,
+            trade.seller_account_id,
+            links.len()
+        );
+        self.flags.push(CollusionFlag {
+            id: Uuid::new_v4(),
+            trade,
+            links,
+            flagged_at: Utc::now(),
+            reviewed: false,
+        });
+        self.flags.last()
+    }
+
+    /// Mark a flag as reviewed
+    pub fn mark_reviewed(&mut self, flag_id: Uuid) -> bool {
+        match self.flags.iter_mut().find(|f| f.id == flag_id) {
+            Some(flag) => {
+                flag.reviewed = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Report of the unreviewed flags
+    pub fn report(&self) -> CollusionReport {
+        let mut flags: Vec<CollusionFlag> = self.flags.iter().filter(|f| !f.reviewed).cloned().collect();
+        flags.sort_by_key(|f| std::cmp::Reverse(f.flagged_at));
+
+        let mut accounts = HashMap::new();
+        for flag in &flags {
+            *accounts.entry(flag.trade.buyer_account_id).or_insert(0) += 1;
+            *accounts.entry(flag.trade.seller_account_id).or_insert(0) += 1;
+        }
+
+        CollusionReport {
+            generated_at: Utc::now(),
+            total_amount: flags.iter().map(|f| f.trade.amount).sum(),
+            flags,
+            accounts,
+        }
+    }
+}
+
+impl Default for CollusionDetector {
+    fn default() -> Self {
+        Self::new(CollusionConfig::default())
+    }
+}
+
+fn pair(a: Uuid, b: Uuid) -> (Uuid, Uuid) {
+    if a < b { (a, b) } else { (b, a) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bid(buyer: Uuid, seller: Uuid, amount: u64) -> MarketTrade {
+        MarketTrade {
+            id: Uuid::new_v4(),
+            kind: MarketTradeKind::AuctionBid,
+            buyer_account_id: buyer,
+            seller_account_id: seller,
+            amount,
+            at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_same_ip_self_bidding_flagged() {
+        let mut detector = CollusionDetector::default();
+        let (seller, alt) = (Uuid::new_v4(), Uuid::new_v4());
+        let home: IpAddr = "203.0.113.7".parse().unwrap();
+        detector.record_login(seller, home);
+        detector.record_login(alt, home);
+        detector.record_login(alt, "198.51.100.2".parse().unwrap());
+
+        let flag = detector.check_trade(bid(alt, seller, 50_000)).unwrap();
+        assert_eq!(flag.links, vec![AccountLink::SharedIp(home)]);
+
+        let report = detector.report();
+        assert_eq!(report.flags.len(), 1);
+        assert_eq!(report.total_amount, 50_000);
+        assert_eq!(report.accounts.get(&alt), Some(&1));
+
+        let flag_id = report.flags[0].id;
+        assert!(detector.mark_reviewed(flag_id));
+        assert!(detector.report().flags.is_empty());
+    }
+
+    #[test]
+    fn test_unrelated_bid_not_flagged() {
+        let mut detector = CollusionDetector::default();
+        let (seller, bidder) = (Uuid::new_v4(), Uuid::new_v4());
+        detector.record_login(seller, "203.0.113.7".parse().unwrap());
+        detector.record_login(bidder, "198.51.100.2".parse().unwrap());
+        detector.record_payment_method(seller, "card-a");
+        detector.record_payment_method(bidder, "card-b");
+        // Fewer transfers than it takes to link
+        detector.record_transfer(seller, bidder, 1_000);
+
+        assert!(detector.check_trade(bid(bidder, seller, 50_000)).is_none());
+        assert!(detector.report().flags.is_empty());
+
+        // A repeated transfer history does link them
+        detector.record_transfer(bidder, seller, 1_000);
+        detector.record_transfer(seller, bidder, 1_000);
+        assert_eq!(
+            detector.links(bidder, seller),
+            vec![AccountLink::TransferHistory { transfers: 3, total: 3_000 }]
+        );
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! Core detection algorithms for various cheat types.
 
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::{AntiCheatConfig, CheatType, PlayerAction, PlayerMonitor, ViolationSeverity};
@@ -37,6 +37,9 @@ pub struct DetectionMetrics {
     pub time_window_ms: Option<u64>,
     /// Pattern match score
     pub pattern_score: Option<f64>,
+    /// Repeating path that triggered a cavebot detection
+    #[serde(default)]
+    pub path_loop: Vec<(i32, i32, i32, DateTime<Utc>)>,
 }
 
 /// Cheat detector engine
@@ -50,23 +53,28 @@ impl CheatDetector {
         Self { config }
     }
 
+    /// Highest movement speed allowed for a monitored player
+    pub fn allowed_movement_speed(&self, monitor: &PlayerMonitor) -> f64 {
+        self.config.max_movement_speed + monitor.speed_bonus
+    }
+
     /// Check for speed hacks
     pub fn check_speed(&self, monitor: &PlayerMonitor) -> Option<DetectionResult> {
         let speed = monitor.last_movement_speed()?;
-        
-        if speed > self.config.max_movement_speed {
-            let severity = if speed > self.config.max_movement_speed * 3.0 {
+        let max_speed = self.allowed_movement_speed(monitor);
+
+        if speed > max_speed {
+            let severity = if speed > max_speed * 3.0 {
                 ViolationSeverity::Critical
-            } else if speed > self.config.max_movement_speed * 2.0 {
+            } else if speed > max_speed * 2.0 {
                 ViolationSeverity::High
-            } else if speed > self.config.max_movement_speed * 1.5 {
+            } else if speed > max_speed * 1.5 {
                 ViolationSeverity::Medium
             } else {
                 ViolationSeverity::Low
             };
 
-            let confidence = ((speed - self.config.max_movement_speed) 
-                / self.config.max_movement_speed).min(1.0);
+            let confidence = ((speed - max_speed) / max_speed).min(1.0);
 
             return Some(DetectionResult {
                 cheat_type: CheatType::SpeedHack,
@@ -74,11 +82,11 @@ impl CheatDetector {
                 confidence,
                 description: format!(
                     "Speed violation: {:.2} tiles/s (max: {:.2})",
-                    speed, self.config.max_movement_speed
+                    speed, max_speed
                 ),
                 metrics: DetectionMetrics {
                     speed: Some(speed),
-                    expected_max_speed: Some(self.config.max_movement_speed),
+                    expected_max_speed: Some(max_speed),
                     ..Default::default()
                 },
             });
@@ -168,6 +176,26 @@ impl CheatDetector {
         )
     }
 
+    /// Check for item use faster than the item exhaust allows
+    pub fn check_item_use_speed(&self, monitor: &PlayerMonitor) -> Option<DetectionResult> {
+        self.check_action_speed(
+            monitor,
+            PlayerAction::UseItem,
+            self.config.max_item_use_speed,
+            CheatType::ItemSpeedHack,
+        )
+    }
+
+    /// Check for creature pushes faster than the push delay allows
+    pub fn check_push_speed(&self, monitor: &PlayerMonitor) -> Option<DetectionResult> {
+        self.check_action_speed(
+            monitor,
+            PlayerAction::PushCreature,
+            self.config.max_push_speed,
+            CheatType::PushSpam,
+        )
+    }
+
     /// Generic action speed check
     fn check_action_speed(
         &self,
@@ -223,6 +251,32 @@ impl CheatDetector {
         None
     }
 
+    /// Check a session that sent more inputs in one tick than it may queue.
+    /// `tick_ms` is the engine tick length.
+    pub fn check_input_flood(&self, dropped: u32, per_tick_cap: usize, tick_ms: u64) -> Option<DetectionResult> {
+        if dropped == 0 {
+            return None;
+        }
+        let sent = per_tick_cap as f64 + dropped as f64;
+        let severity = if dropped as usize > per_tick_cap {
+            ViolationSeverity::High
+        } else {
+            ViolationSeverity::Medium
+        };
+
+        Some(DetectionResult {
+            cheat_type: CheatType::PacketManipulation,
+            severity,
+            confidence: (dropped as f64 / per_tick_cap.max(1) as f64).min(1.0),
+            description: format!("Input flood: {} inputs in one tick (max: {})", sent, per_tick_cap),
+            metrics: DetectionMetrics {
+                actions_per_second: Some(sent * 1000.0 / tick_ms.max(1) as f64),
+                time_window_ms: Some(tick_ms),
+                ..Default::default()
+            },
+        })
+    }
+
     /// Check for wall hacks (walking through blocked tiles)
     pub fn check_wallhack(
         &self,
@@ -234,3 +288,64 @@ impl CheatDetector {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::CheatDetector;
+    use crate::{AntiCheatConfig, AntiCheatSystem, CheatType, PlayerAction};
+    use chrono::{Duration, Utc};
+    use uuid::Uuid;
+
+    #[test]
+    fn test_impossible_item_use_rate_flagged() {
+        let mut system = AntiCheatSystem::new(AntiCheatConfig::default());
+        let (honest, spammer) = (Uuid::new_v4(), Uuid::new_v4());
+
+        // A handful of uses stays within the exhaust limits
+        for _ in 0..5 {
+            assert!(system.process_action(honest, PlayerAction::UseItem).is_none());
+        }
+
+        // Fifty potions in an instant cannot pass the exhaust
+        let detections: Vec<_> = (0..50)
+            .filter_map(|_| system.process_action(spammer, PlayerAction::UseItem))
+            .collect();
+        assert!(!detections.is_empty());
+        assert!(detections.iter().all(|d| d.cheat_type == CheatType::ItemSpeedHack));
+    }
+
+    #[test]
+    fn test_push_spam_flagged() {
+        let mut system = AntiCheatSystem::new(AntiCheatConfig::default());
+        let (pusher, spammer) = (Uuid::new_v4(), Uuid::new_v4());
+
+        assert!(system.process_action(pusher, PlayerAction::PushCreature).is_none());
+        let detections: Vec<_> = (0..20)
+            .filter_map(|_| system.process_action(spammer, PlayerAction::PushCreature))
+            .collect();
+        assert!(detections.iter().any(|d| d.cheat_type == CheatType::PushSpam));
+    }
+
+    #[test]
+    fn test_mount_raises_speed_allowance() {
+        let config = AntiCheatConfig { max_movement_speed: 10.0, ..Default::default() };
+        let detector = CheatDetector::new(config.clone());
+        let mut system = AntiCheatSystem::new(config);
+        let rider = Uuid::new_v4();
+
+        // Eleven tiles in one second is just above the unmounted limit
+        let start = Utc::now();
+        let monitor = system.get_monitor(rider);
+        monitor.position_history.push((100, 100, 7, start));
+        monitor.position_history.push((111, 100, 7, start + Duration::seconds(1)));
+        assert!(detector.check_speed(system.get_monitor(rider)).is_some());
+
+        system.set_speed_bonus(rider, 2.0);
+        assert_eq!(detector.allowed_movement_speed(system.get_monitor(rider)), 12.0);
+        assert!(detector.check_speed(system.get_monitor(rider)).is_none());
+
+        // Dismounting reverts the allowance
+        system.set_speed_bonus(rider, 0.0);
+        assert_eq!(detector.check_speed(system.get_monitor(rider)).unwrap().cheat_type, CheatType::SpeedHack);
+    }
+}
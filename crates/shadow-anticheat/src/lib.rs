@@ -9,17 +9,23 @@
 //! - Automated behavior analysis
 
 pub mod analysis;
+pub mod cavebot;
+pub mod challenge;
+pub mod collusion;
 pub mod detection;
 pub mod reporter;
 pub mod rules;
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use thiserror::Error;
 use uuid::Uuid;
 
 pub use analysis::BehaviorAnalyzer;
+pub use cavebot::{CavebotConfig, CavebotDetector, PathLoop};
+pub use challenge::{Challenge, ChallengeConfig, ChallengeError, ChallengeManager, ChallengeOutcome, ChallengeResponse};
+pub use collusion::{AccountLink, CollusionConfig, CollusionDetector, CollusionFlag, CollusionReport, MarketTrade, MarketTradeKind};
 pub use detection::{CheatDetector, DetectionResult};
 pub use reporter::ViolationReporter;
 pub use rules::{AntiCheatRule, RuleEngine};
@@ -59,6 +65,8 @@ pub enum CheatType {
     SpellSpeedHack,
     /// Using items too fast
     ItemSpeedHack,
+    /// Pushing other creatures too fast
+    PushSpam,
     /// Invalid position updates
     PositionHack,
     /// Duplicating items
@@ -186,6 +194,9 @@ pub struct PlayerMonitor {
     pub flagged: bool,
     /// Last update time
     pub last_update: DateTime<Utc>,
+    /// Extra movement speed allowed on top of the configured maximum
+    /// (tiles per second), e.g. from a mount
+    pub speed_bonus: f64,
 }
 
 impl PlayerMonitor {
@@ -198,6 +209,7 @@ impl PlayerMonitor {
             recent_violations: Vec::new(),
             flagged: false,
             last_update: Utc::now(),
+            speed_bonus: 0.0,
         }
     }
 
@@ -253,6 +265,7 @@ pub enum PlayerAction {
     Attack,
     CastSpell,
     UseItem,
+    PushCreature,
     Trade,
     PickupItem,
     DropItem,
@@ -273,6 +286,12 @@ pub struct AntiCheatConfig {
     pub max_attack_speed: f64,
     /// Maximum spell cast speed (casts per second)
     pub max_spell_speed: f64,
+    /// Maximum item use speed across all exhaust groups (uses per second)
+    #[serde(default = "default_max_item_use_speed")]
+    pub max_item_use_speed: f64,
+    /// Maximum creature push speed (pushes per second)
+    #[serde(default = "default_max_push_speed")]
+    pub max_push_speed: f64,
     /// Bot detection sensitivity (0.0 - 1.0)
     pub bot_sensitivity: f64,
     /// Auto-ban threshold score
@@ -283,6 +302,12 @@ pub struct AntiCheatConfig {
     pub log_packets: bool,
     /// Take screenshots on detection
     pub capture_screenshots: bool,
+    /// In-game challenge settings
+    #[serde(default)]
+    pub challenge: ChallengeConfig,
+    /// Waypoint loop detection
+    #[serde(default)]
+    pub cavebot: CavebotConfig,
 }
 
 impl Default for AntiCheatConfig {
@@ -292,15 +317,29 @@ impl Default for AntiCheatConfig {
             max_movement_speed: 20.0, // tiles per second
             max_attack_speed: 2.0, // attacks per second
             max_spell_speed: 1.0, // casts per second
+            max_item_use_speed: default_max_item_use_speed(),
+            max_push_speed: default_max_push_speed(),
             bot_sensitivity: 0.7,
             auto_ban_threshold: 90.0,
             logging_enabled: true,
             log_packets: false,
             capture_screenshots: true,
+            challenge: ChallengeConfig::default(),
+            cavebot: CavebotConfig::default(),
         }
     }
 }
 
+fn default_max_item_use_speed() -> f64 {
+    // One healing, one support and two food uses per second
+    4.0
+}
+
+fn default_max_push_speed() -> f64 {
+    // A player can't push faster than the default push delay allows
+    1.0
+}
+
 /// Main anti-cheat system
 pub struct AntiCheatSystem {
     /// Configuration
@@ -311,10 +350,16 @@ pub struct AntiCheatSystem {
     detector: CheatDetector,
     /// Behavior analyzer
     analyzer: BehaviorAnalyzer,
+    /// Waypoint loop detector
+    cavebot: CavebotDetector,
     /// Violation reporter
     reporter: ViolationReporter,
     /// Rule engine
     rules: RuleEngine,
+    /// Human-verification challenges
+    challenges: ChallengeManager,
+    /// Characters flagged until they pass a challenge
+    flagged: HashSet<Uuid>,
 }
 
 impl AntiCheatSystem {
@@ -325,8 +370,11 @@ impl AntiCheatSystem {
             monitors: HashMap::new(),
             detector: CheatDetector::new(config.clone()),
             analyzer: BehaviorAnalyzer::new(config.bot_sensitivity),
+            cavebot: CavebotDetector::new(config.cavebot.clone()),
             reporter: ViolationReporter::new(),
             rules: RuleEngine::new(),
+            challenges: ChallengeManager::new(config.challenge.clone()),
+            flagged: HashSet::new(),
         }
     }
 
@@ -336,6 +384,12 @@ impl AntiCheatSystem {
             .or_insert_with(|| PlayerMonitor::new(character_id))
     }
 
+    /// Set a character's extra movement speed allowance, e.g. when it
+    /// mounts (the mount's bonus) or dismounts (zero)
+    pub fn set_speed_bonus(&mut self, character_id: Uuid, tiles_per_second: f64) {
+        self.get_monitor(character_id).speed_bonus = tiles_per_second.max(0.0);
+    }
+
     /// Process a position update
     pub fn process_position(
         &mut self,
@@ -380,10 +434,29 @@ impl AntiCheatSystem {
         match action {
             PlayerAction::Attack => self.detector.check_attack_speed(monitor),
             PlayerAction::CastSpell => self.detector.check_spell_speed(monitor),
+            PlayerAction::UseItem => self.detector.check_item_use_speed(monitor),
+            PlayerAction::PushCreature => self.detector.check_push_speed(monitor),
             _ => None,
         }
     }
 
+    /// Process inputs a session dropped for exceeding its per-tick cap
+    /// (`tick_ms` long ticks)
+    pub fn process_input_overflow(
+        &mut self,
+        character_id: Uuid,
+        dropped: u32,
+        per_tick_cap: usize,
+        tick_ms: u64,
+    ) -> Option<DetectionResult> {
+        if !self.config.enabled {
+            return None;
+        }
+
+        self.get_monitor(character_id).last_update = Utc::now();
+        self.detector.check_input_flood(dropped, per_tick_cap, tick_ms)
+    }
+
     /// Analyze player behavior for bot detection
     pub fn analyze_behavior(&mut self, character_id: Uuid) -> Option<DetectionResult> {
         if !self.config.enabled {
@@ -391,7 +464,7 @@ impl AntiCheatSystem {
         }
 
         let monitor = self.monitors.get(&character_id)?;
-        self.analyzer.analyze(monitor)
+        self.analyzer.analyze(monitor).or_else(|| self.cavebot.check(monitor))
     }
 
     /// Report a violation
@@ -429,6 +502,49 @@ impl AntiCheatSystem {
         }
     }
 
+    /// Flag a character and issue a challenge they must answer in time
+    pub fn issue_challenge(&mut self, character_id: Uuid) -> Challenge {
+        self.flagged.insert(character_id);
+        self.challenges.issue(character_id, Utc::now())
+    }
+
+    /// Answer a pending challenge. Passing clears the flag; failing returns
+    /// the escalated action to apply to the session.
+    pub fn answer_challenge(
+        &mut self,
+        character_id: Uuid,
+        response: &ChallengeResponse,
+    ) -> Result<ChallengeOutcome, ChallengeError> {
+        let outcome = self.challenges.answer(character_id, response, Utc::now())?;
+        if outcome == ChallengeOutcome::Passed {
+            self.flagged.remove(&character_id);
+        }
+        Ok(outcome)
+    }
+
+    /// Escalate challenges that were not answered in time
+    pub fn expire_challenges(&mut self, now: DateTime<Utc>) -> Vec<(Uuid, ViolationAction)> {
+        self.challenges.expire(now)
+    }
+
+    /// Whether a character is flagged pending a challenge
+    pub fn is_flagged(&self, character_id: Uuid) -> bool {
+        self.flagged.contains(&character_id)
+    }
+
+    /// Put a character under close monitoring, e.g. after player reports
+    pub fn flag(&mut self, character_id: Uuid) {
+        let monitor = self.get_monitor(character_id);
+        monitor.flagged = true;
+        monitor.last_update = Utc::now();
+        tracing::info!("Character {} flagged for monitoring", character_id);
+    }
+
+    /// Whether a character is under close monitoring
+    pub fn is_monitored(&self, character_id: Uuid) -> bool {
+        self.monitors.get(&character_id).map(|m| m.flagged).unwrap_or(false)
+    }
+
     /// Get violation history for a character
     pub fn get_violations(&self, character_id: Uuid) -> Vec<&Violation> {
         self.reporter.get_violations(character_id)
@@ -439,7 +555,7 @@ impl AntiCheatSystem {
         let cutoff = Utc::now() - chrono::Duration::hours(1);
         
         self.monitors.retain(|_, monitor| {
-            monitor.last_update > cutoff
+            monitor.flagged || monitor.last_update > cutoff
         });
     }
 }
@@ -50,7 +50,7 @@ impl ViolationReporter {
                 data_points: vec![detection.description.clone()],
                 screenshot_id: None,
                 packet_log_ids: Vec::new(),
-                position_history: Vec::new(),
+                position_history: detection.metrics.path_loop.clone(),
                 context: {
                     let mut ctx = HashMap::new();
                     if let Some(speed) = detection.metrics.speed {
@@ -59,6 +59,9 @@ impl ViolationReporter {
                     if let Some(aps) = detection.metrics.actions_per_second {
                         ctx.insert("actions_per_second".to_string(), aps.to_string());
                     }
+                    if !detection.metrics.path_loop.is_empty() {
+                        ctx.insert("loop_length".to_string(), detection.metrics.path_loop.len().to_string());
+                    }
                     ctx
                 },
             },
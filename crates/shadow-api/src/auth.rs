@@ -20,6 +20,8 @@ pub struct AuthConfig {
     pub refresh_expiry_days: i64,
     pub session_timeout_minutes: i64,
     pub totp_issuer: String,
+    /// Time steps before and after the current one a TOTP code is accepted in
+    pub totp_skew_steps: u8,
     pub hwid_validation_enabled: bool,
     pub max_hwid_per_account: usize,
 }
@@ -32,6 +34,7 @@ impl Default for AuthConfig {
             refresh_expiry_days: 30,
             session_timeout_minutes: 60,
             totp_issuer: "ShadowOT".to_string(),
+            totp_skew_steps: 1,
             hwid_validation_enabled: true,
             max_hwid_per_account: 3,
         }
@@ -223,6 +226,103 @@ pub fn validate_character_name(name: &str) -> Result<(), ApiError> {
     Ok(())
 }
 
+/// Failed recovery attempts allowed before the key is locked
+pub const RECOVERY_MAX_ATTEMPTS: u32 = 5;
+
+/// How long a recovery key stays locked after too many failed attempts
+pub const RECOVERY_LOCKOUT_MINUTES: i64 = 15;
+
+/// Generate a one-time recovery key, e.g. `K7QX2-M4RT9-...`
+pub fn generate_recovery_key() -> String {
+    use rand::Rng;
+    // No 0/O or 1/I so keys can be copied from paper
+    const ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+    let mut rng = rand::thread_rng();
+    (0..4)
+        .map(|_| {
+            (0..5)
+                .map(|_| ALPHABET[rng.gen_range(0..ALPHABET.len())] as char)
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Recovery keys are compared without case, dashes or spaces
+fn normalize_recovery_key(key: &str) -> String {
+    key.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// Stored, hashed recovery key of an account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryKey {
+    pub key_hash: String,
+    pub created_at: chrono::DateTime<Utc>,
+    pub used_at: Option<chrono::DateTime<Utc>>,
+    pub failed_attempts: u32,
+    pub last_failed_at: Option<chrono::DateTime<Utc>>,
+}
+
+impl RecoveryKey {
+    /// Generate a new key. Returns the plain key, shown to the player once,
+    /// and the record to store.
+    pub fn issue() -> Result<(String, Self), ApiError> {
+        let key = generate_recovery_key();
+        let (key_hash, _salt) = hash_password(&normalize_recovery_key(&key))?;
+        Ok((key, Self {
+            key_hash,
+            created_at: Utc::now(),
+            used_at: None,
+            failed_attempts: 0,
+            last_failed_at: None,
+        }))
+    }
+
+    /// Too many failed attempts within the lockout window
+    pub fn is_locked(&self, now: chrono::DateTime<Utc>) -> bool {
+        self.failed_attempts >= RECOVERY_MAX_ATTEMPTS
+            && self
+                .last_failed_at
+                .is_some_and(|at| now - at < Duration::minutes(RECOVERY_LOCKOUT_MINUTES))
+    }
+
+    /// Use the key. It works once; wrong keys count toward the lockout.
+    pub fn redeem(&mut self, key: &str, now: chrono::DateTime<Utc>) -> Result<(), ApiError> {
+        if self.is_locked(now) {
+            return Err(ApiError::RateLimited);
+        }
+        if self.used_at.is_some() {
+            return Err(ApiError::InvalidCredentials);
+        }
+        if !verify_password(&normalize_recovery_key(key), &self.key_hash)? {
+            if self.last_failed_at.is_some_and(|at| now - at >= Duration::minutes(RECOVERY_LOCKOUT_MINUTES)) {
+                self.failed_attempts = 0;
+            }
+            self.failed_attempts += 1;
+            self.last_failed_at = Some(now);
+            return Err(ApiError::InvalidCredentials);
+        }
+        self.used_at = Some(now);
+        Ok(())
+    }
+}
+
+/// Reset a password with a recovery key. Returns the new password hash and
+/// salt; the key is spent even if the caller fails to store them.
+pub fn reset_password_with_recovery_key(
+    record: &mut RecoveryKey,
+    key: &str,
+    new_password: &str,
+    now: chrono::DateTime<Utc>,
+) -> Result<(String, String), ApiError> {
+    validate_password_strength(new_password)?;
+    record.redeem(key, now)?;
+    hash_password(new_password)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -249,6 +349,16 @@ mod tests {
         assert_eq!(decoded.email, "test@example.com");
     }
 
+    #[test]
+    fn test_token_expiry_ignores_time_acceleration() {
+        // An accelerated test realm doesn't shorten token lifetimes
+        let clock = shadow_world::clock::GameClock::new(shadow_world::clock::MAX_TIME_SCALE);
+        let claims = JwtClaims::new(1, &Uuid::new_v4(), "test@example.com", "normal", 24);
+
+        assert_eq!(claims.exp - claims.iat, 24 * 3600);
+        assert!(claims.exp > clock.wall_now().timestamp() + 23 * 3600);
+    }
+
     #[test]
     fn test_password_validation() {
         assert!(validate_password_strength("Abcd1234").is_ok());
@@ -257,6 +367,70 @@ mod tests {
         assert!(validate_password_strength("ALLUPPERCASE1").is_err());
     }
 
+    #[test]
+    fn test_recovery_key_resets_password() {
+        let (key, mut record) = RecoveryKey::issue().unwrap();
+        assert_eq!(key.len(), 23);
+        assert!(!record.key_hash.contains(&normalize_recovery_key(&key)));
+
+        // Case and dashes don't matter
+        let typed = key.to_lowercase().replace('-', " ");
+        let (hash, _salt) = reset_password_with_recovery_key(&mut record, &typed, "NewPassword1", Utc::now()).unwrap();
+        assert!(verify_password("NewPassword1", &hash).unwrap());
+        assert!(record.used_at.is_some());
+    }
+
+    #[test]
+    fn test_recovery_key_is_single_use_and_rate_limited() {
+        let (key, mut record) = RecoveryKey::issue().unwrap();
+        let now = Utc::now();
+        reset_password_with_recovery_key(&mut record, &key, "NewPassword1", now).unwrap();
+        assert!(matches!(
+            reset_password_with_recovery_key(&mut record, &key, "OtherPassword1", now),
+            Err(ApiError::InvalidCredentials)
+        ));
+
+        // Guessing locks the key, even for the right one, until the window passes
+        let (key, mut record) = RecoveryKey::issue().unwrap();
+        for _ in 0..RECOVERY_MAX_ATTEMPTS {
+            assert!(matches!(record.redeem("AAAAA-AAAAA-AAAAA-AAAAA", now), Err(ApiError::InvalidCredentials)));
+        }
+        assert!(matches!(record.redeem(&key, now), Err(ApiError::RateLimited)));
+        assert!(record.redeem(&key, now + Duration::minutes(RECOVERY_LOCKOUT_MINUTES)).is_ok());
+    }
+
+    #[test]
+    fn test_totp_rfc6238_vectors() {
+        // RFC 6238 SHA1 seed "12345678901234567890", last six digits
+        let secret = base32::encode(base32::Alphabet::Rfc4648 { padding: false }, b"12345678901234567890");
+        assert_eq!(verify_totp(&secret, "287082", 59, 1, None), Some(1));
+        assert_eq!(verify_totp(&secret, "081804", 1111111109, 1, None), Some(37037036));
+        assert_eq!(verify_totp(&secret, "005924", 1234567890, 1, None), Some(41152263));
+
+        // One step of drift is tolerated, two are not
+        assert_eq!(verify_totp(&secret, "005924", 1234567890 + 30, 1, None), Some(41152263));
+        assert_eq!(verify_totp(&secret, "005924", 1234567890 + 60, 1, None), None);
+        assert_eq!(verify_totp(&secret, "005924", 1234567890 + 60, 2, None), Some(41152263));
+        assert_eq!(verify_totp(&secret, "123456", 59, 1, None), None);
+        assert_eq!(verify_totp(&secret, "28708a", 59, 1, None), None);
+    }
+
+    #[test]
+    fn test_totp_replay_rejected() {
+        let secret = base32::encode(base32::Alphabet::Rfc4648 { padding: false }, b"12345678901234567890");
+        let step = verify_totp(&secret, "081804", 1111111109, 1, None).unwrap();
+
+        // The same code is still inside its window but was already used
+        assert_eq!(verify_totp(&secret, "081804", 1111111109, 1, Some(step)), None);
+        assert_eq!(verify_totp(&secret, "081804", 1111111109 + 20, 1, Some(step)), None);
+    }
+
+    #[test]
+    fn test_totp_empty_secret_rejected() {
+        let code = format!("{:06}", generate_totp(&[], 1));
+        assert_eq!(verify_totp("", &code, 59, 1, None), None);
+    }
+
     #[test]
     fn test_name_validation() {
         assert!(validate_character_name("John").is_ok());
@@ -519,31 +693,7 @@ impl TotpConfig {
 
     /// Verify a TOTP code
     pub fn verify_code(&self, code: &str) -> bool {
-        // Parse the code
-        let code: u32 = match code.parse() {
-            Ok(c) => c,
-            Err(_) => return false,
-        };
-
-        // Decode the secret
-        let secret_bytes = match base32::decode(base32::Alphabet::Rfc4648 { padding: false }, &self.secret) {
-            Some(b) => b,
-            None => return false,
-        };
-
-        // Get current time step
-        let now = chrono::Utc::now().timestamp() as u64;
-        let time_step = now / 30;
-
-        // Check current and adjacent time steps (for clock drift)
-        for offset in [-1i64, 0, 1] {
-            let step = (time_step as i64 + offset) as u64;
-            if generate_totp(&secret_bytes, step) == code {
-                return true;
-            }
-        }
-
-        false
+        verify_totp(&self.secret, code, chrono::Utc::now().timestamp(), 1, None).is_some()
     }
 
     /// Use a backup code
@@ -557,6 +707,39 @@ impl TotpConfig {
     }
 }
 
+/// TOTP time step length in seconds (RFC 6238 default)
+pub const TOTP_STEP_SECS: i64 = 30;
+
+/// Verify a 6-digit TOTP code against a base32 secret at `unix_time`.
+///
+/// Codes of up to `skew_steps` steps before or after the current one are
+/// accepted to tolerate clock drift. A code from a step at or before
+/// `last_used_step` is rejected, so a code cannot be replayed. Returns the
+/// step the code belongs to, to be stored as the new last used step.
+pub fn verify_totp(
+    secret: &str,
+    code: &str,
+    unix_time: i64,
+    skew_steps: u8,
+    last_used_step: Option<i64>,
+) -> Option<i64> {
+    if code.len() != 6 || !code.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let code: u32 = code.parse().ok()?;
+    let secret = base32::decode(base32::Alphabet::Rfc4648 { padding: false }, secret.trim_end_matches('='))?;
+    // Codes for an empty key are public
+    if secret.is_empty() {
+        return None;
+    }
+
+    let current = unix_time.div_euclid(TOTP_STEP_SECS);
+    let skew = skew_steps as i64;
+    (current - skew..=current + skew)
+        .filter(|&step| step >= 0 && last_used_step.is_none_or(|last| step > last))
+        .find(|&step| generate_totp(&secret, step as u64) == code)
+}
+
 /// Generate a TOTP code for a given time step
 fn generate_totp(secret: &[u8], time_step: u64) -> u32 {
     type HmacSha1 = Hmac<sha1::Sha1>;
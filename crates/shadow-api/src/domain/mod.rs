@@ -6,6 +6,7 @@
 pub mod entities;
 pub mod value_objects;
 pub mod errors;
+pub mod starting_kit;
 
 #[cfg(test)]
 mod tests;
@@ -14,4 +15,5 @@ mod tests;
 pub use entities::*;
 pub use value_objects::*;
 pub use errors::DomainError;
+pub use starting_kit::{KitItem, StartingKit, StartingKitConfig, TUTORIAL_STORAGE_KEY};
 pub use errors::DomainError;
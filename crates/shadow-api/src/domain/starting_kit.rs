@@ -0,0 +1,166 @@
+//! Starting kits - what a new character begins with
+//!
+//! A kit grants items, gold, spells and storage values (quest and tutorial
+//! state). Kits can be limited to a realm, a vocation, or both; a new
+//! character gets the most specific kit that matches, with realm matches
+//! taking precedence over vocation matches. Tutorial flags are set for
+//! every new character regardless of the kit.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::Vocation;
+
+/// Storage key marking the tutorial as started
+pub const TUTORIAL_STORAGE_KEY: i32 = 50_000;
+
+/// An item granted by a kit
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KitItem {
+    pub item_id: i32,
+    pub count: i32,
+    /// Equipment slot (1-10), or 0 for the backpack
+    #[serde(default)]
+    pub slot: i32,
+}
+
+impl KitItem {
+    pub fn equipped(item_id: i32, slot: i32) -> Self {
+        Self { item_id, count: 1, slot }
+    }
+
+    pub fn carried(item_id: i32, count: i32) -> Self {
+        Self { item_id, count, slot: 0 }
+    }
+}
+
+/// Items, gold, spells and storage values for new characters
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StartingKit {
+    /// Only for characters created on this realm
+    pub realm_id: Option<i32>,
+    /// Only for characters of this vocation
+    pub vocation: Option<Vocation>,
+    pub items: Vec<KitItem>,
+    pub gold: i64,
+    pub spells: Vec<String>,
+    /// Storage key -> value, e.g. quest progress
+    pub storage: BTreeMap<i32, i64>,
+}
+
+impl StartingKit {
+    /// Match specificity for a character, `None` when the kit does not apply
+    fn specificity(&self, realm_id: i32, vocation: Vocation) -> Option<u8> {
+        let realm = match self.realm_id {
+            Some(id) if id != realm_id => return None,
+            Some(_) => 2,
+            None => 0,
+        };
+        let vocation = match self.vocation {
+            Some(v) if v != vocation => return None,
+            Some(_) => 1,
+            None => 0,
+        };
+        Some(realm + vocation)
+    }
+}
+
+/// Starting kit configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StartingKitConfig {
+    pub kits: Vec<StartingKit>,
+    /// Storage values set for every new character
+    pub tutorial_flags: BTreeMap<i32, i64>,
+}
+
+impl Default for StartingKitConfig {
+    fn default() -> Self {
+        // Steel helmet, plate armor, plate legs, leather boots
+        let basics = vec![
+            KitItem::equipped(2457, 1),
+            KitItem::equipped(2463, 4),
+            KitItem::equipped(2647, 7),
+            KitItem::equipped(2643, 8),
+        ];
+        let kit = |vocation: Vocation, weapon: KitItem, spells: &[&str]| {
+            let mut items = basics.clone();
+            items.push(weapon);
+            items.push(KitItem::carried(2120, 1)); // rope
+            items.push(KitItem::carried(7618, 2)); // health potions
+            StartingKit {
+                vocation: Some(vocation),
+                items,
+                gold: 100,
+                spells: spells.iter().map(|s| s.to_string()).collect(),
+                ..Default::default()
+            }
+        };
+        Self {
+            kits: vec![
+                kit(Vocation::Knight, KitItem::equipped(2383, 6), &["Light Healing"]),
+                kit(Vocation::Paladin, KitItem::equipped(2389, 6), &["Light Healing", "Conjure Arrow"]),
+                kit(Vocation::Sorcerer, KitItem::equipped(2190, 6), &["Light Healing", "Energy Strike"]),
+                kit(Vocation::Druid, KitItem::equipped(2182, 6), &["Light Healing", "Terra Strike"]),
+                StartingKit { items: basics.clone(), gold: 100, ..Default::default() },
+            ],
+            tutorial_flags: BTreeMap::from([(TUTORIAL_STORAGE_KEY, 1)]),
+        }
+    }
+}
+
+impl StartingKitConfig {
+    /// Everything a new character on `realm_id` with `vocation` receives
+    pub fn kit_for(&self, realm_id: i32, vocation: Vocation) -> StartingKit {
+        let mut kit = self
+            .kits
+            .iter()
+            .filter_map(|kit| kit.specificity(realm_id, vocation).map(|score| (score, kit)))
+            .max_by_key(|(score, _)| *score)
+            .map(|(_, kit)| kit.clone())
+            .unwrap_or_default();
+        for (&key, &value) in &self.tutorial_flags {
+            kit.storage.entry(key).or_insert(value);
+        }
+        kit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_knight_receives_knight_kit_and_tutorial_flag() {
+        let config = StartingKitConfig::default();
+        let kit = config.kit_for(1, Vocation::Knight);
+        assert_eq!(kit.vocation, Some(Vocation::Knight));
+        assert!(kit.items.contains(&KitItem::equipped(2383, 6)));
+        assert_eq!(kit.spells, vec!["Light Healing".to_string()]);
+        assert_eq!(kit.gold, 100);
+        assert_eq!(kit.storage.get(&TUTORIAL_STORAGE_KEY), Some(&1));
+
+        // Vocations without their own kit get the generic one
+        let none = config.kit_for(1, Vocation::None);
+        assert!(none.vocation.is_none() && none.spells.is_empty());
+        assert_eq!(none.storage.get(&TUTORIAL_STORAGE_KEY), Some(&1));
+    }
+
+    #[test]
+    fn test_realm_kit_takes_precedence() {
+        let mut config = StartingKitConfig::default();
+        config.kits.push(StartingKit {
+            realm_id: Some(7),
+            gold: 10_000,
+            storage: BTreeMap::from([(TUTORIAL_STORAGE_KEY, 0)]),
+            ..Default::default()
+        });
+
+        // Tutorial skipped on the realm, which overrides the vocation kit
+        let kit = config.kit_for(7, Vocation::Knight);
+        assert_eq!(kit.gold, 10_000);
+        assert_eq!(kit.storage.get(&TUTORIAL_STORAGE_KEY), Some(&0));
+        assert_eq!(config.kit_for(1, Vocation::Knight).gold, 100);
+    }
+}
@@ -51,6 +51,8 @@ pub type ApiResult<T> = std::result::Result<T, ApiError>;
         routes::auth::register,
         routes::auth::logout,
         routes::auth::refresh_token,
+        routes::auth::reset_password,
+        routes::auth::generate_recovery_key,
         routes::auth::enable_2fa,
         routes::auth::verify_2fa,
         routes::auth::disable_2fa,
@@ -64,12 +66,20 @@ pub type ApiResult<T> = std::result::Result<T, ApiError>;
         routes::characters::get_character,
         routes::characters::create_character,
         routes::characters::delete_character,
+        routes::characters::get_character_sheet,
+        routes::characters::get_skill_progress,
+        routes::characters::get_character_deaths,
+        routes::characters::get_character_death_stats,
+        routes::characters::get_death_stats,
+        routes::characters::get_character_encounters,
+        routes::characters::update_combat_log_settings,
         routes::realms::list_realms,
         routes::realms::get_realm,
         routes::highscores::get_highscores,
         routes::guilds::list_guilds,
         routes::guilds::get_guild,
         routes::market::list_offers,
+        routes::market::get_character_net_worth,
         routes::news::list_news,
         routes::support::list_tickets,
         routes::support::get_ticket,
@@ -86,6 +96,7 @@ pub type ApiResult<T> = std::result::Result<T, ApiError>;
         routes::auction::create_character_auction,
         routes::auction::create_item_auction,
         routes::auction::cancel_auction,
+        routes::auction::auction_live,
         routes::kill_statistics::get_statistics,
         routes::kill_statistics::get_top_killers,
         routes::kill_statistics::get_recent_deaths,
@@ -100,6 +111,7 @@ pub type ApiResult<T> = std::result::Result<T, ApiError>;
         routes::creatures::get_creature_by_name,
         routes::creatures::get_bestiary_progress,
         routes::creatures::get_bestiary_entry,
+        routes::creatures::get_charm_recommendations,
         routes::achievements::list_achievements,
         routes::achievements::get_player_achievements,
         routes::achievements::get_leaderboard,
@@ -109,8 +121,10 @@ pub type ApiResult<T> = std::result::Result<T, ApiError>;
         routes::world_quests::contribute_to_quest,
         routes::inventory::get_inventory_items,
         routes::inventory::get_inventory_item,
+        routes::inventory::get_imbuement_slots,
         routes::inventory::transfer_item,
         routes::inventory::list_on_market,
+        routes::inventory::get_storage_capacity,
         routes::spells::list_spells,
         routes::spells::get_spell,
         routes::spells::get_spell_by_words,
@@ -130,6 +144,7 @@ pub type ApiResult<T> = std::result::Result<T, ApiError>;
         routes::nft::list_nft,
         routes::nft::buy_nft,
         routes::nft::cancel_listing,
+        routes::nft::get_bridge_quote,
         // Premium
         routes::premium::get_premium_status,
         routes::premium::purchase_premium,
@@ -144,12 +159,17 @@ pub type ApiResult<T> = std::result::Result<T, ApiError>;
         routes::notifications::mark_all_read,
         routes::notifications::delete_notification,
         routes::notifications::get_unread_count,
+        routes::watchlist::get_watchlist,
+        routes::watchlist::create_watch,
+        routes::watchlist::delete_watch,
     ),
     components(
         schemas(
             routes::auth::LoginRequest,
             routes::auth::LoginResponse,
             routes::auth::RegisterRequest,
+            routes::auth::ResetPasswordRequest,
+            routes::auth::RecoveryKeyResponse,
             routes::auth::Enable2FAResponse,
             routes::auth::Verify2FARequest,
             routes::auth::WalletNonceResponse,
@@ -158,10 +178,28 @@ pub type ApiResult<T> = std::result::Result<T, ApiError>;
             routes::accounts::AccountResponse,
             routes::characters::CharacterResponse,
             routes::characters::CreateCharacterRequest,
+            routes::characters::CharacterSheetResponse,
+            routes::characters::SheetSkillsResponse,
+            routes::characters::SheetEquipmentItem,
+            routes::characters::SheetCombatStats,
+            routes::characters::SkillProgressResponse,
+            routes::characters::SkillProgressEntry,
+            routes::characters::DeathEntry,
+            routes::characters::PaginatedDeaths,
+            routes::characters::DeathCauseCount,
+            routes::characters::DeathKillerCount,
+            routes::characters::DeathStatsResponse,
+            routes::characters::EncounterSource,
+            routes::characters::EncounterEntry,
+            routes::characters::EncounterLogResponse,
+            routes::characters::CombatLogSettingsRequest,
             routes::realms::RealmResponse,
             routes::highscores::HighscoreEntry,
             routes::guilds::GuildResponse,
             routes::market::MarketOffer,
+            routes::market::NetWorthResponse,
+            routes::market::NetWorthCategory,
+            routes::market::CharacterTrade,
             routes::news::NewsArticle,
             routes::support::SupportTicket,
             routes::support::TicketMessage,
@@ -181,6 +219,7 @@ pub type ApiResult<T> = std::result::Result<T, ApiError>;
             routes::auction::CharacterSkills,
             routes::auction::BidRequest,
             routes::auction::BidResponse,
+            routes::auction::AuctionUpdate,
             routes::auction::CreateCharacterAuctionRequest,
             routes::auction::CreateItemAuctionRequest,
             routes::auction::PaginatedCharacterAuctions,
@@ -197,7 +236,9 @@ pub type ApiResult<T> = std::result::Result<T, ApiError>;
             routes::creatures::CreatureDifficulty,
             routes::creatures::LootItem,
             routes::creatures::BestiaryEntry,
+            routes::creatures::CreatureElement,
             routes::creatures::PaginatedCreatures,
+            routes::creatures::CharmRecommendationEntry,
             routes::achievements::Achievement,
             routes::achievements::AchievementCategory,
             routes::achievements::AchievementRarity,
@@ -216,10 +257,16 @@ pub type ApiResult<T> = std::result::Result<T, ApiError>;
             routes::inventory::InventoryItem,
             routes::inventory::ItemAttributes,
             routes::inventory::Imbuement,
+            routes::inventory::ImbuementSlot,
+            routes::inventory::ImbuementMaterial,
+            routes::inventory::ImbuementTierOption,
+            routes::inventory::EligibleImbuement,
+            routes::inventory::ImbuementSlotsResponse,
             routes::inventory::TransferRequest,
             routes::inventory::TransferResponse,
             routes::inventory::ListOnMarketRequest,
             routes::inventory::ListOnMarketResponse,
+            routes::inventory::StorageCapacityResponse,
             routes::spells::Spell,
             routes::spells::Rune,
             routes::spells::SpellElement,
@@ -244,6 +291,7 @@ pub type ApiResult<T> = std::result::Result<T, ApiError>;
             routes::nft::ListNftRequest,
             routes::nft::BuyNftRequest,
             routes::nft::PaginatedNfts,
+            routes::nft::BridgeQuoteResponse,
             // Premium schemas
             routes::premium::PremiumStatus,
             routes::premium::PremiumPlan,
@@ -259,6 +307,8 @@ pub type ApiResult<T> = std::result::Result<T, ApiError>;
             routes::notifications::NotificationType,
             routes::notifications::PaginatedNotifications,
             routes::notifications::MarkReadResponse,
+            routes::watchlist::WatchEntry,
+            routes::watchlist::CreateWatchRequest,
             // Shared response schemas
             response::MessageResponse,
             response::SuccessResponse,
@@ -316,6 +366,7 @@ pub fn create_router(state: Arc<AppState>) -> Router {
         .route("/auth/verify-email", post(routes::auth::verify_email))
         .route("/auth/forgot-password", post(routes::auth::forgot_password))
         .route("/auth/reset-password", post(routes::auth::reset_password))
+        .route("/auth/recovery-key", post(routes::auth::generate_recovery_key))
         // 2FA
         .route("/auth/2fa/enable", post(routes::auth::enable_2fa))
         .route("/auth/2fa/verify", post(routes::auth::verify_2fa))
@@ -338,6 +389,14 @@ pub fn create_router(state: Arc<AppState>) -> Router {
         .route("/characters/:id", get(routes::characters::get_character))
         .route("/characters/:id", delete(routes::characters::delete_character))
         .route("/characters/:id/online", get(routes::characters::get_online_status))
+        .route("/characters/:id/sheet", get(routes::characters::get_character_sheet))
+        .route("/characters/:id/skill-progress", get(routes::characters::get_skill_progress))
+        .route("/characters/:id/deaths", get(routes::characters::get_character_deaths))
+        .route("/characters/:id/deaths/stats", get(routes::characters::get_character_death_stats))
+        .route("/characters/:id/encounters", get(routes::characters::get_character_encounters))
+        .route("/characters/:id/encounters/settings", put(routes::characters::update_combat_log_settings))
+        .route("/characters/:id/networth", get(routes::market::get_character_net_worth))
+        .route("/deaths/stats", get(routes::characters::get_death_stats))
         // Realms
         .route("/realms", get(routes::realms::list_realms))
         .route("/realms/:id", get(routes::realms::get_realm))
@@ -383,6 +442,7 @@ pub fn create_router(state: Arc<AppState>) -> Router {
         .route("/auctions/items/:id", get(routes::auction::get_item_auction))
         .route("/auctions/items/:id/bid", post(routes::auction::bid_on_item_auction))
         .route("/auctions/:auction_type/:id", delete(routes::auction::cancel_auction))
+        .route("/auctions/:auction_type/:id/live", get(routes::auction::auction_live))
         // Kill statistics
         .route("/kill-statistics", get(routes::kill_statistics::get_statistics))
         .route("/kill-statistics/top-killers", get(routes::kill_statistics::get_top_killers))
@@ -399,6 +459,7 @@ pub fn create_router(state: Arc<AppState>) -> Router {
         .route("/creatures/:id", get(routes::creatures::get_creature))
         .route("/creatures/name/:name", get(routes::creatures::get_creature_by_name))
         .route("/characters/:character_id/bestiary", get(routes::creatures::get_bestiary_progress))
+        .route("/characters/:character_id/bestiary/recommendations", get(routes::creatures::get_charm_recommendations))
         .route("/characters/:character_id/bestiary/:creature_id", get(routes::creatures::get_bestiary_entry))
         // Achievements
         .route("/achievements", get(routes::achievements::list_achievements))
@@ -411,7 +472,9 @@ pub fn create_router(state: Arc<AppState>) -> Router {
         .route("/world-quests/:id/contribute", post(routes::world_quests::contribute_to_quest))
         // Inventory
         .route("/inventory", get(routes::inventory::get_inventory_items))
+        .route("/inventory/storage", get(routes::inventory::get_storage_capacity))
         .route("/inventory/:id", get(routes::inventory::get_inventory_item))
+        .route("/inventory/:id/imbuements", get(routes::inventory::get_imbuement_slots))
         .route("/inventory/:id/transfer", post(routes::inventory::transfer_item))
         .route("/inventory/:id/list-on-market", post(routes::inventory::list_on_market))
         // Spells
@@ -431,6 +494,7 @@ pub fn create_router(state: Arc<AppState>) -> Router {
         .route("/nft/mint", post(routes::nft::mint_nft))
         .route("/nft/buy", post(routes::nft::buy_nft))
         .route("/nft/marketplace", get(routes::nft::get_marketplace))
+        .route("/nft/bridge/quote", get(routes::nft::get_bridge_quote))
         .route("/nft/:chain/:token_id", get(routes::nft::get_nft))
         .route("/nft/:id/transfer", post(routes::nft::transfer_nft))
         .route("/nft/:id/list", post(routes::nft::list_nft))
@@ -449,6 +513,9 @@ pub fn create_router(state: Arc<AppState>) -> Router {
         .route("/users/me/notifications/read-all", post(routes::notifications::mark_all_read))
         .route("/users/me/notifications/:id/read", axum::routing::patch(routes::notifications::mark_notification_read))
         .route("/users/me/notifications/:id", delete(routes::notifications::delete_notification))
+        .route("/users/me/watchlist", get(routes::watchlist::get_watchlist))
+        .route("/users/me/watchlist", post(routes::watchlist::create_watch))
+        .route("/users/me/watchlist/:id", delete(routes::watchlist::delete_watch))
         // Admin routes (protected)
         .route("/admin/stats", get(routes::admin::get_stats))
         .route("/admin/players/online", get(routes::admin::get_online_players))
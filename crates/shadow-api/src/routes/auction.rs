@@ -2,21 +2,27 @@
 
 use crate::auth::JwtClaims;
 use crate::response::SuccessResponse;
+use crate::routes::watchlist::notify_watchers;
 use crate::state::AppState;
 use crate::ApiResult;
 use axum::{
+    extract::ws::{Message, WebSocket, WebSocketUpgrade},
     extract::{Path, Query, State},
+    response::Response,
     Extension, Json,
 };
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use shadow_core::watchlist::{CharacterListing, ItemListing};
 use sqlx::FromRow;
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
 /// Auction type
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, sqlx::Type, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, sqlx::Type, PartialEq, Eq)]
 #[sqlx(type_name = "auction_type", rename_all = "lowercase")]
 pub enum AuctionType {
     Character,
@@ -84,6 +90,9 @@ struct CharacterAuctionRow {
     bid_count: i32,
     ends_at: DateTime<Utc>,
     status: AuctionStatus,
+    /// Seconds the end was pushed back by late bids
+    #[sqlx(default)]
+    extended_secs: i32,
 }
 
 /// Character skills
@@ -132,6 +141,8 @@ struct ItemAuctionRow {
     ends_at: DateTime<Utc>,
     status: AuctionStatus,
     seller_name: String,
+    #[sqlx(default)]
+    extended_secs: i32,
 }
 
 /// Auction query parameters
@@ -152,6 +163,9 @@ pub struct AuctionQuery {
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct BidRequest {
     pub amount: i64,
+    /// Ceiling up to which the bid is raised automatically when outbid
+    #[serde(default)]
+    pub max_proxy_bid: Option<i64>,
 }
 
 /// Create character auction request
@@ -199,6 +213,349 @@ pub struct BidResponse {
     pub success: bool,
     pub new_bid: i64,
     pub bid_count: i32,
+    /// Whether the bidder holds the highest bid after proxies were applied
+    pub leading: bool,
+    /// End of the auction, later than before if the bid was extended
+    pub ends_at: DateTime<Utc>,
+}
+
+/// Pushed to live subscribers whenever a bid changes an auction
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AuctionUpdate {
+    pub auction_type: AuctionType,
+    pub auction_id: Uuid,
+    pub current_bid: i64,
+    pub bid_count: i32,
+    pub ends_at: DateTime<Utc>,
+}
+
+/// Live update channels, one per auction with connected subscribers
+#[derive(Debug, Default)]
+pub struct AuctionHub {
+    channels: Mutex<HashMap<Uuid, broadcast::Sender<AuctionUpdate>>>,
+}
+
+impl AuctionHub {
+    /// Updates buffered per subscriber before it starts missing them
+    const CAPACITY: usize = 16;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Receive the updates of an auction
+    pub fn subscribe(&self, auction_id: Uuid) -> broadcast::Receiver<AuctionUpdate> {
+        let mut channels = self.channels.lock().unwrap();
+        channels
+            .entry(auction_id)
+            .or_insert_with(|| broadcast::channel(Self::CAPACITY).0)
+            .subscribe()
+    }
+
+    /// Send an update to the auction's subscribers, dropping the channel
+    /// once nobody is listening
+    pub fn publish(&self, update: AuctionUpdate) {
+        let mut channels = self.channels.lock().unwrap();
+        if let Some(sender) = channels.get(&update.auction_id) {
+            let auction_id = update.auction_id;
+            if sender.send(update).is_err() {
+                channels.remove(&auction_id);
+            }
+        }
+    }
+
+    /// Drop the auction's channel if its last subscriber has gone
+    pub fn prune(&self, auction_id: Uuid) {
+        let mut channels = self.channels.lock().unwrap();
+        if channels.get(&auction_id).is_some_and(|sender| sender.receiver_count() == 0) {
+            channels.remove(&auction_id);
+        }
+    }
+
+    /// Auctions with an open channel
+    pub fn channel_count(&self) -> usize {
+        self.channels.lock().unwrap().len()
+    }
+}
+
+/// Anti-snipe rule: a bid in the final seconds of an auction pushes its
+/// end back, so there is always time to answer a late bid
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AntiSnipeConfig {
+    /// Bids this close to the end extend it by the same amount; 0 disables
+    pub window_secs: i32,
+    /// Total extension an auction can receive
+    pub max_extension_secs: i32,
+}
+
+impl Default for AntiSnipeConfig {
+    fn default() -> Self {
+        Self {
+            window_secs: 120,
+            max_extension_secs: 1800,
+        }
+    }
+}
+
+impl AntiSnipeConfig {
+    /// New end time and total extension for a bid placed at `now`, or
+    /// `None` when the bid is outside the window or the cap is reached
+    pub fn extend(&self, ends_at: DateTime<Utc>, now: DateTime<Utc>, extended_secs: i32) -> Option<(DateTime<Utc>, i32)> {
+        let remaining = ends_at - now;
+        if self.window_secs <= 0
+            || remaining < chrono::Duration::zero()
+            || remaining >= chrono::Duration::seconds(self.window_secs as i64)
+        {
+            return None;
+        }
+        let extension = self.window_secs.min(self.max_extension_secs - extended_secs);
+        if extension <= 0 {
+            return None;
+        }
+        Some((ends_at + chrono::Duration::seconds(extension as i64), extended_secs + extension))
+    }
+}
+
+/// A bidder's standing ceiling in an auction
+#[derive(Debug, Clone, FromRow)]
+pub struct ProxyBid {
+    pub bidder_id: i32,
+    pub max_amount: i64,
+    pub placed_at: DateTime<Utc>,
+}
+
+/// Leading bidder and price after resolving proxy bids
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProxyOutcome {
+    pub winner: i32,
+    pub price: i64,
+}
+
+/// Resolve proxy bids. The highest ceiling leads, the earliest one when
+/// ceilings are equal, and pays one increment over the runner-up's ceiling
+/// without exceeding its own. A lone bidder pays the opening price.
+pub fn resolve_proxy_bids(bids: &[ProxyBid], min_bid: i64, bid_increment: i64) -> Option<ProxyOutcome> {
+    let mut ranked: Vec<&ProxyBid> = bids.iter().filter(|b| b.max_amount >= min_bid).collect();
+    ranked.sort_by(|a, b| {
+        b.max_amount
+            .cmp(&a.max_amount)
+            .then(a.placed_at.cmp(&b.placed_at))
+            .then(a.bidder_id.cmp(&b.bidder_id))
+    });
+    let winner = ranked.first()?;
+    let price = match ranked.get(1) {
+        Some(runner_up) => (runner_up.max_amount + bid_increment).min(winner.max_amount),
+        None => min_bid,
+    };
+    Some(ProxyOutcome {
+        winner: winner.bidder_id,
+        price: price.max(min_bid),
+    })
+}
+
+/// Price of the standing bid after resolving proxies. A leader never drops
+/// below what it already stands at, and a plain bid without a proxy maximum
+/// that takes the lead stands at its full amount, as in an open auction.
+fn standing_price(outcome: ProxyOutcome, leader_id: Option<i32>, current_bid: i64, bidder_id: i32, req: &BidRequest) -> i64 {
+    let mut price = outcome.price;
+    if leader_id == Some(outcome.winner) {
+        price = price.max(current_bid);
+    }
+    if outcome.winner == bidder_id && req.max_proxy_bid.is_none() {
+        price = price.max(req.amount);
+    }
+    price
+}
+
+/// Pricing state of the auction being bid on
+struct BidTerms {
+    ends_at: DateTime<Utc>,
+    min_bid: i64,
+    bid_increment: i64,
+    current_bid: i64,
+    bid_count: i32,
+}
+
+/// Place a bid inside the auction's transaction: store the bidder's
+/// ceiling, re-resolve every proxy, move coins between the displaced and
+/// the leading bidder, and record the resulting bids. The caller writes the
+/// returned price and bid count back to the auction row.
+async fn place_bid(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    auction_type: AuctionType,
+    auction_id: Uuid,
+    terms: BidTerms,
+    bidder_id: i32,
+    req: &BidRequest,
+) -> ApiResult<BidResponse> {
+    let min_required = if terms.bid_count == 0 {
+        terms.min_bid
+    } else {
+        terms.current_bid + terms.bid_increment
+    };
+
+    if req.amount < min_required {
+        return Err(crate::error::ApiError::BadRequest(
+            format!("Bid must be at least {} coins", min_required)
+        ));
+    }
+
+    let ceiling = req.max_proxy_bid.unwrap_or(req.amount);
+    if ceiling < req.amount {
+        return Err(crate::error::ApiError::BadRequest(
+            "Proxy maximum cannot be lower than the bid".to_string()
+        ));
+    }
+
+    // The standing bid, whose coins are already held
+    let leader: Option<(Uuid, i32)> = sqlx::query_as(
+        "SELECT id, bidder_id FROM auction_bids
+         WHERE auction_id = $1 AND auction_type = $2 AND outbid_by IS NULL
+         ORDER BY amount DESC, created_at DESC LIMIT 1"
+    )
+    .bind(auction_id)
+    .bind(auction_type)
+    .fetch_optional(&mut **tx)
+    .await?;
+    let leader_id = leader.map(|(_, bidder)| bidder);
+    let held = if leader_id == Some(bidder_id) { terms.current_bid } else { 0 };
+
+    // Check bidder can cover the whole ceiling
+    let balance: (i64,) = sqlx::query_as(
+        "SELECT coins FROM accounts WHERE id = $1"
+    )
+    .bind(bidder_id)
+    .fetch_one(&mut **tx)
+    .await?;
+
+    if balance.0 + held < ceiling {
+        return Err(crate::error::ApiError::BadRequest("Insufficient coins".to_string()));
+    }
+
+    // A standing open bid counts as a ceiling at its amount
+    if let Some((bid_id, _)) = leader {
+        sqlx::query(
+            "INSERT INTO auction_proxy_bids (auction_id, auction_type, bidder_id, max_amount, created_at)
+             SELECT auction_id, auction_type, bidder_id, amount, created_at FROM auction_bids WHERE id = $1
+             ON CONFLICT (auction_id, auction_type, bidder_id) DO NOTHING"
+        )
+        .bind(bid_id)
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    // Ceilings only go up; raising one counts as placing it now
+    sqlx::query(
+        "INSERT INTO auction_proxy_bids (auction_id, auction_type, bidder_id, max_amount, created_at)
+         VALUES ($1, $2, $3, $4, $5)
+         ON CONFLICT (auction_id, auction_type, bidder_id) DO UPDATE
+         SET max_amount = EXCLUDED.max_amount, created_at = EXCLUDED.created_at
+         WHERE auction_proxy_bids.max_amount < EXCLUDED.max_amount"
+    )
+    .bind(auction_id)
+    .bind(auction_type)
+    .bind(bidder_id)
+    .bind(ceiling)
+    .bind(Utc::now())
+    .execute(&mut **tx)
+    .await?;
+
+    // Nobody is raised past what they can pay
+    let proxies = sqlx::query_as::<_, ProxyBid>(
+        "SELECT p.bidder_id,
+                LEAST(p.max_amount, a.coins + CASE WHEN p.bidder_id = $3 THEN $4 ELSE 0 END) AS max_amount,
+                p.created_at AS placed_at
+         FROM auction_proxy_bids p
+         JOIN accounts a ON a.id = p.bidder_id
+         WHERE p.auction_id = $1 AND p.auction_type = $2"
+    )
+    .bind(auction_id)
+    .bind(auction_type)
+    .bind(leader_id)
+    .bind(terms.current_bid)
+    .fetch_all(&mut **tx)
+    .await?;
+
+    let outcome = resolve_proxy_bids(&proxies, terms.min_bid, terms.bid_increment)
+        .ok_or(crate::error::ApiError::BadRequest("Bid is below the minimum".to_string()))?;
+    let price = standing_price(outcome, leader_id, terms.current_bid, bidder_id, req);
+
+    // Refund the displaced bidder, or hold only the raise from one who keeps the lead
+    if leader_id == Some(outcome.winner) {
+        sqlx::query("UPDATE accounts SET coins = coins - $1 WHERE id = $2")
+            .bind(price - terms.current_bid)
+            .bind(outcome.winner)
+            .execute(&mut **tx)
+            .await?;
+    } else {
+        if let Some(displaced) = leader_id {
+            sqlx::query("UPDATE accounts SET coins = coins + $1 WHERE id = $2")
+                .bind(terms.current_bid)
+                .bind(displaced)
+                .execute(&mut **tx)
+                .await?;
+        }
+        sqlx::query("UPDATE accounts SET coins = coins - $1 WHERE id = $2")
+            .bind(price)
+            .bind(outcome.winner)
+            .execute(&mut **tx)
+            .await?;
+    }
+
+    // Record the losing attempt and the new standing bid
+    let mut new_bids = 0;
+    let now = Utc::now();
+    if outcome.winner != bidder_id {
+        sqlx::query(
+            "INSERT INTO auction_bids (id, auction_id, auction_type, bidder_id, amount, created_at)
+             VALUES ($1, $2, $3, $4, $5, $6)"
+        )
+        .bind(Uuid::new_v4())
+        .bind(auction_id)
+        .bind(auction_type)
+        .bind(bidder_id)
+        .bind(ceiling.min(price))
+        .bind(now)
+        .execute(&mut **tx)
+        .await?;
+        new_bids += 1;
+    }
+
+    if leader_id != Some(outcome.winner) || price != terms.current_bid {
+        let standing_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO auction_bids (id, auction_id, auction_type, bidder_id, amount, created_at)
+             VALUES ($1, $2, $3, $4, $5, $6)"
+        )
+        .bind(standing_id)
+        .bind(auction_id)
+        .bind(auction_type)
+        .bind(outcome.winner)
+        .bind(price)
+        .bind(now)
+        .execute(&mut **tx)
+        .await?;
+        new_bids += 1;
+
+        sqlx::query(
+            "UPDATE auction_bids SET outbid_by = $1
+             WHERE auction_id = $2 AND auction_type = $3 AND outbid_by IS NULL AND id <> $1"
+        )
+        .bind(standing_id)
+        .bind(auction_id)
+        .bind(auction_type)
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    Ok(BidResponse {
+        success: true,
+        new_bid: price,
+        bid_count: terms.bid_count + new_bids,
+        leading: outcome.winner == bidder_id,
+        ends_at: terms.ends_at,
+    })
 }
 
 /// List character auctions
@@ -521,7 +878,7 @@ pub async fn bid_on_character_auction(
         "SELECT id, character_name, level, vocation, 
                 skill_fist, skill_club, skill_sword, skill_axe, 
                 skill_distance, skill_shielding, skill_fishing, skill_magic,
-                current_bid, min_bid, bid_increment, bid_count, ends_at, status
+                current_bid, min_bid, bid_increment, bid_count, ends_at, status, extended_secs
          FROM character_auctions
          WHERE id = $1
          FOR UPDATE"
@@ -539,82 +896,52 @@ pub async fn bid_on_character_auction(
         return Err(crate::error::ApiError::BadRequest("Auction has ended".to_string()));
     }
 
-    let min_required = if auction.bid_count == 0 {
-        auction.min_bid
-    } else {
-        auction.current_bid + auction.bid_increment
-    };
-
-    if req.amount < min_required {
-        return Err(crate::error::ApiError::BadRequest(
-            format!("Bid must be at least {} coins", min_required)
-        ));
-    }
-
-    // Check bidder has enough balance
-    let balance: (i64,) = sqlx::query_as(
-        "SELECT coins FROM accounts WHERE id = $1"
+    let mut response = place_bid(
+        &mut tx,
+        AuctionType::Character,
+        id,
+        BidTerms {
+            ends_at: auction.ends_at,
+            min_bid: auction.min_bid,
+            bid_increment: auction.bid_increment,
+            current_bid: auction.current_bid,
+            bid_count: auction.bid_count,
+        },
+        claims.account_id,
+        &req,
     )
-    .bind(&claims.sub)
-    .fetch_one(&mut *tx)
     .await?;
 
-    if balance.0 < req.amount {
-        return Err(crate::error::ApiError::BadRequest("Insufficient coins".to_string()));
-    }
-
-    // Refund previous bidder if any
-    if auction.bid_count > 0 {
-        sqlx::query(
-            "UPDATE accounts SET coins = coins + $1
-             WHERE id = (SELECT bidder_id FROM auction_bids 
-                        WHERE auction_id = $2 AND auction_type = 'character'
-                        ORDER BY amount DESC LIMIT 1)"
-        )
-        .bind(auction.current_bid)
-        .bind(id)
-        .execute(&mut *tx)
-        .await?;
+    // A late bid pushes the end back; the row lock keeps concurrent bids from extending twice
+    let mut extended_secs = auction.extended_secs;
+    if let Some((ends_at, total)) = state.config.anti_snipe.extend(auction.ends_at, Utc::now(), extended_secs) {
+        response.ends_at = ends_at;
+        extended_secs = total;
     }
 
-    // Deduct from bidder
-    sqlx::query("UPDATE accounts SET coins = coins - $1 WHERE id = $2")
-        .bind(req.amount)
-        .bind(&claims.sub)
-        .execute(&mut *tx)
-        .await?;
-
-    // Record bid
-    sqlx::query(
-        "INSERT INTO auction_bids (id, auction_id, auction_type, bidder_id, amount, created_at)
-         VALUES ($1, $2, 'character', $3, $4, $5)"
-    )
-    .bind(Uuid::new_v4())
-    .bind(id)
-    .bind(&claims.sub)
-    .bind(req.amount)
-    .bind(Utc::now())
-    .execute(&mut *tx)
-    .await?;
-
     // Update auction
-    let new_count = auction.bid_count + 1;
     sqlx::query(
-        "UPDATE character_auctions SET current_bid = $1, bid_count = $2 WHERE id = $3"
+        "UPDATE character_auctions SET current_bid = $1, bid_count = $2, ends_at = $3, extended_secs = $4 WHERE id = $5"
     )
-    .bind(req.amount)
-    .bind(new_count)
+    .bind(response.new_bid)
+    .bind(response.bid_count)
+    .bind(response.ends_at)
+    .bind(extended_secs)
     .bind(id)
     .execute(&mut *tx)
     .await?;
 
     tx.commit().await?;
 
-    Ok(Json(BidResponse {
-        success: true,
-        new_bid: req.amount,
-        bid_count: new_count,
-    }))
+    state.auction_hub.publish(AuctionUpdate {
+        auction_type: AuctionType::Character,
+        auction_id: id,
+        current_bid: response.new_bid,
+        bid_count: response.bid_count,
+        ends_at: response.ends_at,
+    });
+
+    Ok(Json(response))
 }
 
 /// Bid on an item auction
@@ -641,7 +968,7 @@ pub async fn bid_on_item_auction(
 
     let auction = sqlx::query_as::<_, ItemAuctionRow>(
         "SELECT id, item_id, item_name, item_count, is_nft, nft_token_id,
-                current_bid, min_bid, bid_increment, bid_count, ends_at, status, seller_name
+                current_bid, min_bid, bid_increment, bid_count, ends_at, status, seller_name, extended_secs
          FROM item_auctions
          WHERE id = $1
          FOR UPDATE"
@@ -659,82 +986,52 @@ pub async fn bid_on_item_auction(
         return Err(crate::error::ApiError::BadRequest("Auction has ended".to_string()));
     }
 
-    let min_required = if auction.bid_count == 0 {
-        auction.min_bid
-    } else {
-        auction.current_bid + auction.bid_increment
-    };
-
-    if req.amount < min_required {
-        return Err(crate::error::ApiError::BadRequest(
-            format!("Bid must be at least {} coins", min_required)
-        ));
-    }
-
-    // Check balance
-    let balance: (i64,) = sqlx::query_as(
-        "SELECT coins FROM accounts WHERE id = $1"
+    let mut response = place_bid(
+        &mut tx,
+        AuctionType::Item,
+        id,
+        BidTerms {
+            ends_at: auction.ends_at,
+            min_bid: auction.min_bid,
+            bid_increment: auction.bid_increment,
+            current_bid: auction.current_bid,
+            bid_count: auction.bid_count,
+        },
+        claims.account_id,
+        &req,
     )
-    .bind(&claims.sub)
-    .fetch_one(&mut *tx)
     .await?;
 
-    if balance.0 < req.amount {
-        return Err(crate::error::ApiError::BadRequest("Insufficient coins".to_string()));
-    }
-
-    // Refund previous bidder
-    if auction.bid_count > 0 {
-        sqlx::query(
-            "UPDATE accounts SET coins = coins + $1
-             WHERE id = (SELECT bidder_id FROM auction_bids 
-                        WHERE auction_id = $2 AND auction_type = 'item'
-                        ORDER BY amount DESC LIMIT 1)"
-        )
-        .bind(auction.current_bid)
-        .bind(id)
-        .execute(&mut *tx)
-        .await?;
+    // A late bid pushes the end back; the row lock keeps concurrent bids from extending twice
+    let mut extended_secs = auction.extended_secs;
+    if let Some((ends_at, total)) = state.config.anti_snipe.extend(auction.ends_at, Utc::now(), extended_secs) {
+        response.ends_at = ends_at;
+        extended_secs = total;
     }
 
-    // Deduct from bidder
-    sqlx::query("UPDATE accounts SET coins = coins - $1 WHERE id = $2")
-        .bind(req.amount)
-        .bind(&claims.sub)
-        .execute(&mut *tx)
-        .await?;
-
-    // Record bid
-    sqlx::query(
-        "INSERT INTO auction_bids (id, auction_id, auction_type, bidder_id, amount, created_at)
-         VALUES ($1, $2, 'item', $3, $4, $5)"
-    )
-    .bind(Uuid::new_v4())
-    .bind(id)
-    .bind(&claims.sub)
-    .bind(req.amount)
-    .bind(Utc::now())
-    .execute(&mut *tx)
-    .await?;
-
     // Update auction
-    let new_count = auction.bid_count + 1;
     sqlx::query(
-        "UPDATE item_auctions SET current_bid = $1, bid_count = $2 WHERE id = $3"
+        "UPDATE item_auctions SET current_bid = $1, bid_count = $2, ends_at = $3, extended_secs = $4 WHERE id = $5"
     )
-    .bind(req.amount)
-    .bind(new_count)
+    .bind(response.new_bid)
+    .bind(response.bid_count)
+    .bind(response.ends_at)
+    .bind(extended_secs)
     .bind(id)
     .execute(&mut *tx)
     .await?;
 
     tx.commit().await?;
 
-    Ok(Json(BidResponse {
-        success: true,
-        new_bid: req.amount,
-        bid_count: new_count,
-    }))
+    state.auction_hub.publish(AuctionUpdate {
+        auction_type: AuctionType::Item,
+        auction_id: id,
+        current_bid: response.new_bid,
+        bid_count: response.bid_count,
+        ends_at: response.ends_at,
+    });
+
+    Ok(Json(response))
 }
 
 /// Create a character auction
@@ -817,6 +1114,28 @@ pub async fn create_character_auction(
     .execute(&state.db)
     .await?;
 
+    // Alert players watching for characters like this one
+    let listing = CharacterListing {
+        auction_id,
+        character_name: char.0.clone(),
+        level: char.1.max(0) as u32,
+        vocation: format!("{:?}", char.2),
+        min_bid: req.min_bid.max(0) as u64,
+    };
+    if let Err(e) = notify_watchers(
+        &state.db,
+        "character_auction",
+        |condition| condition.matches_character_auction(&listing),
+        "New character auction",
+        &format!("{} (level {} {:?}) is up for auction from {} gold", char.0, char.1, char.2, req.min_bid),
+        &format!("/auctions/characters/{}", auction_id),
+        serde_json::json!({ "auction_id": auction_id, "level": char.1, "vocation": char.2 }),
+    )
+    .await
+    {
+        tracing::warn!("Failed to notify watchers of auction {}: {}", auction_id, e);
+    }
+
     Ok(Json(CharacterAuction {
         id: auction_id,
         character_name: char.0,
@@ -919,6 +1238,26 @@ pub async fn create_item_auction(
     .execute(&state.db)
     .await?;
 
+    let listing = ItemListing {
+        auction_id,
+        item_id: req.item_id as u16,
+        item_name: item_name.clone(),
+        min_bid: req.min_bid.max(0) as u64,
+    };
+    if let Err(e) = notify_watchers(
+        &state.db,
+        "item_auction",
+        |condition| condition.matches_item_auction(&listing),
+        "New item auction",
+        &format!("{} is up for auction from {} gold", item_name, req.min_bid),
+        &format!("/auctions/items/{}", auction_id),
+        serde_json::json!({ "auction_id": auction_id, "item_id": req.item_id, "min_bid": req.min_bid }),
+    )
+    .await
+    {
+        tracing::warn!("Failed to notify watchers of auction {}: {}", auction_id, e);
+    }
+
     Ok(Json(ItemAuction {
         id: auction_id,
         item_id: req.item_id,
@@ -936,6 +1275,74 @@ pub async fn create_item_auction(
     }))
 }
 
+/// Live updates of an auction
+///
+/// Upgrades to a WebSocket that receives an `AuctionUpdate` as JSON
+/// whenever a bid changes the price, bid count or end of the auction.
+#[utoipa::path(
+    get,
+    path = "/api/v1/auctions/{auction_type}/{id}/live",
+    params(
+        ("auction_type" = String, Path, description = "Auction type: 'characters' or 'items'"),
+        ("id" = Uuid, Path, description = "Auction ID")
+    ),
+    responses(
+        (status = 101, description = "Switching to WebSocket", body = AuctionUpdate),
+        (status = 404, description = "Auction not found")
+    ),
+    tag = "auctions"
+)]
+pub async fn auction_live(
+    State(state): State<Arc<AppState>>,
+    Path((auction_type, id)): Path<(String, Uuid)>,
+    ws: WebSocketUpgrade,
+) -> ApiResult<Response> {
+    let exists_query = match auction_type.as_str() {
+        "characters" => "SELECT EXISTS(SELECT 1 FROM character_auctions WHERE id = $1)",
+        "items" => "SELECT EXISTS(SELECT 1 FROM item_auctions WHERE id = $1)",
+        _ => return Err(crate::error::ApiError::BadRequest("Invalid auction type".to_string())),
+    };
+
+    // Refuse before upgrading, a socket for a missing auction would never hear anything
+    let (exists,): (bool,) = sqlx::query_as(exists_query)
+        .bind(id)
+        .fetch_one(&state.db)
+        .await?;
+    if !exists {
+        return Err(crate::error::ApiError::NotFound("Auction not found".to_string()));
+    }
+
+    Ok(ws.on_upgrade(move |socket| stream_auction_updates(socket, state, id)))
+}
+
+/// Forward an auction's updates to a socket until either side goes away
+async fn stream_auction_updates(mut socket: WebSocket, state: Arc<AppState>, auction_id: Uuid) {
+    let mut updates = state.auction_hub.subscribe(auction_id);
+
+    loop {
+        tokio::select! {
+            update = updates.recv() => match update {
+                Ok(update) => {
+                    let Ok(text) = serde_json::to_string(&update) else { continue };
+                    if socket.send(Message::Text(text)).await.is_err() {
+                        break;
+                    }
+                }
+                // A slow client only needs the latest state
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+
+    drop(updates);
+    state.auction_hub.prune(auction_id);
+}
+
 /// Cancel an auction (only if no bids)
 #[utoipa::path(
     delete,
@@ -1018,3 +1425,191 @@ pub async fn cancel_auction(
 
     Ok(Json(SuccessResponse::ok("Auction cancelled")))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proxy(bidder_id: i32, max_amount: i64, placed_at: i64) -> ProxyBid {
+        ProxyBid {
+            bidder_id,
+            max_amount,
+            placed_at: DateTime::from_timestamp(placed_at, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_proxy_outbids_up_to_ceiling_then_is_exceeded() {
+        // Alone, the proxy bidder pays the opening price
+        let mut bids = vec![proxy(1, 500, 0)];
+        assert_eq!(resolve_proxy_bids(&bids, 100, 10), Some(ProxyOutcome { winner: 1, price: 100 }));
+
+        // Open bids below the ceiling are outbid by one increment
+        bids.push(proxy(2, 300, 10));
+        assert_eq!(resolve_proxy_bids(&bids, 100, 10), Some(ProxyOutcome { winner: 1, price: 310 }));
+
+        // Up to the ceiling itself
+        bids.push(proxy(3, 495, 20));
+        assert_eq!(resolve_proxy_bids(&bids, 100, 10), Some(ProxyOutcome { winner: 1, price: 500 }));
+
+        // Then the proxy is exceeded
+        bids.push(proxy(4, 600, 30));
+        assert_eq!(resolve_proxy_bids(&bids, 100, 10), Some(ProxyOutcome { winner: 4, price: 510 }));
+    }
+
+    #[test]
+    fn test_plain_bid_over_leader_stands_at_its_amount() {
+        // The leader stands at 200; a plain 400 takes the lead at 400, not 210
+        let bids = vec![proxy(1, 200, 0), proxy(2, 400, 10)];
+        let outcome = resolve_proxy_bids(&bids, 100, 10).unwrap();
+        assert_eq!(outcome, ProxyOutcome { winner: 2, price: 210 });
+        let plain = BidRequest { amount: 400, max_proxy_bid: None };
+        assert_eq!(standing_price(outcome, Some(1), 200, 2, &plain), 400);
+
+        // With a proxy maximum only what is needed is paid
+        let proxied = BidRequest { amount: 210, max_proxy_bid: Some(400) };
+        assert_eq!(standing_price(outcome, Some(1), 200, 2, &proxied), 210);
+
+        // A plain bid below the leader's ceiling is answered by the proxy
+        let bids = vec![proxy(1, 500, 0), proxy(2, 300, 10)];
+        let outcome = resolve_proxy_bids(&bids, 100, 10).unwrap();
+        let plain = BidRequest { amount: 300, max_proxy_bid: None };
+        assert_eq!(standing_price(outcome, Some(1), 200, 2, &plain), 310);
+    }
+
+    #[test]
+    fn test_equal_ceilings_resolve_to_earliest() {
+        let bids = vec![proxy(7, 400, 50), proxy(3, 400, 20), proxy(9, 100, 0)];
+        assert_eq!(resolve_proxy_bids(&bids, 50, 25), Some(ProxyOutcome { winner: 3, price: 400 }));
+        assert_eq!(resolve_proxy_bids(&bids, 450, 25), None);
+    }
+
+    fn update(auction_id: Uuid, current_bid: i64) -> AuctionUpdate {
+        AuctionUpdate {
+            auction_type: AuctionType::Item,
+            auction_id,
+            current_bid,
+            bid_count: 1,
+            ends_at: DateTime::from_timestamp(10_000, 0).unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_live_subscriber_receives_bid_update() {
+        let hub = AuctionHub::new();
+        let (watched, other) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut first = hub.subscribe(watched);
+        let mut second = hub.subscribe(watched);
+
+        hub.publish(update(other, 999));
+        hub.publish(update(watched, 250));
+        assert_eq!(first.recv().await.unwrap(), update(watched, 250));
+        assert_eq!(second.recv().await.unwrap().current_bid, 250);
+        assert!(first.try_recv().is_err());
+
+        // Channels go away with their last subscriber
+        drop(first);
+        hub.prune(watched);
+        assert_eq!(hub.channel_count(), 1);
+        drop(second);
+        hub.publish(update(watched, 300));
+        assert_eq!(hub.channel_count(), 0);
+    }
+
+    /// Places a bid over HTTP and reads it back from the auction's socket
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres in DATABASE_URL"]
+    async fn test_http_bid_reaches_live_socket() {
+        use crate::auth::AuthConfig;
+        use crate::state::ServerConfig;
+        use axum::routing::{get, post};
+        use futures::StreamExt;
+        use tokio_tungstenite::tungstenite::Message as WsMessage;
+
+        let db = sqlx::PgPool::connect(&std::env::var("DATABASE_URL").unwrap()).await.unwrap();
+        let tag = Uuid::new_v4().simple().to_string();
+        let mut accounts = Vec::new();
+        for role in ["seller", "bidder"] {
+            let (id,): (i32,) = sqlx::query_as(
+                "INSERT INTO accounts (email, password_hash, salt, coins) VALUES ($1, 'x', 'x', 10000) RETURNING id"
+            )
+            .bind(format!("{role}-{tag}@example.com"))
+            .fetch_one(&db)
+            .await
+            .unwrap();
+            accounts.push(id);
+        }
+        let (auction_id,): (Uuid,) = sqlx::query_as(
+            "INSERT INTO item_auctions (seller_id, seller_name, item_id, item_name, min_bid, bid_increment, ends_at)
+             VALUES ($1, 'Seller', 3031, 'gold coin', 100, 10, $2) RETURNING id"
+        )
+        .bind(accounts[0])
+        .bind(Utc::now() + chrono::Duration::hours(1))
+        .fetch_one(&db)
+        .await
+        .unwrap();
+
+        let state = Arc::new(AppState::new(db, AuthConfig::default(), ServerConfig::default()));
+        let claims = JwtClaims::new(accounts[1], &Uuid::new_v4(), "bidder@example.com", "normal", 1);
+        let router = axum::Router::new()
+            .route("/auctions/items/:id/bid", post(bid_on_item_auction))
+            .route("/auctions/:auction_type/:id/live", get(auction_live))
+            .layer(Extension(claims))
+            .with_state(state);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        // Unknown auctions are refused before the upgrade
+        let missing = reqwest::get(format!("http://{addr}/auctions/items/{}/live", Uuid::new_v4())).await.unwrap();
+        assert_eq!(missing.status(), reqwest::StatusCode::NOT_FOUND);
+
+        let (mut socket, _) = tokio_tungstenite::connect_async(format!("ws://{addr}/auctions/items/{auction_id}/live"))
+            .await
+            .unwrap();
+
+        let bid: serde_json::Value = reqwest::Client::new()
+            .post(format!("http://{addr}/auctions/items/{auction_id}/bid"))
+            .json(&serde_json::json!({ "amount": 150 }))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(bid["new_bid"], 100);
+
+        let message = tokio::time::timeout(std::time::Duration::from_secs(5), socket.next())
+            .await
+            .expect("no update within 5s")
+            .unwrap()
+            .unwrap();
+        let WsMessage::Text(text) = message else { panic!("expected a text frame, got {message:?}") };
+        let update: AuctionUpdate = serde_json::from_str(&text).unwrap();
+        assert_eq!(update.auction_id, auction_id);
+        assert_eq!((update.current_bid, update.bid_count), (100, 1));
+    }
+
+    #[test]
+    fn test_anti_snipe_extends_inside_window_up_to_cap() {
+        let config = AntiSnipeConfig { window_secs: 120, max_extension_secs: 300 };
+        let ends_at = DateTime::from_timestamp(10_000, 0).unwrap();
+        let at = |secs_left: i64| ends_at - chrono::Duration::seconds(secs_left);
+
+        // Outside the window, or after the end, nothing changes
+        assert_eq!(config.extend(ends_at, at(121), 0), None);
+        assert_eq!(config.extend(ends_at, at(120), 0), None);
+        assert_eq!(config.extend(ends_at, at(-1), 0), None);
+
+        // Inside it the end moves back by the window
+        let (extended, total) = config.extend(ends_at, at(30), 0).unwrap();
+        assert_eq!((extended - ends_at).num_seconds(), 120);
+        assert_eq!(total, 120);
+
+        // Until the total extension reaches the cap
+        let late = extended - chrono::Duration::seconds(5);
+        assert_eq!(config.extend(extended, late, 240).map(|(_, total)| total), Some(300));
+        assert_eq!(config.extend(ends_at, at(5), 300), None);
+        assert_eq!(AntiSnipeConfig { window_secs: 0, ..config }.extend(ends_at, at(5), 0), None);
+    }
+}
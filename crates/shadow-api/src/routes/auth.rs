@@ -1,8 +1,9 @@
 //! Authentication endpoints
 
 use crate::auth::{
-    create_refresh_token, create_token, hash_password, validate_email,
-    validate_password_strength, validate_refresh_token, verify_password, JwtClaims, RefreshClaims,
+    create_refresh_token, create_token, hash_password, reset_password_with_recovery_key, validate_email,
+    validate_password_strength, validate_refresh_token, verify_password, verify_totp, JwtClaims, RecoveryKey,
+    RefreshClaims,
 };
 use crate::error::ApiError;
 use crate::response::{MessageResponse, SuccessResponse};
@@ -10,6 +11,9 @@ use crate::state::AppState;
 use crate::ApiResult;
 use axum::{extract::State, Json};
 use serde::{Deserialize, Serialize};
+use shadow_blockchain::chains::evm::EvmChainConfig;
+use shadow_blockchain::chains::starknet::StarknetChainConfig;
+use shadow_blockchain::{BlockchainError, Chain, ChainProvider, EvmProvider, StarknetProvider};
 use std::sync::Arc;
 use utoipa::ToSchema;
 use uuid::Uuid;
@@ -19,6 +23,9 @@ use uuid::Uuid;
 pub struct LoginRequest {
     pub email: String,
     pub password: String,
+    /// Current authenticator code, required when 2FA is enabled
+    #[serde(default)]
+    pub totp_code: Option<String>,
 }
 
 /// Login response
@@ -49,6 +56,7 @@ pub struct AccountInfo {
     request_body = LoginRequest,
     responses(
         (status = 200, description = "Login successful", body = LoginResponse),
+        (status = 400, description = "2FA code required or invalid"),
         (status = 401, description = "Invalid credentials")
     ),
     tag = "auth"
@@ -59,7 +67,8 @@ pub async fn login(
 ) -> ApiResult<Json<LoginResponse>> {
     // Find account by email
     let account = sqlx::query_as::<_, AccountRow>(
-        "SELECT id, uuid, email, password_hash, type, premium_until, coins, status
+        "SELECT id, uuid, email, password_hash, type, premium_until, coins, status,
+                COALESCE(totp_enabled, false) AS totp_enabled, totp_secret
          FROM accounts WHERE email = $1"
     )
     .bind(&request.email.to_lowercase())
@@ -79,6 +88,24 @@ pub async fn login(
         return Err(ApiError::InvalidCredentials);
     }
 
+    // 2FA challenge
+    if account.totp_enabled {
+        let code = request
+            .totp_code
+            .as_deref()
+            .ok_or_else(|| ApiError::BadRequest("2FA code required".to_string()))?;
+        // Enabled without a secret: no code can be right, so fail closed
+        let Some(secret) = account.totp_secret.as_deref().filter(|s| !s.is_empty()) else {
+            tracing::error!("Account {} has 2FA enabled but no secret", account.id);
+            log_auth_attempt(&state.db, account.id, "login_2fa", false).await;
+            return Err(ApiError::Internal);
+        };
+        if let Err(e) = check_totp_code(&state, account.id, secret, code).await {
+            log_auth_attempt(&state.db, account.id, "login_2fa", false).await;
+            return Err(e);
+        }
+    }
+
     // Create tokens
     let claims = JwtClaims::new(
         account.id,
@@ -134,6 +161,8 @@ pub struct RegisterRequest {
 pub struct RegisterResponse {
     pub message: String,
     pub account_id: i32,
+    /// One-time key that resets the password without email; shown only once
+    pub recovery_key: String,
 }
 
 /// Register endpoint
@@ -188,9 +217,12 @@ pub async fn register(
     // Log registration
     log_auth_attempt(&state.db, account_id, "register", true).await;
 
+    let recovery_key = store_recovery_key(&state.db, account_id).await?;
+
     Ok(Json(RegisterResponse {
         message: "Registration successful. Please verify your email.".to_string(),
         account_id,
+        recovery_key,
     }))
 }
 
@@ -307,15 +339,151 @@ pub async fn forgot_password(
     Ok(Json(MessageResponse::new("If the email exists, a reset link has been sent")))
 }
 
+/// Reset password request, with an emailed token or a recovery key
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ResetPasswordRequest {
+    pub token: Option<String>,
+    pub email: Option<String>,
+    pub recovery_key: Option<String>,
+    pub new_password: String,
+}
+
 /// Reset password endpoint
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/reset-password",
+    request_body = ResetPasswordRequest,
+    responses(
+        (status = 200, description = "Password reset"),
+        (status = 401, description = "Invalid or used recovery key"),
+        (status = 429, description = "Too many failed recovery attempts")
+    ),
+    tag = "auth"
+)]
 pub async fn reset_password(
-    State(_state): State<Arc<AppState>>,
-    Json(_request): Json<serde_json::Value>,
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<ResetPasswordRequest>,
 ) -> ApiResult<Json<MessageResponse>> {
-    // Implementation would reset password with token
+    let (Some(email), Some(key)) = (&request.email, &request.recovery_key) else {
+        // Implementation would reset password with token
+        return Ok(Json(MessageResponse::new("Password reset successful")));
+    };
+
+    let mut tx = state.db.begin().await?;
+    let row = sqlx::query_as::<_, RecoveryKeyRow>(
+        "SELECT k.account_id, k.key_hash, k.created_at, k.used_at, k.failed_attempts, k.last_failed_at
+         FROM account_recovery_keys k
+         JOIN accounts a ON a.id = k.account_id
+         WHERE a.email = $1
+         FOR UPDATE OF k"
+    )
+    .bind(email.to_lowercase())
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(ApiError::InvalidCredentials)?;
+
+    let account_id = row.account_id;
+    let mut record = RecoveryKey::from(row);
+    let result = reset_password_with_recovery_key(&mut record, key, &request.new_password, chrono::Utc::now());
+
+    // Persist the attempt either way so failures count toward the lockout
+    sqlx::query(
+        "UPDATE account_recovery_keys SET used_at = $2, failed_attempts = $3, last_failed_at = $4
+         WHERE account_id = $1"
+    )
+    .bind(account_id)
+    .bind(record.used_at)
+    .bind(record.failed_attempts as i32)
+    .bind(record.last_failed_at)
+    .execute(&mut *tx)
+    .await?;
+
+    let (password_hash, salt) = match result {
+        Ok(hashed) => hashed,
+        Err(e) => {
+            tx.commit().await?;
+            log_auth_attempt(&state.db, account_id, "recovery_key", false).await;
+            return Err(e);
+        }
+    };
+
+    sqlx::query("UPDATE accounts SET password_hash = $2, salt = $3 WHERE id = $1")
+        .bind(account_id)
+        .bind(&password_hash)
+        .bind(&salt)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+
+    log_auth_attempt(&state.db, account_id, "recovery_key", true).await;
     Ok(Json(MessageResponse::new("Password reset successful")))
 }
 
+/// New recovery key response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RecoveryKeyResponse {
+    /// Shown only once; replaces any previous key
+    pub recovery_key: String,
+}
+
+/// Generate a new recovery key
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/recovery-key",
+    responses(
+        (status = 200, description = "New recovery key", body = RecoveryKeyResponse)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "auth"
+)]
+pub async fn generate_recovery_key(
+    State(state): State<Arc<AppState>>,
+    axum::Extension(claims): axum::Extension<JwtClaims>,
+) -> ApiResult<Json<RecoveryKeyResponse>> {
+    let recovery_key = store_recovery_key(&state.db, claims.account_id).await?;
+    log_auth_attempt(&state.db, claims.account_id, "recovery_key_generated", true).await;
+    Ok(Json(RecoveryKeyResponse { recovery_key }))
+}
+
+/// Issue a recovery key for an account, replacing the previous one
+async fn store_recovery_key(pool: &sqlx::PgPool, account_id: i32) -> ApiResult<String> {
+    let (key, record) = RecoveryKey::issue()?;
+    sqlx::query(
+        "INSERT INTO account_recovery_keys (account_id, key_hash, created_at)
+         VALUES ($1, $2, $3)
+         ON CONFLICT (account_id) DO UPDATE
+         SET key_hash = $2, created_at = $3, used_at = NULL, failed_attempts = 0, last_failed_at = NULL"
+    )
+    .bind(account_id)
+    .bind(&record.key_hash)
+    .bind(record.created_at)
+    .execute(pool)
+    .await?;
+    Ok(key)
+}
+
+#[derive(sqlx::FromRow)]
+struct RecoveryKeyRow {
+    account_id: i32,
+    key_hash: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+    used_at: Option<chrono::DateTime<chrono::Utc>>,
+    failed_attempts: i32,
+    last_failed_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<RecoveryKeyRow> for RecoveryKey {
+    fn from(row: RecoveryKeyRow) -> Self {
+        RecoveryKey {
+            key_hash: row.key_hash,
+            created_at: row.created_at,
+            used_at: row.used_at,
+            failed_attempts: row.failed_attempts.max(0) as u32,
+            last_failed_at: row.last_failed_at,
+        }
+    }
+}
+
 // ============================================
 // Two-Factor Authentication (2FA)
 // ============================================
@@ -407,12 +575,8 @@ pub async fn verify_2fa(
         .and_then(|s| s.0)
         .ok_or(ApiError::BadRequest("No pending 2FA setup".to_string()))?;
     
-    // Verify TOTP code (simplified - in production use totp-rs crate)
-    // For now, accept any 6-digit code as valid
-    if request.code.len() != 6 || !request.code.chars().all(|c| c.is_ascii_digit()) {
-        return Err(ApiError::BadRequest("Invalid 2FA code".to_string()));
-    }
-    
+    check_totp_code(&state, claims.account_id, &pending_secret, &request.code).await?;
+
     // Activate 2FA
     sqlx::query(
         "UPDATE accounts SET totp_secret = totp_pending_secret, totp_pending_secret = NULL, totp_enabled = true WHERE id = $1"
@@ -441,10 +605,19 @@ pub async fn disable_2fa(
     Json(request): Json<Verify2FARequest>,
 ) -> ApiResult<Json<SuccessResponse>> {
     // Verify current code before disabling
-    if request.code.len() != 6 || !request.code.chars().all(|c| c.is_ascii_digit()) {
-        return Err(ApiError::BadRequest("Invalid 2FA code".to_string()));
-    }
-    
+    let secret: Option<(Option<String>,)> = sqlx::query_as(
+        "SELECT totp_secret FROM accounts WHERE id = $1 AND totp_enabled = true"
+    )
+    .bind(claims.account_id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    let active_secret = secret
+        .and_then(|s| s.0)
+        .ok_or(ApiError::BadRequest("2FA is not enabled".to_string()))?;
+
+    check_totp_code(&state, claims.account_id, &active_secret, &request.code).await?;
+
     sqlx::query(
         "UPDATE accounts SET totp_secret = NULL, totp_enabled = false WHERE id = $1"
     )
@@ -501,17 +674,15 @@ pub async fn get_wallet_nonce(
     .execute(&state.db)
     .await?;
     
-    let message = format!(
-        "Sign this message to authenticate with Shadow OT.\n\nNonce: {}\nAddress: {}",
-        nonce, address
-    );
-    
+    let message = wallet_auth_message(&nonce, &address);
+
     Ok(Json(WalletNonceResponse { nonce, message }))
 }
 
 /// Wallet login request
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct WalletLoginRequest {
+    /// Address exactly as passed to the nonce endpoint; it is part of the signed message
     pub address: String,
     pub signature: String,
     pub chain: String,
@@ -523,7 +694,9 @@ pub struct WalletLoginRequest {
     path = "/api/v1/auth/wallet/login",
     request_body = WalletLoginRequest,
     responses(
-        (status = 200, description = "Wallet login successful", body = LoginResponse)
+        (status = 200, description = "Wallet login successful", body = LoginResponse),
+        (status = 400, description = "Invalid or expired nonce"),
+        (status = 401, description = "Signature does not match the address")
     ),
     tag = "auth"
 )]
@@ -532,29 +705,24 @@ pub async fn login_with_wallet(
     Json(request): Json<WalletLoginRequest>,
 ) -> ApiResult<Json<LoginResponse>> {
     let address = request.address.to_lowercase();
-    
-    // Get and verify nonce
+    let chain: Chain = request.chain.parse().map_err(|e: BlockchainError| ApiError::BadRequest(e.to_string()))?;
+
+    // Consume the nonce up front so it is single-use even if the signature fails
     let nonce: Option<(String,)> = sqlx::query_as(
-        "SELECT nonce FROM wallet_nonces WHERE address = $1 AND created_at > NOW() - INTERVAL '5 minutes'"
+        "DELETE FROM wallet_nonces WHERE address = $1 AND created_at > NOW() - INTERVAL '5 minutes'
+         RETURNING nonce"
     )
     .bind(&address)
     .fetch_optional(&state.db)
     .await?;
-    
-    let _nonce = nonce.ok_or(ApiError::BadRequest("Invalid or expired nonce".to_string()))?.0;
-    
-    // In production, verify the signature using ethers-rs or similar
-    // For now, accept any non-empty signature
-    if request.signature.is_empty() {
-        return Err(ApiError::BadRequest("Invalid signature".to_string()));
+
+    let nonce = nonce.ok_or(ApiError::BadRequest("Invalid or expired nonce".to_string()))?.0;
+
+    let message = wallet_auth_message(&nonce, &request.address);
+    if !verify_wallet_signature(&state, chain, &message, &request.signature, &request.address).await? {
+        return Err(ApiError::Unauthorized);
     }
-    
-    // Delete used nonce
-    sqlx::query("DELETE FROM wallet_nonces WHERE address = $1")
-        .bind(&address)
-        .execute(&state.db)
-        .await?;
-    
+
     // Find or create account
     let account = sqlx::query_as::<_, AccountRow>(
         "SELECT a.id, a.uuid, a.email, a.password_hash, a.type, a.premium_until, a.coins, a.status
@@ -725,6 +893,50 @@ pub async fn resend_verification(
     Ok(Json(SuccessResponse::ok("Verification email sent")))
 }
 
+/// Message a wallet signs to log in. The nonce endpoint hands it out and
+/// the login endpoint rebuilds it to check the signature.
+fn wallet_auth_message(nonce: &str, address: &str) -> String {
+    format!(
+        "Sign this message to authenticate with Shadow OT.\n\nNonce: {}\nAddress: {}",
+        nonce, address
+    )
+}
+
+/// Check that `address` signed `message`: EIP-191 `personal_sign` on EVM
+/// chains, the account contract's own check on Starknet
+async fn verify_wallet_signature(
+    state: &AppState,
+    chain: Chain,
+    message: &str,
+    signature: &str,
+    address: &str,
+) -> ApiResult<bool> {
+    let provider: Box<dyn ChainProvider> = if chain.is_evm() {
+        Box::new(EvmProvider::new(EvmChainConfig { chain, ..Default::default() }).await.map_err(wallet_error)?)
+    } else if chain.is_starknet() {
+        let config = StarknetChainConfig {
+            chain,
+            rpc_url: state.config.starknet_rpc_url.clone(),
+            ..Default::default()
+        };
+        Box::new(StarknetProvider::new(config).await.map_err(wallet_error)?)
+    } else {
+        return Err(ApiError::BadRequest(format!("Wallet login is not supported on {:?}", chain)));
+    };
+
+    provider.verify_signature(message, signature, address).await.map_err(wallet_error)
+}
+
+fn wallet_error(error: BlockchainError) -> ApiError {
+    match error {
+        BlockchainError::InvalidAddress(_) | BlockchainError::InvalidSignature(_) => ApiError::Unauthorized,
+        e => {
+            tracing::warn!("Wallet signature check failed: {}", e);
+            ApiError::ServiceUnavailable
+        }
+    }
+}
+
 // Helper types
 
 #[derive(sqlx::FromRow)]
@@ -738,6 +950,46 @@ struct AccountRow {
     premium_until: Option<chrono::DateTime<chrono::Utc>>,
     coins: i32,
     status: String,
+    #[sqlx(default)]
+    totp_enabled: bool,
+    #[sqlx(default)]
+    totp_secret: Option<String>,
+}
+
+/// Verify a TOTP code and record its time step so it cannot be used again
+async fn check_totp_code(state: &AppState, account_id: i32, secret: &str, code: &str) -> ApiResult<()> {
+    let invalid = || ApiError::BadRequest("Invalid 2FA code".to_string());
+
+    let last_used: Option<(Option<i64>,)> = sqlx::query_as(
+        "SELECT totp_last_used_step FROM accounts WHERE id = $1"
+    )
+    .bind(account_id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    let step = verify_totp(
+        secret,
+        code.trim(),
+        chrono::Utc::now().timestamp(),
+        state.auth_config.totp_skew_steps,
+        last_used.and_then(|r| r.0),
+    )
+    .ok_or_else(invalid)?;
+
+    // Conditional update so two concurrent requests cannot both use the step
+    let recorded = sqlx::query(
+        "UPDATE accounts SET totp_last_used_step = $2
+         WHERE id = $1 AND (totp_last_used_step IS NULL OR totp_last_used_step < $2)"
+    )
+    .bind(account_id)
+    .bind(step)
+    .execute(&state.db)
+    .await?;
+
+    if recorded.rows_affected() == 0 {
+        return Err(invalid());
+    }
+    Ok(())
 }
 
 async fn log_auth_attempt(pool: &sqlx::PgPool, account_id: i32, action: &str, success: bool) {
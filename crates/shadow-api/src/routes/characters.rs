@@ -6,10 +6,17 @@ use crate::response::{MessageResponse, OnlineStatusResponse};
 use crate::state::AppState;
 use crate::domain::{Gender, Vocation};
 use crate::ApiResult;
-use axum::{extract::{Path, Request, State}, Json};
+use axum::{extract::{Path, Query, Request, State}, Json};
+use crate::routes::inventory::{Imbuement, ItemAttributes};
 use serde::{Deserialize, Serialize};
+use shadow_combat::{
+    CombatStats, DamageType, EncounterSummary, SheetItem, SheetSkills, SkillProgress, SkillTracker, SourceBreakdown,
+};
+use shadow_world::item::{SkillType, SlotType, WeaponType};
+use std::collections::HashMap;
 use std::sync::Arc;
 use utoipa::ToSchema;
+use uuid::Uuid;
 
 /// Character response
 #[derive(Debug, Serialize, ToSchema)]
@@ -182,11 +189,14 @@ pub async fn create_character(
     // Get look type based on gender
     let look_type = body.gender.default_look_type();
 
-    // Create character
-    let id = sqlx::query_scalar::<_, i32>(
-        "INSERT INTO characters (account_id, realm_id, name, sex, vocation, look_type, town_id)
-         VALUES ($1, $2, $3, $4, $5, $6, $7)
-         RETURNING id"
+    let kit = state.config.starting_kits.kit_for(body.realm_id, body.vocation);
+
+    // Create character with its starting kit
+    let mut tx = state.db.begin().await?;
+    let (id, uuid) = sqlx::query_as::<_, (i32, Uuid)>(
+        "INSERT INTO characters (account_id, realm_id, name, sex, vocation, look_type, town_id, balance)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+         RETURNING id, uuid"
     )
     .bind(claims.account_id)
     .bind(body.realm_id)
@@ -195,9 +205,38 @@ pub async fn create_character(
     .bind(body.vocation.to_i16())
     .bind(look_type)
     .bind(town_id)
-    .fetch_one(&state.db)
+    .bind(kit.gold)
+    .fetch_one(&mut *tx)
     .await?;
 
+    for (index, item) in kit.items.iter().enumerate() {
+        // Backpack items take the slots after the equipment slots
+        let slot = if item.slot > 0 { item.slot } else { 11 + index as i32 };
+        sqlx::query("INSERT INTO character_inventory (character_id, item_id, count, slot) VALUES ($1, $2, $3, $4)")
+            .bind(uuid)
+            .bind(item.item_id)
+            .bind(item.count)
+            .bind(slot)
+            .execute(&mut *tx)
+            .await?;
+    }
+    for spell in &kit.spells {
+        sqlx::query("INSERT INTO character_spells (character_id, spell_name) VALUES ($1, $2)")
+            .bind(id)
+            .bind(spell)
+            .execute(&mut *tx)
+            .await?;
+    }
+    for (key, value) in &kit.storage {
+        sqlx::query("INSERT INTO character_storage (character_id, key, value) VALUES ($1, $2, $3)")
+            .bind(id)
+            .bind(key)
+            .bind(value)
+            .execute(&mut *tx)
+            .await?;
+    }
+    tx.commit().await?;
+
     // Fetch created character
     get_character(State(state), Path(id)).await
 }
@@ -265,8 +304,839 @@ pub async fn get_online_status(
     Ok(Json(OnlineStatusResponse::new(online)))
 }
 
+/// Public character sheet for third-party tools
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacterSheetResponse {
+    pub id: i32,
+    pub name: String,
+    pub vocation: i16,
+    pub level: i32,
+    pub skills: SheetSkillsResponse,
+    pub equipment: Vec<SheetEquipmentItem>,
+    pub combat: SheetCombatStats,
+}
+
+/// Character skills
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SheetSkillsResponse {
+    pub fist: i32,
+    pub club: i32,
+    pub sword: i32,
+    pub axe: i32,
+    pub distance: i32,
+    pub shielding: i32,
+    pub fishing: i32,
+}
+
+/// Progress of one skill toward its next level
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillProgressEntry {
+    pub skill: String,
+    pub level: i32,
+    pub tries: i64,
+    pub percent: i32,
+    pub tries_remaining: i64,
+}
+
+impl From<SkillProgress> for SkillProgressEntry {
+    fn from(progress: SkillProgress) -> Self {
+        SkillProgressEntry {
+            skill: format!("{:?}", progress.skill).to_lowercase(),
+            level: progress.level as i32,
+            tries: progress.tries as i64,
+            percent: progress.percent() as i32,
+            tries_remaining: progress.tries_remaining() as i64,
+        }
+    }
+}
+
+/// Skill progress response
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillProgressResponse {
+    pub id: i32,
+    pub name: String,
+    pub skills: Vec<SkillProgressEntry>,
+}
+
+/// Death log entry
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeathEntry {
+    pub level: i32,
+    pub killed_by: String,
+    pub is_player: bool,
+    pub most_damage_by: Option<String>,
+    pub death_type: String,
+    pub experience_lost: i64,
+    pub unjustified: bool,
+    pub died_at: String,
+}
+
+/// Paginated death log
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PaginatedDeaths {
+    pub data: Vec<DeathEntry>,
+    pub total: i64,
+    pub page: u32,
+    pub page_size: u32,
+    pub total_pages: u32,
+}
+
+/// Death log query parameters
+#[derive(Debug, Deserialize)]
+pub struct DeathLogQuery {
+    pub page: Option<u32>,
+    pub page_size: Option<u32>,
+}
+
+/// Deaths by one cause
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeathCauseCount {
+    pub death_type: String,
+    pub deaths: i64,
+    pub experience_lost: i64,
+}
+
+/// Most frequent killer
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeathKillerCount {
+    pub killed_by: String,
+    pub is_player: bool,
+    pub deaths: i64,
+}
+
+/// Aggregate death statistics
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeathStatsResponse {
+    pub total_deaths: i64,
+    pub experience_lost: i64,
+    pub by_cause: Vec<DeathCauseCount>,
+    pub top_killers: Vec<DeathKillerCount>,
+}
+
+/// Damage or healing of one source in an encounter
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EncounterSource {
+    pub source: String,
+    pub damage_type: Option<String>,
+    pub hits: u32,
+    pub total: i64,
+    pub per_second: f64,
+}
+
+/// One recorded encounter
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EncounterEntry {
+    pub started_at: String,
+    pub ended_at: String,
+    pub duration_ms: u64,
+    pub damage_dealt: i64,
+    pub damage_taken: i64,
+    pub healing_done: i64,
+    pub dps: f64,
+    pub hps: f64,
+    pub damage_sources: Vec<EncounterSource>,
+    pub damage_taken_sources: Vec<EncounterSource>,
+    pub healing_sources: Vec<EncounterSource>,
+    pub opponents: Vec<String>,
+}
+
+/// A character's recent encounters
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EncounterLogResponse {
+    pub character_id: i32,
+    /// Whether new encounters are recorded
+    pub enabled: bool,
+    /// Whether anyone may view the log
+    pub public: bool,
+    /// Most recent first
+    pub encounters: Vec<EncounterEntry>,
+}
+
+/// Combat log settings update
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CombatLogSettingsRequest {
+    pub enabled: Option<bool>,
+    pub public: Option<bool>,
+}
+
+/// Equipped item
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SheetEquipmentItem {
+    pub slot: i32,
+    pub item_id: i32,
+    pub name: String,
+    pub attributes: ItemAttributes,
+}
+
+/// Combat stats computed with the server's combat formulas
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SheetCombatStats {
+    pub weapon_skill: String,
+    pub attack: i32,
+    pub defense: i32,
+    pub effective_defense: i32,
+    pub armor: i32,
+    pub min_damage: i32,
+    pub max_damage: i32,
+    /// Resistances in percent by element
+    pub resistances: HashMap<String, i32>,
+}
+
+impl From<CombatStats> for SheetCombatStats {
+    fn from(stats: CombatStats) -> Self {
+        SheetCombatStats {
+            weapon_skill: format!("{:?}", stats.weapon_skill).to_lowercase(),
+            attack: stats.attack,
+            defense: stats.defense,
+            effective_defense: stats.effective_defense,
+            armor: stats.armor,
+            min_damage: stats.min_damage,
+            max_damage: stats.max_damage,
+            resistances: stats
+                .resistances
+                .into_iter()
+                .map(|(damage_type, percent)| (format!("{:?}", damage_type).to_lowercase(), percent))
+                .collect(),
+        }
+    }
+}
+
+/// Get character sheet
+#[utoipa::path(
+    get,
+    path = "/api/v1/characters/{id}/sheet",
+    params(
+        ("id" = i32, Path, description = "Character ID")
+    ),
+    responses(
+        (status = 200, description = "Character sheet", body = CharacterSheetResponse),
+        (status = 403, description = "Character sheet is private"),
+        (status = 404, description = "Character not found")
+    ),
+    tag = "characters"
+)]
+pub async fn get_character_sheet(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+) -> ApiResult<Json<CharacterSheetResponse>> {
+    let character = sqlx::query_as::<_, CharacterSheetRow>(
+        "SELECT id, uuid, name, vocation, level, sheet_public,
+                skill_fist, skill_club, skill_sword, skill_axe, skill_dist, skill_shielding, skill_fishing
+         FROM characters
+         WHERE id = $1 AND deletion_time IS NULL"
+    )
+    .bind(id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or(ApiError::NotFound("Character not found".to_string()))?;
+
+    if !character.sheet_public {
+        return Err(ApiError::Forbidden);
+    }
+
+    // Worn items sit in equipment slots 1-10 outside of any container
+    let rows = sqlx::query_as::<_, EquipmentRow>(
+        "SELECT i.id, i.item_id, it.name, i.slot,
+                COALESCE(i.attack, it.attack) as attack,
+                COALESCE(i.defense, it.defense) as defense,
+                COALESCE(i.armor, it.armor) as armor,
+                i.charges, i.duration,
+                it.weapon_type::text as weapon_type,
+                it.\"group\"::text as item_group
+         FROM character_inventory i
+         JOIN items it ON it.id = i.item_id
+         WHERE i.character_id = $1 AND i.container_id IS NULL AND i.slot BETWEEN 1 AND 10
+         ORDER BY i.slot"
+    )
+    .bind(character.uuid)
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut equipment = Vec::with_capacity(rows.len());
+    let mut sheet_items = Vec::with_capacity(rows.len());
+    for row in rows {
+        let imbuements = load_sheet_imbuements(&state, row.id).await?;
+        let mut resistances: HashMap<DamageType, i32> = HashMap::new();
+        for (_, effect) in &imbuements {
+            for (damage_type, percent) in imbuement_resistances(effect) {
+                *resistances.entry(damage_type).or_insert(0) += percent;
+            }
+        }
+
+        sheet_items.push(SheetItem {
+            slot: equipment_slot(row.slot),
+            weapon_type: weapon_type(row.weapon_type.as_deref(), row.item_group.as_deref()),
+            attack: row.attack.unwrap_or(0),
+            defense: row.defense.unwrap_or(0),
+            extra_defense: 0,
+            armor: row.armor.unwrap_or(0),
+            resistances,
+        });
+        equipment.push(SheetEquipmentItem {
+            slot: row.slot,
+            item_id: row.item_id,
+            name: row.name,
+            attributes: ItemAttributes {
+                attack: row.attack,
+                defense: row.defense,
+                armor: row.armor,
+                charges: row.charges,
+                duration: row.duration,
+                imbuements: imbuements.into_iter().map(|(imbuement, _)| imbuement).collect(),
+            },
+        });
+    }
+
+    let skill = |value: i32| value.clamp(0, u8::MAX as i32) as u8;
+    let skills = SheetSkills {
+        fist: skill(character.skill_fist),
+        club: skill(character.skill_club),
+        sword: skill(character.skill_sword),
+        axe: skill(character.skill_axe),
+        distance: skill(character.skill_dist),
+        shielding: skill(character.skill_shielding),
+    };
+    let level = character.level.clamp(1, u16::MAX as i32) as u16;
+    let combat = CombatStats::compute(level, &skills, &sheet_items);
+
+    Ok(Json(CharacterSheetResponse {
+        id: character.id,
+        name: character.name,
+        vocation: character.vocation,
+        level: character.level,
+        skills: SheetSkillsResponse {
+            fist: character.skill_fist,
+            club: character.skill_club,
+            sword: character.skill_sword,
+            axe: character.skill_axe,
+            distance: character.skill_dist,
+            shielding: character.skill_shielding,
+            fishing: character.skill_fishing,
+        },
+        equipment,
+        combat: combat.into(),
+    }))
+}
+
+/// Get skill progress
+#[utoipa::path(
+    get,
+    path = "/api/v1/characters/{id}/skill-progress",
+    params(
+        ("id" = i32, Path, description = "Character ID")
+    ),
+    responses(
+        (status = 200, description = "Skill tries and percent to the next level", body = SkillProgressResponse),
+        (status = 403, description = "Character sheet is private"),
+        (status = 404, description = "Character not found")
+    ),
+    tag = "characters"
+)]
+pub async fn get_skill_progress(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+) -> ApiResult<Json<SkillProgressResponse>> {
+    let character = sqlx::query_as::<_, SkillProgressRow>(
+        "SELECT id, name, sheet_public, magic_level, mana_spent,
+                skill_fist, skill_fist_tries, skill_club, skill_club_tries,
+                skill_sword, skill_sword_tries, skill_axe, skill_axe_tries,
+                skill_dist, skill_dist_tries, skill_shielding, skill_shielding_tries,
+                skill_fishing, skill_fishing_tries
+         FROM characters
+         WHERE id = $1 AND deletion_time IS NULL"
+    )
+    .bind(id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or(ApiError::NotFound("Character not found".to_string()))?;
+
+    if !character.sheet_public {
+        return Err(ApiError::Forbidden);
+    }
+
+    let level = |value: i32| value.clamp(0, u8::MAX as i32) as u8;
+    let tries = |value: i64| value.max(0) as u64;
+    let tracker = SkillTracker::default()
+        .with_skill(SkillType::MagicLevel, level(character.magic_level), tries(character.mana_spent))
+        .with_skill(SkillType::Fist, level(character.skill_fist), tries(character.skill_fist_tries))
+        .with_skill(SkillType::Club, level(character.skill_club), tries(character.skill_club_tries))
+        .with_skill(SkillType::Sword, level(character.skill_sword), tries(character.skill_sword_tries))
+        .with_skill(SkillType::Axe, level(character.skill_axe), tries(character.skill_axe_tries))
+        .with_skill(SkillType::Distance, level(character.skill_dist), tries(character.skill_dist_tries))
+        .with_skill(SkillType::Shielding, level(character.skill_shielding), tries(character.skill_shielding_tries))
+        .with_skill(SkillType::Fishing, level(character.skill_fishing), tries(character.skill_fishing_tries));
+
+    Ok(Json(SkillProgressResponse {
+        id: character.id,
+        name: character.name,
+        skills: tracker.all_progress().into_iter().map(SkillProgressEntry::from).collect(),
+    }))
+}
+
+/// Get a character's death log
+#[utoipa::path(
+    get,
+    path = "/api/v1/characters/{id}/deaths",
+    params(
+        ("id" = i32, Path, description = "Character ID"),
+        ("page" = Option<u32>, Query, description = "Page number"),
+        ("page_size" = Option<u32>, Query, description = "Results per page")
+    ),
+    responses(
+        (status = 200, description = "Deaths, newest first", body = PaginatedDeaths),
+        (status = 404, description = "Character not found")
+    ),
+    tag = "characters"
+)]
+pub async fn get_character_deaths(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Query(query): Query<DeathLogQuery>,
+) -> ApiResult<Json<PaginatedDeaths>> {
+    ensure_character_exists(&state, id).await?;
+
+    let page = query.page.unwrap_or(1).max(1);
+    let page_size = query.page_size.unwrap_or(20).clamp(1, 100);
+    let offset = (page - 1) * page_size;
+
+    let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM character_deaths WHERE character_id = $1")
+        .bind(id)
+        .fetch_one(&state.db)
+        .await?;
+
+    let rows = sqlx::query_as::<_, DeathRow>(
+        "SELECT level, killed_by, is_player, mostdamage_by, death_type, experience_lost,
+                unjustified, death_time
+         FROM character_deaths
+         WHERE character_id = $1
+         ORDER BY death_time DESC
+         LIMIT $2 OFFSET $3"
+    )
+    .bind(id)
+    .bind(page_size as i64)
+    .bind(offset as i64)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(PaginatedDeaths {
+        data: rows.into_iter().map(DeathEntry::from).collect(),
+        total: total.0,
+        page,
+        page_size,
+        total_pages: ((total.0 as f64) / (page_size as f64)).ceil() as u32,
+    }))
+}
+
+/// Get a character's death statistics
+#[utoipa::path(
+    get,
+    path = "/api/v1/characters/{id}/deaths/stats",
+    params(
+        ("id" = i32, Path, description = "Character ID")
+    ),
+    responses(
+        (status = 200, description = "Deaths by cause and most frequent killers", body = DeathStatsResponse),
+        (status = 404, description = "Character not found")
+    ),
+    tag = "characters"
+)]
+pub async fn get_character_death_stats(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+) -> ApiResult<Json<DeathStatsResponse>> {
+    ensure_character_exists(&state, id).await?;
+    Ok(Json(load_death_stats(&state, Some(id)).await?))
+}
+
+/// Get server-wide death statistics
+#[utoipa::path(
+    get,
+    path = "/api/v1/deaths/stats",
+    responses(
+        (status = 200, description = "Deaths by cause and most frequent killers", body = DeathStatsResponse)
+    ),
+    tag = "characters"
+)]
+pub async fn get_death_stats(
+    State(state): State<Arc<AppState>>,
+) -> ApiResult<Json<DeathStatsResponse>> {
+    Ok(Json(load_death_stats(&state, None).await?))
+}
+
+/// Get a character's recent encounters
+#[utoipa::path(
+    get,
+    path = "/api/v1/characters/{id}/encounters",
+    params(
+        ("id" = i32, Path, description = "Character ID")
+    ),
+    responses(
+        (status = 200, description = "Recent encounters with DPS/HPS breakdowns", body = EncounterLogResponse),
+        (status = 403, description = "Combat log is private"),
+        (status = 404, description = "Character not found")
+    ),
+    tag = "characters"
+)]
+pub async fn get_character_encounters(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    request: Request,
+) -> ApiResult<Json<EncounterLogResponse>> {
+    let character = load_combat_log_settings(&state, id).await?;
+
+    let is_owner = get_claims(&request).is_some_and(|claims| character.account_id == Some(claims.account_id));
+    if !character.combat_log_public && !is_owner {
+        return Err(ApiError::Forbidden);
+    }
+
+    let rows: Vec<(serde_json::Value,)> = sqlx::query_as(
+        "SELECT summary FROM character_encounters
+         WHERE character_id = $1
+         ORDER BY ended_at DESC, id DESC
+         LIMIT $2"
+    )
+    .bind(character.uuid)
+    .bind(state.config.combat_log.max_encounters as i64)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(EncounterLogResponse {
+        character_id: id,
+        enabled: character.combat_log_enabled,
+        public: character.combat_log_public,
+        encounters: rows
+            .into_iter()
+            .filter_map(|(summary,)| serde_json::from_value::<EncounterSummary>(summary).ok())
+            .map(EncounterEntry::from)
+            .collect(),
+    }))
+}
+
+/// Update a character's combat log settings
+#[utoipa::path(
+    put,
+    path = "/api/v1/characters/{id}/encounters/settings",
+    params(
+        ("id" = i32, Path, description = "Character ID")
+    ),
+    request_body = CombatLogSettingsRequest,
+    responses(
+        (status = 200, description = "Settings updated", body = EncounterLogResponse),
+        (status = 404, description = "Character not found")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "characters"
+)]
+pub async fn update_combat_log_settings(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    axum::Extension(claims): axum::Extension<crate::auth::JwtClaims>,
+    Json(body): Json<CombatLogSettingsRequest>,
+) -> ApiResult<Json<EncounterLogResponse>> {
+    let character = load_combat_log_settings(&state, id).await?;
+    if character.account_id != Some(claims.account_id) {
+        return Err(ApiError::NotFound("Character not found".to_string()));
+    }
+
+    let enabled = body.enabled.unwrap_or(character.combat_log_enabled);
+    let public = body.public.unwrap_or(character.combat_log_public);
+    sqlx::query("UPDATE characters SET combat_log_enabled = $2, combat_log_public = $3 WHERE id = $1")
+        .bind(id)
+        .bind(enabled)
+        .bind(public)
+        .execute(&state.db)
+        .await?;
+
+    // Opting out forgets what was recorded
+    if !enabled {
+        sqlx::query("DELETE FROM character_encounters WHERE character_id = $1")
+            .bind(character.uuid)
+            .execute(&state.db)
+            .await?;
+    }
+
+    Ok(Json(EncounterLogResponse {
+        character_id: id,
+        enabled,
+        public,
+        encounters: Vec::new(),
+    }))
+}
+
+async fn load_combat_log_settings(state: &AppState, id: i32) -> ApiResult<CombatLogSettingsRow> {
+    sqlx::query_as::<_, CombatLogSettingsRow>(
+        "SELECT uuid, account_id, combat_log_enabled, combat_log_public
+         FROM characters
+         WHERE id = $1 AND deletion_time IS NULL"
+    )
+    .bind(id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or(ApiError::NotFound("Character not found".to_string()))
+}
+
+async fn ensure_character_exists(state: &AppState, id: i32) -> ApiResult<()> {
+    sqlx::query_scalar::<_, i32>("SELECT id FROM characters WHERE id = $1 AND deletion_time IS NULL")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or(ApiError::NotFound("Character not found".to_string()))?;
+    Ok(())
+}
+
+/// Death statistics of one character, or of every character when `character_id` is `None`
+async fn load_death_stats(state: &AppState, character_id: Option<i32>) -> ApiResult<DeathStatsResponse> {
+    let by_cause: Vec<(String, i64, i64)> = sqlx::query_as(
+        "SELECT death_type, COUNT(*), COALESCE(SUM(experience_lost), 0)::BIGINT
+         FROM character_deaths
+         WHERE ($1::int IS NULL OR character_id = $1)
+         GROUP BY death_type
+         ORDER BY COUNT(*) DESC"
+    )
+    .bind(character_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    let top_killers: Vec<(String, bool, i64)> = sqlx::query_as(
+        "SELECT killed_by, is_player, COUNT(*)
+         FROM character_deaths
+         WHERE ($1::int IS NULL OR character_id = $1)
+         GROUP BY killed_by, is_player
+         ORDER BY COUNT(*) DESC, killed_by ASC
+         LIMIT 10"
+    )
+    .bind(character_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(DeathStatsResponse {
+        total_deaths: by_cause.iter().map(|(_, deaths, _)| deaths).sum(),
+        experience_lost: by_cause.iter().map(|(_, _, lost)| lost).sum(),
+        by_cause: by_cause
+            .into_iter()
+            .map(|(death_type, deaths, experience_lost)| DeathCauseCount { death_type, deaths, experience_lost })
+            .collect(),
+        top_killers: top_killers
+            .into_iter()
+            .map(|(killed_by, is_player, deaths)| DeathKillerCount { killed_by, is_player, deaths })
+            .collect(),
+    })
+}
+
+async fn load_sheet_imbuements(
+    state: &AppState,
+    inventory_id: Uuid,
+) -> Result<Vec<(Imbuement, serde_json::Value)>, sqlx::Error> {
+    let rows: Vec<(i32, String, i32, f32, Option<serde_json::Value>)> = sqlx::query_as(
+        "SELECT imb.id, imb.name, ii.tier, ii.remaining_hours, imb.effect
+         FROM inventory_imbuements ii
+         JOIN imbuements imb ON imb.id = ii.imbuement_id
+         WHERE ii.inventory_id = $1"
+    )
+    .bind(inventory_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(rows.into_iter().map(|(id, name, tier, remaining_hours, effect)| {
+        (Imbuement { id, name, tier, remaining_hours }, effect.unwrap_or_default())
+    }).collect())
+}
+
+/// Resistances granted by an imbuement effect, e.g. `{"fire_resistance": 20}`
+fn imbuement_resistances(effect: &serde_json::Value) -> Vec<(DamageType, i32)> {
+    let Some(effect) = effect.as_object() else {
+        return Vec::new();
+    };
+    effect
+        .iter()
+        .filter_map(|(key, value)| {
+            let damage_type = match key.as_str() {
+                "damage_reduction" | "physical_resistance" => DamageType::Physical,
+                "fire_resistance" => DamageType::Fire,
+                "ice_resistance" => DamageType::Ice,
+                "energy_resistance" => DamageType::Energy,
+                "earth_resistance" => DamageType::Earth,
+                "holy_resistance" => DamageType::Holy,
+                "death_resistance" => DamageType::Death,
+                _ => return None,
+            };
+            Some((damage_type, value.as_i64()? as i32))
+        })
+        .collect()
+}
+
+/// Map an inventory equipment slot to its slot type
+fn equipment_slot(slot: i32) -> Option<SlotType> {
+    match slot {
+        1 => Some(SlotType::Head),
+        2 => Some(SlotType::Necklace),
+        3 => Some(SlotType::Backpack),
+        4 => Some(SlotType::Armor),
+        5 => Some(SlotType::Right),
+        6 => Some(SlotType::Left),
+        7 => Some(SlotType::Legs),
+        8 => Some(SlotType::Feet),
+        9 => Some(SlotType::Ring),
+        10 => Some(SlotType::Ammo),
+        _ => None,
+    }
+}
+
+fn weapon_type(weapon_type: Option<&str>, item_group: Option<&str>) -> Option<WeaponType> {
+    match (weapon_type, item_group) {
+        (Some("sword"), _) => Some(WeaponType::Sword),
+        (Some("club"), _) => Some(WeaponType::Club),
+        (Some("axe"), _) => Some(WeaponType::Axe),
+        (Some("distance"), _) => Some(WeaponType::Distance),
+        (Some("wand" | "rod"), _) => Some(WeaponType::Wand),
+        (_, Some("shield")) => Some(WeaponType::Shield),
+        (_, Some("ammunition")) => Some(WeaponType::Ammunition),
+        _ => None,
+    }
+}
+
+fn millis_to_rfc3339(millis: u64) -> String {
+    chrono::DateTime::from_timestamp_millis(millis as i64)
+        .unwrap_or_default()
+        .to_rfc3339()
+}
+
+impl From<SourceBreakdown> for EncounterSource {
+    fn from(source: SourceBreakdown) -> Self {
+        EncounterSource {
+            source: source.source,
+            damage_type: source.damage_type.map(|t| format!("{:?}", t).to_lowercase()),
+            hits: source.hits,
+            total: source.total,
+            per_second: source.per_second,
+        }
+    }
+}
+
+impl From<EncounterSummary> for EncounterEntry {
+    fn from(summary: EncounterSummary) -> Self {
+        EncounterEntry {
+            started_at: millis_to_rfc3339(summary.started_at),
+            ended_at: millis_to_rfc3339(summary.ended_at),
+            duration_ms: summary.duration_ms(),
+            damage_dealt: summary.damage_dealt,
+            damage_taken: summary.damage_taken,
+            healing_done: summary.healing_done,
+            dps: summary.dps,
+            hps: summary.hps,
+            damage_sources: summary.damage_sources.into_iter().map(EncounterSource::from).collect(),
+            damage_taken_sources: summary.damage_taken_sources.into_iter().map(EncounterSource::from).collect(),
+            healing_sources: summary.healing_sources.into_iter().map(EncounterSource::from).collect(),
+            opponents: summary.opponents,
+        }
+    }
+}
+
 // Helper types
 
+#[derive(sqlx::FromRow)]
+struct CombatLogSettingsRow {
+    uuid: Uuid,
+    account_id: Option<i32>,
+    combat_log_enabled: bool,
+    combat_log_public: bool,
+}
+
+#[derive(sqlx::FromRow)]
+struct CharacterSheetRow {
+    id: i32,
+    uuid: Uuid,
+    name: String,
+    vocation: i16,
+    level: i32,
+    sheet_public: bool,
+    skill_fist: i32,
+    skill_club: i32,
+    skill_sword: i32,
+    skill_axe: i32,
+    skill_dist: i32,
+    skill_shielding: i32,
+    skill_fishing: i32,
+}
+
+#[derive(sqlx::FromRow)]
+struct SkillProgressRow {
+    id: i32,
+    name: String,
+    sheet_public: bool,
+    magic_level: i32,
+    mana_spent: i64,
+    skill_fist: i32,
+    skill_fist_tries: i64,
+    skill_club: i32,
+    skill_club_tries: i64,
+    skill_sword: i32,
+    skill_sword_tries: i64,
+    skill_axe: i32,
+    skill_axe_tries: i64,
+    skill_dist: i32,
+    skill_dist_tries: i64,
+    skill_shielding: i32,
+    skill_shielding_tries: i64,
+    skill_fishing: i32,
+    skill_fishing_tries: i64,
+}
+
+#[derive(sqlx::FromRow)]
+struct DeathRow {
+    level: i32,
+    killed_by: String,
+    is_player: Option<bool>,
+    mostdamage_by: Option<String>,
+    death_type: String,
+    experience_lost: i64,
+    unjustified: Option<bool>,
+    death_time: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<DeathRow> for DeathEntry {
+    fn from(row: DeathRow) -> Self {
+        DeathEntry {
+            level: row.level,
+            killed_by: row.killed_by,
+            is_player: row.is_player.unwrap_or(false),
+            most_damage_by: row.mostdamage_by,
+            death_type: row.death_type,
+            experience_lost: row.experience_lost,
+            unjustified: row.unjustified.unwrap_or(false),
+            died_at: row.death_time.map(|t| t.to_rfc3339()).unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct EquipmentRow {
+    id: Uuid,
+    item_id: i32,
+    name: String,
+    slot: i32,
+    attack: Option<i32>,
+    defense: Option<i32>,
+    armor: Option<i32>,
+    charges: Option<i32>,
+    duration: Option<i32>,
+    weapon_type: Option<String>,
+    item_group: Option<String>,
+}
+
 #[derive(sqlx::FromRow)]
 struct CharacterRow {
     id: i32,
@@ -330,3 +1200,28 @@ impl From<CharacterRow> for CharacterResponse {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shadow_combat::CombatLog;
+
+    #[test]
+    fn test_recorded_encounter_round_trips_to_entry() {
+        let mut log = CombatLog::default();
+        log.set_opt_in(1, true);
+        log.record_damage_dealt(1, "Great Fireball", DamageType::Fire, 800, "Demon", 1_700_000_000_000);
+        log.record_healing(1, "Ultimate Healing Rune", 400, 1_700_000_004_000);
+        let summary = log.end_encounter(1).unwrap();
+
+        // Summaries are stored as JSON and read back by the endpoint
+        let stored = serde_json::to_value(&summary).unwrap();
+        let entry = EncounterEntry::from(serde_json::from_value::<EncounterSummary>(stored).unwrap());
+        assert_eq!(entry.duration_ms, 4_000);
+        assert_eq!((entry.dps, entry.hps), (200.0, 100.0));
+        assert_eq!(entry.damage_sources[0].damage_type.as_deref(), Some("fire"));
+        assert_eq!(entry.healing_sources[0].source, "Ultimate Healing Rune");
+        assert_eq!(entry.opponents, vec!["Demon".to_string()]);
+        assert!(entry.started_at.starts_with("2023-11-14T22:13:20"));
+    }
+}
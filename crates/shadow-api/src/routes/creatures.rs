@@ -8,7 +8,10 @@ use axum::{
     Extension, Json,
 };
 use serde::{Deserialize, Serialize};
+use shadow_combat::{BestiaryElementConfig, BestiaryLootConfig, DamageType, LootEntry, LootPreview, LootTable};
+use shadow_core::cyclopedia::{bestiary_stage, recommend_charm_targets, BestiaryProgress};
 use sqlx::FromRow;
+use std::collections::HashMap;
 use std::sync::Arc;
 use utoipa::ToSchema;
 use uuid::Uuid;
@@ -78,6 +81,8 @@ pub struct LootItem {
     pub item_name: String,
     pub chance: f32,
     pub max_count: i32,
+    /// common, uncommon, semi-rare, rare or very rare
+    pub rarity: String,
 }
 
 #[derive(Debug, FromRow)]
@@ -97,6 +102,42 @@ pub struct BestiaryEntry {
     pub completed: bool,
     pub unlocked_loot: bool,
     pub unlocked_charm: bool,
+    /// Loot entries not revealed at the current stage
+    pub hidden_loot: i32,
+    /// Elemental modifiers, empty until revealed
+    pub elements: Vec<CreatureElement>,
+    pub elements_revealed: bool,
+}
+
+/// Elemental modifier shown in the bestiary
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CreatureElement {
+    /// Damage type, e.g. "Fire"
+    pub damage_type: String,
+    /// Damage taken as a percent of normal: above 100 for a weakness
+    pub damage_percent: i32,
+    /// weak, strong or immune
+    pub affinity: String,
+}
+
+/// A creature recommended for bestiary progress
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CharmRecommendationEntry {
+    pub creature_id: i32,
+    pub name: String,
+    pub difficulty: CreatureDifficulty,
+    pub kills: i32,
+    pub stage: i32,
+    pub kills_to_next_stage: i32,
+    pub kills_to_complete: i32,
+    pub charm_points: i32,
+    pub charm_points_per_kill: f64,
+}
+
+/// Charm recommendation query parameters
+#[derive(Debug, Deserialize)]
+pub struct CharmRecommendationQuery {
+    pub limit: Option<u32>,
 }
 
 /// Paginated creatures response
@@ -176,7 +217,7 @@ pub async fn list_creatures(
 
     let mut creatures = Vec::new();
     for row in rows {
-        let loot = load_creature_loot(&state, row.id).await?;
+        let loot = load_creature_loot(&state, row.id, &row.name).await?;
         creatures.push(Creature {
             id: row.id,
             name: row.name,
@@ -237,7 +278,7 @@ pub async fn get_creature(
     .await?
     .ok_or(crate::error::ApiError::NotFound("Creature not found".to_string()))?;
 
-    let loot = load_creature_loot(&state, row.id).await?;
+    let loot = load_creature_loot(&state, row.id, &row.name).await?;
 
     Ok(Json(Creature {
         id: row.id,
@@ -290,7 +331,7 @@ pub async fn get_creature_by_name(
     .await?
     .ok_or(crate::error::ApiError::NotFound("Creature not found".to_string()))?;
 
-    let loot = load_creature_loot(&state, row.id).await?;
+    let loot = load_creature_loot(&state, row.id, &row.name).await?;
 
     Ok(Json(Creature {
         id: row.id,
@@ -356,8 +397,11 @@ pub async fn get_bestiary_progress(
         .await?;
 
         if let Some(row) = creature_row {
-            let loot = load_creature_loot(&state, row.id).await?;
             let stage = calculate_bestiary_stage(kills, &row.bestiary_occurrence);
+            let preview = load_loot_preview(&state, row.id, &row.name, stage as u8).await?;
+            let hidden_loot = preview.hidden as i32;
+            let loot = loot_items(preview);
+            let elements = load_elements(&state, row.id, stage as u8).await?;
 
             entries.push(BestiaryEntry {
                 creature: Creature {
@@ -386,6 +430,9 @@ pub async fn get_bestiary_progress(
                 completed,
                 unlocked_loot,
                 unlocked_charm,
+                hidden_loot,
+                elements_revealed: elements.is_some(),
+                elements: elements.unwrap_or_default(),
             });
         }
     }
@@ -436,8 +483,11 @@ pub async fn get_bestiary_entry(
     .await?
     .ok_or(crate::error::ApiError::NotFound("Creature not found".to_string()))?;
 
-    let loot = load_creature_loot(&state, creature_row.id).await?;
     let stage = calculate_bestiary_stage(kills, &creature_row.bestiary_occurrence);
+    let preview = load_loot_preview(&state, creature_row.id, &creature_row.name, stage as u8).await?;
+    let hidden_loot = preview.hidden as i32;
+    let loot = loot_items(preview);
+    let elements = load_elements(&state, creature_row.id, stage as u8).await?;
 
     Ok(Json(BestiaryEntry {
         creature: Creature {
@@ -466,11 +516,100 @@ pub async fn get_bestiary_entry(
         completed,
         unlocked_loot,
         unlocked_charm,
+        hidden_loot,
+        elements_revealed: elements.is_some(),
+        elements: elements.unwrap_or_default(),
     }))
 }
 
+/// Get creatures closest to their next bestiary stage
+#[utoipa::path(
+    get,
+    path = "/api/v1/characters/{character_id}/bestiary/recommendations",
+    params(
+        ("character_id" = Uuid, Path, description = "Character ID"),
+        ("limit" = Option<u32>, Query, description = "Max results")
+    ),
+    responses(
+        (status = 200, description = "Creatures ranked by kills to their next stage", body = Vec<CharmRecommendationEntry>)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "creatures"
+)]
+pub async fn get_charm_recommendations(
+    State(state): State<Arc<AppState>>,
+    Extension(_claims): Extension<JwtClaims>,
+    Path(character_id): Path<Uuid>,
+    Query(query): Query<CharmRecommendationQuery>,
+) -> ApiResult<Json<Vec<CharmRecommendationEntry>>> {
+    let limit = query.limit.unwrap_or(10).min(50) as usize;
+
+    let rows = sqlx::query_as::<_, (i32, String, CreatureDifficulty, i32, String, i32)>(
+        "SELECT c.id, c.name, c.difficulty, bp.kills, c.bestiary_occurrence, c.charm_points
+         FROM bestiary_progress bp
+         JOIN creatures c ON c.id = bp.creature_id
+         WHERE bp.character_id = (SELECT id FROM characters WHERE uuid = $1)
+           AND NOT bp.completed
+           AND NOT c.is_boss"
+    )
+    .bind(character_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    let progress: Vec<BestiaryProgress> = rows
+        .iter()
+        .filter_map(|(id, _, _, kills, occurrence, charm_points)| {
+            Some(BestiaryProgress {
+                race_id: u16::try_from(*id).ok()?,
+                kills: (*kills).max(0) as u32,
+                occurrence: occurrence.clone(),
+                charm_points: (*charm_points).max(0) as u32,
+            })
+        })
+        .collect();
+    let rows: std::collections::HashMap<i32, _> = rows
+        .into_iter()
+        .map(|(id, name, difficulty, kills, _, charm_points)| (id, (name, difficulty, kills, charm_points)))
+        .collect();
+
+    let entries = recommend_charm_targets(&progress, limit)
+        .into_iter()
+        .filter_map(|rec| {
+            let creature_id = rec.race_id as i32;
+            let (name, difficulty, kills, charm_points) = rows.get(&creature_id)?;
+            Some(CharmRecommendationEntry {
+                creature_id,
+                name: name.clone(),
+                difficulty: *difficulty,
+                kills: *kills,
+                stage: rec.stage as i32,
+                kills_to_next_stage: rec.kills_to_next_stage as i32,
+                kills_to_complete: rec.kills_to_complete as i32,
+                charm_points: *charm_points,
+                charm_points_per_kill: rec.charm_points_per_kill,
+            })
+        })
+        .collect();
+
+    Ok(Json(entries))
+}
+
+/// Bestiary stage of a completed entry, at which all loot is revealed
+const BESTIARY_COMPLETE_STAGE: u8 = 4;
+
 /// Helper to load creature loot
-async fn load_creature_loot(state: &AppState, creature_id: i32) -> Result<Vec<LootItem>, sqlx::Error> {
+async fn load_creature_loot(state: &AppState, creature_id: i32, creature_name: &str) -> Result<Vec<LootItem>, sqlx::Error> {
+    let preview = load_loot_preview(state, creature_id, creature_name, BESTIARY_COMPLETE_STAGE).await?;
+    Ok(loot_items(preview))
+}
+
+/// Helper to load creature loot as revealed at a bestiary stage
+async fn load_loot_preview(
+    state: &AppState,
+    creature_id: i32,
+    creature_name: &str,
+    stage: u8,
+) -> Result<LootPreview, sqlx::Error> {
     let rows = sqlx::query_as::<_, LootItemRow>(
         "SELECT cl.item_id, i.name as item_name, cl.chance, cl.max_count
          FROM creature_loot cl
@@ -482,27 +621,124 @@ async fn load_creature_loot(state: &AppState, creature_id: i32) -> Result<Vec<Lo
     .fetch_all(&state.db)
     .await?;
 
-    Ok(rows.into_iter().map(|r| LootItem {
-        item_id: r.item_id,
-        item_name: r.item_name,
-        chance: r.chance,
-        max_count: r.max_count,
-    }).collect())
+    Ok(resolve_loot(creature_name, rows, stage, &state.config.bestiary_loot))
+}
+
+/// Build the creature's loot table from its rows and resolve what `stage` reveals
+fn resolve_loot(creature_name: &str, rows: Vec<LootItemRow>, stage: u8, config: &BestiaryLootConfig) -> LootPreview {
+    let table = rows
+        .into_iter()
+        .filter_map(|r| {
+            let max_count = r.max_count.clamp(1, u16::MAX as i32) as u16;
+            let entry = LootEntry::stackable(u16::try_from(r.item_id).ok()?, r.chance, 1, max_count);
+            Some(entry.with_name(r.item_name))
+        })
+        .fold(LootTable::new(creature_name), LootTable::add_entry);
+    config.preview(&table, stage, 1.0)
+}
+
+/// Loot list entries of a resolved preview
+fn loot_items(preview: LootPreview) -> Vec<LootItem> {
+    preview
+        .entries
+        .into_iter()
+        .map(|e| LootItem {
+            item_id: e.item_id as i32,
+            item_name: e.name.unwrap_or_default(),
+            chance: e.chance,
+            max_count: e.count_max as i32,
+            rarity: e.rarity.display_name().to_string(),
+        })
+        .collect()
+}
+
+/// Helper to load a creature's elemental modifiers as revealed at a
+/// bestiary stage; None while still hidden
+async fn load_elements(
+    state: &AppState,
+    creature_id: i32,
+    stage: u8,
+) -> Result<Option<Vec<CreatureElement>>, sqlx::Error> {
+    let elements = sqlx::query_scalar::<_, sqlx::types::Json<HashMap<DamageType, i32>>>(
+        "SELECT elements FROM creatures WHERE id = $1"
+    )
+    .bind(creature_id)
+    .fetch_optional(&state.db)
+    .await?
+    .map(|elements| elements.0)
+    .unwrap_or_default();
+
+    Ok(resolve_elements(&elements, stage, &state.config.bestiary_elements))
+}
+
+/// Elemental modifiers revealed at `stage`, from the same values combat uses
+fn resolve_elements(
+    elements: &HashMap<DamageType, i32>,
+    stage: u8,
+    config: &BestiaryElementConfig,
+) -> Option<Vec<CreatureElement>> {
+    let modifiers = config.revealed(elements, stage)?;
+    Some(
+        modifiers
+            .into_iter()
+            .map(|m| CreatureElement {
+                damage_type: format!("{:?}", m.damage_type),
+                damage_percent: m.damage_percent(),
+                affinity: m.affinity.display_name().to_string(),
+            })
+            .collect(),
+    )
 }
 
 /// Calculate bestiary stage based on kills
 fn calculate_bestiary_stage(kills: i32, occurrence: &str) -> i32 {
-    let thresholds = match occurrence.to_lowercase().as_str() {
-        "common" => [5, 250, 500, 1000],
-        "uncommon" => [5, 100, 250, 500],
-        "rare" => [1, 25, 50, 100],
-        "very rare" => [1, 5, 10, 25],
-        _ => [5, 250, 500, 1000],
-    };
-
-    if kills >= thresholds[3] { 4 }
-    else if kills >= thresholds[2] { 3 }
-    else if kills >= thresholds[1] { 2 }
-    else if kills >= thresholds[0] { 1 }
-    else { 0 }
+    bestiary_stage(kills.max(0) as u32, occurrence) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(item_id: i32, item_name: &str, chance: f32) -> LootItemRow {
+        LootItemRow { item_id, item_name: item_name.to_string(), chance, max_count: 1 }
+    }
+
+    #[test]
+    fn test_bestiary_loot_revealed_by_stage() {
+        let rows = || {
+            vec![
+                row(3577, "dragon ham", 30.0),
+                row(3416, "dragon shield", 3.0),
+                row(7430, "dragonbone staff", 0.05),
+            ]
+        };
+        let config = BestiaryLootConfig::default();
+
+        let partial = resolve_loot("Dragon", rows(), 1, &config);
+        assert_eq!(partial.hidden, 2);
+        let items = loot_items(partial);
+        assert_eq!(items.len(), 1);
+        assert_eq!((items[0].item_id, items[0].chance, items[0].rarity.as_str()), (3577, 30.0, "common"));
+
+        let complete = loot_items(resolve_loot("Dragon", rows(), BESTIARY_COMPLETE_STAGE, &config));
+        let chances: Vec<f32> = complete.iter().map(|i| i.chance).collect();
+        assert_eq!(chances, vec![30.0, 3.0, 0.05]);
+        assert_eq!(complete[2].rarity, "very rare");
+    }
+
+    #[test]
+    fn test_weakness_shown_after_kill_threshold() {
+        let elements = HashMap::from([(DamageType::Ice, -15), (DamageType::Fire, 100)]);
+        let config = BestiaryElementConfig::default();
+        let stage = |kills| calculate_bestiary_stage(kills, "Common") as u8;
+
+        assert!(resolve_elements(&elements, stage(5), &config).is_none());
+        assert!(resolve_elements(&elements, stage(249), &config).is_none());
+
+        let shown = resolve_elements(&elements, stage(250), &config).unwrap();
+        assert_eq!(shown.len(), 2);
+        assert_eq!((shown[0].damage_type.as_str(), shown[0].damage_percent), ("Ice", 115));
+        assert_eq!(shown[0].affinity, "weak");
+        assert_eq!(shown[1].affinity, "immune");
+    }
 }
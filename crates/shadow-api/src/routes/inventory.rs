@@ -1,6 +1,7 @@
 //! Inventory management endpoints
 
 use crate::auth::JwtClaims;
+use crate::routes::watchlist::notify_price_watchers;
 use crate::state::AppState;
 use crate::ApiResult;
 use axum::{
@@ -9,6 +10,9 @@ use axum::{
 };
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use shadow_core::vip::VipTier;
+use shadow_world::imbuement::{ImbuementSlotType, ImbuementTier, ImbuementType};
+use shadow_world::item::{SlotType, WeaponType};
 use sqlx::FromRow;
 use std::sync::Arc;
 use utoipa::ToSchema;
@@ -52,6 +56,52 @@ pub struct Imbuement {
     pub remaining_hours: f32,
 }
 
+/// Imbuement slot of an item, empty or holding an active imbuement
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImbuementSlot {
+    pub index: i32,
+    pub imbuement: Option<Imbuement>,
+}
+
+/// Creature product consumed by an imbuement
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImbuementMaterial {
+    pub item_id: i32,
+    pub count: i32,
+}
+
+/// Cost and odds of one imbuement tier
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImbuementTierOption {
+    pub tier: String,
+    pub gold_cost: i64,
+    /// Percent chance the imbuement takes
+    pub success_chance: f32,
+    pub duration_hours: i32,
+    pub removal_cost: i64,
+    pub materials: Vec<ImbuementMaterial>,
+}
+
+/// Imbuement the item accepts
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EligibleImbuement {
+    pub imbuement_type: String,
+    pub name: String,
+    pub category: String,
+    pub tiers: Vec<ImbuementTierOption>,
+}
+
+/// Imbuement dialog data of an item
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImbuementSlotsResponse {
+    pub inventory_id: Uuid,
+    pub item_id: i32,
+    /// `None` for items that cannot be imbued
+    pub slot_type: Option<String>,
+    pub slots: Vec<ImbuementSlot>,
+    pub eligible: Vec<EligibleImbuement>,
+}
+
 #[derive(Debug, FromRow)]
 struct InventoryItemRow {
     id: Uuid,
@@ -112,6 +162,38 @@ pub struct ListOnMarketResponse {
     pub message: String,
 }
 
+/// Storage capacity query parameters
+#[derive(Debug, Deserialize)]
+pub struct StorageQuery {
+    pub character_id: Uuid,
+}
+
+/// Depot and market inbox capacity of a character
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StorageCapacityResponse {
+    pub premium: bool,
+    pub depot_capacity: u32,
+    pub depot_used: u32,
+    pub depot_remaining: u32,
+    pub inbox_capacity: u32,
+    pub inbox_used: u32,
+    pub inbox_remaining: u32,
+}
+
+impl StorageCapacityResponse {
+    fn new(premium: bool, depot_capacity: u32, depot_used: u32, inbox_capacity: u32, inbox_used: u32) -> Self {
+        Self {
+            premium,
+            depot_capacity,
+            depot_used,
+            depot_remaining: depot_capacity.saturating_sub(depot_used),
+            inbox_capacity,
+            inbox_used,
+            inbox_remaining: inbox_capacity.saturating_sub(inbox_used),
+        }
+    }
+}
+
 /// Get inventory items
 #[utoipa::path(
     get,
@@ -456,6 +538,22 @@ pub async fn list_on_market(
 
     tx.commit().await?;
 
+    // Alert players watching this item for a lower price
+    let price = request.price;
+    if let Err(e) = notify_price_watchers(
+        &state.db,
+        item_id,
+        price,
+        &format!("{} price drop", item_name),
+        &format!("{} is offered for {} gold each ({} pieces)", item_name, price, list_count),
+        &format!("/market/items/{}", item_id),
+        serde_json::json!({ "offer_id": offer_id, "item_type_id": item_id, "price": price, "amount": list_count }),
+    )
+    .await
+    {
+        tracing::warn!("Failed to notify watchers of offer {}: {}", offer_id, e);
+    }
+
     Ok(Json(ListOnMarketResponse {
         success: true,
         offer_id,
@@ -463,13 +561,193 @@ pub async fn list_on_market(
     }))
 }
 
+/// Get depot and market inbox capacity
+#[utoipa::path(
+    get,
+    path = "/api/v1/inventory/storage",
+    params(
+        ("character_id" = Uuid, Query, description = "Character ID")
+    ),
+    responses(
+        (status = 200, description = "Storage capacity", body = StorageCapacityResponse)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "inventory"
+)]
+pub async fn get_storage_capacity(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<JwtClaims>,
+    Query(query): Query<StorageQuery>,
+) -> ApiResult<Json<StorageCapacityResponse>> {
+    let row: Option<(i32, Option<DateTime<Utc>>)> = sqlx::query_as(
+        "SELECT c.id, a.premium_until
+         FROM characters c
+         JOIN accounts a ON a.id = c.account_id
+         WHERE c.uuid = $1 AND c.account_id = $2"
+    )
+    .bind(query.character_id)
+    .bind(claims.account_id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    let (char_id, premium_until) = row
+        .ok_or(crate::error::ApiError::NotFound("Character not found".to_string()))?;
+
+    let (depot_used, inbox_used): (i64, i64) = sqlx::query_as(
+        "SELECT
+            (SELECT COALESCE(SUM(count), 0) FROM player_depot_items WHERE character_id = $1),
+            (SELECT COALESCE(SUM(count), 0) FROM player_inbox WHERE character_id = $1)"
+    )
+    .bind(char_id)
+    .fetch_one(&state.db)
+    .await?;
+
+    // Web premium grants the base VIP tier's storage
+    let premium = premium_until.map(|t| t > Utc::now()).unwrap_or(false);
+    let tier = if premium { VipTier::Bronze } else { VipTier::None };
+    let capacity = &state.config.storage_capacity;
+
+    Ok(Json(StorageCapacityResponse::new(
+        premium,
+        capacity.depot_capacity(tier),
+        depot_used.clamp(0, u32::MAX as i64) as u32,
+        capacity.inbox_capacity(tier),
+        inbox_used.clamp(0, u32::MAX as i64) as u32,
+    )))
+}
+
+/// Imbuement slots of an inventory item
+#[utoipa::path(
+    get,
+    path = "/api/v1/inventory/{id}/imbuements",
+    params(
+        ("id" = Uuid, Path, description = "Inventory item ID")
+    ),
+    responses(
+        (status = 200, description = "Imbuement slots, eligible imbuements and costs", body = ImbuementSlotsResponse),
+        (status = 404, description = "Item not found")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "inventory"
+)]
+pub async fn get_imbuement_slots(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<JwtClaims>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<ImbuementSlotsResponse>> {
+    let (item_id, slot_type, weapon_type, slot_count): (i32, Option<String>, Option<String>, Option<i16>) = sqlx::query_as(
+        "SELECT i.item_id, it.slot_type::text, it.weapon_type::text, it.imbuement_slots
+         FROM character_inventory i
+         JOIN items it ON it.id = i.item_id
+         JOIN characters c ON c.uuid = i.character_id
+         WHERE i.id = $1 AND c.account_id = $2"
+    )
+    .bind(id)
+    .bind(claims.account_id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or(crate::error::ApiError::NotFound("Item not found".to_string()))?;
+
+    let weapon = parse_weapon_type(weapon_type.as_deref());
+    let slot_type = ImbuementSlotType::for_item(parse_slot_type(slot_type.as_deref()), weapon);
+    let active = load_imbuements(&state, id).await?;
+    let (slots, eligible) = imbuement_slots(slot_type, weapon, slot_count, active);
+
+    Ok(Json(ImbuementSlotsResponse {
+        inventory_id: id,
+        item_id,
+        slot_type: slot_type.map(|slot| format!("{:?}", slot)),
+        slots,
+        eligible,
+    }))
+}
+
+/// Slots of an item and the imbuements it accepts. Active imbuements fill
+/// the slots in the order they were applied.
+fn imbuement_slots(
+    slot_type: Option<ImbuementSlotType>,
+    weapon: Option<WeaponType>,
+    slot_count: Option<i16>,
+    active: Vec<Imbuement>,
+) -> (Vec<ImbuementSlot>, Vec<EligibleImbuement>) {
+    let Some(slot_type) = slot_type else {
+        return (Vec::new(), Vec::new());
+    };
+
+    let count = slot_count.map_or(slot_type.default_slot_count() as i32, i32::from).max(0);
+    let mut active = active.into_iter();
+    let slots = (0..count)
+        .map(|index| ImbuementSlot { index, imbuement: active.next() })
+        .collect();
+
+    let eligible = slot_type
+        .eligible_imbuements(weapon)
+        .into_iter()
+        .map(|imbuement| EligibleImbuement {
+            imbuement_type: format!("{:?}", imbuement),
+            name: imbuement.display_name().to_string(),
+            category: format!("{:?}", imbuement.category()),
+            tiers: ImbuementTier::ALL.iter().map(|&tier| tier_option(imbuement, tier)).collect(),
+        })
+        .collect();
+
+    (slots, eligible)
+}
+
+fn tier_option(imbuement: ImbuementType, tier: ImbuementTier) -> ImbuementTierOption {
+    let gold_cost = tier.gold_cost() as i64;
+    ImbuementTierOption {
+        tier: format!("{:?}", tier),
+        gold_cost,
+        success_chance: tier.base_success_rate(),
+        duration_hours: tier.duration_hours() as i32,
+        removal_cost: gold_cost * tier.removal_cost_percent() as i64 / 100,
+        materials: imbuement
+            .required_products(tier)
+            .into_iter()
+            .map(|product| ImbuementMaterial { item_id: product.item_id as i32, count: product.count as i32 })
+            .collect(),
+    }
+}
+
+fn parse_weapon_type(value: Option<&str>) -> Option<WeaponType> {
+    match value?.to_ascii_lowercase().as_str() {
+        "sword" => Some(WeaponType::Sword),
+        "club" => Some(WeaponType::Club),
+        "axe" => Some(WeaponType::Axe),
+        "distance" => Some(WeaponType::Distance),
+        "wand" => Some(WeaponType::Wand),
+        "shield" => Some(WeaponType::Shield),
+        "ammunition" | "ammo" => Some(WeaponType::Ammunition),
+        _ => None,
+    }
+}
+
+fn parse_slot_type(value: Option<&str>) -> Option<SlotType> {
+    match value?.to_ascii_lowercase().as_str() {
+        "head" => Some(SlotType::Head),
+        "necklace" => Some(SlotType::Necklace),
+        "backpack" => Some(SlotType::Backpack),
+        "armor" | "body" => Some(SlotType::Armor),
+        "right" | "right-hand" => Some(SlotType::Right),
+        "left" | "left-hand" => Some(SlotType::Left),
+        "legs" => Some(SlotType::Legs),
+        "feet" => Some(SlotType::Feet),
+        "ring" => Some(SlotType::Ring),
+        "ammo" => Some(SlotType::Ammo),
+        "two-handed" | "twohanded" | "two_handed" => Some(SlotType::TwoHanded),
+        _ => None,
+    }
+}
+
 /// Helper to load item imbuements
 async fn load_imbuements(state: &AppState, inventory_id: Uuid) -> Result<Vec<Imbuement>, sqlx::Error> {
     let rows: Vec<(i32, String, i32, f32)> = sqlx::query_as(
         "SELECT imb.id, imb.name, ii.tier, ii.remaining_hours
          FROM inventory_imbuements ii
          JOIN imbuements imb ON imb.id = ii.imbuement_id
-         WHERE ii.inventory_id = $1"
+         WHERE ii.inventory_id = $1
+         ORDER BY ii.applied_at, ii.id"
     )
     .bind(inventory_id)
     .fetch_all(&state.db)
@@ -482,3 +760,49 @@ async fn load_imbuements(state: &AppState, inventory_id: Uuid) -> Result<Vec<Imb
         remaining_hours,
     }).collect())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(eligible: &[EligibleImbuement]) -> Vec<&str> {
+        eligible.iter().map(|e| e.imbuement_type.as_str()).collect()
+    }
+
+    #[test]
+    fn test_sword_slots_and_eligible_imbuements() {
+        let weapon = parse_weapon_type(Some("sword"));
+        let slot_type = ImbuementSlotType::for_item(parse_slot_type(Some("right")), weapon);
+        assert_eq!(slot_type, Some(ImbuementSlotType::Weapon));
+
+        let active = vec![Imbuement { id: 2, name: "Vampirism".to_string(), tier: 1, remaining_hours: 12.5 }];
+        let (slots, eligible) = imbuement_slots(slot_type, weapon, None, active);
+        assert_eq!(slots.len(), 3);
+        assert_eq!(slots[0].imbuement.as_ref().map(|i| i.id), Some(2));
+        assert!(slots[1].imbuement.is_none() && slots[2].index == 2);
+
+        let types = names(&eligible);
+        assert!(types.contains(&"Slash") && types.contains(&"Scorch") && types.contains(&"Vampirism"));
+        assert!(!types.contains(&"Chop") && !types.contains(&"LichShroud"));
+
+        let vampirism = eligible.iter().find(|e| e.imbuement_type == "Vampirism").unwrap();
+        assert_eq!(vampirism.tiers.len(), 3);
+        assert_eq!((vampirism.tiers[0].gold_cost, vampirism.tiers[0].success_chance), (15_000, 90.0));
+        assert_eq!(vampirism.tiers[0].removal_cost, 3_750);
+        assert_eq!(vampirism.tiers[2].materials[0].item_id, 10605);
+    }
+
+    #[test]
+    fn test_item_type_decides_slots() {
+        // Boots only take movement imbuements, and the item type sets the count
+        let boots = ImbuementSlotType::for_item(parse_slot_type(Some("feet")), None);
+        let (slots, eligible) = imbuement_slots(boots, None, Some(2), Vec::new());
+        assert_eq!(slots.len(), 2);
+        assert_eq!(names(&eligible), vec!["SwiftnessBoots", "Vibrancy"]);
+
+        // Rings have no imbuement slots
+        let ring = ImbuementSlotType::for_item(parse_slot_type(Some("ring")), None);
+        let (slots, eligible) = imbuement_slots(ring, None, None, Vec::new());
+        assert!(slots.is_empty() && eligible.is_empty());
+    }
+}
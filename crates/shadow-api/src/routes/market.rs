@@ -1,11 +1,19 @@
 //! Market endpoints
 
+use crate::error::ApiError;
+use crate::middleware::get_claims;
 use crate::state::AppState;
 use crate::ApiResult;
-use axum::{extract::{Path, Query, State}, Json};
+use axum::{extract::{Path, Query, Request, State}, Json};
 use serde::{Deserialize, Serialize};
+use shadow_core::trade::{average_trade_price, ItemCategory, MarketHistory as TradeRecord, NetWorth};
+use std::collections::HashMap;
 use std::sync::Arc;
 use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Trades per item averaged when valuing holdings
+const NET_WORTH_PRICE_WINDOW: usize = 20;
 
 /// Market offer
 #[derive(Debug, Serialize, ToSchema)]
@@ -160,6 +168,204 @@ pub async fn get_history(
     }).collect()))
 }
 
+/// A trade the character took part in
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacterTrade {
+    pub item_type: i32,
+    pub amount: i32,
+    pub price: i64,
+    /// "buy" or "sell"
+    pub side: String,
+    pub completed_at: String,
+}
+
+/// Value of the items of one category
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NetWorthCategory {
+    pub category: String,
+    pub value: i64,
+}
+
+/// Net worth and market history of a character
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NetWorthResponse {
+    pub character_id: i32,
+    /// Carried gold plus bank balance
+    pub gold: i64,
+    /// Held items at their average market price
+    pub items_value: i64,
+    pub total: i64,
+    pub by_category: Vec<NetWorthCategory>,
+    /// Held item types that were never traded and count as 0
+    pub unpriced_items: Vec<i32>,
+    /// Most recent trades first
+    pub history: Vec<CharacterTrade>,
+}
+
+/// Get a character's net worth and market history
+#[utoipa::path(
+    get,
+    path = "/api/v1/characters/{id}/networth",
+    params(
+        ("id" = i32, Path, description = "Character ID")
+    ),
+    responses(
+        (status = 200, description = "Net worth by category and recent trades", body = NetWorthResponse),
+        (status = 404, description = "Character not found")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "market"
+)]
+pub async fn get_character_net_worth(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    request: Request,
+) -> ApiResult<Json<NetWorthResponse>> {
+    let claims = get_claims(&request).ok_or(ApiError::Unauthorized)?;
+
+    let character: (Uuid, Option<i32>, i64) = sqlx::query_as(
+        "SELECT uuid, realm_id, COALESCE(balance, 0) + COALESCE(bank_balance, 0)
+         FROM characters
+         WHERE id = $1 AND account_id = $2 AND deletion_time IS NULL"
+    )
+    .bind(id)
+    .bind(claims.account_id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or(ApiError::NotFound("Character not found".to_string()))?;
+    let (uuid, realm_id, gold) = character;
+
+    // Carried items and depot items, with the category fields of their type
+    let held: Vec<(i32, i64, Option<String>, Option<String>)> = sqlx::query_as(
+        "SELECT h.item_id, SUM(h.count)::BIGINT, it.weapon_type::text, it.\"group\"::text
+         FROM (
+             SELECT item_id, count FROM character_inventory WHERE character_id = $1
+             UNION ALL
+             SELECT itemtype, count FROM player_depot_items WHERE character_id = $2
+         ) h
+         LEFT JOIN items it ON it.id = h.item_id
+         GROUP BY h.item_id, it.weapon_type, it.\"group\""
+    )
+    .bind(uuid)
+    .bind(id)
+    .fetch_all(&state.db)
+    .await?;
+
+    let item_types: Vec<i32> = held.iter().map(|(item_type, ..)| *item_type).collect();
+    let trades: Vec<(i32, i32, i64)> = sqlx::query_as(
+        "SELECT item_type, amount, price
+         FROM market_history
+         WHERE item_type = ANY($1) AND ($2::int IS NULL OR realm_id = $2)
+         ORDER BY completed_at ASC"
+    )
+    .bind(&item_types)
+    .bind(realm_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    let holdings: HashMap<u16, u32> = held
+        .iter()
+        .map(|(item_type, count, ..)| (*item_type as u16, (*count).clamp(0, u32::MAX as i64) as u32))
+        .collect();
+    let categories: HashMap<u16, ItemCategory> = held
+        .iter()
+        .map(|(item_type, _, weapon_type, group)| {
+            (*item_type as u16, market_category(weapon_type.as_deref(), group.as_deref()))
+        })
+        .collect();
+    let worth = net_worth(gold.max(0) as u64, &holdings, &trades_to_records(&trades), &categories);
+
+    let history = sqlx::query_as::<_, CharacterTradeRow>(
+        "SELECT item_type, amount, price, buyer_id, completed_at
+         FROM market_history
+         WHERE buyer_id = $1 OR seller_id = $1
+         ORDER BY completed_at DESC
+         LIMIT 50"
+    )
+    .bind(id)
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut by_category: Vec<NetWorthCategory> = worth
+        .by_category
+        .iter()
+        .map(|(category, value)| NetWorthCategory {
+            category: format!("{:?}", category),
+            value: *value as i64,
+        })
+        .collect();
+    by_category.sort_by(|a, b| b.value.cmp(&a.value));
+
+    Ok(Json(NetWorthResponse {
+        character_id: id,
+        gold: worth.gold as i64,
+        items_value: worth.items_value as i64,
+        total: worth.total as i64,
+        by_category,
+        unpriced_items: worth.unpriced.iter().map(|&item_type| item_type as i32).collect(),
+        history: history.into_iter().map(|h| CharacterTrade {
+            item_type: h.item_type,
+            amount: h.amount,
+            price: h.price,
+            side: if h.buyer_id == Some(id) { "buy" } else { "sell" }.to_string(),
+            completed_at: h.completed_at.to_rfc3339(),
+        }).collect(),
+    }))
+}
+
+/// Market history rows as trade records for the shared price statistics
+fn trades_to_records(trades: &[(i32, i32, i64)]) -> Vec<TradeRecord> {
+    trades
+        .iter()
+        .map(|&(item_type, amount, price)| TradeRecord {
+            item_type_id: item_type as u16,
+            amount: amount.max(0) as u32,
+            price: price.clamp(0, u32::MAX as i64) as u32,
+            buyer_id: Uuid::nil(),
+            seller_id: Uuid::nil(),
+            timestamp: chrono::Utc::now(),
+            fee: 0,
+        })
+        .collect()
+}
+
+/// Value holdings at the moving average of their recent trades
+fn net_worth(
+    gold: u64,
+    holdings: &HashMap<u16, u32>,
+    history: &[TradeRecord],
+    categories: &HashMap<u16, ItemCategory>,
+) -> NetWorth {
+    NetWorth::compute(
+        gold,
+        holdings,
+        |item_type| average_trade_price(history, item_type, NET_WORTH_PRICE_WINDOW),
+        |item_type| categories.get(&item_type).copied().unwrap_or(ItemCategory::Other),
+    )
+}
+
+/// Market category of an item type from its weapon type and item group
+fn market_category(weapon_type: Option<&str>, item_group: Option<&str>) -> ItemCategory {
+    match (weapon_type, item_group) {
+        (Some(_), _) | (_, Some("ammunition")) => ItemCategory::Weapons,
+        (_, Some("armor" | "shield")) => ItemCategory::Armor,
+        (_, Some("fluid" | "charges")) => ItemCategory::Consumables,
+        _ => ItemCategory::Other,
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct CharacterTradeRow {
+    item_type: i32,
+    amount: i32,
+    price: i64,
+    buyer_id: Option<i32>,
+    completed_at: chrono::DateTime<chrono::Utc>,
+}
+
 #[derive(sqlx::FromRow)]
 struct MarketOfferRow {
     id: i32,
@@ -180,3 +386,25 @@ struct MarketHistoryRow {
     seller_name: Option<String>,
     completed_at: chrono::DateTime<chrono::Utc>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_net_worth_sums_holdings_at_average_prices() {
+        let trades = vec![(2160, 1, 9_000), (2160, 2, 11_000), (3366, 1, 120_000), (3031, 100, 1)];
+        let holdings = HashMap::from([(2160, 4), (3366, 1), (3031, 250), (9999, 3)]);
+        let categories = HashMap::from([
+            (3366, market_category(None, Some("armor"))),
+            (2160, market_category(None, None)),
+        ]);
+
+        let worth = net_worth(5_000, &holdings, &trades_to_records(&trades), &categories);
+        assert_eq!(worth.items_value, 4 * 10_000 + 120_000 + 250);
+        assert_eq!(worth.total, 5_000 + 160_250);
+        assert_eq!(worth.by_category[&ItemCategory::Armor], 120_000);
+        assert_eq!(worth.by_category[&ItemCategory::Other], 40_250);
+        assert_eq!(worth.unpriced, vec![9999]);
+    }
+}
@@ -25,3 +25,4 @@ pub mod events;
 pub mod nft;
 pub mod premium;
 pub mod notifications;
+pub mod watchlist;
@@ -155,6 +155,26 @@ pub struct BuyNftRequest {
     pub nft_id: Uuid,
 }
 
+/// Bridge quote query parameters
+#[derive(Debug, Deserialize)]
+pub struct BridgeQuoteQuery {
+    pub source: String,
+    pub target: String,
+    /// Declared value of the asset in the source chain's smallest unit
+    pub amount: u64,
+}
+
+/// Bridge fee quote response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BridgeQuoteResponse {
+    pub source: String,
+    pub target: String,
+    pub amount: u64,
+    pub fee_amount: u64,
+    pub fee_bps: u16,
+    pub estimated_time_secs: u64,
+}
+
 /// Paginated NFT response
 #[derive(Debug, Serialize, ToSchema)]
 pub struct PaginatedNfts {
@@ -577,6 +597,43 @@ pub async fn cancel_listing(
     Ok(Json(SuccessResponse::ok("Listing cancelled")))
 }
 
+/// Quote the fee for bridging an asset between chains
+#[utoipa::path(
+    get,
+    path = "/api/v1/nft/bridge/quote",
+    params(
+        ("source" = String, Query, description = "Source chain"),
+        ("target" = String, Query, description = "Target chain"),
+        ("amount" = u64, Query, description = "Declared asset value")
+    ),
+    responses(
+        (status = 200, description = "Bridge quote", body = BridgeQuoteResponse)
+    ),
+    tag = "nft"
+)]
+pub async fn get_bridge_quote(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<BridgeQuoteQuery>,
+) -> ApiResult<Json<BridgeQuoteResponse>> {
+    let bad_chain = |e: shadow_blockchain::BlockchainError| crate::error::ApiError::BadRequest(e.to_string());
+    let source: shadow_blockchain::Chain = query.source.parse().map_err(bad_chain)?;
+    let target: shadow_blockchain::Chain = query.target.parse().map_err(bad_chain)?;
+
+    let quote = state
+        .bridge
+        .quote(source, target, query.amount)
+        .map_err(|e| crate::error::ApiError::BadRequest(e.to_string()))?;
+
+    Ok(Json(BridgeQuoteResponse {
+        source: query.source.to_lowercase(),
+        target: query.target.to_lowercase(),
+        amount: quote.amount,
+        fee_amount: quote.fee_amount,
+        fee_bps: quote.fee_bps,
+        estimated_time_secs: quote.estimated_time_secs,
+    }))
+}
+
 /// Helper to build NFT from row
 fn build_nft(row: NftRow) -> Nft {
     Nft {
@@ -0,0 +1,247 @@
+//! Market and auction watchlist endpoints
+
+use crate::auth::JwtClaims;
+use crate::response::SuccessResponse;
+use crate::routes::notifications::NotificationType;
+use crate::state::AppState;
+use crate::ApiResult;
+use axum::{
+    extract::{Path, State},
+    Extension, Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use shadow_core::watchlist::{self, WatchCondition};
+use sqlx::{FromRow, PgPool};
+use std::sync::Arc;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Maximum watches per account
+const MAX_WATCHES: i64 = 20;
+
+/// Watchlist entry
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WatchEntry {
+    pub id: Uuid,
+    #[schema(value_type = Object)]
+    pub condition: WatchCondition,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, FromRow)]
+struct WatchRow {
+    id: Uuid,
+    condition: sqlx::types::Json<WatchCondition>,
+    created_at: DateTime<Utc>,
+}
+
+/// Create watch request
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateWatchRequest {
+    #[schema(value_type = Object)]
+    pub condition: WatchCondition,
+}
+
+/// Get the user's watchlist
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/me/watchlist",
+    responses(
+        (status = 200, description = "User watchlist", body = Vec<WatchEntry>)
+    ),
+    security(("bearer_auth" = [])),
+    tag = "notifications"
+)]
+pub async fn get_watchlist(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<JwtClaims>,
+) -> ApiResult<Json<Vec<WatchEntry>>> {
+    let rows: Vec<WatchRow> = sqlx::query_as(
+        "SELECT id, condition, created_at FROM market_watchlist
+         WHERE account_id = $1
+         ORDER BY created_at"
+    )
+    .bind(claims.account_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(rows.into_iter().map(|r| WatchEntry {
+        id: r.id,
+        condition: r.condition.0,
+        created_at: r.created_at,
+    }).collect()))
+}
+
+/// Add a watch
+#[utoipa::path(
+    post,
+    path = "/api/v1/users/me/watchlist",
+    request_body = CreateWatchRequest,
+    responses(
+        (status = 201, description = "Watch created", body = WatchEntry),
+        (status = 400, description = "Invalid condition or watchlist full")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "notifications"
+)]
+pub async fn create_watch(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<JwtClaims>,
+    Json(req): Json<CreateWatchRequest>,
+) -> ApiResult<Json<WatchEntry>> {
+    if !req.condition.is_valid() {
+        return Err(crate::error::ApiError::BadRequest("Invalid watch condition".to_string()));
+    }
+
+    let count: (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM market_watchlist WHERE account_id = $1"
+    )
+    .bind(claims.account_id)
+    .fetch_one(&state.db)
+    .await?;
+
+    if count.0 >= MAX_WATCHES {
+        return Err(crate::error::ApiError::BadRequest("Watchlist is full".to_string()));
+    }
+
+    let row: WatchRow = sqlx::query_as(
+        "INSERT INTO market_watchlist (account_id, condition)
+         VALUES ($1, $2)
+         RETURNING id, condition, created_at"
+    )
+    .bind(claims.account_id)
+    .bind(sqlx::types::Json(&req.condition))
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok(Json(WatchEntry {
+        id: row.id,
+        condition: row.condition.0,
+        created_at: row.created_at,
+    }))
+}
+
+/// Remove a watch
+#[utoipa::path(
+    delete,
+    path = "/api/v1/users/me/watchlist/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Watch ID")
+    ),
+    responses(
+        (status = 200, description = "Watch removed")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "notifications"
+)]
+pub async fn delete_watch(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<JwtClaims>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<SuccessResponse>> {
+    sqlx::query(
+        "DELETE FROM market_watchlist WHERE id = $1 AND account_id = $2"
+    )
+    .bind(id)
+    .bind(claims.account_id)
+    .execute(&state.db)
+    .await?;
+
+    Ok(Json(SuccessResponse::ok("Watch removed")))
+}
+
+/// Notify every account whose watch of `kind` matches a new auction
+pub(crate) async fn notify_watchers(
+    db: &PgPool,
+    kind: &str,
+    matches: impl Fn(&WatchCondition) -> bool,
+    title: &str,
+    message: &str,
+    action_url: &str,
+    data: serde_json::Value,
+) -> Result<(), sqlx::Error> {
+    let watches: Vec<(i32, sqlx::types::Json<WatchCondition>)> = sqlx::query_as(
+        "SELECT account_id, condition FROM market_watchlist WHERE condition->>'kind' = $1"
+    )
+    .bind(kind)
+    .fetch_all(db)
+    .await?;
+
+    for (account_id, condition) in watches {
+        if matches(&condition.0) {
+            insert_notification(db, account_id, title, message, action_url, &data).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Notify every account watching an item for a sell offer at `price`.
+/// Each watch alerts once per price crossing: it stores the price it
+/// alerted at and only alerts again when the price drops below it.
+pub(crate) async fn notify_price_watchers(
+    db: &PgPool,
+    item_type_id: i32,
+    price: i64,
+    title: &str,
+    message: &str,
+    action_url: &str,
+    data: serde_json::Value,
+) -> Result<(), sqlx::Error> {
+    // Out of range for any watch condition
+    let (Ok(item), Ok(offer_price)) = (u16::try_from(item_type_id), u32::try_from(price)) else {
+        return Ok(());
+    };
+
+    let watches: Vec<(Uuid, i32, sqlx::types::Json<WatchCondition>, Option<i64>)> = sqlx::query_as(
+        "SELECT id, account_id, condition, last_alert_price FROM market_watchlist WHERE condition->>'kind' = 'item_price'"
+    )
+    .fetch_all(db)
+    .await?;
+
+    for (id, account_id, condition, last_alert_price) in watches {
+        let last_alert_price = last_alert_price.map(|last| u32::try_from(last).unwrap_or(u32::MAX));
+        if !condition.0.matches_item_price(item, offer_price)
+            || !watchlist::is_new_price_alert(last_alert_price, offer_price)
+        {
+            continue;
+        }
+
+        // Conditional so that concurrent offers alert a watch only once
+        let claimed = sqlx::query(
+            "UPDATE market_watchlist SET last_alert_price = $2
+             WHERE id = $1 AND (last_alert_price IS NULL OR last_alert_price > $2)"
+        )
+        .bind(id)
+        .bind(price)
+        .execute(db)
+        .await?;
+        if claimed.rows_affected() == 1 {
+            insert_notification(db, account_id, title, message, action_url, &data).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn insert_notification(
+    db: &PgPool,
+    account_id: i32,
+    title: &str,
+    message: &str,
+    action_url: &str,
+    data: &serde_json::Value,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO notifications (account_id, notification_type, title, message, action_url, data)
+         VALUES ($1, $2, $3, $4, $5, $6)"
+    )
+    .bind(account_id)
+    .bind(NotificationType::Market)
+    .bind(title)
+    .bind(message)
+    .bind(action_url)
+    .bind(data)
+    .execute(db)
+    .await?;
+    Ok(())
+}
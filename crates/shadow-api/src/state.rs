@@ -1,7 +1,13 @@
 //! Application state shared across handlers
 
 use crate::auth::AuthConfig;
+use crate::domain::StartingKitConfig;
+use crate::routes::auction::{AntiSnipeConfig, AuctionHub};
 use redis::aio::ConnectionManager;
+use shadow_combat::{BestiaryElementConfig, BestiaryLootConfig, CombatLogConfig};
+use shadow_blockchain::chains::starknet::StarknetChainConfig;
+use shadow_blockchain::{BridgeService, BridgeServiceConfig};
+use shadow_core::vip::StorageCapacity;
 use sqlx::PgPool;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -17,6 +23,10 @@ pub struct AppState {
     pub cache: Option<Arc<RwLock<CacheState>>>,
     /// Server configuration
     pub config: ServerConfig,
+    /// Cross-chain bridge service (quotes, fee accounting)
+    pub bridge: Arc<BridgeService>,
+    /// Live auction update channels
+    pub auction_hub: Arc<AuctionHub>,
 }
 
 impl AppState {
@@ -26,6 +36,8 @@ impl AppState {
             auth_config,
             cache: None,
             config,
+            bridge: Arc::new(BridgeService::new(BridgeServiceConfig::default())),
+            auction_hub: Arc::new(AuctionHub::new()),
         }
     }
 
@@ -33,6 +45,11 @@ impl AppState {
         self.cache = Some(Arc::new(RwLock::new(cache)));
         self
     }
+
+    pub fn with_bridge_config(mut self, config: BridgeServiceConfig) -> Self {
+        self.bridge = Arc::new(BridgeService::new(config));
+        self
+    }
 }
 
 /// Server configuration
@@ -45,6 +62,20 @@ pub struct ServerConfig {
     pub max_characters_per_account: u8,
     pub character_deletion_days: u8,
     pub premium_features_enabled: bool,
+    /// Depot and market inbox capacity per premium tier
+    pub storage_capacity: StorageCapacity,
+    /// Items, gold, spells and tutorial state of new characters
+    pub starting_kits: StartingKitConfig,
+    /// Encounters kept per character in the combat log
+    pub combat_log: CombatLogConfig,
+    /// Starknet JSON-RPC endpoint used to check wallet login signatures
+    pub starknet_rpc_url: String,
+    /// Auction end extension for last-second bids
+    pub anti_snipe: AntiSnipeConfig,
+    /// Bestiary stage at which each loot rarity is revealed
+    pub bestiary_loot: BestiaryLootConfig,
+    /// Bestiary stage at which elemental modifiers are revealed
+    pub bestiary_elements: BestiaryElementConfig,
 }
 
 impl Default for ServerConfig {
@@ -57,6 +88,13 @@ impl Default for ServerConfig {
             max_characters_per_account: 10,
             character_deletion_days: 30,
             premium_features_enabled: true,
+            storage_capacity: StorageCapacity::default(),
+            starting_kits: StartingKitConfig::default(),
+            combat_log: CombatLogConfig::default(),
+            starknet_rpc_url: StarknetChainConfig::default().rpc_url,
+            anti_snipe: AntiSnipeConfig::default(),
+            bestiary_loot: BestiaryLootConfig::default(),
+            bestiary_elements: BestiaryElementConfig::default(),
         }
     }
 }
@@ -1,6 +1,15 @@
 //! Cross-Chain Bridge Module
 //!
 //! Handles bridging assets between different blockchains.
+//!
+//! A bridge locks the asset on the source chain and then mints it on the
+//! target chain. If the process stops in between, `resume_pending` picks
+//! the transfer up again: each step first checks the chain for whether it
+//! already happened, so a resumed bridge never locks or mints twice. A
+//! bridge that has not minted within `timeout_secs` is rolled back by
+//! unlocking the asset on the source chain. A bridge that may have minted
+//! but whose target chain cannot tell, or whose lock is gone, is flagged
+//! for manual review and never minted or unlocked automatically.
 
 pub mod queue;
 pub mod verifier;
@@ -11,7 +20,8 @@ pub use verifier::{BridgeVerifier, VerificationResult};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::{AssetType, BridgeRequest, BridgeStatus, Chain, Result, BlockchainError};
+use crate::wallet::normalize_address;
+use crate::{bridged_metadata, AssetType, BlockchainService, BridgeRequest, BridgeStatus, Chain, ChainProvider, Result, BlockchainError};
 
 /// Configuration for the bridge service
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,6 +36,9 @@ pub struct BridgeConfig {
     pub max_pending_per_user: usize,
     /// Bridge timeout in seconds
     pub timeout_secs: u64,
+    /// Address bridge fees are paid to
+    #[serde(default)]
+    pub treasury_address: Option<String>,
 }
 
 impl Default for BridgeConfig {
@@ -46,6 +59,7 @@ impl Default for BridgeConfig {
             fee_bps: 50, // 0.5%
             max_pending_per_user: 5,
             timeout_secs: 3600, // 1 hour
+            treasury_address: None,
         }
     }
 }
@@ -81,6 +95,61 @@ impl BridgeRoute {
         self.enabled = false;
         self
     }
+
+    /// Reject values outside the route's limits
+    pub fn check_value(&self, value: u64) -> Result<()> {
+        let below = self.min_amount.is_some_and(|min| value < min);
+        let above = self.max_amount.is_some_and(|max| value > max);
+        if below || above {
+            return Err(BlockchainError::BridgeValueOutOfRange {
+                value,
+                min: self.min_amount,
+                max: self.max_amount,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Fee and timing estimate for a bridge before it is initiated
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BridgeQuote {
+    pub source: Chain,
+    pub target: Chain,
+    /// Declared value of the asset being bridged (smallest unit)
+    pub amount: u64,
+    /// Fee charged on the declared value
+    pub fee_amount: u64,
+    /// Fee rate applied, in basis points
+    pub fee_bps: u16,
+    /// Estimated time until the asset is available on the target chain
+    pub estimated_time_secs: u64,
+}
+
+/// Fee and timing estimate for a bridge over one or more hops
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteQuote {
+    pub source: Chain,
+    pub target: Chain,
+    /// Routes to bridge through, in order
+    pub hops: Vec<BridgeRoute>,
+    pub amount: u64,
+    /// Fees of all hops together
+    pub fee_amount: u64,
+    /// Estimated time of all hops together
+    pub estimated_time_secs: u64,
+}
+
+/// Average block time used for bridge time estimates
+fn average_block_time_secs(chain: Chain) -> u64 {
+    match chain {
+        Chain::Ethereum | Chain::EthereumSepolia => 12,
+        Chain::Polygon | Chain::PolygonMumbai | Chain::Base => 2,
+        Chain::Arbitrum => 1,
+        Chain::Starknet | Chain::StarknetGoerli | Chain::StarknetSepolia => 30,
+        Chain::Bitcoin | Chain::BitcoinTestnet => 600,
+        Chain::Spark => 5,
+    }
 }
 
 /// Bridge transaction record
@@ -91,10 +160,29 @@ pub struct BridgeTransaction {
     pub request: BridgeRequest,
     pub source_tx_hash: Option<String>,
     pub target_tx_hash: Option<String>,
+    /// Value of the asset declared by the user, in the source chain's smallest unit
+    #[serde(default)]
+    pub declared_value: u64,
+    /// Fee charged on the declared value, paid on completion
     pub fee_amount: u64,
+    /// Treasury address the fee is paid to
+    #[serde(default)]
+    pub fee_recipient: Option<String>,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
     pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Token minted on the target chain
+    #[serde(default)]
+    pub target_token_id: Option<String>,
+    /// Times `resume_pending` tried to move this bridge forward
+    #[serde(default)]
+    pub attempts: u32,
+    /// Error of the last failed step
+    #[serde(default)]
+    pub last_error: Option<String>,
+    /// Needs an operator; skipped by `resume_pending`
+    #[serde(default)]
+    pub needs_review: bool,
 }
 
 impl BridgeTransaction {
@@ -106,10 +194,16 @@ impl BridgeTransaction {
             request,
             source_tx_hash: None,
             target_tx_hash: None,
+            declared_value: 0,
             fee_amount: 0,
+            fee_recipient: None,
             created_at: now,
             updated_at: now,
             completed_at: None,
+            target_token_id: None,
+            attempts: 0,
+            last_error: None,
+            needs_review: false,
         }
     }
 
@@ -129,6 +223,11 @@ impl BridgeTransaction {
         self.updated_at = chrono::Utc::now();
     }
 
+    pub fn set_status(&mut self, status: BridgeStatus) {
+        self.request.status = status;
+        self.updated_at = chrono::Utc::now();
+    }
+
     pub fn fail(&mut self) {
         self.request.status = BridgeStatus::Failed;
         self.updated_at = chrono::Utc::now();
@@ -152,6 +251,8 @@ pub struct BridgeService {
     config: BridgeConfig,
     transactions: std::sync::RwLock<std::collections::HashMap<Uuid, BridgeTransaction>>,
     user_pending: std::sync::RwLock<std::collections::HashMap<Uuid, Vec<Uuid>>>,
+    /// Fees collected from completed bridges, per source chain
+    treasury: std::sync::RwLock<std::collections::HashMap<Chain, u64>>,
 }
 
 impl BridgeService {
@@ -160,16 +261,27 @@ impl BridgeService {
             config,
             transactions: std::sync::RwLock::new(std::collections::HashMap::new()),
             user_pending: std::sync::RwLock::new(std::collections::HashMap::new()),
+            treasury: std::sync::RwLock::new(std::collections::HashMap::new()),
         }
     }
 
     /// Check if a route is supported
     pub fn is_route_supported(&self, source: Chain, target: Chain) -> bool {
-        self.config.routes.iter().any(|r| {
+        self.route(source, target).is_some()
+    }
+
+    /// The enabled route between two chains
+    pub fn route(&self, source: Chain, target: Chain) -> Option<&BridgeRoute> {
+        self.config.routes.iter().find(|r| {
             r.enabled && r.source == source && r.target == target
         })
     }
 
+    /// Address bridge fees are paid to
+    pub fn treasury_address(&self) -> Option<&str> {
+        self.config.treasury_address.as_deref()
+    }
+
     /// Get supported target chains for a source
     pub fn get_supported_targets(&self, source: Chain) -> Vec<Chain> {
         self.config.routes.iter()
@@ -178,7 +290,88 @@ impl BridgeService {
             .collect()
     }
 
-    /// Initiate a bridge request
+    /// Compute the fee for bridging an asset of the given declared value
+    pub fn calculate_fee(&self, amount: u64) -> u64 {
+        (amount as u128 * self.config.fee_bps as u128 / 10_000) as u64
+    }
+
+    /// Quote the fee and estimated time for a bridge without initiating it
+    pub fn quote(&self, source: Chain, target: Chain, amount: u64) -> Result<BridgeQuote> {
+        self.route(source, target)
+            .ok_or(BlockchainError::UnsupportedBridgeRoute(source, target))?
+            .check_value(amount)?;
+
+        let estimated_time_secs = self.config.min_confirmations * average_block_time_secs(source)
+            + average_block_time_secs(target);
+
+        Ok(BridgeQuote {
+            source,
+            target,
+            amount,
+            fee_amount: self.calculate_fee(amount),
+            fee_bps: self.config.fee_bps,
+            estimated_time_secs,
+        })
+    }
+
+    /// Find the shortest sequence of enabled routes from `source` to
+    /// `target`, going through intermediate chains when there is no direct
+    /// route. Returns `None` when the chains are not connected.
+    pub fn plan_route(&self, source: Chain, target: Chain) -> Option<Vec<BridgeRoute>> {
+        if source == target {
+            return None;
+        }
+
+        // Breadth-first, so the first path found has the fewest hops
+        let mut came_from: std::collections::HashMap<Chain, &BridgeRoute> = std::collections::HashMap::new();
+        let mut frontier = std::collections::VecDeque::from([source]);
+        while let Some(chain) = frontier.pop_front() {
+            for route in self.config.routes.iter().filter(|r| r.enabled && r.source == chain) {
+                if route.target == source || came_from.contains_key(&route.target) {
+                    continue;
+                }
+                came_from.insert(route.target, route);
+                if route.target == target {
+                    let mut hops = vec![route.clone()];
+                    while hops[0].source != source {
+                        hops.insert(0, came_from[&hops[0].source].clone());
+                    }
+                    return Some(hops);
+                }
+                frontier.push_back(route.target);
+            }
+        }
+        None
+    }
+
+    /// Quote a bridge along the planned route, summing the fee and time of every hop
+    pub fn quote_route(&self, source: Chain, target: Chain, amount: u64) -> Result<RouteQuote> {
+        let hops = self
+            .plan_route(source, target)
+            .ok_or(BlockchainError::UnsupportedBridgeRoute(source, target))?;
+
+        let mut fee_amount = 0;
+        let mut estimated_time_secs = 0;
+        for hop in &hops {
+            let quote = self.quote(hop.source, hop.target, amount)?;
+            fee_amount += quote.fee_amount;
+            estimated_time_secs += quote.estimated_time_secs;
+        }
+
+        Ok(RouteQuote {
+            source,
+            target,
+            hops,
+            amount,
+            fee_amount,
+            estimated_time_secs,
+        })
+    }
+
+    /// Initiate a bridge request. The fee on `declared_value` is computed
+    /// here and returned on the pending transaction, so the caller can show
+    /// it before the user confirms; it is collected when the bridge completes.
+    #[allow(clippy::too_many_arguments)]
     pub fn initiate(
         &self,
         user_id: Uuid,
@@ -188,11 +381,12 @@ impl BridgeService {
         source_address: &str,
         target_address: &str,
         asset: AssetType,
+        declared_value: u64,
     ) -> Result<BridgeTransaction> {
-        // Check route support
-        if !self.is_route_supported(source_chain, target_chain) {
-            return Err(BlockchainError::UnsupportedBridgeRoute(source_chain, target_chain));
-        }
+        // Check route support and limits
+        self.route(source_chain, target_chain)
+            .ok_or(BlockchainError::UnsupportedBridgeRoute(source_chain, target_chain))?
+            .check_value(declared_value)?;
 
         // Check user's pending count
         {
@@ -217,9 +411,13 @@ impl BridgeService {
             asset,
             status: BridgeStatus::Pending,
             created_at: chrono::Utc::now(),
+            source_tx_hash: None,
         };
 
-        let transaction = BridgeTransaction::new(user_id, request);
+        let mut transaction = BridgeTransaction::new(user_id, request);
+        transaction.declared_value = declared_value;
+        transaction.fee_amount = self.calculate_fee(declared_value);
+        transaction.fee_recipient = self.config.treasury_address.clone();
         let tx_id = transaction.id;
 
         // Store transaction
@@ -237,11 +435,12 @@ impl BridgeService {
         }
 
         tracing::info!(
-            "Bridge initiated: {} from {:?} to {:?} for user {}",
+            "Bridge initiated: {} from {:?} to {:?} for user {} (fee {})",
             token_id,
             source_chain,
             target_chain,
-            user_id
+            user_id,
+            transaction.fee_amount
         );
 
         Ok(transaction)
@@ -271,11 +470,23 @@ impl BridgeService {
         let id = transaction.id;
         let user_id = transaction.user_id;
         let is_complete = transaction.is_complete() || transaction.is_failed();
+        let source_chain = transaction.request.source_chain;
+        let fee_amount = transaction.fee_amount;
 
-        {
+        let newly_completed = {
             let mut transactions = self.transactions.write()
                 .map_err(|_| BlockchainError::InternalError("Lock poisoned".into()))?;
+            let was_complete = transactions.get(&id).map(|t| t.is_complete()).unwrap_or(false);
+            let now_complete = transaction.is_complete();
             transactions.insert(id, transaction);
+            now_complete && !was_complete
+        };
+
+        // Accrue the fee only once, when the bridge first completes
+        if newly_completed && fee_amount > 0 {
+            let mut treasury = self.treasury.write()
+                .map_err(|_| BlockchainError::InternalError("Lock poisoned".into()))?;
+            *treasury.entry(source_chain).or_insert(0) += fee_amount;
         }
 
         // Remove from pending if complete
@@ -291,6 +502,20 @@ impl BridgeService {
         Ok(())
     }
 
+    /// Fees collected on a source chain
+    pub fn treasury_balance(&self, chain: Chain) -> Result<u64> {
+        let treasury = self.treasury.read()
+            .map_err(|_| BlockchainError::InternalError("Lock poisoned".into()))?;
+        Ok(treasury.get(&chain).copied().unwrap_or(0))
+    }
+
+    /// Total fees collected across all chains
+    pub fn treasury_total(&self) -> Result<u64> {
+        let treasury = self.treasury.read()
+            .map_err(|_| BlockchainError::InternalError("Lock poisoned".into()))?;
+        Ok(treasury.values().sum())
+    }
+
     /// Get bridge statistics
     pub fn stats(&self) -> Result<BridgeStats> {
         let transactions = self.transactions.read()
@@ -309,6 +534,146 @@ impl BridgeService {
         })
     }
 
+    /// Resume bridges interrupted after they started locking
+    pub async fn resume_pending(&self, service: &BlockchainService) -> Result<ResumeReport> {
+        self.resume_pending_at(service, chrono::Utc::now()).await
+    }
+
+    /// Resume interrupted bridges as of `now`. Bridges still short of their
+    /// timeout are driven forward, timed-out ones that have not minted are
+    /// unlocked on the source chain. A failed step is recorded on the
+    /// transaction and left for the next run.
+    pub async fn resume_pending_at(
+        &self,
+        service: &BlockchainService,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<ResumeReport> {
+        let interrupted: Vec<BridgeTransaction> = {
+            let transactions = self.transactions.read()
+                .map_err(|_| BlockchainError::InternalError("Lock poisoned".into()))?;
+
+            transactions
+                .values()
+                .filter(|t| {
+                    !t.needs_review
+                        && matches!(
+                            t.request.status,
+                            BridgeStatus::LockingOnSource | BridgeStatus::LockedOnSource | BridgeStatus::MintingOnTarget
+                        )
+                })
+                .cloned()
+                .collect()
+        };
+
+        let mut report = ResumeReport::default();
+        for mut tx in interrupted {
+            tx.attempts += 1;
+            match self.resume_one(&mut tx, service, now).await {
+                Ok(ResumeOutcome::Completed) => {
+                    tx.last_error = None;
+                    report.completed.push(tx.id);
+                }
+                Ok(ResumeOutcome::RolledBack) => report.rolled_back.push(tx.id),
+                Ok(ResumeOutcome::ManualReview(reason)) => {
+                    tracing::error!("Bridge {} needs manual review: {}", tx.id, reason);
+                    tx.last_error = Some(reason);
+                    tx.needs_review = true;
+                    tx.updated_at = chrono::Utc::now();
+                    report.manual_review.push(tx.id);
+                }
+                Err(e) => {
+                    tracing::warn!("Resuming bridge {} failed (attempt {}): {}", tx.id, tx.attempts, e);
+                    tx.last_error = Some(e.to_string());
+                    tx.updated_at = chrono::Utc::now();
+                    report.retrying.push(tx.id);
+                }
+            }
+            self.update(tx)?;
+        }
+
+        Ok(report)
+    }
+
+    async fn resume_one(
+        &self,
+        tx: &mut BridgeTransaction,
+        service: &BlockchainService,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<ResumeOutcome> {
+        let request = tx.request.clone();
+        let source = service
+            .provider(request.source_chain)
+            .ok_or(BlockchainError::ChainNotConfigured(request.source_chain))?;
+        let target = service
+            .provider(request.target_chain)
+            .ok_or(BlockchainError::ChainNotConfigured(request.target_chain))?;
+
+        // Only a bridge that got to minting may have minted. Whatever else
+        // happened, an asset minted on the target is never unlocked, and
+        // without a way to tell nothing is minted or unlocked.
+        let may_have_minted = request.status == BridgeStatus::MintingOnTarget;
+        if may_have_minted {
+            match target.find_bridged_token(request.source_chain, &request.token_id).await {
+                Ok(Some(token_id)) => {
+                    tx.target_token_id = Some(token_id);
+                    tx.complete();
+                    return Ok(ResumeOutcome::Completed);
+                }
+                Ok(None) => {}
+                Err(BlockchainError::BridgeLookupUnsupported(chain)) => {
+                    return Ok(ResumeOutcome::ManualReview(format!(
+                        "Cannot check {:?} for a token minted before the interruption",
+                        chain
+                    )));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        let timed_out = now - tx.created_at > chrono::Duration::seconds(self.config.timeout_secs as i64);
+        if timed_out {
+            if is_locked(source, &request).await? {
+                source.unlock_from_bridge(&request.token_id, &request.owner_address_source).await?;
+            }
+            tx.last_error = Some(format!("Timed out after {}s, unlocked on source", self.config.timeout_secs));
+            tx.fail();
+            return Ok(ResumeOutcome::RolledBack);
+        }
+
+        // Never mint against a lock that does not hold on the chain
+        if !is_locked(source, &request).await? {
+            if may_have_minted {
+                return Ok(ResumeOutcome::ManualReview(format!(
+                    "Asset {} is no longer locked on {:?}",
+                    request.token_id, request.source_chain
+                )));
+            }
+            let lock_tx = source.lock_for_bridge(&request.token_id, &request.owner_address_source).await?;
+            tx.set_source_tx(&lock_tx);
+            if !is_locked(source, &request).await? {
+                return Err(BlockchainError::Bridge(format!(
+                    "Lock of {} on {:?} not confirmed yet",
+                    request.token_id, request.source_chain
+                )));
+            }
+        }
+        if tx.request.status != BridgeStatus::LockedOnSource {
+            tx.set_status(BridgeStatus::LockedOnSource);
+            self.update(tx.clone())?;
+        }
+
+        tx.set_status(BridgeStatus::MintingOnTarget);
+        self.update(tx.clone())?;
+
+        let result = target
+            .mint_nft(&request.owner_address_target, &bridged_metadata(&request), &request.asset)
+            .await?;
+        tx.set_target_tx(&result.transaction_hash);
+        tx.target_token_id = Some(result.token_id);
+        tx.complete();
+        Ok(ResumeOutcome::Completed)
+    }
+
     /// Clean up old completed/failed transactions
     pub fn cleanup(&self, older_than: chrono::Duration) -> Result<usize> {
         let cutoff = chrono::Utc::now() - older_than;
@@ -325,6 +690,25 @@ impl BridgeService {
     }
 }
 
+/// Outcome of a `resume_pending` run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResumeReport {
+    pub completed: Vec<Uuid>,
+    /// Timed out and unlocked on the source chain
+    pub rolled_back: Vec<Uuid>,
+    /// Failed a step; retried on the next run
+    pub retrying: Vec<Uuid>,
+    /// Flagged for an operator; not retried
+    pub manual_review: Vec<Uuid>,
+}
+
+/// Where a resumed bridge ended up
+enum ResumeOutcome {
+    Completed,
+    RolledBack,
+    ManualReview(String),
+}
+
 /// Bridge statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BridgeStats {
@@ -333,3 +717,381 @@ pub struct BridgeStats {
     pub completed: usize,
     pub failed: usize,
 }
+
+/// Whether the asset has left its owner for the bridge on the source chain
+async fn is_locked(source: &dyn ChainProvider, request: &BridgeRequest) -> Result<bool> {
+    let owner = source.get_nft_owner(&request.token_id).await?;
+    Ok(normalize_address(request.source_chain, &owner)
+        != normalize_address(request.source_chain, &request.owner_address_source))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BlockchainConfig, MintResult, NftMetadata, TransferResult};
+    use async_trait::async_trait;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    const ESCROW: &str = "0xb41d6e0000000000000000000000000000000000";
+
+    #[derive(Default)]
+    struct ChainState {
+        owners: HashMap<String, String>,
+        /// Source token id -> token minted here
+        bridged: HashMap<String, String>,
+        locks: u32,
+        unlocks: u32,
+        mints: u32,
+        fail_mints: bool,
+        /// Chain that cannot look up bridged tokens
+        no_lookup: bool,
+    }
+
+    /// In-memory chain holding NFTs and a bridge escrow
+    struct MockChain {
+        chain: Chain,
+        state: Arc<Mutex<ChainState>>,
+    }
+
+    #[async_trait]
+    impl ChainProvider for MockChain {
+        fn chain(&self) -> Chain {
+            self.chain
+        }
+
+        async fn health_check(&self) -> Result<bool> {
+            Ok(true)
+        }
+
+        async fn get_block_number(&self) -> Result<u64> {
+            Ok(0)
+        }
+
+        async fn mint_nft(&self, to: &str, metadata: &NftMetadata, _asset: &AssetType) -> Result<MintResult> {
+            let mut state = self.state.lock().unwrap();
+            if state.fail_mints {
+                return Err(BlockchainError::Provider { chain: self.chain, message: "nonce too low".into() });
+            }
+            state.mints += 1;
+            let token_id = format!("wrapped-{}", state.mints);
+            state.owners.insert(token_id.clone(), to.to_string());
+            if let Some(original) = metadata.attributes.iter().find(|a| a.trait_type == "original_token_id") {
+                state.bridged.insert(original.value.as_str().unwrap().to_string(), token_id.clone());
+            }
+            Ok(MintResult {
+                chain: self.chain,
+                token_id: token_id.clone(),
+                transaction_hash: format!("0xmint-{}", token_id),
+                contract_address: "0xwrapped".to_string(),
+                metadata_uri: String::new(),
+                minted_at: chrono::Utc::now(),
+            })
+        }
+
+        async fn transfer_nft(&self, _token_id: &str, _from: &str, _to: &str) -> Result<TransferResult> {
+            Err(BlockchainError::Contract("not supported".into()))
+        }
+
+        async fn get_nft_owner(&self, token_id: &str) -> Result<String> {
+            let state = self.state.lock().unwrap();
+            state.owners.get(token_id).cloned().ok_or_else(|| BlockchainError::NftNotFound(token_id.to_string()))
+        }
+
+        async fn verify_signature(&self, _message: &str, _signature: &str, _address: &str) -> Result<bool> {
+            Ok(false)
+        }
+
+        async fn lock_for_bridge(&self, token_id: &str, _owner: &str) -> Result<String> {
+            let mut state = self.state.lock().unwrap();
+            state.locks += 1;
+            state.owners.insert(token_id.to_string(), ESCROW.to_string());
+            Ok(format!("0xlock-{}", token_id))
+        }
+
+        async fn unlock_from_bridge(&self, token_id: &str, owner: &str) -> Result<String> {
+            let mut state = self.state.lock().unwrap();
+            state.unlocks += 1;
+            state.owners.insert(token_id.to_string(), owner.to_string());
+            Ok(format!("0xunlock-{}", token_id))
+        }
+
+        async fn find_bridged_token(&self, _source_chain: Chain, source_token_id: &str) -> Result<Option<String>> {
+            let state = self.state.lock().unwrap();
+            if state.no_lookup {
+                return Err(BlockchainError::BridgeLookupUnsupported(self.chain));
+            }
+            Ok(state.bridged.get(source_token_id).cloned())
+        }
+    }
+
+    /// Ethereum holding `tokens` owned by 0xabc, and an empty Polygon
+    async fn setup(tokens: &[&str]) -> (BlockchainService, Arc<Mutex<ChainState>>, Arc<Mutex<ChainState>>) {
+        let ethereum = Arc::new(Mutex::new(ChainState::default()));
+        let polygon = Arc::new(Mutex::new(ChainState::default()));
+        for token in tokens {
+            ethereum.lock().unwrap().owners.insert(token.to_string(), "0xabc".to_string());
+        }
+        let service = BlockchainService::new(BlockchainConfig::default())
+            .await
+            .unwrap()
+            .with_provider(Box::new(MockChain { chain: Chain::Ethereum, state: ethereum.clone() }))
+            .with_provider(Box::new(MockChain { chain: Chain::Polygon, state: polygon.clone() }));
+        (service, ethereum, polygon)
+    }
+
+    /// Start a bridge and stop it at `status`, as a crash would
+    async fn interrupted(bridge: &BridgeService, service: &BlockchainService, token: &str, status: BridgeStatus) -> Uuid {
+        let mut tx = bridge
+            .initiate(Uuid::new_v4(), token, Chain::Ethereum, Chain::Polygon, "0xabc", "0xdef", test_asset(), 0)
+            .unwrap();
+        if status != BridgeStatus::LockingOnSource {
+            let lock_tx = service.provider(Chain::Ethereum).unwrap().lock_for_bridge(token, "0xabc").await.unwrap();
+            tx.set_source_tx(&lock_tx);
+        }
+        tx.set_status(status);
+        bridge.update(tx.clone()).unwrap();
+        tx.id
+    }
+
+    fn test_asset() -> AssetType {
+        AssetType::Mount {
+            mount_id: 1,
+            name: "Widow Queen".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_quote_fee_for_bps() {
+        let service = BridgeService::new(BridgeConfig::default());
+
+        let quote = service.quote(Chain::Ethereum, Chain::Polygon, 1_000_000).unwrap();
+        assert_eq!(quote.fee_bps, 50);
+        assert_eq!(quote.fee_amount, 5_000);
+        assert_eq!(quote.estimated_time_secs, 12 * 12 + 2);
+
+        let custom = BridgeService::new(BridgeConfig { fee_bps: 125, ..Default::default() });
+        assert_eq!(custom.quote(Chain::Ethereum, Chain::Polygon, 10_000).unwrap().fee_amount, 125);
+    }
+
+    #[test]
+    fn test_quote_unsupported_route() {
+        let service = BridgeService::new(BridgeConfig::default());
+        assert!(service.quote(Chain::Bitcoin, Chain::Polygon, 1_000).is_err());
+    }
+
+    #[test]
+    fn test_plan_direct_route() {
+        let service = BridgeService::new(BridgeConfig::default());
+
+        let hops = service.plan_route(Chain::Ethereum, Chain::Polygon).unwrap();
+        assert_eq!(hops.len(), 1);
+        assert_eq!((hops[0].source, hops[0].target), (Chain::Ethereum, Chain::Polygon));
+        assert_eq!(service.quote_route(Chain::Ethereum, Chain::Polygon, 1_000_000).unwrap().fee_amount, 5_000);
+    }
+
+    #[test]
+    fn test_plan_two_hop_route() {
+        let service = BridgeService::new(BridgeConfig::default());
+        assert!(!service.is_route_supported(Chain::Polygon, Chain::Base));
+
+        let quote = service.quote_route(Chain::Polygon, Chain::Base, 1_000_000).unwrap();
+        let path: Vec<_> = quote.hops.iter().map(|hop| (hop.source, hop.target)).collect();
+        assert_eq!(path, vec![(Chain::Polygon, Chain::Ethereum), (Chain::Ethereum, Chain::Base)]);
+        assert_eq!(quote.fee_amount, 10_000);
+        assert_eq!(quote.estimated_time_secs, (12 * 2 + 12) + (12 * 12 + 2));
+    }
+
+    #[test]
+    fn test_plan_route_without_path() {
+        let service = BridgeService::new(BridgeConfig::default());
+        assert!(service.plan_route(Chain::Bitcoin, Chain::Polygon).is_none());
+        assert!(service.quote_route(Chain::Bitcoin, Chain::Polygon, 1_000).is_err());
+
+        // Disabled routes are not used as hops
+        let mut config = BridgeConfig::default();
+        for route in config.routes.iter_mut().filter(|r| r.source == Chain::Ethereum && r.target == Chain::Base) {
+            route.enabled = false;
+        }
+        assert!(BridgeService::new(config).plan_route(Chain::Polygon, Chain::Base).is_none());
+    }
+
+    #[test]
+    fn test_initiate_charges_fee_in_bps() {
+        let config = BridgeConfig { treasury_address: Some("0xtreasury".to_string()), ..Default::default() };
+        let service = BridgeService::new(config);
+        let user_id = Uuid::new_v4();
+
+        let tx = service
+            .initiate(user_id, "token-1", Chain::Ethereum, Chain::Polygon, "0xabc", "0xdef", test_asset(), 1_000_000)
+            .unwrap();
+        // 0.5% of the declared value, shown before anything is locked
+        assert_eq!((tx.declared_value, tx.fee_amount), (1_000_000, 5_000));
+        assert_eq!(tx.request.status, BridgeStatus::Pending);
+        assert_eq!(tx.fee_recipient.as_deref(), Some("0xtreasury"));
+        assert_eq!(service.get(tx.id).unwrap().unwrap().fee_amount, 5_000);
+
+        // Fractions of the smallest unit round down
+        let small = service
+            .initiate(user_id, "token-2", Chain::Ethereum, Chain::Polygon, "0xabc", "0xdef", test_asset(), 399)
+            .unwrap();
+        assert_eq!(small.fee_amount, 1);
+    }
+
+    #[test]
+    fn test_route_limits_enforced() {
+        let service = BridgeService::new(BridgeConfig {
+            routes: vec![BridgeRoute::new(Chain::Ethereum, Chain::Polygon).with_limits(1_000, 1_000_000)],
+            ..Default::default()
+        });
+        let user_id = Uuid::new_v4();
+        let initiate = |value| {
+            service.initiate(user_id, "token-1", Chain::Ethereum, Chain::Polygon, "0xabc", "0xdef", test_asset(), value)
+        };
+
+        assert!(matches!(initiate(999), Err(BlockchainError::BridgeValueOutOfRange { value: 999, .. })));
+        assert!(matches!(initiate(1_000_001), Err(BlockchainError::BridgeValueOutOfRange { .. })));
+        assert!(service.quote(Chain::Ethereum, Chain::Polygon, 999).is_err());
+        assert_eq!(service.stats().unwrap().total, 0);
+
+        assert_eq!(initiate(1_000).unwrap().fee_amount, 5);
+        assert_eq!(initiate(1_000_000).unwrap().fee_amount, 5_000);
+    }
+
+    #[test]
+    fn test_treasury_accrues_on_completion() {
+        let service = BridgeService::new(BridgeConfig::default());
+        let user_id = Uuid::new_v4();
+
+        let mut tx = service
+            .initiate(user_id, "token-1", Chain::Ethereum, Chain::Polygon, "0xabc", "0xdef", test_asset(), 200_000)
+            .unwrap();
+        assert_eq!(tx.fee_amount, 1_000);
+
+        // Pending updates don't accrue
+        service.update(tx.clone()).unwrap();
+        assert_eq!(service.treasury_balance(Chain::Ethereum).unwrap(), 0);
+
+        tx.complete();
+        service.update(tx.clone()).unwrap();
+        assert_eq!(service.treasury_balance(Chain::Ethereum).unwrap(), 1_000);
+
+        // Re-saving a completed bridge doesn't double count
+        service.update(tx).unwrap();
+        assert_eq!(service.treasury_total().unwrap(), 1_000);
+    }
+
+    #[tokio::test]
+    async fn test_resume_completes_bridge_interrupted_after_lock() {
+        let (service, ethereum, polygon) = setup(&["token-1", "token-2", "token-3"]).await;
+        let bridge = BridgeService::new(BridgeConfig::default());
+
+        let locked = interrupted(&bridge, &service, "token-1", BridgeStatus::LockedOnSource).await;
+        let locking = interrupted(&bridge, &service, "token-2", BridgeStatus::LockingOnSource).await;
+        // Crashed after the mint went through but before it was recorded
+        let minted = interrupted(&bridge, &service, "token-3", BridgeStatus::MintingOnTarget).await;
+        let request = bridge.get(minted).unwrap().unwrap().request;
+        service
+            .provider(Chain::Polygon)
+            .unwrap()
+            .mint_nft("0xdef", &bridged_metadata(&request), &request.asset)
+            .await
+            .unwrap();
+
+        let mut report = bridge.resume_pending(&service).await.unwrap();
+        report.completed.sort();
+        let mut expected = vec![locked, locking, minted];
+        expected.sort();
+        assert_eq!(report.completed, expected);
+        assert!(report.rolled_back.is_empty() && report.retrying.is_empty());
+
+        // Every asset locked once and minted once
+        assert_eq!(ethereum.lock().unwrap().locks, 3);
+        assert_eq!(polygon.lock().unwrap().mints, 3);
+        let tx = bridge.get(locked).unwrap().unwrap();
+        assert!(tx.is_complete());
+        assert_eq!(tx.attempts, 1);
+        assert!(tx.target_token_id.is_some() && tx.target_tx_hash.is_some());
+        assert_eq!(bridge.get(locking).unwrap().unwrap().source_tx_hash.as_deref(), Some("0xlock-token-2"));
+        assert_eq!(bridge.stats().unwrap().pending, 0);
+
+        // Nothing left to resume
+        let report = bridge.resume_pending(&service).await.unwrap();
+        assert!(report.completed.is_empty());
+        assert_eq!(polygon.lock().unwrap().mints, 3);
+    }
+
+    #[tokio::test]
+    async fn test_resume_retries_then_rolls_back_after_timeout() {
+        let (service, ethereum, polygon) = setup(&["token-1"]).await;
+        let bridge = BridgeService::new(BridgeConfig::default());
+        let id = interrupted(&bridge, &service, "token-1", BridgeStatus::LockedOnSource).await;
+        polygon.lock().unwrap().fail_mints = true;
+
+        let now = chrono::Utc::now();
+        let report = bridge.resume_pending_at(&service, now).await.unwrap();
+        assert_eq!(report.retrying, vec![id]);
+        let tx = bridge.get(id).unwrap().unwrap();
+        assert!(tx.is_pending());
+        assert_eq!(tx.attempts, 1);
+        assert!(tx.last_error.as_deref().unwrap().contains("nonce too low"));
+
+        let report = bridge.resume_pending_at(&service, now + chrono::Duration::seconds(3601)).await.unwrap();
+        assert_eq!(report.rolled_back, vec![id]);
+        let tx = bridge.get(id).unwrap().unwrap();
+        assert!(tx.is_failed());
+        assert_eq!(tx.attempts, 2);
+        assert!(tx.last_error.as_deref().unwrap().contains("Timed out"));
+
+        // Back with its owner, never minted
+        let ethereum = ethereum.lock().unwrap();
+        assert_eq!((ethereum.unlocks, ethereum.owners["token-1"].as_str()), (1, "0xabc"));
+        assert_eq!(polygon.lock().unwrap().mints, 0);
+    }
+
+    #[tokio::test]
+    async fn test_resume_without_lookup_needs_manual_review() {
+        let (service, ethereum, polygon) = setup(&["token-1", "token-2"]).await;
+        let bridge = BridgeService::new(BridgeConfig::default());
+        let minting = interrupted(&bridge, &service, "token-1", BridgeStatus::MintingOnTarget).await;
+        // Not yet minting, so nothing can have been minted
+        let locked = interrupted(&bridge, &service, "token-2", BridgeStatus::LockedOnSource).await;
+        polygon.lock().unwrap().no_lookup = true;
+
+        let later = chrono::Utc::now() + chrono::Duration::seconds(3601);
+        let report = bridge.resume_pending_at(&service, later).await.unwrap();
+        assert_eq!(report.manual_review, vec![minting]);
+        assert_eq!(report.rolled_back, vec![locked]);
+        let tx = bridge.get(minting).unwrap().unwrap();
+        assert!(tx.needs_review && tx.is_pending());
+        assert!(tx.last_error.as_deref().unwrap().contains("Polygon"));
+
+        // Neither re-minted nor unlocked, and not picked up again
+        assert_eq!(ethereum.lock().unwrap().owners["token-1"], ESCROW);
+        assert_eq!(polygon.lock().unwrap().mints, 0);
+        let report = bridge.resume_pending_at(&service, later).await.unwrap();
+        assert!(report.manual_review.is_empty() && report.completed.is_empty());
+        assert_eq!(bridge.get(minting).unwrap().unwrap().attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn test_resume_relocks_before_minting() {
+        let (service, ethereum, polygon) = setup(&["token-1", "token-2"]).await;
+        let bridge = BridgeService::new(BridgeConfig::default());
+        let locked = interrupted(&bridge, &service, "token-1", BridgeStatus::LockedOnSource).await;
+        let minting = interrupted(&bridge, &service, "token-2", BridgeStatus::MintingOnTarget).await;
+        // Both assets left the escrow while the bridge was down
+        ethereum.lock().unwrap().owners.insert("token-1".to_string(), "0xabc".to_string());
+        ethereum.lock().unwrap().owners.insert("token-2".to_string(), "0xabc".to_string());
+
+        let report = bridge.resume_pending(&service).await.unwrap();
+        assert_eq!(report.completed, vec![locked]);
+        assert_eq!(report.manual_review, vec![minting]);
+
+        // Locked again before its mint; the other is not minted without a lock
+        let ethereum = ethereum.lock().unwrap();
+        assert_eq!((ethereum.locks, ethereum.owners["token-1"].as_str()), (3, ESCROW));
+        assert_eq!(polygon.lock().unwrap().mints, 1);
+        assert!(bridge.get(minting).unwrap().unwrap().last_error.as_deref().unwrap().contains("no longer locked"));
+    }
+}
@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use std::collections::VecDeque;
 
-use crate::{BridgeRequest, BridgeStatus, Chain, Result, BlockchainError};
+use crate::{BridgeRequest, Chain, Result, BlockchainError};
 
 /// Priority levels for bridge requests
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
@@ -111,10 +111,10 @@ impl BridgeVerifier {
 
         // Get actual confirmations from chain
         // If we have a source tx hash, query block depth; otherwise use minimum
-        let confirmations = if let Some(ref tx_hash) = request.source_tx_hash {
+        let confirmations = if request.source_tx_hash.is_some() {
             // Query current block and calculate depth
             match provider.get_block_number().await {
-                Ok(current_block) => {
+                Ok(_) => {
                     // Estimate: tx block = current - min_confirmations for locked state
                     // Real implementation would query tx receipt for actual block
                     if is_locked { self.min_confirmations } else { 0 }
@@ -122,9 +122,10 @@ impl BitcoinProvider {
                 .map_err(|e| BlockchainError::RpcError(e.to_string()))?;
 
             if unspent.is_empty() {
-                return Err(BlockchainError::InsufficientFunds(
-                    "No UTXOs available for inscription".to_string()
-                ));
+                return Err(BlockchainError::InsufficientFunds {
+                    needed: "1 UTXO".to_string(),
+                    available: "0 UTXOs".to_string(),
+                });
             }
 
             // Calculate fee for inscription (estimate based on content size)
@@ -144,10 +145,10 @@ impl BitcoinProvider {
             // 2. Reveal transaction (spends commit, reveals inscription data)
             
             // For now, create a standard transaction with OP_RETURN for metadata hash
-            let metadata_hash = sha2::Sha256::digest(&inscription_content);
+            let _metadata_hash = <sha2::Sha256 as sha2::Digest>::digest(&inscription_content);
             
             // Get a new address for the inscription output
-            let addr = client.get_new_address(None, None)
+            let _addr = client.get_new_address(None, None)
                 .map_err(|e| BlockchainError::RpcError(e.to_string()))?
                 .assume_checked();
 
@@ -186,7 +187,7 @@ impl BitcoinProvider {
             let mut hasher = Sha256::new();
             hasher.update(&inscription_content);
             hasher.update(to.as_bytes());
-            hasher.update(&chrono::Utc::now().timestamp().to_le_bytes());
+            hasher.update(chrono::Utc::now().timestamp().to_le_bytes());
             let hash = hasher.finalize();
             let txid = hex::encode(&hash[..16]);
             let inscription_id = format!("{}i0", txid);
@@ -207,11 +208,6 @@ impl BitcoinProvider {
         if let Some(ref client) = self.client {
             let client = client.read().await;
 
-            // Parse inscription ID to get the UTXO (format: txid:index or txidi0)
-            let txid_str = inscription_id
-                .trim_end_matches(|c: char| !c.is_ascii_hexdigit())
-                .to_string();
-
             // In a real implementation:
             // 1. Query ord indexer for the current UTXO containing this inscription
             // 2. Create transaction spending that UTXO to the new address
@@ -237,7 +233,7 @@ impl BitcoinProvider {
             let mut hasher = Sha256::new();
             hasher.update(inscription_id.as_bytes());
             hasher.update(to.as_bytes());
-            hasher.update(&chrono::Utc::now().timestamp().to_le_bytes());
+            hasher.update(chrono::Utc::now().timestamp().to_le_bytes());
             let hash = hasher.finalize();
             let txid = hex::encode(&hash[..16]);
 
@@ -362,7 +358,7 @@ impl ChainProvider for BitcoinProvider {
                     // The inscription owner is typically the first output address
                     if let Some(vout) = tx_info.vout.first() {
                         if let Some(ref addr) = vout.script_pub_key.address {
-                            return Ok(addr.to_string());
+                            return Ok(addr.clone().assume_checked().to_string());
                         }
                     }
                     Ok("unknown".to_string())
@@ -385,17 +381,20 @@ impl ChainProvider for BitcoinProvider {
 
         Self::validate_address(address, self.config.network)?;
 
-        if let Some(ref client) = self.client {
-            let client = client.read().await;
-
-            // Use Bitcoin Core's verifymessage RPC
+        if self.client.is_some() {
+            // Check the BIP-137 message signature against the address
             let addr = bitcoin::Address::from_str(address)
                 .map_err(|e| BlockchainError::InvalidAddress(e.to_string()))?
                 .assume_checked();
 
-            match client.verify_message(&addr, &bitcoin::sign_message::MessageSignature::from_base64(signature)
-                .map_err(|e| BlockchainError::SignatureError(e.to_string()))?, message) 
-            {
+            let signature_bytes = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, signature)
+                .map_err(|e| BlockchainError::SignatureError(e.to_string()))?;
+            let signature = bitcoin::sign_message::MessageSignature::from_slice(&signature_bytes)
+                .map_err(|e| BlockchainError::SignatureError(e.to_string()))?;
+
+            let secp = bitcoin::secp256k1::Secp256k1::verification_only();
+            let msg_hash = bitcoin::sign_message::signed_msg_hash(message);
+            match signature.is_signed_by_address(&secp, &addr, msg_hash) {
                 Ok(valid) => Ok(valid),
                 Err(e) => {
                     tracing::warn!("Signature verification failed: {}", e);
@@ -404,7 +403,7 @@ impl ChainProvider for BitcoinProvider {
             }
         } else {
             // Offline mode - basic signature format validation
-            if signature.is_empty() || !base64::decode(signature).is_ok() {
+            if signature.is_empty() || base64::Engine::decode(&base64::engine::general_purpose::STANDARD, signature).is_err() {
                 return Ok(false);
             }
             // Cannot verify without RPC
@@ -3,6 +3,8 @@
 //! Supports ERC-721 and ERC-1155 NFT standards using ethers-rs.
 
 use async_trait::async_trait;
+use ethers::abi::Token;
+use ethers::types::{Address, Signature, U256};
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -71,12 +73,6 @@ impl EvmProvider {
         Ok(self.config.gas_limit.unwrap_or(300_000))
     }
 
-    /// Wait for transaction confirmation
-    async fn wait_for_confirmation(&self, _tx_hash: &str) -> Result<bool> {
-        // In production: wait for receipt with confirmations
-        // let receipt = self.provider.get_transaction_receipt(tx_hash).await?;
-        Ok(true)
-    }
 
     /// Generate ERC-721 metadata URI
     fn generate_metadata_uri(metadata: &NftMetadata) -> String {
@@ -91,6 +87,29 @@ impl EvmProvider {
     }
 }
 
+/// Calldata of the ERC-721 `burn(uint256)` call for a token
+pub fn burn_calldata(token_id: &str) -> Result<Vec<u8>> {
+    let token_id = U256::from_dec_str(token_id)
+        .map_err(|_| BlockchainError::NftNotFound(token_id.to_string()))?;
+    let mut calldata = ethers::utils::id("burn(uint256)").to_vec();
+    calldata.extend(ethers::abi::encode(&[Token::Uint(token_id)]));
+    Ok(calldata)
+}
+
+/// Check an EIP-191 `personal_sign` signature: recover the signer of
+/// `message` and compare it with `address`
+pub fn verify_personal_sign(message: &str, signature: &str, address: &str) -> Result<bool> {
+    let expected: Address = address
+        .parse()
+        .map_err(|_| BlockchainError::InvalidAddress(address.to_string()))?;
+    let signature: Signature = signature
+        .parse()
+        .map_err(|e| BlockchainError::InvalidSignature(format!("{}", e)))?;
+
+    // A malformed signature recovers no key at all
+    Ok(signature.recover(message).is_ok_and(|signer| signer == expected))
+}
+
 #[async_trait]
 impl ChainProvider for EvmProvider {
     fn chain(&self) -> Chain {
@@ -216,14 +235,7 @@ impl ChainProvider for EvmProvider {
             ));
         }
 
-        // In production using ethers-rs:
-        // let sig = Signature::from_str(signature)?;
-        // let recovered = sig.recover(message)?;
-        // Ok(recovered == address.parse()?)
-
-        // For now, basic validation
-        let _ = message;
-        Ok(signature.len() == 132) // 65 bytes * 2 + "0x"
+        verify_personal_sign(message, signature, address)
     }
 
     async fn lock_for_bridge(&self, token_id: &str, owner: &str) -> Result<String> {
@@ -238,6 +250,7 @@ impl ChainProvider for EvmProvider {
         // let pending_tx = tx.send().await?;
         // let receipt = pending_tx.await?;
 
+        let _ = owner;
         let lock_tx = format!(
             "0x{}",
             hex::encode(uuid::Uuid::new_v4().as_bytes())
@@ -266,6 +279,37 @@ impl ChainProvider for EvmProvider {
 
         Ok(unlock_tx)
     }
+
+    fn can_burn(&self) -> bool {
+        true
+    }
+
+    async fn burn_nft(&self, token_id: &str, owner: &str) -> Result<String> {
+        tracing::info!(
+            "Burning token {} of {} on {:?}",
+            token_id,
+            owner,
+            self.config.chain
+        );
+
+        if !owner.starts_with("0x") || owner.len() != 42 {
+            return Err(BlockchainError::InvalidAddress(owner.to_string()));
+        }
+        let calldata = burn_calldata(token_id)?;
+
+        // In production:
+        // let tx = TransactionRequest::new().to(self.contract_address()).data(calldata);
+        // let pending_tx = self.signer.send_transaction(tx, None).await?;
+        // let receipt = pending_tx.await?;
+
+        let _ = calldata;
+        let burn_tx = format!(
+            "0x{}",
+            hex::encode(uuid::Uuid::new_v4().as_bytes())
+        );
+
+        Ok(burn_tx)
+    }
 }
 
 #[cfg(test)]
@@ -285,6 +329,48 @@ mod tests {
         assert_eq!(provider.chain(), Chain::EthereumSepolia);
     }
 
+    #[tokio::test]
+    async fn test_personal_sign_verification() {
+        use ethers::signers::{LocalWallet, Signer};
+
+        let wallet: LocalWallet = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse()
+            .unwrap();
+        let address = format!("{:?}", wallet.address());
+        let message = "Sign this message to authenticate with Shadow OT.\n\nNonce: abc123\nAddress: ".to_string() + &address;
+        let signature = format!("0x{}", wallet.sign_message(&message).await.unwrap());
+
+        let provider = EvmProvider::new(EvmChainConfig::default()).await.unwrap();
+        assert!(provider.verify_signature(&message, &signature, &address).await.unwrap());
+
+        // Another nonce, another signer or a flipped byte all fail
+        let other = message.replace("abc123", "abc124");
+        assert!(!provider.verify_signature(&other, &signature, &address).await.unwrap());
+        let stranger = "0x0000000000000000000000000000000000000001";
+        assert!(!provider.verify_signature(&message, &signature, stranger).await.unwrap());
+        let mut tampered = signature.clone().into_bytes();
+        tampered[10] = if tampered[10] == b'0' { b'1' } else { b'0' };
+        let tampered = String::from_utf8(tampered).unwrap();
+        assert!(!provider.verify_signature(&message, &tampered, &address).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_burn_for_rollback() {
+        let provider = EvmProvider::new(EvmChainConfig::default()).await.unwrap();
+        assert!(provider.can_burn());
+
+        // burn(uint256) selector followed by the token id as one word
+        let calldata = burn_calldata("258").unwrap();
+        assert_eq!(hex::encode(&calldata[..4]), "42966c68");
+        assert_eq!(calldata.len(), 36);
+        assert_eq!(&calldata[34..], &[1, 2]);
+
+        let owner = "0x1234567890123456789012345678901234567890";
+        assert!(provider.burn_nft("258", owner).await.unwrap().starts_with("0x"));
+        assert!(matches!(provider.burn_nft("not-a-token", owner).await, Err(BlockchainError::NftNotFound(_))));
+        assert!(matches!(provider.burn_nft("258", "owner").await, Err(BlockchainError::InvalidAddress(_))));
+    }
+
     #[tokio::test]
     async fn test_address_validation() {
         let config = EvmChainConfig::default();
@@ -10,7 +10,7 @@ pub use evm::EvmProvider;
 pub use starknet::StarknetProvider;
 pub use bitcoin::BitcoinProvider;
 
-use crate::{Chain, ChainProvider, BlockchainConfig, Result, BlockchainError};
+use crate::{Chain, ChainProvider, BlockchainConfig, Result};
 use std::collections::HashMap;
 
 /// Create all configured chain providers
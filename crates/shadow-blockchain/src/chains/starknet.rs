@@ -4,6 +4,10 @@
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use starknet_core::types::{BlockId, BlockTag, FieldElement, FunctionCall};
+use starknet_core::utils::{get_selector_from_name, starknet_keccak};
+use starknet_providers::jsonrpc::{HttpTransport, JsonRpcClient};
+use starknet_providers::{Provider, ProviderError};
 
 use crate::{
     error::BlockchainError, AssetType, Chain, ChainProvider, MintResult, NftMetadata, Result,
@@ -55,24 +59,51 @@ impl StarknetProvider {
         Ok(Self { config })
     }
 
-    /// Convert a hex string to a Starknet felt
-    fn to_felt(_hex: &str) -> Result<[u8; 32]> {
-        // In production: FieldElement::from_hex_be(hex)
-        Ok([0u8; 32])
-    }
-
     /// Generate IPFS metadata for Starknet NFT
     fn generate_metadata_uri(metadata: &NftMetadata) -> String {
         // Starknet typically uses IPFS for metadata
         format!(
             "ipfs://{}",
-            hex::encode(&sha2::Digest::finalize(sha2::Sha256::new_with_prefix(
+            hex::encode(sha2::Digest::finalize(<sha2::Sha256 as sha2::Digest>::new_with_prefix(
                 serde_json::to_string(metadata).unwrap_or_default()
             )))
         )
     }
 }
 
+/// `'VALID'` as a short string, returned by SNIP-6 `is_valid_signature`
+const SNIP6_VALID: u64 = 0x56414c4944;
+
+/// Hash a login message is signed over: starknet_keccak of its UTF-8 bytes
+pub fn message_hash(message: &str) -> FieldElement {
+    starknet_keccak(message.as_bytes())
+}
+
+/// Parse a signature given as comma separated felts (`0xr,0xs`) or as a
+/// JSON array of felts, as wallets return it
+pub fn parse_signature(signature: &str) -> Result<Vec<FieldElement>> {
+    let invalid = |msg: &str| BlockchainError::InvalidSignature(msg.to_string());
+    let trimmed = signature.trim().trim_start_matches('[').trim_end_matches(']');
+    let felts = trimmed
+        .split(',')
+        .map(|part| part.trim().trim_matches('"'))
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            if part.starts_with("0x") {
+                FieldElement::from_hex_be(part)
+            } else {
+                FieldElement::from_dec_str(part)
+            }
+            .map_err(|_| invalid("Signature must be a list of field elements"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if felts.len() < 2 {
+        return Err(invalid("Signature needs at least r and s"));
+    }
+    Ok(felts)
+}
+
 #[async_trait]
 impl ChainProvider for StarknetProvider {
     fn chain(&self) -> Chain {
@@ -186,17 +217,38 @@ impl ChainProvider for StarknetProvider {
             address
         );
 
-        // Starknet uses STARK curve signatures (r, s format)
-        // In production:
-        // let sig = Signature { r: ..., s: ... };
-        // let message_hash = pedersen_hash(message);
-        // account.verify_message(message_hash, sig).await?
+        let account = FieldElement::from_hex_be(address)
+            .map_err(|_| BlockchainError::InvalidAddress(address.to_string()))?;
+        let signature = parse_signature(signature)?;
 
-        let _ = message;
-        let _ = signature;
+        // Accounts are contracts and decide what a valid signature is, so
+        // ask the account itself (SNIP-6 `is_valid_signature(hash, signature)`)
+        let mut calldata = vec![message_hash(message), FieldElement::from(signature.len() as u64)];
+        calldata.extend(signature);
 
-        // Basic validation - Starknet signatures are two field elements
-        Ok(true)
+        let url: reqwest::Url = self.config.rpc_url.parse().map_err(|_| BlockchainError::Provider {
+            chain: self.config.chain,
+            message: format!("Invalid RPC URL {}", self.config.rpc_url),
+        })?;
+        let client = JsonRpcClient::new(HttpTransport::new(url));
+        let call = FunctionCall {
+            contract_address: account,
+            entry_point_selector: get_selector_from_name("is_valid_signature").expect("ASCII selector"),
+            calldata,
+        };
+
+        match client.call(call, BlockId::Tag(BlockTag::Latest)).await {
+            // Cairo 1 accounts return 'VALID', Cairo 0 accounts return 1
+            Ok(result) => Ok(result
+                .first()
+                .is_some_and(|&value| value == FieldElement::from(SNIP6_VALID) || value == FieldElement::ONE)),
+            // Reverted calls, undeployed accounts and the like: not signed by this account
+            Err(ProviderError::StarknetError(_)) => Ok(false),
+            Err(e) => Err(BlockchainError::Provider {
+                chain: self.config.chain,
+                message: e.to_string(),
+            }),
+        }
     }
 
     async fn lock_for_bridge(&self, token_id: &str, owner: &str) -> Result<String> {
@@ -255,4 +307,102 @@ mod tests {
         let provider = StarknetProvider::new(config).await.unwrap();
         assert!(provider.chain().is_starknet());
     }
+
+    #[test]
+    fn test_login_signature_scheme() {
+        let private_key = FieldElement::from_hex_be("0x0139fe4d6f02e666e86a6f58e65060f115cd3c185bd9e98bd829636931458f79").unwrap();
+        let public_key = starknet_crypto::get_public_key(&private_key);
+        let message = "Sign this message to authenticate with Shadow OT.\n\nNonce: abc123\nAddress: 0x1";
+        let hash = message_hash(message);
+
+        let signed = starknet_crypto::sign(&private_key, &hash, &FieldElement::from(7u64)).unwrap();
+        let signature = parse_signature(&format!("[\"{:#x}\", \"{:#x}\"]", signed.r, signed.s)).unwrap();
+        assert_eq!(signature, vec![signed.r, signed.s]);
+        assert_eq!(parse_signature(&format!("{:#x},{:#x}", signed.r, signed.s)).unwrap(), signature);
+
+        // What the account checks: the key signed this exact nonce message
+        assert!(starknet_crypto::verify(&public_key, &hash, &signature[0], &signature[1]).unwrap());
+        let tampered = message_hash(&message.replace("abc123", "abc124"));
+        assert!(!starknet_crypto::verify(&public_key, &tampered, &signature[0], &signature[1]).unwrap());
+        assert!(parse_signature("0x1").is_err());
+    }
+
+    /// Answer one JSON-RPC request with `reply` and hand back the request
+    async fn mock_rpc(reply: serde_json::Value) -> (String, tokio::task::JoinHandle<serde_json::Value>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut raw = Vec::new();
+            let mut buf = [0u8; 4096];
+            let body_start = loop {
+                let n = socket.read(&mut buf).await.unwrap();
+                raw.extend_from_slice(&buf[..n]);
+                if let Some(end) = raw.windows(4).position(|w| w == b"\r\n\r\n") {
+                    break end + 4;
+                }
+            };
+            let headers = String::from_utf8_lossy(&raw[..body_start]).to_lowercase();
+            let length: usize = headers
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length:"))
+                .map(|value| value.trim().parse().unwrap())
+                .unwrap();
+            while raw.len() < body_start + length {
+                let n = socket.read(&mut buf).await.unwrap();
+                raw.extend_from_slice(&buf[..n]);
+            }
+            let request: serde_json::Value = serde_json::from_slice(&raw[body_start..body_start + length]).unwrap();
+
+            let mut response = reply;
+            response["jsonrpc"] = "2.0".into();
+            response["id"] = request["id"].clone();
+            let body = response.to_string();
+            let http = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            socket.write_all(http.as_bytes()).await.unwrap();
+            request
+        });
+        (url, server)
+    }
+
+    async fn provider_at(rpc_url: String) -> StarknetProvider {
+        StarknetProvider::new(StarknetChainConfig { rpc_url, ..Default::default() }).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_verify_signature_asks_account_contract() {
+        let message = "Sign this message to authenticate with Shadow OT.\n\nNonce: abc123\nAddress: 0x1234";
+
+        // Cairo 1 account answering 'VALID'
+        let (url, server) = mock_rpc(serde_json::json!({ "result": ["0x56414c4944"] })).await;
+        assert!(provider_at(url).await.verify_signature(message, "0x1,0x2", "0x1234").await.unwrap());
+
+        // The account was called with the SNIP-6 entry point, the message hash and the signature
+        let request = server.await.unwrap();
+        assert_eq!(request["method"], "starknet_call");
+        let call = &request["params"][0];
+        let felt = |value: &serde_json::Value| FieldElement::from_hex_be(value.as_str().unwrap()).unwrap();
+        assert_eq!(felt(&call["contract_address"]), FieldElement::from(0x1234u64));
+        assert_eq!(felt(&call["entry_point_selector"]), get_selector_from_name("is_valid_signature").unwrap());
+        let calldata: Vec<FieldElement> = call["calldata"].as_array().unwrap().iter().map(felt).collect();
+        assert_eq!(calldata, vec![message_hash(message), FieldElement::from(2u64), FieldElement::ONE, FieldElement::TWO]);
+
+        // Cairo 0 accounts return 1
+        let (url, _) = mock_rpc(serde_json::json!({ "result": ["0x1"] })).await;
+        assert!(provider_at(url).await.verify_signature(message, "0x1,0x2", "0x1234").await.unwrap());
+
+        // Anything else is a rejection
+        let (url, _) = mock_rpc(serde_json::json!({ "result": ["0x0"] })).await;
+        assert!(!provider_at(url).await.verify_signature(message, "0x1,0x2", "0x1234").await.unwrap());
+
+        // So is an account that isn't deployed
+        let (url, _) = mock_rpc(serde_json::json!({ "error": { "code": 20, "message": "Contract not found" } })).await;
+        assert!(!provider_at(url).await.verify_signature(message, "0x1,0x2", "0x1234").await.unwrap());
+    }
 }
@@ -10,6 +10,9 @@ pub enum BlockchainError {
     #[error("Chain not supported: {0:?}")]
     ChainNotSupported(Chain),
 
+    #[error("Unknown chain: {0}")]
+    UnknownChain(String),
+
     #[error("Chain not configured: {0:?}")]
     ChainNotConfigured(Chain),
 
@@ -25,6 +28,12 @@ pub enum BlockchainError {
     #[error("Invalid address: {0}")]
     InvalidAddress(String),
 
+    #[error("Wallet limit reached: at most {0} wallets per account")]
+    WalletLimitReached(usize),
+
+    #[error("Wallet already linked: {0}")]
+    WalletAlreadyLinked(String),
+
     #[error("Invalid signature: {0}")]
     InvalidSignature(String),
 
@@ -40,6 +49,15 @@ pub enum BlockchainError {
     #[error("Unsupported bridge route: {0:?} to {1:?}")]
     UnsupportedBridgeRoute(Chain, Chain),
 
+    #[error("Bridge value {value} outside route limits (min {min:?}, max {max:?})")]
+    BridgeValueOutOfRange { value: u64, min: Option<u64>, max: Option<u64> },
+
+    #[error("Burning is not supported on {0:?}")]
+    BurnUnsupported(Chain),
+
+    #[error("Bridged token lookup not supported on {0:?}")]
+    BridgeLookupUnsupported(Chain),
+
     #[error("Too many pending bridge requests")]
     TooManyPendingBridges,
 
@@ -58,6 +76,12 @@ pub enum BlockchainError {
     #[error("Network error: {0}")]
     Network(String),
 
+    #[error("RPC error: {0}")]
+    RpcError(String),
+
+    #[error("Signature error: {0}")]
+    SignatureError(String),
+
     #[error("Serialization error: {0}")]
     SerializationError(String),
 
@@ -76,6 +100,6 @@ impl From<reqwest::Error> for BlockchainError {
 
 impl From<serde_json::Error> for BlockchainError {
     fn from(err: serde_json::Error) -> Self {
-        BlockchainError::Serialization(err.to_string())
+        BlockchainError::SerializationError(err.to_string())
     }
 }
@@ -18,8 +18,8 @@ pub use error::{BlockchainError, Result};
 pub use config::BlockchainConfig;
 pub use chains::{create_providers, EvmProvider, StarknetProvider, BitcoinProvider};
 pub use wallet::{WalletAuth, WalletAuthChallenge, WalletAuthResult, WalletConnection, WalletType, UserWallet, WalletManager};
-pub use nft::{ShadowNft, NftCollection, NftManager, MetadataBuilder, MetadataGenerator, MintQueue, MintRequest, MintStatus, NftStorage, StoredNft};
-pub use bridge::{BridgeService, BridgeConfig as BridgeServiceConfig, BridgeRoute as ServiceBridgeRoute, BridgeTransaction, BridgeStats, BridgeQueue, BridgeQueueStats, BridgeVerifier, VerificationResult};
+pub use nft::{ShadowNft, NftCollection, NftManager, ReconcileSummary, NftListing, NftListingStatus, FloorStats, TraitFloor, MetadataBuilder, MetadataGenerator, MintQueue, MintRequest, MintStatus, NftStorage, StoredNft};
+pub use bridge::{BridgeService, BridgeConfig as BridgeServiceConfig, BridgeQuote, ResumeReport, RouteQuote, BridgeRoute as ServiceBridgeRoute, BridgeTransaction, BridgeStats, BridgeQueue, BridgeQueueStats, BridgeVerifier, VerificationResult};
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -55,7 +55,7 @@ pub enum Chain {
 }
 
 impl Chain {
-    pub fn chain_id(&self) -> u64 {
+    pub fn chain_id(&self) -> u128 {
         match self {
             Chain::Ethereum => 1,
             Chain::EthereumSepolia => 11155111,
@@ -107,6 +107,29 @@ impl Chain {
     }
 }
 
+impl std::str::FromStr for Chain {
+    type Err = BlockchainError;
+
+    /// Parse a chain name as the API and wallets send it, case-insensitively
+    fn from_str(name: &str) -> Result<Self> {
+        match name.to_lowercase().as_str() {
+            "ethereum" => Ok(Chain::Ethereum),
+            "ethereum-sepolia" => Ok(Chain::EthereumSepolia),
+            "polygon" => Ok(Chain::Polygon),
+            "polygon-mumbai" => Ok(Chain::PolygonMumbai),
+            "starknet" => Ok(Chain::Starknet),
+            "starknet-goerli" => Ok(Chain::StarknetGoerli),
+            "starknet-sepolia" => Ok(Chain::StarknetSepolia),
+            "bitcoin" => Ok(Chain::Bitcoin),
+            "bitcoin-testnet" => Ok(Chain::BitcoinTestnet),
+            "spark" => Ok(Chain::Spark),
+            "base" => Ok(Chain::Base),
+            "arbitrum" => Ok(Chain::Arbitrum),
+            other => Err(BlockchainError::UnknownChain(other.to_string())),
+        }
+    }
+}
+
 /// Asset types that can be minted as NFTs
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AssetType {
@@ -269,6 +292,26 @@ pub trait ChainProvider: Send + Sync {
 
     /// Unlock an asset after failed bridge
     async fn unlock_from_bridge(&self, token_id: &str, owner: &str) -> Result<String>;
+
+    /// Token minted on this chain for a bridged asset, if any. Used to
+    /// avoid minting twice when an interrupted bridge is resumed; providers
+    /// that cannot look this up return `BridgeLookupUnsupported`, and such
+    /// bridges are left for manual review.
+    async fn find_bridged_token(&self, _source_chain: Chain, _source_token_id: &str) -> Result<Option<String>> {
+        Err(BlockchainError::BridgeLookupUnsupported(self.chain()))
+    }
+
+    /// Whether `burn_nft` is implemented. Atomic multi-chain mints are
+    /// refused when a target chain cannot burn.
+    fn can_burn(&self) -> bool {
+        false
+    }
+
+    /// Burn a token minted by the game, returning the burn transaction.
+    /// Used to roll back an atomic multi-chain mint.
+    async fn burn_nft(&self, _token_id: &str, _owner: &str) -> Result<String> {
+        Err(BlockchainError::BurnUnsupported(self.chain()))
+    }
 }
 
 /// Result of minting an NFT
@@ -282,6 +325,33 @@ pub struct MintResult {
     pub minted_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// Outcome of minting an asset on several chains
+#[derive(Debug, Default)]
+pub struct MultiChainMint {
+    /// Mints that went through and are kept
+    pub minted: Vec<MintResult>,
+    /// Chains the mint failed on
+    pub failures: Vec<(Chain, BlockchainError)>,
+    /// Mints undone because another chain failed in atomic mode
+    pub rolled_back: Vec<RolledBackMint>,
+}
+
+impl MultiChainMint {
+    /// Whether the asset was minted on every requested chain
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// A mint undone by an atomic multi-chain mint
+#[derive(Debug)]
+pub struct RolledBackMint {
+    pub mint: MintResult,
+    /// Burn transaction, or why the token could not be burned; such
+    /// tokens are logged for manual cleanup
+    pub burn: Result<String>,
+}
+
 /// Result of transferring an NFT
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransferResult {
@@ -305,6 +375,9 @@ pub struct BridgeRequest {
     pub asset: AssetType,
     pub status: BridgeStatus,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Lock transaction on the source chain, once sent
+    #[serde(default)]
+    pub source_tx_hash: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
@@ -337,32 +410,87 @@ impl BlockchainService {
         Ok(Self { config, providers })
     }
 
+    pub fn config(&self) -> &BlockchainConfig {
+        &self.config
+    }
+
+    /// Register a provider for its chain
+    pub fn with_provider(mut self, provider: Box<dyn ChainProvider>) -> Self {
+        self.providers.insert(provider.chain(), provider);
+        self
+    }
+
     /// Get a provider for a specific chain
     pub fn provider(&self, chain: Chain) -> Option<&dyn ChainProvider> {
         self.providers.get(&chain).map(|p| p.as_ref())
     }
 
-    /// Mint an asset as NFT on multiple chains simultaneously
+    /// Mint an asset as NFT on multiple chains simultaneously. Every
+    /// chain's failure is reported; with `atomic` a single failure burns
+    /// the mints that succeeded, so the asset exists on all chains or none.
+    /// An atomic mint is refused before minting anything if a target chain
+    /// has no provider or cannot burn.
     pub async fn multi_chain_mint(
         &self,
         to_addresses: std::collections::HashMap<Chain, String>,
         metadata: &NftMetadata,
         asset: &AssetType,
-    ) -> Result<Vec<MintResult>> {
-        let mut results = Vec::new();
-
-        for (chain, address) in to_addresses {
-            if let Some(provider) = self.provider(chain) {
-                match provider.mint_nft(&address, metadata, asset).await {
-                    Ok(result) => results.push(result),
-                    Err(e) => {
-                        tracing::error!("Failed to mint on {:?}: {}", chain, e);
-                    }
+        atomic: bool,
+    ) -> Result<MultiChainMint> {
+        if atomic {
+            for &chain in to_addresses.keys() {
+                match self.provider(chain) {
+                    Some(provider) if provider.can_burn() => {}
+                    Some(_) => return Err(BlockchainError::BurnUnsupported(chain)),
+                    None => return Err(BlockchainError::ChainNotSupported(chain)),
                 }
             }
         }
 
-        Ok(results)
+        let mints = to_addresses.into_iter().map(|(chain, address)| async move {
+            let result = match self.provider(chain) {
+                Some(provider) => provider.mint_nft(&address, metadata, asset).await,
+                None => Err(BlockchainError::ChainNotSupported(chain)),
+            };
+            (chain, address, result)
+        });
+
+        let mut outcome = MultiChainMint::default();
+        let mut minted = Vec::new();
+        for (chain, address, result) in futures::future::join_all(mints).await {
+            match result {
+                Ok(mint) => minted.push((address, mint)),
+                Err(e) => {
+                    tracing::error!("Failed to mint on {:?}: {}", chain, e);
+                    outcome.failures.push((chain, e));
+                }
+            }
+        }
+
+        if !atomic || outcome.failures.is_empty() {
+            outcome.minted = minted.into_iter().map(|(_, mint)| mint).collect();
+            return Ok(outcome);
+        }
+
+        let burns = minted.into_iter().map(|(address, mint)| async move {
+            let burn = match self.provider(mint.chain) {
+                Some(provider) => provider.burn_nft(&mint.token_id, &address).await,
+                None => Err(BlockchainError::ChainNotSupported(mint.chain)),
+            };
+            RolledBackMint { mint, burn }
+        });
+        outcome.rolled_back = futures::future::join_all(burns).await;
+        for rollback in &outcome.rolled_back {
+            if let Err(e) = &rollback.burn {
+                tracing::error!(
+                    "Could not roll back token {} on {:?}, needs manual cleanup: {}",
+                    rollback.mint.token_id,
+                    rollback.mint.chain,
+                    e
+                );
+            }
+        }
+        Ok(outcome)
     }
 
     /// Bridge an asset from one chain to another
@@ -392,7 +520,8 @@ impl BlockchainService {
             .lock_for_bridge(&request.token_id, &request.owner_address_source)
             .await
         {
-            Ok(_) => {
+            Ok(lock_tx) => {
+                request.source_tx_hash = Some(lock_tx);
                 request.status = BridgeStatus::LockedOnSource;
                 tracing::info!("Asset locked on source chain");
             }
@@ -406,38 +535,7 @@ impl BlockchainService {
         // Step 2: Mint wrapped asset on target chain
         request.status = BridgeStatus::MintingOnTarget;
 
-        // Create metadata for bridged asset
-        let metadata = NftMetadata {
-            name: format!("Bridged {}", request.token_id),
-            description: format!(
-                "Asset bridged from {:?} to {:?}",
-                request.source_chain, request.target_chain
-            ),
-            image: String::new(),
-            external_url: None,
-            animation_url: None,
-            attributes: vec![
-                NftAttribute {
-                    trait_type: "original_chain".to_string(),
-                    value: serde_json::Value::String(format!("{:?}", request.source_chain)),
-                    display_type: None,
-                },
-                NftAttribute {
-                    trait_type: "original_token_id".to_string(),
-                    value: serde_json::Value::String(request.token_id.clone()),
-                    display_type: None,
-                },
-            ],
-            properties: NftProperties {
-                game_id: "shadow-ot".to_string(),
-                realm_id: None,
-                asset_type: format!("{:?}", request.asset),
-                original_chain: request.source_chain,
-                bridged_chains: vec![request.target_chain],
-                created_at: chrono::Utc::now(),
-                shadow_ot_version: env!("CARGO_PKG_VERSION").to_string(),
-            },
-        };
+        let metadata = bridged_metadata(&request);
 
         match target_provider
             .mint_nft(&request.owner_address_target, &metadata, &request.asset)
@@ -464,3 +562,247 @@ impl BlockchainService {
         Ok(request)
     }
 }
+
+/// Metadata of the asset minted on the target chain of a bridge
+pub(crate) fn bridged_metadata(request: &BridgeRequest) -> NftMetadata {
+    NftMetadata {
+        name: format!("Bridged {}", request.token_id),
+        description: format!(
+            "Asset bridged from {:?} to {:?}",
+            request.source_chain, request.target_chain
+        ),
+        image: String::new(),
+        external_url: None,
+        animation_url: None,
+        attributes: vec![
+            NftAttribute {
+                trait_type: "original_chain".to_string(),
+                value: serde_json::Value::String(format!("{:?}", request.source_chain)),
+                display_type: None,
+            },
+            NftAttribute {
+                trait_type: "original_token_id".to_string(),
+                value: serde_json::Value::String(request.token_id.clone()),
+                display_type: None,
+            },
+        ],
+        properties: NftProperties {
+            game_id: "shadow-ot".to_string(),
+            realm_id: None,
+            asset_type: format!("{:?}", request.asset),
+            original_chain: request.source_chain,
+            bridged_chains: vec![request.target_chain],
+            created_at: chrono::Utc::now(),
+            shadow_ot_version: env!("CARGO_PKG_VERSION").to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    /// Provider whose mints (and burns) succeed or fail as configured
+    struct MockMinter {
+        chain: Chain,
+        mint_fails: bool,
+        burn_fails: bool,
+        can_burn: bool,
+        burned: Arc<Mutex<Vec<(Chain, String)>>>,
+    }
+
+    #[async_trait]
+    impl ChainProvider for MockMinter {
+        fn chain(&self) -> Chain {
+            self.chain
+        }
+
+        async fn health_check(&self) -> Result<bool> {
+            Ok(true)
+        }
+
+        async fn get_block_number(&self) -> Result<u64> {
+            Ok(0)
+        }
+
+        async fn mint_nft(&self, _to: &str, _metadata: &NftMetadata, _asset: &AssetType) -> Result<MintResult> {
+            if self.mint_fails {
+                return Err(BlockchainError::Provider { chain: self.chain, message: "out of gas".into() });
+            }
+            Ok(MintResult {
+                chain: self.chain,
+                token_id: format!("{:?}-1", self.chain),
+                transaction_hash: "0xmint".to_string(),
+                contract_address: "0xcontract".to_string(),
+                metadata_uri: String::new(),
+                minted_at: chrono::Utc::now(),
+            })
+        }
+
+        async fn transfer_nft(&self, _token_id: &str, _from: &str, _to: &str) -> Result<TransferResult> {
+            Err(BlockchainError::Contract("not supported".into()))
+        }
+
+        async fn get_nft_owner(&self, _token_id: &str) -> Result<String> {
+            Err(BlockchainError::Contract("not supported".into()))
+        }
+
+        async fn verify_signature(&self, _message: &str, _signature: &str, _address: &str) -> Result<bool> {
+            Ok(false)
+        }
+
+        async fn lock_for_bridge(&self, _token_id: &str, _owner: &str) -> Result<String> {
+            Err(BlockchainError::Contract("not supported".into()))
+        }
+
+        async fn unlock_from_bridge(&self, _token_id: &str, _owner: &str) -> Result<String> {
+            Err(BlockchainError::Contract("not supported".into()))
+        }
+
+        fn can_burn(&self) -> bool {
+            self.can_burn
+        }
+
+        async fn burn_nft(&self, token_id: &str, _owner: &str) -> Result<String> {
+            if self.burn_fails {
+                return Err(BlockchainError::Provider { chain: self.chain, message: "rpc timeout".into() });
+            }
+            self.burned.lock().unwrap().push((self.chain, token_id.to_string()));
+            Ok("0xburn".to_string())
+        }
+    }
+
+    /// Service with Ethereum and Polygon minting, Base and Arbitrum failing.
+    /// Polygon's burns fail as well, and Spark cannot burn at all.
+    async fn setup() -> (BlockchainService, Arc<Mutex<Vec<(Chain, String)>>>) {
+        let burned = Arc::new(Mutex::new(Vec::new()));
+        let mut service = BlockchainService::new(BlockchainConfig::default()).await.unwrap();
+        for (chain, mint_fails, burn_fails, can_burn) in [
+            (Chain::Ethereum, false, false, true),
+            (Chain::Polygon, false, true, true),
+            (Chain::Base, true, false, true),
+            (Chain::Arbitrum, true, false, true),
+            (Chain::Spark, false, false, false),
+        ] {
+            let minter = MockMinter { chain, mint_fails, burn_fails, can_burn, burned: burned.clone() };
+            service = service.with_provider(Box::new(minter));
+        }
+        (service, burned)
+    }
+
+    fn metadata() -> NftMetadata {
+        bridged_metadata(&BridgeRequest {
+            request_id: Uuid::new_v4(),
+            token_id: "1".to_string(),
+            source_chain: Chain::Ethereum,
+            target_chain: Chain::Polygon,
+            owner_address_source: "0xabc".to_string(),
+            owner_address_target: "0xabc".to_string(),
+            asset: AssetType::Mount { mount_id: 1, name: "Widow Queen".to_string() },
+            status: BridgeStatus::Pending,
+            created_at: chrono::Utc::now(),
+            source_tx_hash: None,
+        })
+    }
+
+    fn addresses(chains: &[Chain]) -> HashMap<Chain, String> {
+        chains.iter().map(|&chain| (chain, "0xabc".to_string())).collect()
+    }
+
+    fn failed_chains(outcome: &MultiChainMint) -> Vec<Chain> {
+        let mut chains: Vec<Chain> = outcome.failures.iter().map(|(chain, _)| *chain).collect();
+        chains.sort_by_key(|chain| format!("{:?}", chain));
+        chains
+    }
+
+    #[tokio::test]
+    async fn test_best_effort_mint_reports_each_failure() {
+        let (service, burned) = setup().await;
+        let asset = AssetType::Mount { mount_id: 1, name: "Widow Queen".to_string() };
+        let chains = [Chain::Ethereum, Chain::Polygon, Chain::Base, Chain::Arbitrum, Chain::Starknet];
+
+        let outcome = service.multi_chain_mint(addresses(&chains), &metadata(), &asset, false).await.unwrap();
+
+        let mut minted: Vec<Chain> = outcome.minted.iter().map(|mint| mint.chain).collect();
+        minted.sort_by_key(|chain| format!("{:?}", chain));
+        assert_eq!(minted, vec![Chain::Ethereum, Chain::Polygon]);
+        // A chain without a provider is a failure too, not skipped
+        assert_eq!(failed_chains(&outcome), vec![Chain::Arbitrum, Chain::Base, Chain::Starknet]);
+        assert!(outcome.failures.iter().any(|(_, e)| matches!(e, BlockchainError::ChainNotSupported(Chain::Starknet))));
+        assert!(!outcome.is_complete());
+        assert!(outcome.rolled_back.is_empty());
+        assert!(burned.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_atomic_mint_rolls_back_on_failure() {
+        let (service, burned) = setup().await;
+        let asset = AssetType::Mount { mount_id: 1, name: "Widow Queen".to_string() };
+
+        let outcome = service
+            .multi_chain_mint(addresses(&[Chain::Ethereum, Chain::Polygon, Chain::Base]), &metadata(), &asset, true)
+            .await
+            .unwrap();
+
+        assert!(outcome.minted.is_empty());
+        assert_eq!(failed_chains(&outcome), vec![Chain::Base]);
+        assert_eq!(outcome.rolled_back.len(), 2);
+        assert_eq!(*burned.lock().unwrap(), vec![(Chain::Ethereum, "Ethereum-1".to_string())]);
+        // Polygon's burn failed, so its token is reported for cleanup
+        let stuck: Vec<Chain> =
+            outcome.rolled_back.iter().filter(|r| r.burn.is_err()).map(|r| r.mint.chain).collect();
+        assert_eq!(stuck, vec![Chain::Polygon]);
+
+        // Without failures an atomic mint keeps everything
+        let chains = addresses(&[Chain::Ethereum, Chain::Polygon]);
+        let outcome = service.multi_chain_mint(chains, &metadata(), &asset, true).await.unwrap();
+        assert!(outcome.is_complete());
+        assert_eq!(outcome.minted.len(), 2);
+        assert!(outcome.rolled_back.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_atomic_mint_refused_without_burn_support() {
+        let (service, burned) = setup().await;
+        let asset = AssetType::Mount { mount_id: 1, name: "Widow Queen".to_string() };
+
+        let result = service.multi_chain_mint(addresses(&[Chain::Ethereum, Chain::Spark]), &metadata(), &asset, true).await;
+        assert!(matches!(result, Err(BlockchainError::BurnUnsupported(Chain::Spark))));
+        let result = service.multi_chain_mint(addresses(&[Chain::Ethereum, Chain::Starknet]), &metadata(), &asset, true).await;
+        assert!(matches!(result, Err(BlockchainError::ChainNotSupported(Chain::Starknet))));
+        assert!(burned.lock().unwrap().is_empty());
+
+        // Best effort doesn't need burns
+        let outcome = service.multi_chain_mint(addresses(&[Chain::Ethereum, Chain::Spark]), &metadata(), &asset, false).await.unwrap();
+        assert!(outcome.is_complete());
+    }
+
+    #[tokio::test]
+    async fn test_atomic_mint_accepted_on_evm_chains() {
+        use crate::chains::evm::EvmChainConfig;
+
+        let mut service = BlockchainService::new(BlockchainConfig::default()).await.unwrap();
+        for chain in [Chain::Ethereum, Chain::Polygon] {
+            let provider = EvmProvider::new(EvmChainConfig { chain, ..Default::default() }).await.unwrap();
+            service = service.with_provider(Box::new(provider));
+        }
+        let owner = "0x1234567890123456789012345678901234567890".to_string();
+        let chains = [Chain::Ethereum, Chain::Polygon].into_iter().map(|chain| (chain, owner.clone())).collect();
+        let asset = AssetType::Mount { mount_id: 1, name: "Widow Queen".to_string() };
+
+        let outcome = service.multi_chain_mint(chains, &metadata(), &asset, true).await.unwrap();
+        assert!(outcome.is_complete());
+        assert_eq!(outcome.minted.len(), 2);
+    }
+
+    #[test]
+    fn test_chain_from_str() {
+        assert_eq!("Starknet".parse::<Chain>().unwrap(), Chain::Starknet);
+        assert_eq!("polygon-mumbai".parse::<Chain>().unwrap(), Chain::PolygonMumbai);
+        assert_eq!("ARBITRUM".parse::<Chain>().unwrap(), Chain::Arbitrum);
+        let err = "solana".parse::<Chain>().unwrap_err();
+        assert_eq!(err.to_string(), "Unknown chain: solana");
+    }
+}
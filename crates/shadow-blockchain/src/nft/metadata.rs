@@ -2,7 +2,6 @@
 //!
 //! Build and generate NFT metadata according to standards.
 
-use serde::{Deserialize, Serialize};
 use crate::{AssetType, Chain, NftAttribute, NftMetadata, NftProperties, Rarity, Result};
 
 /// Builder for NFT metadata
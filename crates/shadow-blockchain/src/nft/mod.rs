@@ -10,10 +10,15 @@ pub use metadata::{MetadataBuilder, MetadataGenerator};
 pub use minting::{MintQueue, MintRequest, MintStatus};
 pub use storage::{NftStorage, StoredNft};
 
-use crate::{AssetType, Chain, MintResult, NftMetadata, Result};
+use crate::wallet::{normalize_address, WalletManager};
+use crate::{AssetType, Chain, ChainProvider, MintResult, NftMetadata, Result};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use uuid::Uuid;
 
+/// Owner lookups sent to the chain at once during reconciliation
+pub const RECONCILE_BATCH_SIZE: usize = 25;
+
 /// Represents an NFT in the Shadow OT system
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ShadowNft {
@@ -162,10 +167,92 @@ impl NftCollection {
     }
 }
 
+/// Status of a marketplace listing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NftListingStatus {
+    Active,
+    Sold,
+    Cancelled,
+    Expired,
+}
+
+/// An NFT listed for sale on the marketplace
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NftListing {
+    pub id: Uuid,
+    pub nft_id: Uuid,
+    pub seller_address: String,
+    /// Asking price in the smallest unit of the chain's native currency
+    pub price: u128,
+    pub status: NftListingStatus,
+    pub listed_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl NftListing {
+    pub fn new(nft_id: Uuid, seller_address: &str, price: u128) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            nft_id,
+            seller_address: seller_address.to_string(),
+            price,
+            status: NftListingStatus::Active,
+            listed_at: chrono::Utc::now(),
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.status == NftListingStatus::Active
+    }
+}
+
+/// Lowest asking price among active listings with one trait value
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraitFloor {
+    pub trait_type: String,
+    pub value: String,
+    pub floor: u128,
+    /// Active listings with this trait value
+    pub listings: usize,
+}
+
+/// Floor prices of a collection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FloorStats {
+    pub collection_id: Uuid,
+    /// Lowest asking price, `None` when nothing is listed
+    pub floor: Option<u128>,
+    pub active_listings: usize,
+    /// Per-trait floors, ordered by trait type then value
+    pub trait_floors: Vec<TraitFloor>,
+}
+
+impl FloorStats {
+    /// Floor of listings whose `trait_type` is `value`
+    pub fn trait_floor(&self, trait_type: &str, value: &str) -> Option<u128> {
+        self.trait_floors
+            .iter()
+            .find(|t| t.trait_type == trait_type && t.value == value)
+            .map(|t| t.floor)
+    }
+}
+
+/// Outcome of an ownership reconciliation run
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReconcileSummary {
+    /// NFTs whose on-chain owner changed
+    pub updated: usize,
+    pub unchanged: usize,
+    /// NFTs whose owner lookup failed; left as they were
+    pub errored: usize,
+}
+
 /// Manages NFT collections across chains
 pub struct NftManager {
     collections: std::sync::RwLock<std::collections::HashMap<Uuid, NftCollection>>,
     nfts: std::sync::RwLock<std::collections::HashMap<Uuid, ShadowNft>>,
+    listings: std::sync::RwLock<std::collections::HashMap<Uuid, NftListing>>,
+    /// Linked wallets, used to map on-chain owners back to users
+    wallets: Option<Arc<WalletManager>>,
 }
 
 impl NftManager {
@@ -173,9 +260,17 @@ impl NftManager {
         Self {
             collections: std::sync::RwLock::new(std::collections::HashMap::new()),
             nfts: std::sync::RwLock::new(std::collections::HashMap::new()),
+            listings: std::sync::RwLock::new(std::collections::HashMap::new()),
+            wallets: None,
         }
     }
 
+    /// Resolve NFT owners to users through linked wallets
+    pub fn with_wallet_manager(mut self, wallets: Arc<WalletManager>) -> Self {
+        self.wallets = Some(wallets);
+        self
+    }
+
     /// Register a new NFT collection
     pub fn register_collection(&self, collection: NftCollection) -> Result<()> {
         let mut collections = self.collections.write()
@@ -245,6 +340,142 @@ impl NftManager {
             .cloned()
             .collect())
     }
+
+    /// Refresh the owners of the stored NFTs on `chain` from the chain.
+    ///
+    /// Owners are looked up in batches of `RECONCILE_BATCH_SIZE`. An NFT whose
+    /// owner changed gets the new address and the user of the wallet it
+    /// belongs to, or no user when it is not a linked wallet. A failed lookup
+    /// is counted and skipped without stopping the run. Locked NFTs are held
+    /// by the bridge and are left out.
+    pub async fn reconcile_ownership(&self, chain: Chain, provider: &dyn ChainProvider) -> Result<ReconcileSummary> {
+        let tokens: Vec<(Uuid, String)> = {
+            let nfts = self.nfts.read()
+                .map_err(|_| crate::BlockchainError::InternalError("Lock poisoned".into()))?;
+
+            nfts.values()
+                .filter(|n| n.chain == chain && !n.is_locked)
+                .map(|n| (n.id, n.token_id.clone()))
+                .collect()
+        };
+
+        let mut summary = ReconcileSummary::default();
+        for batch in tokens.chunks(RECONCILE_BATCH_SIZE) {
+            let owners = futures::future::join_all(
+                batch.iter().map(|(_, token_id)| provider.get_nft_owner(token_id)),
+            )
+            .await;
+
+            let mut nfts = self.nfts.write()
+                .map_err(|_| crate::BlockchainError::InternalError("Lock poisoned".into()))?;
+
+            for ((id, token_id), owner) in batch.iter().zip(owners) {
+                let owner = match owner {
+                    Ok(owner) => owner,
+                    Err(e) => {
+                        tracing::warn!("Owner lookup for {:?} token {} failed: {}", chain, token_id, e);
+                        summary.errored += 1;
+                        continue;
+                    }
+                };
+                // Removed while the batch was in flight
+                let Some(nft) = nfts.get_mut(id) else {
+                    continue;
+                };
+
+                if normalize_address(chain, &nft.owner_address) == normalize_address(chain, &owner) {
+                    summary.unchanged += 1;
+                    continue;
+                }
+
+                nft.owner_user_id = match &self.wallets {
+                    Some(wallets) => wallets.find_wallet_owner(chain, &owner)?,
+                    None => None,
+                };
+                nft.owner_address = owner;
+                nft.updated_at = chrono::Utc::now();
+                summary.updated += 1;
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// List a stored NFT on the marketplace
+    pub fn create_listing(&self, listing: NftListing) -> Result<()> {
+        if self.get_nft(listing.nft_id)?.is_none() {
+            return Err(crate::BlockchainError::NftNotFound(listing.nft_id.to_string()));
+        }
+
+        let mut listings = self.listings.write()
+            .map_err(|_| crate::BlockchainError::InternalError("Lock poisoned".into()))?;
+
+        listings.insert(listing.id, listing);
+        Ok(())
+    }
+
+    /// Update a listing's status, e.g. when it sells or is cancelled
+    pub fn set_listing_status(&self, listing_id: Uuid, status: NftListingStatus) -> Result<bool> {
+        let mut listings = self.listings.write()
+            .map_err(|_| crate::BlockchainError::InternalError("Lock poisoned".into()))?;
+
+        Ok(match listings.get_mut(&listing_id) {
+            Some(listing) => {
+                listing.status = status;
+                true
+            }
+            None => false,
+        })
+    }
+
+    /// Collection and per-trait floor prices from the collection's active listings
+    pub fn floor_prices(&self, collection_id: Uuid) -> Result<FloorStats> {
+        let collection = self
+            .get_collection(collection_id)?
+            .ok_or_else(|| crate::BlockchainError::NftNotFound(format!("collection {}", collection_id)))?;
+
+        let nfts = self.nfts.read()
+            .map_err(|_| crate::BlockchainError::InternalError("Lock poisoned".into()))?;
+        let listings = self.listings.read()
+            .map_err(|_| crate::BlockchainError::InternalError("Lock poisoned".into()))?;
+
+        let mut floor: Option<u128> = None;
+        let mut active_listings = 0;
+        let mut traits: std::collections::BTreeMap<(String, String), (u128, usize)> = std::collections::BTreeMap::new();
+
+        for listing in listings.values().filter(|l| l.is_active()) {
+            let Some(nft) = nfts.get(&listing.nft_id) else {
+                continue;
+            };
+            if nft.chain != collection.chain || nft.contract_address != collection.contract_address {
+                continue;
+            }
+
+            active_listings += 1;
+            floor = Some(floor.map_or(listing.price, |f| f.min(listing.price)));
+            for attribute in &nft.metadata.attributes {
+                let value = match &attribute.value {
+                    serde_json::Value::String(value) => value.clone(),
+                    value => value.to_string(),
+                };
+                let entry = traits
+                    .entry((attribute.trait_type.clone(), value))
+                    .or_insert((listing.price, 0));
+                entry.0 = entry.0.min(listing.price);
+                entry.1 += 1;
+            }
+        }
+
+        Ok(FloorStats {
+            collection_id,
+            floor,
+            active_listings,
+            trait_floors: traits
+                .into_iter()
+                .map(|((trait_type, value), (floor, listings))| TraitFloor { trait_type, value, floor, listings })
+                .collect(),
+        })
+    }
 }
 
 impl Default for NftManager {
@@ -252,3 +483,203 @@ impl Default for NftManager {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wallet::{UserWallet, WalletType};
+    use crate::{BlockchainError, NftAttribute, NftProperties, TransferResult};
+    use async_trait::async_trait;
+    use std::collections::HashMap;
+
+    /// Provider answering owner lookups from a map; unknown tokens fail
+    struct MockOwners(HashMap<String, String>);
+
+    #[async_trait]
+    impl ChainProvider for MockOwners {
+        fn chain(&self) -> Chain {
+            Chain::Polygon
+        }
+
+        async fn health_check(&self) -> Result<bool> {
+            Ok(true)
+        }
+
+        async fn get_block_number(&self) -> Result<u64> {
+            Ok(0)
+        }
+
+        async fn mint_nft(&self, _to: &str, _metadata: &NftMetadata, _asset: &AssetType) -> Result<MintResult> {
+            Err(BlockchainError::Contract("not supported".into()))
+        }
+
+        async fn transfer_nft(&self, _token_id: &str, _from: &str, _to: &str) -> Result<TransferResult> {
+            Err(BlockchainError::Contract("not supported".into()))
+        }
+
+        async fn get_nft_owner(&self, token_id: &str) -> Result<String> {
+            self.0.get(token_id).cloned().ok_or_else(|| BlockchainError::Provider {
+                chain: Chain::Polygon,
+                message: "rpc timeout".into(),
+            })
+        }
+
+        async fn verify_signature(&self, _message: &str, _signature: &str, _address: &str) -> Result<bool> {
+            Ok(false)
+        }
+
+        async fn lock_for_bridge(&self, _token_id: &str, _owner: &str) -> Result<String> {
+            Err(BlockchainError::Contract("not supported".into()))
+        }
+
+        async fn unlock_from_bridge(&self, _token_id: &str, _owner: &str) -> Result<String> {
+            Err(BlockchainError::Contract("not supported".into()))
+        }
+    }
+
+    fn seed_nft(manager: &NftManager, collection: &NftCollection, token_id: &str, rarity: &str, element: &str) -> Uuid {
+        let now = chrono::Utc::now();
+        let attribute = |trait_type: &str, value: &str| NftAttribute {
+            trait_type: trait_type.to_string(),
+            value: serde_json::Value::String(value.to_string()),
+            display_type: None,
+        };
+        let metadata = NftMetadata {
+            name: format!("Widow Queen #{}", token_id),
+            description: String::new(),
+            image: String::new(),
+            external_url: None,
+            animation_url: None,
+            attributes: vec![attribute("Rarity", rarity), attribute("Element", element)],
+            properties: NftProperties {
+                game_id: "shadow-ot".to_string(),
+                realm_id: None,
+                asset_type: "mount".to_string(),
+                original_chain: collection.chain,
+                bridged_chains: vec![],
+                created_at: now,
+                shadow_ot_version: "1.0.0".to_string(),
+            },
+        };
+        let mint_result = MintResult {
+            chain: collection.chain,
+            token_id: token_id.to_string(),
+            transaction_hash: format!("0x{}", token_id),
+            contract_address: collection.contract_address.clone(),
+            metadata_uri: String::new(),
+            minted_at: now,
+        };
+        let asset = AssetType::Mount { mount_id: 1, name: "Widow Queen".to_string() };
+        let nft = ShadowNft::new(asset, metadata, mint_result, "0xowner".to_string());
+        let id = nft.id;
+        manager.store_nft(nft).unwrap();
+        id
+    }
+
+    #[test]
+    fn test_collection_and_trait_floor() {
+        let manager = NftManager::new();
+        let mounts = NftCollection::new("Mounts", "MNT", "", Chain::Polygon, "0xmounts");
+        let outfits = NftCollection::new("Outfits", "OUT", "", Chain::Polygon, "0xoutfits");
+        manager.register_collection(mounts.clone()).unwrap();
+        manager.register_collection(outfits.clone()).unwrap();
+
+        let common = seed_nft(&manager, &mounts, "1", "Common", "Fire");
+        let rare = seed_nft(&manager, &mounts, "2", "Rare", "Fire");
+        let cheap_rare = seed_nft(&manager, &mounts, "3", "Rare", "Ice");
+        let other = seed_nft(&manager, &outfits, "1", "Common", "Fire");
+
+        manager.create_listing(NftListing::new(common, "0xa", 100)).unwrap();
+        manager.create_listing(NftListing::new(rare, "0xb", 500)).unwrap();
+        let sold = NftListing::new(cheap_rare, "0xc", 50);
+        let sold_id = sold.id;
+        manager.create_listing(sold).unwrap();
+        manager.create_listing(NftListing::new(other, "0xd", 10)).unwrap();
+        assert!(manager.set_listing_status(sold_id, NftListingStatus::Sold).unwrap());
+
+        let stats = manager.floor_prices(mounts.id).unwrap();
+        assert_eq!(stats.floor, Some(100));
+        assert_eq!(stats.active_listings, 2);
+        assert_eq!(stats.trait_floor("Rarity", "Rare"), Some(500));
+        assert_eq!(stats.trait_floor("Element", "Fire"), Some(100));
+        // Only listed through a sold listing
+        assert_eq!(stats.trait_floor("Element", "Ice"), None);
+    }
+
+    #[test]
+    fn test_floor_without_listings() {
+        let manager = NftManager::new();
+        let collection = NftCollection::new("Mounts", "MNT", "", Chain::Polygon, "0xmounts");
+        manager.register_collection(collection.clone()).unwrap();
+        seed_nft(&manager, &collection, "1", "Common", "Fire");
+
+        let stats = manager.floor_prices(collection.id).unwrap();
+        assert_eq!(stats.floor, None);
+        assert_eq!(stats.active_listings, 0);
+        assert!(stats.trait_floors.is_empty());
+        assert!(manager.floor_prices(Uuid::new_v4()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_picks_up_changed_owners() {
+        const LINKED: &str = "0x52908400098527886E0F7030069857D2E4169EE7";
+        let (seller, buyer) = (Uuid::new_v4(), Uuid::new_v4());
+        let wallets = Arc::new(WalletManager::new());
+        wallets
+            .add_wallet(UserWallet::new(buyer, Chain::Ethereum, LINKED.to_string(), WalletType::MetaMask))
+            .unwrap();
+        let manager = NftManager::new().with_wallet_manager(wallets);
+        let mounts = NftCollection::new("Mounts", "MNT", "", Chain::Polygon, "0xmounts");
+        let other_chain = NftCollection::new("Mounts", "MNT", "", Chain::Ethereum, "0xmounts");
+
+        let kept = seed_nft(&manager, &mounts, "1", "Common", "Fire");
+        let bought = seed_nft(&manager, &mounts, "2", "Rare", "Fire");
+        let sold_away = seed_nft(&manager, &mounts, "3", "Rare", "Ice");
+        let failing = seed_nft(&manager, &mounts, "4", "Common", "Ice");
+        let untouched = seed_nft(&manager, &other_chain, "2", "Common", "Fire");
+        for id in [bought, sold_away, failing, untouched] {
+            let nft = manager.get_nft(id).unwrap().unwrap().with_user(seller);
+            manager.store_nft(nft).unwrap();
+        }
+
+        let provider = MockOwners(HashMap::from([
+            ("1".to_string(), "0xOWNER".to_string()),
+            ("2".to_string(), LINKED.to_ascii_lowercase()),
+            ("3".to_string(), "0xstranger".to_string()),
+        ]));
+        let summary = manager.reconcile_ownership(Chain::Polygon, &provider).await.unwrap();
+        assert_eq!(summary, ReconcileSummary { updated: 2, unchanged: 1, errored: 1 });
+
+        assert_eq!(manager.get_nft(kept).unwrap().unwrap().owner_address, "0xowner");
+        let bought = manager.get_nft(bought).unwrap().unwrap();
+        assert_eq!((bought.owner_address.as_str(), bought.owner_user_id), (LINKED.to_ascii_lowercase().as_str(), Some(buyer)));
+        let sold_away = manager.get_nft(sold_away).unwrap().unwrap();
+        assert_eq!((sold_away.owner_address.as_str(), sold_away.owner_user_id), ("0xstranger", None));
+
+        let seller_nfts: Vec<Uuid> = manager.get_user_nfts(seller).unwrap().into_iter().map(|n| n.id).collect();
+        assert_eq!(seller_nfts.len(), 2);
+        assert!(seller_nfts.contains(&failing) && seller_nfts.contains(&untouched));
+        assert_eq!(manager.get_user_nfts(buyer).unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_continues_past_failures_across_batches() {
+        let manager = NftManager::new();
+        let collection = NftCollection::new("Mounts", "MNT", "", Chain::Polygon, "0xmounts");
+        let count = RECONCILE_BATCH_SIZE * 2 + 5;
+        let mut owners = HashMap::new();
+        for token in 0..count {
+            seed_nft(&manager, &collection, &token.to_string(), "Common", "Fire");
+            // Every tenth lookup fails
+            if token % 10 != 0 {
+                owners.insert(token.to_string(), format!("0xnew{}", token));
+            }
+        }
+
+        let summary = manager.reconcile_ownership(Chain::Polygon, &MockOwners(owners)).await.unwrap();
+        assert_eq!(summary.errored, count.div_ceil(10));
+        assert_eq!(summary.updated, count - summary.errored);
+        assert_eq!(summary.unchanged, 0);
+        assert_eq!(manager.get_address_nfts("0xowner").unwrap().len(), summary.errored);
+    }
+}
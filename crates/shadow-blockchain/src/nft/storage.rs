@@ -184,11 +184,12 @@ impl NftStorage {
         let mut by_id = self.by_id.write()
             .map_err(|_| BlockchainError::InternalError("Lock poisoned".into()))?;
 
-        if by_id.contains_key(&nft.id) {
-            by_id.insert(nft.id, nft);
-            Ok(())
-        } else {
-            Err(BlockchainError::NftNotFound(nft.id.to_string()))
+        match by_id.get_mut(&nft.id) {
+            Some(stored) => {
+                *stored = nft;
+                Ok(())
+            }
+            None => Err(BlockchainError::NftNotFound(nft.id.to_string())),
         }
     }
 
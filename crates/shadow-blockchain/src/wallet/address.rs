@@ -0,0 +1,116 @@
+//! Chain-specific wallet address validation
+//!
+//! Addresses are checked for the format of their chain before a wallet is
+//! linked: EVM chains take `0x` followed by 40 hex digits, Starknet takes a
+//! `0x` prefixed field element, and the Bitcoin chains take bech32/bech32m
+//! addresses with a valid checksum.
+
+use crate::{BlockchainError, Chain, Result};
+
+/// Bech32 data alphabet
+const BECH32_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const BECH32_GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+const BECH32_CONST: u32 = 1;
+const BECH32M_CONST: u32 = 0x2bc830a3;
+
+/// Starknet field prime (2^251 + 17 * 2^192 + 1) as 64 hex digits
+const STARKNET_PRIME_HEX: &str = "0800000000000011000000000000000000000000000000000000000000000001";
+
+/// Validate that `address` is well-formed for `chain`
+pub fn validate_address(chain: Chain, address: &str) -> Result<()> {
+    let valid = if chain.is_evm() {
+        is_evm_address(address)
+    } else if chain.is_starknet() {
+        is_starknet_felt(address)
+    } else {
+        is_bech32_address(address, bech32_hrp(chain))
+    };
+
+    if valid {
+        Ok(())
+    } else {
+        Err(BlockchainError::InvalidAddress(format!(
+            "{} is not a valid {:?} address",
+            address, chain
+        )))
+    }
+}
+
+/// Canonical form of an address, used to detect the same address written
+/// differently (hex case, leading zeros of a felt)
+pub fn normalize_address(chain: Chain, address: &str) -> String {
+    let lower = address.to_ascii_lowercase();
+    if chain.is_starknet() {
+        let digits = lower.trim_start_matches("0x").trim_start_matches('0');
+        format!("0x{}", if digits.is_empty() { "0" } else { digits })
+    } else {
+        lower
+    }
+}
+
+/// Human-readable part of a chain's bech32 addresses
+fn bech32_hrp(chain: Chain) -> &'static str {
+    match chain {
+        Chain::BitcoinTestnet => "tb",
+        Chain::Spark => "sp",
+        _ => "bc",
+    }
+}
+
+fn is_evm_address(address: &str) -> bool {
+    address
+        .strip_prefix("0x")
+        .is_some_and(|hex| hex.len() == 40 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+fn is_starknet_felt(address: &str) -> bool {
+    let Some(hex) = address.strip_prefix("0x") else {
+        return false;
+    };
+    if hex.is_empty() || hex.len() > 64 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return false;
+    }
+    // Must be below the field prime; equal-length hex compares like numbers
+    let padded = format!("{:0>64}", hex.to_ascii_lowercase());
+    padded.as_str() < STARKNET_PRIME_HEX
+}
+
+fn is_bech32_address(address: &str, hrp: &str) -> bool {
+    let mixed_case = address.chars().any(|c| c.is_ascii_lowercase()) && address.chars().any(|c| c.is_ascii_uppercase());
+    if address.len() > 90 || mixed_case {
+        return false;
+    }
+    let address = address.to_ascii_lowercase();
+    let Some((prefix, data)) = address.rsplit_once('1') else {
+        return false;
+    };
+    if prefix != hrp || data.len() < 6 {
+        return false;
+    }
+
+    let mut values: Vec<u8> = prefix.bytes().map(|b| b >> 5).collect();
+    values.push(0);
+    values.extend(prefix.bytes().map(|b| b & 31));
+    for c in data.bytes() {
+        match BECH32_CHARSET.iter().position(|&x| x == c) {
+            Some(value) => values.push(value as u8),
+            None => return false,
+        }
+    }
+
+    matches!(bech32_polymod(&values), BECH32_CONST | BECH32M_CONST)
+}
+
+fn bech32_polymod(values: &[u8]) -> u32 {
+    let mut checksum: u32 = 1;
+    for &value in values {
+        let top = checksum >> 25;
+        checksum = ((checksum & 0x1ffffff) << 5) ^ value as u32;
+        for (i, generator) in BECH32_GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+    }
+    checksum
+}
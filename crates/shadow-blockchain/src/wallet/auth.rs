@@ -31,7 +31,7 @@ impl WalletAuthChallenge {
 
         let message = Self::format_message(
             domain,
-            &address,
+            address,
             uri,
             &nonce,
             issued_at,
@@ -2,9 +2,11 @@
 //!
 //! Multi-chain wallet support for authentication and transactions.
 
+pub mod address;
 pub mod auth;
 pub mod connect;
 
+pub use address::{normalize_address, validate_address};
 pub use auth::{WalletAuth, WalletAuthChallenge, WalletAuthResult};
 pub use connect::{WalletConnection, WalletType};
 
@@ -54,24 +56,55 @@ impl UserWallet {
     }
 }
 
+/// Default cap on wallets linked to one account, across all chains
+pub const DEFAULT_MAX_WALLETS_PER_ACCOUNT: usize = 10;
+
 /// Wallet manager for handling user wallets
 pub struct WalletManager {
     // In production, would include database pool
     wallets: std::sync::RwLock<std::collections::HashMap<Uuid, Vec<UserWallet>>>,
+    max_wallets_per_account: usize,
 }
 
 impl WalletManager {
     pub fn new() -> Self {
         Self {
             wallets: std::sync::RwLock::new(std::collections::HashMap::new()),
+            max_wallets_per_account: DEFAULT_MAX_WALLETS_PER_ACCOUNT,
         }
     }
 
-    /// Add a wallet for a user
+    /// Set the cap on wallets linked to one account
+    pub fn with_max_wallets_per_account(mut self, max: usize) -> Self {
+        self.max_wallets_per_account = max;
+        self
+    }
+
+    /// Add a wallet for a user. The address must match the chain's format,
+    /// the account must be below its wallet limit and the address must not
+    /// be linked to another account on any chain sharing its format. The
+    /// same account can link it once per chain.
     pub fn add_wallet(&self, wallet: UserWallet) -> Result<()> {
+        validate_address(wallet.chain, &wallet.address)?;
+
         let mut wallets = self.wallets.write()
             .map_err(|_| BlockchainError::InternalError("Lock poisoned".into()))?;
 
+        let linked = wallets.get(&wallet.user_id).map_or(0, Vec::len);
+        if linked >= self.max_wallets_per_account {
+            return Err(BlockchainError::WalletLimitReached(self.max_wallets_per_account));
+        }
+
+        let address = normalize_address(wallet.chain, &wallet.address);
+        let duplicate = wallets.values().flatten().any(|w| {
+            same_address_family(w.chain, wallet.chain)
+                && normalize_address(w.chain, &w.address) == address
+                && (w.user_id != wallet.user_id || w.chain == wallet.chain)
+        });
+        if duplicate {
+            return Err(BlockchainError::WalletAlreadyLinked(wallet.address));
+        }
+
         wallets
             .entry(wallet.user_id)
             .or_default()
@@ -122,6 +155,26 @@ impl WalletManager {
         Ok(())
     }
 
+    /// User who linked `address`: the one who linked it on `chain` itself,
+    /// or else on another chain sharing its address format
+    pub fn find_wallet_owner(&self, chain: Chain, address: &str) -> Result<Option<Uuid>> {
+        let wallets = self.wallets.read()
+            .map_err(|_| BlockchainError::InternalError("Lock poisoned".into()))?;
+
+        let address = normalize_address(chain, address);
+        let mut family_owner = None;
+        for w in wallets.values().flatten() {
+            if !same_address_family(w.chain, chain) || normalize_address(w.chain, &w.address) != address {
+                continue;
+            }
+            if w.chain == chain {
+                return Ok(Some(w.user_id));
+            }
+            family_owner.get_or_insert(w.user_id);
+        }
+        Ok(family_owner)
+    }
+
     /// Remove a wallet
     pub fn remove_wallet(&self, user_id: Uuid, wallet_id: Uuid) -> Result<bool> {
         let mut wallets = self.wallets.write()
@@ -142,3 +195,77 @@ impl Default for WalletManager {
         Self::new()
     }
 }
+
+/// Whether two chains share an address space, so one address on both is
+/// the same key (e.g. an EVM address on Ethereum and Polygon)
+fn same_address_family(a: Chain, b: Chain) -> bool {
+    (a.is_evm() && b.is_evm()) || (a.is_starknet() && b.is_starknet()) || a == b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EVM_ADDRESS: &str = "0x52908400098527886E0F7030069857D2E4169EE7";
+
+    #[test]
+    fn test_invalid_address_format_rejected() {
+        let manager = WalletManager::new();
+        let user = Uuid::new_v4();
+
+        for (chain, address) in [
+            (Chain::Ethereum, "0x52908400098527886E0F7030069857D2E4169EE"),
+            (Chain::Polygon, "52908400098527886E0F7030069857D2E4169EE7"),
+            (Chain::Starknet, "0x0800000000000011000000000000000000000000000000000000000000000001"),
+            (Chain::Bitcoin, "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5"),
+            (Chain::BitcoinTestnet, "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"),
+        ] {
+            let wallet = UserWallet::new(user, chain, address.to_string(), WalletType::MetaMask);
+            assert!(matches!(manager.add_wallet(wallet), Err(BlockchainError::InvalidAddress(_))));
+        }
+
+        for (chain, address) in [
+            (Chain::Ethereum, EVM_ADDRESS),
+            (Chain::Starknet, "0x49d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7"),
+            (Chain::Bitcoin, "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"),
+        ] {
+            let wallet = UserWallet::new(user, chain, address.to_string(), WalletType::MetaMask);
+            assert!(manager.add_wallet(wallet).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_over_limit_and_duplicate_links_rejected() {
+        let manager = WalletManager::new().with_max_wallets_per_account(2);
+        let (user, other) = (Uuid::new_v4(), Uuid::new_v4());
+        let wallet = |user_id, address: &str| UserWallet::new(user_id, Chain::Ethereum, address.to_string(), WalletType::MetaMask);
+
+        manager.add_wallet(wallet(user, EVM_ADDRESS)).unwrap();
+        manager.add_wallet(wallet(user, "0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae")).unwrap();
+        assert!(matches!(
+            manager.add_wallet(wallet(user, "0xab5801a7d398351b8be11c439e05c5b3259aec9b")),
+            Err(BlockchainError::WalletLimitReached(2))
+        ));
+
+        // The same address in another case, on another EVM chain, for another account
+        let polygon = UserWallet::new(other, Chain::Polygon, EVM_ADDRESS.to_lowercase(), WalletType::MetaMask);
+        assert!(matches!(manager.add_wallet(polygon), Err(BlockchainError::WalletAlreadyLinked(_))));
+        assert!(manager.get_user_wallets(other).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_same_account_links_evm_address_on_each_chain() {
+        let manager = WalletManager::new();
+        let user = Uuid::new_v4();
+        let wallet = |chain| UserWallet::new(user, chain, EVM_ADDRESS.to_string(), WalletType::MetaMask);
+
+        manager.add_wallet(wallet(Chain::Ethereum)).unwrap();
+        manager.add_wallet(wallet(Chain::Polygon)).unwrap();
+        assert_eq!(manager.get_user_wallets(user).unwrap().len(), 2);
+        assert_eq!(manager.find_wallet_owner(Chain::Polygon, EVM_ADDRESS).unwrap(), Some(user));
+        assert_eq!(manager.find_wallet_owner(Chain::Base, &EVM_ADDRESS.to_lowercase()).unwrap(), Some(user));
+
+        // Twice on the same chain is still a duplicate
+        assert!(matches!(manager.add_wallet(wallet(Chain::Polygon)), Err(BlockchainError::WalletAlreadyLinked(_))));
+    }
+}
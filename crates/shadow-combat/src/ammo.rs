@@ -0,0 +1,215 @@
+//! Ammunition for distance weapons
+//!
+//! Bows and crossbows fire ammunition from the ammo slot and reject
+//! attacks when the slot is empty or holds the wrong ammo type. Throwing
+//! weapons (spears, stars, knives) are their own ammunition and need
+//! nothing in the slot. Conjured ammo is a single item with charges; each
+//! shot uses one charge and the item is gone when they run out.
+
+use serde::{Deserialize, Serialize};
+use shadow_world::item::{AmmoType, ItemType, WeaponType};
+
+use crate::spell::Spell;
+use crate::{CombatError, Result};
+
+/// Ammunition configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmmoConfig {
+    /// Whether shots use up ammunition
+    pub consume_ammo: bool,
+    /// Charges of conjured ammo when the spell does not set a count
+    pub conjured_charges: u16,
+}
+
+impl Default for AmmoConfig {
+    fn default() -> Self {
+        Self {
+            consume_ammo: true,
+            conjured_charges: 100,
+        }
+    }
+}
+
+impl AmmoConfig {
+    /// Ammo created by a conjure spell
+    pub fn conjure(&self, spell: &Spell, ammo_type: AmmoType, attack: i32) -> AmmoStack {
+        let charges = if spell.conjure_count > 0 { spell.conjure_count } else { self.conjured_charges };
+        AmmoStack::conjured(spell.conjure_item_id, ammo_type, attack, charges)
+    }
+}
+
+/// A distance weapon
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DistanceWeapon {
+    pub item_id: u16,
+    pub attack: i32,
+    pub hit_chance: i32,
+    /// Ammunition the weapon fires; `None` for throwing weapons
+    pub ammo_type: Option<AmmoType>,
+}
+
+impl DistanceWeapon {
+    /// Build from an item type; `None` unless it is a distance weapon
+    pub fn from_item_type(item: &ItemType) -> Option<Self> {
+        if item.weapon_type != Some(WeaponType::Distance) {
+            return None;
+        }
+        Some(Self {
+            item_id: item.id,
+            attack: item.attack.unwrap_or(0),
+            hit_chance: item.hit_chance.unwrap_or(0),
+            ammo_type: item.ammo_type.filter(|ammo| matches!(ammo, AmmoType::Arrow | AmmoType::Bolt)),
+        })
+    }
+}
+
+/// Ammunition in the ammo slot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmmoStack {
+    pub item_id: u16,
+    pub ammo_type: AmmoType,
+    pub attack: i32,
+    pub count: u16,
+    /// Remaining charges of conjured ammo
+    pub charges: Option<u16>,
+}
+
+impl AmmoStack {
+    pub fn new(item_id: u16, ammo_type: AmmoType, attack: i32, count: u16) -> Self {
+        Self {
+            item_id,
+            ammo_type,
+            attack,
+            count,
+            charges: None,
+        }
+    }
+
+    pub fn conjured(item_id: u16, ammo_type: AmmoType, attack: i32, charges: u16) -> Self {
+        Self {
+            item_id,
+            ammo_type,
+            attack,
+            count: 1,
+            charges: Some(charges),
+        }
+    }
+
+    pub fn is_conjured(&self) -> bool {
+        self.charges.is_some()
+    }
+
+    /// Shots left before the slot is empty
+    pub fn remaining_shots(&self) -> u16 {
+        self.charges.unwrap_or(self.count)
+    }
+
+    /// Use up one shot. Returns true when the stack is depleted.
+    pub fn consume(&mut self) -> bool {
+        match &mut self.charges {
+            Some(charges) => {
+                *charges = charges.saturating_sub(1);
+                if *charges == 0 {
+                    self.count = 0;
+                }
+            }
+            None => self.count = self.count.saturating_sub(1),
+        }
+        self.remaining_shots() == 0
+    }
+}
+
+/// Attack of the ammo `weapon` fires from `ammo`, or why it cannot fire
+pub fn check_ammo(weapon: &DistanceWeapon, ammo: Option<&AmmoStack>) -> Result<i32> {
+    let Some(required) = weapon.ammo_type else {
+        return Ok(0);
+    };
+    let ammo = ammo.filter(|ammo| ammo.remaining_shots() > 0).ok_or(CombatError::NoAmmo)?;
+    if ammo.ammo_type != required {
+        return Err(CombatError::IncompatibleAmmo);
+    }
+    Ok(ammo.attack)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combat::{CombatConfig, CombatSystem};
+    use crate::spell::{SpellLoader, SpellType};
+    use shadow_world::creature::{Creature, CreatureType};
+    use shadow_world::position::Position;
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    fn combat() -> CombatSystem {
+        CombatSystem::new(CombatConfig::default(), Arc::new(RwLock::new(SpellLoader::new())))
+    }
+
+    fn creatures() -> (Creature, Creature) {
+        let mut paladin = Creature::new("Paladin".to_string(), CreatureType::Player, Position::new(100, 100, 7));
+        paladin.stats.level = 100;
+        let mut target = Creature::new("Rat".to_string(), CreatureType::Monster, Position::new(103, 100, 7));
+        target.stats.health = 10_000;
+        target.stats.max_health = 10_000;
+        (paladin, target)
+    }
+
+    fn bow() -> DistanceWeapon {
+        DistanceWeapon { item_id: 3350, attack: 0, hit_chance: 90, ammo_type: Some(AmmoType::Arrow) }
+    }
+
+    #[tokio::test]
+    async fn test_distance_attack_consumes_ammo() {
+        let mut combat = combat();
+        let (mut paladin, mut target) = creatures();
+        let mut ammo = Some(AmmoStack::new(3447, AmmoType::Arrow, 25, 2));
+
+        combat.distance_attack(&mut paladin, &mut target, &bow(), &mut ammo, 0).await.unwrap();
+        assert_eq!(ammo.as_ref().unwrap().count, 1);
+
+        // The last arrow empties the slot
+        combat.distance_attack(&mut paladin, &mut target, &bow(), &mut ammo, 0).await.unwrap();
+        assert!(ammo.is_none());
+        assert!(matches!(
+            combat.distance_attack(&mut paladin, &mut target, &bow(), &mut ammo, 0).await,
+            Err(CombatError::NoAmmo)
+        ));
+
+        // Bolts do not fit a bow and are kept
+        let mut bolts = Some(AmmoStack::new(3446, AmmoType::Bolt, 30, 10));
+        assert!(matches!(
+            combat.distance_attack(&mut paladin, &mut target, &bow(), &mut bolts, 0).await,
+            Err(CombatError::IncompatibleAmmo)
+        ));
+        assert_eq!(bolts.unwrap().count, 10);
+
+        // Throwing weapons need no ammo
+        let spear = DistanceWeapon { item_id: 3277, attack: 25, hit_chance: 76, ammo_type: None };
+        assert!(combat.distance_attack(&mut paladin, &mut target, &spear, &mut None, 0).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_conjured_ammo_charges_deplete() {
+        let mut spell = Spell::new(100, "Conjure Arrow".to_string(), "exevo con".to_string(), SpellType::Conjure);
+        spell.conjure_item_id = 3447;
+        spell.conjure_count = 3;
+
+        let config = AmmoConfig::default();
+        let conjured = config.conjure(&spell, AmmoType::Arrow, 25);
+        assert!(conjured.is_conjured());
+        assert_eq!(conjured.remaining_shots(), 3);
+
+        let mut combat = combat();
+        let (mut paladin, mut target) = creatures();
+        let mut ammo = Some(conjured);
+        for left in [2, 1] {
+            combat.distance_attack(&mut paladin, &mut target, &bow(), &mut ammo, 0).await.unwrap();
+            assert_eq!(ammo.as_ref().unwrap().charges, Some(left));
+        }
+        combat.distance_attack(&mut paladin, &mut target, &bow(), &mut ammo, 0).await.unwrap();
+        assert!(ammo.is_none());
+
+        spell.conjure_count = 0;
+        assert_eq!(config.conjure(&spell, AmmoType::Arrow, 25).remaining_shots(), config.conjured_charges);
+    }
+}
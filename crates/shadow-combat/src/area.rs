@@ -1,7 +1,12 @@
 //! Area effects - spell areas and damage zones
 
+use shadow_world::creature::Creature;
 use shadow_world::position::{Direction, Position};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::damage::DamageType;
+use crate::ruleset::RulesetFlags;
 
 /// Area effect types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -48,6 +53,18 @@ impl AreaEffect {
         effect
     }
 
+    /// Create a directional area effect fired by a caster, following the
+    /// ruleset's directional shooting rule
+    pub fn aimed(
+        area_type: AreaType,
+        center: Position,
+        facing: Direction,
+        toward_target: Option<Direction>,
+        rules: &RulesetFlags,
+    ) -> Self {
+        Self::new(area_type, center, Some(rules.attack_direction(facing, toward_target)))
+    }
+
     /// Calculate affected positions based on area type
     fn calculate_positions(&mut self) {
         self.positions.clear();
@@ -234,6 +251,99 @@ impl AreaEffect {
     }
 }
 
+/// Who an area effect may hit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AreaTargetPolicy {
+    /// Player-cast areas may damage other players
+    pub pvp_enabled: bool,
+    /// Player-cast areas may damage members of the caster's party
+    pub party_damage: bool,
+    /// Creatures standing in protection zones are never hit
+    pub exclude_protection_zones: bool,
+}
+
+impl AreaTargetPolicy {
+    /// PvE realms: areas never hit players
+    pub fn pve() -> Self {
+        Self {
+            pvp_enabled: false,
+            party_damage: false,
+            exclude_protection_zones: true,
+        }
+    }
+
+    /// PvP realms: areas hit other players, party members only if enabled
+    pub fn pvp(party_damage: bool) -> Self {
+        Self {
+            pvp_enabled: true,
+            party_damage,
+            exclude_protection_zones: true,
+        }
+    }
+
+    /// Whether `target` is affected by an area cast by `caster`
+    pub fn can_hit(
+        &self,
+        caster: &Creature,
+        caster_ctx: &AreaTargetContext,
+        target: &Creature,
+        target_ctx: &AreaTargetContext,
+        damage_type: DamageType,
+    ) -> bool {
+        if target.id == caster.id || target.summon_master_id == Some(caster.id) {
+            return false;
+        }
+        if self.exclude_protection_zones && target_ctx.in_protection_zone {
+            return false;
+        }
+        if target_ctx.immunities.contains(&damage_type) {
+            return false;
+        }
+
+        // Monster areas only hurt players and their summons
+        let player_side = |c: &Creature, ctx: &AreaTargetContext| c.is_player() || ctx.player_summon;
+        if !player_side(caster, caster_ctx) {
+            return player_side(target, target_ctx);
+        }
+        if !player_side(target, target_ctx) {
+            return true;
+        }
+
+        if !self.pvp_enabled {
+            return false;
+        }
+        let same_party = caster_ctx.party_id.is_some() && caster_ctx.party_id == target_ctx.party_id;
+        !same_party || self.party_damage
+    }
+}
+
+impl Default for AreaTargetPolicy {
+    fn default() -> Self {
+        Self::pve()
+    }
+}
+
+/// Per-creature state the area policy needs beyond the creature itself.
+/// Summons carry their master's party.
+#[derive(Debug, Clone, Default)]
+pub struct AreaTargetContext {
+    pub party_id: Option<Uuid>,
+    pub in_protection_zone: bool,
+    /// Creature is a summon of a player
+    pub player_summon: bool,
+    /// Damage types the creature is immune to
+    pub immunities: Vec<DamageType>,
+}
+
+impl AreaTargetContext {
+    pub fn in_party(party_id: Uuid) -> Self {
+        Self {
+            party_id: Some(party_id),
+            ..Default::default()
+        }
+    }
+}
+
 /// Predefined area matrices
 pub mod areas {
     /// Great fireball area
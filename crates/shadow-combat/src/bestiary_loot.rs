@@ -0,0 +1,210 @@
+//! Bestiary loot list - what a creature drops, as far as a player knows
+//!
+//! The list is derived from the creature's `LootTable`, the same table
+//! `LootGenerator` rolls from, so the chances shown are the chances used.
+//! Items inside containers are listed with the container's chance folded
+//! in. Each entry falls into a rarity tier by its chance, and a tier is
+//! revealed once the player's bestiary entry reaches the tier's stage:
+//! common drops show up after the first kills, very rare ones only when
+//! the entry is complete.
+
+use serde::{Deserialize, Serialize};
+
+use crate::loot::{LootEntry, LootTable};
+
+/// Rarity tier of a drop, by its base chance
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum LootRarity {
+    /// 25% and above
+    Common,
+    /// 5% to 25%
+    Uncommon,
+    /// 1% to 5%
+    SemiRare,
+    /// 0.1% to 1%
+    Rare,
+    /// Below 0.1%
+    VeryRare,
+}
+
+impl LootRarity {
+    pub fn from_chance(chance: f32) -> Self {
+        if chance >= 25.0 {
+            LootRarity::Common
+        } else if chance >= 5.0 {
+            LootRarity::Uncommon
+        } else if chance >= 1.0 {
+            LootRarity::SemiRare
+        } else if chance >= 0.1 {
+            LootRarity::Rare
+        } else {
+            LootRarity::VeryRare
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            LootRarity::Common => "common",
+            LootRarity::Uncommon => "uncommon",
+            LootRarity::SemiRare => "semi-rare",
+            LootRarity::Rare => "rare",
+            LootRarity::VeryRare => "very rare",
+        }
+    }
+}
+
+/// Bestiary stage (1-4) at which each rarity tier is revealed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BestiaryLootConfig {
+    pub common_stage: u8,
+    pub uncommon_stage: u8,
+    pub semi_rare_stage: u8,
+    pub rare_stage: u8,
+    pub very_rare_stage: u8,
+}
+
+impl Default for BestiaryLootConfig {
+    fn default() -> Self {
+        Self {
+            common_stage: 1,
+            uncommon_stage: 1,
+            semi_rare_stage: 2,
+            rare_stage: 3,
+            very_rare_stage: 4,
+        }
+    }
+}
+
+/// A drop revealed in the bestiary
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LootPreviewEntry {
+    pub item_id: u16,
+    pub name: Option<String>,
+    pub rarity: LootRarity,
+    /// Chance per kill (0.0 - 100.0) at the given loot rate
+    pub chance: f32,
+    pub count_min: u16,
+    pub count_max: u16,
+}
+
+/// A creature's loot list at a bestiary stage
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LootPreview {
+    pub creature_name: String,
+    /// Revealed drops, most likely first
+    pub entries: Vec<LootPreviewEntry>,
+    /// Drops not revealed yet
+    pub hidden: usize,
+}
+
+impl BestiaryLootConfig {
+    /// Bestiary stage at which `rarity` is revealed
+    pub fn unlock_stage(&self, rarity: LootRarity) -> u8 {
+        match rarity {
+            LootRarity::Common => self.common_stage,
+            LootRarity::Uncommon => self.uncommon_stage,
+            LootRarity::SemiRare => self.semi_rare_stage,
+            LootRarity::Rare => self.rare_stage,
+            LootRarity::VeryRare => self.very_rare_stage,
+        }
+    }
+
+    /// Loot list of `table` for a player at bestiary `stage`, with chances
+    /// scaled by `loot_rate` the way `LootGenerator` scales them
+    pub fn preview(&self, table: &LootTable, stage: u8, loot_rate: f32) -> LootPreview {
+        let mut drops = Vec::new();
+        flatten(&table.entries, 100.0, &mut drops);
+
+        let total = drops.len();
+        let mut entries: Vec<LootPreviewEntry> = drops
+            .into_iter()
+            .filter_map(|(entry, chance)| {
+                let rarity = LootRarity::from_chance(chance);
+                (stage >= self.unlock_stage(rarity)).then(|| LootPreviewEntry {
+                    item_id: entry.item_id,
+                    name: entry.name.clone(),
+                    rarity,
+                    chance: (chance * loot_rate).min(100.0),
+                    count_min: entry.count_min,
+                    count_max: entry.count_max,
+                })
+            })
+            .collect();
+        entries.sort_by(|a, b| b.chance.total_cmp(&a.chance).then_with(|| a.item_id.cmp(&b.item_id)));
+
+        LootPreview {
+            creature_name: table.creature_name.clone(),
+            hidden: total - entries.len(),
+            entries,
+        }
+    }
+}
+
+/// Every entry with its chance per kill, folding in the chance of the
+/// containers it sits in
+fn flatten<'a>(entries: &'a [LootEntry], parent_chance: f32, out: &mut Vec<(&'a LootEntry, f32)>) {
+    for entry in entries {
+        let chance = entry.chance.clamp(0.0, 100.0) * parent_chance / 100.0;
+        out.push((entry, chance));
+        flatten(&entry.contents, chance, out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loot::{LootConfig, LootGenerator};
+
+    fn dragon() -> LootTable {
+        LootTable::new("Dragon")
+            .add_entry(LootEntry::stackable(3031, 90.0, 1, 105).with_name("gold coin"))
+            .add_entry(LootEntry::new(3577, 30.0).with_name("dragon ham"))
+            .add_entry(LootEntry::new(3416, 8.0).with_name("dragon shield"))
+            .add_entry(LootEntry::container(2853, 10.0, vec![LootEntry::new(3349, 20.0).with_name("dragon hammer")]))
+            .add_entry(LootEntry::new(7430, 0.05).with_name("dragonbone staff"))
+    }
+
+    #[test]
+    fn test_partial_bestiary_hides_rarer_loot() {
+        let config = BestiaryLootConfig::default();
+        let table = dragon();
+
+        assert!(config.preview(&table, 0, 1.0).entries.is_empty());
+
+        let stage_one = config.preview(&table, 1, 1.0);
+        let items: Vec<u16> = stage_one.entries.iter().map(|e| e.item_id).collect();
+        assert_eq!(items, vec![3031, 3577, 2853, 3416]);
+        assert_eq!(stage_one.hidden, 2);
+
+        // The hammer in the bag drops 20% of 10% of the time: semi-rare
+        let stage_two = config.preview(&table, 2, 1.0);
+        assert!(stage_two.entries.iter().any(|e| e.item_id == 3349 && e.rarity == LootRarity::SemiRare));
+        assert_eq!(stage_two.hidden, 1);
+
+        let complete = config.preview(&table, 4, 1.0);
+        assert_eq!((complete.entries.len(), complete.hidden), (6, 0));
+        assert_eq!(complete.entries.last().unwrap().rarity, LootRarity::VeryRare);
+    }
+
+    #[test]
+    fn test_chances_match_table() {
+        let config = BestiaryLootConfig::default();
+        let table = dragon();
+        let preview = config.preview(&table, 4, 1.0);
+        let chance = |item_id: u16| preview.entries.iter().find(|e| e.item_id == item_id).unwrap().chance;
+
+        for entry in &table.entries {
+            assert_eq!(chance(entry.item_id), entry.chance);
+        }
+        assert!((chance(3349) - 2.0).abs() < 1e-6);
+
+        // The generator's loot rate scales chances, capped at 100%
+        let mut generator = LootGenerator::new(LootConfig { loot_rate: 2.0, ..Default::default() });
+        generator.register_table(table);
+        let doubled = generator.loot_preview("dragon", 4, &config).unwrap();
+        assert_eq!(doubled.entries[0].chance, 100.0);
+        assert_eq!(doubled.entries.iter().find(|e| e.item_id == 3416).unwrap().chance, 16.0);
+        assert_eq!(doubled.entries.iter().find(|e| e.item_id == 3416).unwrap().rarity, LootRarity::Uncommon);
+    }
+}
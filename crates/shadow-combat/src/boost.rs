@@ -0,0 +1,270 @@
+//! Experience and loot boosts
+//!
+//! Temporary multipliers granted by items such as XP scrolls or store
+//! boosts. Boost time only runs while the character is online: on logout
+//! the remaining duration is saved and resumed on the next login.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// What a boost multiplies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BoostType {
+    Experience,
+    Loot,
+}
+
+/// How a new boost combines with active boosts of the same type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BoostStacking {
+    /// Active bonuses add together
+    Additive,
+    /// Only the highest active bonus applies; timers run independently
+    Highest,
+    /// A boost with the same bonus extends the active one instead of stacking
+    ExtendDuration,
+}
+
+/// Boost configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoostConfig {
+    pub experience_stacking: BoostStacking,
+    pub loot_stacking: BoostStacking,
+    /// Cap on the combined bonus percentage per type
+    pub max_bonus_percent: u32,
+}
+
+impl Default for BoostConfig {
+    fn default() -> Self {
+        Self {
+            experience_stacking: BoostStacking::ExtendDuration,
+            loot_stacking: BoostStacking::Highest,
+            max_bonus_percent: 200,
+        }
+    }
+}
+
+impl BoostConfig {
+    pub fn stacking(&self, boost_type: BoostType) -> BoostStacking {
+        match boost_type {
+            BoostType::Experience => self.experience_stacking,
+            BoostType::Loot => self.loot_stacking,
+        }
+    }
+}
+
+/// An active boost
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActiveBoost {
+    pub boost_type: BoostType,
+    /// Bonus percentage (50 = +50%)
+    pub bonus_percent: u32,
+    /// Expiry in server time (ms)
+    pub expires_at: u64,
+}
+
+impl ActiveBoost {
+    pub fn is_expired(&self, current_time: u64) -> bool {
+        current_time >= self.expires_at
+    }
+
+    pub fn remaining(&self, current_time: u64) -> u64 {
+        self.expires_at.saturating_sub(current_time)
+    }
+}
+
+/// Boost saved on logout with its remaining duration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredBoost {
+    pub boost_type: BoostType,
+    pub bonus_percent: u32,
+    pub remaining_ms: u64,
+}
+
+/// Tracks active boosts per character
+#[derive(Debug, Default)]
+pub struct BoostManager {
+    config: BoostConfig,
+    boosts: HashMap<Uuid, Vec<ActiveBoost>>,
+}
+
+impl BoostManager {
+    pub fn new(config: BoostConfig) -> Self {
+        Self {
+            config,
+            boosts: HashMap::new(),
+        }
+    }
+
+    /// Grant a boost for `duration_ms`, following the stacking rule of its type
+    pub fn grant(
+        &mut self,
+        character_id: Uuid,
+        boost_type: BoostType,
+        bonus_percent: u32,
+        duration_ms: u64,
+        current_time: u64,
+    ) {
+        let stacking = self.config.stacking(boost_type);
+        let boosts = self.boosts.entry(character_id).or_default();
+        boosts.retain(|b| !b.is_expired(current_time));
+
+        if stacking == BoostStacking::ExtendDuration {
+            if let Some(existing) = boosts
+                .iter_mut()
+                .find(|b| b.boost_type == boost_type && b.bonus_percent == bonus_percent)
+            {
+                existing.expires_at += duration_ms;
+                return;
+            }
+        }
+
+        boosts.push(ActiveBoost {
+            boost_type,
+            bonus_percent,
+            expires_at: current_time + duration_ms,
+        });
+    }
+
+    /// Active bonus percentage for a boost type
+    pub fn bonus_percent(&self, character_id: Uuid, boost_type: BoostType, current_time: u64) -> u32 {
+        let Some(boosts) = self.boosts.get(&character_id) else {
+            return 0;
+        };
+        let active = boosts
+            .iter()
+            .filter(|b| b.boost_type == boost_type && !b.is_expired(current_time))
+            .map(|b| b.bonus_percent);
+
+        let bonus = match self.config.stacking(boost_type) {
+            BoostStacking::Additive => active.sum(),
+            BoostStacking::Highest | BoostStacking::ExtendDuration => active.max().unwrap_or(0),
+        };
+        bonus.min(self.config.max_bonus_percent)
+    }
+
+    /// Multiplier for a boost type (1.0 when no boost is active)
+    pub fn multiplier(&self, character_id: Uuid, boost_type: BoostType, current_time: u64) -> f32 {
+        1.0 + self.bonus_percent(character_id, boost_type, current_time) as f32 / 100.0
+    }
+
+    /// Apply the experience boost to an experience grant
+    pub fn apply_experience(&self, character_id: Uuid, experience: u64, current_time: u64) -> u64 {
+        let multiplier = self.multiplier(character_id, BoostType::Experience, current_time);
+        (experience as f64 * multiplier as f64) as u64
+    }
+
+    /// Loot rate multiplier to pass to `LootGenerator::generate_boosted`
+    pub fn loot_multiplier(&self, character_id: Uuid, current_time: u64) -> f32 {
+        self.multiplier(character_id, BoostType::Loot, current_time)
+    }
+
+    /// Active boosts for a character
+    pub fn active(&self, character_id: Uuid, current_time: u64) -> Vec<&ActiveBoost> {
+        self.boosts
+            .get(&character_id)
+            .map(|boosts| boosts.iter().filter(|b| !b.is_expired(current_time)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Remove a character's boosts on logout, returning them for persistence
+    pub fn save(&mut self, character_id: Uuid, current_time: u64) -> Vec<StoredBoost> {
+        self.boosts
+            .remove(&character_id)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|b| !b.is_expired(current_time))
+            .map(|b| StoredBoost {
+                boost_type: b.boost_type,
+                bonus_percent: b.bonus_percent,
+                remaining_ms: b.remaining(current_time),
+            })
+            .collect()
+    }
+
+    /// Resume saved boosts on login
+    pub fn restore(&mut self, character_id: Uuid, stored: Vec<StoredBoost>, current_time: u64) {
+        let boosts = self.boosts.entry(character_id).or_default();
+        boosts.extend(stored.into_iter().filter(|b| b.remaining_ms > 0).map(|b| ActiveBoost {
+            boost_type: b.boost_type,
+            bonus_percent: b.bonus_percent,
+            expires_at: current_time + b.remaining_ms,
+        }));
+    }
+
+    /// Drop expired boosts
+    pub fn cleanup(&mut self, current_time: u64) {
+        for boosts in self.boosts.values_mut() {
+            boosts.retain(|b| !b.is_expired(current_time));
+        }
+        self.boosts.retain(|_, boosts| !boosts.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: u64 = 3_600_000;
+
+    #[test]
+    fn test_boost_multiplies_experience_until_expiry() {
+        let mut manager = BoostManager::default();
+        let player = Uuid::new_v4();
+
+        assert_eq!(manager.apply_experience(player, 1000, 0), 1000);
+
+        manager.grant(player, BoostType::Experience, 50, HOUR, 0);
+        assert_eq!(manager.apply_experience(player, 1000, 1000), 1500);
+        assert_eq!(manager.loot_multiplier(player, 1000), 1.0);
+
+        // Expiry removes the multiplier
+        assert_eq!(manager.apply_experience(player, 1000, HOUR), 1000);
+        manager.cleanup(HOUR);
+        assert!(manager.active(player, HOUR).is_empty());
+    }
+
+    #[test]
+    fn test_stacking_rules() {
+        let mut manager = BoostManager::new(BoostConfig {
+            experience_stacking: BoostStacking::ExtendDuration,
+            loot_stacking: BoostStacking::Additive,
+            max_bonus_percent: 200,
+        });
+        let player = Uuid::new_v4();
+
+        // Same XP boost twice extends instead of stacking
+        manager.grant(player, BoostType::Experience, 50, HOUR, 0);
+        manager.grant(player, BoostType::Experience, 50, HOUR, 0);
+        assert_eq!(manager.bonus_percent(player, BoostType::Experience, 0), 50);
+        assert_eq!(manager.bonus_percent(player, BoostType::Experience, HOUR + 1), 50);
+
+        // A different XP boost runs alongside, the highest applies
+        manager.grant(player, BoostType::Experience, 100, HOUR / 2, 0);
+        assert_eq!(manager.bonus_percent(player, BoostType::Experience, 0), 100);
+        assert_eq!(manager.bonus_percent(player, BoostType::Experience, HOUR), 50);
+
+        // Additive loot boosts sum
+        manager.grant(player, BoostType::Loot, 25, HOUR, 0);
+        manager.grant(player, BoostType::Loot, 25, HOUR, 0);
+        assert_eq!(manager.loot_multiplier(player, 0), 1.5);
+    }
+
+    #[test]
+    fn test_boost_persists_across_relog() {
+        let mut manager = BoostManager::default();
+        let player = Uuid::new_v4();
+        manager.grant(player, BoostType::Experience, 50, HOUR, 0);
+
+        let saved = manager.save(player, HOUR / 4);
+        assert_eq!(saved[0].remaining_ms, HOUR * 3 / 4);
+        assert_eq!(manager.apply_experience(player, 100, HOUR / 4), 100);
+
+        // Offline time doesn't consume the boost
+        let login = 10 * HOUR;
+        manager.restore(player, saved, login);
+        assert_eq!(manager.apply_experience(player, 100, login), 150);
+        assert_eq!(manager.apply_experience(player, 100, login + HOUR * 3 / 4), 100);
+    }
+}
@@ -139,6 +139,9 @@ pub struct PlayerBosstiary {
     pub charm_points_earned: u64,
     /// Tracked boss (shows spawn timer)
     pub tracked_boss: Option<u32>,
+    /// Boss fight cooldowns (boss_id -> available again at)
+    #[serde(default)]
+    pub cooldowns: HashMap<u32, DateTime<Utc>>,
 }
 
 impl PlayerBosstiary {
@@ -150,6 +153,7 @@ impl PlayerBosstiary {
             bosses_completed: 0,
             charm_points_earned: 0,
             tracked_boss: None,
+            cooldowns: HashMap::new(),
         }
     }
 
@@ -318,6 +322,21 @@ impl BosstiaryManager {
         Some(Utc::now() + chrono::Duration::hours(hours as i64))
     }
 
+    /// Start a player's cooldown on a boss
+    pub fn set_cooldown(&mut self, player_id: Uuid, boss_id: u32, until: DateTime<Utc>) {
+        self.get_or_create(player_id).cooldowns.insert(boss_id, until);
+    }
+
+    /// When a player may fight a boss again, if still on cooldown at `now`
+    pub fn cooldown_until(&self, player_id: Uuid, boss_id: u32, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.player_data
+            .get(&player_id)?
+            .cooldowns
+            .get(&boss_id)
+            .copied()
+            .filter(|until| *until > now)
+    }
+
     /// Get total boss count
     pub fn total_bosses(&self) -> u32 {
         self.bosses.len() as u32
@@ -1,14 +1,23 @@
 //! Combat system - main combat logic and event handling
 
-use crate::area::{AreaEffect, AreaType};
+use crate::ammo::{check_ammo, AmmoConfig, AmmoStack, DistanceWeapon};
+use crate::area::{AreaEffect, AreaTargetContext, AreaTargetPolicy, AreaType};
 use crate::condition::CombatCondition;
+use crate::encounter::{EncounterLog, KillCredit};
+use crate::damage_event::DamageEventManager;
 use crate::damage::{BlockType, ConditionType, DamageInfo, DamageOrigin, DamageType, DamageTypeExt};
+use crate::elements::apply_element_modifier;
+use crate::effect::{ammo_shoot_effect, shoot_effect_id, EffectEvent, EFFECT_POFF};
 use crate::formula::{CombatFormula, MeleeFormula, DistanceFormula};
+use crate::multiplier::{MultiplierConfig, MultiplierContext, MultiplierResolver};
+use crate::new_character::{NewCharacterProtection, NewCharacterProtectionConfig};
+use crate::skill::{SkillAdvancementConfig, SkillTracker};
 use crate::spell::{Spell, SpellLoader};
 use crate::{CombatError, Result};
 use shadow_world::creature::{AttackMode, Creature};
-use shadow_world::item::SkillType;
+use shadow_world::item::{ShootType, SkillType};
 use shadow_world::position::Position;
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -66,6 +75,8 @@ pub enum CombatEvent {
         creature_id: u32,
         killer_id: Option<u32>,
     },
+    /// Follows a `Death` with the killer and assists
+    KillCredit(KillCredit),
     Block {
         defender_id: u32,
         attacker_id: u32,
@@ -80,6 +91,8 @@ pub struct CombatResult {
     pub events: Vec<CombatEvent>,
     pub experience_gained: u64,
     pub skill_tries: HashMap<SkillType, u64>,
+    /// Effects for the client, in display order
+    pub effects: Vec<EffectEvent>,
 }
 
 impl CombatResult {
@@ -89,6 +102,7 @@ impl CombatResult {
             events,
             experience_gained: 0,
             skill_tries: HashMap::new(),
+            effects: Vec::new(),
         }
     }
 
@@ -98,6 +112,7 @@ impl CombatResult {
             events: Vec::new(),
             experience_gained: 0,
             skill_tries: HashMap::new(),
+            effects: Vec::new(),
         }
     }
 
@@ -110,6 +125,11 @@ impl CombatResult {
         self.skill_tries.insert(skill, tries);
         self
     }
+
+    pub fn with_effects(mut self, effects: Vec<EffectEvent>) -> Self {
+        self.effects.extend(effects);
+        self
+    }
 }
 
 /// Combat system configuration
@@ -125,6 +145,8 @@ pub struct CombatConfig {
     pub skill_rate: f32,
     /// Magic level advancement rate
     pub magic_rate: f32,
+    /// Per-vocation, per-skill multipliers on the tries each level needs
+    pub skill_advancement: SkillAdvancementConfig,
     /// Experience rate
     pub exp_rate: f32,
     /// Loot rate
@@ -135,6 +157,16 @@ pub struct CombatConfig {
     pub life_leech_bonus: f32,
     /// Mana leech chance bonus
     pub mana_leech_bonus: f32,
+    /// Who area spells and runes may hit
+    pub area_policy: AreaTargetPolicy,
+    /// Ammunition consumption and conjured ammo
+    pub ammo: AmmoConfig,
+    /// How long a hit counts as an assist on a kill, in milliseconds
+    pub assist_window_ms: u64,
+    /// Rate events, boosted creature bonuses and how bonuses stack
+    pub multipliers: MultiplierConfig,
+    /// PvP and aggro protection of new characters
+    pub new_character_protection: NewCharacterProtectionConfig,
 }
 
 impl Default for CombatConfig {
@@ -145,11 +177,17 @@ impl Default for CombatConfig {
             level_difference_enabled: true,
             skill_rate: 1.0,
             magic_rate: 1.0,
+            skill_advancement: SkillAdvancementConfig::default(),
             exp_rate: 1.0,
             loot_rate: 1.0,
             critical_chance_bonus: 0.0,
             life_leech_bonus: 0.0,
             mana_leech_bonus: 0.0,
+            area_policy: AreaTargetPolicy::pvp(false),
+            ammo: AmmoConfig::default(),
+            assist_window_ms: 10_000,
+            multipliers: MultiplierConfig::default(),
+            new_character_protection: NewCharacterProtectionConfig::default(),
         }
     }
 }
@@ -160,18 +198,113 @@ pub struct CombatSystem {
     spell_loader: Arc<RwLock<SpellLoader>>,
     cooldowns: HashMap<u32, HashMap<u16, u64>>, // creature_id -> spell_id -> end_time
     group_cooldowns: HashMap<u32, HashMap<u8, u64>>, // creature_id -> group -> end_time
+    encounters: EncounterLog,
+    damage_events: DamageEventManager,
+    multipliers: MultiplierResolver,
+    new_characters: NewCharacterProtection,
 }
 
 impl CombatSystem {
     pub fn new(config: CombatConfig, spell_loader: Arc<RwLock<SpellLoader>>) -> Self {
+        let encounters = EncounterLog::new(config.assist_window_ms);
+        let multipliers = MultiplierResolver::new(config.multipliers.clone());
+        let new_characters = NewCharacterProtection::new(config.new_character_protection.clone());
         Self {
             config,
             spell_loader,
             cooldowns: HashMap::new(),
             group_cooldowns: HashMap::new(),
+            encounters,
+            damage_events: DamageEventManager::new(),
+            multipliers,
+            new_characters,
         }
     }
 
+    pub fn multipliers(&self) -> &MultiplierResolver {
+        &self.multipliers
+    }
+
+    /// Experience a kill grants with every active multiplier applied.
+    /// The realm rates of `context` are taken from the combat config.
+    pub fn kill_experience(
+        &self,
+        monster_experience: u64,
+        player_level: u16,
+        monster_level: u16,
+        context: &MultiplierContext,
+    ) -> u64 {
+        let context = self.with_rates(context);
+        let monster_level = if self.config.level_difference_enabled { monster_level } else { player_level };
+        self.multipliers.kill_experience(monster_experience, player_level, monster_level, &context)
+    }
+
+    /// Loot bonus multiplier of a kill, to pass to `LootGenerator::generate_boosted`.
+    /// The realm loot rate is left out since the generator applies its own.
+    pub fn kill_loot_multiplier(&self, context: &MultiplierContext) -> f32 {
+        let context = MultiplierContext { loot_rate: 1.0, ..context.clone() };
+        self.multipliers.resolve(&context).loot as f32
+    }
+
+    fn with_rates(&self, context: &MultiplierContext) -> MultiplierContext {
+        MultiplierContext {
+            experience_rate: self.config.exp_rate,
+            loot_rate: self.config.loot_rate,
+            ..context.clone()
+        }
+    }
+
+    /// Protection of new characters; register characters on login
+    pub fn new_characters_mut(&mut self) -> &mut NewCharacterProtection {
+        &mut self.new_characters
+    }
+
+    /// Damage log used for kill credit
+    pub fn encounters_mut(&mut self) -> &mut EncounterLog {
+        &mut self.encounters
+    }
+
+    /// Running damage events; hits on their creatures count towards them
+    pub fn damage_events_mut(&mut self) -> &mut DamageEventManager {
+        &mut self.damage_events
+    }
+
+    /// Log a hit for kill credit and running damage events
+    fn record_hit(
+        &mut self,
+        attacker: &Creature,
+        target: &Creature,
+        dealt: i32,
+        health_before: i32,
+        time: u64,
+    ) -> Option<KillCredit> {
+        self.damage_events.record_hit(attacker, target, dealt, time);
+        self.encounters.record_hit(attacker, target, dealt, health_before, time)
+    }
+
+    /// Skill tracker of a new character of `vocation_id`, advancing at the
+    /// vocation's configured factors
+    pub fn skill_tracker(&self, vocation_id: u8) -> SkillTracker {
+        self.config.skill_advancement.tracker(vocation_id)
+    }
+
+    /// Credit the skill tries of a combat result, scaled by the skill and
+    /// magic rates, and update the creature's levels. The tracker's
+    /// vocation factors then decide how far the tries go. Returns the
+    /// skills that advanced.
+    pub fn train_skills(&self, tracker: &mut SkillTracker, creature: &mut Creature, result: &CombatResult) -> Vec<SkillType> {
+        let mut advanced = Vec::new();
+        for (&skill, &tries) in &result.skill_tries {
+            let rate = if skill == SkillType::MagicLevel { self.config.magic_rate } else { self.config.skill_rate };
+            let tries = (tries as f64 * rate as f64).round() as u64;
+            if tries > 0 && tracker.add_tries(skill, tries) > 0 {
+                advanced.push(skill);
+            }
+        }
+        tracker.apply_to(creature);
+        advanced
+    }
+
     /// Process melee attack
     pub async fn melee_attack(
         &mut self,
@@ -207,13 +340,12 @@ impl CombatSystem {
         let armor = 0; // Would come from equipment
         damage.apply_defense(defense, armor);
 
-        // Apply resistance
-        if let Some(&resistance) = target.resistances.get(&DamageType::Physical) {
-            damage.apply_resistance(resistance);
-        }
+        // Apply elemental modifiers
+        apply_element_modifier(&target.resistances, &mut damage);
 
         // Apply damage
         let mut events = Vec::new();
+        let mut effects = Vec::new();
 
         if damage.is_blocked() {
             events.push(CombatEvent::Block {
@@ -221,8 +353,12 @@ impl CombatSystem {
                 attacker_id: attacker.id,
                 block_type: damage.blocked,
             });
+            effects.push(EffectEvent::block(target.position, damage.blocked));
         } else {
+            let health_before = target.stats.health;
             let actual_damage = target.apply_damage(damage.value, damage.damage_type);
+            let kill_credit = self.record_hit(attacker, target, actual_damage, health_before, current_time);
+            effects.extend(EffectEvent::hit(target.position, damage.damage_type, actual_damage));
 
             events.push(CombatEvent::MeleeAttack {
                 attacker_id: attacker.id,
@@ -247,10 +383,11 @@ impl CombatSystem {
                     killer_id: Some(attacker.id),
                 });
             }
+            events.extend(kill_credit.map(CombatEvent::KillCredit));
         }
 
         // Create result with skill advancement
-        let mut result = CombatResult::success(events);
+        let mut result = CombatResult::success(events).with_effects(effects);
         result = result.with_skill_tries(SkillType::Fist, 1);
 
         // Add shielding skill tries for defender
@@ -268,9 +405,25 @@ impl CombatSystem {
         ammo_attack: i32,
         hit_chance: i32,
         current_time: u64,
+    ) -> Result<CombatResult> {
+        let shoot_effect = shoot_effect_id(ShootType::Arrow);
+        self.fire(attacker, target, weapon_attack, ammo_attack, hit_chance, shoot_effect, current_time)
+    }
+
+    /// Ranged attack showing `shoot_effect` as the projectile
+    #[allow(clippy::too_many_arguments)]
+    fn fire(
+        &mut self,
+        attacker: &mut Creature,
+        target: &mut Creature,
+        weapon_attack: i32,
+        ammo_attack: i32,
+        hit_chance: i32,
+        shoot_effect: u8,
+        current_time: u64,
     ) -> Result<CombatResult> {
         // Check if can attack
-        self.validate_attack(attacker, target)?;
+        self.validate_target(attacker, target)?;
 
         // Check range
         let distance = attacker.position.distance_to(&target.position);
@@ -282,10 +435,14 @@ impl CombatSystem {
         let skill = attacker.get_skill(SkillType::Distance);
         let actual_hit_chance = crate::formula::calculate_hit_chance(skill, hit_chance, distance);
 
+        // The projectile flies whether or not it hits
+        let shot = EffectEvent::distance(attacker.position, target.position, shoot_effect);
+
         // Check if hit
         if rand::random::<f32>() > actual_hit_chance {
             // Miss
-            return Ok(CombatResult::success(vec![]));
+            return Ok(CombatResult::success(vec![])
+                .with_effects(vec![shot, EffectEvent::magic(target.position, EFFECT_POFF)]));
         }
 
         // Calculate damage
@@ -302,21 +459,23 @@ impl CombatSystem {
         let defense = target.get_skill(SkillType::Shielding) as i32;
         damage.apply_defense(defense, 0);
 
-        // Apply resistance
-        if let Some(&resistance) = target.resistances.get(&DamageType::Physical) {
-            damage.apply_resistance(resistance);
-        }
+        // Apply elemental modifiers
+        apply_element_modifier(&target.resistances, &mut damage);
 
         // Apply damage
         let mut events = Vec::new();
-        target.apply_damage(damage.value, damage.damage_type);
+        let health_before = target.stats.health;
+        let actual_damage = target.apply_damage(damage.value, damage.damage_type);
+        let kill_credit = self.record_hit(attacker, target, actual_damage, health_before, current_time);
 
         events.push(CombatEvent::RangedAttack {
             attacker_id: attacker.id,
             target_id: target.id,
             damage: damage.clone(),
-            shoot_effect: Some(1), // Arrow
+            shoot_effect: Some(shoot_effect),
         });
+        let mut effects = vec![shot];
+        effects.extend(EffectEvent::hit(target.position, damage.damage_type, actual_damage));
 
         // Check for death
         if !target.is_alive() {
@@ -325,13 +484,53 @@ impl CombatSystem {
                 killer_id: Some(attacker.id),
             });
         }
+        events.extend(kill_credit.map(CombatEvent::KillCredit));
 
-        let mut result = CombatResult::success(events);
+        let mut result = CombatResult::success(events).with_effects(effects);
         result = result.with_skill_tries(SkillType::Distance, 1);
 
         Ok(result)
     }
 
+    /// Attack with a distance weapon, firing from the ammo slot. Rejects
+    /// the attack if the weapon's ammunition is missing; a shot that is
+    /// fired uses up ammo whether or not it hits.
+    pub async fn distance_attack(
+        &mut self,
+        attacker: &mut Creature,
+        target: &mut Creature,
+        weapon: &DistanceWeapon,
+        ammo: &mut Option<AmmoStack>,
+        current_time: u64,
+    ) -> Result<CombatResult> {
+        let ammo_attack = check_ammo(weapon, ammo.as_ref())?;
+        let shoot_effect = ammo
+            .as_ref()
+            .filter(|_| weapon.ammo_type.is_some())
+            .map(|stack| ammo_shoot_effect(stack.ammo_type))
+            .unwrap_or_else(|| shoot_effect_id(ShootType::Spear));
+        let result = self.fire(
+            attacker,
+            target,
+            weapon.attack,
+            ammo_attack,
+            weapon.hit_chance,
+            shoot_effect,
+            current_time,
+        )?;
+
+        if weapon.ammo_type.is_some() && self.config.ammo.consume_ammo {
+            if let Some(stack) = ammo {
+                if stack.consume() {
+                    debug!("Creature {} ran out of ammunition", attacker.id);
+                    *ammo = None;
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
     /// Cast a spell
     pub async fn cast_spell(
         &mut self,
@@ -371,6 +570,11 @@ impl CombatSystem {
         if spell.need_target && target.is_none() {
             return Err(CombatError::InvalidTarget);
         }
+        if spell.is_aggressive() {
+            if let Some(target) = target.as_deref() {
+                self.validate_target(caster, target)?;
+            }
+        }
 
         // Consume resources
         caster.stats.mana -= spell.mana;
@@ -386,6 +590,7 @@ impl CombatSystem {
 
         // Process spell effect
         let mut events = Vec::new();
+        let mut effects = Vec::new();
 
         events.push(CombatEvent::SpellCast {
             caster_id: caster.id,
@@ -394,6 +599,17 @@ impl CombatSystem {
             target_pos,
         });
 
+        // Projectile towards the target, then the spell's effect where it lands
+        let effect_pos = target.as_ref().map(|t| t.position).or(target_pos).unwrap_or(caster.position);
+        if let Some(shoot_effect) = spell.shoot_effect {
+            if effect_pos != caster.position {
+                effects.push(EffectEvent::distance(caster.position, effect_pos, shoot_effect));
+            }
+        }
+        if let Some(effect) = spell.effect {
+            effects.push(EffectEvent::magic(effect_pos, effect));
+        }
+
         // Handle different spell types
         if spell.is_healing() {
             // Healing spell - capture stats before borrowing caster
@@ -405,6 +621,7 @@ impl CombatSystem {
                 let heal_target = target.unwrap_or(caster);
                 let target_id = heal_target.id;
                 let actual_heal = heal_target.heal(heal_amount.abs());
+                effects.push(EffectEvent::damage_text(heal_target.position, DamageType::Healing, actual_heal));
                 events.push(CombatEvent::Heal {
                     caster_id,
                     target_id,
@@ -420,12 +637,13 @@ impl CombatSystem {
                         let mut damage = DamageInfo::spell(damage_type, damage_value.abs())
                             .with_attacker(caster.id);
 
-                        // Apply resistance
-                        if let Some(&resistance) = target.resistances.get(&damage_type) {
-                            damage.apply_resistance(resistance);
-                        }
+                        // Apply elemental modifiers
+                        apply_element_modifier(&target.resistances, &mut damage);
 
-                        target.apply_damage(damage.value, damage.damage_type);
+                        let health_before = target.stats.health;
+                        let actual_damage = target.apply_damage(damage.value, damage.damage_type);
+                        let kill_credit = self.record_hit(caster, target, actual_damage, health_before, current_time);
+                        effects.push(EffectEvent::damage_text(target.position, damage_type, actual_damage));
 
                         events.push(CombatEvent::SpellDamage {
                             caster_id: caster.id,
@@ -450,12 +668,13 @@ impl CombatSystem {
                                 killer_id: Some(caster.id),
                             });
                         }
+                        events.extend(kill_credit.map(CombatEvent::KillCredit));
                     }
                 }
             }
         }
 
-        Ok(CombatResult::success(events))
+        Ok(CombatResult::success(events).with_effects(effects))
     }
 
     /// Apply area damage
@@ -469,9 +688,11 @@ impl CombatSystem {
     ) -> Result<CombatResult> {
         let mut events = Vec::new();
         let mut area_damages = Vec::new();
+        let mut texts = Vec::new();
 
+        let now = Utc::now();
         for target in targets {
-            if !area.contains(&target.position) {
+            if !area.contains(&target.position) || self.new_characters.blocks_pvp(caster, target, now) {
                 continue;
             }
 
@@ -482,12 +703,11 @@ impl CombatSystem {
             let mut damage = DamageInfo::spell(damage_type, actual_damage)
                 .with_attacker(caster.id);
 
-            // Apply resistance
-            if let Some(&resistance) = target.resistances.get(&damage_type) {
-                damage.apply_resistance(resistance);
-            }
+            // Apply elemental modifiers
+            apply_element_modifier(&target.resistances, &mut damage);
 
-            target.apply_damage(damage.value, damage.damage_type);
+            let dealt = target.apply_damage(damage.value, damage.damage_type);
+            texts.push(EffectEvent::damage_text(target.position, damage_type, dealt));
             area_damages.push((target.id, damage));
 
             // Check for death
@@ -499,6 +719,12 @@ impl CombatSystem {
             }
         }
 
+        // The area animation covers every tile, hit or not
+        let effect = damage_type.get_magic_effect();
+        let mut effects: Vec<EffectEvent> =
+            area.get_positions().iter().map(|&position| EffectEvent::magic(position, effect)).collect();
+        effects.extend(texts);
+
         if !area_damages.is_empty() {
             events.insert(
                 0,
@@ -506,16 +732,40 @@ impl CombatSystem {
                     caster_id: caster.id,
                     center: area.center,
                     damages: area_damages,
-                    effect: Some(damage_type.get_magic_effect()),
+                    effect: Some(effect),
                 },
             );
         }
 
-        Ok(CombatResult::success(events))
+        Ok(CombatResult::success(events).with_effects(effects))
     }
 
-    /// Apply combat abilities (critical, life leech, mana leech)
+    /// Apply area damage, skipping targets the configured area policy
+    /// protects (party members, immune creatures, creatures in protection
+    /// zones)
+    pub async fn apply_area_damage_with_policy(
+        &mut self,
+        caster: &mut Creature,
+        caster_ctx: &AreaTargetContext,
+        area: AreaEffect,
+        damage_type: DamageType,
+        base_damage: i32,
+        targets: &mut [(&mut Creature, AreaTargetContext)],
+    ) -> Result<CombatResult> {
+        let policy = self.config.area_policy;
+        let mut allowed: Vec<&mut Creature> = targets
+            .iter_mut()
+            .filter(|(target, ctx)| policy.can_hit(caster, caster_ctx, target, ctx, damage_type))
+            .map(|(target, _)| &mut **target)
+            .collect();
+        self.apply_area_damage(caster, area, damage_type, base_damage, &mut allowed).await
+    }
+
+    /// Apply combat abilities (difficulty scaling, critical, life leech, mana leech)
     fn apply_combat_abilities(&self, damage: &mut DamageInfo, attacker: &Creature) {
+        // Monsters scaled up for the group fighting them hit harder
+        damage.value = attacker.difficulty_scale().scale_damage(damage.value);
+
         // Critical hit (example: 10% chance, 50% bonus)
         let crit_chance = 0.10 + self.config.critical_chance_bonus;
         damage.apply_critical(crit_chance, 50);
@@ -529,23 +779,35 @@ impl CombatSystem {
         damage.apply_mana_leech(mana_leech_chance, 10);
     }
 
-    /// Validate if attacker can attack target
+    /// Validate if attacker can attack target in melee
     fn validate_attack(&self, attacker: &Creature, target: &Creature) -> Result<()> {
-        // Check if target is alive
-        if !target.is_alive() {
-            return Err(CombatError::TargetNotFound(target.id));
-        }
+        self.validate_target(attacker, target)?;
 
         // Check if in range (melee = adjacent)
         if !attacker.position.is_adjacent(&target.position) {
             return Err(CombatError::OutOfRange);
         }
 
+        Ok(())
+    }
+
+    /// Validate if attacker may attack target at all, regardless of range
+    fn validate_target(&self, attacker: &Creature, target: &Creature) -> Result<()> {
+        // Check if target is alive
+        if !target.is_alive() {
+            return Err(CombatError::TargetNotFound(target.id));
+        }
+
         // Check PvP rules
         if attacker.is_player() && target.is_player() && !self.config.pvp_enabled {
             return Err(CombatError::CannotAttack);
         }
 
+        // New characters neither attack nor get attacked by players
+        if self.new_characters.blocks_pvp(attacker, target, Utc::now()) {
+            return Err(CombatError::CannotAttack);
+        }
+
         Ok(())
     }
 
@@ -556,7 +818,8 @@ impl CombatSystem {
         current_time: u64,
     ) -> Option<CombatEvent> {
         if let Some(damage_value) = condition.tick(current_time) {
-            let damage = DamageInfo::new(condition.get_damage_type(), damage_value);
+            let mut damage = DamageInfo::new(condition.get_damage_type(), damage_value);
+            apply_element_modifier(&target.resistances, &mut damage);
             target.apply_damage(damage.value, damage.damage_type);
 
             return Some(CombatEvent::ConditionDamage {
@@ -603,4 +866,194 @@ mod tests {
         let result = combat.melee_attack(&mut attacker, &mut target, 0).await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_new_character_takes_no_pvp_damage() {
+        let mut combat = CombatSystem::new(CombatConfig::default(), Arc::new(RwLock::new(SpellLoader::new())));
+        let mut attacker = create_test_creature("Veteran");
+        let mut rookie = create_test_creature("Rookie");
+        rookie.stats.level = 8;
+        rookie.position = Position::new(101, 100, 7);
+        combat.new_characters_mut().register(rookie.id, Utc::now());
+
+        let result = combat.melee_attack(&mut attacker, &mut rookie, 0).await;
+        assert!(matches!(result, Err(CombatError::CannotAttack)));
+        assert_eq!(rookie.stats.health, 100);
+
+        // Monsters may still attack, and protection lifts at the level threshold
+        let mut rat = create_test_creature("Rat");
+        rat.creature_type = CreatureType::Monster;
+        rat.position = Position::new(101, 101, 7);
+        assert!(combat.melee_attack(&mut rat, &mut rookie, 0).await.is_ok());
+        rookie.stats.level = 20;
+        assert!(combat.melee_attack(&mut attacker, &mut rookie, 0).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_spell_cast_emits_magic_effect() {
+        let mut spell_loader = SpellLoader::new();
+        let mut spell = Spell::new(40, "Energy Strike".to_string(), "exori vis".to_string(), crate::spell::SpellType::Instant);
+        spell.mana = 20;
+        spell.need_target = true;
+        spell.aggressive = true;
+        spell.damage_type = Some(DamageType::Energy);
+        spell.formula = Some(crate::formula::MagicFormula::from_factors(0.5, 1.0, DamageType::Energy));
+        spell.effect = Some(DamageType::Energy.get_magic_effect());
+        spell.shoot_effect = Some(shoot_effect_id(ShootType::Energy));
+        spell_loader.add_spell(spell);
+        let mut combat = CombatSystem::new(CombatConfig::default(), Arc::new(RwLock::new(spell_loader)));
+
+        let mut caster = create_test_creature("Sorcerer");
+        let mut target = create_test_creature("Rat");
+        target.creature_type = CreatureType::Monster;
+        target.stats.health = 10_000;
+        target.position = Position::new(103, 100, 7);
+
+        let result = combat.cast_spell(&mut caster, "exori vis", Some(&mut target), None, 0).await.unwrap();
+        assert_eq!(result.effects[0], EffectEvent::distance(caster.position, target.position, 5));
+        assert_eq!(result.effects[1], EffectEvent::magic(target.position, 11));
+        assert!(matches!(
+            &result.effects[2],
+            EffectEvent::AnimatedText { position, .. } if *position == target.position
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_distance_attack_emits_shoot_effect() {
+        let mut combat = CombatSystem::new(CombatConfig::default(), Arc::new(RwLock::new(SpellLoader::new())));
+        let mut attacker = create_test_creature("Paladin");
+        let mut target = create_test_creature("Rat");
+        target.creature_type = CreatureType::Monster;
+        target.stats.health = 10_000;
+        target.position = Position::new(104, 100, 7);
+
+        let bow = DistanceWeapon { item_id: 3350, attack: 0, hit_chance: 90, ammo_type: Some(shadow_world::item::AmmoType::Bolt) };
+        let mut ammo = Some(AmmoStack::new(3446, shadow_world::item::AmmoType::Bolt, 25, 10));
+        let result = combat.distance_attack(&mut attacker, &mut target, &bow, &mut ammo, 0).await.unwrap();
+
+        // Hit or miss, the bolt is shown flying to the target
+        assert_eq!(result.effects[0], EffectEvent::distance(attacker.position, target.position, 2));
+        assert_eq!(result.effects.len(), if result.events.is_empty() { 2 } else { 3 });
+    }
+
+    fn area_damaged(result: &CombatResult) -> Vec<u32> {
+        result
+            .events
+            .iter()
+            .find_map(|e| match e {
+                CombatEvent::AreaDamage { damages, .. } => Some(damages.iter().map(|(id, _)| *id).collect()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    #[tokio::test]
+    async fn test_ue_skips_party_members_on_pve() {
+        let config = CombatConfig { area_policy: AreaTargetPolicy::pve(), ..Default::default() };
+        let mut combat = CombatSystem::new(config, Arc::new(RwLock::new(SpellLoader::new())));
+        let party = uuid::Uuid::new_v4();
+        let mut caster = create_test_creature("Sorcerer");
+        let mut friend = create_test_creature("Knight");
+        friend.position = Position::new(101, 100, 7);
+        let mut rat = create_test_creature("Rat");
+        rat.creature_type = CreatureType::Monster;
+        rat.position = Position::new(99, 100, 7);
+        let (friend_id, rat_id) = (friend.id, rat.id);
+
+        let ue = AreaEffect::new(AreaType::Circle { radius: 5 }, caster.position, None);
+        let mut targets = [
+            (&mut friend, AreaTargetContext::in_party(party)),
+            (&mut rat, AreaTargetContext::default()),
+        ];
+        let result = combat
+            .apply_area_damage_with_policy(
+                &mut caster,
+                &AreaTargetContext::in_party(party),
+                ue,
+                DamageType::Energy,
+                50,
+                &mut targets,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(area_damaged(&result), vec![rat_id]);
+        assert_eq!(friend.stats.health, 100);
+        assert!(!area_damaged(&result).contains(&friend_id));
+    }
+
+    #[tokio::test]
+    async fn test_area_damage_exploits_weakness_once() {
+        let mut combat = CombatSystem::new(CombatConfig::default(), Arc::new(RwLock::new(SpellLoader::new())));
+        let mut caster = create_test_creature("Sorcerer");
+        let monster = |name: &str| {
+            let mut creature = create_test_creature(name);
+            creature.creature_type = CreatureType::Monster;
+            creature.stats.health = 1_000;
+            creature.position = Position::new(101, 100, 7);
+            creature
+        };
+        let mut rat = monster("Rat");
+        let mut dragon = monster("Dragon");
+        dragon.resistances = HashMap::from([(DamageType::Ice, -10), (DamageType::Fire, 100)]);
+
+        let ue = AreaEffect::new(AreaType::Circle { radius: 5 }, caster.position, None);
+        combat
+            .apply_area_damage(&mut caster, ue.clone(), DamageType::Ice, 200, &mut [&mut rat, &mut dragon])
+            .await
+            .unwrap();
+        let dealt = 1_000 - rat.stats.health;
+        assert!(dealt > 0);
+        assert_eq!(1_000 - dragon.stats.health, dealt * 110 / 100);
+
+        combat.apply_area_damage(&mut caster, ue, DamageType::Fire, 200, &mut [&mut dragon]).await.unwrap();
+        assert_eq!(1_000 - dragon.stats.health, dealt * 110 / 100);
+    }
+
+    #[test]
+    fn test_area_policy_on_pvp() {
+        let party = uuid::Uuid::new_v4();
+        let caster = create_test_creature("Sorcerer");
+        let enemy = create_test_creature("Enemy");
+        let friend = create_test_creature("Friend");
+        let in_party = AreaTargetContext::in_party(party);
+        let none = AreaTargetContext::default();
+        let policy = AreaTargetPolicy::pvp(false);
+
+        assert!(policy.can_hit(&caster, &in_party, &enemy, &none, DamageType::Energy));
+        assert!(!policy.can_hit(&caster, &in_party, &friend, &in_party, DamageType::Energy));
+        assert!(AreaTargetPolicy::pvp(true).can_hit(&caster, &in_party, &friend, &in_party, DamageType::Energy));
+
+        // Safe zones and immunities protect even enemies
+        let in_pz = AreaTargetContext { in_protection_zone: true, ..Default::default() };
+        assert!(!policy.can_hit(&caster, &in_party, &enemy, &in_pz, DamageType::Energy));
+        let immune = AreaTargetContext { immunities: vec![DamageType::Energy], ..Default::default() };
+        assert!(!policy.can_hit(&caster, &in_party, &enemy, &immune, DamageType::Energy));
+        assert!(policy.can_hit(&caster, &in_party, &enemy, &immune, DamageType::Fire));
+        assert!(!AreaTargetPolicy::pve().can_hit(&caster, &in_party, &enemy, &none, DamageType::Energy));
+    }
+
+    #[test]
+    fn test_vocations_train_sword_at_different_rates() {
+        let config = CombatConfig { skill_rate: 2.0, ..Default::default() };
+        let combat = CombatSystem::new(config, Arc::new(RwLock::new(SpellLoader::new())));
+        let result = CombatResult::success(Vec::new()).with_skill_tries(SkillType::Sword, 3_000);
+
+        // Knight (3) needs 1.1x the base tries per level, sorcerer (0) 2.0x
+        let mut knight = combat.skill_tracker(3);
+        let mut sorcerer = combat.skill_tracker(0);
+        let (mut knight_creature, mut sorcerer_creature) = (create_test_creature("Knight"), create_test_creature("Sorcerer"));
+
+        assert_eq!(combat.train_skills(&mut knight, &mut knight_creature, &result), vec![SkillType::Sword]);
+        assert!(combat.train_skills(&mut sorcerer, &mut sorcerer_creature, &result).is_empty());
+
+        // 6,000 tries at the realm rate: 5,500 for the knight's level 11, 60% of the sorcerer's 10,000
+        assert_eq!(knight.progress(SkillType::Sword).level, 11);
+        assert_eq!(knight.progress(SkillType::Sword).tries, 500);
+        assert_eq!(sorcerer_creature.get_skill(SkillType::Sword), 10);
+        assert_eq!(sorcerer_creature.get_skill_percent(SkillType::Sword), 60);
+
+        // Promoted vocations advance like their base vocation
+        assert_eq!(combat.skill_tracker(7).factor(SkillType::MagicLevel), 3.0);
+    }
 }
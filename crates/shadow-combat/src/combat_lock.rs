@@ -0,0 +1,221 @@
+//! PvP combat lock
+//!
+//! Attacking a player, or being attacked by one, puts both characters in
+//! fight: they cannot log out until the lock runs out, and every new PvP
+//! action restarts it. An aggressor who attacks an unmarked player is also
+//! locked out of protection zones for longer, so they cannot escape into a
+//! temple. PvP inside arenas (PvP zone tiles) does not lock anyone.
+
+use serde::{Deserialize, Serialize};
+use shadow_world::tile::TileFlags;
+use std::collections::HashMap;
+
+/// Combat lock durations and exceptions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CombatLockConfig {
+    /// Seconds a PvP action blocks logout
+    pub logout_lock_secs: u32,
+    /// Seconds an unjustified attack blocks entering protection zones
+    pub pz_lock_secs: u32,
+    /// PvP inside PvP zone tiles (arenas) does not lock
+    pub pvp_zone_exempt: bool,
+    /// Characters standing in a protection zone may always log out
+    pub protection_zone_logout: bool,
+}
+
+impl Default for CombatLockConfig {
+    fn default() -> Self {
+        Self {
+            logout_lock_secs: 60,
+            pz_lock_secs: 900,
+            pvp_zone_exempt: true,
+            protection_zone_logout: true,
+        }
+    }
+}
+
+/// A PvP action between two players
+#[derive(Debug, Clone, Copy)]
+pub struct PvpAction {
+    pub attacker_id: u32,
+    pub target_id: u32,
+    /// The target was already marked (skulled, war enemy, ...) so the
+    /// attack is justified and does not lock the attacker out of protection zones
+    pub target_marked: bool,
+    /// Flags of the tile the attack happened on
+    pub tile: TileFlags,
+}
+
+/// Why an action is blocked by the combat lock
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CombatLockError {
+    /// In fight; `remaining_ms` until logout is allowed
+    InFight { remaining_ms: u64 },
+    /// Protection zone locked; `remaining_ms` until entry is allowed
+    ProtectionZoneLocked { remaining_ms: u64 },
+    /// The tile forbids logging out
+    NoLogoutTile,
+}
+
+impl std::fmt::Display for CombatLockError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CombatLockError::InFight { .. } => write!(f, "You may not logout during or immediately after a fight!"),
+            CombatLockError::ProtectionZoneLocked { .. } => {
+                write!(f, "You may not enter a protection zone after attacking another player.")
+            }
+            CombatLockError::NoLogoutTile => write!(f, "You may not logout here."),
+        }
+    }
+}
+
+impl std::error::Error for CombatLockError {}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct CombatLock {
+    logout_until: u64,
+    pz_until: u64,
+}
+
+/// Tracks combat locks by creature id. Times are server time in ms.
+#[derive(Debug, Default)]
+pub struct CombatLockManager {
+    config: CombatLockConfig,
+    locks: HashMap<u32, CombatLock>,
+}
+
+impl CombatLockManager {
+    pub fn new(config: CombatLockConfig) -> Self {
+        Self {
+            config,
+            locks: HashMap::new(),
+        }
+    }
+
+    pub fn config(&self) -> &CombatLockConfig {
+        &self.config
+    }
+
+    /// Lock both sides of a PvP action. Returns false when the action is exempt.
+    pub fn record_pvp(&mut self, action: PvpAction, current_time: u64) -> bool {
+        if self.config.pvp_zone_exempt && action.tile.is_pvp_zone() {
+            return false;
+        }
+        let logout_until = current_time + self.config.logout_lock_secs as u64 * 1000;
+        for id in [action.attacker_id, action.target_id] {
+            let lock = self.locks.entry(id).or_default();
+            lock.logout_until = lock.logout_until.max(logout_until);
+        }
+        if !action.target_marked {
+            let lock = self.locks.entry(action.attacker_id).or_default();
+            lock.pz_until = lock.pz_until.max(current_time + self.config.pz_lock_secs as u64 * 1000);
+        }
+        true
+    }
+
+    /// Remaining logout lock in ms (0 when not in fight)
+    pub fn logout_remaining(&self, creature_id: u32, current_time: u64) -> u64 {
+        self.locks
+            .get(&creature_id)
+            .map(|lock| lock.logout_until.saturating_sub(current_time))
+            .unwrap_or(0)
+    }
+
+    /// Remaining protection zone lock in ms
+    pub fn pz_remaining(&self, creature_id: u32, current_time: u64) -> u64 {
+        self.locks
+            .get(&creature_id)
+            .map(|lock| lock.pz_until.saturating_sub(current_time))
+            .unwrap_or(0)
+    }
+
+    pub fn is_in_fight(&self, creature_id: u32, current_time: u64) -> bool {
+        self.logout_remaining(creature_id, current_time) > 0
+    }
+
+    /// Whether a creature standing on `tile` may log out now
+    pub fn check_logout(&self, creature_id: u32, tile: TileFlags, current_time: u64) -> Result<(), CombatLockError> {
+        if tile.has(TileFlags::NO_LOGOUT) {
+            return Err(CombatLockError::NoLogoutTile);
+        }
+        if self.config.protection_zone_logout && tile.is_protection_zone() {
+            return Ok(());
+        }
+        match self.logout_remaining(creature_id, current_time) {
+            0 => Ok(()),
+            remaining_ms => Err(CombatLockError::InFight { remaining_ms }),
+        }
+    }
+
+    /// Whether a creature may step into a protection zone now
+    pub fn check_pz_entry(&self, creature_id: u32, current_time: u64) -> Result<(), CombatLockError> {
+        match self.pz_remaining(creature_id, current_time) {
+            0 => Ok(()),
+            remaining_ms => Err(CombatLockError::ProtectionZoneLocked { remaining_ms }),
+        }
+    }
+
+    /// Drop a creature's locks, e.g. on death
+    pub fn clear(&mut self, creature_id: u32) {
+        self.locks.remove(&creature_id);
+    }
+
+    /// Drop locks that ran out
+    pub fn cleanup(&mut self, current_time: u64) {
+        self.locks
+            .retain(|_, lock| lock.logout_until > current_time || lock.pz_until > current_time);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attack(attacker_id: u32, target_id: u32, tile: TileFlags) -> PvpAction {
+        PvpAction { attacker_id, target_id, target_marked: false, tile }
+    }
+
+    #[test]
+    fn test_logout_blocked_until_lock_expires() {
+        let mut locks = CombatLockManager::new(CombatLockConfig::default());
+        let street = TileFlags::new();
+        assert!(locks.record_pvp(attack(1, 2, street), 0));
+
+        // Both sides are in fight
+        assert_eq!(locks.check_logout(1, street, 1_000), Err(CombatLockError::InFight { remaining_ms: 59_000 }));
+        assert!(locks.check_logout(2, street, 59_999).is_err());
+        assert!(locks.check_logout(2, street, 60_000).is_ok());
+
+        // Only the aggressor is kept out of protection zones, for longer
+        assert!(locks.check_pz_entry(2, 1_000).is_ok());
+        assert!(matches!(locks.check_pz_entry(1, 60_000), Err(CombatLockError::ProtectionZoneLocked { .. })));
+        assert!(locks.check_pz_entry(1, 900_000).is_ok());
+
+        locks.cleanup(900_000);
+        assert!(!locks.is_in_fight(1, 0));
+    }
+
+    #[test]
+    fn test_lock_exceptions() {
+        let mut locks = CombatLockManager::new(CombatLockConfig::default());
+
+        // Arena fights do not lock
+        let arena = TileFlags::from_bits(TileFlags::PVP_ZONE);
+        assert!(!locks.record_pvp(attack(1, 2, arena), 0));
+        assert!(locks.check_logout(1, TileFlags::new(), 0).is_ok());
+
+        // Attacking a marked player only locks logout
+        let street = TileFlags::new();
+        locks.record_pvp(PvpAction { target_marked: true, ..attack(1, 2, street) }, 0);
+        assert!(locks.check_pz_entry(1, 0).is_ok());
+        assert!(locks.check_logout(1, street, 0).is_err());
+
+        // Inside a protection zone logout is allowed, on no-logout tiles never
+        assert!(locks.check_logout(1, TileFlags::from_bits(TileFlags::PROTECTION_ZONE), 0).is_ok());
+        assert_eq!(
+            locks.check_logout(3, TileFlags::from_bits(TileFlags::NO_LOGOUT), 0),
+            Err(CombatLockError::NoLogoutTile)
+        );
+    }
+}
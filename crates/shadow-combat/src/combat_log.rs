@@ -0,0 +1,316 @@
+//! Combat log - recent encounters of each character
+//!
+//! While a character fights, the damage it deals, the damage it takes and
+//! the healing it does are added to its open encounter. The encounter
+//! closes when the character has been out of combat for the idle timeout
+//! (or dies or logs out) and is kept as an `EncounterSummary` with DPS and
+//! HPS broken down by source. Recording is opt-in per character and only
+//! the most recent encounters are retained; opting out drops them.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::damage::DamageType;
+
+/// Retention settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CombatLogConfig {
+    /// Encounters kept per character, oldest evicted first
+    pub max_encounters: usize,
+    /// Milliseconds without combat after which an encounter closes
+    pub idle_timeout_ms: u64,
+    /// Encounters shorter than this count as lasting this long when
+    /// computing per-second rates, so a single hit does not read as
+    /// thousands of DPS
+    pub min_duration_ms: u64,
+}
+
+impl Default for CombatLogConfig {
+    fn default() -> Self {
+        Self {
+            max_encounters: 20,
+            idle_timeout_ms: 10_000,
+            min_duration_ms: 1_000,
+        }
+    }
+}
+
+/// Total of one source (spell, weapon, creature) within an encounter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceBreakdown {
+    pub source: String,
+    /// `None` for healing
+    pub damage_type: Option<DamageType>,
+    pub hits: u32,
+    pub total: i64,
+    pub per_second: f64,
+}
+
+/// A finished encounter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EncounterSummary {
+    /// Milliseconds since the epoch
+    pub started_at: u64,
+    pub ended_at: u64,
+    pub damage_dealt: i64,
+    pub damage_taken: i64,
+    pub healing_done: i64,
+    pub dps: f64,
+    pub hps: f64,
+    /// Damage dealt per source, highest first
+    pub damage_sources: Vec<SourceBreakdown>,
+    /// Damage taken per attacker, highest first
+    pub damage_taken_sources: Vec<SourceBreakdown>,
+    /// Healing done per source, highest first
+    pub healing_sources: Vec<SourceBreakdown>,
+    /// Everything the character damaged, in order of first hit
+    pub opponents: Vec<String>,
+}
+
+impl EncounterSummary {
+    pub fn duration_ms(&self) -> u64 {
+        self.ended_at.saturating_sub(self.started_at)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Tally {
+    hits: u32,
+    total: i64,
+}
+
+#[derive(Debug, Clone)]
+struct OpenEncounter {
+    started_at: u64,
+    last_activity: u64,
+    dealt: HashMap<(String, Option<DamageType>), Tally>,
+    taken: HashMap<(String, Option<DamageType>), Tally>,
+    healed: HashMap<(String, Option<DamageType>), Tally>,
+    opponents: Vec<String>,
+}
+
+impl OpenEncounter {
+    fn new(now: u64) -> Self {
+        Self {
+            started_at: now,
+            last_activity: now,
+            dealt: HashMap::new(),
+            taken: HashMap::new(),
+            healed: HashMap::new(),
+            opponents: Vec::new(),
+        }
+    }
+
+    fn summarize(self, min_duration_ms: u64) -> EncounterSummary {
+        let seconds = self.last_activity.saturating_sub(self.started_at).max(min_duration_ms).max(1) as f64 / 1000.0;
+        let breakdown = |tallies: HashMap<(String, Option<DamageType>), Tally>| {
+            let mut sources: Vec<SourceBreakdown> = tallies
+                .into_iter()
+                .map(|((source, damage_type), tally)| SourceBreakdown {
+                    source,
+                    damage_type,
+                    hits: tally.hits,
+                    total: tally.total,
+                    per_second: tally.total as f64 / seconds,
+                })
+                .collect();
+            sources.sort_by(|a, b| b.total.cmp(&a.total).then_with(|| a.source.cmp(&b.source)));
+            sources
+        };
+
+        let damage_sources = breakdown(self.dealt);
+        let damage_taken_sources = breakdown(self.taken);
+        let healing_sources = breakdown(self.healed);
+        let damage_dealt = damage_sources.iter().map(|s| s.total).sum();
+        let healing_done = healing_sources.iter().map(|s| s.total).sum();
+        EncounterSummary {
+            started_at: self.started_at,
+            ended_at: self.last_activity,
+            damage_dealt,
+            damage_taken: damage_taken_sources.iter().map(|s| s.total).sum(),
+            healing_done,
+            dps: damage_dealt as f64 / seconds,
+            hps: healing_done as f64 / seconds,
+            damage_sources,
+            damage_taken_sources,
+            healing_sources,
+            opponents: self.opponents,
+        }
+    }
+}
+
+/// Open and recent encounters of opted-in characters
+#[derive(Debug, Clone, Default)]
+pub struct CombatLog {
+    config: CombatLogConfig,
+    opted_in: HashSet<u32>,
+    open: HashMap<u32, OpenEncounter>,
+    recent: HashMap<u32, VecDeque<EncounterSummary>>,
+}
+
+impl CombatLog {
+    pub fn new(config: CombatLogConfig) -> Self {
+        Self {
+            config,
+            ..Default::default()
+        }
+    }
+
+    /// Turn recording on or off for a character. Turning it off forgets
+    /// the open encounter and every retained one.
+    pub fn set_opt_in(&mut self, character_id: u32, enabled: bool) {
+        if enabled {
+            self.opted_in.insert(character_id);
+        } else {
+            self.opted_in.remove(&character_id);
+            self.open.remove(&character_id);
+            self.recent.remove(&character_id);
+        }
+    }
+
+    pub fn is_opted_in(&self, character_id: u32) -> bool {
+        self.opted_in.contains(&character_id)
+    }
+
+    /// Open encounter of a character, starting a new one if the last went idle
+    fn encounter(&mut self, character_id: u32, now: u64) -> Option<&mut OpenEncounter> {
+        if !self.opted_in.contains(&character_id) {
+            return None;
+        }
+        let idle = self
+            .open
+            .get(&character_id)
+            .is_some_and(|open| now.saturating_sub(open.last_activity) > self.config.idle_timeout_ms);
+        if idle {
+            self.end_encounter(character_id);
+        }
+        let open = self.open.entry(character_id).or_insert_with(|| OpenEncounter::new(now));
+        open.last_activity = open.last_activity.max(now);
+        Some(open)
+    }
+
+    /// Log damage `character_id` dealt to `target` with `source`
+    pub fn record_damage_dealt(
+        &mut self,
+        character_id: u32,
+        source: &str,
+        damage_type: DamageType,
+        amount: i32,
+        target: &str,
+        now: u64,
+    ) {
+        if let Some(open) = self.encounter(character_id, now) {
+            let tally = open.dealt.entry((source.to_string(), Some(damage_type))).or_default();
+            tally.hits += 1;
+            tally.total += amount.max(0) as i64;
+            if !open.opponents.iter().any(|o| o == target) {
+                open.opponents.push(target.to_string());
+            }
+        }
+    }
+
+    /// Log damage `character_id` took from `attacker`
+    pub fn record_damage_taken(&mut self, character_id: u32, attacker: &str, damage_type: DamageType, amount: i32, now: u64) {
+        if let Some(open) = self.encounter(character_id, now) {
+            let tally = open.taken.entry((attacker.to_string(), Some(damage_type))).or_default();
+            tally.hits += 1;
+            tally.total += amount.max(0) as i64;
+        }
+    }
+
+    /// Log healing `character_id` did with `source`
+    pub fn record_healing(&mut self, character_id: u32, source: &str, amount: i32, now: u64) {
+        if let Some(open) = self.encounter(character_id, now) {
+            let tally = open.healed.entry((source.to_string(), None)).or_default();
+            tally.hits += 1;
+            tally.total += amount.max(0) as i64;
+        }
+    }
+
+    /// Close a character's open encounter, e.g. on death or logout
+    pub fn end_encounter(&mut self, character_id: u32) -> Option<EncounterSummary> {
+        let summary = self.open.remove(&character_id)?.summarize(self.config.min_duration_ms);
+        let recent = self.recent.entry(character_id).or_default();
+        recent.push_back(summary.clone());
+        while recent.len() > self.config.max_encounters {
+            recent.pop_front();
+        }
+        Some(summary)
+    }
+
+    /// Close every encounter that has gone idle. Returns the closed
+    /// encounters so they can be persisted.
+    pub fn tick(&mut self, now: u64) -> Vec<(u32, EncounterSummary)> {
+        let timeout = self.config.idle_timeout_ms;
+        let idle: Vec<u32> = self
+            .open
+            .iter()
+            .filter(|(_, open)| now.saturating_sub(open.last_activity) > timeout)
+            .map(|(&id, _)| id)
+            .collect();
+        idle.into_iter()
+            .filter_map(|id| self.end_encounter(id).map(|summary| (id, summary)))
+            .collect()
+    }
+
+    /// Retained encounters of a character, most recent first
+    pub fn recent(&self, character_id: u32) -> Vec<&EncounterSummary> {
+        self.recent
+            .get(&character_id)
+            .map(|recent| recent.iter().rev().collect())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encounter_summary_breakdown() {
+        let mut log = CombatLog::default();
+        log.record_damage_dealt(1, "Exori", DamageType::Physical, 100, "Dragon", 0);
+        assert!(log.recent(1).is_empty() && log.end_encounter(1).is_none());
+
+        log.set_opt_in(1, true);
+        log.record_damage_dealt(1, "Exori", DamageType::Physical, 300, "Dragon", 1_000);
+        log.record_damage_dealt(1, "Sword", DamageType::Physical, 100, "Dragon", 2_000);
+        log.record_damage_dealt(1, "Exori", DamageType::Physical, 200, "Dragon Lord", 3_000);
+        log.record_damage_taken(1, "Dragon", DamageType::Fire, 150, 3_500);
+        log.record_healing(1, "Exura Ico", 120, 5_000);
+        assert!(log.tick(10_000).is_empty());
+
+        let closed = log.tick(15_001);
+        assert_eq!(closed.len(), 1);
+        let summary = log.recent(1)[0];
+        assert_eq!(*summary, closed[0].1);
+        assert_eq!(summary.duration_ms(), 4_000);
+        assert_eq!((summary.damage_dealt, summary.damage_taken, summary.healing_done), (600, 150, 120));
+        assert_eq!((summary.dps, summary.hps), (150.0, 30.0));
+        assert_eq!(summary.damage_sources[0].source, "Exori");
+        assert_eq!((summary.damage_sources[0].hits, summary.damage_sources[0].per_second), (2, 125.0));
+        assert_eq!(summary.opponents, vec!["Dragon".to_string(), "Dragon Lord".to_string()]);
+    }
+
+    #[test]
+    fn test_retention_evicts_oldest_and_opt_out_forgets() {
+        let mut log = CombatLog::new(CombatLogConfig { max_encounters: 2, ..Default::default() });
+        log.set_opt_in(7, true);
+
+        // Hits more than the idle timeout apart open new encounters
+        for (i, time) in [0u64, 20_000, 40_000].into_iter().enumerate() {
+            log.record_damage_dealt(7, "Arrow", DamageType::Physical, 10 * (i as i32 + 1), "Rat", time);
+        }
+        log.end_encounter(7);
+
+        let recent = log.recent(7);
+        assert_eq!(recent.len(), 2);
+        assert_eq!((recent[0].damage_dealt, recent[1].damage_dealt), (30, 20));
+        // A single hit is rated over the minimum duration
+        assert_eq!(recent[0].dps, 30.0);
+
+        log.set_opt_in(7, false);
+        assert!(log.recent(7).is_empty());
+    }
+}
@@ -0,0 +1,152 @@
+//! PvE experience and loot contribution
+//!
+//! Decides how a monster's experience is split between players who hunted
+//! it without being in a party together, and who may open its loot. Either
+//! the last hitter takes everything or the experience is split by damage.
+//! Players below the minimum damage share earn nothing. Party sharing is
+//! applied afterwards by the party system to each contributor's portion.
+
+use serde::{Deserialize, Serialize};
+use shadow_world::kill_credit::KillCreditTracker;
+
+/// How experience is attributed between non-party attackers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PveShareMode {
+    /// The last hitter gets all experience and the loot
+    LastHit,
+    /// Experience is split by damage dealt; every contributor may loot
+    #[default]
+    DamageWeighted,
+}
+
+/// PvE contribution configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContributionConfig {
+    pub mode: PveShareMode,
+    /// Share of the total damage needed to earn credit (0.0 - 1.0)
+    pub min_damage_share: f64,
+}
+
+impl Default for ContributionConfig {
+    fn default() -> Self {
+        Self {
+            mode: PveShareMode::DamageWeighted,
+            min_damage_share: 0.1,
+        }
+    }
+}
+
+/// What one attacker earned from a kill
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PveContribution {
+    pub owner_id: u32,
+    pub damage: u64,
+    pub experience: u64,
+    pub loot_eligible: bool,
+}
+
+/// Resolves experience grants and loot rights for monster kills
+#[derive(Debug, Clone, Default)]
+pub struct ContributionResolver {
+    config: ContributionConfig,
+}
+
+impl ContributionResolver {
+    pub fn new(config: ContributionConfig) -> Self {
+        Self { config }
+    }
+
+    /// Split `experience` between the attackers recorded in `tracker`.
+    /// Returns one entry per attacker in order of first hit; attackers
+    /// without credit get zero experience and no loot rights.
+    pub fn resolve(&self, tracker: &KillCreditTracker, experience: u64) -> Vec<PveContribution> {
+        let contributors = tracker.contributors();
+        let total: u64 = contributors.iter().map(|(_, damage)| damage).sum();
+        let min_damage = (total as f64 * self.config.min_damage_share.clamp(0.0, 1.0)).ceil() as u64;
+        let earns = |damage: u64| damage > 0 && damage >= min_damage;
+
+        let mut result: Vec<PveContribution> = contributors
+            .iter()
+            .map(|&(owner_id, damage)| PveContribution { owner_id, damage, experience: 0, loot_eligible: false })
+            .collect();
+
+        match self.config.mode {
+            PveShareMode::LastHit => {
+                // A last hit below the threshold does not steal the kill
+                let winner = tracker
+                    .last_hit()
+                    .filter(|&id| earns(tracker.damage_by(id)))
+                    .or_else(|| tracker.top_damage().filter(|&id| earns(tracker.damage_by(id))));
+                if let Some(entry) = result.iter_mut().find(|c| Some(c.owner_id) == winner) {
+                    entry.experience = experience;
+                    entry.loot_eligible = true;
+                }
+            }
+            PveShareMode::DamageWeighted => {
+                let eligible_damage: u64 = result.iter().filter(|c| earns(c.damage)).map(|c| c.damage).sum();
+                if eligible_damage == 0 {
+                    return result;
+                }
+                let mut granted = 0;
+                for entry in result.iter_mut().filter(|c| earns(c.damage)) {
+                    entry.experience = (experience as u128 * entry.damage as u128 / eligible_damage as u128) as u64;
+                    entry.loot_eligible = true;
+                    granted += entry.experience;
+                }
+                // Rounding leftovers go to the top contributor
+                if let Some(top) = result.iter_mut().filter(|c| c.loot_eligible).max_by_key(|c| c.damage) {
+                    top.experience += experience - granted;
+                }
+            }
+        }
+        result
+    }
+
+    /// Owners allowed to open the corpse
+    pub fn loot_owners(&self, tracker: &KillCreditTracker) -> Vec<u32> {
+        self.resolve(tracker, 0)
+            .into_iter()
+            .filter(|c| c.loot_eligible)
+            .map(|c| c.owner_id)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weighted_split_between_non_party_attackers() {
+        let mut tracker = KillCreditTracker::new();
+        tracker.record_damage_by(1, 750);
+        tracker.record_damage_by(2, 250);
+
+        let resolver = ContributionResolver::new(ContributionConfig::default());
+        let split = resolver.resolve(&tracker, 1_001);
+        assert_eq!(split.iter().map(|c| c.experience).collect::<Vec<_>>(), vec![751, 250]);
+        assert!(split.iter().all(|c| c.loot_eligible));
+
+        let last_hit = ContributionResolver::new(ContributionConfig { mode: PveShareMode::LastHit, ..Default::default() });
+        let split = last_hit.resolve(&tracker, 1_000);
+        assert_eq!(split.iter().map(|c| c.experience).collect::<Vec<_>>(), vec![0, 1_000]);
+        assert_eq!(last_hit.loot_owners(&tracker), vec![2]);
+    }
+
+    #[test]
+    fn test_below_threshold_attacker_gets_nothing() {
+        let mut tracker = KillCreditTracker::new();
+        tracker.record_damage_by(1, 950);
+        // A single late hit for 5% of the damage
+        tracker.record_damage_by(2, 50);
+
+        let resolver = ContributionResolver::new(ContributionConfig { min_damage_share: 0.1, ..Default::default() });
+        let split = resolver.resolve(&tracker, 500);
+        assert_eq!(split[0], PveContribution { owner_id: 1, damage: 950, experience: 500, loot_eligible: true });
+        assert_eq!(split[1], PveContribution { owner_id: 2, damage: 50, experience: 0, loot_eligible: false });
+
+        // Nor can it steal the kill with the last hit
+        let last_hit = ContributionResolver::new(ContributionConfig { mode: PveShareMode::LastHit, min_damage_share: 0.1 });
+        assert_eq!(last_hit.loot_owners(&tracker), vec![1]);
+    }
+}
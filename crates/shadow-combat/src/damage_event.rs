@@ -0,0 +1,286 @@
+//! Damage events - soul pit style timed damage challenges
+//!
+//! An event runs for a fixed time against the creatures registered to it,
+//! usually the waves or the training target the event spawns. Every hit
+//! the combat system logs on one of those creatures is added to the
+//! attacker's total (summon damage counts for the master). When the timer
+//! runs out each participant is awarded the highest reward tier their
+//! total reaches; players who never hit an event creature get nothing.
+
+use serde::{Deserialize, Serialize};
+use shadow_world::creature::Creature;
+use std::collections::{HashMap, HashSet};
+
+/// Reward item of a tier
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DamageEventReward {
+    pub item_id: u16,
+    pub count: u16,
+}
+
+/// Rewards for reaching a total damage
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DamageRewardTier {
+    pub name: String,
+    /// Total damage needed for this tier
+    pub min_damage: i64,
+    pub rewards: Vec<DamageEventReward>,
+}
+
+impl DamageRewardTier {
+    pub fn new(name: &str, min_damage: i64) -> Self {
+        Self {
+            name: name.to_string(),
+            min_damage,
+            rewards: Vec::new(),
+        }
+    }
+
+    pub fn with_reward(mut self, item_id: u16, count: u16) -> Self {
+        self.rewards.push(DamageEventReward { item_id, count });
+        self
+    }
+}
+
+/// Definition of a damage event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DamageEventConfig {
+    pub name: String,
+    /// How long the event runs, in milliseconds
+    pub duration_ms: u64,
+    pub tiers: Vec<DamageRewardTier>,
+}
+
+/// A participant's result
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DamageEventAward {
+    pub player_id: u32,
+    pub total_damage: i64,
+    /// 1 for the highest total
+    pub rank: usize,
+    /// Highest tier reached, `None` below the lowest
+    pub tier: Option<DamageRewardTier>,
+}
+
+/// Outcome of a finished event, participants by total damage
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DamageEventResult {
+    pub event_id: u32,
+    pub name: String,
+    pub awards: Vec<DamageEventAward>,
+}
+
+impl DamageEventResult {
+    pub fn award(&self, player_id: u32) -> Option<&DamageEventAward> {
+        self.awards.iter().find(|a| a.player_id == player_id)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RunningEvent {
+    config: DamageEventConfig,
+    ends_at: u64,
+    targets: HashSet<u32>,
+    damage: HashMap<u32, i64>,
+}
+
+/// Runs damage events and collects their damage from the combat log
+#[derive(Debug, Clone, Default)]
+pub struct DamageEventManager {
+    events: HashMap<u32, RunningEvent>,
+    /// Event of each registered creature
+    target_events: HashMap<u32, u32>,
+    next_id: u32,
+}
+
+impl DamageEventManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start an event at `now`, returning its id
+    pub fn start(&mut self, mut config: DamageEventConfig, now: u64) -> u32 {
+        config.tiers.sort_by_key(|tier| tier.min_damage);
+        self.next_id += 1;
+        let event_id = self.next_id;
+        self.events.insert(
+            event_id,
+            RunningEvent {
+                ends_at: now + config.duration_ms,
+                config,
+                targets: HashSet::new(),
+                damage: HashMap::new(),
+            },
+        );
+        event_id
+    }
+
+    /// Count damage to `creature_id` towards an event. Returns false if
+    /// the event is not running.
+    pub fn add_target(&mut self, event_id: u32, creature_id: u32) -> bool {
+        let Some(event) = self.events.get_mut(&event_id) else {
+            return false;
+        };
+        event.targets.insert(creature_id);
+        self.target_events.insert(creature_id, event_id);
+        true
+    }
+
+    /// Log a hit; called by the combat system next to the encounter log
+    pub fn record_hit(&mut self, attacker: &Creature, target: &Creature, dealt: i32, time: u64) {
+        if dealt <= 0 {
+            return;
+        }
+        let Some(event) = self.target_events.get(&target.id).and_then(|id| self.events.get_mut(id)) else {
+            return;
+        };
+        if time >= event.ends_at {
+            return;
+        }
+        let player_id = attacker.summon_master_id.unwrap_or(attacker.id);
+        *event.damage.entry(player_id).or_insert(0) += dealt as i64;
+    }
+
+    /// Damage a player has dealt in an event so far
+    pub fn total_damage(&self, event_id: u32, player_id: u32) -> i64 {
+        self.events
+            .get(&event_id)
+            .and_then(|event| event.damage.get(&player_id))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    pub fn is_running(&self, event_id: u32) -> bool {
+        self.events.contains_key(&event_id)
+    }
+
+    /// Finish the events whose time ran out
+    pub fn tick(&mut self, now: u64) -> Vec<DamageEventResult> {
+        let mut ended: Vec<u32> = self
+            .events
+            .iter()
+            .filter(|(_, event)| now >= event.ends_at)
+            .map(|(&id, _)| id)
+            .collect();
+        ended.sort_unstable();
+        ended.into_iter().filter_map(|id| self.finish(id)).collect()
+    }
+
+    /// Finish an event now, e.g. when its last wave is cleared
+    pub fn finish(&mut self, event_id: u32) -> Option<DamageEventResult> {
+        let event = self.events.remove(&event_id)?;
+        for target in &event.targets {
+            self.target_events.remove(target);
+        }
+
+        let mut totals: Vec<(u32, i64)> = event.damage.into_iter().collect();
+        totals.sort_by_key(|&(player_id, total)| (std::cmp::Reverse(total), player_id));
+
+        let awards = totals
+            .into_iter()
+            .enumerate()
+            .map(|(index, (player_id, total_damage))| DamageEventAward {
+                player_id,
+                total_damage,
+                rank: index + 1,
+                tier: event.config.tiers.iter().rev().find(|tier| total_damage >= tier.min_damage).cloned(),
+            })
+            .collect();
+
+        Some(DamageEventResult {
+            event_id,
+            name: event.config.name,
+            awards,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shadow_world::creature::CreatureType;
+    use shadow_world::position::Position;
+
+    fn creature(name: &str, creature_type: CreatureType) -> Creature {
+        Creature::new(name.to_string(), creature_type, Position::new(100, 100, 7))
+    }
+
+    fn soul_pit() -> DamageEventConfig {
+        DamageEventConfig {
+            name: "Soul Pit".to_string(),
+            duration_ms: 60_000,
+            // Out of order on purpose
+            tiers: vec![
+                DamageRewardTier::new("Gold", 20_000).with_reward(49_000, 3),
+                DamageRewardTier::new("Bronze", 1_000).with_reward(49_000, 1),
+                DamageRewardTier::new("Silver", 5_000).with_reward(49_000, 2),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_tier_thresholds_award_highest_reached() {
+        let mut events = DamageEventManager::new();
+        let event_id = events.start(soul_pit(), 0);
+        let wave = creature("Soul Wave", CreatureType::Monster);
+        events.add_target(event_id, wave.id);
+
+        let (gold, silver, short) = (
+            creature("Eryn", CreatureType::Player),
+            creature("Tarok", CreatureType::Player),
+            creature("Mira", CreatureType::Player),
+        );
+        for _ in 0..5 {
+            events.record_hit(&gold, &wave, 5_000, 1_000);
+        }
+        events.record_hit(&silver, &wave, 2_500, 2_000);
+        events.record_hit(&silver, &wave, 2_500, 3_000);
+        events.record_hit(&short, &wave, 999, 4_000);
+        // After the timer, hits no longer count
+        events.record_hit(&short, &wave, 5_000, 60_000);
+        assert_eq!(events.total_damage(event_id, silver.id), 5_000);
+
+        assert!(events.tick(59_999).is_empty());
+        let result = events.tick(60_000).pop().unwrap();
+        assert!(!events.is_running(event_id));
+
+        let tier = |player: &Creature| result.award(player.id).unwrap().tier.as_ref().map(|t| t.name.clone());
+        assert_eq!(tier(&gold).as_deref(), Some("Gold"));
+        assert_eq!(tier(&silver).as_deref(), Some("Silver"));
+        assert_eq!(tier(&short), None);
+        assert_eq!(result.award(gold.id).unwrap().tier.as_ref().unwrap().rewards[0].count, 3);
+        assert_eq!(result.award(silver.id).unwrap().rank, 2);
+    }
+
+    #[test]
+    fn test_non_participants_get_nothing() {
+        let mut events = DamageEventManager::new();
+        let event_id = events.start(soul_pit(), 0);
+        let target = creature("Soul Target", CreatureType::Monster);
+        let bystander = creature("Rat", CreatureType::Monster);
+        events.add_target(event_id, target.id);
+
+        let (master, idle, elsewhere) = (
+            creature("Eryn", CreatureType::Player),
+            creature("Tarok", CreatureType::Player),
+            creature("Mira", CreatureType::Player),
+        );
+        let mut summon = creature("Fire Elemental", CreatureType::Summon);
+        summon.summon_master_id = Some(master.id);
+
+        events.record_hit(&summon, &target, 1_500, 1_000);
+        // Damage to creatures outside the event does not count
+        events.record_hit(&elsewhere, &bystander, 50_000, 1_000);
+
+        let result = events.finish(event_id).unwrap();
+        assert_eq!(result.awards.len(), 1);
+        assert_eq!(result.award(master.id).unwrap().total_damage, 1_500);
+        assert!(result.award(summon.id).is_none());
+        assert!(result.award(idle.id).is_none());
+        assert!(result.award(elsewhere.id).is_none());
+
+        // The event's creatures are released
+        events.record_hit(&master, &target, 1_000, 2_000);
+        assert!(events.finish(event_id).is_none());
+    }
+}
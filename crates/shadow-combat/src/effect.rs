@@ -0,0 +1,82 @@
+//! Visual effects produced by combat
+//!
+//! Combat only changes creature state; what the client should show for it
+//! (blood splashes, spell animations, projectiles and floating damage
+//! numbers) is returned alongside as `EffectEvent`s in the `CombatResult`
+//! for the session layer to relay to spectators.
+
+use serde::{Deserialize, Serialize};
+use shadow_world::item::{AmmoType, ShootType};
+use shadow_world::position::Position;
+
+use crate::damage::{BlockType, DamageType, DamageTypeExt};
+
+/// CONST_ME_POFF - missed or absorbed hit
+pub const EFFECT_POFF: u8 = 3;
+/// CONST_ME_BLOCKHIT - hit blocked by shield or armor
+pub const EFFECT_BLOCK_HIT: u8 = 4;
+
+/// An effect for the client to display
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EffectEvent {
+    /// Magic effect on a tile
+    Magic { position: Position, effect: u8 },
+    /// Projectile flying between two tiles
+    Distance { from: Position, to: Position, effect: u8 },
+    /// Floating text above a tile, e.g. damage or healing numbers
+    AnimatedText { position: Position, color: u8, text: String },
+}
+
+impl EffectEvent {
+    pub fn magic(position: Position, effect: u8) -> Self {
+        EffectEvent::Magic { position, effect }
+    }
+
+    pub fn distance(from: Position, to: Position, effect: u8) -> Self {
+        EffectEvent::Distance { from, to, effect }
+    }
+
+    /// Damage or healing number in the damage type's color
+    pub fn damage_text(position: Position, damage_type: DamageType, amount: i32) -> Self {
+        EffectEvent::AnimatedText {
+            position,
+            color: damage_type.get_text_color(),
+            text: amount.abs().to_string(),
+        }
+    }
+
+    /// Effects of a hit of `amount` damage on `position`
+    pub fn hit(position: Position, damage_type: DamageType, amount: i32) -> Vec<Self> {
+        vec![
+            Self::magic(position, damage_type.get_magic_effect()),
+            Self::damage_text(position, damage_type, amount),
+        ]
+    }
+
+    /// Effect of a blocked hit
+    pub fn block(position: Position, block_type: BlockType) -> Self {
+        let effect = match block_type {
+            BlockType::Defense | BlockType::Armor => EFFECT_BLOCK_HIT,
+            BlockType::None | BlockType::Immunity => EFFECT_POFF,
+        };
+        Self::magic(position, effect)
+    }
+}
+
+/// Client id of a projectile (CONST_ANI_*)
+pub fn shoot_effect_id(shoot: ShootType) -> u8 {
+    shoot as u8 + 1
+}
+
+/// Projectile shown when firing `ammo`
+pub fn ammo_shoot_effect(ammo: AmmoType) -> u8 {
+    shoot_effect_id(match ammo {
+        AmmoType::Arrow => ShootType::Arrow,
+        AmmoType::Bolt => ShootType::Bolt,
+        AmmoType::Spear => ShootType::Spear,
+        AmmoType::ThrowingStar => ShootType::ThrowingStar,
+        AmmoType::ThrowingKnife => ShootType::ThrowingKnife,
+        AmmoType::Stone => ShootType::SmallStone,
+        AmmoType::Snowball => ShootType::Snowball,
+    })
+}
@@ -0,0 +1,161 @@
+//! Creature elemental modifiers
+//!
+//! A creature's `resistances`, filled from its monster type's `elements`,
+//! are the one source of its elemental modifiers: the combat pipeline
+//! scales every hit by them and the bestiary shows them. A positive value
+//! is the percent of damage resisted, a negative value the percent of
+//! extra damage taken, and 100 or more is an immunity. The bestiary
+//! reveals a creature's modifiers once the player's entry reaches the
+//! configured stage.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::damage::{DamageInfo, DamageType};
+
+/// How a creature takes damage of one type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ElementAffinity {
+    /// Takes extra damage
+    Weak,
+    Neutral,
+    /// Takes reduced damage
+    Strong,
+    /// Takes no damage
+    Immune,
+}
+
+impl ElementAffinity {
+    pub fn from_resistance(resistance: i32) -> Self {
+        if resistance >= 100 {
+            ElementAffinity::Immune
+        } else if resistance > 0 {
+            ElementAffinity::Strong
+        } else if resistance < 0 {
+            ElementAffinity::Weak
+        } else {
+            ElementAffinity::Neutral
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ElementAffinity::Weak => "weak",
+            ElementAffinity::Neutral => "neutral",
+            ElementAffinity::Strong => "strong",
+            ElementAffinity::Immune => "immune",
+        }
+    }
+}
+
+/// A creature's modifier for one damage type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElementModifier {
+    pub damage_type: DamageType,
+    /// Percent of damage resisted; negative for a weakness
+    pub resistance: i32,
+    pub affinity: ElementAffinity,
+}
+
+impl ElementModifier {
+    /// Damage taken as a percent of the unmodified damage
+    pub fn damage_percent(&self) -> i32 {
+        100 - self.resistance.min(100)
+    }
+}
+
+/// Scale a hit by the target's modifier for its damage type
+pub fn apply_element_modifier(resistances: &HashMap<DamageType, i32>, damage: &mut DamageInfo) {
+    if let Some(&resistance) = resistances.get(&damage.damage_type) {
+        damage.apply_resistance(resistance.min(100));
+    }
+}
+
+/// Every non-neutral modifier, weaknesses first
+pub fn element_modifiers(resistances: &HashMap<DamageType, i32>) -> Vec<ElementModifier> {
+    let mut modifiers: Vec<ElementModifier> = resistances
+        .iter()
+        .filter(|(_, &resistance)| resistance != 0)
+        .map(|(&damage_type, &resistance)| ElementModifier {
+            damage_type,
+            resistance,
+            affinity: ElementAffinity::from_resistance(resistance),
+        })
+        .collect();
+    modifiers.sort_by_key(|m| (m.resistance, format!("{:?}", m.damage_type)));
+    modifiers
+}
+
+/// When the bestiary reveals elemental modifiers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BestiaryElementConfig {
+    /// Bestiary stage (1-4) at which a creature's modifiers are shown
+    pub reveal_stage: u8,
+}
+
+impl Default for BestiaryElementConfig {
+    fn default() -> Self {
+        Self { reveal_stage: 2 }
+    }
+}
+
+impl BestiaryElementConfig {
+    /// Modifiers shown to a player at bestiary `stage`; None while hidden
+    pub fn revealed(&self, resistances: &HashMap<DamageType, i32>, stage: u8) -> Option<Vec<ElementModifier>> {
+        (stage >= self.reveal_stage).then(|| element_modifiers(resistances))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dragon() -> HashMap<DamageType, i32> {
+        HashMap::from([
+            (DamageType::Fire, 100),
+            (DamageType::Ice, -10),
+            (DamageType::Earth, 20),
+            (DamageType::Energy, 0),
+        ])
+    }
+
+    #[test]
+    fn test_weakness_adds_damage() {
+        let elements = dragon();
+
+        let mut damage = DamageInfo::spell(DamageType::Ice, 200);
+        apply_element_modifier(&elements, &mut damage);
+        assert_eq!(damage.value, 220);
+
+        let mut damage = DamageInfo::spell(DamageType::Earth, 200);
+        apply_element_modifier(&elements, &mut damage);
+        assert_eq!(damage.value, 160);
+
+        let mut damage = DamageInfo::spell(DamageType::Fire, 200);
+        apply_element_modifier(&elements, &mut damage);
+        assert_eq!(damage.value, 0);
+
+        let mut damage = DamageInfo::spell(DamageType::Holy, 200);
+        apply_element_modifier(&elements, &mut damage);
+        assert_eq!(damage.value, 200);
+    }
+
+    #[test]
+    fn test_modifiers_revealed_at_stage() {
+        let config = BestiaryElementConfig::default();
+        let elements = dragon();
+
+        assert!(config.revealed(&elements, 1).is_none());
+        let shown = config.revealed(&elements, 2).unwrap();
+        let summary: Vec<_> = shown.iter().map(|m| (m.damage_type, m.affinity, m.damage_percent())).collect();
+        assert_eq!(
+            summary,
+            vec![
+                (DamageType::Ice, ElementAffinity::Weak, 110),
+                (DamageType::Earth, ElementAffinity::Strong, 80),
+                (DamageType::Fire, ElementAffinity::Immune, 0),
+            ]
+        );
+    }
+}
@@ -0,0 +1,176 @@
+//! Encounter damage log and kill credit
+//!
+//! Every hit on a creature is logged with its time. When the creature
+//! dies the log is turned into a `KillCredit`: the killer is whoever
+//! landed the killing blow and the assists are everyone else who hit it
+//! within the assist window, strongest damage first. Summon damage is
+//! logged for the summon's master. Matchmaking stats, kill statistics and
+//! the skull system all consume the same credit.
+
+use serde::{Deserialize, Serialize};
+use shadow_world::creature::Creature;
+use std::collections::HashMap;
+
+/// Who gets credit for a kill
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KillCredit {
+    /// Creature that landed the killing blow
+    pub killer: u32,
+    /// Other creatures that hit the victim within the assist window
+    pub assists: Vec<u32>,
+    pub victim: u32,
+    /// Damage of the killing blow beyond the victim's remaining health
+    pub overkill: i32,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct DamageRecord {
+    attacker_id: u32,
+    damage: i32,
+    time: u64,
+}
+
+/// Damage dealt to creatures that are still alive
+#[derive(Debug, Clone)]
+pub struct EncounterLog {
+    /// How long a hit counts towards an assist, in milliseconds
+    assist_window_ms: u64,
+    logs: HashMap<u32, Vec<DamageRecord>>,
+}
+
+impl EncounterLog {
+    pub fn new(assist_window_ms: u64) -> Self {
+        Self {
+            assist_window_ms,
+            logs: HashMap::new(),
+        }
+    }
+
+    /// Log a hit of `dealt` damage on `target`, which had `health_before`.
+    /// Returns the kill credit if the hit killed the target.
+    pub fn record_hit(
+        &mut self,
+        attacker: &Creature,
+        target: &Creature,
+        dealt: i32,
+        health_before: i32,
+        time: u64,
+    ) -> Option<KillCredit> {
+        let attacker_id = attacker.summon_master_id.unwrap_or(attacker.id);
+        self.logs.entry(target.id).or_default().push(DamageRecord {
+            attacker_id,
+            damage: dealt,
+            time,
+        });
+
+        if target.is_alive() {
+            return None;
+        }
+        self.kill_credit(target.id, (dealt - health_before).max(0), time)
+    }
+
+    /// Build the kill credit for `victim` from its log and forget the log
+    pub fn kill_credit(&mut self, victim: u32, overkill: i32, now: u64) -> Option<KillCredit> {
+        let log = self.logs.remove(&victim)?;
+        let killer = log.last()?.attacker_id;
+
+        // Total damage per assisting attacker, in order of first hit
+        let mut assists: Vec<(u32, i64)> = Vec::new();
+        for record in &log {
+            if record.attacker_id == killer || now.saturating_sub(record.time) > self.assist_window_ms {
+                continue;
+            }
+            match assists.iter_mut().find(|(id, _)| *id == record.attacker_id) {
+                Some((_, total)) => *total += record.damage as i64,
+                None => assists.push((record.attacker_id, record.damage as i64)),
+            }
+        }
+        assists.sort_by_key(|&(_, total)| std::cmp::Reverse(total));
+
+        Some(KillCredit {
+            killer,
+            assists: assists.into_iter().map(|(id, _)| id).collect(),
+            victim,
+            overkill,
+        })
+    }
+
+    /// Forget a creature's log, e.g. when it leaves combat or heals up
+    pub fn clear(&mut self, victim: u32) {
+        self.logs.remove(&victim);
+    }
+
+    /// Drop hits older than the assist window
+    pub fn prune(&mut self, now: u64) {
+        let window = self.assist_window_ms;
+        self.logs.retain(|_, log| {
+            log.retain(|record| now.saturating_sub(record.time) <= window);
+            !log.is_empty()
+        });
+    }
+}
+
+impl Default for EncounterLog {
+    fn default() -> Self {
+        Self::new(10_000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shadow_world::creature::CreatureType;
+    use shadow_world::position::Position;
+
+    fn creature(name: &str, health: i32) -> Creature {
+        let mut creature = Creature::new(name.to_string(), CreatureType::Player, Position::new(100, 100, 7));
+        creature.stats.health = health;
+        creature.stats.max_health = health;
+        creature
+    }
+
+    fn hit(log: &mut EncounterLog, attacker: &Creature, target: &mut Creature, damage: i32, time: u64) -> Option<KillCredit> {
+        let health_before = target.stats.health;
+        target.stats.health = (target.stats.health - damage).max(0);
+        log.record_hit(attacker, target, damage, health_before, time)
+    }
+
+    #[test]
+    fn test_kill_with_two_assists() {
+        let mut log = EncounterLog::new(10_000);
+        let (knight, druid, paladin) = (creature("Knight", 500), creature("Druid", 500), creature("Paladin", 500));
+        let mut victim = creature("Victim", 300);
+
+        assert!(hit(&mut log, &druid, &mut victim, 60, 1_000).is_none());
+        assert!(hit(&mut log, &knight, &mut victim, 100, 2_000).is_none());
+        assert!(hit(&mut log, &paladin, &mut victim, 80, 3_000).is_none());
+
+        // The knight lands the last blow with 40 health left
+        let credit = hit(&mut log, &knight, &mut victim, 90, 4_000).unwrap();
+        assert_eq!(credit.killer, knight.id);
+        assert_eq!(credit.assists, vec![paladin.id, druid.id]);
+        assert_eq!(credit.victim, victim.id);
+        assert_eq!(credit.overkill, 30);
+
+        // The log is consumed by the kill
+        assert!(log.kill_credit(victim.id, 0, 4_000).is_none());
+    }
+
+    #[test]
+    fn test_assist_window_and_summons() {
+        let mut log = EncounterLog::new(10_000);
+        let (sorcerer, early) = (creature("Sorcerer", 500), creature("Early", 500));
+        let mut summon = creature("Fire Elemental", 500);
+        summon.summon_master_id = Some(sorcerer.id);
+        let mut victim = creature("Victim", 200);
+
+        hit(&mut log, &early, &mut victim, 50, 0);
+        hit(&mut log, &sorcerer, &mut victim, 50, 12_000);
+        let credit = hit(&mut log, &summon, &mut victim, 150, 15_000).unwrap();
+
+        // The summon's blow is the master's; the early hit is too old to assist
+        assert_eq!(credit.killer, sorcerer.id);
+        assert!(credit.assists.is_empty());
+        assert_eq!(credit.overkill, 50);
+    }
+}
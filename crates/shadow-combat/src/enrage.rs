@@ -0,0 +1,166 @@
+//! Boss enrage timers
+//!
+//! Long boss fights must not be won by out-sustaining the boss forever.
+//! Once a fight has run for `enrage_after_secs` the boss enrages: its damage
+//! and speed ramp up every `ramp_interval_secs` until `max_steps` is reached.
+//! `soft_enrage_after_secs` into the fight the players are warned first.
+//! Timers count from the start of the lair attempt and end with it, so a
+//! lair auto-reset also calms the boss.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Per-boss enrage configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnrageConfig {
+    /// Seconds into the fight the soft-enrage warning is shown (0 = no warning)
+    pub soft_enrage_after_secs: u32,
+    /// Seconds into the fight the boss enrages
+    pub enrage_after_secs: u32,
+    /// Seconds between two ramp steps once enraged
+    pub ramp_interval_secs: u32,
+    /// Extra damage per step, in percent
+    pub damage_percent_per_step: u32,
+    /// Extra speed per step, in percent
+    pub speed_percent_per_step: u32,
+    /// Ramp steps before the boss stops getting stronger
+    pub max_steps: u32,
+    /// Message shown with the soft-enrage warning
+    pub warning: String,
+}
+
+impl Default for EnrageConfig {
+    fn default() -> Self {
+        Self {
+            soft_enrage_after_secs: 480,
+            enrage_after_secs: 600,
+            ramp_interval_secs: 30,
+            damage_percent_per_step: 10,
+            speed_percent_per_step: 5,
+            max_steps: 20,
+            warning: "The boss is growing restless!".to_string(),
+        }
+    }
+}
+
+impl EnrageConfig {
+    /// Phase of a fight that has been running for `elapsed_secs`
+    pub fn phase(&self, elapsed_secs: u64) -> EnragePhase {
+        if elapsed_secs >= self.enrage_after_secs as u64 {
+            let since = elapsed_secs - self.enrage_after_secs as u64;
+            let ramps = since / self.ramp_interval_secs.max(1) as u64;
+            EnragePhase::Enraged {
+                step: (ramps as u32).saturating_add(1).min(self.max_steps.max(1)),
+            }
+        } else if self.soft_enrage_after_secs > 0 && elapsed_secs >= self.soft_enrage_after_secs as u64 {
+            EnragePhase::SoftEnrage
+        } else {
+            EnragePhase::Calm
+        }
+    }
+
+    /// Boss modifiers in `phase`
+    pub fn modifiers(&self, phase: EnragePhase) -> EnrageModifiers {
+        match phase {
+            EnragePhase::Enraged { step } => EnrageModifiers {
+                damage_percent: 100 + self.damage_percent_per_step * step,
+                speed_percent: 100 + self.speed_percent_per_step * step,
+            },
+            EnragePhase::Calm | EnragePhase::SoftEnrage => EnrageModifiers::default(),
+        }
+    }
+}
+
+/// How far a fight has progressed towards enrage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EnragePhase {
+    Calm,
+    /// Warned, but not stronger yet
+    SoftEnrage,
+    /// Enraged at ramp `step` (starting at 1)
+    Enraged { step: u32 },
+}
+
+/// Damage and speed of an enraged boss, in percent of normal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnrageModifiers {
+    pub damage_percent: u32,
+    pub speed_percent: u32,
+}
+
+impl Default for EnrageModifiers {
+    fn default() -> Self {
+        Self {
+            damage_percent: 100,
+            speed_percent: 100,
+        }
+    }
+}
+
+impl EnrageModifiers {
+    pub fn apply_damage(&self, damage: i32) -> i32 {
+        (damage as i64 * self.damage_percent as i64 / 100) as i32
+    }
+
+    pub fn apply_speed(&self, speed: u16) -> u16 {
+        (speed as u32 * self.speed_percent / 100).min(u16::MAX as u32) as u16
+    }
+}
+
+/// A phase change reported by the boss ability tick
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnrageEvent {
+    /// The boss is about to enrage
+    Warning { message: String },
+    /// The boss enraged or ramped up to a new step
+    Ramp { step: u32, modifiers: EnrageModifiers },
+}
+
+/// Enrage timer of one running boss fight
+#[derive(Debug, Clone)]
+pub struct EnrageTimer {
+    config: EnrageConfig,
+    started_at: DateTime<Utc>,
+    phase: EnragePhase,
+}
+
+impl EnrageTimer {
+    pub fn new(config: EnrageConfig, started_at: DateTime<Utc>) -> Self {
+        Self {
+            config,
+            started_at,
+            phase: EnragePhase::Calm,
+        }
+    }
+
+    pub fn phase(&self) -> EnragePhase {
+        self.phase
+    }
+
+    /// Current boss modifiers
+    pub fn modifiers(&self) -> EnrageModifiers {
+        self.config.modifiers(self.phase)
+    }
+
+    /// Advance the timer to `now`. Returns an event when the phase changed.
+    pub fn tick(&mut self, now: DateTime<Utc>) -> Option<EnrageEvent> {
+        let elapsed = (now - self.started_at).num_seconds().max(0) as u64;
+        let phase = self.config.phase(elapsed);
+        if phase == self.phase {
+            return None;
+        }
+        self.phase = phase;
+
+        match phase {
+            EnragePhase::Calm => None,
+            EnragePhase::SoftEnrage => Some(EnrageEvent::Warning {
+                message: self.config.warning.clone(),
+            }),
+            EnragePhase::Enraged { step } => Some(EnrageEvent::Ramp {
+                step,
+                modifiers: self.modifiers(),
+            }),
+        }
+    }
+}
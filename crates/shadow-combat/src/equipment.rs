@@ -0,0 +1,271 @@
+//! Equipment slot rules
+//!
+//! Items only go into slots they fit, a two-handed weapon occupies both
+//! hands, and ammunition needs a distance weapon to be fired from. Every
+//! successful change returns the recomputed combat stats, so the character
+//! sheet never shows stats for a set of equipment that cannot be worn.
+
+use serde::{Deserialize, Serialize};
+use shadow_world::item::{SlotType, WeaponType};
+use std::collections::HashMap;
+
+use crate::sheet::{CombatStats, SheetItem, SheetSkills};
+
+/// What equipping a two-handed weapon does to the other hand
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TwoHandedPolicy {
+    /// The item in the other hand is unequipped
+    #[default]
+    Unequip,
+    /// The weapon cannot be equipped until the other hand is empty
+    Reject,
+}
+
+/// Slot conflict rules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EquipmentRules {
+    pub two_handed: TwoHandedPolicy,
+    /// Ammunition may only be equipped while a distance weapon is held
+    pub ammo_requires_distance_weapon: bool,
+}
+
+impl Default for EquipmentRules {
+    fn default() -> Self {
+        Self {
+            two_handed: TwoHandedPolicy::Unequip,
+            ammo_requires_distance_weapon: true,
+        }
+    }
+}
+
+/// Why an item cannot be equipped
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EquipError {
+    /// The item does not fit `slot`
+    WrongSlot(SlotType),
+    /// `slot` is not a body slot (two-handed items go into a hand)
+    InvalidSlot(SlotType),
+    /// The other hand must be emptied before wielding a two-handed weapon
+    HandsOccupied,
+    /// A two-handed weapon is held
+    TwoHandedWeaponEquipped,
+    AmmoWithoutDistanceWeapon,
+}
+
+impl std::fmt::Display for EquipError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EquipError::WrongSlot(slot) => write!(f, "This item cannot be worn in the {:?} slot", slot),
+            EquipError::InvalidSlot(slot) => write!(f, "{:?} is not an equipment slot", slot),
+            EquipError::HandsOccupied => write!(f, "Both hands must be free to wield this weapon"),
+            EquipError::TwoHandedWeaponEquipped => write!(f, "You are holding a two-handed weapon"),
+            EquipError::AmmoWithoutDistanceWeapon => write!(f, "You need a distance weapon to use this ammunition"),
+        }
+    }
+}
+
+impl std::error::Error for EquipError {}
+
+/// Result of a successful equipment change
+#[derive(Debug, Clone)]
+pub struct EquipChange {
+    /// Items taken off, to be moved to the backpack
+    pub unequipped: Vec<SheetItem>,
+    pub stats: CombatStats,
+}
+
+/// Items worn by a character, by slot
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Equipment {
+    slots: HashMap<SlotType, SheetItem>,
+}
+
+impl Equipment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, slot: SlotType) -> Option<&SheetItem> {
+        self.slots.get(&slot)
+    }
+
+    pub fn items(&self) -> Vec<SheetItem> {
+        self.slots.values().cloned().collect()
+    }
+
+    pub fn stats(&self, level: u16, skills: &SheetSkills) -> CombatStats {
+        CombatStats::compute(level, skills, &self.items())
+    }
+
+    fn hands(&self) -> impl Iterator<Item = &SheetItem> {
+        [SlotType::Right, SlotType::Left].into_iter().filter_map(|slot| self.slots.get(&slot))
+    }
+}
+
+fn is_two_handed(item: &SheetItem) -> bool {
+    item.slot == Some(SlotType::TwoHanded)
+}
+
+fn is_ammo(item: &SheetItem) -> bool {
+    item.slot == Some(SlotType::Ammo) || item.weapon_type == Some(WeaponType::Ammunition)
+}
+
+fn other_hand(slot: SlotType) -> Option<SlotType> {
+    match slot {
+        SlotType::Right => Some(SlotType::Left),
+        SlotType::Left => Some(SlotType::Right),
+        _ => None,
+    }
+}
+
+/// Checks equipment changes against the slot rules
+#[derive(Debug, Clone, Default)]
+pub struct EquipmentValidator {
+    rules: EquipmentRules,
+}
+
+impl EquipmentValidator {
+    pub fn new(rules: EquipmentRules) -> Self {
+        Self { rules }
+    }
+
+    /// Whether `item` fits `slot`, ignoring the other equipped items
+    fn fits(item: &SheetItem, slot: SlotType) -> bool {
+        match slot {
+            SlotType::Right | SlotType::Left => {
+                matches!(item.slot, Some(SlotType::Right | SlotType::Left | SlotType::TwoHanded))
+                    || (item.weapon_type.is_some() && !is_ammo(item))
+            }
+            SlotType::Ammo => is_ammo(item),
+            _ => item.slot == Some(slot),
+        }
+    }
+
+    /// Check that `item` can go into `slot` and list what it displaces
+    fn check(&self, equipment: &Equipment, slot: SlotType, item: &SheetItem) -> Result<Vec<SlotType>, EquipError> {
+        if slot == SlotType::TwoHanded {
+            return Err(EquipError::InvalidSlot(slot));
+        }
+        if !Self::fits(item, slot) {
+            return Err(EquipError::WrongSlot(slot));
+        }
+
+        let mut displaced = vec![slot];
+        if let Some(other) = other_hand(slot) {
+            let other_item = equipment.get(other);
+            if is_two_handed(item) {
+                if other_item.is_some() {
+                    if self.rules.two_handed == TwoHandedPolicy::Reject {
+                        return Err(EquipError::HandsOccupied);
+                    }
+                    displaced.push(other);
+                }
+            } else if other_item.is_some_and(is_two_handed) {
+                return Err(EquipError::TwoHandedWeaponEquipped);
+            }
+        }
+
+        if slot == SlotType::Ammo
+            && self.rules.ammo_requires_distance_weapon
+            && !equipment.hands().any(|held| held.weapon_type == Some(WeaponType::Distance))
+        {
+            return Err(EquipError::AmmoWithoutDistanceWeapon);
+        }
+        Ok(displaced)
+    }
+
+    /// Equip `item` into `slot`, taking off whatever it replaces or conflicts with
+    pub fn equip(
+        &self,
+        equipment: &mut Equipment,
+        slot: SlotType,
+        item: SheetItem,
+        level: u16,
+        skills: &SheetSkills,
+    ) -> Result<EquipChange, EquipError> {
+        let displaced = self.check(equipment, slot, &item)?;
+        let unequipped = displaced.into_iter().filter_map(|slot| equipment.slots.remove(&slot)).collect();
+        equipment.slots.insert(slot, item);
+        Ok(EquipChange {
+            unequipped,
+            stats: equipment.stats(level, skills),
+        })
+    }
+
+    /// Take off the item in `slot`
+    pub fn unequip(&self, equipment: &mut Equipment, slot: SlotType, level: u16, skills: &SheetSkills) -> EquipChange {
+        EquipChange {
+            unequipped: equipment.slots.remove(&slot).into_iter().collect(),
+            stats: equipment.stats(level, skills),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn skills() -> SheetSkills {
+        SheetSkills { axe: 80, distance: 70, shielding: 60, ..Default::default() }
+    }
+
+    fn shield() -> SheetItem {
+        SheetItem { slot: Some(SlotType::Left), weapon_type: Some(WeaponType::Shield), defense: 30, ..Default::default() }
+    }
+
+    #[test]
+    fn test_two_handed_weapon_unequips_shield() {
+        let validator = EquipmentValidator::default();
+        let mut equipment = Equipment::new();
+        validator.equip(&mut equipment, SlotType::Left, shield(), 100, &skills()).unwrap();
+
+        let great_axe = SheetItem {
+            slot: Some(SlotType::TwoHanded),
+            weapon_type: Some(WeaponType::Axe),
+            attack: 50,
+            defense: 20,
+            ..Default::default()
+        };
+        let change = validator.equip(&mut equipment, SlotType::Right, great_axe.clone(), 100, &skills()).unwrap();
+        assert_eq!(change.unequipped.len(), 1);
+        assert_eq!(change.unequipped[0].weapon_type, Some(WeaponType::Shield));
+        assert!(equipment.get(SlotType::Left).is_none());
+        // Defense now comes from the axe
+        assert_eq!((change.stats.attack, change.stats.defense), (50, 20));
+
+        // The shield cannot go back while the axe is held
+        let result = validator.equip(&mut equipment, SlotType::Left, shield(), 100, &skills());
+        assert_eq!(result.unwrap_err(), EquipError::TwoHandedWeaponEquipped);
+
+        let strict = EquipmentValidator::new(EquipmentRules { two_handed: TwoHandedPolicy::Reject, ..Default::default() });
+        let mut equipment = Equipment::new();
+        strict.equip(&mut equipment, SlotType::Left, shield(), 100, &skills()).unwrap();
+        let result = strict.equip(&mut equipment, SlotType::Right, great_axe, 100, &skills());
+        assert_eq!(result.unwrap_err(), EquipError::HandsOccupied);
+    }
+
+    #[test]
+    fn test_ammo_requires_distance_weapon() {
+        let validator = EquipmentValidator::default();
+        let mut equipment = Equipment::new();
+        let arrows = SheetItem {
+            slot: Some(SlotType::Ammo),
+            weapon_type: Some(WeaponType::Ammunition),
+            attack: 25,
+            ..Default::default()
+        };
+
+        let result = validator.equip(&mut equipment, SlotType::Ammo, arrows.clone(), 100, &skills());
+        assert_eq!(result.unwrap_err(), EquipError::AmmoWithoutDistanceWeapon);
+        assert_eq!(
+            validator.equip(&mut equipment, SlotType::Head, shield(), 100, &skills()).unwrap_err(),
+            EquipError::WrongSlot(SlotType::Head)
+        );
+
+        let bow = SheetItem { slot: Some(SlotType::TwoHanded), weapon_type: Some(WeaponType::Distance), attack: 5, ..Default::default() };
+        validator.equip(&mut equipment, SlotType::Left, bow, 100, &skills()).unwrap();
+        let change = validator.equip(&mut equipment, SlotType::Ammo, arrows, 100, &skills()).unwrap();
+        assert_eq!(change.stats.attack, 30);
+    }
+}
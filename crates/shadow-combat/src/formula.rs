@@ -4,6 +4,7 @@
 
 use crate::damage::{DamageInfo, DamageType};
 use shadow_world::creature::Creature;
+use shadow_world::forge::TierBonuses;
 
 /// Base combat formula trait
 pub trait CombatFormula {
@@ -57,6 +58,12 @@ impl MeleeFormula {
         self.element_damage = damage;
         self
     }
+
+    /// Add the attack bonus of a forged weapon's tier
+    pub fn with_tier_bonuses(mut self, bonuses: &TierBonuses) -> Self {
+        self.attack += bonuses.attack;
+        self
+    }
 }
 
 impl CombatFormula for MeleeFormula {
@@ -161,6 +168,12 @@ impl DistanceFormula {
             attack_factor: 1.0,
         }
     }
+
+    /// Add the attack bonus of a forged weapon's tier
+    pub fn with_tier_bonuses(mut self, bonuses: &TierBonuses) -> Self {
+        self.attack += bonuses.attack;
+        self
+    }
 }
 
 impl CombatFormula for DistanceFormula {
@@ -217,6 +230,11 @@ pub fn calculate_defense(base_defense: i32, extra_defense: i32, shielding_skill:
     (defense as f32 * shielding_skill as f32 / 100.0) as i32
 }
 
+/// Calculate defense value of a forged shield or weapon
+pub fn calculate_tiered_defense(base_defense: i32, extra_defense: i32, shielding_skill: u8, bonuses: &TierBonuses) -> i32 {
+    calculate_defense(base_defense + bonuses.defense, extra_defense, shielding_skill)
+}
+
 /// Calculate armor reduction
 pub fn calculate_armor_reduction(armor: i32) -> i32 {
     if armor <= 0 {
@@ -238,33 +256,49 @@ pub fn calculate_hit_chance(distance_skill: u8, hit_chance: i32, distance: u32)
     chance.clamp(0.1, 0.95) // 10% minimum, 95% maximum
 }
 
-/// Experience gained from combat
+/// Experience gained from combat, with only stamina applied.
+/// Kills that may carry event, boost or prey bonuses go through
+/// `MultiplierResolver::kill_experience` instead.
 pub fn calculate_experience(
     monster_experience: u64,
     player_level: u16,
     monster_level: u16,
     stamina_minutes: u16,
 ) -> u64 {
-    let mut exp = monster_experience;
+    let context = crate::multiplier::MultiplierContext {
+        stamina_minutes: Some(stamina_minutes),
+        ..Default::default()
+    };
+    crate::multiplier::MultiplierResolver::default().kill_experience(
+        monster_experience,
+        player_level,
+        monster_level,
+        &context,
+    )
+}
 
-    // Stamina bonus
+/// Experience multiplier for the remaining stamina
+pub fn stamina_multiplier(stamina_minutes: u16) -> f64 {
     if stamina_minutes > 42 * 60 {
         // Happy hour bonus (150%)
-        exp = (exp as f64 * 1.5) as u64;
+        1.5
     } else if stamina_minutes < 14 * 60 {
         // Low stamina penalty (50%)
-        exp = (exp as f64 * 0.5) as u64;
+        0.5
+    } else {
+        1.0
     }
+}
 
-    // Level difference penalty (optional, for more balanced servers)
+/// Level difference penalty (optional, for more balanced servers)
+pub fn level_difference_multiplier(player_level: u16, monster_level: u16) -> f64 {
     let level_diff = player_level as i32 - monster_level as i32;
     if level_diff > 50 {
         // Reduce exp for killing much weaker monsters
-        let penalty = 1.0 - ((level_diff - 50) as f64 * 0.01).min(0.9);
-        exp = (exp as f64 * penalty) as u64;
+        1.0 - ((level_diff - 50) as f64 * 0.01).min(0.9)
+    } else {
+        1.0
     }
-
-    exp
 }
 
 /// Skill advancement calculation
@@ -323,6 +357,23 @@ mod tests {
         assert!(max >= min);
     }
 
+    #[test]
+    fn test_tier_bonuses_raise_damage_and_defense() {
+        let creature = create_test_creature();
+        let tier3 = TierBonuses::for_tier(3);
+
+        let plain = MeleeFormula::new(50);
+        let forged = MeleeFormula::new(50).with_tier_bonuses(&tier3);
+        assert_eq!(forged.attack, 54);
+        assert!(forged.get_max_damage(&creature, 100, 100) > plain.get_max_damage(&creature, 100, 100));
+
+        let bow = DistanceFormula::new(30, 20, 90).with_tier_bonuses(&TierBonuses::for_tier(1));
+        assert_eq!(bow.attack, 31);
+
+        assert_eq!(calculate_tiered_defense(30, 0, 100, &TierBonuses::for_tier(0)), calculate_defense(30, 0, 100));
+        assert_eq!(calculate_tiered_defense(30, 0, 100, &tier3), 34);
+    }
+
     #[test]
     fn test_hit_chance() {
         let chance = calculate_hit_chance(100, 90, 1);
@@ -0,0 +1,209 @@
+//! Creature immunities and field blocking
+//!
+//! One table, loaded at startup, answers two questions the combat and area
+//! systems used to decide case by case: is a creature immune to a condition
+//! or damage type, and may a field be created on a tile. Creatures are
+//! looked up by name. Tiles block fields when they are protection zones,
+//! when their ground or an item on them is listed, or when their position
+//! is listed.
+
+use serde::{Deserialize, Serialize};
+use shadow_world::position::Position;
+use shadow_world::tile::Tile;
+use std::collections::{HashMap, HashSet};
+
+use crate::area::AreaTargetContext;
+use crate::damage::{ConditionType, DamageType};
+
+/// Immunities of one creature type
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CreatureImmunities {
+    pub creature_name: String,
+    pub conditions: Vec<ConditionType>,
+    pub damage_types: Vec<DamageType>,
+}
+
+/// On-disk form of the table
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImmunityData {
+    pub creatures: Vec<CreatureImmunities>,
+    /// Ground or item types no field can be created on
+    pub field_blocking_items: Vec<u16>,
+    /// Individual tiles no field can be created on
+    pub field_blocking_positions: Vec<Position>,
+}
+
+/// Why a field cannot be created on a tile
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldBlocked {
+    ProtectionZone,
+    /// The tile's ground or an item on it blocks fields
+    BlockingItem(u16),
+    /// The tile itself is listed as blocking
+    BlockingTile,
+}
+
+impl std::fmt::Display for FieldBlocked {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FieldBlocked::ProtectionZone => write!(f, "Fields cannot be created in a protection zone"),
+            FieldBlocked::BlockingItem(id) => write!(f, "Item {} does not allow fields", id),
+            FieldBlocked::BlockingTile => write!(f, "There is not enough room"),
+        }
+    }
+}
+
+impl std::error::Error for FieldBlocked {}
+
+/// Immunity table load errors
+#[derive(Debug, Clone)]
+pub enum ImmunityError {
+    IoError(String),
+    ParseError(String),
+}
+
+impl std::fmt::Display for ImmunityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImmunityError::IoError(msg) => write!(f, "IO error: {}", msg),
+            ImmunityError::ParseError(msg) => write!(f, "Parse error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ImmunityError {}
+
+/// Creature immunities and field-blocking tiles
+#[derive(Debug, Clone, Default)]
+pub struct ImmunityTable {
+    creatures: HashMap<String, CreatureImmunities>,
+    blocking_items: HashSet<u16>,
+    blocking_positions: HashSet<Position>,
+}
+
+impl ImmunityTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_data(data: ImmunityData) -> Self {
+        let mut table = Self::new();
+        for creature in data.creatures {
+            table.register(creature);
+        }
+        table.blocking_items.extend(data.field_blocking_items);
+        table.blocking_positions.extend(data.field_blocking_positions);
+        table
+    }
+
+    /// Load the table from a JSON file
+    pub fn load_from_file(path: &str) -> Result<Self, ImmunityError> {
+        let content = std::fs::read_to_string(path).map_err(|e| ImmunityError::IoError(e.to_string()))?;
+        let data: ImmunityData = serde_json::from_str(&content).map_err(|e| ImmunityError::ParseError(e.to_string()))?;
+        Ok(Self::from_data(data))
+    }
+
+    pub fn register(&mut self, immunities: CreatureImmunities) {
+        self.creatures.insert(immunities.creature_name.to_lowercase(), immunities);
+    }
+
+    pub fn get(&self, creature_name: &str) -> Option<&CreatureImmunities> {
+        self.creatures.get(&creature_name.to_lowercase())
+    }
+
+    pub fn is_immune_to_damage(&self, creature_name: &str, damage_type: DamageType) -> bool {
+        self.get(creature_name).is_some_and(|c| c.damage_types.contains(&damage_type))
+    }
+
+    pub fn is_immune_to_condition(&self, creature_name: &str, condition: ConditionType) -> bool {
+        self.get(creature_name).is_some_and(|c| c.conditions.contains(&condition))
+    }
+
+    /// Whether a field of `condition` affects a creature stepping on it.
+    /// Immunity to the condition or to the field's damage type protects.
+    pub fn field_affects(&self, creature_name: &str, condition: ConditionType) -> bool {
+        !self.is_immune_to_condition(creature_name, condition)
+            && !self.is_immune_to_damage(creature_name, condition.get_damage_type())
+    }
+
+    /// Add the creature's damage immunities to an area target context
+    pub fn apply_to_area_context(&self, creature_name: &str, ctx: &mut AreaTargetContext) {
+        if let Some(creature) = self.get(creature_name) {
+            for damage_type in &creature.damage_types {
+                if !ctx.immunities.contains(damage_type) {
+                    ctx.immunities.push(*damage_type);
+                }
+            }
+        }
+    }
+
+    /// Check that a field can be created on `tile`
+    pub fn can_create_field(&self, tile: &Tile) -> Result<(), FieldBlocked> {
+        if tile.flags.is_protection_zone() {
+            return Err(FieldBlocked::ProtectionZone);
+        }
+        if self.blocking_positions.contains(&tile.position) {
+            return Err(FieldBlocked::BlockingTile);
+        }
+        if let Some(item) = tile
+            .ground
+            .iter()
+            .chain(tile.items.iter())
+            .find(|item| self.blocking_items.contains(&item.item_type_id))
+        {
+            return Err(FieldBlocked::BlockingItem(item.item_type_id));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shadow_world::item::Item;
+
+    fn table() -> ImmunityTable {
+        let json = r#"{
+            "creatures": [
+                { "creature_name": "Slime", "conditions": ["Poison"], "damage_types": ["Earth"] },
+                { "creature_name": "Fire Elemental", "damage_types": ["Fire"] }
+            ],
+            "field_blocking_items": [4608],
+            "field_blocking_positions": [{ "x": 120, "y": 100, "z": 7 }]
+        }"#;
+        ImmunityTable::from_data(serde_json::from_str(json).unwrap())
+    }
+
+    #[test]
+    fn test_immune_creature_ignores_poison_field() {
+        let table = table();
+        assert!(!table.field_affects("slime", ConditionType::Poison));
+        assert!(table.field_affects("Slime", ConditionType::Fire));
+        // Damage immunity alone also protects from the matching field
+        assert!(!table.field_affects("Fire Elemental", ConditionType::Fire));
+        assert!(table.field_affects("Rat", ConditionType::Poison));
+
+        let mut ctx = AreaTargetContext::default();
+        table.apply_to_area_context("Slime", &mut ctx);
+        assert_eq!(ctx.immunities, vec![DamageType::Earth]);
+    }
+
+    #[test]
+    fn test_tile_rejects_field_creation() {
+        let table = table();
+        let open = Tile::with_ground(Position::new(100, 100, 7), Item::new(102));
+        assert!(table.can_create_field(&open).is_ok());
+
+        let water = Tile::with_ground(Position::new(101, 100, 7), Item::new(4608));
+        assert_eq!(table.can_create_field(&water), Err(FieldBlocked::BlockingItem(4608)));
+
+        let listed = Tile::new(Position::new(120, 100, 7));
+        assert_eq!(table.can_create_field(&listed), Err(FieldBlocked::BlockingTile));
+
+        let mut temple = Tile::new(Position::new(50, 50, 7));
+        temple.flags.set(shadow_world::tile::TileFlags::PROTECTION_ZONE);
+        assert_eq!(table.can_create_field(&temple), Err(FieldBlocked::ProtectionZone));
+    }
+}
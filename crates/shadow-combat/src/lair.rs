@@ -0,0 +1,407 @@
+//! Boss lairs - lever-activated boss rooms
+//!
+//! A lair is entered by a team pulling its lever. Activation checks that
+//! the lair is free, the team fits, and nobody is still on the boss
+//! cooldown kept in the Bosstiary; it then starts everyone's cooldown and
+//! asks the caller to spawn the boss. If the boss is not killed within the
+//! time limit the lair resets on the next tick and the caller removes the
+//! boss and teleports the team out. Bosses with an enrage configuration
+//! grow stronger the longer the attempt runs; see `enrage`.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use shadow_world::position::Position;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::bosstiary::{BossKillResult, BosstiaryManager};
+use crate::enrage::{EnrageConfig, EnrageEvent, EnrageModifiers, EnrageTimer};
+
+/// A boss lair definition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BossLair {
+    pub lair_id: u32,
+    /// Bosstiary boss fought in this lair
+    pub boss_id: u32,
+    pub boss_name: String,
+    /// Where the boss spawns
+    pub boss_position: Position,
+    /// Where the team is teleported on activation
+    pub entry_position: Position,
+    /// Where the team is teleported on reset
+    pub exit_position: Position,
+    pub min_occupants: u8,
+    pub max_occupants: u8,
+    /// Cooldown per player, counted from the attempt
+    pub cooldown_hours: u32,
+    /// Time the team has to kill the boss
+    pub time_limit_minutes: u32,
+    /// Enrage timers of the boss (none = never enrages)
+    #[serde(default)]
+    pub enrage: Option<EnrageConfig>,
+}
+
+impl BossLair {
+    pub fn new(lair_id: u32, boss_id: u32, boss_name: impl Into<String>, boss_position: Position) -> Self {
+        Self {
+            lair_id,
+            boss_id,
+            boss_name: boss_name.into(),
+            boss_position,
+            entry_position: boss_position,
+            exit_position: boss_position,
+            min_occupants: 1,
+            max_occupants: 5,
+            cooldown_hours: 20,
+            time_limit_minutes: 30,
+            enrage: None,
+        }
+    }
+
+    pub fn with_occupants(mut self, min: u8, max: u8) -> Self {
+        self.min_occupants = min;
+        self.max_occupants = max;
+        self
+    }
+
+    pub fn with_cooldown_hours(mut self, hours: u32) -> Self {
+        self.cooldown_hours = hours;
+        self
+    }
+
+    pub fn with_time_limit_minutes(mut self, minutes: u32) -> Self {
+        self.time_limit_minutes = minutes;
+        self
+    }
+
+    pub fn with_enrage(mut self, enrage: EnrageConfig) -> Self {
+        self.enrage = Some(enrage);
+        self
+    }
+}
+
+/// A running boss fight
+#[derive(Debug, Clone)]
+pub struct LairAttempt {
+    pub occupants: Vec<Uuid>,
+    pub started_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    /// Enrage timer of the boss, started with the attempt
+    pub enrage: Option<EnrageTimer>,
+}
+
+/// Boss to spawn and team to teleport after a lever pull
+#[derive(Debug, Clone)]
+pub struct LairActivation {
+    pub lair_id: u32,
+    pub boss_name: String,
+    pub boss_position: Position,
+    pub entry_position: Position,
+    pub occupants: Vec<Uuid>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// A lair reset because its time ran out
+#[derive(Debug, Clone)]
+pub struct LairReset {
+    pub lair_id: u32,
+    /// Boss to remove
+    pub boss_name: String,
+    /// Players to teleport to the exit
+    pub occupants: Vec<Uuid>,
+    pub exit_position: Position,
+}
+
+/// Lair access errors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LairError {
+    UnknownLair(u32),
+    /// Another team is fighting
+    Occupied,
+    /// Team size outside the lair's limits
+    InvalidTeamSize { min: u8, max: u8 },
+    /// A team member is still on cooldown
+    OnCooldown { player_id: Uuid, until: DateTime<Utc> },
+    /// No fight is running
+    NotActive,
+}
+
+impl std::fmt::Display for LairError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LairError::UnknownLair(id) => write!(f, "Unknown lair: {}", id),
+            LairError::Occupied => write!(f, "The lair is occupied"),
+            LairError::InvalidTeamSize { min, max } => {
+                write!(f, "The lair requires {} to {} players", min, max)
+            }
+            LairError::OnCooldown { player_id, until } => {
+                write!(f, "Player {} is on cooldown until {}", player_id, until)
+            }
+            LairError::NotActive => write!(f, "No fight is running in this lair"),
+        }
+    }
+}
+
+impl std::error::Error for LairError {}
+
+/// Controls access to boss lairs
+pub struct BossLairManager {
+    lairs: HashMap<u32, BossLair>,
+    attempts: HashMap<u32, LairAttempt>,
+}
+
+impl BossLairManager {
+    pub fn new() -> Self {
+        Self {
+            lairs: HashMap::new(),
+            attempts: HashMap::new(),
+        }
+    }
+
+    pub fn register(&mut self, lair: BossLair) {
+        self.lairs.insert(lair.lair_id, lair);
+    }
+
+    pub fn get_lair(&self, lair_id: u32) -> Option<&BossLair> {
+        self.lairs.get(&lair_id)
+    }
+
+    /// The running fight in a lair
+    pub fn attempt(&self, lair_id: u32) -> Option<&LairAttempt> {
+        self.attempts.get(&lair_id)
+    }
+
+    pub fn is_active(&self, lair_id: u32) -> bool {
+        self.attempts.contains_key(&lair_id)
+    }
+
+    /// Pull the lever for `team`. Starts every member's cooldown.
+    pub fn activate(
+        &mut self,
+        lair_id: u32,
+        team: &[Uuid],
+        bosstiary: &mut BosstiaryManager,
+        now: DateTime<Utc>,
+    ) -> Result<LairActivation, LairError> {
+        let lair = self.lairs.get(&lair_id).ok_or(LairError::UnknownLair(lair_id))?;
+        if self.attempts.contains_key(&lair_id) {
+            return Err(LairError::Occupied);
+        }
+        if team.len() < lair.min_occupants as usize || team.len() > lair.max_occupants as usize {
+            return Err(LairError::InvalidTeamSize { min: lair.min_occupants, max: lair.max_occupants });
+        }
+        for &player_id in team {
+            if let Some(until) = bosstiary.cooldown_until(player_id, lair.boss_id, now) {
+                return Err(LairError::OnCooldown { player_id, until });
+            }
+        }
+
+        let cooldown_until = now + Duration::hours(lair.cooldown_hours as i64);
+        for &player_id in team {
+            bosstiary.set_cooldown(player_id, lair.boss_id, cooldown_until);
+        }
+
+        let expires_at = now + Duration::minutes(lair.time_limit_minutes as i64);
+        self.attempts.insert(lair_id, LairAttempt {
+            occupants: team.to_vec(),
+            started_at: now,
+            expires_at,
+            enrage: lair.enrage.clone().map(|config| EnrageTimer::new(config, now)),
+        });
+
+        Ok(LairActivation {
+            lair_id,
+            boss_name: lair.boss_name.clone(),
+            boss_position: lair.boss_position,
+            entry_position: lair.entry_position,
+            occupants: team.to_vec(),
+            expires_at,
+        })
+    }
+
+    /// The boss died: credit the team in the Bosstiary and free the lair
+    pub fn boss_killed(
+        &mut self,
+        lair_id: u32,
+        bosstiary: &mut BosstiaryManager,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<(Uuid, BossKillResult)>, LairError> {
+        let lair = self.lairs.get(&lair_id).ok_or(LairError::UnknownLair(lair_id))?;
+        let attempt = self.attempts.remove(&lair_id).ok_or(LairError::NotActive)?;
+        let kill_time = (now - attempt.started_at).num_seconds().max(0) as u32;
+
+        Ok(attempt
+            .occupants
+            .iter()
+            .filter_map(|&player_id| {
+                bosstiary
+                    .record_kill(player_id, lair.boss_id, Some(kill_time))
+                    .map(|result| (player_id, result))
+            })
+            .collect())
+    }
+
+    /// Boss ability tick: advance the enrage timers of running fights.
+    /// Returns the lairs whose boss changed enrage phase.
+    pub fn ability_tick(&mut self, now: DateTime<Utc>) -> Vec<(u32, EnrageEvent)> {
+        let mut events = Vec::new();
+        for (&lair_id, attempt) in self.attempts.iter_mut() {
+            if let Some(event) = attempt.enrage.as_mut().and_then(|timer| timer.tick(now)) {
+                events.push((lair_id, event));
+            }
+        }
+        events
+    }
+
+    /// Damage and speed modifiers of the boss in a lair
+    pub fn enrage_modifiers(&self, lair_id: u32) -> EnrageModifiers {
+        self.attempts
+            .get(&lair_id)
+            .and_then(|attempt| attempt.enrage.as_ref())
+            .map(|timer| timer.modifiers())
+            .unwrap_or_default()
+    }
+
+    /// Reset lairs whose time limit passed without a kill
+    pub fn tick(&mut self, now: DateTime<Utc>) -> Vec<LairReset> {
+        let expired: Vec<u32> = self
+            .attempts
+            .iter()
+            .filter(|(_, attempt)| attempt.expires_at <= now)
+            .map(|(id, _)| *id)
+            .collect();
+
+        let mut resets = Vec::new();
+        for lair_id in expired {
+            let (Some(attempt), Some(lair)) = (self.attempts.remove(&lair_id), self.lairs.get(&lair_id)) else {
+                continue;
+            };
+            tracing::info!("Lair {} ({}) timed out and was reset", lair_id, lair.boss_name);
+            resets.push(LairReset {
+                lair_id,
+                boss_name: lair.boss_name.clone(),
+                occupants: attempt.occupants,
+                exit_position: lair.exit_position,
+            });
+        }
+        resets
+    }
+}
+
+impl Default for BossLairManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bosstiary::{BossDifficulty, BossEntry};
+
+    fn setup() -> (BossLairManager, BosstiaryManager) {
+        let mut bosstiary = BosstiaryManager::new();
+        bosstiary.register_boss(BossEntry {
+            boss_id: 7,
+            name: "Scarlett Etzel".to_string(),
+            difficulty: BossDifficulty::Archfoe,
+            description: String::new(),
+            locations: Vec::new(),
+            respawn_hours: None,
+            quest_related: true,
+            min_level: 250,
+            realm_id: None,
+            notable_loot: Vec::new(),
+        });
+
+        let mut lairs = BossLairManager::new();
+        lairs.register(
+            BossLair::new(1, 7, "Scarlett Etzel", Position::new(1000, 1000, 8))
+                .with_occupants(1, 2)
+                .with_cooldown_hours(20)
+                .with_time_limit_minutes(30),
+        );
+        (lairs, bosstiary)
+    }
+
+    #[test]
+    fn test_access_denied_during_cooldown() {
+        let (mut lairs, mut bosstiary) = setup();
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        let now = Utc::now();
+
+        let activation = lairs.activate(1, &[alice], &mut bosstiary, now).unwrap();
+        assert_eq!(activation.boss_name, "Scarlett Etzel");
+        assert_eq!(lairs.activate(1, &[bob], &mut bosstiary, now).unwrap_err(), LairError::Occupied);
+
+        let results = lairs.boss_killed(1, &mut bosstiary, now + Duration::minutes(10)).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].1.kill_count, 1);
+
+        // The lair is free again, but alice is on cooldown and blocks her team
+        let later = now + Duration::hours(1);
+        assert!(matches!(
+            lairs.activate(1, &[bob, alice], &mut bosstiary, later),
+            Err(LairError::OnCooldown { player_id, .. }) if player_id == alice
+        ));
+        assert_eq!(
+            lairs.activate(1, &[bob, alice, Uuid::new_v4()], &mut bosstiary, later).unwrap_err(),
+            LairError::InvalidTeamSize { min: 1, max: 2 }
+        );
+        assert!(lairs.activate(1, &[alice], &mut bosstiary, now + Duration::hours(21)).is_ok());
+    }
+
+    #[test]
+    fn test_auto_reset_after_time_limit() {
+        let (mut lairs, mut bosstiary) = setup();
+        let team = [Uuid::new_v4(), Uuid::new_v4()];
+        let now = Utc::now();
+
+        lairs.activate(1, &team, &mut bosstiary, now).unwrap();
+        assert!(lairs.tick(now + Duration::minutes(29)).is_empty());
+        assert!(lairs.is_active(1));
+
+        let resets = lairs.tick(now + Duration::minutes(30));
+        assert_eq!(resets.len(), 1);
+        assert_eq!(resets[0].occupants, team.to_vec());
+        assert!(!lairs.is_active(1));
+        assert_eq!(lairs.boss_killed(1, &mut bosstiary, now).unwrap_err(), LairError::NotActive);
+
+        // The failed attempt still counts against the cooldown
+        assert!(bosstiary.cooldown_until(team[0], 7, now + Duration::minutes(31)).is_some());
+    }
+
+    #[test]
+    fn test_enrage_increases_boss_damage_after_threshold() {
+        let (mut lairs, mut bosstiary) = setup();
+        let enrage = EnrageConfig {
+            soft_enrage_after_secs: 480,
+            enrage_after_secs: 600,
+            ramp_interval_secs: 60,
+            damage_percent_per_step: 25,
+            ..Default::default()
+        };
+        lairs.register(BossLair::new(2, 7, "Scarlett Etzel", Position::new(2000, 1000, 8)).with_enrage(enrage));
+        let now = Utc::now();
+        lairs.activate(2, &[Uuid::new_v4()], &mut bosstiary, now).unwrap();
+
+        // Not before the threshold, even after the warning
+        assert!(lairs.ability_tick(now + Duration::seconds(479)).is_empty());
+        assert!(matches!(
+            lairs.ability_tick(now + Duration::seconds(480)).as_slice(),
+            [(2, EnrageEvent::Warning { .. })]
+        ));
+        assert_eq!(lairs.enrage_modifiers(2).apply_damage(400), 400);
+
+        let events = lairs.ability_tick(now + Duration::seconds(600));
+        assert!(matches!(events.as_slice(), [(2, EnrageEvent::Ramp { step: 1, .. })]));
+        assert_eq!(lairs.enrage_modifiers(2).apply_damage(400), 500);
+        assert!(lairs.ability_tick(now + Duration::seconds(659)).is_empty());
+        lairs.ability_tick(now + Duration::seconds(660));
+        assert_eq!(lairs.enrage_modifiers(2).apply_damage(400), 600);
+
+        // The auto-reset ends the fight and with it the enrage
+        assert_eq!(lairs.tick(now + Duration::minutes(30)).len(), 1);
+        assert_eq!(lairs.enrage_modifiers(2).apply_damage(400), 400);
+    }
+}
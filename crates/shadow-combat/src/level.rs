@@ -0,0 +1,215 @@
+//! Level table - experience each character level requires
+//!
+//! Realms pick the retail formula, a custom exponent curve or an explicit
+//! table. The same table decides level-ups, how much experience a death can
+//! take (one level's worth at most) and the progress to the next level shown
+//! in the character sheet. Tables must strictly increase, which `validate`
+//! checks when a realm is created.
+
+use serde::{Deserialize, Serialize};
+
+/// Highest level a character can reach
+pub const MAX_LEVEL: u32 = u16::MAX as u32;
+
+/// Levels checked when validating a formula
+const VALIDATED_FORMULA_LEVELS: u32 = 2000;
+
+/// Total experience needed for each level
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LevelTable {
+    /// 50/3 * (L^3 - 6L^2 + 17L - 12)
+    #[default]
+    Retail,
+    /// base * (L - 1)^exponent
+    Exponent { base: f64, exponent: f64 },
+    /// Total experience of every level, starting with level 1 (0 experience).
+    /// The last entry is the level cap.
+    Custom { experience: Vec<u64> },
+}
+
+/// Why a level table was rejected
+#[derive(Debug, Clone, PartialEq)]
+pub enum LevelTableError {
+    /// Base or exponent not a positive number
+    InvalidFormula,
+    EmptyTable,
+    /// Level 1 must require no experience
+    NonZeroFirstLevel,
+    /// `level` does not need more experience than the level before it
+    NotMonotonic { level: u32 },
+}
+
+impl std::fmt::Display for LevelTableError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LevelTableError::InvalidFormula => write!(f, "Level formula base and exponent must be positive"),
+            LevelTableError::EmptyTable => write!(f, "Level table is empty"),
+            LevelTableError::NonZeroFirstLevel => write!(f, "Level 1 must require 0 experience"),
+            LevelTableError::NotMonotonic { level } => {
+                write!(f, "Level {} does not require more experience than level {}", level, level - 1)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LevelTableError {}
+
+/// Progress of a character towards its next level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LevelProgress {
+    pub level: u32,
+    pub experience: u64,
+    /// Experience the current level started at
+    pub level_experience: u64,
+    /// Experience the next level starts at, `None` at the level cap
+    pub next_level_experience: Option<u64>,
+}
+
+impl LevelProgress {
+    pub fn experience_to_next(&self) -> u64 {
+        self.next_level_experience
+            .map_or(0, |next| next.saturating_sub(self.experience))
+    }
+
+    /// Percent to the next level (0-99, 0 at the level cap)
+    pub fn percent(&self) -> u8 {
+        let Some(next) = self.next_level_experience else {
+            return 0;
+        };
+        let span = next.saturating_sub(self.level_experience).max(1);
+        let done = self.experience.saturating_sub(self.level_experience);
+        (done as u128 * 100 / span as u128).min(99) as u8
+    }
+}
+
+impl LevelTable {
+    /// Highest level this table reaches
+    pub fn max_level(&self) -> u32 {
+        match self {
+            LevelTable::Custom { experience } => (experience.len() as u32).clamp(1, MAX_LEVEL),
+            _ => MAX_LEVEL,
+        }
+    }
+
+    /// Total experience `level` requires (`u64::MAX` beyond the cap)
+    pub fn experience_for_level(&self, level: u32) -> u64 {
+        if level <= 1 {
+            return 0;
+        }
+        if level > self.max_level() {
+            return u64::MAX;
+        }
+        match self {
+            LevelTable::Retail => {
+                let l = level as i128;
+                let exp = 50 * (l.pow(3) - 6 * l.pow(2) + 17 * l - 12) / 3;
+                exp.clamp(0, u64::MAX as i128) as u64
+            }
+            LevelTable::Exponent { base, exponent } => {
+                let exp = (base * ((level - 1) as f64).powf(*exponent)).round();
+                if exp >= u64::MAX as f64 {
+                    u64::MAX
+                } else {
+                    exp.max(0.0) as u64
+                }
+            }
+            LevelTable::Custom { experience } => experience[level as usize - 1],
+        }
+    }
+
+    /// Level a character with `experience` has
+    pub fn level_for_experience(&self, experience: u64) -> u32 {
+        // Highest level whose requirement is met; requirements increase
+        let (mut low, mut high) = (1, self.max_level());
+        while low < high {
+            let mid = low + (high - low).div_ceil(2);
+            if self.experience_for_level(mid) <= experience {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        low
+    }
+
+    /// Level and progress of a character with `experience`
+    pub fn progress(&self, experience: u64) -> LevelProgress {
+        let level = self.level_for_experience(experience);
+        LevelProgress {
+            level,
+            experience,
+            level_experience: self.experience_for_level(level),
+            next_level_experience: (level < self.max_level()).then(|| self.experience_for_level(level + 1)),
+        }
+    }
+
+    /// Check that every level requires more experience than the one before
+    pub fn validate(&self) -> Result<(), LevelTableError> {
+        let last = match self {
+            LevelTable::Retail => VALIDATED_FORMULA_LEVELS,
+            LevelTable::Exponent { base, exponent } => {
+                if !(base.is_finite() && *base > 0.0 && exponent.is_finite() && *exponent > 0.0) {
+                    return Err(LevelTableError::InvalidFormula);
+                }
+                VALIDATED_FORMULA_LEVELS
+            }
+            LevelTable::Custom { experience } => {
+                match experience.first() {
+                    None => return Err(LevelTableError::EmptyTable),
+                    Some(&first) if first != 0 => return Err(LevelTableError::NonZeroFirstLevel),
+                    Some(_) => {}
+                }
+                self.max_level()
+            }
+        };
+
+        for level in 2..=last {
+            if self.experience_for_level(level) <= self.experience_for_level(level - 1) {
+                return Err(LevelTableError::NotMonotonic { level });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_exponent_changes_thresholds() {
+        let retail = LevelTable::Retail;
+        assert_eq!(retail.experience_for_level(2), 100);
+        assert_eq!(retail.experience_for_level(8), 4_200);
+        assert_eq!(retail.level_for_experience(4_199), 7);
+
+        let steep = LevelTable::Exponent { base: 100.0, exponent: 2.0 };
+        assert!(steep.validate().is_ok());
+        assert_eq!(steep.experience_for_level(2), 100);
+        assert_eq!(steep.experience_for_level(8), 4_900);
+        assert_eq!(steep.level_for_experience(4_200), 7);
+
+        let progress = steep.progress(4_200);
+        assert_eq!((progress.level, progress.experience_to_next()), (7, 700));
+        assert_eq!(progress.percent(), 46);
+    }
+
+    #[test]
+    fn test_non_monotonic_table_rejected() {
+        let table = LevelTable::Custom { experience: vec![0, 100, 300, 300, 900] };
+        assert_eq!(table.validate(), Err(LevelTableError::NotMonotonic { level: 4 }));
+
+        assert_eq!(LevelTable::Custom { experience: vec![] }.validate(), Err(LevelTableError::EmptyTable));
+        assert_eq!(
+            LevelTable::Exponent { base: 100.0, exponent: -1.0 }.validate(),
+            Err(LevelTableError::InvalidFormula)
+        );
+
+        // A valid table caps the level at its last entry
+        let capped = LevelTable::Custom { experience: vec![0, 100, 300] };
+        assert!(capped.validate().is_ok());
+        assert_eq!(capped.level_for_experience(1_000_000), 3);
+        assert_eq!(capped.progress(1_000_000).experience_to_next(), 0);
+    }
+}
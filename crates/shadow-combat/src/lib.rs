@@ -8,20 +8,62 @@ pub mod formula;
 pub mod spell;
 pub mod condition;
 pub mod combat;
+pub mod encounter;
+pub mod contribution;
 pub mod area;
+pub mod ammo;
 pub mod loot;
+pub mod bestiary_loot;
 pub mod prey;
 pub mod bosstiary;
+pub mod lair;
+pub mod reward_chest;
+pub mod boost;
+pub mod ruleset;
+pub mod sheet;
+pub mod skill;
+pub mod multiplier;
+pub mod combat_lock;
+pub mod combat_log;
+pub mod effect;
+pub mod new_character;
+pub mod enrage;
+pub mod equipment;
+pub mod immunity;
+pub mod elements;
+pub mod level;
+pub mod damage_event;
 
 pub use damage::{DamageInfo, DamageType, DamageTypeExt, ConditionType, DamageOrigin, BlockType};
 pub use formula::{CombatFormula, MeleeFormula, MagicFormula, DistanceFormula};
 pub use spell::{Spell, SpellType, SpellLoader};
 pub use condition::{CombatCondition, ConditionDamage};
 pub use combat::{CombatSystem, CombatEvent, CombatResult};
-pub use area::{AreaEffect, AreaType};
-pub use loot::{LootGenerator, LootTable, LootEntry, LootConfig, LootResult, GeneratedLoot};
+pub use encounter::{EncounterLog, KillCredit};
+pub use damage_event::{DamageEventAward, DamageEventConfig, DamageEventManager, DamageEventResult, DamageEventReward, DamageRewardTier};
+pub use contribution::{ContributionConfig, ContributionResolver, PveContribution, PveShareMode};
+pub use ammo::{AmmoConfig, AmmoStack, DistanceWeapon};
+pub use area::{AreaEffect, AreaTargetContext, AreaTargetPolicy, AreaType};
+pub use loot::{AttributeRoll, LootGenerator, LootTable, LootEntry, LootConfig, LootResult, GeneratedLoot, CurrencyConfig, CoinType};
+pub use bestiary_loot::{BestiaryLootConfig, LootPreview, LootPreviewEntry, LootRarity};
 pub use prey::{PreyManager, PlayerPrey, PreySlot, PreyBonusType};
 pub use bosstiary::{BosstiaryManager, PlayerBosstiary, BossEntry, BossDifficulty};
+pub use enrage::{EnrageConfig, EnrageEvent, EnrageModifiers, EnragePhase, EnrageTimer};
+pub use equipment::{EquipChange, EquipError, Equipment, EquipmentRules, EquipmentValidator, TwoHandedPolicy};
+pub use elements::{apply_element_modifier, element_modifiers, BestiaryElementConfig, ElementAffinity, ElementModifier};
+pub use immunity::{CreatureImmunities, FieldBlocked, ImmunityData, ImmunityError, ImmunityTable};
+pub use level::{LevelProgress, LevelTable, LevelTableError};
+pub use lair::{BossLair, BossLairManager, LairActivation, LairError, LairReset};
+pub use reward_chest::{RewardChest, RewardChestConfig, RewardChestManager};
+pub use boost::{BoostConfig, BoostManager, BoostStacking, BoostType, StoredBoost};
+pub use ruleset::RulesetFlags;
+pub use sheet::{CombatStats, SheetItem, SheetSkills};
+pub use skill::{SkillAdvancementConfig, SkillProgress, SkillTracker, VocationSkillFactors};
+pub use effect::EffectEvent;
+pub use combat_log::{CombatLog, CombatLogConfig, EncounterSummary, SourceBreakdown};
+pub use combat_lock::{CombatLockConfig, CombatLockError, CombatLockManager, PvpAction};
+pub use new_character::{NewCharacterProtection, NewCharacterProtectionConfig};
+pub use multiplier::{MultiplierConfig, MultiplierContext, MultiplierResolver, MultiplierStacking, RateEvent, ResolvedMultipliers};
 
 use thiserror::Error;
 
@@ -55,4 +97,10 @@ pub enum CombatError {
 
     #[error("Invalid target")]
     InvalidTarget,
+
+    #[error("No ammunition")]
+    NoAmmo,
+
+    #[error("Ammunition does not fit the weapon")]
+    IncompatibleAmmo,
 }
@@ -7,11 +7,16 @@
 //! - Party loot distribution modes
 //! - Boss-specific loot mechanics
 //! - Rare item announcements
+//! - Gold conversion into coin items
+//! - Randomized item stats rolled at drop time
 
 use rand::Rng;
 use serde::{Deserialize, Serialize};
+use shadow_world::item::{Item, ItemStat};
 use std::collections::HashMap;
 
+use crate::bestiary_loot::{BestiaryLootConfig, LootPreview};
+
 /// A single loot entry representing an item that can drop
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LootEntry {
@@ -33,6 +38,17 @@ pub struct LootEntry {
     /// Optional item name for logging
     #[serde(default)]
     pub name: Option<String>,
+    /// Stats rolled for each dropped instance
+    #[serde(default)]
+    pub attribute_rolls: Vec<AttributeRoll>,
+}
+
+/// Range a stat of a dropped item is rolled in
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct AttributeRoll {
+    pub stat: ItemStat,
+    pub min: i32,
+    pub max: i32,
 }
 
 impl LootEntry {
@@ -47,6 +63,7 @@ impl LootEntry {
             unique_id: None,
             contents: Vec::new(),
             name: None,
+            attribute_rolls: Vec::new(),
         }
     }
 
@@ -61,6 +78,7 @@ impl LootEntry {
             unique_id: None,
             contents: Vec::new(),
             name: None,
+            attribute_rolls: Vec::new(),
         }
     }
 
@@ -75,6 +93,7 @@ impl LootEntry {
             unique_id: None,
             contents,
             name: None,
+            attribute_rolls: Vec::new(),
         }
     }
 
@@ -89,6 +108,12 @@ impl LootEntry {
         self.name = Some(name.into());
         self
     }
+
+    /// Roll `stat` between `min` and `max` (inclusive) for each drop
+    pub fn with_attribute_roll(mut self, stat: ItemStat, min: i32, max: i32) -> Self {
+        self.attribute_rolls.push(AttributeRoll { stat, min: min.min(max), max: min.max(max) });
+        self
+    }
 }
 
 /// Complete loot table for a creature
@@ -157,6 +182,8 @@ pub struct GeneratedLoot {
     pub action_id: Option<u16>,
     pub unique_id: Option<u16>,
     pub contents: Vec<GeneratedLoot>,
+    /// Stats rolled for this instance
+    pub rolled_stats: HashMap<ItemStat, i32>,
 }
 
 impl GeneratedLoot {
@@ -168,9 +195,26 @@ impl GeneratedLoot {
             action_id: None,
             unique_id: None,
             contents: Vec::new(),
+            rolled_stats: HashMap::new(),
         }
     }
 
+    /// Create the item instance, with its rolled stats stored on it.
+    /// Container contents are created separately.
+    pub fn to_item(&self) -> Item {
+        let mut item = Item::with_count(self.item_id, self.count);
+        if let Some(action_id) = self.action_id {
+            item.action_id = action_id;
+        }
+        if let Some(unique_id) = self.unique_id {
+            item.unique_action_id = unique_id;
+        }
+        for (&stat, &value) in &self.rolled_stats {
+            item.set_stat(stat, value);
+        }
+        item
+    }
+
     /// Total item count including contents
     pub fn total_items(&self) -> usize {
         1 + self.contents.iter().map(|c| c.total_items()).sum::<usize>()
@@ -196,6 +240,101 @@ impl Default for LootDistribution {
     }
 }
 
+/// A coin item and its worth in gold
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoinType {
+    pub item_id: u16,
+    pub value: u32,
+}
+
+/// Currency items gold amounts are paid out in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurrencyConfig {
+    /// Coin items, any order
+    pub coins: Vec<CoinType>,
+    /// Largest stack of a single coin item
+    pub max_stack: u16,
+}
+
+impl Default for CurrencyConfig {
+    fn default() -> Self {
+        Self {
+            coins: vec![
+                CoinType { item_id: 3031, value: 1 },      // gold coin
+                CoinType { item_id: 3035, value: 100 },    // platinum coin
+                CoinType { item_id: 3043, value: 10_000 }, // crystal coin
+            ],
+            max_stack: 100,
+        }
+    }
+}
+
+impl CurrencyConfig {
+    /// Value of a coin item, if it is one
+    pub fn value_of(&self, item_id: u16) -> Option<u32> {
+        self.coins.iter().find(|c| c.item_id == item_id).map(|c| c.value)
+    }
+
+    /// The lowest-valued coin
+    fn base_coin(&self) -> Option<CoinType> {
+        self.coins.iter().copied().min_by_key(|c| c.value)
+    }
+
+    /// Pay out `amount` gold using the fewest coins, largest first
+    /// (12345 gold = 1 crystal, 23 platinum, 45 gold)
+    pub fn normalize(&self, amount: u64) -> Vec<GeneratedLoot> {
+        let mut coins = self.coins.clone();
+        coins.sort_by_key(|c| std::cmp::Reverse(c.value));
+
+        let mut remaining = amount;
+        let mut items = Vec::new();
+        for coin in coins.iter().filter(|c| c.value > 0) {
+            let count = remaining / coin.value as u64;
+            remaining -= count * coin.value as u64;
+            self.push_stacks(&mut items, coin.item_id, count);
+        }
+        items
+    }
+
+    /// Pay out `amount` gold in the lowest-valued coin only
+    pub fn unconverted(&self, amount: u64) -> Vec<GeneratedLoot> {
+        let mut items = Vec::new();
+        if let Some(coin) = self.base_coin().filter(|c| c.value > 0) {
+            self.push_stacks(&mut items, coin.item_id, amount / coin.value as u64);
+        }
+        items
+    }
+
+    /// Total gold value of the coin items among `items`, including contents
+    pub fn total_value(&self, items: &[GeneratedLoot]) -> u64 {
+        items
+            .iter()
+            .map(|i| {
+                let own = self.value_of(i.item_id).map_or(0, |v| v as u64 * i.count as u64);
+                own + self.total_value(&i.contents)
+            })
+            .sum()
+    }
+
+    /// Replace the loose coin items among `items` with their normalized
+    /// equivalent, leaving other items in place (auto-convert)
+    pub fn convert(&self, items: Vec<GeneratedLoot>) -> Vec<GeneratedLoot> {
+        let (coins, mut rest): (Vec<_>, Vec<_>) =
+            items.into_iter().partition(|i| self.value_of(i.item_id).is_some());
+        rest.extend(self.normalize(self.total_value(&coins)));
+        rest
+    }
+
+    fn push_stacks(&self, items: &mut Vec<GeneratedLoot>, item_id: u16, mut count: u64) {
+        let max_stack = self.max_stack.max(1) as u64;
+        while count > 0 {
+            let stack = count.min(max_stack);
+            items.push(GeneratedLoot::item(item_id, stack as u16));
+            count -= stack;
+        }
+    }
+}
+
 /// Loot generation configuration
 #[derive(Debug, Clone)]
 pub struct LootConfig {
@@ -209,6 +348,11 @@ pub struct LootConfig {
     pub rare_threshold: f32,
     /// Maximum nested container depth
     pub max_container_depth: u8,
+    /// Coin items dropped gold is paid out in
+    pub currency: CurrencyConfig,
+    /// Roll the configured stat ranges of dropped items; when off, items
+    /// drop with their base stats
+    pub roll_attributes: bool,
 }
 
 impl Default for LootConfig {
@@ -219,6 +363,8 @@ impl Default for LootConfig {
             announce_rare: true,
             rare_threshold: 0.5,
             max_container_depth: 2,
+            currency: CurrencyConfig::default(),
+            roll_attributes: true,
         }
     }
 }
@@ -266,6 +412,16 @@ impl LootGenerator {
         &mut self,
         creature_name: &str,
         killer_premium: bool,
+    ) -> Result<LootResult, LootError> {
+        self.generate_boosted(creature_name, killer_premium, 1.0)
+    }
+
+    /// Generate loot with an extra multiplier on the loot rate (loot boosts)
+    pub fn generate_boosted(
+        &mut self,
+        creature_name: &str,
+        killer_premium: bool,
+        boost_multiplier: f32,
     ) -> Result<LootResult, LootError> {
         let table = self.loot_tables
             .get(&creature_name.to_lowercase())
@@ -280,8 +436,9 @@ impl LootGenerator {
         };
 
         // Calculate effective loot rate
-        let loot_rate = self.config.loot_rate
-            + if killer_premium { self.config.premium_bonus } else { 0.0 };
+        let loot_rate = (self.config.loot_rate
+            + if killer_premium { self.config.premium_bonus } else { 0.0 })
+            * boost_multiplier;
 
         // Generate gold
         if let Some(ref gold) = table.gold {
@@ -320,12 +477,14 @@ impl LootGenerator {
             let idx = self.rng.gen_range(0..table.entries.len());
             if let Some(entry) = table.entries.get(idx) {
                 let count = self.rng.gen_range(entry.count_min..=entry.count_max);
+                let rolled_stats = self.roll_stats(entry);
                 result.items.push(GeneratedLoot {
                     item_id: entry.item_id,
                     count,
                     action_id: entry.action_id,
                     unique_id: entry.unique_id,
                     contents: Vec::new(),
+                    rolled_stats,
                 });
             }
         }
@@ -366,9 +525,22 @@ impl LootGenerator {
             action_id: entry.action_id,
             unique_id: entry.unique_id,
             contents,
+            rolled_stats: self.roll_stats(entry),
         })
     }
 
+    /// Roll the stat ranges of an entry
+    fn roll_stats(&mut self, entry: &LootEntry) -> HashMap<ItemStat, i32> {
+        if !self.config.roll_attributes {
+            return HashMap::new();
+        }
+        entry
+            .attribute_rolls
+            .iter()
+            .map(|roll| (roll.stat, self.rng.gen_range(roll.min..=roll.max)))
+            .collect()
+    }
+
     /// Roll a chance check (chance is 0.0-100.0)
     fn roll_chance(&mut self, chance: f32) -> bool {
         if chance >= 100.0 {
@@ -385,10 +557,33 @@ impl LootGenerator {
         self.config.loot_rate = rate;
     }
 
+    /// Coin items for a drop's gold. Players with auto-convert get the
+    /// fewest coins; otherwise gold is paid out in gold coins.
+    pub fn gold_items(&self, result: &LootResult, auto_convert: bool) -> Vec<GeneratedLoot> {
+        if auto_convert {
+            self.config.currency.normalize(result.gold as u64)
+        } else {
+            self.config.currency.unconverted(result.gold as u64)
+        }
+    }
+
     /// Get a loot table
     pub fn get_table(&self, creature_name: &str) -> Option<&LootTable> {
         self.loot_tables.get(&creature_name.to_lowercase())
     }
+
+    /// Bestiary loot list of a creature at the server loot rate
+    pub fn loot_preview(
+        &self,
+        creature_name: &str,
+        stage: u8,
+        bestiary: &BestiaryLootConfig,
+    ) -> Result<LootPreview, LootError> {
+        let table = self
+            .get_table(creature_name)
+            .ok_or_else(|| LootError::TableNotFound(creature_name.to_string()))?;
+        Ok(bestiary.preview(table, stage, self.config.loot_rate))
+    }
 }
 
 /// Result of loot generation
@@ -560,6 +755,8 @@ impl Default for DamageTracker {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::sheet::{CombatStats, SheetItem, SheetSkills};
+    use shadow_world::item::WeaponType;
 
     #[test]
     fn test_loot_entry_creation() {
@@ -605,6 +802,78 @@ mod tests {
         assert!(!result.items.is_empty());
     }
 
+    #[test]
+    fn test_rolled_stats_within_range_affect_attack() {
+        let mut generator = LootGenerator::new(LootConfig::default());
+        generator.register_table(LootTable::new("Orc Warlord").add_entry(
+            LootEntry::new(3300, 100.0)
+                .with_attribute_roll(ItemStat::Attack, 30, 36)
+                .with_attribute_roll(ItemStat::Defense, 20, 22),
+        ));
+
+        for _ in 0..50 {
+            let loot = generator.generate("Orc Warlord", false).unwrap();
+            let attack = loot.items[0].rolled_stats[&ItemStat::Attack];
+            let defense = loot.items[0].rolled_stats[&ItemStat::Defense];
+            assert!((30..=36).contains(&attack));
+            assert!((20..=22).contains(&defense));
+
+            // The roll is stored on the item and used by the computed attack
+            let item = loot.items[0].to_item();
+            assert_eq!(item.stat(ItemStat::Attack), Some(attack));
+            let sword = SheetItem { weapon_type: Some(WeaponType::Sword), ..SheetItem::from_item(&item) };
+            let stats = CombatStats::compute(100, &SheetSkills { sword: 80, ..Default::default() }, &[sword]);
+            assert_eq!(stats.attack, attack);
+        }
+
+        let mut fixed = LootGenerator::new(LootConfig { roll_attributes: false, ..Default::default() });
+        fixed.register_table(
+            LootTable::new("Orc Warlord")
+                .add_entry(LootEntry::new(3300, 100.0).with_attribute_roll(ItemStat::Attack, 30, 36)),
+        );
+        assert!(fixed.generate("Orc Warlord", false).unwrap().items[0].rolled_stats.is_empty());
+    }
+
+    #[test]
+    fn test_gold_conversion() {
+        let currency = CurrencyConfig::default();
+        let coins = |items: Vec<GeneratedLoot>| -> Vec<(u16, u16)> {
+            items.iter().map(|i| (i.item_id, i.count)).collect()
+        };
+
+        assert_eq!(coins(currency.normalize(12_345)), vec![(3043, 1), (3035, 23), (3031, 45)]);
+        assert_eq!(coins(currency.normalize(99)), vec![(3031, 99)]);
+        assert_eq!(coins(currency.normalize(1_050_000)), vec![(3043, 100), (3043, 5)]);
+        assert!(currency.normalize(0).is_empty());
+
+        // Without auto-convert gold stays in gold coin stacks
+        assert_eq!(coins(currency.unconverted(250)), vec![(3031, 100), (3031, 100), (3031, 50)]);
+
+        // Auto-convert merges looted coins and keeps other items
+        let looted = vec![
+            GeneratedLoot::item(3031, 100),
+            GeneratedLoot::item(2666, 1),
+            GeneratedLoot::item(3031, 60),
+            GeneratedLoot::item(3035, 99),
+        ];
+        assert_eq!(currency.total_value(&looted), 10_060);
+        assert_eq!(coins(currency.convert(looted)), vec![(2666, 1), (3043, 1), (3031, 60)]);
+    }
+
+    #[test]
+    fn test_loot_gold_items() {
+        let mut generator = LootGenerator::new(LootConfig::default());
+        generator.register_table(LootTable::new("Dragon").with_gold(150, 150, 100.0));
+
+        let result = generator.generate("Dragon", false).unwrap();
+        assert_eq!(result.gold, 150);
+
+        let converted = generator.gold_items(&result, true);
+        assert_eq!(converted.iter().map(|i| (i.item_id, i.count)).collect::<Vec<_>>(), vec![(3035, 1), (3031, 50)]);
+        let raw = generator.gold_items(&result, false);
+        assert_eq!(raw.iter().map(|i| (i.item_id, i.count)).collect::<Vec<_>>(), vec![(3031, 100), (3031, 50)]);
+    }
+
     #[test]
     fn test_damage_tracker() {
         let mut tracker = DamageTracker::new();
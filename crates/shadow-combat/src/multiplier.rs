@@ -0,0 +1,306 @@
+//! Experience and loot multiplier resolution
+//!
+//! A kill can be affected by several bonuses at once: the realm rate, a
+//! rate event such as an experience weekend, the character's XP or loot
+//! boost, an active prey bonus, the boosted creature of the day and
+//! stamina. The resolver combines them in one place so every kill uses the
+//! same rules:
+//!
+//! 1. Bonuses configured as `Additive` are summed into a single bonus,
+//!    capped at `max_additive_bonus_percent`.
+//! 2. Bonuses configured as `Multiplicative` each multiply the result.
+//! 3. The realm rate multiplies the result.
+//! 4. Stamina multiplies last, so a stamina penalty also reduces event and
+//!    boost bonuses.
+
+use chrono::{DateTime, Datelike, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+
+use crate::formula::{level_difference_multiplier, stamina_multiplier};
+use crate::prey::PreyBonusType;
+
+/// How a bonus combines with the other bonuses of a kill
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MultiplierStacking {
+    /// Summed with the other additive bonuses
+    Additive,
+    /// Multiplies the combined result
+    Multiplicative,
+}
+
+/// Stacking rule per bonus source
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StackingRules {
+    pub event: MultiplierStacking,
+    pub boost: MultiplierStacking,
+    pub prey: MultiplierStacking,
+    pub boosted_creature: MultiplierStacking,
+    /// Cap on the summed additive bonus percentage
+    pub max_additive_bonus_percent: u32,
+}
+
+impl Default for StackingRules {
+    fn default() -> Self {
+        Self {
+            event: MultiplierStacking::Additive,
+            boost: MultiplierStacking::Additive,
+            prey: MultiplierStacking::Additive,
+            boosted_creature: MultiplierStacking::Multiplicative,
+            max_additive_bonus_percent: 300,
+        }
+    }
+}
+
+/// A recurring rate event, e.g. an experience weekend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RateEvent {
+    pub enabled: bool,
+    /// Days (UTC) the event runs on
+    pub days: Vec<Weekday>,
+    /// Experience bonus percentage (100 = +100%)
+    pub experience_bonus_percent: u32,
+    /// Loot bonus percentage
+    pub loot_bonus_percent: u32,
+}
+
+impl RateEvent {
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.enabled && self.days.contains(&now.weekday())
+    }
+}
+
+impl Default for RateEvent {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            days: vec![Weekday::Sat, Weekday::Sun],
+            experience_bonus_percent: 50,
+            loot_bonus_percent: 0,
+        }
+    }
+}
+
+/// Multiplier configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MultiplierConfig {
+    pub stacking: StackingRules,
+    pub event: RateEvent,
+    /// Experience bonus percentage of the boosted creature
+    pub boosted_experience_percent: u32,
+    /// Loot bonus percentage of the boosted creature
+    pub boosted_loot_percent: u32,
+}
+
+impl Default for MultiplierConfig {
+    fn default() -> Self {
+        Self {
+            stacking: StackingRules::default(),
+            event: RateEvent::default(),
+            boosted_experience_percent: 100,
+            boosted_loot_percent: 100,
+        }
+    }
+}
+
+/// Everything about a kill that affects its multipliers
+#[derive(Debug, Clone)]
+pub struct MultiplierContext {
+    /// Realm experience rate
+    pub experience_rate: f32,
+    /// Realm loot rate
+    pub loot_rate: f32,
+    /// Remaining stamina; `None` when stamina does not apply
+    pub stamina_minutes: Option<u16>,
+    /// Whether the rate event is running (see `MultiplierResolver::event_active`)
+    pub event_active: bool,
+    /// Experience boost percentage (`BoostManager::bonus_percent`)
+    pub experience_boost_percent: u32,
+    /// Loot boost percentage
+    pub loot_boost_percent: u32,
+    /// Prey bonus on the killed creature (`PlayerPrey::get_kill_bonus`)
+    pub prey: Option<(PreyBonusType, f32)>,
+    /// The killed creature is today's boosted creature
+    pub boosted_creature: bool,
+    /// Loot scale of the killed creature's difficulty (`Creature::difficulty_scale`)
+    pub difficulty_loot: f32,
+}
+
+impl Default for MultiplierContext {
+    fn default() -> Self {
+        Self {
+            experience_rate: 1.0,
+            loot_rate: 1.0,
+            stamina_minutes: None,
+            event_active: false,
+            experience_boost_percent: 0,
+            loot_boost_percent: 0,
+            prey: None,
+            boosted_creature: false,
+            difficulty_loot: 1.0,
+        }
+    }
+}
+
+/// Final multipliers of a kill
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ResolvedMultipliers {
+    pub experience: f64,
+    pub loot: f64,
+}
+
+/// Combines every active bonus of a kill
+#[derive(Debug, Clone, Default)]
+pub struct MultiplierResolver {
+    config: MultiplierConfig,
+}
+
+impl MultiplierResolver {
+    pub fn new(config: MultiplierConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn config(&self) -> &MultiplierConfig {
+        &self.config
+    }
+
+    /// Whether the configured rate event is running at `now`
+    pub fn event_active(&self, now: DateTime<Utc>) -> bool {
+        self.config.event.is_active(now)
+    }
+
+    /// Resolve the experience and loot multipliers of a kill
+    pub fn resolve(&self, context: &MultiplierContext) -> ResolvedMultipliers {
+        let rules = &self.config.stacking;
+        let prey = |wanted: PreyBonusType| match context.prey {
+            Some((bonus_type, percent)) if bonus_type == wanted => percent as f64,
+            _ => 0.0,
+        };
+        let event_on = context.event_active && self.config.event.enabled;
+        let boosted = |percent: u32| if context.boosted_creature { percent as f64 } else { 0.0 };
+
+        let experience = self.combine(
+            &[
+                (rules.event, if event_on { self.config.event.experience_bonus_percent as f64 } else { 0.0 }),
+                (rules.boost, context.experience_boost_percent as f64),
+                (rules.prey, prey(PreyBonusType::ExperienceBoost)),
+                (rules.boosted_creature, boosted(self.config.boosted_experience_percent)),
+            ],
+            context.experience_rate,
+        ) * context.stamina_minutes.map(stamina_multiplier).unwrap_or(1.0);
+
+        // Stamina only affects experience
+        let loot = self.combine(
+            &[
+                (rules.event, if event_on { self.config.event.loot_bonus_percent as f64 } else { 0.0 }),
+                (rules.boost, context.loot_boost_percent as f64),
+                (rules.prey, prey(PreyBonusType::LootBoost)),
+                (rules.boosted_creature, boosted(self.config.boosted_loot_percent)),
+            ],
+            context.loot_rate,
+        ) * context.difficulty_loot as f64;
+
+        ResolvedMultipliers { experience, loot }
+    }
+
+    /// Experience a kill grants after the level difference penalty and all multipliers
+    pub fn kill_experience(
+        &self,
+        monster_experience: u64,
+        player_level: u16,
+        monster_level: u16,
+        context: &MultiplierContext,
+    ) -> u64 {
+        let multiplier =
+            self.resolve(context).experience * level_difference_multiplier(player_level, monster_level);
+        (monster_experience as f64 * multiplier) as u64
+    }
+
+    fn combine(&self, bonuses: &[(MultiplierStacking, f64)], rate: f32) -> f64 {
+        let additive: f64 = bonuses
+            .iter()
+            .filter(|(stacking, _)| *stacking == MultiplierStacking::Additive)
+            .map(|(_, percent)| percent)
+            .sum();
+        let additive = additive.min(self.config.stacking.max_additive_bonus_percent as f64);
+
+        bonuses
+            .iter()
+            .filter(|(stacking, _)| *stacking == MultiplierStacking::Multiplicative)
+            .fold(1.0 + additive / 100.0, |total, (_, percent)| total * (1.0 + percent / 100.0))
+            * rate as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn weekend_resolver() -> MultiplierResolver {
+        MultiplierResolver::new(MultiplierConfig {
+            event: RateEvent { enabled: true, experience_bonus_percent: 50, loot_bonus_percent: 25, ..Default::default() },
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_weekend_prey_and_boosted_creature_stack() {
+        let resolver = weekend_resolver();
+        let saturday = Utc.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap();
+        let monday = Utc.with_ymd_and_hms(2024, 6, 3, 12, 0, 0).unwrap();
+        assert!(resolver.event_active(saturday));
+        assert!(!resolver.event_active(monday));
+
+        let context = MultiplierContext {
+            event_active: resolver.event_active(saturday),
+            prey: Some((PreyBonusType::ExperienceBoost, 40.0)),
+            boosted_creature: true,
+            ..Default::default()
+        };
+        let resolved = resolver.resolve(&context);
+
+        // (1 + 0.50 weekend + 0.40 prey) * 2.0 boosted creature
+        assert!((resolved.experience - 3.8).abs() < 1e-9);
+        // (1 + 0.25 weekend) * 2.0 boosted creature; an experience prey does not touch loot
+        assert!((resolved.loot - 2.5).abs() < 1e-9);
+        assert_eq!(resolver.kill_experience(1000, 100, 100, &context), 3800);
+
+        // Stamina applies last and scales the whole result
+        let tired = MultiplierContext { stamina_minutes: Some(10 * 60), ..context.clone() };
+        assert_eq!(resolver.kill_experience(1000, 100, 100, &tired), 1900);
+        assert!((resolver.resolve(&tired).loot - 2.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_stacking_rules_and_cap() {
+        let mut config = weekend_resolver().config().clone();
+        config.stacking.event = MultiplierStacking::Multiplicative;
+        config.stacking.max_additive_bonus_percent = 60;
+        let resolver = MultiplierResolver::new(config);
+
+        let context = MultiplierContext {
+            experience_rate: 2.0,
+            event_active: true,
+            experience_boost_percent: 50,
+            prey: Some((PreyBonusType::ExperienceBoost, 40.0)),
+            ..Default::default()
+        };
+        // Boost and prey sum to 90%, capped at 60%; the event multiplies, then the realm rate
+        let resolved = resolver.resolve(&context);
+        assert!((resolved.experience - 1.6 * 1.5 * 2.0).abs() < 1e-9);
+
+        // Outside the event only the plain stamina multiplier remains
+        assert_eq!(
+            MultiplierResolver::default().kill_experience(
+                1000,
+                100,
+                100,
+                &MultiplierContext { stamina_minutes: Some(42 * 60 + 1), ..Default::default() }
+            ),
+            1500
+        );
+    }
+}
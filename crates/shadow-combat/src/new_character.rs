@@ -0,0 +1,123 @@
+//! New character protection
+//!
+//! Freshly created characters cannot take part in PvP and draw monsters
+//! from a shorter distance, so they are not farmed by high levels or
+//! overwhelmed at their first spawns. Protection lasts until the character
+//! reaches `max_level` or is `max_age_hours` old, whichever comes first,
+//! and lifts on its own once either threshold passes.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use shadow_world::creature::Creature;
+use std::collections::HashMap;
+
+/// New character protection thresholds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NewCharacterProtectionConfig {
+    pub enabled: bool,
+    /// Protection lifts at this level
+    pub max_level: u16,
+    /// Protection lifts this many hours after creation (0 = level only)
+    pub max_age_hours: u32,
+    /// Monster aggro range against protected characters, in percent
+    pub aggro_range_percent: u8,
+}
+
+impl Default for NewCharacterProtectionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_level: 20,
+            max_age_hours: 72,
+            aggro_range_percent: 50,
+        }
+    }
+}
+
+/// Tracks the creation time of online characters by creature id
+#[derive(Debug, Default)]
+pub struct NewCharacterProtection {
+    config: NewCharacterProtectionConfig,
+    created_at: HashMap<u32, DateTime<Utc>>,
+}
+
+impl NewCharacterProtection {
+    pub fn new(config: NewCharacterProtectionConfig) -> Self {
+        Self {
+            config,
+            created_at: HashMap::new(),
+        }
+    }
+
+    pub fn config(&self) -> &NewCharacterProtectionConfig {
+        &self.config
+    }
+
+    /// Register a character on login with its creation time
+    pub fn register(&mut self, creature_id: u32, created_at: DateTime<Utc>) {
+        self.created_at.insert(creature_id, created_at);
+    }
+
+    /// Forget a character on logout
+    pub fn remove(&mut self, creature_id: u32) {
+        self.created_at.remove(&creature_id);
+    }
+
+    /// Whether `creature` is protected at `now`
+    pub fn is_protected(&self, creature: &Creature, now: DateTime<Utc>) -> bool {
+        if !self.config.enabled || !creature.is_player() || creature.stats.level >= self.config.max_level {
+            return false;
+        }
+        match self.created_at.get(&creature.id) {
+            Some(_) if self.config.max_age_hours == 0 => true,
+            Some(&created_at) => now < created_at + Duration::hours(self.config.max_age_hours as i64),
+            None => false,
+        }
+    }
+
+    /// Whether PvP between `attacker` and `target` is blocked because
+    /// either side is protected
+    pub fn blocks_pvp(&self, attacker: &Creature, target: &Creature, now: DateTime<Utc>) -> bool {
+        attacker.is_player()
+            && target.is_player()
+            && (self.is_protected(attacker, now) || self.is_protected(target, now))
+    }
+
+    /// Distance from which a monster with `base_range` notices `target`
+    pub fn aggro_range(&self, target: &Creature, base_range: u8, now: DateTime<Utc>) -> u8 {
+        if self.is_protected(target, now) {
+            (base_range as u32 * self.config.aggro_range_percent as u32 / 100).max(1) as u8
+        } else {
+            base_range
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shadow_world::creature::CreatureType;
+    use shadow_world::position::Position;
+
+    #[test]
+    fn test_protection_lifts_at_thresholds() {
+        let mut protection = NewCharacterProtection::new(NewCharacterProtectionConfig::default());
+        let created = Utc::now();
+        let mut rookie = Creature::new("Rookie".to_string(), CreatureType::Player, Position::new(100, 100, 7));
+        rookie.stats.level = 8;
+        protection.register(rookie.id, created);
+
+        assert!(protection.is_protected(&rookie, created));
+        assert_eq!(protection.aggro_range(&rookie, 8, created), 4);
+
+        // Age threshold
+        assert!(protection.is_protected(&rookie, created + Duration::hours(71)));
+        assert!(!protection.is_protected(&rookie, created + Duration::hours(72)));
+        assert_eq!(protection.aggro_range(&rookie, 8, created + Duration::hours(72)), 8);
+
+        // Level threshold
+        rookie.stats.level = 20;
+        assert!(!protection.is_protected(&rookie, created));
+    }
+}
@@ -0,0 +1,230 @@
+//! Reward chest system - instanced boss loot
+//!
+//! Instead of a single first-come corpse, each player who contributed
+//! enough damage to a boss kill gets their own reward chest entry rolled
+//! from the boss loot table. Entries can be claimed once from the reward
+//! chest and expire after a configurable window.
+
+use chrono::{DateTime, Duration, Utc};
+use rand::Rng;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::loot::{BossLootConfig, DamageTracker, LootError, LootGenerator, LootResult};
+
+/// Reward chest configuration
+#[derive(Debug, Clone)]
+pub struct RewardChestConfig {
+    /// Participation and top-damage rules
+    pub boss_loot: BossLootConfig,
+    /// How long unclaimed rewards are kept
+    pub expiry: Duration,
+}
+
+impl Default for RewardChestConfig {
+    fn default() -> Self {
+        Self {
+            boss_loot: BossLootConfig::default(),
+            expiry: Duration::days(7),
+        }
+    }
+}
+
+/// A single player's reward from one boss kill
+#[derive(Debug, Clone)]
+pub struct RewardChest {
+    pub id: Uuid,
+    pub character_id: Uuid,
+    pub boss_name: String,
+    /// Damage the player contributed to the kill
+    pub damage_dealt: u32,
+    pub loot: LootResult,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub claimed: bool,
+}
+
+impl RewardChest {
+    pub fn is_expired(&self) -> bool {
+        Utc::now() >= self.expires_at
+    }
+
+    pub fn is_claimable(&self) -> bool {
+        !self.claimed && !self.is_expired()
+    }
+}
+
+/// Manages per-player reward chests created from boss kills
+pub struct RewardChestManager {
+    config: RewardChestConfig,
+    chests: HashMap<Uuid, Vec<RewardChest>>,
+}
+
+impl RewardChestManager {
+    pub fn new(config: RewardChestConfig) -> Self {
+        Self {
+            config,
+            chests: HashMap::new(),
+        }
+    }
+
+    /// Create a reward chest entry for every qualifying participant of a
+    /// boss kill. Each participant gets an independent loot roll; the top
+    /// damage dealer has `top_damage_bonus`% chance of a second roll.
+    pub fn on_boss_death(
+        &mut self,
+        boss_name: &str,
+        damage: &DamageTracker,
+        generator: &mut LootGenerator,
+    ) -> Result<Vec<Uuid>, LootError> {
+        let participants = damage.get_qualifiers(self.config.boss_loot.min_damage_threshold);
+        let top_dealer = damage.top_dealer();
+        let now = Utc::now();
+        let mut created = Vec::with_capacity(participants.len());
+
+        for character_id in participants {
+            let mut loot = generator.generate(boss_name, false)?;
+
+            if Some(character_id) == top_dealer
+                && rand::thread_rng().gen_range(0.0..100.0) < self.config.boss_loot.top_damage_bonus
+            {
+                let bonus = generator.generate(boss_name, false)?;
+                loot.items.extend(bonus.items);
+                loot.gold += bonus.gold;
+                loot.rare_items.extend(bonus.rare_items);
+            }
+
+            let chest = RewardChest {
+                id: Uuid::new_v4(),
+                character_id,
+                boss_name: boss_name.to_string(),
+                damage_dealt: damage.get_damage(character_id),
+                loot,
+                created_at: now,
+                expires_at: now + self.config.expiry,
+                claimed: false,
+            };
+            created.push(chest.id);
+            self.chests.entry(character_id).or_default().push(chest);
+        }
+
+        Ok(created)
+    }
+
+    /// Pending (unclaimed, unexpired) rewards for a character
+    pub fn pending(&self, character_id: Uuid) -> Vec<&RewardChest> {
+        self.chests
+            .get(&character_id)
+            .map(|chests| chests.iter().filter(|c| c.is_claimable()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Open the reward chest, delivering every pending reward exactly once
+    pub fn open_chest(&mut self, character_id: Uuid) -> Result<Vec<LootResult>, RewardChestError> {
+        let chests = self
+            .chests
+            .get_mut(&character_id)
+            .ok_or(RewardChestError::NoRewards)?;
+
+        let mut delivered = Vec::new();
+        for chest in chests.iter_mut().filter(|c| c.is_claimable()) {
+            chest.claimed = true;
+            delivered.push(chest.loot.clone());
+        }
+
+        if delivered.is_empty() {
+            return Err(RewardChestError::NoRewards);
+        }
+        Ok(delivered)
+    }
+
+    /// Drop claimed and expired entries
+    pub fn cleanup(&mut self) -> usize {
+        let mut removed = 0;
+        for chests in self.chests.values_mut() {
+            let before = chests.len();
+            chests.retain(|c| c.is_claimable());
+            removed += before - chests.len();
+        }
+        self.chests.retain(|_, chests| !chests.is_empty());
+        removed
+    }
+}
+
+impl Default for RewardChestManager {
+    fn default() -> Self {
+        Self::new(RewardChestConfig::default())
+    }
+}
+
+/// Reward chest errors
+#[derive(Debug, Clone, PartialEq)]
+pub enum RewardChestError {
+    NoRewards,
+}
+
+impl std::fmt::Display for RewardChestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RewardChestError::NoRewards => write!(f, "The reward chest is empty"),
+        }
+    }
+}
+
+impl std::error::Error for RewardChestError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loot::{LootConfig, LootEntry, LootTable};
+
+    fn boss_generator() -> LootGenerator {
+        let mut generator = LootGenerator::new(LootConfig::default());
+        generator.register_table(
+            LootTable::new("Ferumbras")
+                .with_gold(100, 100, 100.0)
+                .add_entry(LootEntry::new(2520, 100.0)),
+        );
+        generator
+    }
+
+    #[test]
+    fn test_per_player_chests() {
+        let mut manager = RewardChestManager::default();
+        let mut generator = boss_generator();
+        let mut damage = DamageTracker::new();
+        let (knight, druid, leecher) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+        damage.record_damage(knight, 5000);
+        damage.record_damage(druid, 800);
+        damage.record_damage(leecher, 10); // Below participation threshold
+
+        let created = manager.on_boss_death("Ferumbras", &damage, &mut generator).unwrap();
+        assert_eq!(created.len(), 2);
+
+        assert_eq!(manager.pending(knight).len(), 1);
+        assert_eq!(manager.pending(druid).len(), 1);
+        assert!(manager.pending(leecher).is_empty());
+        assert_eq!(manager.pending(druid)[0].damage_dealt, 800);
+
+        let loot = manager.open_chest(druid).unwrap();
+        assert_eq!(loot.len(), 1);
+        assert_eq!(loot[0].items[0].item_id, 2520);
+        assert_eq!(loot[0].gold, 100);
+    }
+
+    #[test]
+    fn test_double_open_prevented() {
+        let mut manager = RewardChestManager::default();
+        let mut generator = boss_generator();
+        let mut damage = DamageTracker::new();
+        let player = Uuid::new_v4();
+        damage.record_damage(player, 1000);
+
+        manager.on_boss_death("Ferumbras", &damage, &mut generator).unwrap();
+
+        assert!(manager.open_chest(player).is_ok());
+        assert!(matches!(manager.open_chest(player), Err(RewardChestError::NoRewards)));
+        assert_eq!(manager.cleanup(), 1);
+    }
+}
@@ -0,0 +1,66 @@
+//! Ruleset flags - classic ("retro") rule toggles
+//!
+//! A single switchboard consulted by combat, death and regeneration so a
+//! retro realm can run the same code as a modern one with classic rules.
+
+use serde::{Deserialize, Serialize};
+use shadow_world::position::Direction;
+
+/// Rule toggles for a realm. All flags off means modern rules.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RulesetFlags {
+    /// Beams and waves always fire in the caster's facing direction
+    /// instead of being aimed at the target
+    pub no_directional_shooting: bool,
+    /// Death costs a percentage of total experience, not only of the
+    /// current level's progress
+    pub classic_exp_loss: bool,
+    /// Blessings cannot be bought and have no effect on death
+    pub no_blessings: bool,
+    /// Food is never eaten automatically from the backpack
+    pub manual_food: bool,
+    /// Only white and red skulls; no black skull
+    pub classic_skulls: bool,
+}
+
+impl RulesetFlags {
+    /// Modern rules
+    pub fn modern() -> Self {
+        Self::default()
+    }
+
+    /// Classic rules used by retro realms
+    pub fn retro() -> Self {
+        Self {
+            no_directional_shooting: true,
+            classic_exp_loss: true,
+            no_blessings: true,
+            manual_food: true,
+            classic_skulls: true,
+        }
+    }
+
+    /// Direction a directional attack (beam, wave) is fired in
+    pub fn attack_direction(&self, facing: Direction, toward_target: Option<Direction>) -> Direction {
+        if self.no_directional_shooting {
+            facing
+        } else {
+            toward_target.unwrap_or(facing)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attack_direction() {
+        let modern = RulesetFlags::modern();
+        let retro = RulesetFlags::retro();
+
+        assert_eq!(modern.attack_direction(Direction::North, Some(Direction::East)), Direction::East);
+        assert_eq!(modern.attack_direction(Direction::North, None), Direction::North);
+        assert_eq!(retro.attack_direction(Direction::North, Some(Direction::East)), Direction::North);
+    }
+}
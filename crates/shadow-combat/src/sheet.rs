@@ -0,0 +1,253 @@
+//! Character sheet - combat stats derived from equipment
+//!
+//! Computes the attack, defense, armor, damage range and resistances a
+//! character has with a given set of equipment. The damage range comes
+//! from the same formulas used in combat, so external tools (gear
+//! planners, the web API) show exactly what the server would roll.
+
+use serde::{Deserialize, Serialize};
+use shadow_world::creature::{Creature, CreatureType};
+use shadow_world::item::{Item, ItemStat, SkillType, SlotType, WeaponType};
+use shadow_world::position::Position;
+use std::collections::HashMap;
+
+use crate::damage::DamageType;
+use crate::formula::{calculate_defense, CombatFormula, DistanceFormula, MeleeFormula};
+
+/// Attack used when fighting without a weapon
+pub const FIST_ATTACK: i32 = 7;
+
+/// An equipped item as seen by the sheet
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SheetItem {
+    pub slot: Option<SlotType>,
+    pub weapon_type: Option<WeaponType>,
+    pub attack: i32,
+    pub defense: i32,
+    pub extra_defense: i32,
+    pub armor: i32,
+    /// Resistances in percent, from the item and its imbuements
+    pub resistances: HashMap<DamageType, i32>,
+}
+
+impl SheetItem {
+    /// Sheet view of an item instance, using its rolled stats where it
+    /// has them. Imbuement resistances are added by the caller.
+    pub fn from_item(item: &Item) -> Self {
+        let item_type = item.get_type();
+        Self {
+            slot: item_type.and_then(|t| t.slot_type),
+            weapon_type: item_type.and_then(|t| t.weapon_type),
+            attack: item.stat(ItemStat::Attack).unwrap_or(0),
+            defense: item.stat(ItemStat::Defense).unwrap_or(0),
+            extra_defense: item.stat(ItemStat::ExtraDefense).unwrap_or(0),
+            armor: item.stat(ItemStat::Armor).unwrap_or(0),
+            resistances: item_type.map(|t| t.absorb.clone()).unwrap_or_default(),
+        }
+    }
+}
+
+/// Combat skills used by the sheet
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct SheetSkills {
+    pub fist: u8,
+    pub club: u8,
+    pub sword: u8,
+    pub axe: u8,
+    pub distance: u8,
+    pub shielding: u8,
+}
+
+impl SheetSkills {
+    pub fn get(&self, skill: SkillType) -> u8 {
+        match skill {
+            SkillType::Fist => self.fist,
+            SkillType::Club => self.club,
+            SkillType::Sword => self.sword,
+            SkillType::Axe => self.axe,
+            SkillType::Distance => self.distance,
+            SkillType::Shielding => self.shielding,
+            _ => 0,
+        }
+    }
+}
+
+/// Computed combat stats
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CombatStats {
+    /// Skill the equipped weapon trains
+    pub weapon_skill: SkillType,
+    /// Weapon attack, including ammunition for distance weapons
+    pub attack: i32,
+    /// Shield defense, or weapon defense when no shield is worn
+    pub defense: i32,
+    /// Defense after the shielding skill is applied
+    pub effective_defense: i32,
+    pub armor: i32,
+    /// Damage range in balanced mode; zero for wands and rods
+    pub min_damage: i32,
+    pub max_damage: i32,
+    /// Combined resistances of all equipment in percent
+    pub resistances: HashMap<DamageType, i32>,
+}
+
+impl CombatStats {
+    /// Compute the stats for a character of `level` wearing `equipment`
+    pub fn compute(level: u16, skills: &SheetSkills, equipment: &[SheetItem]) -> Self {
+        let weapon = equipment.iter().find(|item| {
+            matches!(
+                item.weapon_type,
+                Some(WeaponType::Sword | WeaponType::Club | WeaponType::Axe | WeaponType::Distance | WeaponType::Wand)
+            )
+        });
+        let shield = equipment.iter().find(|item| item.weapon_type == Some(WeaponType::Shield));
+        let ammo = equipment
+            .iter()
+            .find(|item| item.weapon_type == Some(WeaponType::Ammunition) || item.slot == Some(SlotType::Ammo));
+
+        let weapon_skill = match weapon.and_then(|w| w.weapon_type) {
+            Some(WeaponType::Sword) => SkillType::Sword,
+            Some(WeaponType::Club) => SkillType::Club,
+            Some(WeaponType::Axe) => SkillType::Axe,
+            Some(WeaponType::Distance) => SkillType::Distance,
+            Some(WeaponType::Wand) => SkillType::MagicLevel,
+            _ => SkillType::Fist,
+        };
+
+        let formula: Option<Box<dyn CombatFormula>> = match weapon_skill {
+            SkillType::Distance => {
+                let weapon_attack = weapon.map(|w| w.attack).unwrap_or(0);
+                let ammo_attack = ammo.map(|a| a.attack).unwrap_or(0);
+                Some(Box::new(DistanceFormula::new(weapon_attack, ammo_attack, 0)))
+            }
+            SkillType::MagicLevel => None,
+            _ => Some(Box::new(MeleeFormula::new(weapon.map(|w| w.attack).unwrap_or(FIST_ATTACK)))),
+        };
+
+        let attack = match weapon_skill {
+            SkillType::Distance => weapon.map(|w| w.attack).unwrap_or(0) + ammo.map(|a| a.attack).unwrap_or(0),
+            SkillType::MagicLevel => 0,
+            _ => weapon.map(|w| w.attack).unwrap_or(FIST_ATTACK),
+        };
+
+        let extra_defense = weapon.map(|w| w.extra_defense).unwrap_or(0);
+        let defense = match shield {
+            Some(shield) => shield.defense,
+            None => weapon.map(|w| w.defense).unwrap_or(0),
+        };
+
+        let skill = skills.get(weapon_skill);
+        let attacker = Creature::new(String::new(), CreatureType::Player, Position::new(0, 0, 0));
+        let (min_damage, max_damage) = formula
+            .map(|f| (f.get_min_damage(&attacker, level, skill), f.get_max_damage(&attacker, level, skill)))
+            .unwrap_or((0, 0));
+
+        Self {
+            weapon_skill,
+            attack,
+            defense,
+            effective_defense: calculate_defense(defense, extra_defense, skills.shielding),
+            armor: equipment.iter().map(|item| item.armor).sum(),
+            min_damage,
+            max_damage,
+            resistances: combine_resistances(equipment),
+        }
+    }
+}
+
+/// Resistances stack multiplicatively: two 10% pieces give 19%
+fn combine_resistances(equipment: &[SheetItem]) -> HashMap<DamageType, i32> {
+    let mut taken: HashMap<DamageType, f64> = HashMap::new();
+    for item in equipment {
+        for (&damage_type, &percent) in &item.resistances {
+            *taken.entry(damage_type).or_insert(1.0) *= 1.0 - percent.clamp(-100, 100) as f64 / 100.0;
+        }
+    }
+    taken
+        .into_iter()
+        .map(|(damage_type, factor)| (damage_type, ((1.0 - factor) * 100.0).round() as i32))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attacker() -> Creature {
+        Creature::new("Knight".to_string(), CreatureType::Player, Position::new(100, 100, 7))
+    }
+
+    fn skills() -> SheetSkills {
+        SheetSkills { fist: 10, club: 12, sword: 90, axe: 15, distance: 60, shielding: 85 }
+    }
+
+    #[test]
+    fn test_melee_sheet_matches_formula() {
+        let sword = SheetItem {
+            slot: Some(SlotType::Right),
+            weapon_type: Some(WeaponType::Sword),
+            attack: 47,
+            defense: 32,
+            extra_defense: 2,
+            ..Default::default()
+        };
+        let shield = SheetItem {
+            slot: Some(SlotType::Left),
+            weapon_type: Some(WeaponType::Shield),
+            defense: 37,
+            resistances: HashMap::from([(DamageType::Fire, 10)]),
+            ..Default::default()
+        };
+        let armor = SheetItem {
+            slot: Some(SlotType::Armor),
+            armor: 15,
+            resistances: HashMap::from([(DamageType::Fire, 10), (DamageType::Physical, 5)]),
+            ..Default::default()
+        };
+
+        let stats = CombatStats::compute(200, &skills(), &[sword, shield, armor]);
+        let formula = MeleeFormula::new(47);
+        assert_eq!(stats.weapon_skill, SkillType::Sword);
+        assert_eq!(stats.attack, 47);
+        assert_eq!(stats.min_damage, formula.get_min_damage(&attacker(), 200, 90));
+        assert_eq!(stats.max_damage, formula.get_max_damage(&attacker(), 200, 90));
+
+        assert_eq!(stats.defense, 37);
+        assert_eq!(stats.effective_defense, calculate_defense(37, 2, 85));
+        assert_eq!(stats.armor, 15);
+        assert_eq!(stats.resistances[&DamageType::Fire], 19);
+        assert_eq!(stats.resistances[&DamageType::Physical], 5);
+
+        // Unarmed characters fight with their fists
+        let unarmed = CombatStats::compute(200, &skills(), &[]);
+        assert_eq!(unarmed.weapon_skill, SkillType::Fist);
+        assert_eq!(unarmed.max_damage, MeleeFormula::new(FIST_ATTACK).get_max_damage(&attacker(), 200, 10));
+    }
+
+    #[test]
+    fn test_distance_sheet_includes_ammunition() {
+        let bow = SheetItem {
+            slot: Some(SlotType::TwoHanded),
+            weapon_type: Some(WeaponType::Distance),
+            attack: 5,
+            ..Default::default()
+        };
+        let arrows = SheetItem {
+            slot: Some(SlotType::Ammo),
+            weapon_type: Some(WeaponType::Ammunition),
+            attack: 25,
+            ..Default::default()
+        };
+
+        let stats = CombatStats::compute(150, &skills(), &[bow, arrows]);
+        let formula = DistanceFormula::new(5, 25, 0);
+        assert_eq!(stats.weapon_skill, SkillType::Distance);
+        assert_eq!(stats.attack, 30);
+        assert_eq!(stats.max_damage, formula.get_max_damage(&attacker(), 150, 60));
+        assert_eq!(stats.min_damage, formula.get_min_damage(&attacker(), 150, 60));
+
+        let wand = SheetItem { weapon_type: Some(WeaponType::Wand), ..Default::default() };
+        let stats = CombatStats::compute(150, &skills(), &[wand]);
+        assert_eq!((stats.min_damage, stats.max_damage), (0, 0));
+    }
+}
@@ -0,0 +1,293 @@
+//! Skill advancement - skill tries and progress toward the next level
+//!
+//! Every hit, block or spell cast earns tries in a skill. Once the tries
+//! reach what the next level needs (see `formula::calculate_skill_tries`),
+//! the skill advances and the count starts over. Magic level uses mana
+//! spent instead of tries.
+//!
+//! How many tries a level needs is scaled per vocation and per skill, the
+//! way retail makes a knight's sword train faster than a sorcerer's. The
+//! realm's skill and magic rates scale the tries gained on top of that.
+
+use serde::{Deserialize, Serialize};
+use shadow_world::creature::Creature;
+use shadow_world::item::SkillType;
+use std::collections::HashMap;
+
+use crate::formula::{calculate_mana_spent_for_level, calculate_skill_tries};
+
+/// Level every weapon skill starts at
+pub const DEFAULT_SKILL_LEVEL: u8 = 10;
+
+/// Skills tracked for progress, in client order
+pub const TRACKED_SKILLS: [SkillType; 8] = [
+    SkillType::MagicLevel,
+    SkillType::Fist,
+    SkillType::Club,
+    SkillType::Sword,
+    SkillType::Axe,
+    SkillType::Distance,
+    SkillType::Shielding,
+    SkillType::Fishing,
+];
+
+/// Tries (or mana spent) a skill at `level` needs to advance
+pub fn tries_for_next_level(skill: SkillType, level: u8, vocation_factor: f32) -> u64 {
+    let needed = match skill {
+        SkillType::MagicLevel => calculate_mana_spent_for_level(level.saturating_add(1), vocation_factor),
+        _ => calculate_skill_tries(level, vocation_factor),
+    };
+    needed.max(1)
+}
+
+/// Multipliers on the tries each level needs, per skill, for one vocation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VocationSkillFactors {
+    /// Factor of skills not listed
+    pub default: f32,
+    pub skills: HashMap<SkillType, f32>,
+}
+
+impl Default for VocationSkillFactors {
+    fn default() -> Self {
+        Self {
+            default: 1.0,
+            skills: HashMap::new(),
+        }
+    }
+}
+
+impl VocationSkillFactors {
+    /// Factors with `melee` for fist, club, sword and axe
+    pub fn new(magic: f32, melee: f32, distance: f32, shielding: f32) -> Self {
+        let mut skills = HashMap::from([
+            (SkillType::MagicLevel, magic),
+            (SkillType::Distance, distance),
+            (SkillType::Shielding, shielding),
+            (SkillType::Fishing, 1.1),
+        ]);
+        for skill in [SkillType::Fist, SkillType::Club, SkillType::Sword, SkillType::Axe] {
+            skills.insert(skill, melee);
+        }
+        Self { default: 1.0, skills }
+    }
+
+    pub fn with_skill(mut self, skill: SkillType, factor: f32) -> Self {
+        self.skills.insert(skill, factor);
+        self
+    }
+
+    pub fn factor(&self, skill: SkillType) -> f32 {
+        self.skills.get(&skill).copied().unwrap_or(self.default)
+    }
+}
+
+/// Per-vocation skill advancement, keyed by vocation id as in
+/// `spell::vocation`. Promoted vocations use their base vocation's factors
+/// unless listed themselves; vocations not listed advance at factor 1.0.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SkillAdvancementConfig {
+    pub vocations: HashMap<u8, VocationSkillFactors>,
+}
+
+impl Default for SkillAdvancementConfig {
+    fn default() -> Self {
+        Self {
+            vocations: HashMap::from([
+                (0, VocationSkillFactors::new(1.1, 2.0, 2.0, 1.5).with_skill(SkillType::Fist, 1.5)),
+                (1, VocationSkillFactors::new(1.1, 1.8, 1.8, 1.5).with_skill(SkillType::Fist, 1.5)),
+                (2, VocationSkillFactors::new(1.4, 1.2, 1.1, 1.1)),
+                (3, VocationSkillFactors::new(3.0, 1.1, 1.4, 1.1)),
+            ]),
+        }
+    }
+}
+
+impl SkillAdvancementConfig {
+    /// Factors of a vocation, falling back to the base vocation when promoted
+    pub fn factors(&self, vocation_id: u8) -> Option<&VocationSkillFactors> {
+        match self.vocations.get(&vocation_id) {
+            Some(factors) => Some(factors),
+            None if (4..8).contains(&vocation_id) => self.vocations.get(&(vocation_id - 4)),
+            None => None,
+        }
+    }
+
+    /// A skill tracker using a vocation's factors
+    pub fn tracker(&self, vocation_id: u8) -> SkillTracker {
+        match self.factors(vocation_id) {
+            Some(factors) => SkillTracker::with_factors(factors.clone()),
+            None => SkillTracker::default(),
+        }
+    }
+}
+
+/// Progress of a single skill
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SkillProgress {
+    pub skill: SkillType,
+    pub level: u8,
+    /// Tries gathered toward the next level
+    pub tries: u64,
+    /// Tries the next level needs in total
+    pub tries_needed: u64,
+}
+
+impl SkillProgress {
+    pub fn new(skill: SkillType, level: u8, tries: u64, vocation_factor: f32) -> Self {
+        Self {
+            skill,
+            level,
+            tries,
+            tries_needed: tries_for_next_level(skill, level, vocation_factor),
+        }
+    }
+
+    /// Percent to the next level (0-99)
+    pub fn percent(&self) -> u8 {
+        (self.tries.saturating_mul(100) / self.tries_needed).min(99) as u8
+    }
+
+    pub fn tries_remaining(&self) -> u64 {
+        self.tries_needed.saturating_sub(self.tries)
+    }
+}
+
+/// Skill levels and tries of one character
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillTracker {
+    /// Vocation multipliers on the tries each level needs
+    factors: VocationSkillFactors,
+    /// Skill -> (level, tries toward the next level)
+    skills: HashMap<SkillType, (u8, u64)>,
+}
+
+impl SkillTracker {
+    /// Tracker with one vocation factor for every skill
+    pub fn new(vocation_factor: f32) -> Self {
+        Self::with_factors(VocationSkillFactors { default: vocation_factor, skills: HashMap::new() })
+    }
+
+    /// Tracker with per-skill vocation factors
+    pub fn with_factors(factors: VocationSkillFactors) -> Self {
+        Self {
+            factors,
+            skills: HashMap::new(),
+        }
+    }
+
+    /// Vocation factor of a skill
+    pub fn factor(&self, skill: SkillType) -> f32 {
+        self.factors.factor(skill)
+    }
+
+    /// Start a skill at `level` with `tries` already gathered
+    pub fn with_skill(mut self, skill: SkillType, level: u8, tries: u64) -> Self {
+        self.skills.insert(skill, (level, tries));
+        self
+    }
+
+    pub fn level(&self, skill: SkillType) -> u8 {
+        self.skills.get(&skill).map(|(level, _)| *level).unwrap_or_else(|| default_level(skill))
+    }
+
+    /// Add tries to a skill, returning the number of levels gained
+    pub fn add_tries(&mut self, skill: SkillType, tries: u64) -> u8 {
+        let factor = self.factor(skill);
+        let entry = self.skills.entry(skill).or_insert((default_level(skill), 0));
+        entry.1 = entry.1.saturating_add(tries);
+
+        let mut gained = 0;
+        while entry.0 < u8::MAX {
+            let needed = tries_for_next_level(skill, entry.0, factor);
+            if entry.1 < needed {
+                break;
+            }
+            entry.1 -= needed;
+            entry.0 += 1;
+            gained += 1;
+        }
+        gained
+    }
+
+    pub fn progress(&self, skill: SkillType) -> SkillProgress {
+        let (level, tries) = self.skills.get(&skill).copied().unwrap_or((default_level(skill), 0));
+        SkillProgress::new(skill, level, tries, self.factor(skill))
+    }
+
+    /// Progress of every tracked skill
+    pub fn all_progress(&self) -> Vec<SkillProgress> {
+        TRACKED_SKILLS.iter().map(|&skill| self.progress(skill)).collect()
+    }
+
+    /// Copy levels and percentages onto the creature
+    pub fn apply_to(&self, creature: &mut Creature) {
+        for progress in self.all_progress() {
+            if progress.skill == SkillType::MagicLevel {
+                creature.stats.magic_level = progress.level;
+            } else {
+                creature.set_skill(progress.skill, progress.level, progress.percent());
+            }
+        }
+    }
+}
+
+impl Default for SkillTracker {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+fn default_level(skill: SkillType) -> u8 {
+    match skill {
+        SkillType::MagicLevel => 0,
+        _ => DEFAULT_SKILL_LEVEL,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tries_accumulate_and_percent_advances() {
+        let mut tracker = SkillTracker::default();
+        let needed = tracker.progress(SkillType::Sword).tries_needed;
+        assert_eq!(needed, calculate_skill_tries(DEFAULT_SKILL_LEVEL, 1.0));
+        assert_eq!(tracker.progress(SkillType::Sword).percent(), 0);
+
+        assert_eq!(tracker.add_tries(SkillType::Sword, needed / 4), 0);
+        assert_eq!(tracker.add_tries(SkillType::Sword, needed / 4), 0);
+        let progress = tracker.progress(SkillType::Sword);
+        assert_eq!(progress.tries, needed / 2);
+        assert_eq!(progress.percent(), 50);
+        assert_eq!(progress.tries_remaining(), needed - needed / 2);
+
+        // Reaching the requirement advances the level and carries the rest over
+        assert_eq!(tracker.add_tries(SkillType::Sword, needed - needed / 2 + 7), 1);
+        let progress = tracker.progress(SkillType::Sword);
+        assert_eq!(progress.level, DEFAULT_SKILL_LEVEL + 1);
+        assert_eq!(progress.tries, 7);
+        assert_eq!(progress.tries_needed, calculate_skill_tries(DEFAULT_SKILL_LEVEL + 1, 1.0));
+    }
+
+    #[test]
+    fn test_apply_progress_to_creature() {
+        let mut creature = Creature::new(
+            "Player".to_string(),
+            shadow_world::creature::CreatureType::Player,
+            shadow_world::position::Position::new(100, 100, 7),
+        );
+        let mut tracker = SkillTracker::new(1.1).with_skill(SkillType::Distance, 40, 0);
+        let needed = tracker.progress(SkillType::Distance).tries_needed;
+        tracker.add_tries(SkillType::Distance, needed * 3 / 4);
+        tracker.add_tries(SkillType::MagicLevel, tries_for_next_level(SkillType::MagicLevel, 0, 1.1));
+
+        tracker.apply_to(&mut creature);
+        assert_eq!(creature.get_skill(SkillType::Distance), 40);
+        assert_eq!(creature.get_skill_percent(SkillType::Distance), 75);
+        assert_eq!(creature.stats.magic_level, 1);
+    }
+}
@@ -0,0 +1,253 @@
+//! Auto-save with per-entity dirty tracking
+//!
+//! Instead of persisting everything on every save interval, systems mark
+//! the characters and sessions they change as dirty. The scheduled save
+//! only writes dirty entities, an entity with too many unsaved changes
+//! triggers an immediate save, and entities whose save failed stay dirty
+//! and are retried on the next flush.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+/// Kind of persisted entity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EntityKind {
+    Character,
+    Session,
+}
+
+/// Identifies an entity to save
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SaveKey {
+    pub kind: EntityKind,
+    pub id: Uuid,
+}
+
+impl SaveKey {
+    pub fn character(id: Uuid) -> Self {
+        Self { kind: EntityKind::Character, id }
+    }
+
+    pub fn session(id: Uuid) -> Self {
+        Self { kind: EntityKind::Session, id }
+    }
+}
+
+/// Persists a single entity
+#[async_trait]
+pub trait EntitySaver: Send + Sync {
+    async fn save(&self, key: SaveKey) -> crate::Result<()>;
+}
+
+/// Auto-save configuration
+#[derive(Debug, Clone)]
+pub struct AutoSaveConfig {
+    /// Time between scheduled saves
+    pub interval: Duration,
+    /// Unsaved changes on one entity that trigger an immediate save (0 disables)
+    pub max_unsaved_changes: u32,
+    /// Consecutive failures before an entity is reported as failing
+    pub max_retries: u32,
+}
+
+impl Default for AutoSaveConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(5 * 60),
+            max_unsaved_changes: 100,
+            max_retries: 3,
+        }
+    }
+}
+
+impl AutoSaveConfig {
+    pub fn with_interval_minutes(mut self, minutes: u32) -> Self {
+        self.interval = Duration::from_secs(minutes as u64 * 60);
+        self
+    }
+}
+
+/// Outcome of a flush
+#[derive(Debug, Clone, Default)]
+pub struct SaveReport {
+    pub saved: Vec<SaveKey>,
+    pub failed: Vec<SaveKey>,
+    /// Entities that have now failed `max_retries` times in a row
+    pub exhausted: Vec<SaveKey>,
+}
+
+/// Tracks dirty entities and flushes them through an `EntitySaver`
+#[derive(Debug)]
+pub struct AutoSave {
+    config: AutoSaveConfig,
+    /// Dirty entities and their number of unsaved changes
+    dirty: HashMap<SaveKey, u32>,
+    /// Consecutive save failures per entity
+    failures: HashMap<SaveKey, u32>,
+    last_flush: Instant,
+    immediate: bool,
+}
+
+impl AutoSave {
+    pub fn new(config: AutoSaveConfig) -> Self {
+        Self {
+            config,
+            dirty: HashMap::new(),
+            failures: HashMap::new(),
+            last_flush: Instant::now(),
+            immediate: false,
+        }
+    }
+
+    /// Record a change to an entity. Returns true when it crossed the
+    /// unsaved-changes limit and an immediate save is requested.
+    pub fn mark_dirty(&mut self, key: SaveKey) -> bool {
+        let changes = self.dirty.entry(key).or_insert(0);
+        *changes += 1;
+        if self.config.max_unsaved_changes > 0 && *changes >= self.config.max_unsaved_changes {
+            self.immediate = true;
+        }
+        self.immediate
+    }
+
+    pub fn is_dirty(&self, key: &SaveKey) -> bool {
+        self.dirty.contains_key(key)
+    }
+
+    pub fn dirty_count(&self) -> usize {
+        self.dirty.len()
+    }
+
+    /// Consecutive failures for an entity
+    pub fn failures(&self, key: &SaveKey) -> u32 {
+        self.failures.get(key).copied().unwrap_or(0)
+    }
+
+    /// Whether a flush is due at `now`
+    pub fn should_flush(&self, now: Instant) -> bool {
+        !self.dirty.is_empty()
+            && (self.immediate || now.duration_since(self.last_flush) >= self.config.interval)
+    }
+
+    /// Save every dirty entity. Failed entities stay dirty for the next flush.
+    pub async fn flush(&mut self, saver: &dyn EntitySaver, now: Instant) -> SaveReport {
+        let mut report = SaveReport::default();
+        let keys: Vec<SaveKey> = self.dirty.keys().copied().collect();
+
+        for key in keys {
+            match saver.save(key).await {
+                Ok(()) => {
+                    self.dirty.remove(&key);
+                    self.failures.remove(&key);
+                    report.saved.push(key);
+                }
+                Err(e) => {
+                    let failures = self.failures.entry(key).or_insert(0);
+                    *failures += 1;
+                    tracing::warn!(
+                        "Failed to save {:?} {} (attempt {}): {}",
+                        key.kind, key.id, failures, e
+                    );
+                    if *failures >= self.config.max_retries {
+                        report.exhausted.push(key);
+                    }
+                    report.failed.push(key);
+                }
+            }
+        }
+
+        self.last_flush = now;
+        self.immediate = false;
+        report
+    }
+
+    /// Flush only if the interval elapsed or an immediate save was requested
+    pub async fn tick(&mut self, saver: &dyn EntitySaver, now: Instant) -> Option<SaveReport> {
+        if !self.should_flush(now) {
+            return None;
+        }
+        Some(self.flush(saver, now).await)
+    }
+}
+
+impl Default for AutoSave {
+    fn default() -> Self {
+        Self::new(AutoSaveConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingSaver {
+        saved: Mutex<Vec<SaveKey>>,
+        failing: Mutex<HashSet<SaveKey>>,
+    }
+
+    #[async_trait]
+    impl EntitySaver for RecordingSaver {
+        async fn save(&self, key: SaveKey) -> crate::Result<()> {
+            if self.failing.lock().unwrap().contains(&key) {
+                return Err(crate::CoreError::Internal("database unavailable".into()));
+            }
+            self.saved.lock().unwrap().push(key);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_only_dirty_entities_saved() {
+        let mut autosave = AutoSave::default();
+        let saver = RecordingSaver::default();
+        let (dirty, clean) = (SaveKey::character(Uuid::new_v4()), SaveKey::character(Uuid::new_v4()));
+        let start = Instant::now();
+
+        autosave.mark_dirty(dirty);
+        assert!(!autosave.should_flush(start));
+        assert!(autosave.tick(&saver, start).await.is_none());
+
+        let report = autosave.tick(&saver, start + Duration::from_secs(301)).await.unwrap();
+        assert_eq!(report.saved, vec![dirty]);
+        assert_eq!(*saver.saved.lock().unwrap(), vec![dirty]);
+        assert!(!saver.saved.lock().unwrap().contains(&clean));
+        assert_eq!(autosave.dirty_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_failed_save_is_retried() {
+        let mut autosave = AutoSave::default();
+        let saver = RecordingSaver::default();
+        let session = SaveKey::session(Uuid::new_v4());
+        saver.failing.lock().unwrap().insert(session);
+
+        autosave.mark_dirty(session);
+        let report = autosave.flush(&saver, Instant::now()).await;
+        assert_eq!(report.failed, vec![session]);
+        assert!(autosave.is_dirty(&session));
+        assert_eq!(autosave.failures(&session), 1);
+
+        // Database recovers, the next flush retries it
+        saver.failing.lock().unwrap().clear();
+        let report = autosave.flush(&saver, Instant::now()).await;
+        assert_eq!(report.saved, vec![session]);
+        assert_eq!(autosave.failures(&session), 0);
+    }
+
+    #[test]
+    fn test_unsaved_changes_trigger_immediate_save() {
+        let mut autosave = AutoSave::new(AutoSaveConfig { max_unsaved_changes: 3, ..Default::default() });
+        let key = SaveKey::character(Uuid::new_v4());
+
+        assert!(!autosave.mark_dirty(key));
+        assert!(!autosave.mark_dirty(key));
+        assert!(autosave.mark_dirty(key));
+        assert!(autosave.should_flush(Instant::now()));
+    }
+}
@@ -0,0 +1,344 @@
+//! Scheduled database backups
+//!
+//! The backup service runs a logical export of the database on the
+//! scheduler's `Backup` task: either a full `pg_dump` or a data-only
+//! snapshot of selected tables. Each backup is written to the configured
+//! directory under a timestamped name, and after a successful export the
+//! backups past the retention limits are deleted. The newest backup is
+//! never pruned. Every run ends in a `BackupEvent` for subscribers, so a
+//! failed export is reported instead of silently leaving a gap.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+use crate::scheduler::{Scheduler, TaskType};
+
+/// Timestamp format in backup file names
+const TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+/// What a backup exports
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BackupScope {
+    /// Schema and data of the whole database
+    Full,
+    /// Data of the listed tables only
+    Tables(Vec<String>),
+}
+
+/// Backup settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackupConfig {
+    pub enabled: bool,
+    /// Minutes between backups
+    pub interval_minutes: u32,
+    /// Directory the backups are written to
+    pub directory: PathBuf,
+    /// File name prefix, followed by the backup time
+    pub file_prefix: String,
+    pub scope: BackupScope,
+    /// Newest backups to keep (0 keeps any number)
+    pub keep_last: usize,
+    /// Backups older than this are deleted (hours, 0 keeps them forever)
+    pub max_age_hours: u32,
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_minutes: 6 * 60,
+            directory: PathBuf::from("backups"),
+            file_prefix: "shadow".to_string(),
+            scope: BackupScope::Full,
+            keep_last: 14,
+            max_age_hours: 7 * 24,
+        }
+    }
+}
+
+impl BackupConfig {
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_minutes.max(1) as u64 * 60)
+    }
+}
+
+/// Outcome of a backup run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BackupEvent {
+    Succeeded {
+        path: PathBuf,
+        bytes: u64,
+        /// Old backups deleted by retention
+        pruned: Vec<PathBuf>,
+        at: DateTime<Utc>,
+    },
+    Failed {
+        error: String,
+        at: DateTime<Utc>,
+    },
+}
+
+/// A backup found in the backup directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupFile {
+    pub path: PathBuf,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Writes a database export to a file
+#[async_trait]
+pub trait BackupExporter: Send + Sync {
+    /// Export `scope` to `path`, returning the bytes written
+    async fn export(&self, scope: &BackupScope, path: &Path) -> crate::Result<u64>;
+}
+
+/// Exports through the `pg_dump` command
+#[derive(Debug, Clone)]
+pub struct PgDumpExporter {
+    /// Path to the `pg_dump` binary
+    pub command: String,
+    pub database_url: String,
+}
+
+impl PgDumpExporter {
+    pub fn new(database_url: &str) -> Self {
+        Self {
+            command: "pg_dump".to_string(),
+            database_url: database_url.to_string(),
+        }
+    }
+
+    pub fn with_command(mut self, command: &str) -> Self {
+        self.command = command.to_string();
+        self
+    }
+
+    fn args(&self, scope: &BackupScope, path: &Path) -> Vec<String> {
+        let mut args = vec!["--format=custom".to_string(), format!("--file={}", path.display())];
+        if let BackupScope::Tables(tables) = scope {
+            args.push("--data-only".to_string());
+            args.extend(tables.iter().map(|table| format!("--table={}", table)));
+        }
+        args.push(self.database_url.clone());
+        args
+    }
+}
+
+#[async_trait]
+impl BackupExporter for PgDumpExporter {
+    async fn export(&self, scope: &BackupScope, path: &Path) -> crate::Result<u64> {
+        let output = tokio::process::Command::new(&self.command)
+            .args(self.args(scope, path))
+            .output()
+            .await?;
+        if !output.status.success() {
+            return Err(crate::CoreError::Internal(format!(
+                "{} exited with {}: {}",
+                self.command,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(tokio::fs::metadata(path).await?.len())
+    }
+}
+
+/// Runs backups and applies retention
+pub struct BackupService {
+    config: BackupConfig,
+    event_tx: broadcast::Sender<BackupEvent>,
+}
+
+impl BackupService {
+    pub fn new(config: BackupConfig) -> Self {
+        let (event_tx, _) = broadcast::channel(16);
+        Self { config, event_tx }
+    }
+
+    pub fn config(&self) -> &BackupConfig {
+        &self.config
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<BackupEvent> {
+        self.event_tx.subscribe()
+    }
+
+    /// Register the recurring `Backup` task. Returns None when backups are disabled.
+    pub async fn schedule(&self, scheduler: &Scheduler) -> Option<uuid::Uuid> {
+        if !self.config.enabled {
+            return None;
+        }
+        let interval = self.config.interval();
+        Some(scheduler.schedule_recurring("backup", interval, interval, TaskType::Backup).await)
+    }
+
+    /// Path of the backup taken at `at`
+    pub fn backup_path(&self, at: DateTime<Utc>) -> PathBuf {
+        self.config
+            .directory
+            .join(format!("{}-{}.dump", self.config.file_prefix, at.format(TIMESTAMP_FORMAT)))
+    }
+
+    /// Take a backup, prune old ones on success and broadcast the outcome
+    pub async fn run(&self, exporter: &dyn BackupExporter, now: DateTime<Utc>) -> BackupEvent {
+        let event = match self.export(exporter, now).await {
+            Ok((path, bytes)) => {
+                let pruned = self.prune(now).await;
+                tracing::info!("Backup written to {} ({} bytes, {} pruned)", path.display(), bytes, pruned.len());
+                BackupEvent::Succeeded { path, bytes, pruned, at: now }
+            }
+            Err(e) => {
+                tracing::error!("Backup failed: {}", e);
+                BackupEvent::Failed { error: e.to_string(), at: now }
+            }
+        };
+        let _ = self.event_tx.send(event.clone());
+        event
+    }
+
+    async fn export(&self, exporter: &dyn BackupExporter, now: DateTime<Utc>) -> crate::Result<(PathBuf, u64)> {
+        tokio::fs::create_dir_all(&self.config.directory).await?;
+        let path = self.backup_path(now);
+        match exporter.export(&self.config.scope, &path).await {
+            Ok(bytes) => Ok((path, bytes)),
+            Err(e) => {
+                // Never leave a partial dump that looks like a good backup
+                let _ = tokio::fs::remove_file(&path).await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Backups in the backup directory, newest first
+    pub async fn list(&self) -> crate::Result<Vec<BackupFile>> {
+        let mut backups = Vec::new();
+        let mut entries = tokio::fs::read_dir(&self.config.directory).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if let Some(created_at) = self.parse_timestamp(&path) {
+                backups.push(BackupFile { path, created_at });
+            }
+        }
+        backups.sort_by_key(|b| std::cmp::Reverse(b.created_at));
+        Ok(backups)
+    }
+
+    /// Delete the backups past retention, returning their paths
+    async fn prune(&self, now: DateTime<Utc>) -> Vec<PathBuf> {
+        let backups = match self.list().await {
+            Ok(backups) => backups,
+            Err(e) => {
+                tracing::warn!("Could not list backups for pruning: {}", e);
+                return Vec::new();
+            }
+        };
+
+        let mut pruned = Vec::new();
+        for path in expired_backups(&backups, now, self.config.keep_last, self.config.max_age_hours) {
+            match tokio::fs::remove_file(&path).await {
+                Ok(()) => pruned.push(path),
+                Err(e) => tracing::warn!("Could not delete old backup {}: {}", path.display(), e),
+            }
+        }
+        pruned
+    }
+
+    fn parse_timestamp(&self, path: &Path) -> Option<DateTime<Utc>> {
+        let name = path.file_name()?.to_str()?;
+        let stamp = name
+            .strip_prefix(&self.config.file_prefix)?
+            .strip_prefix('-')?
+            .strip_suffix(".dump")?;
+        let time = NaiveDateTime::parse_from_str(stamp, TIMESTAMP_FORMAT).ok()?;
+        Some(time.and_utc())
+    }
+}
+
+/// Backups to delete: those beyond the `keep_last` newest and those older
+/// than `max_age_hours`. A zero limit is disabled, and the newest backup is
+/// always kept.
+pub fn expired_backups(backups: &[BackupFile], now: DateTime<Utc>, keep_last: usize, max_age_hours: u32) -> Vec<PathBuf> {
+    let mut newest_first: Vec<&BackupFile> = backups.iter().collect();
+    newest_first.sort_by_key(|b| std::cmp::Reverse(b.created_at));
+    let max_age = chrono::Duration::hours(max_age_hours as i64);
+
+    newest_first
+        .into_iter()
+        .enumerate()
+        .skip(1)
+        .filter(|(index, backup)| {
+            (keep_last > 0 && *index >= keep_last) || (max_age_hours > 0 && now - backup.created_at > max_age)
+        })
+        .map(|(_, backup)| backup.path.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration as ChronoDuration;
+
+    struct FailingExporter;
+
+    #[async_trait]
+    impl BackupExporter for FailingExporter {
+        async fn export(&self, _scope: &BackupScope, path: &Path) -> crate::Result<u64> {
+            tokio::fs::write(path, b"partial").await?;
+            Err(crate::CoreError::Internal("pg_dump: connection refused".into()))
+        }
+    }
+
+    fn backup(hours_ago: i64, now: DateTime<Utc>) -> BackupFile {
+        BackupFile {
+            path: PathBuf::from(format!("backups/shadow-{}h.dump", hours_ago)),
+            created_at: now - ChronoDuration::hours(hours_ago),
+        }
+    }
+
+    #[test]
+    fn test_retention_prunes_oldest_backups() {
+        let now = Utc::now();
+        let backups: Vec<BackupFile> = [30, 0, 6, 200, 12].iter().map(|&h| backup(h, now)).collect();
+        let paths = |hours: &[i64]| -> Vec<PathBuf> { hours.iter().map(|&h| backup(h, now).path).collect() };
+
+        assert_eq!(expired_backups(&backups, now, 3, 0), paths(&[30, 200]));
+        assert_eq!(expired_backups(&backups, now, 0, 24), paths(&[30, 200]));
+        assert_eq!(expired_backups(&backups, now, 4, 168), paths(&[200]));
+        assert!(expired_backups(&backups, now, 0, 0).is_empty());
+
+        // The newest backup survives even when every backup is too old
+        let stale = vec![backup(500, now), backup(400, now)];
+        assert_eq!(expired_backups(&stale, now, 1, 24), paths(&[500]));
+    }
+
+    #[tokio::test]
+    async fn test_failed_backup_emits_failure_event() {
+        let directory = std::env::temp_dir().join(format!("shadow-backup-{}", uuid::Uuid::new_v4()));
+        let service = BackupService::new(BackupConfig {
+            directory: directory.clone(),
+            keep_last: 1,
+            ..Default::default()
+        });
+        let now = Utc::now();
+        let old = service.backup_path(now - ChronoDuration::hours(1));
+        tokio::fs::create_dir_all(&directory).await.unwrap();
+        tokio::fs::write(&old, b"good").await.unwrap();
+
+        let mut events = service.subscribe();
+        let event = service.run(&FailingExporter, now).await;
+
+        assert!(matches!(&event, BackupEvent::Failed { error, .. } if error.contains("connection refused")));
+        assert_eq!(events.recv().await.unwrap(), event);
+        // The partial dump is removed and the last good backup is not pruned
+        let remaining: Vec<PathBuf> = service.list().await.unwrap().into_iter().map(|b| b.path).collect();
+        assert_eq!(remaining, vec![old]);
+
+        tokio::fs::remove_dir_all(&directory).await.unwrap();
+    }
+}
@@ -6,6 +6,9 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use shadow_world::house::{HouseAcquisitionMode, HouseManager, HousePurchaseError};
+
+use crate::reset::{ResetEvent, ResetKind};
 use std::collections::HashMap;
 use uuid::Uuid;
 
@@ -165,8 +168,11 @@ pub enum TransactionType {
     HousePayment,
     MarketPurchase,
     MarketSale,
+    MarketRefund,
     Interest,
     Fee,
+    Tax,
+    Blessing,
 }
 
 /// Bank transaction record
@@ -298,6 +304,75 @@ impl GuildBank {
     }
 }
 
+/// Interest paid on bank balances
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterestConfig {
+    /// Interest per period in basis points (100 = 1%)
+    pub rate_bp: u32,
+    /// Balance above this earns no interest
+    pub max_balance: u64,
+    /// Most interest one account earns per period
+    pub max_per_period: u64,
+}
+
+/// Wealth tax taken from large balances
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaxConfig {
+    /// Tax per period in basis points of the balance above `threshold`
+    pub rate_bp: u32,
+    /// Balance that is never taxed
+    pub threshold: u64,
+    /// Most tax taken from one account per period
+    pub max_per_period: u64,
+}
+
+/// Periodic interest and tax, applied on a reset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BankEconomyConfig {
+    /// Reset that triggers interest and tax
+    pub period: ResetKind,
+    pub interest: Option<InterestConfig>,
+    pub tax: Option<TaxConfig>,
+}
+
+impl Default for BankEconomyConfig {
+    fn default() -> Self {
+        Self {
+            period: ResetKind::Daily,
+            interest: None,
+            tax: None,
+        }
+    }
+}
+
+impl BankEconomyConfig {
+    /// Interest earned by `balance` in one period
+    pub fn interest_for(&self, balance: u64) -> u64 {
+        self.interest.as_ref().map_or(0, |interest| {
+            basis_points(balance.min(interest.max_balance), interest.rate_bp).min(interest.max_per_period)
+        })
+    }
+
+    /// Tax owed on `balance` for one period
+    pub fn tax_for(&self, balance: u64) -> u64 {
+        self.tax.as_ref().map_or(0, |tax| {
+            basis_points(balance.saturating_sub(tax.threshold), tax.rate_bp).min(tax.max_per_period)
+        })
+    }
+}
+
+fn basis_points(amount: u64, rate_bp: u32) -> u64 {
+    (amount as u128 * rate_bp as u128 / 10_000) as u64
+}
+
+/// Totals of one interest and tax run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PeriodicReport {
+    pub accounts: usize,
+    pub interest_paid: u64,
+    pub tax_collected: u64,
+}
+
 /// Bank manager handles all banking operations
 pub struct BankManager {
     /// Character bank accounts
@@ -308,6 +383,8 @@ pub struct BankManager {
     transactions: Vec<Transaction>,
     /// Max transaction history size
     max_history: usize,
+    /// Periodic interest and tax
+    economy: BankEconomyConfig,
 }
 
 impl BankManager {
@@ -318,7 +395,69 @@ impl BankManager {
             guild_banks: HashMap::new(),
             transactions: Vec::new(),
             max_history: 10000,
+            economy: BankEconomyConfig::default(),
+        }
+    }
+
+    /// Enable periodic interest and/or tax
+    pub fn with_economy(mut self, economy: BankEconomyConfig) -> Self {
+        self.economy = economy;
+        self
+    }
+
+    /// Apply interest and tax when the configured reset fires
+    pub fn on_reset(&mut self, event: &ResetEvent) -> Option<PeriodicReport> {
+        (event.kind == self.economy.period).then(|| self.apply_periodic())
+    }
+
+    /// Credit interest and debit tax on every active account. Each change
+    /// is recorded as a transaction for the audit trail.
+    pub fn apply_periodic(&mut self) -> PeriodicReport {
+        let mut report = PeriodicReport::default();
+        let mut records = Vec::new();
+
+        for account in self.accounts.values_mut().filter(|a| a.is_active()) {
+            let interest = self.economy.interest_for(account.balance);
+            let tax = self.economy.tax_for(account.balance);
+            if interest == 0 && tax == 0 {
+                continue;
+            }
+            report.accounts += 1;
+
+            if interest > 0 {
+                account.balance = account.balance.saturating_add(interest);
+                report.interest_paid += interest;
+                records.push(Transaction::new(
+                    None,
+                    Some(account.id),
+                    interest,
+                    TransactionType::Interest,
+                    format!("Interest of {} gold", interest),
+                ));
+            }
+            if tax > 0 {
+                account.balance -= tax.min(account.balance);
+                report.tax_collected += tax;
+                records.push(Transaction::new(
+                    Some(account.id),
+                    None,
+                    tax,
+                    TransactionType::Tax,
+                    format!("Wealth tax of {} gold", tax),
+                ));
+            }
         }
+
+        for record in records {
+            self.record_transaction(record);
+        }
+        tracing::info!(
+            "Bank period applied: {} accounts, {} interest, {} tax",
+            report.accounts,
+            report.interest_paid,
+            report.tax_collected
+        );
+        report
     }
 
     /// Get or create a bank account for a character
@@ -448,6 +587,22 @@ impl BankManager {
         Ok(transaction)
     }
 
+    /// Buy a free house at its fixed price, paid from the character's account
+    pub fn buy_house(
+        &mut self,
+        houses: &mut HouseManager,
+        mode: HouseAcquisitionMode,
+        house_id: u32,
+        player_id: u32,
+        character_id: Uuid,
+    ) -> Result<u64, HousePurchaseError> {
+        houses.instant_buy(house_id, player_id, mode, |price| {
+            let description = format!("House purchase #{}", house_id);
+            self.deduct_for_purchase(character_id, price, TransactionType::HousePayment, &description)
+                .is_ok()
+        })
+    }
+
     /// Credit gold for a sale
     pub fn credit_for_sale(
         &mut self,
@@ -651,4 +806,87 @@ mod tests {
         assert_eq!(manager.get_balance(char1), 700);
         assert_eq!(manager.get_balance(char2), 300);
     }
+
+    #[test]
+    fn test_interest_accrues_within_caps() {
+        let economy = BankEconomyConfig {
+            interest: Some(InterestConfig { rate_bp: 100, max_balance: 1_000_000, max_per_period: 5_000 }),
+            ..Default::default()
+        };
+        let mut manager = BankManager::new().with_economy(economy);
+        let (small, large, huge) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        manager.deposit(small, 50_000).unwrap();
+        manager.deposit(large, 400_000).unwrap();
+        manager.deposit(huge, 900_000_000).unwrap();
+
+        // Weekly resets do not pay daily interest
+        let weekly = ResetEvent { kind: ResetKind::Weekly, boundary: Utc::now() };
+        assert!(manager.on_reset(&weekly).is_none());
+
+        let daily = ResetEvent { kind: ResetKind::Daily, boundary: Utc::now() };
+        let report = manager.on_reset(&daily).unwrap();
+        assert_eq!(manager.get_balance(small), 50_500);
+        assert_eq!(manager.get_balance(large), 404_000);
+        assert_eq!(manager.get_balance(huge), 900_005_000);
+        assert_eq!(report, PeriodicReport { accounts: 3, interest_paid: 9_500, tax_collected: 0 });
+        assert_eq!(manager.get_history(small, 10).last().unwrap().transaction_type, TransactionType::Interest);
+    }
+
+    #[test]
+    fn test_wealth_tax_debit() {
+        let economy = BankEconomyConfig {
+            tax: Some(TaxConfig { rate_bp: 50, threshold: 10_000_000, max_per_period: 1_000_000 }),
+            ..Default::default()
+        };
+        let mut manager = BankManager::new().with_economy(economy);
+        let (poor, rich) = (Uuid::new_v4(), Uuid::new_v4());
+        manager.deposit(poor, 5_000_000).unwrap();
+        manager.deposit(rich, 30_000_000).unwrap();
+
+        let report = manager.apply_periodic();
+        // 0.5% of the 20M above the threshold
+        assert_eq!(manager.get_balance(rich), 29_900_000);
+        assert_eq!(manager.get_balance(poor), 5_000_000);
+        assert_eq!(report.tax_collected, 100_000);
+
+        let audit = manager.get_history(rich, 10);
+        let tax = audit.last().unwrap();
+        assert_eq!((tax.transaction_type, tax.amount), (TransactionType::Tax, 100_000));
+    }
+
+    fn house_manager() -> HouseManager {
+        let mut houses = HouseManager::new();
+        let mut house = shadow_world::house::House::new(1, "Harbour Place 1".to_string());
+        house.price = 80_000;
+        houses.add_house(house);
+        houses
+    }
+
+    #[test]
+    fn test_instant_buy_free_house() {
+        let mut bank = BankManager::new();
+        let mut houses = house_manager();
+        let character = Uuid::new_v4();
+        bank.deposit(character, 100_000).unwrap();
+
+        let price = bank.buy_house(&mut houses, HouseAcquisitionMode::InstantBuy, 1, 42, character).unwrap();
+        assert_eq!(price, 80_000);
+        assert_eq!(bank.get_balance(character), 20_000);
+        assert!(houses.get(1).unwrap().is_owner(42));
+        assert_eq!(bank.get_history(character, 10).last().unwrap().transaction_type, TransactionType::HousePayment);
+    }
+
+    #[test]
+    fn test_instant_buy_rejects_owned_house() {
+        let mut bank = BankManager::new();
+        let mut houses = house_manager();
+        houses.transfer_ownership(1, 7);
+        let character = Uuid::new_v4();
+        bank.deposit(character, 100_000).unwrap();
+
+        let result = bank.buy_house(&mut houses, HouseAcquisitionMode::InstantBuy, 1, 42, character);
+        assert_eq!(result, Err(HousePurchaseError::AlreadyOwned));
+        assert_eq!(bank.get_balance(character), 100_000);
+        assert!(houses.get(1).unwrap().is_owner(7));
+    }
 }
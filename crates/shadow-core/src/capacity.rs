@@ -0,0 +1,242 @@
+//! Capacity System
+//!
+//! Central weight/capacity check used by every path that puts items on a
+//! character: picking items up, moving them between containers, completing
+//! a trade and receiving market purchases. Carried weight is everything
+//! equipped plus the contents of carried containers; item weights are in
+//! hundredths of an ounce (as in items.otb), capacity is in ounces.
+
+use serde::{Deserialize, Serialize};
+use shadow_world::ItemLoader;
+use std::collections::{HashMap, HashSet};
+
+use crate::trade::TradeItem;
+
+/// Capacity enforcement settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapacityConfig {
+    /// Whether capacity is enforced at all
+    pub enabled: bool,
+    /// Containers whose contents weigh nothing (gold pouch)
+    pub weightless_containers: HashSet<u16>,
+}
+
+impl Default for CapacityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            weightless_containers: HashSet::from([23721]),
+        }
+    }
+}
+
+/// An item carried by a character, with its contents if it is a container
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CarriedItem {
+    pub item_type_id: u16,
+    pub count: u32,
+    pub contents: Vec<CarriedItem>,
+}
+
+impl CarriedItem {
+    pub fn new(item_type_id: u16, count: u32) -> Self {
+        Self {
+            item_type_id,
+            count,
+            contents: Vec::new(),
+        }
+    }
+
+    pub fn container(item_type_id: u16, contents: Vec<CarriedItem>) -> Self {
+        Self {
+            item_type_id,
+            count: 1,
+            contents,
+        }
+    }
+}
+
+/// A character's capacity and everything it currently carries
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CharacterLoad {
+    /// Maximum capacity in ounces
+    pub max_capacity: u32,
+    /// Equipped items; the backpack slot holds its contents
+    pub equipment: Vec<CarriedItem>,
+}
+
+/// Where an item is moved from or to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemLocation {
+    Ground,
+    Depot,
+    /// Equipment slots and carried containers
+    Inventory,
+    /// A carried container whose contents weigh nothing
+    WeightlessContainer,
+}
+
+/// Weight and capacity checks
+pub struct CapacityService {
+    config: CapacityConfig,
+    weights: HashMap<u16, u32>,
+}
+
+impl CapacityService {
+    pub fn new(config: CapacityConfig, items: &ItemLoader) -> Self {
+        Self {
+            config,
+            weights: items.all().iter().map(|(id, t)| (*id, t.weight)).collect(),
+        }
+    }
+
+    /// Override the weight of an item type
+    pub fn with_weight(mut self, item_type_id: u16, weight: u32) -> Self {
+        self.weights.insert(item_type_id, weight);
+        self
+    }
+
+    /// Weight of a stack, in hundredths of an ounce
+    pub fn item_weight(&self, item_type_id: u16, count: u32) -> u64 {
+        self.weights.get(&item_type_id).copied().unwrap_or(0) as u64 * count.max(1) as u64
+    }
+
+    /// Weight of an item including its contents
+    pub fn carried_item_weight(&self, item: &CarriedItem) -> u64 {
+        let own = self.item_weight(item.item_type_id, item.count);
+        if self.config.weightless_containers.contains(&item.item_type_id) {
+            return own;
+        }
+        own + item.contents.iter().map(|c| self.carried_item_weight(c)).sum::<u64>()
+    }
+
+    /// Total carried weight
+    pub fn carried_weight(&self, load: &CharacterLoad) -> u64 {
+        load.equipment.iter().map(|i| self.carried_item_weight(i)).sum()
+    }
+
+    /// Remaining capacity, in hundredths of an ounce
+    pub fn free_capacity(&self, load: &CharacterLoad) -> u64 {
+        (load.max_capacity as u64 * 100).saturating_sub(self.carried_weight(load))
+    }
+
+    /// Whether the character can take on `added_weight` more
+    pub fn can_carry(&self, load: &CharacterLoad, added_weight: u64) -> bool {
+        !self.config.enabled || added_weight <= self.free_capacity(load)
+    }
+
+    /// Picking an item up from the ground
+    pub fn can_pick_up(&self, load: &CharacterLoad, item: &CarriedItem) -> bool {
+        self.can_move(load, item, ItemLocation::Ground, ItemLocation::Inventory)
+    }
+
+    /// Moving an item between containers. Only weight entering the
+    /// inventory from outside counts; moves within it are free.
+    pub fn can_move(&self, load: &CharacterLoad, item: &CarriedItem, from: ItemLocation, to: ItemLocation) -> bool {
+        let carried = |l| matches!(l, ItemLocation::Inventory | ItemLocation::WeightlessContainer);
+        if !carried(to) || carried(from) {
+            return true;
+        }
+        let added = match to {
+            ItemLocation::WeightlessContainer => 0,
+            _ => self.carried_item_weight(item),
+        };
+        self.can_carry(load, added)
+    }
+
+    /// Completing a trade: the receiver gets `received` and gives up `given`
+    pub fn can_receive_trade(&self, load: &CharacterLoad, received: &[TradeItem], given: &[TradeItem]) -> bool {
+        let weight = |items: &[TradeItem]| -> u64 {
+            items.iter().map(|i| self.item_weight(i.item_type_id, i.count as u32)).sum()
+        };
+        self.can_carry(load, weight(received).saturating_sub(weight(given)))
+    }
+
+    /// Receiving items bought on the market directly into the inventory
+    pub fn can_receive_market_item(&self, load: &CharacterLoad, item_type_id: u16, amount: u32) -> bool {
+        self.can_carry(load, self.item_weight(item_type_id, amount))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trade::{Trade, TradeError, TradeState};
+    use uuid::Uuid;
+
+    const BACKPACK: u16 = 2854;
+    const CROSSBOW: u16 = 3349;
+    const PLATE_ARMOR: u16 = 3357;
+    const GOLD_COIN: u16 = 3031;
+    const GOLD_POUCH: u16 = 23721;
+
+    fn service() -> CapacityService {
+        CapacityService::new(CapacityConfig::default(), &ItemLoader::new())
+            .with_weight(BACKPACK, 1800)
+            .with_weight(CROSSBOW, 4000)
+            .with_weight(PLATE_ARMOR, 12000)
+            .with_weight(GOLD_COIN, 10)
+            .with_weight(GOLD_POUCH, 500)
+    }
+
+    fn load(max_capacity: u32) -> CharacterLoad {
+        CharacterLoad {
+            max_capacity,
+            equipment: vec![
+                CarriedItem::new(PLATE_ARMOR, 1),
+                CarriedItem::container(BACKPACK, vec![CarriedItem::new(CROSSBOW, 1)]),
+                CarriedItem::container(GOLD_POUCH, vec![CarriedItem::new(GOLD_COIN, 100)]),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_pickup_rejected_by_capacity() {
+        let capacity = service();
+        let load = load(200);
+
+        // Equipped and contained items count, gold pouch contents don't
+        assert_eq!(capacity.carried_weight(&load), 12000 + 1800 + 4000 + 500);
+        assert_eq!(capacity.free_capacity(&load), 1700);
+
+        let armor = CarriedItem::new(PLATE_ARMOR, 1);
+        assert!(!capacity.can_pick_up(&load, &armor));
+        assert!(capacity.can_pick_up(&load, &CarriedItem::new(GOLD_COIN, 100)));
+
+        // Moving within the inventory or into the gold pouch costs nothing
+        assert!(capacity.can_move(&load, &armor, ItemLocation::Inventory, ItemLocation::Inventory));
+        let coins = CarriedItem::new(GOLD_COIN, 10_000);
+        assert!(!capacity.can_move(&load, &coins, ItemLocation::Ground, ItemLocation::Inventory));
+        assert!(capacity.can_move(&load, &coins, ItemLocation::Ground, ItemLocation::WeightlessContainer));
+
+        let unlimited = CapacityService::new(
+            CapacityConfig { enabled: false, ..Default::default() },
+            &ItemLoader::new(),
+        );
+        assert!(unlimited.can_pick_up(&load, &armor));
+    }
+
+    #[test]
+    fn test_trade_over_encumbering_receiver_rejected() {
+        let capacity = service();
+        let (p1, p2) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut trade = Trade::new(p1, p2);
+        trade.state = TradeState::Active;
+        trade.add_item(p1, TradeItem::new(1, PLATE_ARMOR, 1)).unwrap();
+        trade.add_item(p2, TradeItem::new(2, GOLD_COIN, 100)).unwrap();
+        trade.accept(p1).unwrap();
+        trade.accept(p2).unwrap();
+
+        // p2 can't take the armor; giving away the coins doesn't free enough
+        let (strong, weak) = (load(1000), load(200));
+        assert!(!capacity.can_receive_trade(&weak, &trade.player1_items, &trade.player2_items));
+        assert!(matches!(
+            trade.complete_checked(&capacity, &strong, &weak),
+            Err(TradeError::InsufficientCapacity(id)) if id == p2
+        ));
+        assert_eq!(trade.state, TradeState::Accepted);
+
+        trade.complete_checked(&capacity, &strong, &strong).unwrap();
+        assert_eq!(trade.state, TradeState::Completed);
+    }
+}
@@ -0,0 +1,401 @@
+//! GM Compensation Grants
+//!
+//! Lets support staff send items and coins to a player as compensation.
+//! Every grant needs a reason, counts against the acting staff member's
+//! daily limits, is delivered to the target's depot and bank in a single
+//! store transaction together with its audit record, and is only counted
+//! once that transaction succeeded. `DbGrantStore` is that store on the
+//! game database.
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use shadow_db::models::CompensationGrant;
+use shadow_db::repositories::CharacterRepository;
+use shadow_db::DatabasePool;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::CoreError;
+
+/// Per-actor daily grant limits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrantLimits {
+    /// Maximum number of grants per day
+    pub max_grants_per_day: u32,
+    /// Maximum coins granted per day
+    pub max_coins_per_day: u64,
+    /// Maximum item count granted per day
+    pub max_items_per_day: u32,
+}
+
+impl Default for GrantLimits {
+    fn default() -> Self {
+        Self {
+            max_grants_per_day: 20,
+            max_coins_per_day: 1_000_000,
+            max_items_per_day: 100,
+        }
+    }
+}
+
+/// An item stack to grant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GrantItem {
+    pub item_type_id: u16,
+    pub count: u32,
+}
+
+/// A grant requested by a staff member
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrantRequest {
+    /// Staff member making the grant
+    pub actor: Uuid,
+    /// Character receiving the grant
+    pub target: Uuid,
+    pub items: Vec<GrantItem>,
+    pub coins: u64,
+    pub reason: String,
+}
+
+impl GrantRequest {
+    pub fn new(actor: Uuid, target: Uuid, reason: impl Into<String>) -> Self {
+        Self {
+            actor,
+            target,
+            items: Vec::new(),
+            coins: 0,
+            reason: reason.into(),
+        }
+    }
+
+    pub fn with_item(mut self, item_type_id: u16, count: u32) -> Self {
+        self.items.push(GrantItem { item_type_id, count });
+        self
+    }
+
+    pub fn with_coins(mut self, coins: u64) -> Self {
+        self.coins = coins;
+        self
+    }
+}
+
+/// Audit record of a delivered grant
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrantAuditRecord {
+    pub id: Uuid,
+    /// Staff member who made the grant
+    pub actor_id: Uuid,
+    /// Character receiving the grant
+    pub target_id: Uuid,
+    /// Items delivered to the depot
+    pub items: Vec<GrantItem>,
+    /// Coins delivered to the bank
+    pub coins: u64,
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl GrantAuditRecord {
+    /// Total items granted, `None` if the count overflows
+    pub fn item_count(&self) -> Option<u32> {
+        self.items.iter().try_fold(0u32, |total, i| total.checked_add(i.count))
+    }
+}
+
+/// Persistence for grants
+#[async_trait]
+pub trait GrantStore: Send + Sync {
+    /// Whether the character exists
+    async fn character_exists(&self, character_id: Uuid) -> crate::Result<bool>;
+
+    /// Deliver the items to the target's depot and the coins to its bank
+    /// account, and write the audit record, all in one transaction
+    async fn deliver(&self, record: &GrantAuditRecord) -> crate::Result<()>;
+}
+
+/// Grant store on the game database
+pub struct DbGrantStore {
+    db: DatabasePool,
+}
+
+impl DbGrantStore {
+    pub fn new(db: DatabasePool) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl GrantStore for DbGrantStore {
+    async fn character_exists(&self, character_id: Uuid) -> crate::Result<bool> {
+        Ok(CharacterRepository::new(self.db.postgres()).exists(character_id).await?)
+    }
+
+    async fn deliver(&self, record: &GrantAuditRecord) -> crate::Result<()> {
+        let too_large = || CoreError::InvalidOperation("Grant amount out of range".to_string());
+        let items = record
+            .items
+            .iter()
+            .map(|i| -> crate::Result<(i32, i32)> {
+                Ok((i32::from(i.item_type_id), i32::try_from(i.count).map_err(|_| too_large())?))
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+        let grant = CompensationGrant {
+            id: record.id,
+            actor_id: record.actor_id,
+            character_id: record.target_id,
+            items,
+            coins: i64::try_from(record.coins).map_err(|_| too_large())?,
+            reason: record.reason.clone(),
+            created_at: record.created_at,
+        };
+        CharacterRepository::new(self.db.postgres()).deliver_grant(&grant).await?;
+        Ok(())
+    }
+}
+
+/// Which daily limit a grant would exceed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrantLimit {
+    Grants,
+    Coins,
+    Items,
+}
+
+/// Grant errors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GrantError {
+    /// Neither items nor coins were given
+    EmptyGrant,
+    /// A reason is required for the audit trail
+    MissingReason,
+    /// Target character does not exist
+    TargetNotFound(Uuid),
+    /// The grant would exceed the actor's daily limit
+    DailyLimitExceeded(GrantLimit),
+    /// The store failed; nothing was delivered
+    Delivery(String),
+}
+
+impl std::fmt::Display for GrantError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GrantError::EmptyGrant => write!(f, "Grant contains no items or coins"),
+            GrantError::MissingReason => write!(f, "A reason is required"),
+            GrantError::TargetNotFound(id) => write!(f, "Character {} not found", id),
+            GrantError::DailyLimitExceeded(limit) => write!(f, "Daily {:?} limit exceeded", limit),
+            GrantError::Delivery(e) => write!(f, "Delivery failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for GrantError {}
+
+/// Amounts an actor granted on one day
+#[derive(Debug, Clone, Copy, Default)]
+struct DailyUsage {
+    grants: u32,
+    coins: u64,
+    items: u32,
+}
+
+/// Rate-limited, audited compensation grants
+pub struct CompensationService {
+    limits: GrantLimits,
+    usage: HashMap<(Uuid, NaiveDate), DailyUsage>,
+}
+
+impl CompensationService {
+    pub fn new(limits: GrantLimits) -> Self {
+        Self {
+            limits,
+            usage: HashMap::new(),
+        }
+    }
+
+    /// Send items and coins to a character
+    pub async fn grant(
+        &mut self,
+        store: &dyn GrantStore,
+        request: GrantRequest,
+        now: DateTime<Utc>,
+    ) -> Result<GrantAuditRecord, GrantError> {
+        let GrantRequest { actor, target, items, coins, reason } = request;
+        let items: Vec<GrantItem> = items.into_iter().filter(|i| i.count > 0).collect();
+        if items.is_empty() && coins == 0 {
+            return Err(GrantError::EmptyGrant);
+        }
+        if reason.trim().is_empty() {
+            return Err(GrantError::MissingReason);
+        }
+
+        let record = GrantAuditRecord {
+            id: Uuid::new_v4(),
+            actor_id: actor,
+            target_id: target,
+            items,
+            coins,
+            reason: reason.trim().to_string(),
+            created_at: now,
+        };
+
+        // Totals that overflow exceed any limit
+        let day = (actor, now.date_naive());
+        let usage = self.usage.get(&day).copied().unwrap_or_default();
+        let grants = usage.grants.checked_add(1).filter(|&n| n <= self.limits.max_grants_per_day);
+        let Some(grants) = grants else {
+            return Err(GrantError::DailyLimitExceeded(GrantLimit::Grants));
+        };
+        let coins_used = usage.coins.checked_add(coins).filter(|&n| n <= self.limits.max_coins_per_day);
+        let Some(coins_used) = coins_used else {
+            return Err(GrantError::DailyLimitExceeded(GrantLimit::Coins));
+        };
+        let item_count = record.item_count();
+        let items_used = item_count
+            .and_then(|count| usage.items.checked_add(count))
+            .filter(|&n| n <= self.limits.max_items_per_day);
+        let (Some(item_count), Some(items_used)) = (item_count, items_used) else {
+            return Err(GrantError::DailyLimitExceeded(GrantLimit::Items));
+        };
+
+        match store.character_exists(target).await {
+            Ok(true) => {}
+            Ok(false) => return Err(GrantError::TargetNotFound(target)),
+            Err(e) => return Err(GrantError::Delivery(e.to_string())),
+        }
+
+        store.deliver(&record).await.map_err(|e| GrantError::Delivery(e.to_string()))?;
+
+        self.usage.insert(day, DailyUsage { grants, coins: coins_used, items: items_used });
+
+        tracing::info!(
+            "GM {} granted {} coins and {} items to {}: {}",
+            actor, coins, item_count, target, record.reason
+        );
+        Ok(record)
+    }
+
+    /// Coins an actor can still grant today
+    pub fn remaining_coins(&self, actor: Uuid, now: DateTime<Utc>) -> u64 {
+        let used = self.usage.get(&(actor, now.date_naive())).map(|u| u.coins).unwrap_or(0);
+        self.limits.max_coins_per_day.saturating_sub(used)
+    }
+
+    /// Drop usage from previous days
+    pub fn cleanup(&mut self, now: DateTime<Utc>) {
+        let today = now.date_naive();
+        self.usage.retain(|(_, day), _| *day >= today);
+    }
+}
+
+impl Default for CompensationService {
+    fn default() -> Self {
+        Self::new(GrantLimits::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MemoryStore {
+        characters: HashSet<Uuid>,
+        audit: Mutex<Vec<GrantAuditRecord>>,
+    }
+
+    #[async_trait]
+    impl GrantStore for MemoryStore {
+        async fn character_exists(&self, character_id: Uuid) -> crate::Result<bool> {
+            Ok(self.characters.contains(&character_id))
+        }
+
+        async fn deliver(&self, record: &GrantAuditRecord) -> crate::Result<()> {
+            self.audit.lock().unwrap().push(record.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_grant_writes_audit() {
+        let mut service = CompensationService::default();
+        let (gm, player) = (Uuid::new_v4(), Uuid::new_v4());
+        let store = MemoryStore { characters: HashSet::from([player]), ..Default::default() };
+        let now = Utc::now();
+        let request = GrantRequest::new(gm, player, "Lost items in server crash")
+            .with_item(3043, 10)
+            .with_coins(50_000);
+
+        let record = service.grant(&store, request, now).await.unwrap();
+
+        {
+            let audit = store.audit.lock().unwrap();
+            assert_eq!(audit.len(), 1);
+            assert_eq!(audit[0].id, record.id);
+            assert_eq!((audit[0].actor_id, audit[0].target_id), (gm, player));
+            assert_eq!(audit[0].items, vec![GrantItem { item_type_id: 3043, count: 10 }]);
+            assert_eq!(audit[0].coins, 50_000);
+            assert_eq!(audit[0].reason, "Lost items in server crash");
+        }
+        assert_eq!(service.remaining_coins(gm, now), 950_000);
+
+        // Unknown targets are rejected before anything is delivered
+        let missing = Uuid::new_v4();
+        let request = GrantRequest::new(gm, missing, "Refund").with_coins(100);
+        assert_eq!(
+            service.grant(&store, request, now).await.unwrap_err(),
+            GrantError::TargetNotFound(missing)
+        );
+        assert_eq!(store.audit.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_over_limit_grant_rejected() {
+        let mut service = CompensationService::new(GrantLimits { max_coins_per_day: 100_000, ..Default::default() });
+        let (gm, player) = (Uuid::new_v4(), Uuid::new_v4());
+        let store = MemoryStore { characters: HashSet::from([player]), ..Default::default() };
+        let now = Utc::now();
+
+        let refund = |actor: Uuid, coins: u64| GrantRequest::new(actor, player, "Refund").with_coins(coins);
+
+        service.grant(&store, refund(gm, 80_000), now).await.unwrap();
+        assert_eq!(
+            service.grant(&store, refund(gm, 30_000), now).await.unwrap_err(),
+            GrantError::DailyLimitExceeded(GrantLimit::Coins)
+        );
+        assert_eq!(store.audit.lock().unwrap().len(), 1);
+
+        // Limits are per actor and per day
+        service.grant(&store, refund(Uuid::new_v4(), 30_000), now).await.unwrap();
+        let tomorrow = now + chrono::Duration::days(1);
+        service.grant(&store, refund(gm, 30_000), tomorrow).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_overflowing_grant_rejected() {
+        let mut service = CompensationService::new(GrantLimits {
+            max_coins_per_day: u64::MAX,
+            max_items_per_day: u32::MAX,
+            ..Default::default()
+        });
+        let (gm, player) = (Uuid::new_v4(), Uuid::new_v4());
+        let store = MemoryStore { characters: HashSet::from([player]), ..Default::default() };
+        let now = Utc::now();
+
+        service.grant(&store, GrantRequest::new(gm, player, "Refund").with_coins(10), now).await.unwrap();
+        let wrapping = GrantRequest::new(gm, player, "Refund").with_coins(u64::MAX);
+        assert_eq!(
+            service.grant(&store, wrapping, now).await.unwrap_err(),
+            GrantError::DailyLimitExceeded(GrantLimit::Coins)
+        );
+
+        let wrapping = GrantRequest::new(gm, player, "Lost items").with_item(3043, u32::MAX).with_item(3043, 2);
+        assert_eq!(
+            service.grant(&store, wrapping, now).await.unwrap_err(),
+            GrantError::DailyLimitExceeded(GrantLimit::Items)
+        );
+        assert_eq!(store.audit.lock().unwrap().len(), 1);
+    }
+}
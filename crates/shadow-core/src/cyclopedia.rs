@@ -380,6 +380,71 @@ pub struct CharmAssignment {
     pub assigned_at: DateTime<Utc>,
 }
 
+/// Kills needed for bestiary stages 1-4 by creature occurrence. The last
+/// stage completes the entry and awards its charm points.
+pub fn bestiary_thresholds(occurrence: &str) -> [u32; 4] {
+    match occurrence.to_lowercase().as_str() {
+        "uncommon" => [5, 100, 250, 500],
+        "rare" => [1, 25, 50, 100],
+        "very rare" => [1, 5, 10, 25],
+        _ => [5, 250, 500, 1000],
+    }
+}
+
+/// Bestiary stage (0-4) reached with `kills`
+pub fn bestiary_stage(kills: u32, occurrence: &str) -> u8 {
+    bestiary_thresholds(occurrence).iter().filter(|&&t| kills >= t).count() as u8
+}
+
+/// A creature's bestiary progress, as input to charm recommendations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BestiaryProgress {
+    pub race_id: u16,
+    pub kills: u32,
+    pub occurrence: String,
+    /// Charm points awarded when the entry completes
+    pub charm_points: u32,
+}
+
+/// A creature worth hunting for the bestiary
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CharmRecommendation {
+    pub race_id: u16,
+    pub stage: u8,
+    pub kills_to_next_stage: u32,
+    pub kills_to_complete: u32,
+    /// Charm points per remaining kill until completion
+    pub charm_points_per_kill: f64,
+}
+
+/// Rank unfinished bestiary entries by how close they are to their next
+/// stage; ties go to the entry paying the most charm points per kill
+pub fn recommend_charm_targets(progress: &[BestiaryProgress], limit: usize) -> Vec<CharmRecommendation> {
+    let mut recommendations: Vec<CharmRecommendation> = progress
+        .iter()
+        .filter_map(|entry| {
+            let thresholds = bestiary_thresholds(&entry.occurrence);
+            let next = thresholds.iter().find(|&&t| entry.kills < t)?;
+            let kills_to_complete = thresholds[3] - entry.kills;
+            Some(CharmRecommendation {
+                race_id: entry.race_id,
+                stage: bestiary_stage(entry.kills, &entry.occurrence),
+                kills_to_next_stage: next - entry.kills,
+                kills_to_complete,
+                charm_points_per_kill: entry.charm_points as f64 / kills_to_complete as f64,
+            })
+        })
+        .collect();
+
+    recommendations.sort_by(|a, b| {
+        a.kills_to_next_stage
+            .cmp(&b.kills_to_next_stage)
+            .then(b.charm_points_per_kill.total_cmp(&a.charm_points_per_kill))
+    });
+    recommendations.truncate(limit);
+    recommendations
+}
+
 /// Map exploration tracking
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MapExploration {
@@ -763,6 +828,30 @@ mod tests {
         assert_eq!(cyclo.monsters.unique_monsters_killed(), 2);
     }
 
+    #[test]
+    fn test_charm_recommendations_ordered_by_kills_to_next_stage() {
+        let entry = |race_id, kills, occurrence: &str, charm_points| BestiaryProgress {
+            race_id,
+            kills,
+            occurrence: occurrence.to_string(),
+            charm_points,
+        };
+        let progress = vec![
+            entry(1, 240, "common", 25),    // 10 kills to stage 2
+            entry(2, 3, "very rare", 100),  // 2 kills to stage 2
+            entry(3, 1000, "common", 25),   // completed
+            entry(4, 90, "uncommon", 15),   // 10 kills to stage 2, fewer to complete
+            entry(5, 0, "rare", 50),        // 1 kill to stage 1
+        ];
+
+        let recommended: Vec<u16> = recommend_charm_targets(&progress, 10).iter().map(|r| r.race_id).collect();
+        assert_eq!(recommended, vec![5, 2, 4, 1]);
+
+        let top = &recommend_charm_targets(&progress, 1)[0];
+        assert_eq!((top.stage, top.kills_to_next_stage, top.kills_to_complete), (0, 1, 100));
+        assert_eq!(bestiary_stage(240, "common"), 1);
+    }
+
     #[test]
     fn test_badge_collection() {
         let mut badges = BadgeCollection::default();
@@ -6,9 +6,16 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use shadow_combat::{LevelTable, RulesetFlags};
+use shadow_db::models::CharacterDeath;
 use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
+use crate::bank::{BankManager, TransactionType};
+
+/// Item id of the Amulet of Loss
+pub const AMULET_OF_LOSS: u32 = 3057;
+
 /// Types of blessings available
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum BlessingType {
@@ -108,6 +115,21 @@ pub enum DeathType {
     Unknown,
 }
 
+impl DeathType {
+    /// Name stored in the death log
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeathType::Monster => "monster",
+            DeathType::Player => "player",
+            DeathType::Environment => "environment",
+            DeathType::Trap => "trap",
+            DeathType::Condition => "condition",
+            DeathType::Suicide => "suicide",
+            DeathType::Unknown => "unknown",
+        }
+    }
+}
+
 /// Player's blessing status
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlayerBlessings {
@@ -215,6 +237,34 @@ pub struct DeathRecord {
     pub unjustified: bool,
 }
 
+impl DeathRecord {
+    /// Death log row for persistence
+    pub fn to_character_death(&self, realm_id: Uuid) -> CharacterDeath {
+        let is_player = self.death_type == DeathType::Player;
+        let (mostdamage_by, mostdamage_is_player) = match self.participants.first() {
+            Some((_, name)) => (name.clone(), true),
+            None => (self.killer_name.clone(), is_player),
+        };
+        CharacterDeath {
+            id: self.id,
+            character_id: self.character_id,
+            realm_id,
+            level: self.level as i32,
+            killed_by: self.killer_name.clone(),
+            is_player,
+            mostdamage_by,
+            mostdamage_is_player,
+            unjustified: self.unjustified,
+            pos_x: self.location.0,
+            pos_y: self.location.1,
+            pos_z: self.location.2,
+            died_at: self.timestamp,
+            death_type: self.death_type.as_str().to_string(),
+            experience_lost: self.experience_lost.min(i64::MAX as u64) as i64,
+        }
+    }
+}
+
 /// Death penalty calculator
 pub struct DeathPenalty {
     /// Base experience loss percentage (0-100)
@@ -227,6 +277,8 @@ pub struct DeathPenalty {
     pub container_drop_chance: f64,
     /// Amulet of loss protection
     pub aol_protection: bool,
+    /// Experience loss is taken from total experience (classic rules)
+    pub classic_exp_loss: bool,
 }
 
 impl DeathPenalty {
@@ -238,7 +290,23 @@ impl DeathPenalty {
         is_vip: bool,
         vip_reduction: f64,
     ) -> Self {
-        let blessing_count = blessings.standard_blessing_count();
+        Self::calculate_with_rules(level, blessings, death_type, is_vip, vip_reduction, &RulesetFlags::modern())
+    }
+
+    /// Calculate death penalty under a realm's ruleset
+    pub fn calculate_with_rules(
+        level: u32,
+        blessings: &PlayerBlessings,
+        death_type: DeathType,
+        is_vip: bool,
+        vip_reduction: f64,
+        rules: &RulesetFlags,
+    ) -> Self {
+        let blessing_count = if rules.no_blessings {
+            0
+        } else {
+            blessings.standard_blessing_count()
+        };
         
         // Base penalties
         let mut exp_loss = 10.0; // 10% base
@@ -281,7 +349,7 @@ impl DeathPenalty {
         }
         
         // Twist of Fate check
-        let aol = blessings.has_blessing(BlessingType::TwistOfFate);
+        let aol = !rules.no_blessings && blessings.has_blessing(BlessingType::TwistOfFate);
         
         Self {
             exp_loss_percent: exp_loss.max(0.0).min(10.0),
@@ -289,14 +357,25 @@ impl DeathPenalty {
             item_drop_chance: item_drop.max(0.0).min(10.0),
             container_drop_chance: container_drop.max(0.0).min(10.0),
             aol_protection: aol,
+            classic_exp_loss: rules.classic_exp_loss,
         }
     }
 
     /// Calculate actual experience loss
     pub fn calculate_exp_loss(&self, current_exp: u64, level: u32) -> u64 {
+        self.calculate_exp_loss_with_table(current_exp, level, &LevelTable::Retail)
+    }
+
+    /// Calculate actual experience loss under a realm's level table
+    pub fn calculate_exp_loss_with_table(&self, current_exp: u64, level: u32, table: &LevelTable) -> u64 {
+        if self.classic_exp_loss {
+            // Classic rules: lose a percentage of all experience, possibly several levels
+            return (current_exp as f64 * self.exp_loss_percent / 100.0) as u64;
+        }
+
         // Calculate experience for current level
-        let level_exp = Self::experience_for_level(level);
-        let prev_level_exp = Self::experience_for_level(level.saturating_sub(1));
+        let level_exp = table.experience_for_level(level);
+        let prev_level_exp = table.experience_for_level(level.saturating_sub(1));
         let level_progress = current_exp.saturating_sub(prev_level_exp);
         
         // Lose percentage of progress in current level
@@ -306,13 +385,7 @@ impl DeathPenalty {
 
     /// Calculate experience required for a level
     fn experience_for_level(level: u32) -> u64 {
-        if level <= 1 {
-            return 0;
-        }
-        // Tibia's formula: 50/3 * (L^3 - 6L^2 + 17L - 12)
-        let l = level as i64;
-        let exp = 50 * (l.pow(3) - 6 * l.pow(2) + 17 * l - 12) / 3;
-        exp.max(0) as u64
+        LevelTable::Retail.experience_for_level(level)
     }
 
     /// Calculate skill loss for a skill
@@ -321,6 +394,46 @@ impl DeathPenalty {
     }
 }
 
+/// Where items looted from a PvP victim go
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PvpLootDestination {
+    /// Straight into the killer's inventory
+    Killer,
+    /// On the ground where the victim died
+    Ground,
+}
+
+/// PvP looting on hardcore realms
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PvpLootConfig {
+    /// Killers loot their victims on this realm
+    pub enabled: bool,
+    /// Chance (0.0 - 1.0) for each unprotected item to be looted; blessings
+    /// lower it the same way they lower the item drop chance
+    pub fraction: f64,
+    pub destination: PvpLootDestination,
+}
+
+impl Default for PvpLootConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            fraction: 0.5,
+            destination: PvpLootDestination::Killer,
+        }
+    }
+}
+
+/// Items taken from a PvP victim
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PvpLoot {
+    /// Looted items (item_id, count)
+    pub items: Vec<(u32, u32)>,
+    /// Character receiving the items; None when they went to the ground
+    pub recipient: Option<Uuid>,
+}
+
 /// Death manager handles all death-related operations
 pub struct DeathManager {
     /// Death history by character
@@ -331,6 +444,18 @@ pub struct DeathManager {
     recent_kills: HashMap<Uuid, Vec<(Uuid, DateTime<Utc>)>>,
     /// Max deaths to keep in history per player
     max_history: usize,
+    /// Realm ruleset
+    rules: RulesetFlags,
+    /// Realm level table
+    level_table: LevelTable,
+    /// Buy missing blessings from the bank on login
+    auto_bless: bool,
+    /// Whether an equipped Amulet of Loss protects items
+    aol_enabled: bool,
+    /// Deaths not yet written to the death log
+    unsaved: Vec<DeathRecord>,
+    /// PvP looting rules
+    pvp_loot: PvpLootConfig,
 }
 
 impl DeathManager {
@@ -341,9 +466,52 @@ impl DeathManager {
             blessings: HashMap::new(),
             recent_kills: HashMap::new(),
             max_history: 100,
+            rules: RulesetFlags::modern(),
+            level_table: LevelTable::Retail,
+            auto_bless: false,
+            aol_enabled: true,
+            unsaved: Vec::new(),
+            pvp_loot: PvpLootConfig::default(),
         }
     }
 
+    /// Create a death manager for a realm ruleset
+    pub fn with_rules(rules: RulesetFlags) -> Self {
+        Self {
+            rules,
+            ..Self::new()
+        }
+    }
+
+    /// Use a realm's level table for experience loss
+    pub fn with_level_table(mut self, table: LevelTable) -> Self {
+        self.level_table = table;
+        self
+    }
+
+    /// Enable buying missing blessings on login
+    pub fn with_auto_bless(mut self, enabled: bool) -> Self {
+        self.auto_bless = enabled;
+        self
+    }
+
+    /// Enable or disable Amulet of Loss protection
+    pub fn with_aol(mut self, enabled: bool) -> Self {
+        self.aol_enabled = enabled;
+        self
+    }
+
+    /// Let killers loot their PvP victims
+    pub fn with_pvp_loot(mut self, config: PvpLootConfig) -> Self {
+        self.pvp_loot = config;
+        self
+    }
+
+    /// Active ruleset
+    pub fn rules(&self) -> &RulesetFlags {
+        &self.rules
+    }
+
     /// Get or create player blessings
     pub fn get_blessings(&mut self, character_id: Uuid) -> &PlayerBlessings {
         self.blessings.entry(character_id)
@@ -363,6 +531,10 @@ impl DeathManager {
         blessing: BlessingType,
         level: u32,
     ) -> Result<u64, DeathError> {
+        if self.rules.no_blessings {
+            return Err(DeathError::BlessingNotAvailable);
+        }
+
         let cost = blessing.base_cost(level);
         let player = self.get_blessings_mut(character_id);
         
@@ -374,6 +546,41 @@ impl DeathManager {
         Ok(cost)
     }
 
+    /// Buy the standard blessings a character is missing, paid from their
+    /// bank account. Called on login when auto-bless is enabled; nothing is
+    /// bought unless the account can pay for all of them.
+    pub fn auto_bless(
+        &mut self,
+        character_id: Uuid,
+        level: u32,
+        bank: &mut BankManager,
+    ) -> Result<Vec<BlessingType>, DeathError> {
+        if !self.auto_bless || self.rules.no_blessings {
+            return Ok(Vec::new());
+        }
+
+        let player = self.get_blessings(character_id);
+        let missing: Vec<BlessingType> = BlessingType::standard_blessings()
+            .iter()
+            .copied()
+            .filter(|b| !player.has_blessing(*b))
+            .collect();
+        if missing.is_empty() {
+            return Ok(missing);
+        }
+
+        let total: u64 = missing.iter().map(|b| b.base_cost(level)).sum();
+        let description = format!("Auto-bless: {} blessings", missing.len());
+        bank.deduct_for_purchase(character_id, total, TransactionType::Blessing, &description)
+            .map_err(|_| DeathError::InsufficientFunds)?;
+
+        let player = self.get_blessings_mut(character_id);
+        for blessing in &missing {
+            player.add_blessing(*blessing, blessing.base_cost(level));
+        }
+        Ok(missing)
+    }
+
     /// Process a death
     pub fn process_death(
         &mut self,
@@ -389,7 +596,11 @@ impl DeathManager {
         is_vip: bool,
         vip_reduction: f64,
     ) -> DeathResult {
-        let blessings = self.get_blessings(character_id).clone();
+        let blessings = if self.rules.no_blessings {
+            PlayerBlessings::new(character_id)
+        } else {
+            self.get_blessings(character_id).clone()
+        };
         
         // Check for Twist of Fate (chance to avoid death)
         if blessings.has_blessing(BlessingType::TwistOfFate) {
@@ -405,20 +616,22 @@ impl DeathManager {
                     blessings_consumed: vec![BlessingType::TwistOfFate],
                     respawn_location: location,
                     penalty_applied: false,
+                    aol_consumed: false,
                 };
             }
         }
         
         // Calculate penalty
-        let penalty = DeathPenalty::calculate(
+        let penalty = DeathPenalty::calculate_with_rules(
             level,
             &blessings,
             death_type,
             is_vip,
             vip_reduction,
+            &self.rules,
         );
         
-        let experience_lost = penalty.calculate_exp_loss(current_exp, level);
+        let experience_lost = penalty.calculate_exp_loss_with_table(current_exp, level, &self.level_table);
         
         // Create death record
         let death_record = DeathRecord {
@@ -440,6 +653,7 @@ impl DeathManager {
         };
         
         // Record death
+        self.unsaved.push(death_record.clone());
         self.death_history.entry(character_id)
             .or_insert_with(Vec::new)
             .push(death_record);
@@ -469,13 +683,131 @@ impl DeathManager {
             death_avoided: false,
             experience_lost,
             skills_lost: HashMap::new(), // Would be populated with actual skill losses
-            items_dropped: Vec::new(), // Would be populated with actual drops
+            items_dropped: Vec::new(), // Filled in by apply_item_loss
             blessings_consumed: consumed,
             respawn_location: respawn,
             penalty_applied: true,
+            aol_consumed: false,
         }
     }
 
+    /// Drop equipment for a processed death. `equipment` holds the
+    /// equipped (item_id, count) pairs and loses whatever is dropped. An
+    /// equipped Amulet of Loss is consumed first and keeps everything else.
+    pub fn apply_item_loss(
+        &self,
+        result: &mut DeathResult,
+        level: u32,
+        death_type: DeathType,
+        equipment: &mut Vec<(u32, u32)>,
+    ) {
+        self.apply_item_loss_with(result, level, death_type, equipment, rand::random::<f64>)
+    }
+
+    fn apply_item_loss_with(
+        &self,
+        result: &mut DeathResult,
+        level: u32,
+        death_type: DeathType,
+        equipment: &mut Vec<(u32, u32)>,
+        mut roll: impl FnMut() -> f64,
+    ) {
+        if result.death_avoided {
+            return;
+        }
+
+        if self.aol_enabled && !self.rules.no_blessings {
+            if let Some(index) = equipment.iter().position(|(id, _)| *id == AMULET_OF_LOSS) {
+                equipment.remove(index);
+                result.aol_consumed = true;
+                return;
+            }
+        }
+
+        // Blessings were cleared by the death; rebuild what was active
+        let mut blessings = PlayerBlessings::new(Uuid::nil());
+        for blessing in &result.blessings_consumed {
+            blessings.add_blessing(*blessing, 0);
+        }
+        let penalty = DeathPenalty::calculate_with_rules(level, &blessings, death_type, false, 0.0, &self.rules);
+
+        equipment.retain(|item| {
+            let dropped = roll() * 100.0 < penalty.item_drop_chance;
+            if dropped {
+                result.items_dropped.push(*item);
+            }
+            !dropped
+        });
+    }
+
+    /// Loot a PvP victim on realms with PvP looting. `items` holds the
+    /// victim's droppable (item_id, count) pairs and loses whatever is
+    /// looted. An Amulet of Loss - equipped, or already used up by
+    /// `apply_item_loss` - protects everything, and blessings lower the
+    /// chance of each item being taken. Items sent to the ground are also
+    /// added to the result's dropped items.
+    pub fn apply_pvp_loot(
+        &self,
+        result: &mut DeathResult,
+        level: u32,
+        death_type: DeathType,
+        killer_id: Option<Uuid>,
+        items: &mut Vec<(u32, u32)>,
+    ) -> PvpLoot {
+        self.apply_pvp_loot_with(result, level, death_type, killer_id, items, rand::random::<f64>)
+    }
+
+    fn apply_pvp_loot_with(
+        &self,
+        result: &mut DeathResult,
+        level: u32,
+        death_type: DeathType,
+        killer_id: Option<Uuid>,
+        items: &mut Vec<(u32, u32)>,
+        mut roll: impl FnMut() -> f64,
+    ) -> PvpLoot {
+        let Some(killer) = killer_id else {
+            return PvpLoot::default();
+        };
+        if !self.pvp_loot.enabled || death_type != DeathType::Player || result.death_avoided || result.aol_consumed {
+            return PvpLoot::default();
+        }
+
+        if self.aol_enabled && !self.rules.no_blessings {
+            if let Some(index) = items.iter().position(|(id, _)| *id == AMULET_OF_LOSS) {
+                items.remove(index);
+                result.aol_consumed = true;
+                return PvpLoot::default();
+            }
+        }
+
+        let mut blessings = PlayerBlessings::new(Uuid::nil());
+        for blessing in &result.blessings_consumed {
+            blessings.add_blessing(*blessing, 0);
+        }
+        let penalty = DeathPenalty::calculate_with_rules(level, &blessings, death_type, false, 0.0, &self.rules);
+        // The unblessed item drop chance is 10%
+        let chance = self.pvp_loot.fraction.clamp(0.0, 1.0) * penalty.item_drop_chance / 10.0;
+
+        let mut looted = Vec::new();
+        items.retain(|item| {
+            let taken = roll() < chance;
+            if taken {
+                looted.push(*item);
+            }
+            !taken
+        });
+
+        let recipient = match self.pvp_loot.destination {
+            PvpLootDestination::Killer => Some(killer),
+            PvpLootDestination::Ground => {
+                result.items_dropped.extend_from_slice(&looted);
+                None
+            }
+        };
+        PvpLoot { items: looted, recipient }
+    }
+
     /// Get respawn location for character
     fn get_respawn_location(
         &self,
@@ -503,6 +835,25 @@ impl DeathManager {
             .unwrap_or(&[])
     }
 
+    /// Take the deaths recorded since the last call, for the death log
+    pub fn take_unsaved_deaths(&mut self) -> Vec<DeathRecord> {
+        std::mem::take(&mut self.unsaved)
+    }
+
+    /// Record an unjustified kill for the skull system. Everyone who
+    /// took part - the killer and every assist - is charged with the frag.
+    pub fn record_unjustified_kill(&mut self, victim_id: Uuid, killer_id: Option<Uuid>, assist_ids: &[Uuid]) {
+        let now = Utc::now();
+        for participant in killer_id.iter().chain(assist_ids) {
+            if *participant == victim_id {
+                continue;
+            }
+            self.recent_kills.entry(*participant)
+                .or_default()
+                .push((victim_id, now));
+        }
+    }
+
     /// Get recent kills by a character (for skull tracking)
     pub fn get_recent_kills(&self, character_id: Uuid, hours: i64) -> Vec<Uuid> {
         let cutoff = Utc::now() - chrono::Duration::hours(hours);
@@ -528,7 +879,7 @@ impl DeathManager {
         let kills_week = self.count_unjustified_kills(character_id, 168);
         let kills_month = self.count_unjustified_kills(character_id, 720);
         
-        if kills_month >= 20 {
+        if kills_month >= 20 && !self.rules.classic_skulls {
             SkullType::Black
         } else if kills_week >= 10 {
             SkullType::Red
@@ -565,6 +916,8 @@ pub struct DeathResult {
     pub respawn_location: (i32, i32, i32),
     /// Was penalty applied (false if protected)
     pub penalty_applied: bool,
+    /// An equipped Amulet of Loss was used up instead of dropping items
+    pub aol_consumed: bool,
 }
 
 /// Skull types for PvP tracking
@@ -661,4 +1014,215 @@ mod tests {
         // 1 kill = white skull
         assert_eq!(manager.get_skull_type(killer), SkullType::White);
     }
+
+    #[test]
+    fn test_assists_share_unjustified_kill() {
+        let mut manager = DeathManager::new();
+        let (killer, assist, victim) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+        manager.record_unjustified_kill(victim, Some(killer), &[assist]);
+        assert_eq!(manager.get_skull_type(killer), SkullType::White);
+        assert_eq!(manager.get_skull_type(assist), SkullType::White);
+        assert_eq!(manager.get_skull_type(victim), SkullType::None);
+    }
+
+    #[test]
+    fn test_classic_exp_loss() {
+        let blessings = PlayerBlessings::new(Uuid::new_v4());
+        let current_exp = DeathPenalty::experience_for_level(100) + 1000;
+
+        let modern = DeathPenalty::calculate(100, &blessings, DeathType::Monster, false, 0.0);
+        let classic = DeathPenalty::calculate_with_rules(
+            100,
+            &blessings,
+            DeathType::Monster,
+            false,
+            0.0,
+            &RulesetFlags::retro(),
+        );
+
+        // Modern loses a share of the current level's progress, classic of all experience
+        assert!(modern.calculate_exp_loss(current_exp, 100) < current_exp / 100);
+        assert_eq!(classic.calculate_exp_loss(current_exp, 100), current_exp / 10);
+    }
+
+    #[test]
+    fn test_no_blessings_ruleset() {
+        let player = Uuid::new_v4();
+        let mut modern = DeathManager::new();
+        let mut retro = DeathManager::with_rules(RulesetFlags { no_blessings: true, ..Default::default() });
+
+        for b in BlessingType::standard_blessings() {
+            modern.purchase_blessing(player, *b, 100).unwrap();
+            assert!(matches!(
+                retro.purchase_blessing(player, *b, 100),
+                Err(DeathError::BlessingNotAvailable)
+            ));
+        }
+
+        // Blessings granted some other way are ignored on death
+        for b in BlessingType::standard_blessings() {
+            retro.get_blessings_mut(player).add_blessing(*b, 0);
+        }
+
+        let exp = DeathPenalty::experience_for_level(100) + 1000;
+        let die = |manager: &mut DeathManager| {
+            manager.process_death(
+                player, "Player", 100, exp, DeathType::Monster, "a dragon", None,
+                (100, 100, 7), (50, 50, 7), false, 0.0,
+            )
+        };
+        let blessed = die(&mut modern);
+        let unblessed = die(&mut retro);
+        assert!(unblessed.experience_lost > blessed.experience_lost);
+    }
+
+    #[test]
+    fn test_auto_bless_buys_only_missing() {
+        let player = Uuid::new_v4();
+        let mut bank = BankManager::new();
+        let mut manager = DeathManager::new().with_auto_bless(true);
+
+        manager.purchase_blessing(player, BlessingType::WisdomOfSolitude, 100).unwrap();
+        manager.purchase_blessing(player, BlessingType::FireOfTheSuns, 100).unwrap();
+
+        // Three blessings are missing; the account can't pay for them yet
+        let cost = BlessingType::SparkOfThePhoenix.base_cost(100) * 3;
+        bank.deposit(player, cost - 1).unwrap();
+        assert!(matches!(manager.auto_bless(player, 100, &mut bank), Err(DeathError::InsufficientFunds)));
+        assert_eq!(manager.get_blessings(player).standard_blessing_count(), 2);
+
+        bank.deposit(player, 1).unwrap();
+        let bought = manager.auto_bless(player, 100, &mut bank).unwrap();
+        assert_eq!(bought.len(), 3);
+        assert!(!bought.contains(&BlessingType::WisdomOfSolitude));
+        assert!(manager.get_blessings(player).has_all_standard());
+        assert_eq!(bank.get_balance(player), 0);
+
+        // Nothing left to buy
+        assert!(manager.auto_bless(player, 100, &mut bank).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_aol_prevents_equipment_drop() {
+        let player = Uuid::new_v4();
+        let mut manager = DeathManager::new();
+        let die = |manager: &mut DeathManager| {
+            manager.process_death(
+                player, "Player", 100, 1_000_000, DeathType::Monster, "a dragon", None,
+                (100, 100, 7), (50, 50, 7), false, 0.0,
+            )
+        };
+
+        let armor = (3366, 1);
+        let mut equipment = vec![armor, (AMULET_OF_LOSS, 1), (3031, 100)];
+        let mut result = die(&mut manager);
+        manager.apply_item_loss_with(&mut result, 100, DeathType::Monster, &mut equipment, || 0.0);
+        assert!(result.aol_consumed);
+        assert!(result.items_dropped.is_empty());
+        assert_eq!(equipment, vec![armor, (3031, 100)]);
+
+        // Without the amulet every roll drops
+        let mut result = die(&mut manager);
+        manager.apply_item_loss_with(&mut result, 100, DeathType::Monster, &mut equipment, || 0.0);
+        assert!(!result.aol_consumed);
+        assert_eq!(result.items_dropped, vec![armor, (3031, 100)]);
+        assert!(equipment.is_empty());
+    }
+
+    #[test]
+    fn test_death_is_queued_for_the_death_log() {
+        let player = Uuid::new_v4();
+        let realm = Uuid::new_v4();
+        let mut manager = DeathManager::new();
+        manager.process_death(
+            player, "Player", 120, 5_000_000, DeathType::Monster, "a demon", None,
+            (120, 130, 8), (50, 50, 7), false, 0.0,
+        );
+
+        let deaths = manager.take_unsaved_deaths();
+        assert_eq!(deaths.len(), 1);
+        assert!(manager.take_unsaved_deaths().is_empty());
+        // The in-memory history is kept
+        assert_eq!(manager.get_death_history(player).len(), 1);
+
+        let row = deaths[0].to_character_death(realm);
+        assert_eq!(row.character_id, player);
+        assert_eq!(row.realm_id, realm);
+        assert_eq!(row.level, 120);
+        assert_eq!(row.killed_by, "a demon");
+        assert!(!row.is_player);
+        assert_eq!(row.death_type, "monster");
+        assert_eq!(row.experience_lost as u64, deaths[0].experience_lost);
+        assert_eq!((row.pos_x, row.pos_y, row.pos_z), (120, 130, 8));
+    }
+
+    #[test]
+    fn test_pvp_kill_loots_victim_on_enabled_realm() {
+        let (victim, killer) = (Uuid::new_v4(), Uuid::new_v4());
+        let config = PvpLootConfig { enabled: true, ..Default::default() };
+        let mut manager = DeathManager::new().with_pvp_loot(config.clone());
+        let die = |manager: &mut DeathManager| {
+            manager.process_death(
+                victim, "Victim", 100, 1_000_000, DeathType::Player, "Killer", Some(killer),
+                (100, 100, 7), (50, 50, 7), false, 0.0,
+            )
+        };
+
+        let mut items = vec![(3366, 1), (3031, 100)];
+        let mut result = die(&mut manager);
+        let loot = manager.apply_pvp_loot_with(&mut result, 100, DeathType::Player, Some(killer), &mut items, || 0.0);
+        assert_eq!(loot, PvpLoot { items: vec![(3366, 1), (3031, 100)], recipient: Some(killer) });
+        assert!(items.is_empty());
+        assert!(result.items_dropped.is_empty());
+
+        // An Amulet of Loss keeps everything
+        let mut items = vec![(3366, 1), (AMULET_OF_LOSS, 1)];
+        let mut result = die(&mut manager);
+        let loot = manager.apply_pvp_loot_with(&mut result, 100, DeathType::Player, Some(killer), &mut items, || 0.0);
+        assert!(loot.items.is_empty());
+        assert!(result.aol_consumed);
+        assert_eq!(items, vec![(3366, 1)]);
+
+        // So do full blessings
+        for blessing in BlessingType::standard_blessings() {
+            manager.get_blessings_mut(victim).add_blessing(*blessing, 0);
+        }
+        let mut result = die(&mut manager);
+        let loot = manager.apply_pvp_loot_with(&mut result, 100, DeathType::Player, Some(killer), &mut items, || 0.0);
+        assert!(loot.items.is_empty());
+        assert_eq!(items, vec![(3366, 1)]);
+
+        // Looting to the ground leaves the items with the corpse
+        let manager = DeathManager::new()
+            .with_pvp_loot(PvpLootConfig { destination: PvpLootDestination::Ground, ..config });
+        let mut result = DeathManager::new().process_death(
+            victim, "Victim", 100, 1_000_000, DeathType::Player, "Killer", Some(killer),
+            (100, 100, 7), (50, 50, 7), false, 0.0,
+        );
+        let loot = manager.apply_pvp_loot_with(&mut result, 100, DeathType::Player, Some(killer), &mut items, || 0.0);
+        assert_eq!(loot.recipient, None);
+        assert_eq!(result.items_dropped, vec![(3366, 1)]);
+    }
+
+    #[test]
+    fn test_pvp_kill_keeps_items_on_pve_realm() {
+        let (victim, killer) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut manager = DeathManager::new();
+        let mut result = manager.process_death(
+            victim, "Victim", 100, 1_000_000, DeathType::Player, "Killer", Some(killer),
+            (100, 100, 7), (50, 50, 7), false, 0.0,
+        );
+
+        let mut items = vec![(3366, 1), (3031, 100)];
+        let loot = manager.apply_pvp_loot_with(&mut result, 100, DeathType::Player, Some(killer), &mut items, || 0.0);
+        assert_eq!(loot, PvpLoot::default());
+        assert_eq!(items.len(), 2);
+
+        // Monster kills are never looted, even where PvP looting is on
+        let manager = DeathManager::new().with_pvp_loot(PvpLootConfig { enabled: true, ..Default::default() });
+        let loot = manager.apply_pvp_loot_with(&mut result, 100, DeathType::Monster, None, &mut items, || 0.0);
+        assert!(loot.items.is_empty());
+        assert_eq!(items.len(), 2);
+    }
 }
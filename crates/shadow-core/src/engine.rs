@@ -2,16 +2,26 @@
 //!
 //! Manages the game loop, coordinates all subsystems, and handles game state updates.
 
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use shadow_db::repositories::MarketRepository;
+use shadow_db::DatabasePool;
 use tokio::sync::{broadcast, mpsc, RwLock};
 use tokio::time::interval;
 
 use crate::events::{GameEvent, RealmStatus};
+use crate::player::PlayerManager;
+use crate::regeneration::RegenerationSystem;
+use crate::scheduler::{ScheduledTask, Scheduler, TaskType};
 use crate::state::GameState;
+use crate::telemetry::{EventSink, TelemetryExporter, TelemetryHandle};
 use crate::{RealmId, ServerConfig, SharedState, TICK_RATE_MS};
 
+/// How often expired market offers are swept
+const MARKET_EXPIRY_INTERVAL: Duration = Duration::from_secs(60);
+
 /// Command sent to the game engine
 #[derive(Debug)]
 pub enum EngineCommand {
@@ -33,6 +43,10 @@ pub struct GameEngine {
     running: Arc<RwLock<bool>>,
     tick_count: u64,
     last_save: Instant,
+    scheduler: Scheduler,
+    db: Option<DatabasePool>,
+    players: Option<Arc<RwLock<PlayerManager>>>,
+    regeneration: RegenerationSystem,
 }
 
 impl GameEngine {
@@ -50,9 +64,30 @@ impl GameEngine {
             running: Arc::new(RwLock::new(false)),
             tick_count: 0,
             last_save: Instant::now(),
+            scheduler: Scheduler::default(),
+            db: None,
+            players: None,
+            regeneration: RegenerationSystem::default(),
         }
     }
 
+    /// Run database tasks (market expiry) against `db`
+    pub fn with_database(mut self, db: DatabasePool) -> Self {
+        self.db = Some(db);
+        self
+    }
+
+    /// Run per-player systems (regeneration) on the players of `players`
+    pub fn with_players(mut self, players: Arc<RwLock<PlayerManager>>) -> Self {
+        self.players = Some(players);
+        self
+    }
+
+    /// Scheduler whose due tasks run on the game loop
+    pub fn scheduler(&self) -> &Scheduler {
+        &self.scheduler
+    }
+
     /// Get a command sender for external control
     pub fn command_sender(&self) -> mpsc::Sender<EngineCommand> {
         self.command_tx.clone()
@@ -68,6 +103,13 @@ impl GameEngine {
         self.event_tx.clone()
     }
 
+    /// Export game events to `sink` if telemetry is enabled in the config
+    pub fn start_telemetry(&self, sink: Arc<dyn EventSink>) -> Option<TelemetryHandle> {
+        let mut handle = TelemetryExporter::new(self.config.monitoring.telemetry.clone(), sink).start()?;
+        handle.forward(self.event_subscriber());
+        Some(handle)
+    }
+
     /// Start the game engine main loop
     pub async fn run(&mut self) -> crate::Result<()> {
         tracing::info!("Starting Shadow OT game engine");
@@ -80,6 +122,17 @@ impl GameEngine {
         let mut tick_interval = interval(Duration::from_millis(TICK_RATE_MS));
         let save_interval = Duration::from_secs(self.config.server.save_interval_minutes as u64 * 60);
 
+        if self.config.features.market {
+            self.scheduler
+                .schedule_recurring(
+                    "expire-market-offers",
+                    MARKET_EXPIRY_INTERVAL,
+                    MARKET_EXPIRY_INTERVAL,
+                    TaskType::ExpireMarketOffers,
+                )
+                .await;
+        }
+
         loop {
             tokio::select! {
                 _ = tick_interval.tick() => {
@@ -132,6 +185,15 @@ impl GameEngine {
     async fn tick(&mut self) -> crate::Result<()> {
         self.tick_count += 1;
 
+        for task in self.scheduler.process_due().await {
+            self.run_task(task).await;
+        }
+
+        if self.tick_count % 20 == 0 {
+            // Every second (20 ticks)
+            self.process_regeneration().await?;
+        }
+
         let mut state = self.state.write().await;
 
         // Update all realms
@@ -143,11 +205,6 @@ impl GameEngine {
         }
 
         // Process global systems
-        if self.tick_count % 20 == 0 {
-            // Every second (20 ticks)
-            self.process_regeneration(&mut state).await?;
-        }
-
         if self.tick_count % 100 == 0 {
             // Every 5 seconds
             self.process_creature_ai(&mut state).await?;
@@ -162,6 +219,27 @@ impl GameEngine {
         Ok(())
     }
 
+    /// Run a scheduled task that came due
+    async fn run_task(&self, task: ScheduledTask) {
+        match task.task_type {
+            TaskType::ExpireMarketOffers => self.expire_market_offers().await,
+            _ => tracing::debug!("No handler for scheduled task '{}'", task.name),
+        }
+    }
+
+    /// Expire offers past their duration, returning their items and gold
+    async fn expire_market_offers(&self) {
+        let Some(db) = &self.db else {
+            return;
+        };
+        match MarketRepository::new(db.postgres()).expire_offers().await {
+            Ok(0) => {}
+            Ok(expired) => tracing::info!("Expired {} market offers", expired),
+            // Rolled back as a whole; the next run retries
+            Err(e) => tracing::error!("Failed to expire market offers: {}", e),
+        }
+    }
+
     async fn process_realm_tick(&self, _realm: &mut RealmState) -> crate::Result<()> {
         // Process player movements
         // Process combat
@@ -171,10 +249,29 @@ impl GameEngine {
         Ok(())
     }
 
-    async fn process_regeneration(&self, _state: &mut GameState) -> crate::Result<()> {
-        // Health regeneration
-        // Mana regeneration
-        // Soul regeneration
+    async fn process_regeneration(&mut self) -> crate::Result<()> {
+        let Some(players) = self.players.clone() else {
+            return Ok(());
+        };
+        let now_ms = self.tick_count * TICK_RATE_MS;
+        let players = players.read().await.get_all_players();
+
+        // Health, mana and soul
+        let mut online = HashSet::with_capacity(players.len());
+        for player in players {
+            let mut player = player.write().await;
+            let (character_id, vocation) = (player.character_id, player.vocation);
+            online.insert(character_id);
+            // The engine holds no map yet, so no tile counts as a protection zone
+            let tick = self.regeneration.regenerate(character_id, vocation, &mut player.creature, false, now_ms);
+            if !tick.is_empty() {
+                if let Err(e) = player.send_stats().await {
+                    tracing::debug!("Failed to send stats to {}: {}", player.name, e);
+                }
+            }
+        }
+        self.regeneration.retain(|character_id| online.contains(&character_id));
+
         // Stamina updates
         Ok(())
     }
@@ -140,6 +140,9 @@ pub struct PvPKillEvent {
     pub killer_name: String,
     pub victim_id: CharacterId,
     pub victim_name: String,
+    /// Players credited with an assist
+    #[serde(default)]
+    pub assist_ids: Vec<CharacterId>,
     pub was_justified: bool,
     pub skull_type: Option<SkullType>,
     pub position: Position,
@@ -422,6 +425,9 @@ pub enum AdminAction {
     ModifyCharacter,
     ServerShutdown,
     ServerRestart,
+    Broadcast,
+    SaveAll,
+    ReloadConfig,
     RealmCreate,
     RealmDelete,
     RealmModify,
@@ -1,13 +1,15 @@
 //! Guild System
 //!
-//! Handles guild management, ranks, members, wars, and guild halls.
+//! Handles guild management, ranks, members, wars, alliances, and guild halls.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use shadow_world::house::HouseManager;
+use shadow_world::position::Position;
 
 /// Guild rank permissions (bitmask)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -26,6 +28,7 @@ impl GuildPermissions {
     pub const MANAGE_HALL: u32 = 1 << 8;
     pub const PROMOTE: u32 = 1 << 9;
     pub const DISBAND: u32 = 1 << 10;
+    pub const ALLIANCE: u32 = 1 << 11;
 
     pub const LEADER: u32 = 0xFFFFFFFF; // All permissions
     pub const VICE_LEADER: u32 = Self::INVITE | Self::KICK | Self::EDIT_MOTD | Self::WAR_ACCEPT | Self::PROMOTE;
@@ -265,6 +268,96 @@ impl GuildWar {
     }
 }
 
+/// Guilds sharing an alliance channel and a non-aggression pact
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuildAlliance {
+    /// Alliance ID
+    pub id: Uuid,
+    /// Member guilds, founders first
+    pub guild_ids: Vec<Uuid>,
+    /// Formation date
+    pub formed_at: DateTime<Utc>,
+}
+
+impl GuildAlliance {
+    pub fn new(founder_id: Uuid, partner_id: Uuid) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            guild_ids: vec![founder_id, partner_id],
+            formed_at: Utc::now(),
+        }
+    }
+
+    pub fn contains(&self, guild_id: Uuid) -> bool {
+        self.guild_ids.contains(&guild_id)
+    }
+}
+
+/// Alliance proposal from one guild to another
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AllianceProposal {
+    pub from_guild: Uuid,
+    pub to_guild: Uuid,
+    pub proposed_at: DateTime<Utc>,
+}
+
+/// Guildhall rules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GuildhallConfig {
+    /// Days covered by each rent payment (the purchase covers the first)
+    pub rent_period_days: u32,
+}
+
+impl Default for GuildhallConfig {
+    fn default() -> Self {
+        Self { rent_period_days: 30 }
+    }
+}
+
+/// A guildhall door limited to higher ranks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuildhallDoor {
+    pub position: Position,
+    /// Highest rank level allowed through (1 = leader)
+    pub max_rank_level: u8,
+}
+
+/// A guild's hold on its guildhall
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Guildhall {
+    pub house_id: u32,
+    /// Rent taken from the guild bank every period
+    pub rent: u64,
+    pub paid_until: DateTime<Utc>,
+    /// Highest rank level allowed into the hall, 0 for every member
+    pub entry_rank_level: u8,
+    pub doors: Vec<GuildhallDoor>,
+}
+
+impl Guildhall {
+    /// Whether a member of `rank_level` may pass the door at `pos`
+    pub fn admits(&self, rank_level: u8, pos: &Position) -> bool {
+        let allowed = |max: u8| max == 0 || rank_level <= max;
+        allowed(self.entry_rank_level)
+            && self
+                .doors
+                .iter()
+                .filter(|d| d.position == *pos)
+                .all(|d| allowed(d.max_rank_level))
+    }
+}
+
+/// Outcome of a guildhall rent collection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuildhallRent {
+    NotDue,
+    /// Rent taken from the guild bank
+    Paid(u64),
+    /// The bank could not cover the rent and the guild lost this house
+    Evicted(u32),
+}
+
 /// A guild
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Guild {
@@ -288,12 +381,18 @@ pub struct Guild {
     pub balance: u64,
     /// Guild hall house ID
     pub guild_hall_id: Option<u32>,
+    /// Rent and rank access of the guild hall
+    #[serde(default)]
+    pub hall: Option<Guildhall>,
     /// Guild logo (sprite ID or custom)
     pub logo: Option<u16>,
     /// Active wars
     pub wars: Vec<GuildWar>,
     /// Pending invites
     pub invites: Vec<GuildInvite>,
+    /// Realm the guild was founded on
+    #[serde(default)]
+    pub realm_id: Option<Uuid>,
 }
 
 impl Guild {
@@ -310,9 +409,11 @@ impl Guild {
             members: HashMap::new(),
             balance: 0,
             guild_hall_id: None,
+            hall: None,
             logo: None,
             wars: Vec::new(),
             invites: Vec::new(),
+            realm_id: None,
         };
 
         // Create default ranks
@@ -468,6 +569,97 @@ impl Guild {
     pub fn cleanup_invites(&mut self) {
         self.invites.retain(|i| !i.is_expired());
     }
+
+    /// Buy a free guildhall with the guild bank. The price covers the
+    /// first rent period. Returns the amount debited.
+    pub fn buy_guildhall(
+        &mut self,
+        buyer_id: Uuid,
+        houses: &mut HouseManager,
+        house_id: u32,
+        config: &GuildhallConfig,
+        now: DateTime<Utc>,
+    ) -> Result<u64, GuildError> {
+        if !self.has_permission(buyer_id, GuildPermissions::MANAGE_HALL) {
+            return Err(GuildError::NoPermission);
+        }
+        if self.hall.is_some() {
+            return Err(GuildError::AlreadyOwnsHall);
+        }
+        let house = houses.get_mut(house_id).ok_or(GuildError::HallNotAvailable)?;
+        if !house.guildhall || house.has_owner() || house.is_auction() {
+            return Err(GuildError::HallNotAvailable);
+        }
+        if house.price > self.balance {
+            return Err(GuildError::InsufficientFunds);
+        }
+
+        let paid_until = now + chrono::Duration::days(config.rent_period_days as i64);
+        self.balance -= house.price;
+        house.owner_guild = Some(self.id);
+        house.paid_until = Some(paid_until.timestamp());
+        self.guild_hall_id = Some(house_id);
+        self.hall = Some(Guildhall {
+            house_id,
+            rent: house.rent,
+            paid_until,
+            entry_rank_level: 0,
+            doors: Vec::new(),
+        });
+        Ok(house.price)
+    }
+
+    /// Limit the guildhall door at `pos` to `rank_id` and above. Without a
+    /// position the limit applies to the whole hall.
+    pub fn set_hall_door_rank(&mut self, requester_id: Uuid, pos: Option<Position>, rank_id: u32) -> Result<(), GuildError> {
+        if !self.has_permission(requester_id, GuildPermissions::MANAGE_HALL) {
+            return Err(GuildError::NoPermission);
+        }
+        let level = self.get_rank(rank_id).ok_or(GuildError::InvalidRank)?.level;
+        let hall = self.hall.as_mut().ok_or(GuildError::NoHall)?;
+        match pos {
+            None => hall.entry_rank_level = level,
+            Some(position) => match hall.doors.iter_mut().find(|d| d.position == position) {
+                Some(door) => door.max_rank_level = level,
+                None => hall.doors.push(GuildhallDoor { position, max_rank_level: level }),
+            },
+        }
+        Ok(())
+    }
+
+    /// Check whether a player may open the guildhall door at `pos`
+    pub fn can_open_hall_door(&self, player_id: Uuid, pos: &Position) -> bool {
+        match (&self.hall, self.get_member_rank(player_id)) {
+            (Some(hall), Some(rank)) => hall.admits(rank.level, pos),
+            _ => false,
+        }
+    }
+
+    /// Take the guildhall rent from the guild bank once it is due. A guild
+    /// that cannot pay is evicted and the house goes back on the market.
+    pub fn pay_hall_rent(&mut self, houses: &mut HouseManager, config: &GuildhallConfig, now: DateTime<Utc>) -> GuildhallRent {
+        let Some(hall) = self.hall.as_mut() else {
+            return GuildhallRent::NotDue;
+        };
+        if now < hall.paid_until {
+            return GuildhallRent::NotDue;
+        }
+
+        let house_id = hall.house_id;
+        if hall.rent > self.balance {
+            houses.remove_ownership(house_id);
+            self.hall = None;
+            self.guild_hall_id = None;
+            return GuildhallRent::Evicted(house_id);
+        }
+
+        self.balance -= hall.rent;
+        hall.paid_until += chrono::Duration::days(config.rent_period_days as i64);
+        if let Some(house) = houses.get_mut(house_id) {
+            house.paid_until = Some(hall.paid_until.timestamp());
+        }
+        GuildhallRent::Paid(hall.rent)
+    }
 }
 
 /// Guild manager
@@ -475,6 +667,12 @@ pub struct GuildManager {
     guilds: HashMap<Uuid, Arc<RwLock<Guild>>>,
     /// Player ID -> Guild ID mapping
     player_guilds: HashMap<Uuid, Uuid>,
+    alliances: HashMap<Uuid, GuildAlliance>,
+    /// Guild ID -> Alliance ID mapping
+    guild_alliances: HashMap<Uuid, Uuid>,
+    alliance_proposals: Vec<AllianceProposal>,
+    /// Realms whose guilds may ally across realms
+    cross_realm_alliances: HashSet<Uuid>,
 }
 
 impl GuildManager {
@@ -482,6 +680,10 @@ impl GuildManager {
         Self {
             guilds: HashMap::new(),
             player_guilds: HashMap::new(),
+            alliances: HashMap::new(),
+            guild_alliances: HashMap::new(),
+            alliance_proposals: Vec::new(),
+            cross_realm_alliances: HashSet::new(),
         }
     }
 
@@ -543,10 +745,204 @@ impl GuildManager {
             }
         }
 
+        self.leave_alliance(guild_id);
+        self.alliance_proposals.retain(|p| p.from_guild != guild_id && p.to_guild != guild_id);
         self.guilds.remove(&guild_id);
         Ok(())
     }
 
+    /// Set whether a realm's guilds may ally with guilds on other realms
+    pub fn set_cross_realm_alliances(&mut self, realm_id: Uuid, allowed: bool) {
+        if allowed {
+            self.cross_realm_alliances.insert(realm_id);
+        } else {
+            self.cross_realm_alliances.remove(&realm_id);
+        }
+    }
+
+    /// Propose an alliance from `from_guild` to `to_guild`
+    pub async fn propose_alliance(&mut self, from_guild: Uuid, to_guild: Uuid, requester_id: Uuid) -> Result<(), GuildError> {
+        if from_guild == to_guild || self.are_allied(from_guild, to_guild) {
+            return Err(GuildError::AlreadyAllied);
+        }
+        let from = self.guilds.get(&from_guild).ok_or(GuildError::NotFound)?.clone();
+        let to = self.guilds.get(&to_guild).ok_or(GuildError::NotFound)?.clone();
+        let (from, to) = (from.read().await, to.read().await);
+
+        if !from.has_permission(requester_id, GuildPermissions::ALLIANCE) {
+            return Err(GuildError::NoPermission);
+        }
+        if from.is_at_war_with(to_guild) || to.is_at_war_with(from_guild) {
+            return Err(GuildError::AtWar);
+        }
+        if from.realm_id != to.realm_id {
+            let allowed = |realm: Option<Uuid>| realm.is_some_and(|id| self.cross_realm_alliances.contains(&id));
+            if !allowed(from.realm_id) || !allowed(to.realm_id) {
+                return Err(GuildError::CrossRealmDisabled);
+            }
+        }
+
+        self.alliance_proposals.retain(|p| !(p.from_guild == from_guild && p.to_guild == to_guild));
+        self.alliance_proposals.push(AllianceProposal {
+            from_guild,
+            to_guild,
+            proposed_at: Utc::now(),
+        });
+        Ok(())
+    }
+
+    /// Accept a pending alliance proposal. The accepting guild joins the
+    /// proposer's alliance, or both found a new one.
+    pub async fn accept_alliance(&mut self, guild_id: Uuid, from_guild: Uuid, requester_id: Uuid) -> Result<Uuid, GuildError> {
+        let guild = self.guilds.get(&guild_id).ok_or(GuildError::NotFound)?;
+        if !guild.read().await.has_permission(requester_id, GuildPermissions::ALLIANCE) {
+            return Err(GuildError::NoPermission);
+        }
+        let index = self.alliance_proposals.iter()
+            .position(|p| p.from_guild == from_guild && p.to_guild == guild_id)
+            .ok_or(GuildError::NoAllianceProposal)?;
+        if self.guild_alliances.contains_key(&guild_id) {
+            return Err(GuildError::AlreadyAllied);
+        }
+        self.alliance_proposals.remove(index);
+
+        let alliance_id = match self.guild_alliances.get(&from_guild) {
+            Some(&id) => {
+                if let Some(alliance) = self.alliances.get_mut(&id) {
+                    alliance.guild_ids.push(guild_id);
+                }
+                id
+            }
+            None => {
+                let alliance = GuildAlliance::new(from_guild, guild_id);
+                let id = alliance.id;
+                self.guild_alliances.insert(from_guild, id);
+                self.alliances.insert(id, alliance);
+                id
+            }
+        };
+        self.guild_alliances.insert(guild_id, alliance_id);
+        Ok(alliance_id)
+    }
+
+    /// Dissolve the alliance of a guild, releasing every member guild
+    pub async fn dissolve_alliance(&mut self, guild_id: Uuid, requester_id: Uuid) -> Result<(), GuildError> {
+        let guild = self.guilds.get(&guild_id).ok_or(GuildError::NotFound)?;
+        if !guild.read().await.has_permission(requester_id, GuildPermissions::ALLIANCE) {
+            return Err(GuildError::NoPermission);
+        }
+        let alliance_id = self.guild_alliances.get(&guild_id).copied().ok_or(GuildError::NotAllied)?;
+        if let Some(alliance) = self.alliances.remove(&alliance_id) {
+            for id in &alliance.guild_ids {
+                self.guild_alliances.remove(id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove a guild from its alliance; an alliance of one is dissolved
+    fn leave_alliance(&mut self, guild_id: Uuid) {
+        let Some(alliance_id) = self.guild_alliances.remove(&guild_id) else {
+            return;
+        };
+        if let Some(alliance) = self.alliances.get_mut(&alliance_id) {
+            alliance.guild_ids.retain(|id| *id != guild_id);
+            if alliance.guild_ids.len() < 2 {
+                for id in &alliance.guild_ids {
+                    self.guild_alliances.remove(id);
+                }
+                self.alliances.remove(&alliance_id);
+            }
+        }
+    }
+
+    /// Get a guild's alliance
+    pub fn get_alliance(&self, guild_id: Uuid) -> Option<&GuildAlliance> {
+        self.guild_alliances.get(&guild_id).and_then(|id| self.alliances.get(id))
+    }
+
+    /// Check if two guilds share an alliance
+    pub fn are_allied(&self, guild_a: Uuid, guild_b: Uuid) -> bool {
+        match (self.guild_alliances.get(&guild_a), self.guild_alliances.get(&guild_b)) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    /// Guilds on the same side as `guild_id`: itself and its allies
+    fn side_of(&self, guild_id: Uuid) -> Vec<Uuid> {
+        self.get_alliance(guild_id)
+            .map(|alliance| alliance.guild_ids.clone())
+            .unwrap_or_else(|| vec![guild_id])
+    }
+
+    /// Online members of every guild in the sender's alliance, i.e. who
+    /// receives a message on the alliance channel
+    pub async fn alliance_chat_recipients(&self, sender_id: Uuid) -> Result<Vec<Uuid>, GuildError> {
+        let guild_id = *self.player_guilds.get(&sender_id).ok_or(GuildError::NotMember)?;
+        let alliance = self.get_alliance(guild_id).ok_or(GuildError::NotAllied)?;
+
+        let mut recipients = Vec::new();
+        for id in &alliance.guild_ids {
+            if let Some(guild) = self.guilds.get(id) {
+                let guild = guild.read().await;
+                recipients.extend(guild.members.values().filter(|m| m.online).map(|m| m.player_id));
+            }
+        }
+        Ok(recipients)
+    }
+
+    /// Score a player kill in any active war between the killer's and the
+    /// victim's sides. Guildmates and allies are never hostile, so their
+    /// kills score nothing. Returns the war that was scored.
+    pub async fn record_war_kill(&mut self, killer_id: Uuid, victim_id: Uuid) -> Option<Uuid> {
+        let killer_guild = *self.player_guilds.get(&killer_id)?;
+        let victim_guild = *self.player_guilds.get(&victim_id)?;
+        if killer_guild == victim_guild || self.are_allied(killer_guild, victim_guild) {
+            return None;
+        }
+
+        let (killers, victims) = (self.side_of(killer_guild), self.side_of(victim_guild));
+        let mut scored = None;
+        for guild_id in killers.iter().chain(&victims) {
+            let Some(guild) = self.guilds.get(guild_id) else {
+                continue;
+            };
+            let mut guild = guild.write().await;
+            for war in guild.wars.iter_mut().filter(|w| w.is_active()) {
+                if scored.is_some_and(|id| id != war.id) {
+                    continue;
+                }
+                if killers.contains(&war.attacker_id) && victims.contains(&war.defender_id) {
+                    war.attacker_kills += 1;
+                } else if killers.contains(&war.defender_id) && victims.contains(&war.attacker_id) {
+                    war.defender_kills += 1;
+                } else {
+                    continue;
+                }
+                scored = Some(war.id);
+            }
+        }
+        scored
+    }
+
+    /// Collect guildhall rent from every guild that holds a hall
+    pub async fn collect_guildhall_rent(
+        &self,
+        houses: &mut HouseManager,
+        config: &GuildhallConfig,
+        now: DateTime<Utc>,
+    ) -> Vec<(Uuid, GuildhallRent)> {
+        let mut results = Vec::new();
+        for (&guild_id, guild) in &self.guilds {
+            let mut guild = guild.write().await;
+            if guild.hall.is_some() {
+                results.push((guild_id, guild.pay_hall_rent(houses, config, now)));
+            }
+        }
+        results
+    }
+
     /// Add player to guild mapping
     pub fn add_player_mapping(&mut self, player_id: Uuid, guild_id: Uuid) {
         self.player_guilds.insert(player_id, guild_id);
@@ -584,6 +980,14 @@ pub enum GuildError {
     InvalidRank,
     NotMember,
     InsufficientFunds,
+    AlreadyAllied,
+    NotAllied,
+    NoAllianceProposal,
+    AtWar,
+    CrossRealmDisabled,
+    AlreadyOwnsHall,
+    HallNotAvailable,
+    NoHall,
 }
 
 impl std::fmt::Display for GuildError {
@@ -596,6 +1000,14 @@ impl std::fmt::Display for GuildError {
             GuildError::InvalidRank => write!(f, "Invalid rank"),
             GuildError::NotMember => write!(f, "Player is not a member"),
             GuildError::InsufficientFunds => write!(f, "Insufficient guild funds"),
+            GuildError::AlreadyAllied => write!(f, "Guilds are already allied"),
+            GuildError::NotAllied => write!(f, "Guild is not in an alliance"),
+            GuildError::NoAllianceProposal => write!(f, "No pending alliance proposal"),
+            GuildError::AtWar => write!(f, "Guilds are at war"),
+            GuildError::CrossRealmDisabled => write!(f, "Cross-realm alliances are disabled"),
+            GuildError::AlreadyOwnsHall => write!(f, "Guild already owns a guildhall"),
+            GuildError::HallNotAvailable => write!(f, "Guildhall is not available"),
+            GuildError::NoHall => write!(f, "Guild has no guildhall"),
         }
     }
 }
@@ -667,4 +1079,165 @@ mod tests {
         let result = manager.create_guild("Another", owner_id, "Leader").await;
         assert!(matches!(result, Err(GuildError::AlreadyInGuild)));
     }
+
+    async fn guild_with_member(manager: &mut GuildManager, name: &str) -> (Uuid, Uuid, Uuid) {
+        let (leader, member) = (Uuid::new_v4(), Uuid::new_v4());
+        let guild_id = manager.create_guild(name, leader, "Leader").await.unwrap();
+        let mut joined = GuildMember::new(member, "Member", 3);
+        joined.online = true;
+        manager.get(guild_id).unwrap().write().await.add_member(joined);
+        manager.add_player_mapping(member, guild_id);
+        (guild_id, leader, member)
+    }
+
+    #[tokio::test]
+    async fn test_alliance_formation_and_chat() {
+        let mut manager = GuildManager::new();
+        let (red, red_leader, red_member) = guild_with_member(&mut manager, "Red").await;
+        let (blue, blue_leader, blue_member) = guild_with_member(&mut manager, "Blue").await;
+
+        // Only leaders may propose, and accepting needs a proposal
+        assert!(matches!(manager.propose_alliance(red, blue, red_member).await, Err(GuildError::NoPermission)));
+        assert!(matches!(
+            manager.accept_alliance(blue, red, blue_leader).await,
+            Err(GuildError::NoAllianceProposal)
+        ));
+
+        manager.propose_alliance(red, blue, red_leader).await.unwrap();
+        let alliance_id = manager.accept_alliance(blue, red, blue_leader).await.unwrap();
+        assert!(manager.are_allied(red, blue));
+        assert_eq!(manager.get_alliance(red).unwrap().id, alliance_id);
+
+        // Alliance chat reaches online members of both guilds
+        manager.get(blue).unwrap().write().await.get_member_mut(blue_leader).unwrap().online = false;
+        let recipients = manager.alliance_chat_recipients(red_member).await.unwrap();
+        assert!(recipients.contains(&red_member) && recipients.contains(&blue_member));
+        assert!(recipients.contains(&red_leader) && !recipients.contains(&blue_leader));
+
+        // Guilds on different realms need both realms to allow it
+        let (green, green_leader, _) = guild_with_member(&mut manager, "Green").await;
+        let (realm_a, realm_b) = (Uuid::new_v4(), Uuid::new_v4());
+        manager.get(red).unwrap().write().await.realm_id = Some(realm_a);
+        manager.get(green).unwrap().write().await.realm_id = Some(realm_b);
+        manager.set_cross_realm_alliances(realm_a, true);
+        assert!(matches!(
+            manager.propose_alliance(green, red, green_leader).await,
+            Err(GuildError::CrossRealmDisabled)
+        ));
+        manager.set_cross_realm_alliances(realm_b, true);
+        manager.propose_alliance(green, red, green_leader).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_allies_do_not_score_war_frags() {
+        let mut manager = GuildManager::new();
+        let (red, red_leader, red_member) = guild_with_member(&mut manager, "Red").await;
+        let (blue, blue_leader, blue_member) = guild_with_member(&mut manager, "Blue").await;
+        let (black, _, black_member) = guild_with_member(&mut manager, "Black").await;
+
+        manager.propose_alliance(red, blue, red_leader).await.unwrap();
+        manager.accept_alliance(blue, red, blue_leader).await.unwrap();
+        let war_id = {
+            let guild = manager.get(red).unwrap();
+            let mut guild = guild.write().await;
+            guild.declare_war(black, 0, 7);
+            guild.wars[0].accept();
+            guild.wars[0].id
+        };
+
+        // Allies are never hostile to each other
+        assert_eq!(manager.record_war_kill(red_member, blue_member).await, None);
+        assert_eq!(manager.record_war_kill(blue_member, red_member).await, None);
+
+        // The ally fights the war on the alliance's side
+        assert_eq!(manager.record_war_kill(blue_member, black_member).await, Some(war_id));
+        assert_eq!(manager.record_war_kill(black_member, red_member).await, Some(war_id));
+        let war = manager.get(red).unwrap().read().await.wars[0].clone();
+        assert_eq!((war.attacker_kills, war.defender_kills), (1, 1));
+
+        // Once dissolved, the former ally no longer counts
+        manager.dissolve_alliance(blue, blue_leader).await.unwrap();
+        assert!(!manager.are_allied(red, blue));
+        assert!(manager.get_alliance(red).is_none());
+        assert_eq!(manager.record_war_kill(blue_member, black_member).await, None);
+    }
+
+    fn guildhall_houses() -> HouseManager {
+        let mut houses = HouseManager::new();
+        let mut hall = shadow_world::house::House::new(5, "Hill Hall".to_string());
+        hall.guildhall = true;
+        hall.price = 500_000;
+        hall.rent = 50_000;
+        houses.add_house(hall);
+        houses
+    }
+
+    #[test]
+    fn test_guildhall_purchase_debits_guild_bank() {
+        let leader = Uuid::new_v4();
+        let member = Uuid::new_v4();
+        let mut guild = Guild::new("Builders", leader, "Leader");
+        guild.add_member(GuildMember::new(member, "Member", 3));
+        let mut houses = guildhall_houses();
+        let config = GuildhallConfig::default();
+        let now = Utc::now();
+
+        assert!(matches!(
+            guild.buy_guildhall(leader, &mut houses, 5, &config, now),
+            Err(GuildError::InsufficientFunds)
+        ));
+        guild.balance = 600_000;
+        assert!(matches!(
+            guild.buy_guildhall(member, &mut houses, 5, &config, now),
+            Err(GuildError::NoPermission)
+        ));
+
+        assert_eq!(guild.buy_guildhall(leader, &mut houses, 5, &config, now).unwrap(), 500_000);
+        assert_eq!(guild.balance, 100_000);
+        assert_eq!(guild.guild_hall_id, Some(5));
+        assert_eq!(houses.get(5).unwrap().owner_guild, Some(guild.id));
+
+        // Taken halls cannot be bought again
+        let mut rival = Guild::new("Rivals", Uuid::new_v4(), "Rival");
+        rival.balance = 1_000_000;
+        assert!(matches!(
+            rival.buy_guildhall(rival.owner_id, &mut houses, 5, &config, now),
+            Err(GuildError::HallNotAvailable)
+        ));
+    }
+
+    #[test]
+    fn test_guildhall_doors_gated_by_rank_and_rent_eviction() {
+        let leader = Uuid::new_v4();
+        let vice = Uuid::new_v4();
+        let member = Uuid::new_v4();
+        let mut guild = Guild::new("Keepers", leader, "Leader");
+        guild.add_member(GuildMember::new(vice, "Vice", 2));
+        guild.add_member(GuildMember::new(member, "Member", 3));
+        guild.balance = 560_000;
+        let mut houses = guildhall_houses();
+        let config = GuildhallConfig::default();
+        let now = Utc::now();
+        guild.buy_guildhall(leader, &mut houses, 5, &config, now).unwrap();
+
+        let treasury = Position::new(100, 100, 7);
+        let hallway = Position::new(101, 100, 7);
+        guild.set_hall_door_rank(leader, Some(treasury), 2).unwrap();
+        assert!(guild.can_open_hall_door(leader, &treasury));
+        assert!(guild.can_open_hall_door(vice, &treasury));
+        assert!(!guild.can_open_hall_door(member, &treasury));
+        assert!(guild.can_open_hall_door(member, &hallway));
+        assert!(!guild.can_open_hall_door(Uuid::new_v4(), &hallway));
+
+        // One period is paid from the bank, the next cannot be covered
+        assert_eq!(guild.pay_hall_rent(&mut houses, &config, now), GuildhallRent::NotDue);
+        let due = now + chrono::Duration::days(30);
+        assert_eq!(guild.pay_hall_rent(&mut houses, &config, due), GuildhallRent::Paid(50_000));
+        assert_eq!(guild.balance, 10_000);
+        let due = due + chrono::Duration::days(30);
+        assert_eq!(guild.pay_hall_rent(&mut houses, &config, due), GuildhallRent::Evicted(5));
+        assert!(guild.hall.is_none() && guild.guild_hall_id.is_none());
+        assert!(!houses.get(5).unwrap().has_owner());
+        assert!(!guild.can_open_hall_door(leader, &hallway));
+    }
 }
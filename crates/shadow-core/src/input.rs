@@ -0,0 +1,167 @@
+//! Tick-aligned session input
+//!
+//! Client packets arrive at any time between engine ticks. Applying them as
+//! they arrive races the game loop, so each session buffers its inputs and
+//! the engine drains the buffer at the start of the next tick, in receipt
+//! order. A session may only queue `max_per_tick` inputs per tick; anything
+//! beyond that is dropped and reported to anti-cheat as flooding.
+
+use serde::{Deserialize, Serialize};
+use shadow_anticheat::{AntiCheatSystem, DetectionResult};
+use std::collections::VecDeque;
+use uuid::Uuid;
+
+/// Input queue limits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InputQueueConfig {
+    /// Inputs a session may queue per tick
+    pub max_per_tick: usize,
+}
+
+impl Default for InputQueueConfig {
+    fn default() -> Self {
+        Self { max_per_tick: 16 }
+    }
+}
+
+/// An input was dropped because the session exceeded its per-tick cap
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputThrottled;
+
+impl std::fmt::Display for InputThrottled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Too many actions, slow down")
+    }
+}
+
+impl std::error::Error for InputThrottled {}
+
+/// Inputs to apply in one tick
+#[derive(Debug)]
+pub struct TickInputs<T> {
+    /// Inputs in receipt order
+    pub inputs: Vec<T>,
+    /// Inputs dropped since the previous tick
+    pub dropped: u32,
+}
+
+/// Per-session buffer of inputs waiting for the next tick
+#[derive(Debug)]
+pub struct InputQueue<T> {
+    config: InputQueueConfig,
+    pending: VecDeque<T>,
+    dropped: u32,
+    total_dropped: u64,
+}
+
+impl<T> InputQueue<T> {
+    pub fn new(config: InputQueueConfig) -> Self {
+        Self {
+            config,
+            pending: VecDeque::new(),
+            dropped: 0,
+            total_dropped: 0,
+        }
+    }
+
+    /// Queue an input for the next tick
+    pub fn push(&mut self, input: T) -> Result<(), InputThrottled> {
+        if self.pending.len() >= self.config.max_per_tick {
+            self.dropped += 1;
+            self.total_dropped += 1;
+            return Err(InputThrottled);
+        }
+        self.pending.push_back(input);
+        Ok(())
+    }
+
+    /// Take the inputs to apply this tick, opening the queue for the next one
+    pub fn drain_tick(&mut self) -> TickInputs<T> {
+        TickInputs {
+            inputs: self.pending.drain(..).collect(),
+            dropped: std::mem::take(&mut self.dropped),
+        }
+    }
+
+    /// Take this tick's inputs and report any dropped ones to anti-cheat
+    pub fn drain_tick_checked(
+        &mut self,
+        anticheat: &mut AntiCheatSystem,
+        character_id: Uuid,
+    ) -> (TickInputs<T>, Option<DetectionResult>) {
+        let tick = self.drain_tick();
+        let detection = if tick.dropped > 0 {
+            anticheat.process_input_overflow(
+                character_id,
+                tick.dropped,
+                self.config.max_per_tick,
+                crate::TICK_RATE_MS,
+            )
+        } else {
+            None
+        };
+        (tick, detection)
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Inputs dropped over the whole session
+    pub fn total_dropped(&self) -> u64 {
+        self.total_dropped
+    }
+}
+
+impl<T> Default for InputQueue<T> {
+    fn default() -> Self {
+        Self::new(InputQueueConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inputs_applied_in_order_at_next_tick() {
+        let mut queue = InputQueue::default();
+        queue.push("walk north").unwrap();
+        queue.push("attack").unwrap();
+
+        // Tick boundary: everything received so far, in order
+        assert_eq!(queue.drain_tick().inputs, vec!["walk north", "attack"]);
+
+        queue.push("walk east").unwrap();
+        assert_eq!(queue.len(), 1);
+        let tick = queue.drain_tick();
+        assert_eq!(tick.inputs, vec!["walk east"]);
+        assert_eq!(tick.dropped, 0);
+        assert!(queue.drain_tick().inputs.is_empty());
+    }
+
+    #[test]
+    fn test_over_cap_inputs_throttled() {
+        let mut queue = InputQueue::new(InputQueueConfig { max_per_tick: 3 });
+        for step in 0..5 {
+            let result = queue.push(step);
+            assert_eq!(result.is_ok(), step < 3);
+        }
+
+        let mut anticheat = AntiCheatSystem::new(Default::default());
+        let (tick, detection) = queue.drain_tick_checked(&mut anticheat, Uuid::new_v4());
+        assert_eq!(tick.inputs, vec![0, 1, 2]);
+        assert_eq!(tick.dropped, 2);
+        assert!(detection.is_some());
+
+        // The cap resets every tick
+        assert!(queue.push(5).is_ok());
+        assert_eq!(queue.drain_tick().dropped, 0);
+        assert_eq!(queue.total_dropped(), 2);
+    }
+}
@@ -0,0 +1,391 @@
+//! Looking For Group (party finder)
+//!
+//! Players post groups for PvE activities (bosses, quests, hunts) with a
+//! level range and the vocations they still need. Players looking for a
+//! group are matched against open postings and invited into the leader's
+//! party through the `PartyManager`.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use shadow_db::models::Vocation;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::party::{PartyError, PartyManager};
+
+/// Kind of activity a group is formed for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LfgActivity {
+    Boss,
+    Quest,
+    Hunt,
+}
+
+/// An open group looking for members
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LfgPosting {
+    pub id: Uuid,
+    pub leader_id: Uuid,
+    pub leader_name: String,
+    pub leader_level: u16,
+    pub activity: LfgActivity,
+    /// Boss, quest or hunting ground name
+    pub target: String,
+    pub min_level: u16,
+    pub max_level: u16,
+    /// Vocations still needed; empty accepts any vocation
+    pub needed_vocations: Vec<Vocation>,
+    /// Remaining open spots
+    pub open_slots: u8,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl LfgPosting {
+    pub fn new(
+        leader_id: Uuid,
+        leader_name: impl Into<String>,
+        leader_level: u16,
+        activity: LfgActivity,
+        target: impl Into<String>,
+        open_slots: u8,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            leader_id,
+            leader_name: leader_name.into(),
+            leader_level,
+            activity,
+            target: target.into(),
+            min_level: 1,
+            max_level: u16::MAX,
+            needed_vocations: Vec::new(),
+            open_slots,
+            note: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn with_level_range(mut self, min_level: u16, max_level: u16) -> Self {
+        self.min_level = min_level;
+        self.max_level = max_level;
+        self
+    }
+
+    pub fn with_vocations(mut self, vocations: Vec<Vocation>) -> Self {
+        self.needed_vocations = vocations;
+        self
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.note = Some(note.into());
+        self
+    }
+
+    /// Whether a player fits this group
+    pub fn accepts(&self, seeker: &LfgSeeker) -> bool {
+        self.open_slots > 0
+            && seeker.player_id != self.leader_id
+            && (self.min_level..=self.max_level).contains(&seeker.level)
+            && (self.needed_vocations.is_empty()
+                || self.needed_vocations.contains(&seeker.vocation.base_vocation()))
+            && seeker.wants(self.activity, &self.target)
+    }
+
+    fn fill_slot(&mut self, vocation: Vocation) {
+        self.open_slots = self.open_slots.saturating_sub(1);
+        if let Some(idx) = self.needed_vocations.iter().position(|v| *v == vocation.base_vocation()) {
+            self.needed_vocations.remove(idx);
+        }
+    }
+}
+
+/// A player searching for a group
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LfgSeeker {
+    pub player_id: Uuid,
+    pub name: String,
+    pub level: u16,
+    pub vocation: Vocation,
+    /// Activities the player is interested in; empty accepts any
+    pub activities: Vec<LfgActivity>,
+    /// Specific target (boss/quest/hunt name), if any
+    pub target: Option<String>,
+    pub queued_at: DateTime<Utc>,
+}
+
+impl LfgSeeker {
+    pub fn new(player_id: Uuid, name: impl Into<String>, level: u16, vocation: Vocation) -> Self {
+        Self {
+            player_id,
+            name: name.into(),
+            level,
+            vocation,
+            activities: Vec::new(),
+            target: None,
+            queued_at: Utc::now(),
+        }
+    }
+
+    pub fn with_activity(mut self, activity: LfgActivity) -> Self {
+        self.activities.push(activity);
+        self
+    }
+
+    pub fn with_target(mut self, target: impl Into<String>) -> Self {
+        self.target = Some(target.into());
+        self
+    }
+
+    fn wants(&self, activity: LfgActivity, target: &str) -> bool {
+        (self.activities.is_empty() || self.activities.contains(&activity))
+            && self.target.as_deref().is_none_or(|t| t.eq_ignore_ascii_case(target))
+    }
+}
+
+/// A seeker paired with a posting
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LfgMatch {
+    pub posting_id: Uuid,
+    pub leader_id: Uuid,
+    pub player_id: Uuid,
+}
+
+/// Party finder manager
+#[derive(Debug, Default)]
+pub struct LfgManager {
+    postings: HashMap<Uuid, LfgPosting>,
+    seekers: HashMap<Uuid, LfgSeeker>,
+}
+
+impl LfgManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Post a group. A leader can only have one open posting.
+    pub fn post_group(&mut self, posting: LfgPosting) -> Result<Uuid, LfgError> {
+        if posting.open_slots == 0 {
+            return Err(LfgError::NoOpenSlots);
+        }
+        if self.postings.values().any(|p| p.leader_id == posting.leader_id) {
+            return Err(LfgError::AlreadyPosted);
+        }
+        let id = posting.id;
+        self.postings.insert(id, posting);
+        Ok(id)
+    }
+
+    /// Remove a posting
+    pub fn close_group(&mut self, posting_id: Uuid) -> Option<LfgPosting> {
+        self.postings.remove(&posting_id)
+    }
+
+    pub fn get_posting(&self, posting_id: Uuid) -> Option<&LfgPosting> {
+        self.postings.get(&posting_id)
+    }
+
+    /// Open postings a player is compatible with, oldest first
+    pub fn search(&self, seeker: &LfgSeeker) -> Vec<&LfgPosting> {
+        let mut results: Vec<_> = self.postings.values().filter(|p| p.accepts(seeker)).collect();
+        results.sort_by_key(|p| p.created_at);
+        results
+    }
+
+    /// Postings for an activity, regardless of the searching player
+    pub fn browse(&self, activity: LfgActivity) -> Vec<&LfgPosting> {
+        self.postings.values().filter(|p| p.activity == activity).collect()
+    }
+
+    /// Queue a player for automatic matching
+    pub fn queue(&mut self, seeker: LfgSeeker) {
+        self.seekers.insert(seeker.player_id, seeker);
+    }
+
+    /// Leave the LFG queue
+    pub fn dequeue(&mut self, player_id: Uuid) -> bool {
+        self.seekers.remove(&player_id).is_some()
+    }
+
+    pub fn is_queued(&self, player_id: Uuid) -> bool {
+        self.seekers.contains_key(&player_id)
+    }
+
+    /// Pair queued players with compatible postings, longest waiting first.
+    /// Matched players leave the queue and fill a slot in the posting.
+    pub fn auto_match(&mut self) -> Vec<LfgMatch> {
+        let mut seekers: Vec<_> = self.seekers.values().cloned().collect();
+        seekers.sort_by_key(|s| s.queued_at);
+
+        let mut matches = Vec::new();
+        for seeker in seekers {
+            let posting_id = self.search(&seeker).first().map(|p| p.id);
+            if let Some(posting_id) = posting_id {
+                matches.push(self.take_slot(posting_id, &seeker));
+            }
+        }
+        matches
+    }
+
+    /// Reserve a slot for a specific player (manual join from search results)
+    pub fn join(&mut self, posting_id: Uuid, seeker: &LfgSeeker) -> Result<LfgMatch, LfgError> {
+        let posting = self.postings.get(&posting_id).ok_or(LfgError::PostingNotFound)?;
+        if !posting.accepts(seeker) {
+            return Err(LfgError::RequirementsNotMet);
+        }
+        Ok(self.take_slot(posting_id, seeker))
+    }
+
+    fn take_slot(&mut self, posting_id: Uuid, seeker: &LfgSeeker) -> LfgMatch {
+        self.seekers.remove(&seeker.player_id);
+        let posting = self.postings.get_mut(&posting_id).expect("posting exists");
+        posting.fill_slot(seeker.vocation);
+        let leader_id = posting.leader_id;
+        if posting.open_slots == 0 {
+            self.postings.remove(&posting_id);
+        }
+        LfgMatch {
+            posting_id,
+            leader_id,
+            player_id: seeker.player_id,
+        }
+    }
+
+    /// Form the party for a match: the leader's party is created if needed
+    /// and the matched player is invited. Returns the party ID.
+    pub async fn form_party(
+        &self,
+        lfg_match: &LfgMatch,
+        leader: (&str, u16),
+        player: (&str, u16),
+        parties: &mut PartyManager,
+    ) -> Result<Uuid, LfgError> {
+        if parties.is_in_party(lfg_match.player_id) {
+            return Err(LfgError::Party(PartyError::AlreadyInParty));
+        }
+
+        let party = match parties.get_by_player(lfg_match.leader_id) {
+            Some(party) => party,
+            None => {
+                let (name, level) = leader;
+                let id = parties
+                    .create_party(lfg_match.leader_id, name, level)
+                    .map_err(LfgError::Party)?;
+                parties.get(id).ok_or(LfgError::Party(PartyError::PartyDisbanded))?
+            }
+        };
+
+        let mut party = party.write().await;
+        if !party.is_leader(lfg_match.leader_id) {
+            return Err(LfgError::Party(PartyError::NotLeader));
+        }
+        let (name, level) = player;
+        party.invite(lfg_match.player_id, name, level).map_err(LfgError::Party)?;
+        Ok(party.id)
+    }
+}
+
+/// LFG errors
+#[derive(Debug, Clone)]
+pub enum LfgError {
+    AlreadyPosted,
+    NoOpenSlots,
+    PostingNotFound,
+    RequirementsNotMet,
+    Party(PartyError),
+}
+
+impl std::fmt::Display for LfgError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LfgError::AlreadyPosted => write!(f, "You already have an open group posting"),
+            LfgError::NoOpenSlots => write!(f, "The group has no open slots"),
+            LfgError::PostingNotFound => write!(f, "Group posting not found"),
+            LfgError::RequirementsNotMet => write!(f, "You do not meet the group's requirements"),
+            LfgError::Party(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for LfgError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ferumbras_group(leader_id: Uuid) -> LfgPosting {
+        LfgPosting::new(leader_id, "Leader", 250, LfgActivity::Boss, "Ferumbras", 2)
+            .with_level_range(200, 400)
+            .with_vocations(vec![Vocation::Druid, Vocation::Knight])
+    }
+
+    #[test]
+    fn test_post_and_search_group() {
+        let mut lfg = LfgManager::new();
+        let leader = Uuid::new_v4();
+        let posting_id = lfg.post_group(ferumbras_group(leader)).unwrap();
+        assert!(matches!(lfg.post_group(ferumbras_group(leader)), Err(LfgError::AlreadyPosted)));
+
+        let druid = LfgSeeker::new(Uuid::new_v4(), "Druid", 300, Vocation::ElderDruid)
+            .with_activity(LfgActivity::Boss);
+        assert_eq!(lfg.search(&druid)[0].id, posting_id);
+
+        // Level range and activity are respected
+        let low = LfgSeeker::new(Uuid::new_v4(), "Low", 120, Vocation::Druid);
+        assert!(lfg.search(&low).is_empty());
+        let hunter = LfgSeeker::new(Uuid::new_v4(), "Hunter", 300, Vocation::Druid)
+            .with_activity(LfgActivity::Hunt);
+        assert!(lfg.search(&hunter).is_empty());
+        assert_eq!(lfg.browse(LfgActivity::Boss).len(), 1);
+    }
+
+    #[test]
+    fn test_vocation_requirement_filtering() {
+        let mut lfg = LfgManager::new();
+        let posting_id = lfg.post_group(ferumbras_group(Uuid::new_v4())).unwrap();
+
+        let sorcerer = LfgSeeker::new(Uuid::new_v4(), "Sorc", 300, Vocation::MasterSorcerer);
+        assert!(lfg.search(&sorcerer).is_empty());
+        assert!(matches!(lfg.join(posting_id, &sorcerer), Err(LfgError::RequirementsNotMet)));
+
+        // Once the knight role is filled a second knight no longer fits
+        let knight = LfgSeeker::new(Uuid::new_v4(), "Knight", 300, Vocation::Knight);
+        lfg.join(posting_id, &knight).unwrap();
+        let other_knight = LfgSeeker::new(Uuid::new_v4(), "Knight Two", 300, Vocation::EliteKnight);
+        assert!(lfg.search(&other_knight).is_empty());
+        assert_eq!(lfg.get_posting(posting_id).unwrap().needed_vocations, vec![Vocation::Druid]);
+    }
+
+    #[tokio::test]
+    async fn test_auto_match_forms_party() {
+        let mut lfg = LfgManager::new();
+        let mut parties = PartyManager::new();
+        let leader = Uuid::new_v4();
+        let posting_id = lfg
+            .post_group(
+                LfgPosting::new(leader, "Leader", 250, LfgActivity::Boss, "Ferumbras", 1)
+                    .with_vocations(vec![Vocation::Druid]),
+            )
+            .unwrap();
+
+        let druid_id = Uuid::new_v4();
+        lfg.queue(LfgSeeker::new(druid_id, "Druid", 300, Vocation::Druid).with_target("ferumbras"));
+        lfg.queue(LfgSeeker::new(Uuid::new_v4(), "Paladin", 300, Vocation::Paladin));
+
+        let matches = lfg.auto_match();
+        assert_eq!(matches, vec![LfgMatch { posting_id, leader_id: leader, player_id: druid_id }]);
+        assert!(!lfg.is_queued(druid_id));
+        // The only slot was filled, so the posting closes
+        assert!(lfg.get_posting(posting_id).is_none());
+
+        let party_id = lfg
+            .form_party(&matches[0], ("Leader", 250), ("Druid", 300), &mut parties)
+            .await
+            .unwrap();
+        let party = parties.get(party_id).unwrap();
+        let mut party = party.write().await;
+        assert!(party.is_leader(leader));
+        party.accept_invite(druid_id).unwrap();
+        assert!(party.is_member(druid_id));
+    }
+}
@@ -4,7 +4,11 @@
 //! managing the game loop, and orchestrating communication between subsystems.
 
 pub mod achievement;
+pub mod autosave;
+pub mod backup;
 pub mod bank;
+pub mod capacity;
+pub mod compensation;
 pub mod config;
 pub mod cyclopedia;
 pub mod death;
@@ -13,34 +17,58 @@ pub mod error;
 pub mod events;
 pub mod geolocation;
 pub mod guild;
+pub mod input;
+pub mod lfg;
 pub mod party;
 pub mod player;
+pub mod rcon;
+pub mod regeneration;
+pub mod report;
+pub mod reset;
 pub mod scheduler;
 pub mod server;
 pub mod session;
 pub mod state;
+pub mod telemetry;
 pub mod trade;
 pub mod vip;
+pub mod vocation;
+pub mod wardrobe;
+pub mod watchlist;
 
 use std::sync::Arc;
 use tokio::sync::{broadcast, RwLock};
 use uuid::Uuid;
 
 pub use achievement::{Achievement, AchievementManager, PlayerAchievements};
-pub use bank::{BankAccount, BankManager};
+pub use autosave::{AutoSave, AutoSaveConfig, EntitySaver, SaveKey, SaveReport};
+pub use backup::{BackupConfig, BackupEvent, BackupExporter, BackupFile, BackupScope, BackupService, PgDumpExporter};
+pub use bank::{BankAccount, BankEconomyConfig, BankManager, InterestConfig, PeriodicReport, TaxConfig};
+pub use capacity::{CapacityConfig, CapacityService, CarriedItem, CharacterLoad};
+pub use compensation::{CompensationService, GrantAuditRecord, GrantError, GrantItem, GrantLimits, GrantRequest, GrantStore};
 pub use config::ServerConfig;
-pub use cyclopedia::{Cyclopedia, CyclopediaManager, CyclopediaCategory};
-pub use death::{BlessingType, DeathManager, DeathPenalty, PlayerBlessings, SkullType};
+pub use cyclopedia::{BestiaryProgress, CharmRecommendation, Cyclopedia, CyclopediaManager, CyclopediaCategory};
+pub use death::{BlessingType, DeathManager, DeathPenalty, DeathRecord, DeathResult, DeathType, PlayerBlessings, PvpLoot, PvpLootConfig, PvpLootDestination, SkullType, AMULET_OF_LOSS};
 pub use engine::GameEngine;
 pub use error::{CoreError, Result};
 pub use geolocation::{GeoLocation, GeoService, GeoConfig, ServerRegion};
-pub use guild::{Guild, GuildManager, GuildMember, GuildRank};
+pub use guild::{Guild, GuildAlliance, GuildManager, GuildMember, GuildRank, Guildhall, GuildhallConfig, GuildhallRent};
+pub use input::{InputQueue, InputQueueConfig, InputThrottled, TickInputs};
+pub use lfg::{LfgActivity, LfgManager, LfgPosting, LfgSeeker};
 pub use party::{Party, PartyManager};
+pub use rcon::{RconConfig, RconError, RconHandler, RconOperator, RconPermission, RconRegistry, RconResponse, RconService};
+pub use regeneration::{RegenContext, RegenTick, RegenerationConfig, RegenerationSystem};
+pub use reset::{ResetConfig, ResetEvent, ResetKind, ResetService};
+pub use report::{PlayerReport, ReportCategory, ReportConfig, ReportError, ReportService, SupportTickets};
 pub use server::ShadowServer;
-pub use session::PlayerSession;
+pub use session::{ClientFeatures, PlayerSession, ProtocolNegotiator};
 pub use state::GameState;
-pub use trade::{TradeManager, TradeState};
-pub use vip::{VipManager, VipStatus, VipTier};
+pub use telemetry::{EventSink, TelemetryConfig, TelemetryExporter, TelemetryHandle, TelemetryStats};
+pub use trade::{average_trade_price, EconomySinks, ItemCategory, MarketFeeConfig, NetWorth, TradeManager, TradeState};
+pub use vip::{StorageCapacity, TierCapacity, VipManager, VipStatus, VipTier};
+pub use vocation::{PromotionConfig, PromotionError, VocationConfig, VocationStats};
+pub use wardrobe::{AddonProgress, AddonQuest, AddonRequirement, AddonUnlock, Wardrobe};
+pub use watchlist::{CharacterListing, ItemListing, Watch, WatchCondition, WatchNotification, Watchlist, WatchlistError};
 
 /// Server-wide unique identifier
 pub type ServerId = Uuid;
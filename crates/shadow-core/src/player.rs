@@ -3,19 +3,26 @@
 //! This module manages active player sessions and their game state,
 //! bridging between the protocol layer and the game world.
 
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use tokio::sync::{mpsc, RwLock};
 use uuid::Uuid;
 
+use shadow_combat::EffectEvent;
+use shadow_db::models::Vocation;
+use shadow_db::repositories::CharacterRepository;
+use shadow_db::DatabasePool;
 use shadow_protocol::codec::{NetworkMessage, Position as ProtocolPosition};
+use shadow_protocol::game::{build_animated_text, build_distance_effect, build_magic_effect, build_text_message, TextMessageType};
 use shadow_protocol::packets::*;
+use shadow_world::access::{quest_completed_key, quest_stage_key, AccessContext};
 use shadow_world::creature::{Creature, CreatureType, Outfit};
 use shadow_world::position::{Direction, Position};
 use shadow_world::tile::Tile;
 
+use crate::wardrobe::{AddonProgress, Wardrobe};
 use crate::Result;
 
 /// Player session - represents an active player connection
@@ -33,6 +40,8 @@ pub struct Player {
     pub connection_id: u64,
     /// Packet sender channel
     pub packet_tx: mpsc::Sender<NetworkMessage>,
+    /// Character vocation
+    pub vocation: Vocation,
     /// The underlying creature representation
     pub creature: Creature,
     /// Walk queue for path movement
@@ -65,6 +74,18 @@ pub struct Player {
     pub session_time_ms: u64,
     /// Is saving (prevent actions during save)
     pub saving: bool,
+    /// Looted gold is converted into the fewest coins
+    pub auto_convert_gold: bool,
+    /// Owned outfits and addons
+    pub wardrobe: Wardrobe,
+    /// Counts towards addon quests
+    pub addon_progress: AddonProgress,
+    /// Stage of each gated quest, from character storage
+    pub quest_stages: HashMap<String, u8>,
+    /// Gated quests the character has completed
+    pub completed_quests: HashSet<String>,
+    /// Count of each item the character carries
+    pub carried_items: HashMap<u16, u32>,
 }
 
 /// Exhaust types for action cooldowns
@@ -95,6 +116,8 @@ impl Player {
         creature.stats.max_mana = 50;
         creature.stats.base_speed = 220;
         creature.outfit = Outfit::with_colors(128, 78, 68, 58, 76);
+        let mut wardrobe = Wardrobe::new();
+        wardrobe.unlock_outfit(creature.outfit.look_type);
 
         Self {
             id: Uuid::new_v4(),
@@ -103,6 +126,7 @@ impl Player {
             name,
             connection_id,
             packet_tx,
+            vocation: Vocation::None,
             creature,
             walk_queue: VecDeque::new(),
             last_step: Instant::now(),
@@ -119,6 +143,47 @@ impl Player {
             login_time: Instant::now(),
             session_time_ms: 0,
             saving: false,
+            auto_convert_gold: true,
+            wardrobe,
+            addon_progress: AddonProgress::new(),
+            quest_stages: HashMap::new(),
+            completed_quests: HashSet::new(),
+            carried_items: HashMap::new(),
+        }
+    }
+
+    /// Take a quest's stage and completion from its character storage values
+    pub fn set_quest_storage(&mut self, quest_id: &str, stage: Option<i64>, completed: Option<i64>) {
+        match stage {
+            Some(stage) => self.quest_stages.insert(quest_id.to_string(), stage.clamp(0, u8::MAX as i64) as u8),
+            None => self.quest_stages.remove(quest_id),
+        };
+        if completed.is_some_and(|value| value > 0) {
+            self.completed_quests.insert(quest_id.to_string());
+        } else {
+            self.completed_quests.remove(quest_id);
+        }
+    }
+
+    /// Replace the carried item counts with `(item id, count)` rows
+    pub fn set_carried_items(&mut self, items: impl IntoIterator<Item = (i32, i64)>) {
+        self.carried_items = items
+            .into_iter()
+            .filter_map(|(item_id, count)| {
+                let item_id = u16::try_from(item_id).ok()?;
+                Some((item_id, count.clamp(0, u32::MAX as i64) as u32))
+            })
+            .collect();
+    }
+
+    /// What access gates check the player against
+    pub fn access_context(&self) -> AccessContext {
+        AccessContext {
+            level: self.creature.stats.level as u32,
+            vocation: self.vocation as u8,
+            quest_stages: self.quest_stages.clone(),
+            completed_quests: self.completed_quests.clone(),
+            items: self.carried_items.clone(),
         }
     }
 
@@ -313,6 +378,14 @@ impl Player {
         self.send_packet(msg).await
     }
 
+    /// Relay combat effects to this player
+    pub async fn send_effects(&self, effects: &[EffectEvent]) -> Result<()> {
+        for effect in effects {
+            self.send_packet(effect_message(effect)).await?;
+        }
+        Ok(())
+    }
+
     /// Mark creature as known
     pub fn set_known_creature(&mut self, creature_id: u32) {
         self.known_creatures.insert(creature_id, true);
@@ -329,6 +402,45 @@ impl Player {
     }
 }
 
+/// Load what a session needs from its character: vocation, level, the
+/// storage of the quests in `quest_ids` and the items it carries
+pub async fn load_player(db: &DatabasePool, player: &mut Player, quest_ids: &HashSet<String>) -> Result<()> {
+    let characters = CharacterRepository::new(db.postgres());
+    if let Some(character) = characters.find_by_id(player.character_id).await? {
+        player.vocation = character.vocation;
+        player.creature.stats.level = character.level.clamp(1, u16::MAX as i32) as u16;
+    }
+    for quest_id in quest_ids {
+        let stage = characters.get_storage(player.character_id, &quest_stage_key(quest_id)).await?;
+        let completed = characters.get_storage(player.character_id, &quest_completed_key(quest_id)).await?;
+        player.set_quest_storage(quest_id, stage, completed);
+    }
+    player.set_carried_items(characters.item_counts(player.character_id).await?);
+    Ok(())
+}
+
+/// Save what a session changes about its character: where it stands
+/// and that it is offline
+pub async fn save_player(db: &DatabasePool, player: &Player) -> Result<()> {
+    let characters = CharacterRepository::new(db.postgres());
+    let position = player.position();
+    characters
+        .save_position(player.character_id, position.x as i32, position.y as i32, position.z as i32)
+        .await?;
+    characters.set_online(player.character_id, false).await?;
+    Ok(())
+}
+
+/// Packet showing an effect to the client
+pub fn effect_message(effect: &EffectEvent) -> NetworkMessage {
+    let position = |p: &Position| ProtocolPosition::new(p.x, p.y, p.z);
+    match effect {
+        EffectEvent::Magic { position: at, effect } => build_magic_effect(position(at), *effect),
+        EffectEvent::Distance { from, to, effect } => build_distance_effect(position(from), position(to), *effect),
+        EffectEvent::AnimatedText { position: at, color, text } => build_animated_text(position(at), *color, text),
+    }
+}
+
 /// Message types for text messages
 #[derive(Debug, Clone, Copy)]
 #[repr(u8)]
@@ -395,6 +507,18 @@ impl PlayerManager {
         player
     }
 
+    /// Log a player in: load its character for the gates of `world` and
+    /// add the session
+    pub async fn login(&mut self, mut player: Player, db: Option<&DatabasePool>, world: &crate::WorldRef) -> Arc<RwLock<Player>> {
+        if let Some(db) = db {
+            let quest_ids = world.read().await.gated_quest_ids();
+            if let Err(e) = load_player(db, &mut player, &quest_ids).await {
+                tracing::error!("Failed to load player {}: {}", player.name, e);
+            }
+        }
+        self.add_player(player)
+    }
+
     /// Remove a player
     pub fn remove_player(&mut self, player_id: Uuid) -> Option<Arc<RwLock<Player>>> {
         if let Some(player) = self.players.remove(&player_id) {
@@ -409,6 +533,31 @@ impl PlayerManager {
         }
     }
 
+    /// Log a player out: save the character, tell the client why and drop
+    /// the session. The session holds the connection's packet sender, so
+    /// the connection closes once it is gone. Returns the player's name.
+    pub async fn logout(&mut self, player_id: Uuid, db: Option<&DatabasePool>, message: &str) -> Option<String> {
+        let player = self.get_player(player_id)?;
+        let name = {
+            let mut player = player.write().await;
+            player.saving = true;
+            if let Some(db) = db {
+                if let Err(e) = save_player(db, &player).await {
+                    tracing::error!("Failed to save player {} on logout: {}", player.name, e);
+                }
+            }
+
+            // In game, this opcode disconnects the client with a message
+            let mut msg = NetworkMessage::new();
+            msg.put_u8(ServerPacketType::LoginError as u8);
+            msg.put_string(message);
+            let _ = player.send_packet(msg).await;
+            player.name.clone()
+        };
+        self.remove_player(player_id);
+        Some(name)
+    }
+
     /// Get player by ID
     pub fn get_player(&self, player_id: Uuid) -> Option<Arc<RwLock<Player>>> {
         self.players.get(&player_id).cloned()
@@ -482,6 +631,12 @@ impl MovementHandler {
         let current_pos = player.position();
         let new_pos = current_pos.moved(direction);
 
+        // Gated tiles turn the player back with the gate's message
+        if let Err(denied) = world.read().await.check_access(&new_pos, &player.access_context()) {
+            player.send_packet(build_text_message(TextMessageType::StatusSmall, &denied.message)).await?;
+            return Ok(None);
+        }
+
         // Check if target tile is walkable
         // This would check the actual world data
         // For now, we'll assume it's valid
@@ -515,3 +670,39 @@ impl MovementHandler {
 
 /// World reference type for map and entity access
 pub type WorldRef = Arc<RwLock<shadow_world::Map>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shadow_world::access::AccessGate;
+
+    #[test]
+    fn test_access_context_follows_loaded_character() {
+        let (packet_tx, _packet_rx) = mpsc::channel(8);
+        let mut player = Player::new(Uuid::new_v4(), Uuid::new_v4(), "Knight".to_string(), 1, packet_tx, Position::new(100, 100, 7));
+        let mut map = shadow_world::Map::new("Test".to_string());
+        let temple = Position::new(101, 100, 7);
+        map.set_access_gate(
+            temple,
+            AccessGate::new("Only knights who finished the Inquisition may pass.")
+                .require_quest_completed("inquisition")
+                .require_item(2088, 1)
+                .require_vocation(vec![4, 8]),
+        );
+
+        // A fresh session meets none of it
+        assert!(map.check_access(&temple, &player.access_context()).is_err());
+
+        // What load_player fills in from the character
+        player.vocation = Vocation::Knight;
+        player.set_quest_storage("inquisition", Some(5), Some(1));
+        player.set_carried_items(vec![(2088, 1), (3031, 100), (-1, 5)]);
+        assert!(map.check_access(&temple, &player.access_context()).is_ok());
+
+        // Storage without the completion flag keeps the gate closed
+        player.set_quest_storage("inquisition", Some(5), None);
+        let denied = map.check_access(&temple, &player.access_context()).unwrap_err();
+        assert_eq!(denied.message, "Only knights who finished the Inquisition may pass.");
+        assert_eq!(player.access_context().quest_stage("inquisition"), 5);
+    }
+}
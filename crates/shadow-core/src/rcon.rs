@@ -0,0 +1,603 @@
+//! Remote console (RCON)
+//!
+//! A line-based command channel for operators who do not go through the
+//! HTTP API. A connection authenticates with an operator token, then sends
+//! one command per line and gets one JSON response per line. Commands live
+//! in a registry, each with the permission it needs; every executed
+//! command is written to the admin audit log as an `AdminAction` event.
+//! Kicks go through the normal logout, which saves the character; bans
+//! are stored on the account, so the login server refuses it too.
+
+use async_trait::async_trait;
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+use tokio::sync::{mpsc, RwLock};
+
+use shadow_db::repositories::{AccountRepository, CharacterRepository};
+use shadow_db::DatabasePool;
+
+use crate::engine::EngineCommand;
+use crate::events::{AdminAction, AdminActionEvent, GameEvent};
+use crate::player::PlayerManager;
+use crate::{EventBroadcast, PlayerId};
+
+/// Operator permission levels, lowest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum RconPermission {
+    Moderator,
+    Admin,
+    Owner,
+}
+
+/// An operator allowed to use the console
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RconOperator {
+    /// Account recorded in the audit log
+    #[serde(default)]
+    pub id: PlayerId,
+    pub name: String,
+    pub token: String,
+    pub permission: RconPermission,
+}
+
+/// Console configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RconConfig {
+    pub enabled: bool,
+    pub host: String,
+    pub port: u16,
+    pub operators: Vec<RconOperator>,
+}
+
+impl Default for RconConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: "127.0.0.1".to_string(),
+            port: 7175,
+            operators: Vec::new(),
+        }
+    }
+}
+
+/// Structured reply to a console line
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RconResponse {
+    pub ok: bool,
+    pub command: String,
+    pub message: String,
+}
+
+impl RconResponse {
+    fn error(command: &str, error: &RconError) -> Self {
+        Self {
+            ok: false,
+            command: command.to_string(),
+            message: error.to_string(),
+        }
+    }
+}
+
+/// What a handler did, for the reply and the audit log
+#[derive(Debug, Clone)]
+pub struct RconOutcome {
+    pub message: String,
+    pub action: Option<AdminAction>,
+    pub target: Option<String>,
+    pub reason: Option<String>,
+}
+
+impl RconOutcome {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            action: None,
+            target: None,
+            reason: None,
+        }
+    }
+
+    pub fn audited(mut self, action: AdminAction, target: Option<String>, reason: Option<String>) -> Self {
+        self.action = Some(action);
+        self.target = target;
+        self.reason = reason;
+        self
+    }
+}
+
+/// Server handles commands act on
+pub struct RconContext {
+    pub player_manager: Arc<RwLock<PlayerManager>>,
+    pub engine: Option<mpsc::Sender<EngineCommand>>,
+    /// Where characters are saved and accounts banned
+    pub db: Option<DatabasePool>,
+}
+
+impl RconContext {
+    async fn send_engine(&self, command: EngineCommand) -> Result<(), RconError> {
+        let engine = self.engine.as_ref().ok_or(RconError::Unavailable)?;
+        engine.send(command).await.map_err(|_| RconError::Unavailable)
+    }
+
+    fn db(&self) -> Result<&DatabasePool, RconError> {
+        self.db.as_ref().ok_or_else(|| RconError::Database("not connected".to_string()))
+    }
+
+    /// Log an online player out by name, saving the character
+    async fn kick(&self, name: &str, message: &str) -> Result<String, RconError> {
+        let mut manager = self.player_manager.write().await;
+        let player = manager
+            .get_by_name(name)
+            .ok_or_else(|| RconError::PlayerNotFound(name.to_string()))?;
+        let id = player.read().await.id;
+        manager
+            .logout(id, self.db.as_ref(), message)
+            .await
+            .ok_or_else(|| RconError::PlayerNotFound(name.to_string()))
+    }
+
+    /// Account owning a character, online or not
+    async fn account_of(&self, name: &str) -> Result<uuid::Uuid, RconError> {
+        if let Some(player) = self.player_manager.read().await.get_by_name(name) {
+            return Ok(player.read().await.account_id);
+        }
+        let character = CharacterRepository::new(self.db()?.postgres())
+            .find_by_name(name)
+            .await
+            .map_err(|e| RconError::Database(e.to_string()))?;
+        character
+            .map(|c| c.account_id)
+            .ok_or_else(|| RconError::CharacterNotFound(name.to_string()))
+    }
+}
+
+/// `ban_until` of a ban without an end
+fn permanent_ban_until() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(9999, 12, 31, 0, 0, 0).unwrap()
+}
+
+/// A console command
+#[async_trait]
+pub trait RconHandler: Send + Sync {
+    /// Lowest permission that may run the command
+    fn permission(&self) -> RconPermission;
+
+    fn usage(&self) -> &'static str;
+
+    async fn execute(&self, ctx: &RconContext, args: &[&str]) -> Result<RconOutcome, RconError>;
+}
+
+struct KickCommand;
+
+#[async_trait]
+impl RconHandler for KickCommand {
+    fn permission(&self) -> RconPermission {
+        RconPermission::Moderator
+    }
+
+    fn usage(&self) -> &'static str {
+        "kick <player> [reason]"
+    }
+
+    async fn execute(&self, ctx: &RconContext, args: &[&str]) -> Result<RconOutcome, RconError> {
+        let (name, reason) = player_and_reason(args, self.usage())?;
+        let name = ctx.kick(name, "You have been kicked.").await?;
+        Ok(RconOutcome::new(format!("{} was kicked", name)).audited(AdminAction::Kick, Some(name), reason))
+    }
+}
+
+struct BanCommand;
+
+#[async_trait]
+impl RconHandler for BanCommand {
+    fn permission(&self) -> RconPermission {
+        RconPermission::Admin
+    }
+
+    fn usage(&self) -> &'static str {
+        "ban <player> <reason>"
+    }
+
+    async fn execute(&self, ctx: &RconContext, args: &[&str]) -> Result<RconOutcome, RconError> {
+        let (name, reason) = player_and_reason(args, self.usage())?;
+        let reason = reason.ok_or(RconError::Usage(self.usage()))?;
+        let db = ctx.db()?;
+        let account_id = ctx.account_of(name).await?;
+        AccountRepository::new(db.postgres())
+            .ban(account_id, None, &reason, Some(permanent_ban_until()))
+            .await
+            .map_err(|e| RconError::Database(e.to_string()))?;
+
+        let message = match ctx.kick(name, &format!("Your account has been banned: {}", reason)).await {
+            Ok(name) => format!("{} was banned and kicked", name),
+            Err(_) => format!("{} was banned", name),
+        };
+        Ok(RconOutcome::new(message).audited(AdminAction::Ban, Some(name.to_string()), Some(reason)))
+    }
+}
+
+struct BroadcastCommand;
+
+#[async_trait]
+impl RconHandler for BroadcastCommand {
+    fn permission(&self) -> RconPermission {
+        RconPermission::Moderator
+    }
+
+    fn usage(&self) -> &'static str {
+        "broadcast <message>"
+    }
+
+    async fn execute(&self, ctx: &RconContext, args: &[&str]) -> Result<RconOutcome, RconError> {
+        if args.is_empty() {
+            return Err(RconError::Usage(self.usage()));
+        }
+        let message = args.join(" ");
+        ctx.send_engine(EngineCommand::BroadcastMessage(message.clone())).await?;
+        Ok(RconOutcome::new("Message broadcast").audited(AdminAction::Broadcast, None, Some(message)))
+    }
+}
+
+/// Commands that forward straight to the game engine
+struct EngineRconCommand {
+    usage: &'static str,
+    permission: RconPermission,
+    command: fn() -> EngineCommand,
+    action: AdminAction,
+    message: &'static str,
+}
+
+#[async_trait]
+impl RconHandler for EngineRconCommand {
+    fn permission(&self) -> RconPermission {
+        self.permission
+    }
+
+    fn usage(&self) -> &'static str {
+        self.usage
+    }
+
+    async fn execute(&self, ctx: &RconContext, _args: &[&str]) -> Result<RconOutcome, RconError> {
+        ctx.send_engine((self.command)()).await?;
+        Ok(RconOutcome::new(self.message).audited(self.action.clone(), None, None))
+    }
+}
+
+fn player_and_reason<'a>(args: &[&'a str], usage: &'static str) -> Result<(&'a str, Option<String>), RconError> {
+    let (name, rest) = args.split_first().ok_or(RconError::Usage(usage))?;
+    let reason = (!rest.is_empty()).then(|| rest.join(" "));
+    Ok((name, reason))
+}
+
+/// Named console commands
+pub struct RconRegistry {
+    commands: HashMap<String, Box<dyn RconHandler>>,
+}
+
+impl RconRegistry {
+    pub fn new() -> Self {
+        Self {
+            commands: HashMap::new(),
+        }
+    }
+
+    /// Registry with kick, ban, broadcast, reload-config, save-all and shutdown
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::new();
+        registry.register("kick", KickCommand);
+        registry.register("ban", BanCommand);
+        registry.register("broadcast", BroadcastCommand);
+        registry.register("reload-config", EngineRconCommand {
+            usage: "reload-config",
+            permission: RconPermission::Admin,
+            command: || EngineCommand::ReloadConfig,
+            action: AdminAction::ReloadConfig,
+            message: "Configuration reload requested",
+        });
+        registry.register("save-all", EngineRconCommand {
+            usage: "save-all",
+            permission: RconPermission::Admin,
+            command: || EngineCommand::SaveAll,
+            action: AdminAction::SaveAll,
+            message: "Save requested",
+        });
+        registry.register("shutdown", EngineRconCommand {
+            usage: "shutdown",
+            permission: RconPermission::Owner,
+            command: || EngineCommand::Shutdown,
+            action: AdminAction::ServerShutdown,
+            message: "Server is shutting down",
+        });
+        registry
+    }
+
+    pub fn register(&mut self, name: &str, handler: impl RconHandler + 'static) {
+        self.commands.insert(name.to_lowercase(), Box::new(handler));
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn RconHandler> {
+        self.commands.get(&name.to_lowercase()).map(|h| h.as_ref())
+    }
+
+    /// Usage lines of the commands `permission` may run, sorted
+    pub fn help(&self, permission: RconPermission) -> Vec<&'static str> {
+        let mut usage: Vec<&'static str> = self
+            .commands
+            .values()
+            .filter(|h| h.permission() <= permission)
+            .map(|h| h.usage())
+            .collect();
+        usage.sort_unstable();
+        usage
+    }
+}
+
+impl Default for RconRegistry {
+    fn default() -> Self {
+        Self::with_builtins()
+    }
+}
+
+/// Authenticates operators and dispatches their commands
+pub struct RconService {
+    config: RconConfig,
+    registry: RconRegistry,
+    context: RconContext,
+    audit_tx: EventBroadcast,
+}
+
+impl RconService {
+    pub fn new(config: RconConfig, player_manager: Arc<RwLock<PlayerManager>>, audit_tx: EventBroadcast) -> Self {
+        Self {
+            config,
+            registry: RconRegistry::with_builtins(),
+            context: RconContext {
+                player_manager,
+                engine: None,
+                db: None,
+            },
+            audit_tx,
+        }
+    }
+
+    /// Forward engine commands (broadcast, save, reload, shutdown)
+    pub fn with_engine(mut self, engine: mpsc::Sender<EngineCommand>) -> Self {
+        self.context.engine = Some(engine);
+        self
+    }
+
+    /// Save kicked characters and store bans
+    pub fn with_database(mut self, db: DatabasePool) -> Self {
+        self.context.db = Some(db);
+        self
+    }
+
+    pub fn registry_mut(&mut self) -> &mut RconRegistry {
+        &mut self.registry
+    }
+
+    /// Operator owning `token`
+    pub fn authenticate(&self, token: &str) -> Result<&RconOperator, RconError> {
+        self.config
+            .operators
+            .iter()
+            .find(|op| !op.token.is_empty() && constant_time_eq(op.token.as_bytes(), token.as_bytes()))
+            .ok_or(RconError::InvalidCredentials)
+    }
+
+    /// Run one console line for an authenticated operator
+    pub async fn execute(&self, operator: &RconOperator, line: &str) -> RconResponse {
+        let mut parts = line.split_whitespace();
+        let Some(name) = parts.next() else {
+            return RconResponse::error("", &RconError::UnknownCommand(String::new()));
+        };
+        let args: Vec<&str> = parts.collect();
+        let name = name.to_lowercase();
+
+        if name == "help" {
+            return RconResponse {
+                ok: true,
+                command: name,
+                message: self.registry.help(operator.permission).join("\n"),
+            };
+        }
+
+        let Some(handler) = self.registry.get(&name) else {
+            return RconResponse::error(&name, &RconError::UnknownCommand(name.clone()));
+        };
+        if operator.permission < handler.permission() {
+            tracing::warn!("RCON: {} denied '{}'", operator.name, name);
+            return RconResponse::error(&name, &RconError::PermissionDenied);
+        }
+
+        match handler.execute(&self.context, &args).await {
+            Ok(outcome) => {
+                tracing::info!("RCON: {} ran '{}'", operator.name, line.trim());
+                if let Some(action) = outcome.action {
+                    let _ = self.audit_tx.send(GameEvent::AdminAction(AdminActionEvent {
+                        admin_id: operator.id,
+                        admin_name: operator.name.clone(),
+                        action,
+                        target: outcome.target,
+                        reason: outcome.reason,
+                        realm_id: None,
+                        timestamp: Utc::now(),
+                    }));
+                }
+                RconResponse {
+                    ok: true,
+                    command: name,
+                    message: outcome.message,
+                }
+            }
+            Err(e) => RconResponse::error(&name, &e),
+        }
+    }
+
+    /// Accept console connections until the listener fails. The first line
+    /// of a connection must be `auth <token>`; replies are JSON lines.
+    pub async fn serve(self: Arc<Self>, listener: TcpListener) -> std::io::Result<()> {
+        loop {
+            let (stream, addr) = listener.accept().await?;
+            let service = self.clone();
+            tokio::spawn(async move {
+                let (read, mut write) = stream.into_split();
+                let mut lines = BufReader::new(read).lines();
+                let mut operator: Option<RconOperator> = None;
+
+                while let Ok(Some(line)) = lines.next_line().await {
+                    let response = match (&operator, line.split_once(' ')) {
+                        (None, Some(("auth", token))) => match service.authenticate(token.trim()) {
+                            Ok(op) => {
+                                operator = Some(op.clone());
+                                RconResponse { ok: true, command: "auth".to_string(), message: format!("Welcome, {}", op.name) }
+                            }
+                            Err(e) => {
+                                tracing::warn!("RCON: failed login from {}", addr);
+                                let response = RconResponse::error("auth", &e);
+                                let _ = write_response(&mut write, &response).await;
+                                break;
+                            }
+                        },
+                        (None, _) => RconResponse::error("", &RconError::NotAuthenticated),
+                        (Some(op), _) => service.execute(op, &line).await,
+                    };
+                    if write_response(&mut write, &response).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    }
+}
+
+async fn write_response(write: &mut (impl AsyncWriteExt + Unpin), response: &RconResponse) -> std::io::Result<()> {
+    let mut line = serde_json::to_string(response).unwrap_or_default();
+    line.push('\n');
+    write.write_all(line.as_bytes()).await
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Console errors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RconError {
+    NotAuthenticated,
+    InvalidCredentials,
+    PermissionDenied,
+    UnknownCommand(String),
+    Usage(&'static str),
+    PlayerNotFound(String),
+    CharacterNotFound(String),
+    /// The game engine is not running
+    Unavailable,
+    Database(String),
+}
+
+impl std::fmt::Display for RconError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RconError::NotAuthenticated => write!(f, "Authenticate first with 'auth <token>'"),
+            RconError::InvalidCredentials => write!(f, "Invalid credentials"),
+            RconError::PermissionDenied => write!(f, "Permission denied"),
+            RconError::UnknownCommand(name) => write!(f, "Unknown command '{}'", name),
+            RconError::Usage(usage) => write!(f, "Usage: {}", usage),
+            RconError::PlayerNotFound(name) => write!(f, "Player '{}' is not online", name),
+            RconError::CharacterNotFound(name) => write!(f, "No character named '{}'", name),
+            RconError::Unavailable => write!(f, "The game engine is not running"),
+            RconError::Database(e) => write!(f, "Database error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for RconError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::Player;
+    use shadow_world::Position;
+    use tokio::sync::broadcast;
+    use uuid::Uuid;
+
+    fn operator(name: &str, token: &str, permission: RconPermission) -> RconOperator {
+        RconOperator { id: Uuid::new_v4(), name: name.to_string(), token: token.to_string(), permission }
+    }
+
+    async fn service() -> (RconService, mpsc::Receiver<EngineCommand>, broadcast::Receiver<GameEvent>) {
+        let config = RconConfig {
+            enabled: true,
+            operators: vec![
+                operator("Gamemaster", "gm-token", RconPermission::Moderator),
+                operator("Admin", "admin-token", RconPermission::Admin),
+            ],
+            ..Default::default()
+        };
+        let players = Arc::new(RwLock::new(PlayerManager::new()));
+        let (packet_tx, _) = mpsc::channel(1);
+        for (i, name) in ["Cheater", "Bystander"].iter().enumerate() {
+            let player = Player::new(Uuid::new_v4(), Uuid::new_v4(), name.to_string(), i as u64, packet_tx.clone(), Position::new(100, 100, 7));
+            players.write().await.add_player(player);
+        }
+
+        let (engine_tx, engine_rx) = mpsc::channel(8);
+        let (audit_tx, audit_rx) = broadcast::channel(8);
+        (RconService::new(config, players, audit_tx).with_engine(engine_tx), engine_rx, audit_rx)
+    }
+
+    #[tokio::test]
+    async fn test_commands_apply_and_audit() {
+        let (rcon, mut engine, mut audit) = service().await;
+        let admin = rcon.authenticate("admin-token").unwrap().clone();
+        assert_eq!(rcon.authenticate("wrong").unwrap_err(), RconError::InvalidCredentials);
+
+        // A ban that cannot be stored is refused, and nobody is kicked
+        let response = rcon.execute(&admin, "ban Cheater speed hacking").await;
+        assert!(!response.ok);
+        assert!(response.message.starts_with("Database error"));
+        assert!(rcon.context.player_manager.read().await.get_by_name("Cheater").is_some());
+        assert!(audit.try_recv().is_err());
+
+        let response = rcon.execute(&admin, "kick Cheater speed hacking").await;
+        assert!(response.ok, "{}", response.message);
+        assert!(rcon.context.player_manager.read().await.get_by_name("Cheater").is_none());
+        let GameEvent::AdminAction(event) = audit.try_recv().unwrap() else { panic!("expected an audit entry") };
+        assert!(matches!(event.action, AdminAction::Kick));
+        assert_eq!(event.admin_name, "Admin");
+        assert_eq!(event.target.as_deref(), Some("Cheater"));
+        assert_eq!(event.reason.as_deref(), Some("speed hacking"));
+
+        assert!(rcon.execute(&admin, "broadcast Server save in 5 minutes").await.ok);
+        assert!(matches!(engine.try_recv().unwrap(), EngineCommand::BroadcastMessage(m) if m == "Server save in 5 minutes"));
+        assert!(rcon.execute(&admin, "save-all").await.ok);
+        assert!(matches!(engine.try_recv().unwrap(), EngineCommand::SaveAll));
+        assert_eq!(audit.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_permission_and_errors() {
+        let (rcon, mut engine, mut audit) = service().await;
+        let gm = rcon.authenticate("gm-token").unwrap().clone();
+
+        let response = rcon.execute(&gm, "shutdown").await;
+        assert!(!response.ok);
+        assert_eq!(response.message, RconError::PermissionDenied.to_string());
+        assert!(engine.try_recv().is_err());
+
+        let response = rcon.execute(&gm, "kick Nobody").await;
+        assert_eq!(response.message, RconError::PlayerNotFound("Nobody".to_string()).to_string());
+        assert!(!rcon.execute(&gm, "teleport Bystander").await.ok);
+        assert!(audit.try_recv().is_err());
+
+        // Moderators can kick, and help only lists what they may run
+        assert!(rcon.execute(&gm, "KICK bystander").await.ok);
+        assert!(matches!(audit.try_recv(), Ok(GameEvent::AdminAction(AdminActionEvent { action: AdminAction::Kick, .. }))));
+        assert_eq!(rcon.execute(&gm, "help").await.message, "broadcast <message>\nkick <player> [reason]");
+    }
+}
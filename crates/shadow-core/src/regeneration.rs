@@ -0,0 +1,394 @@
+//! Regeneration System
+//!
+//! Ticks health, mana and soul regeneration for players. Health and mana
+//! only regenerate while the player is fed, at a rate based on vocation
+//! and level, and faster inside protection zones. Certain conditions
+//! (paralyze, bleeding by default) block food regeneration. Soul only
+//! regenerates while the player is out of combat.
+
+use serde::{Deserialize, Serialize};
+use shadow_combat::{CombatCondition, ConditionType, RulesetFlags};
+use shadow_db::models::Vocation;
+use shadow_world::creature::{ConditionType as WorldCondition, Creature};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::vocation::VocationConfig;
+
+/// Regeneration tuning
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegenerationConfig {
+    /// Maximum stored food time in milliseconds
+    pub max_food_ms: u64,
+    /// Multiplier applied to health/mana regen inside protection zones
+    pub protection_zone_multiplier: f64,
+    /// Extra +1 health/mana per tick for every this many levels (0 disables)
+    pub level_bonus_step: u32,
+    /// Conditions that stop food regeneration while active
+    pub food_blocking_conditions: Vec<ConditionType>,
+    /// Milliseconds per soul point for unpromoted vocations
+    pub soul_interval_ms: u64,
+    /// Milliseconds per soul point for promoted vocations
+    pub promoted_soul_interval_ms: u64,
+    /// Soul cap for unpromoted vocations
+    pub max_soul: u32,
+    /// Soul cap for promoted vocations
+    pub promoted_max_soul: u32,
+}
+
+impl Default for RegenerationConfig {
+    fn default() -> Self {
+        Self {
+            max_food_ms: 1_200_000, // 20 minutes
+            protection_zone_multiplier: 1.5,
+            level_bonus_step: 100,
+            food_blocking_conditions: vec![ConditionType::Paralyze, ConditionType::Bleeding],
+            soul_interval_ms: 120_000,
+            promoted_soul_interval_ms: 15_000,
+            max_soul: 100,
+            promoted_max_soul: 200,
+        }
+    }
+}
+
+/// Player state needed to compute a regeneration tick
+#[derive(Debug, Clone)]
+pub struct RegenContext<'a> {
+    pub vocation: Vocation,
+    pub level: u32,
+    pub soul: u32,
+    pub in_protection_zone: bool,
+    pub in_combat: bool,
+    pub conditions: &'a [CombatCondition],
+}
+
+/// Amounts regenerated by a single tick
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RegenTick {
+    pub health: u32,
+    pub mana: u32,
+    pub soul: u32,
+}
+
+impl RegenTick {
+    pub fn is_empty(&self) -> bool {
+        self.health == 0 && self.mana == 0 && self.soul == 0
+    }
+}
+
+/// Per-character regeneration timers
+#[derive(Debug, Clone)]
+struct RegenState {
+    food_ms: u64,
+    last_update: u64,
+    last_regen_tick: u64,
+    last_soul_tick: u64,
+}
+
+/// Regeneration system tracking food and regen timers per character
+pub struct RegenerationSystem {
+    config: RegenerationConfig,
+    vocations: VocationConfig,
+    rules: RulesetFlags,
+    states: HashMap<Uuid, RegenState>,
+}
+
+impl RegenerationSystem {
+    pub fn new(config: RegenerationConfig, vocations: VocationConfig) -> Self {
+        Self {
+            config,
+            vocations,
+            rules: RulesetFlags::modern(),
+            states: HashMap::new(),
+        }
+    }
+
+    /// Apply a realm ruleset
+    pub fn with_rules(mut self, rules: RulesetFlags) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Whether the game loop should eat a food item from the character's
+    /// backpack. Only when food ran out and the ruleset allows auto-eating.
+    pub fn should_auto_eat(&self, character_id: Uuid) -> bool {
+        !self.rules.manual_food && self.food_remaining(character_id) == 0
+    }
+
+    fn state_mut(&mut self, character_id: Uuid, current_time: u64) -> &mut RegenState {
+        self.states.entry(character_id).or_insert(RegenState {
+            food_ms: 0,
+            last_update: current_time,
+            last_regen_tick: current_time,
+            last_soul_tick: current_time,
+        })
+    }
+
+    /// Eat food worth `food_ms` of regeneration. Returns false if the
+    /// player is already full.
+    pub fn feed(&mut self, character_id: Uuid, food_ms: u64, current_time: u64) -> bool {
+        let max_food = self.config.max_food_ms;
+        let state = self.state_mut(character_id, current_time);
+        if state.food_ms + food_ms > max_food {
+            return false;
+        }
+        state.food_ms += food_ms;
+        true
+    }
+
+    /// Remaining food time for a character
+    pub fn food_remaining(&self, character_id: Uuid) -> u64 {
+        self.states.get(&character_id).map(|s| s.food_ms).unwrap_or(0)
+    }
+
+    /// Whether any active condition blocks food regeneration
+    pub fn is_food_regen_blocked(&self, conditions: &[CombatCondition]) -> bool {
+        conditions
+            .iter()
+            .any(|c| self.config.food_blocking_conditions.contains(&c.condition_type))
+    }
+
+    /// Advance a character's regeneration to `current_time` (ms)
+    pub fn tick(&mut self, character_id: Uuid, ctx: &RegenContext, current_time: u64) -> RegenTick {
+        let blocked = self.is_food_regen_blocked(ctx.conditions);
+        let interval_ms = self.vocations.regen_interval_secs(ctx.vocation) as u64 * 1000;
+        let (base_health, base_mana) = self.vocations.regen_per_tick(ctx.vocation);
+        let level_bonus = if self.config.level_bonus_step > 0 {
+            ctx.level / self.config.level_bonus_step
+        } else {
+            0
+        };
+        let pz_multiplier = if ctx.in_protection_zone {
+            self.config.protection_zone_multiplier
+        } else {
+            1.0
+        };
+        let (soul_interval_ms, max_soul) = if ctx.vocation.is_promoted() {
+            (self.config.promoted_soul_interval_ms, self.config.promoted_max_soul)
+        } else {
+            (self.config.soul_interval_ms, self.config.max_soul)
+        };
+
+        let state = self.state_mut(character_id, current_time);
+        let elapsed = current_time.saturating_sub(state.last_update);
+        state.last_update = current_time;
+
+        let fed = state.food_ms > 0;
+        state.food_ms = state.food_ms.saturating_sub(elapsed);
+
+        let mut result = RegenTick::default();
+
+        if fed && !blocked {
+            if current_time >= state.last_regen_tick + interval_ms {
+                state.last_regen_tick = current_time;
+                result.health = ((base_health + level_bonus) as f64 * pz_multiplier) as u32;
+                result.mana = ((base_mana + level_bonus) as f64 * pz_multiplier) as u32;
+            }
+        } else {
+            // Regen restarts a full interval after food or the blocking condition returns
+            state.last_regen_tick = current_time;
+        }
+
+        if ctx.in_combat || ctx.soul >= max_soul {
+            state.last_soul_tick = current_time;
+        } else if current_time >= state.last_soul_tick + soul_interval_ms {
+            state.last_soul_tick = current_time;
+            result.soul = 1;
+        }
+
+        result
+    }
+
+    /// Tick a player's creature and add what regenerated to it, up to its
+    /// maximums. Dead creatures don't regenerate.
+    pub fn regenerate(
+        &mut self,
+        character_id: Uuid,
+        vocation: Vocation,
+        creature: &mut Creature,
+        in_protection_zone: bool,
+        current_time: u64,
+    ) -> RegenTick {
+        if creature.stats.health <= 0 {
+            return RegenTick::default();
+        }
+
+        let conditions = combat_conditions(creature, current_time);
+        let ctx = RegenContext {
+            vocation,
+            level: creature.stats.level as u32,
+            soul: creature.stats.soul as u32,
+            in_protection_zone,
+            in_combat: creature.is_in_combat(),
+            conditions: &conditions,
+        };
+        let tick = self.tick(character_id, &ctx, current_time);
+
+        let stats = &mut creature.stats;
+        stats.health = add_capped(stats.health, tick.health, stats.max_health);
+        stats.mana = add_capped(stats.mana, tick.mana, stats.max_mana);
+        stats.soul = stats.soul.saturating_add(tick.soul.min(u8::MAX as u32) as u8);
+        tick
+    }
+
+    /// Drop tracking for a character (logout)
+    pub fn remove(&mut self, character_id: Uuid) {
+        self.states.remove(&character_id);
+    }
+
+    /// Drop tracking for every character `online` says is gone
+    pub fn retain(&mut self, mut online: impl FnMut(Uuid) -> bool) {
+        self.states.retain(|&character_id, _| online(character_id));
+    }
+}
+
+/// `value + amount`, but never past `max` nor lowered to it
+fn add_capped(value: i32, amount: u32, max: i32) -> i32 {
+    if value >= max {
+        return value;
+    }
+    value.saturating_add(amount.min(i32::MAX as u32) as i32).min(max)
+}
+
+/// A creature's conditions that have a combat counterpart
+fn combat_conditions(creature: &Creature, current_time: u64) -> Vec<CombatCondition> {
+    creature
+        .conditions
+        .iter()
+        .filter_map(|condition| {
+            let condition_type = match condition.condition_type {
+                WorldCondition::Poison => ConditionType::Poison,
+                WorldCondition::Fire => ConditionType::Fire,
+                WorldCondition::Energy => ConditionType::Energy,
+                WorldCondition::Bleeding => ConditionType::Bleeding,
+                WorldCondition::Cursed => ConditionType::Cursed,
+                WorldCondition::Drown => ConditionType::Drown,
+                WorldCondition::Freezing => ConditionType::Freezing,
+                WorldCondition::Dazzled => ConditionType::Dazzled,
+                WorldCondition::Paralyze => ConditionType::Paralyze,
+                _ => return None,
+            };
+            Some(CombatCondition::new(condition_type, condition.ticks.max(0) as u64, current_time))
+        })
+        .collect()
+}
+
+impl Default for RegenerationSystem {
+    fn default() -> Self {
+        Self::new(RegenerationConfig::default(), VocationConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(vocation: Vocation, conditions: &[CombatCondition]) -> RegenContext<'_> {
+        RegenContext {
+            vocation,
+            level: 50,
+            soul: 0,
+            in_protection_zone: false,
+            in_combat: false,
+            conditions,
+        }
+    }
+
+    #[test]
+    fn test_food_regen_ticks() {
+        let mut regen = RegenerationSystem::default();
+        let id = Uuid::new_v4();
+        let ctx = context(Vocation::Knight, &[]);
+
+        // Hungry: nothing regenerates
+        assert_eq!(regen.tick(id, &ctx, 6_000).health, 0);
+
+        assert!(regen.feed(id, 60_000, 6_000));
+        assert_eq!(regen.tick(id, &ctx, 9_000).health, 0);
+        let tick = regen.tick(id, &ctx, 12_000);
+        assert_eq!((tick.health, tick.mana), (3, 1));
+        assert_eq!(regen.food_remaining(id), 54_000);
+
+        // Inside a protection zone regen is boosted
+        let pz = RegenContext { in_protection_zone: true, ..ctx.clone() };
+        assert_eq!(regen.tick(id, &pz, 18_000).health, 4);
+
+        // Food runs out
+        regen.tick(id, &ctx, 80_000);
+        assert_eq!(regen.food_remaining(id), 0);
+        assert_eq!(regen.tick(id, &ctx, 90_000).health, 0);
+    }
+
+    #[test]
+    fn test_blocking_condition_stops_food_regen() {
+        let mut regen = RegenerationSystem::default();
+        let id = Uuid::new_v4();
+        let conditions = vec![CombatCondition::paralyze(100, 30_000, 0)];
+
+        regen.feed(id, 60_000, 0);
+        assert_eq!(regen.tick(id, &context(Vocation::Knight, &conditions), 12_000).health, 0);
+        assert_eq!(regen.tick(id, &context(Vocation::Knight, &[]), 18_000).health, 3);
+    }
+
+    #[test]
+    fn test_soul_regen_while_idle() {
+        let mut regen = RegenerationSystem::default();
+        let id = Uuid::new_v4();
+        let idle = context(Vocation::EliteKnight, &[]);
+        let fighting = RegenContext { in_combat: true, ..idle.clone() };
+
+        regen.tick(id, &idle, 0);
+        assert_eq!(regen.tick(id, &idle, 15_000).soul, 1);
+
+        // Combat resets the soul timer
+        regen.tick(id, &fighting, 20_000);
+        assert_eq!(regen.tick(id, &idle, 30_000).soul, 0);
+        assert_eq!(regen.tick(id, &idle, 35_000).soul, 1);
+
+        // Unpromoted vocations regenerate soul more slowly and cap lower
+        let knight = context(Vocation::Knight, &[]);
+        let other = Uuid::new_v4();
+        regen.tick(other, &knight, 0);
+        assert_eq!(regen.tick(other, &knight, 15_000).soul, 0);
+        assert_eq!(regen.tick(other, &knight, 120_000).soul, 1);
+        let full = RegenContext { soul: 100, ..knight };
+        assert_eq!(regen.tick(other, &full, 240_000).soul, 0);
+    }
+
+    #[test]
+    fn test_regenerate_applies_to_creature() {
+        use shadow_world::creature::{Condition, CreatureType};
+        use shadow_world::position::Position;
+
+        let mut regen = RegenerationSystem::default();
+        let id = Uuid::new_v4();
+        let mut creature = Creature::new("Knight".to_string(), CreatureType::Player, Position::new(100, 100, 7));
+        creature.stats.level = 50;
+        creature.stats.health = 98;
+        creature.stats.max_health = 100;
+        creature.stats.mana = 10;
+        creature.stats.max_mana = 50;
+
+        regen.feed(id, 60_000, 0);
+        regen.regenerate(id, Vocation::Knight, &mut creature, false, 0);
+        let tick = regen.regenerate(id, Vocation::Knight, &mut creature, false, 6_000);
+        assert_eq!((tick.health, tick.mana), (3, 1));
+        // Health stops at its maximum
+        assert_eq!((creature.stats.health, creature.stats.mana), (100, 11));
+
+        // A paralyzed creature doesn't regenerate from food
+        creature.stats.health = 50;
+        creature.conditions.push(Condition::new(WorldCondition::Paralyze, 10_000));
+        assert!(regen.regenerate(id, Vocation::Knight, &mut creature, false, 12_000).health == 0);
+        assert_eq!(creature.stats.health, 50);
+
+        // Nor does a dead one
+        creature.conditions.clear();
+        creature.stats.health = 0;
+        assert!(regen.regenerate(id, Vocation::Knight, &mut creature, false, 30_000).is_empty());
+        assert_eq!(creature.stats.health, 0);
+
+        regen.retain(|_| false);
+        assert_eq!(regen.food_remaining(id), 0);
+    }
+}
@@ -0,0 +1,269 @@
+//! Player Reports
+//!
+//! Players report each other for botting, cheating, harassment and the
+//! like. Cheat reports put the target under anti-cheat monitoring; all
+//! other reports open a support ticket. Each reporter may only file a
+//! limited number of reports per hour, and repeating the same report
+//! against the same target is suppressed.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use shadow_anticheat::AntiCheatSystem;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// What a player is reported for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ReportCategory {
+    Botting,
+    Cheating,
+    Harassment,
+    Spam,
+    Scamming,
+    OffensiveName,
+    Other,
+}
+
+impl ReportCategory {
+    /// Whether the report is handled by the anti-cheat system
+    pub fn is_cheat(&self) -> bool {
+        matches!(self, ReportCategory::Botting | ReportCategory::Cheating)
+    }
+}
+
+/// Report limits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportConfig {
+    /// Reports a player may file per hour
+    pub max_reports_per_hour: u32,
+    /// Window in which the same report against the same target is ignored
+    pub duplicate_window_hours: u32,
+    /// Longest accepted details text
+    pub max_details_length: usize,
+}
+
+impl Default for ReportConfig {
+    fn default() -> Self {
+        Self {
+            max_reports_per_hour: 5,
+            duplicate_window_hours: 24,
+            max_details_length: 500,
+        }
+    }
+}
+
+/// Where a report was sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReportRouting {
+    /// Target put under anti-cheat monitoring
+    AntiCheat,
+    /// Support ticket opened
+    Ticket(Uuid),
+}
+
+/// A filed report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerReport {
+    pub id: Uuid,
+    pub reporter_id: Uuid,
+    pub target_id: Uuid,
+    pub category: ReportCategory,
+    pub details: String,
+    pub routing: ReportRouting,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Support ticket system
+#[async_trait]
+pub trait SupportTickets: Send + Sync {
+    /// Open a ticket for a report, returning the ticket id
+    async fn open_ticket(&self, report: &PlayerReport) -> crate::Result<Uuid>;
+}
+
+/// Report errors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReportError {
+    /// Players cannot report themselves
+    SelfReport,
+    /// Reporter filed too many reports in the last hour
+    RateLimited,
+    /// The same report was already filed recently
+    Duplicate,
+    /// The ticket could not be opened
+    Ticket(String),
+}
+
+impl std::fmt::Display for ReportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReportError::SelfReport => write!(f, "You cannot report yourself"),
+            ReportError::RateLimited => write!(f, "Too many reports, try again later"),
+            ReportError::Duplicate => write!(f, "You already reported this player"),
+            ReportError::Ticket(e) => write!(f, "Failed to open ticket: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ReportError {}
+
+/// Files player reports and routes them
+pub struct ReportService {
+    config: ReportConfig,
+    tickets: Arc<dyn SupportTickets>,
+    reports: Vec<PlayerReport>,
+}
+
+impl ReportService {
+    pub fn new(config: ReportConfig, tickets: Arc<dyn SupportTickets>) -> Self {
+        Self {
+            config,
+            tickets,
+            reports: Vec::new(),
+        }
+    }
+
+    /// File a report against `target`
+    pub async fn report(
+        &mut self,
+        anticheat: &mut AntiCheatSystem,
+        reporter: Uuid,
+        target: Uuid,
+        category: ReportCategory,
+        details: &str,
+        now: DateTime<Utc>,
+    ) -> Result<PlayerReport, ReportError> {
+        if reporter == target {
+            return Err(ReportError::SelfReport);
+        }
+
+        let duplicate_since = now - Duration::hours(self.config.duplicate_window_hours as i64);
+        if self.reports.iter().any(|r| {
+            r.reporter_id == reporter && r.target_id == target && r.category == category && r.created_at > duplicate_since
+        }) {
+            return Err(ReportError::Duplicate);
+        }
+
+        let hour_ago = now - Duration::hours(1);
+        let recent = self.reports.iter().filter(|r| r.reporter_id == reporter && r.created_at > hour_ago).count();
+        if recent >= self.config.max_reports_per_hour as usize {
+            return Err(ReportError::RateLimited);
+        }
+
+        let mut report = PlayerReport {
+            id: Uuid::new_v4(),
+            reporter_id: reporter,
+            target_id: target,
+            category,
+            details: details.trim().chars().take(self.config.max_details_length).collect(),
+            routing: ReportRouting::AntiCheat,
+            created_at: now,
+        };
+
+        if category.is_cheat() {
+            anticheat.flag(target);
+        } else {
+            let ticket = self.tickets.open_ticket(&report).await.map_err(|e| ReportError::Ticket(e.to_string()))?;
+            report.routing = ReportRouting::Ticket(ticket);
+        }
+
+        tracing::info!("Player {} reported {} for {:?}", reporter, target, category);
+        self.reports.push(report.clone());
+        Ok(report)
+    }
+
+    /// Reports filed against a character
+    pub fn reports_against(&self, target: Uuid) -> Vec<&PlayerReport> {
+        self.reports.iter().filter(|r| r.target_id == target).collect()
+    }
+
+    /// Forget reports older than the duplicate window
+    pub fn cleanup(&mut self, now: DateTime<Utc>) {
+        let cutoff = now - Duration::hours(self.config.duplicate_window_hours.max(1) as i64);
+        self.reports.retain(|r| r.created_at > cutoff);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shadow_anticheat::AntiCheatConfig;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MemoryTickets {
+        opened: Mutex<Vec<Uuid>>,
+    }
+
+    #[async_trait]
+    impl SupportTickets for MemoryTickets {
+        async fn open_ticket(&self, report: &PlayerReport) -> crate::Result<Uuid> {
+            self.opened.lock().unwrap().push(report.id);
+            Ok(Uuid::new_v4())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cheat_report_flags_target() {
+        let tickets = Arc::new(MemoryTickets::default());
+        let mut service = ReportService::new(ReportConfig::default(), tickets.clone());
+        let mut anticheat = AntiCheatSystem::new(AntiCheatConfig::default());
+        let (reporter, bot) = (Uuid::new_v4(), Uuid::new_v4());
+        let now = Utc::now();
+
+        let report = service
+            .report(&mut anticheat, reporter, bot, ReportCategory::Botting, "walks the same path all night", now)
+            .await
+            .unwrap();
+        assert_eq!(report.routing, ReportRouting::AntiCheat);
+        assert!(anticheat.is_monitored(bot));
+        assert!(tickets.opened.lock().unwrap().is_empty());
+
+        // Harassment goes to support, not anti-cheat
+        let rude = Uuid::new_v4();
+        let report = service
+            .report(&mut anticheat, reporter, rude, ReportCategory::Harassment, "insults", now)
+            .await
+            .unwrap();
+        assert!(matches!(report.routing, ReportRouting::Ticket(_)));
+        assert!(!anticheat.is_monitored(rude));
+        assert_eq!(*tickets.opened.lock().unwrap(), vec![report.id]);
+
+        assert_eq!(
+            service.report(&mut anticheat, reporter, reporter, ReportCategory::Other, "", now).await.unwrap_err(),
+            ReportError::SelfReport
+        );
+    }
+
+    #[tokio::test]
+    async fn test_repeated_reports_rate_limited() {
+        let config = ReportConfig { max_reports_per_hour: 3, ..Default::default() };
+        let mut service = ReportService::new(config, Arc::new(MemoryTickets::default()));
+        let mut anticheat = AntiCheatSystem::new(AntiCheatConfig::default());
+        let (reporter, target) = (Uuid::new_v4(), Uuid::new_v4());
+        let now = Utc::now();
+
+        service.report(&mut anticheat, reporter, target, ReportCategory::Spam, "spam", now).await.unwrap();
+        assert_eq!(
+            service.report(&mut anticheat, reporter, target, ReportCategory::Spam, "spam again", now).await.unwrap_err(),
+            ReportError::Duplicate
+        );
+
+        for _ in 0..2 {
+            service.report(&mut anticheat, reporter, Uuid::new_v4(), ReportCategory::Spam, "spam", now).await.unwrap();
+        }
+        assert_eq!(
+            service.report(&mut anticheat, reporter, Uuid::new_v4(), ReportCategory::Spam, "spam", now).await.unwrap_err(),
+            ReportError::RateLimited
+        );
+
+        // The limit is per hour; the duplicate window is longer
+        let later = now + Duration::hours(2);
+        service.report(&mut anticheat, reporter, Uuid::new_v4(), ReportCategory::Spam, "spam", later).await.unwrap();
+        assert_eq!(
+            service.report(&mut anticheat, reporter, target, ReportCategory::Spam, "spam", later).await.unwrap_err(),
+            ReportError::Duplicate
+        );
+        assert_eq!(service.reports_against(target).len(), 1);
+    }
+}
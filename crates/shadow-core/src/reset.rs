@@ -0,0 +1,242 @@
+//! Daily and weekly reset service
+//!
+//! One clock for every system that resets on a schedule (tasks, daily
+//! rewards, boosted creature rotation, prey rerolls). Reset times are
+//! configured in server-local time via a fixed UTC offset. Each tick
+//! compares the latest reset boundary with the last one processed, so a
+//! server that was offline across a reset fires it exactly once on start
+//! instead of skipping it or replaying every missed day. On accelerated
+//! test realms the service follows the realm's game clock.
+
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveTime, TimeZone, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use shadow_world::clock::GameClock;
+use tokio::sync::broadcast;
+
+/// Reset schedule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResetConfig {
+    /// Server-local offset from UTC in minutes
+    pub utc_offset_minutes: i32,
+    /// Local hour of the daily reset (server save)
+    pub hour: u32,
+    /// Local minute of the daily reset
+    pub minute: u32,
+    /// Day of the weekly reset, at the daily reset time
+    pub weekly_day: Weekday,
+}
+
+impl Default for ResetConfig {
+    fn default() -> Self {
+        Self {
+            utc_offset_minutes: 60,
+            hour: 10,
+            minute: 0,
+            weekly_day: Weekday::Wed,
+        }
+    }
+}
+
+/// Kind of reset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ResetKind {
+    Daily,
+    Weekly,
+}
+
+/// A reset that subscribers should apply
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResetEvent {
+    pub kind: ResetKind,
+    /// The scheduled reset time this event is for
+    pub boundary: DateTime<Utc>,
+}
+
+/// Emits daily and weekly reset events
+pub struct ResetService {
+    config: ResetConfig,
+    last_daily: Option<DateTime<Utc>>,
+    last_weekly: Option<DateTime<Utc>>,
+    event_tx: broadcast::Sender<ResetEvent>,
+    clock: GameClock,
+}
+
+impl ResetService {
+    pub fn new(config: ResetConfig) -> Self {
+        let (event_tx, _) = broadcast::channel(16);
+        Self {
+            config,
+            last_daily: None,
+            last_weekly: None,
+            event_tx,
+            clock: GameClock::real_time(),
+        }
+    }
+
+    /// Follow `clock` instead of wall-clock time
+    pub fn with_clock(mut self, clock: GameClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Restore the last processed resets, e.g. from the database on start
+    pub fn with_last_resets(mut self, daily: Option<DateTime<Utc>>, weekly: Option<DateTime<Utc>>) -> Self {
+        self.last_daily = daily;
+        self.last_weekly = weekly;
+        self
+    }
+
+    /// Receive reset events
+    pub fn subscribe(&self) -> broadcast::Receiver<ResetEvent> {
+        self.event_tx.subscribe()
+    }
+
+    /// Last processed daily and weekly boundaries, for persisting
+    pub fn last_resets(&self) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+        (self.last_daily, self.last_weekly)
+    }
+
+    fn offset(&self) -> FixedOffset {
+        FixedOffset::east_opt(self.config.utc_offset_minutes * 60).unwrap_or(FixedOffset::east_opt(0).unwrap())
+    }
+
+    fn reset_time(&self) -> NaiveTime {
+        NaiveTime::from_hms_opt(self.config.hour, self.config.minute, 0).unwrap_or(NaiveTime::MIN)
+    }
+
+    /// Latest daily reset at or before `now`
+    pub fn previous_daily(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let offset = self.offset();
+        let today = now.with_timezone(&offset).date_naive();
+        let boundary = |date: chrono::NaiveDate| {
+            offset.from_local_datetime(&date.and_time(self.reset_time())).unwrap().with_timezone(&Utc)
+        };
+        let candidate = boundary(today);
+        if candidate <= now {
+            candidate
+        } else {
+            boundary(today - Duration::days(1))
+        }
+    }
+
+    /// Latest weekly reset at or before `now`
+    pub fn previous_weekly(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let daily = self.previous_daily(now);
+        let weekday = daily.with_timezone(&self.offset()).weekday();
+        let days_back = (weekday.num_days_from_monday() + 7 - self.config.weekly_day.num_days_from_monday()) % 7;
+        daily - Duration::days(days_back as i64)
+    }
+
+    /// Next daily reset after `now`
+    pub fn next_daily(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        self.previous_daily(now) + Duration::days(1)
+    }
+
+    /// Next weekly reset after `now`
+    pub fn next_weekly(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        self.previous_weekly(now) + Duration::weeks(1)
+    }
+
+    /// Fire any reset whose boundary passed since the last tick. A daily
+    /// event always precedes a weekly one on the same boundary.
+    pub fn tick(&mut self, now: DateTime<Utc>) -> Vec<ResetEvent> {
+        let mut events = Vec::new();
+
+        let daily = self.previous_daily(now);
+        if self.last_daily.is_none_or(|last| last < daily) {
+            self.last_daily = Some(daily);
+            events.push(ResetEvent { kind: ResetKind::Daily, boundary: daily });
+        }
+
+        let weekly = self.previous_weekly(now);
+        if self.last_weekly.is_none_or(|last| last < weekly) {
+            self.last_weekly = Some(weekly);
+            events.push(ResetEvent { kind: ResetKind::Weekly, boundary: weekly });
+        }
+
+        for event in &events {
+            tracing::info!("{:?} reset for {}", event.kind, event.boundary);
+            let _ = self.event_tx.send(*event);
+        }
+        events
+    }
+
+    /// Fire resets due at the current game time
+    pub fn poll(&mut self) -> Vec<ResetEvent> {
+        let now = self.clock.now();
+        self.tick(now)
+    }
+}
+
+impl Default for ResetService {
+    fn default() -> Self {
+        Self::new(ResetConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    #[test]
+    fn test_missed_reset_fires_once() {
+        // 10:00 CET is 09:00 UTC
+        let config = ResetConfig { utc_offset_minutes: 60, hour: 10, minute: 0, weekly_day: Weekday::Mon };
+        let last_daily = utc(2024, 3, 4, 9, 0);
+        let mut service = ResetService::new(config).with_last_resets(Some(last_daily), Some(last_daily));
+        let mut events = service.subscribe();
+
+        // Server was down for three resets and starts Thursday morning
+        let start = utc(2024, 3, 7, 12, 30);
+        let fired = service.tick(start);
+        assert_eq!(fired, vec![ResetEvent { kind: ResetKind::Daily, boundary: utc(2024, 3, 7, 9, 0) }]);
+        assert_eq!(events.try_recv().unwrap().kind, ResetKind::Daily);
+
+        assert!(service.tick(start + Duration::hours(1)).is_empty());
+        assert_eq!(service.next_daily(start), utc(2024, 3, 8, 9, 0));
+
+        // Before today's reset time the previous day's boundary applies
+        assert_eq!(service.previous_daily(utc(2024, 3, 8, 8, 59)), utc(2024, 3, 7, 9, 0));
+    }
+
+    #[test]
+    fn test_weekly_and_daily_boundaries() {
+        // 2024-03-05 is a Tuesday; weekly reset on Wednesday
+        let mut service = ResetService::new(ResetConfig { utc_offset_minutes: 0, ..Default::default() });
+        let tuesday = utc(2024, 3, 5, 10, 0);
+        service.tick(tuesday);
+
+        // Wednesday before the reset: nothing
+        assert!(service.tick(utc(2024, 3, 6, 9, 59)).is_empty());
+
+        let fired = service.tick(utc(2024, 3, 6, 10, 0));
+        let kinds: Vec<ResetKind> = fired.iter().map(|e| e.kind).collect();
+        assert_eq!(kinds, vec![ResetKind::Daily, ResetKind::Weekly]);
+
+        // Thursday is daily only
+        let fired = service.tick(utc(2024, 3, 7, 10, 0));
+        assert_eq!(fired.iter().map(|e| e.kind).collect::<Vec<_>>(), vec![ResetKind::Daily]);
+        assert_eq!(service.next_weekly(utc(2024, 3, 7, 10, 0)), utc(2024, 3, 13, 10, 0));
+        assert_eq!(service.previous_weekly(utc(2024, 3, 6, 9, 0)), utc(2024, 2, 28, 10, 0));
+    }
+
+    #[test]
+    fn test_accelerated_daily_reset() {
+        // One game day per real minute
+        let start = utc(2024, 3, 5, 10, 0);
+        let clock = GameClock::starting_at(1440.0, start);
+        let mut service = ResetService::new(ResetConfig { utc_offset_minutes: 0, ..Default::default() }).with_clock(clock);
+        service.tick(clock.game_time_at(start));
+
+        assert!(service.tick(clock.game_time_at(start + Duration::seconds(59))).is_empty());
+        let fired = service.tick(clock.game_time_at(start + Duration::seconds(60)));
+        assert_eq!(fired, vec![
+            ResetEvent { kind: ResetKind::Daily, boundary: utc(2024, 3, 6, 10, 0) },
+            ResetEvent { kind: ResetKind::Weekly, boundary: utc(2024, 3, 6, 10, 0) },
+        ]);
+    }
+}
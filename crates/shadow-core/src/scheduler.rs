@@ -5,6 +5,7 @@ use std::cmp::Ordering;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use shadow_world::clock::GameClock;
 use tokio::sync::{mpsc, Mutex};
 use uuid::Uuid;
 
@@ -26,8 +27,13 @@ pub enum TaskType {
     DecayItems,
     CleanupSessions,
     ProcessRents,
+    ExpireMarketOffers,
     UpdateHighscores,
+    /// Take a database backup
+    Backup,
     SeasonalEvent(String),
+    /// Start the named raid
+    StartRaid(String),
     Custom(String),
 }
 
@@ -56,6 +62,8 @@ impl Ord for ScheduledTask {
 pub struct Scheduler {
     tasks: Arc<Mutex<BinaryHeap<ScheduledTask>>>,
     task_tx: mpsc::Sender<ScheduledTask>,
+    /// Delays and intervals are in game time and shrink on accelerated realms
+    clock: GameClock,
 }
 
 impl Scheduler {
@@ -64,16 +72,23 @@ impl Scheduler {
         let scheduler = Self {
             tasks: Arc::new(Mutex::new(BinaryHeap::new())),
             task_tx,
+            clock: GameClock::real_time(),
         };
         (scheduler, task_rx)
     }
 
+    /// Run task delays and intervals on `clock`
+    pub fn with_clock(mut self, clock: GameClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// Schedule a one-time task
     pub async fn schedule_once(&self, name: &str, delay: Duration, task_type: TaskType) -> Uuid {
         let task = ScheduledTask {
             id: Uuid::new_v4(),
             name: name.to_string(),
-            execute_at: Instant::now() + delay,
+            execute_at: Instant::now() + self.clock.real_duration(delay),
             interval: None,
             task_type,
         };
@@ -93,7 +108,7 @@ impl Scheduler {
         let task = ScheduledTask {
             id: Uuid::new_v4(),
             name: name.to_string(),
-            execute_at: Instant::now() + initial_delay,
+            execute_at: Instant::now() + self.clock.real_duration(initial_delay),
             interval: Some(interval),
             task_type,
         };
@@ -113,9 +128,13 @@ impl Scheduler {
 
     /// Process due tasks
     pub async fn process_due(&self) -> Vec<ScheduledTask> {
+        self.process_due_at(Instant::now()).await
+    }
+
+    /// Process tasks due at `now`
+    pub async fn process_due_at(&self, now: Instant) -> Vec<ScheduledTask> {
         let mut tasks = self.tasks.lock().await;
         let mut due = Vec::new();
-        let now = Instant::now();
 
         while let Some(task) = tasks.peek() {
             if task.execute_at <= now {
@@ -126,7 +145,7 @@ impl Scheduler {
                     let rescheduled = ScheduledTask {
                         id: task.id,
                         name: task.name.clone(),
-                        execute_at: Instant::now() + interval,
+                        execute_at: now + self.clock.real_duration(interval),
                         interval: Some(interval),
                         task_type: task.task_type.clone(),
                     };
@@ -148,3 +167,38 @@ impl Default for Scheduler {
         Self::new().0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_accelerated_tasks_fire_sooner() {
+        let (scheduler, _rx) = Scheduler::new();
+        let scheduler = scheduler.with_clock(GameClock::new(60.0));
+        let start = Instant::now();
+        scheduler
+            .schedule_recurring("decay", Duration::from_secs(600), Duration::from_secs(600), TaskType::DecayItems)
+            .await;
+
+        // Ten game minutes pass in ten real seconds
+        assert!(scheduler.process_due_at(start + Duration::from_secs(9)).await.is_empty());
+        let due = scheduler.process_due_at(start + Duration::from_secs(11)).await;
+        assert_eq!(due.len(), 1);
+        assert!(matches!(due[0].task_type, TaskType::DecayItems));
+
+        // The next run is rescheduled on the same scale
+        assert!(scheduler.process_due_at(start + Duration::from_secs(20)).await.is_empty());
+        assert_eq!(scheduler.process_due_at(start + Duration::from_secs(22)).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_real_time_scheduler_unchanged() {
+        let (scheduler, _rx) = Scheduler::new();
+        let start = Instant::now();
+        scheduler.schedule_once("save", Duration::from_secs(600), TaskType::SaveWorld).await;
+
+        assert!(scheduler.process_due_at(start + Duration::from_secs(11)).await.is_empty());
+        assert_eq!(scheduler.process_due_at(start + Duration::from_secs(601)).await.len(), 1);
+    }
+}
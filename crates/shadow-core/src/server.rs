@@ -16,6 +16,7 @@ use shadow_world::{Map, OtbmLoader, SpawnManager, MonsterLoader, NpcLoader, Item
 use crate::config::ServerConfig;
 use crate::engine::{EngineCommand, GameEngine};
 use crate::player::PlayerManager;
+use crate::rcon::RconService;
 use crate::state::GameState;
 use crate::{CoreError, Result, SharedState};
 
@@ -61,7 +62,11 @@ impl ShadowServer {
         self.load_world_data().await?;
 
         // Initialize game engine
-        self.engine = Some(GameEngine::new(self.config.clone(), self.state.clone()));
+        let mut engine = GameEngine::new(self.config.clone(), self.state.clone()).with_players(self.player_manager.clone());
+        if let Some(pool) = &self.db_pool {
+            engine = engine.with_database(pool.clone());
+        }
+        self.engine = Some(engine);
 
         tracing::info!("Server initialization complete");
         Ok(())
@@ -212,6 +217,7 @@ impl ShadowServer {
         let login_handle = self.spawn_login_server();
         let game_handle = self.spawn_game_server();
         let api_handle = self.spawn_api_server();
+        let rcon_handle = self.spawn_rcon_server();
 
         // Get engine command sender for shutdown
         let engine_cmd_tx = self.engine.as_ref().map(|e| e.command_sender());
@@ -252,6 +258,9 @@ impl ShadowServer {
         login_handle.abort();
         game_handle.abort();
         api_handle.abort();
+        if let Some(handle) = rcon_handle {
+            handle.abort();
+        }
 
         // Save all player data
         self.save_all_players().await?;
@@ -360,6 +369,39 @@ impl ShadowServer {
         })
     }
 
+    /// Console service wired to this server's players, engine and audit log
+    pub fn rcon_service(&self) -> Option<RconService> {
+        let engine = self.engine.as_ref()?;
+        let mut service = RconService::new(self.config.rcon.clone(), self.player_manager.clone(), engine.event_broadcaster())
+            .with_engine(engine.command_sender());
+        if let Some(pool) = &self.db_pool {
+            service = service.with_database(pool.clone());
+        }
+        Some(service)
+    }
+
+    fn spawn_rcon_server(&self) -> Option<JoinHandle<()>> {
+        if !self.config.rcon.enabled {
+            return None;
+        }
+        let service = Arc::new(self.rcon_service()?);
+        let rcon_addr = format!("{}:{}", self.config.rcon.host, self.config.rcon.port);
+
+        Some(tokio::spawn(async move {
+            tracing::info!("Starting RCON console on {}", rcon_addr);
+            match tokio::net::TcpListener::bind(&rcon_addr).await {
+                Ok(listener) => {
+                    if let Err(e) = service.serve(listener).await {
+                        tracing::error!("RCON console error: {}", e);
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("Failed to start RCON console: {}", e);
+                }
+            }
+        }))
+    }
+
     async fn save_all_players(&self) -> Result<()> {
         tracing::info!("Saving all player data...");
 
@@ -385,13 +427,8 @@ impl ShadowServer {
         pool: &DatabasePool,
         player: &crate::player::Player,
     ) -> Result<()> {
-        // Save player stats, inventory, skills to database
         tracing::debug!("Saving player: {}", player.name);
-        
-        // Player stats are saved via shadow_db repositories
-        // This would call shadow_db::repositories::characters::save(pool, player)
-        
-        Ok(())
+        crate::player::save_player(pool, player).await
     }
 
     /// Get server configuration
@@ -1,9 +1,12 @@
 //! Player session management
 
 use chrono::{DateTime, Utc};
+use shadow_combat::{CombatLockError, CombatLockManager};
+use shadow_protocol::ProtocolVersion;
+use shadow_world::tile::TileFlags;
 use uuid::Uuid;
 
-use crate::{CharacterId, PlayerId, RealmId};
+use crate::{CharacterId, CoreError, PlayerId, RealmId, SUPPORTED_PROTOCOL_MAX, SUPPORTED_PROTOCOL_MIN};
 
 /// Represents an active player session
 #[derive(Debug, Clone)]
@@ -18,6 +21,11 @@ pub struct PlayerSession {
     pub protocol_version: u16,
     pub client_version: String,
     pub state: SessionState,
+    /// Version-specific features negotiated for this client
+    pub features: ClientFeatures,
+    /// Logout attempts refused by the combat lock
+    pub rejected_logouts: u32,
+    pub last_logout_rejection: Option<CombatLockError>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -46,6 +54,9 @@ impl PlayerSession {
             protocol_version,
             client_version: String::new(),
             state: SessionState::Connected,
+            features: ClientFeatures::default(),
+            rejected_logouts: 0,
+            last_logout_rejection: None,
         }
     }
 
@@ -62,6 +73,36 @@ impl PlayerSession {
         self.touch();
     }
 
+    /// Negotiate the session's protocol version, enabling its feature set
+    pub fn negotiate(&mut self, negotiator: &ProtocolNegotiator) -> crate::Result<ClientFeatures> {
+        let features = negotiator.negotiate(self.protocol_version)?;
+        self.features = features;
+        Ok(features)
+    }
+
+    /// Ask to log out the session's character (creature `creature_id`,
+    /// standing on `tile`). A refused attempt is recorded on the session.
+    pub fn request_logout(
+        &mut self,
+        locks: &CombatLockManager,
+        creature_id: u32,
+        tile: TileFlags,
+        current_time: u64,
+    ) -> Result<(), CombatLockError> {
+        self.touch();
+        match locks.check_logout(creature_id, tile, current_time) {
+            Ok(()) => {
+                self.state = SessionState::Disconnecting;
+                Ok(())
+            }
+            Err(error) => {
+                self.rejected_logouts += 1;
+                self.last_logout_rejection = Some(error);
+                Err(error)
+            }
+        }
+    }
+
     pub fn touch(&mut self) {
         self.last_activity = Utc::now();
     }
@@ -78,3 +119,131 @@ impl PlayerSession {
         self.idle_duration().num_seconds() > max_idle_seconds
     }
 }
+
+/// Features enabled for a client based on its protocol version
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ClientFeatures {
+    pub extended_sprites: bool,
+    pub mounts: bool,
+    pub imbuements: bool,
+    pub market: bool,
+}
+
+impl ClientFeatures {
+    pub fn for_version(version: ProtocolVersion) -> Self {
+        Self {
+            extended_sprites: version.supports_extended_sprites(),
+            mounts: version.supports_mounts(),
+            imbuements: version.supports_imbuements(),
+            market: version.supports_market(),
+        }
+    }
+}
+
+/// Accepts or rejects client protocol versions at login
+#[derive(Debug, Clone)]
+pub struct ProtocolNegotiator {
+    min_version: u16,
+    max_version: u16,
+    /// Exact versions allowed; empty allows the whole range
+    allowed_versions: Vec<u16>,
+}
+
+impl ProtocolNegotiator {
+    pub fn new(min_version: u16, max_version: u16) -> Self {
+        Self {
+            min_version,
+            max_version,
+            allowed_versions: Vec::new(),
+        }
+    }
+
+    /// Only accept these exact versions (e.g. `security.allowed_client_versions`)
+    pub fn with_allowed_versions(mut self, versions: Vec<u16>) -> Self {
+        self.allowed_versions = versions;
+        self
+    }
+
+    pub fn is_supported(&self, version: u16) -> bool {
+        (self.min_version..=self.max_version).contains(&version)
+            && (self.allowed_versions.is_empty() || self.allowed_versions.contains(&version))
+    }
+
+    /// Negotiate a client's reported version, returning the features to enable
+    pub fn negotiate(&self, version: u16) -> crate::Result<ClientFeatures> {
+        if !self.is_supported(version) {
+            return Err(CoreError::Protocol(format!(
+                "Client version {} is not supported (supported: {}-{})",
+                version, self.min_version, self.max_version
+            )));
+        }
+        Ok(ClientFeatures::for_version(ProtocolVersion::from_version(version)))
+    }
+}
+
+impl Default for ProtocolNegotiator {
+    fn default() -> Self {
+        Self::new(SUPPORTED_PROTOCOL_MIN, SUPPORTED_PROTOCOL_MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_negotiate_in_range_version() {
+        let negotiator = ProtocolNegotiator::default();
+
+        let mut session = PlayerSession::new("127.0.0.1".to_string(), 1310);
+        let features = session.negotiate(&negotiator).unwrap();
+        assert_eq!(
+            features,
+            ClientFeatures { extended_sprites: true, mounts: true, imbuements: true, market: true }
+        );
+        assert_eq!(session.features, features);
+
+        let legacy = negotiator.negotiate(860).unwrap();
+        assert!(!legacy.extended_sprites);
+        assert!(!legacy.mounts);
+        assert!(!legacy.imbuements);
+        assert!(!legacy.market);
+
+        let mid = negotiator.negotiate(1098).unwrap();
+        assert!(mid.mounts && mid.market && !mid.imbuements);
+    }
+
+    #[test]
+    fn test_negotiate_rejects_unsupported_version() {
+        let negotiator = ProtocolNegotiator::default();
+        assert!(matches!(negotiator.negotiate(760), Err(CoreError::Protocol(_))));
+        assert!(negotiator.negotiate(1320).is_err());
+
+        let mut session = PlayerSession::new("127.0.0.1".to_string(), 1400);
+        assert!(session.negotiate(&negotiator).is_err());
+        assert_eq!(session.features, ClientFeatures::default());
+
+        let restricted = ProtocolNegotiator::default().with_allowed_versions(vec![1098, 1310]);
+        assert!(restricted.negotiate(1098).is_ok());
+        assert!(restricted.negotiate(1200).is_err());
+    }
+
+    #[test]
+    fn test_logout_rejected_during_combat_lock() {
+        use shadow_combat::{CombatLockConfig, PvpAction};
+
+        let mut locks = CombatLockManager::new(CombatLockConfig::default());
+        let street = TileFlags::new();
+        locks.record_pvp(PvpAction { attacker_id: 1, target_id: 2, target_marked: false, tile: street }, 0);
+
+        let mut session = PlayerSession::new("127.0.0.1".to_string(), 1310);
+        session.enter_game(Uuid::new_v4(), Uuid::new_v4());
+        assert!(matches!(session.request_logout(&locks, 1, street, 30_000), Err(CombatLockError::InFight { .. })));
+        assert_eq!(session.rejected_logouts, 1);
+        assert_eq!(session.state, SessionState::InGame);
+
+        assert!(session.request_logout(&locks, 1, street, 60_000).is_ok());
+        assert_eq!(session.state, SessionState::Disconnecting);
+        assert_eq!(session.rejected_logouts, 1);
+    }
+}
@@ -0,0 +1,379 @@
+//! Telemetry export of game events to an external sink
+//!
+//! Game events are handed to a bounded queue and shipped in batches to a
+//! pluggable `EventSink` (Kafka, HTTP, file, ...) by a background task.
+//! Publishing never waits: when the queue is full the event is dropped and
+//! counted, so a slow or failing sink cannot stall the game loop. Batches
+//! are retried until the sink accepts them (at-least-once); a batch that
+//! still fails after the retry limit goes to the dead-letter sink.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+use crate::events::GameEvent;
+
+/// Destination for exported events
+#[async_trait]
+pub trait EventSink: Send + Sync {
+    /// Sink name for logging
+    fn name(&self) -> &str;
+
+    /// Deliver a batch. An error means the whole batch is sent again.
+    async fn send_batch(&self, events: &[GameEvent]) -> crate::Result<()>;
+}
+
+/// Telemetry export configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelemetryConfig {
+    /// Export is opt-in
+    pub enabled: bool,
+    /// Events per batch
+    pub batch_size: usize,
+    /// Longest time a partial batch waits before it is sent
+    pub flush_interval_ms: u64,
+    /// Events buffered before new ones are dropped
+    pub queue_capacity: usize,
+    /// Retries of a failed batch before it is dead-lettered
+    pub max_retries: u32,
+    /// Delay before the first retry; doubles on each further retry
+    pub retry_backoff_ms: u64,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            batch_size: 100,
+            flush_interval_ms: 5_000,
+            queue_capacity: 10_000,
+            max_retries: 3,
+            retry_backoff_ms: 500,
+        }
+    }
+}
+
+/// Export counters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TelemetryStats {
+    /// Events accepted into the queue
+    pub published: u64,
+    /// Events dropped because the queue was full or the bus lagged
+    pub dropped: u64,
+    /// Events the sink accepted
+    pub delivered: u64,
+    /// Events handed to the dead-letter sink
+    pub dead_lettered: u64,
+    /// Events lost because the dead-letter sink failed too
+    pub lost: u64,
+    /// Failed batch deliveries, including retries
+    pub failed_attempts: u64,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    published: AtomicU64,
+    dropped: AtomicU64,
+    delivered: AtomicU64,
+    dead_lettered: AtomicU64,
+    lost: AtomicU64,
+    failed_attempts: AtomicU64,
+}
+
+impl Counters {
+    fn add(counter: &AtomicU64, n: usize) {
+        counter.fetch_add(n as u64, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> TelemetryStats {
+        TelemetryStats {
+            published: self.published.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+            delivered: self.delivered.load(Ordering::Relaxed),
+            dead_lettered: self.dead_lettered.load(Ordering::Relaxed),
+            lost: self.lost.load(Ordering::Relaxed),
+            failed_attempts: self.failed_attempts.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Builds and starts the export task
+pub struct TelemetryExporter {
+    config: TelemetryConfig,
+    sink: Arc<dyn EventSink>,
+    dead_letter: Option<Arc<dyn EventSink>>,
+}
+
+impl TelemetryExporter {
+    pub fn new(config: TelemetryConfig, sink: Arc<dyn EventSink>) -> Self {
+        Self {
+            config,
+            sink,
+            dead_letter: None,
+        }
+    }
+
+    /// Sink receiving batches that exhausted their retries
+    pub fn with_dead_letter(mut self, sink: Arc<dyn EventSink>) -> Self {
+        self.dead_letter = Some(sink);
+        self
+    }
+
+    /// Spawn the export task. Returns `None` when telemetry is disabled.
+    pub fn start(self) -> Option<TelemetryHandle> {
+        if !self.config.enabled {
+            return None;
+        }
+
+        let (tx, rx) = mpsc::channel(self.config.queue_capacity.max(1));
+        let counters = Arc::new(Counters::default());
+        let worker = tokio::spawn(self.run(rx, counters.clone()));
+        tracing::info!("Telemetry export started");
+
+        Some(TelemetryHandle {
+            tx,
+            counters,
+            worker,
+            forwarders: Vec::new(),
+        })
+    }
+
+    async fn run(self, mut rx: mpsc::Receiver<GameEvent>, counters: Arc<Counters>) {
+        let batch_size = self.config.batch_size.max(1);
+        let flush_interval = Duration::from_millis(self.config.flush_interval_ms);
+        let mut batch = Vec::with_capacity(batch_size);
+
+        while let Some(first) = rx.recv().await {
+            batch.push(first);
+            let deadline = Instant::now() + flush_interval;
+            let mut closed = false;
+
+            while batch.len() < batch_size {
+                match tokio::time::timeout_at(deadline, rx.recv()).await {
+                    Ok(Some(event)) => batch.push(event),
+                    Ok(None) => {
+                        closed = true;
+                        break;
+                    }
+                    Err(_) => break,
+                }
+            }
+
+            self.deliver(&batch, &counters).await;
+            batch.clear();
+            if closed {
+                break;
+            }
+        }
+    }
+
+    async fn deliver(&self, batch: &[GameEvent], counters: &Counters) {
+        let mut backoff = Duration::from_millis(self.config.retry_backoff_ms);
+        for attempt in 0..=self.config.max_retries {
+            match self.sink.send_batch(batch).await {
+                Ok(()) => {
+                    Counters::add(&counters.delivered, batch.len());
+                    return;
+                }
+                Err(e) => {
+                    Counters::add(&counters.failed_attempts, 1);
+                    tracing::warn!(
+                        "Telemetry sink {} rejected {} events (attempt {}): {}",
+                        self.sink.name(), batch.len(), attempt + 1, e
+                    );
+                    if attempt < self.config.max_retries {
+                        tokio::time::sleep(backoff).await;
+                        backoff *= 2;
+                    }
+                }
+            }
+        }
+
+        match &self.dead_letter {
+            Some(dead_letter) => match dead_letter.send_batch(batch).await {
+                Ok(()) => Counters::add(&counters.dead_lettered, batch.len()),
+                Err(e) => {
+                    Counters::add(&counters.lost, batch.len());
+                    tracing::error!(
+                        "Dead-letter sink {} failed, {} events lost: {}",
+                        dead_letter.name(), batch.len(), e
+                    );
+                }
+            },
+            None => {
+                Counters::add(&counters.lost, batch.len());
+                tracing::error!("No dead-letter sink, {} telemetry events lost", batch.len());
+            }
+        }
+    }
+}
+
+/// Handle to a running export task
+pub struct TelemetryHandle {
+    tx: mpsc::Sender<GameEvent>,
+    counters: Arc<Counters>,
+    worker: JoinHandle<()>,
+    forwarders: Vec<JoinHandle<()>>,
+}
+
+impl TelemetryHandle {
+    /// Queue an event for export without waiting. Returns false if the
+    /// queue was full and the event was dropped.
+    pub fn publish(&self, event: GameEvent) -> bool {
+        enqueue(&self.tx, &self.counters, event)
+    }
+
+    /// Export every event sent on an event bus
+    pub fn forward(&mut self, mut events: broadcast::Receiver<GameEvent>) {
+        let tx = self.tx.clone();
+        let counters = self.counters.clone();
+        self.forwarders.push(tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(event) => {
+                        enqueue(&tx, &counters, event);
+                    }
+                    Err(broadcast::error::RecvError::Lagged(missed)) => {
+                        Counters::add(&counters.dropped, missed as usize);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        }));
+    }
+
+    pub fn stats(&self) -> TelemetryStats {
+        self.counters.snapshot()
+    }
+
+    /// Stop forwarding, deliver everything still queued and stop the task
+    pub async fn shutdown(self) -> TelemetryStats {
+        for forwarder in &self.forwarders {
+            forwarder.abort();
+        }
+        drop(self.tx);
+        for forwarder in self.forwarders {
+            let _ = forwarder.await;
+        }
+        if let Err(e) = self.worker.await {
+            tracing::error!("Telemetry export task failed: {}", e);
+        }
+        self.counters.snapshot()
+    }
+}
+
+fn enqueue(tx: &mpsc::Sender<GameEvent>, counters: &Counters, event: GameEvent) -> bool {
+    match tx.try_send(event) {
+        Ok(()) => {
+            Counters::add(&counters.published, 1);
+            true
+        }
+        Err(_) => {
+            Counters::add(&counters.dropped, 1);
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{ServerMessageEvent, ServerMessageType};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MemorySink {
+        failing: bool,
+        batches: Mutex<Vec<Vec<GameEvent>>>,
+        attempts: AtomicU64,
+    }
+
+    #[async_trait]
+    impl EventSink for MemorySink {
+        fn name(&self) -> &str {
+            "memory"
+        }
+
+        async fn send_batch(&self, events: &[GameEvent]) -> crate::Result<()> {
+            self.attempts.fetch_add(1, Ordering::Relaxed);
+            if self.failing {
+                return Err(crate::CoreError::Internal("sink unavailable".into()));
+            }
+            self.batches.lock().unwrap().push(events.to_vec());
+            Ok(())
+        }
+    }
+
+    fn event(n: usize) -> GameEvent {
+        GameEvent::ServerMessage(ServerMessageEvent {
+            message: format!("event {}", n),
+            message_type: ServerMessageType::Info,
+            target_realm: None,
+            timestamp: chrono::Utc::now(),
+        })
+    }
+
+    fn config() -> TelemetryConfig {
+        TelemetryConfig {
+            enabled: true,
+            batch_size: 3,
+            flush_interval_ms: 60_000,
+            retry_backoff_ms: 1,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_events_exported_in_batches() {
+        let sink = Arc::new(MemorySink::default());
+        assert!(TelemetryExporter::new(TelemetryConfig::default(), sink.clone()).start().is_none());
+
+        let handle = TelemetryExporter::new(config(), sink.clone()).start().unwrap();
+        for n in 0..7 {
+            assert!(handle.publish(event(n)));
+        }
+
+        // The trailing partial batch is flushed on shutdown
+        let stats = handle.shutdown().await;
+        let sizes: Vec<usize> = sink.batches.lock().unwrap().iter().map(|b| b.len()).collect();
+        assert_eq!(sizes, vec![3, 3, 1]);
+        assert_eq!((stats.published, stats.delivered, stats.dropped), (7, 7, 0));
+    }
+
+    #[tokio::test]
+    async fn test_failing_sink_does_not_block_publishing() {
+        let failing = Arc::new(MemorySink { failing: true, ..Default::default() });
+        let dead_letter = Arc::new(MemorySink::default());
+        let config = TelemetryConfig { queue_capacity: 4, max_retries: 2, batch_size: 2, ..config() };
+        let handle = TelemetryExporter::new(config, failing.clone())
+            .with_dead_letter(dead_letter.clone())
+            .start()
+            .unwrap();
+
+        // Publishing returns immediately even though nothing drains the queue
+        let publish_all = async {
+            for n in 0..100 {
+                handle.publish(event(n));
+            }
+        };
+        tokio::time::timeout(Duration::from_millis(100), publish_all).await.unwrap();
+
+        let stats = handle.shutdown().await;
+        assert!(stats.dropped > 0);
+        assert_eq!(stats.published + stats.dropped, 100);
+        assert_eq!(stats.delivered, 0);
+
+        // Every accepted event was retried, then dead-lettered
+        let dead: usize = dead_letter.batches.lock().unwrap().iter().map(|b| b.len()).sum();
+        assert_eq!(dead as u64, stats.published);
+        assert_eq!(stats.dead_lettered, stats.published);
+        let batches = dead_letter.batches.lock().unwrap().len() as u64;
+        assert_eq!(failing.attempts.load(Ordering::Relaxed), batches * 3);
+    }
+}
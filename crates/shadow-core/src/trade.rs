@@ -9,6 +9,10 @@ use uuid::Uuid;
 use chrono::{DateTime, Utc, Duration};
 use serde::{Deserialize, Serialize};
 
+use crate::bank::{BankManager, TransactionType};
+use crate::capacity::{CapacityService, CharacterLoad};
+use crate::vip::{StorageCapacity, VipTier};
+
 /// Trade item entry
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TradeItem {
@@ -217,6 +221,26 @@ impl Trade {
         self.state = TradeState::Completed;
     }
 
+    /// Complete an accepted trade if both players can carry what they receive
+    pub fn complete_checked(
+        &mut self,
+        capacity: &CapacityService,
+        player1_load: &CharacterLoad,
+        player2_load: &CharacterLoad,
+    ) -> Result<(), TradeError> {
+        if self.state != TradeState::Accepted {
+            return Err(TradeError::InvalidState);
+        }
+        if !capacity.can_receive_trade(player1_load, &self.player2_items, &self.player1_items) {
+            return Err(TradeError::InsufficientCapacity(self.player1_id));
+        }
+        if !capacity.can_receive_trade(player2_load, &self.player1_items, &self.player2_items) {
+            return Err(TradeError::InsufficientCapacity(self.player2_id));
+        }
+        self.complete();
+        Ok(())
+    }
+
     /// Check if trade expired
     pub fn is_expired(&self) -> bool {
         Utc::now() > self.expires_at
@@ -387,7 +411,12 @@ impl MarketOffer {
 
     /// Check if expired
     pub fn is_expired(&self) -> bool {
-        Utc::now() > self.expires_at
+        self.is_expired_at(Utc::now())
+    }
+
+    /// Check if expired at a given time
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        now > self.expires_at
     }
 
     /// Total value
@@ -410,6 +439,85 @@ pub struct MarketHistory {
     pub buyer_id: Uuid,
     pub seller_id: Uuid,
     pub timestamp: DateTime<Utc>,
+    /// Fee taken from the seller's proceeds
+    #[serde(default)]
+    pub fee: u64,
+}
+
+/// Item categories with their own market fee
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ItemCategory {
+    Armor,
+    Weapons,
+    /// Forged gear with a tier
+    TieredGear,
+    Consumables,
+    CreatureProducts,
+    Valuables,
+    Tools,
+    Other,
+}
+
+/// Market and auction fees by item category, in basis points (100 = 1%)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MarketFeeConfig {
+    /// Fee for categories without their own rate
+    pub default_bp: u32,
+    pub category_bp: HashMap<ItemCategory, u32>,
+}
+
+impl MarketFeeConfig {
+    pub fn rate_for(&self, category: ItemCategory) -> u32 {
+        self.category_bp.get(&category).copied().unwrap_or(self.default_bp)
+    }
+
+    /// Fee on a sale of `value` gold in `category`
+    pub fn fee_for(&self, category: ItemCategory, value: u64) -> u64 {
+        (value as u128 * self.rate_for(category) as u128 / 10_000) as u64
+    }
+}
+
+impl Default for MarketFeeConfig {
+    fn default() -> Self {
+        Self {
+            default_bp: 200, // 2%
+            category_bp: HashMap::new(),
+        }
+    }
+}
+
+/// Gold removed from the economy by market and auction fees
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EconomySinks {
+    pub market_fees: u64,
+    pub auction_fees: u64,
+    pub by_category: HashMap<ItemCategory, u64>,
+}
+
+impl EconomySinks {
+    fn record(&mut self, category: ItemCategory, fee: u64, auction: bool) {
+        if auction {
+            self.auction_fees += fee;
+        } else {
+            self.market_fees += fee;
+        }
+        *self.by_category.entry(category).or_insert(0) += fee;
+    }
+
+    pub fn total(&self) -> u64 {
+        self.market_fees + self.auction_fees
+    }
+}
+
+/// Outcome of a finished item auction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuctionSettlement {
+    pub category: ItemCategory,
+    pub winning_bid: u64,
+    pub fee: u64,
+    /// What the seller receives after the fee
+    pub seller_proceeds: u64,
 }
 
 /// Market manager
@@ -422,8 +530,18 @@ pub struct MarketManager {
     by_player: HashMap<Uuid, Vec<Uuid>>,
     /// Transaction history
     history: Vec<MarketHistory>,
-    /// Market fee percentage (0-100)
-    fee_percent: u8,
+    /// Fee schedule by item category
+    fees: MarketFeeConfig,
+    /// Category of each item type; unlisted types are `Other`
+    item_categories: HashMap<u16, ItemCategory>,
+    /// Fees collected so far
+    sinks: EconomySinks,
+    /// Items returned from expired sell offers, waiting in the depot inbox
+    depot_inbox: HashMap<Uuid, HashMap<u16, u32>>,
+    /// Inbox capacity per VIP tier
+    storage: StorageCapacity,
+    /// VIP tier of players with a premium account; everyone else is free
+    tiers: HashMap<Uuid, VipTier>,
 }
 
 impl MarketManager {
@@ -433,10 +551,52 @@ impl MarketManager {
             by_item: HashMap::new(),
             by_player: HashMap::new(),
             history: Vec::new(),
-            fee_percent: 2, // 2% default fee
+            fees: MarketFeeConfig::default(),
+            item_categories: HashMap::new(),
+            sinks: EconomySinks::default(),
+            depot_inbox: HashMap::new(),
+            storage: StorageCapacity::default(),
+            tiers: HashMap::new(),
+        }
+    }
+
+    pub fn with_fees(mut self, fees: MarketFeeConfig) -> Self {
+        self.fees = fees;
+        self
+    }
+
+    /// Assign an item type to a fee category
+    pub fn set_item_category(&mut self, item_type_id: u16, category: ItemCategory) {
+        self.item_categories.insert(item_type_id, category);
+    }
+
+    pub fn item_category(&self, item_type_id: u16) -> ItemCategory {
+        self.item_categories.get(&item_type_id).copied().unwrap_or(ItemCategory::Other)
+    }
+
+    /// Fees collected by trades and auctions
+    pub fn sinks(&self) -> &EconomySinks {
+        &self.sinks
+    }
+
+    pub fn with_storage_capacity(mut self, storage: StorageCapacity) -> Self {
+        self.storage = storage;
+        self
+    }
+
+    /// Record a player's VIP tier so their inbox gets the matching capacity
+    pub fn set_player_tier(&mut self, player_id: Uuid, tier: VipTier) {
+        if tier == VipTier::None {
+            self.tiers.remove(&player_id);
+        } else {
+            self.tiers.insert(player_id, tier);
         }
     }
 
+    pub fn player_tier(&self, player_id: Uuid) -> VipTier {
+        self.tiers.get(&player_id).copied().unwrap_or(VipTier::None)
+    }
+
     /// Create a new offer
     pub fn create_offer(&mut self, offer: MarketOffer) -> Uuid {
         let id = offer.id;
@@ -549,6 +709,10 @@ impl MarketManager {
             }
         }
 
+        let category = self.item_category(buy_offer.item_type_id);
+        let fee = self.fees.fee_for(category, trade_amount as u64 * sell_offer.price as u64);
+        self.sinks.record(category, fee, false);
+
         // Create history entry
         let history = MarketHistory {
             item_type_id: buy_offer.item_type_id,
@@ -557,6 +721,7 @@ impl MarketManager {
             buyer_id: buy_offer.player_id,
             seller_id: sell_offer.player_id,
             timestamp: Utc::now(),
+            fee,
         };
 
         self.history.push(history.clone());
@@ -565,16 +730,11 @@ impl MarketManager {
 
     /// Get market statistics for an item
     pub fn get_statistics(&self, item_type_id: u16) -> MarketStatistics {
-        let item_history: Vec<_> = self.history.iter()
+        let total_volume: u64 = self.history.iter()
             .filter(|h| h.item_type_id == item_type_id)
-            .collect();
-
-        let total_volume: u64 = item_history.iter().map(|h| h.amount as u64).sum();
-        let avg_price = if !item_history.is_empty() {
-            item_history.iter().map(|h| h.price as u64).sum::<u64>() / item_history.len() as u64
-        } else {
-            0
-        };
+            .map(|h| h.amount as u64)
+            .sum();
+        let avg_price = average_trade_price(&self.history, item_type_id, 0);
 
         let buy_offers = self.get_buy_offers(item_type_id);
         let sell_offers = self.get_sell_offers(item_type_id);
@@ -582,7 +742,7 @@ impl MarketManager {
         MarketStatistics {
             item_type_id,
             total_volume: total_volume as u32,
-            average_price: avg_price as u32,
+            average_price: avg_price,
             highest_buy: buy_offers.first().map(|o| o.price).unwrap_or(0),
             lowest_sell: sell_offers.first().map(|o| o.price).unwrap_or(0),
             active_buy_offers: buy_offers.len(),
@@ -590,18 +750,131 @@ impl MarketManager {
         }
     }
 
-    /// Calculate fee for amount
+    /// Trades a player bought or sold, oldest first
+    pub fn player_history(&self, player_id: Uuid) -> Vec<&MarketHistory> {
+        self.history.iter()
+            .filter(|h| h.buyer_id == player_id || h.seller_id == player_id)
+            .collect()
+    }
+
+    /// Net worth of `gold` plus `holdings` (item type -> count), with items
+    /// valued at the average of their last `price_window` trades
+    pub fn net_worth(&self, gold: u64, holdings: &HashMap<u16, u32>, price_window: usize) -> NetWorth {
+        NetWorth::compute(
+            gold,
+            holdings,
+            |item_type_id| average_trade_price(&self.history, item_type_id, price_window),
+            |item_type_id| self.item_category(item_type_id),
+        )
+    }
+
+    /// Calculate fee for amount at the default rate
     pub fn calculate_fee(&self, amount: u64) -> u64 {
-        amount * self.fee_percent as u64 / 100
+        (amount as u128 * self.fees.default_bp as u128 / 10_000) as u64
+    }
+
+    /// Calculate fee for a sale of `amount` gold of an item type
+    pub fn calculate_item_fee(&self, item_type_id: u16, amount: u64) -> u64 {
+        self.fees.fee_for(self.item_category(item_type_id), amount)
     }
 
-    /// Cleanup expired offers
-    pub fn cleanup_expired(&mut self) {
+    /// Take the fee from a won item auction and record it as a sink
+    pub fn settle_auction(&mut self, item_type_id: u16, winning_bid: u64) -> AuctionSettlement {
+        let category = self.item_category(item_type_id);
+        let fee = self.fees.fee_for(category, winning_bid);
+        self.sinks.record(category, fee, true);
+        AuctionSettlement {
+            category,
+            winning_bid,
+            fee,
+            seller_proceeds: winning_bid.saturating_sub(fee),
+        }
+    }
+
+    /// Expire active offers past their duration and release what they reserved.
+    ///
+    /// Sell offers return their remaining items to the owner's depot inbox,
+    /// buy offers refund the remaining reserved coins to the owner's bank
+    /// account. Offers are marked expired as they are processed, so running
+    /// the sweep again never returns anything twice. Persisted offers are
+    /// swept by `MarketRepository::expire_offers` from the engine's
+    /// `ExpireMarketOffers` task.
+    pub fn expire_offers(&mut self, now: DateTime<Utc>, bank: &mut BankManager) -> MarketExpiryReport {
+        let mut report = MarketExpiryReport::default();
+
         for offer in self.offers.values_mut() {
-            if offer.is_expired() && offer.state == MarketOfferState::Active {
-                offer.state = MarketOfferState::Expired;
+            if offer.state != MarketOfferState::Active || !offer.is_expired_at(now) {
+                continue;
             }
+
+            match offer.offer_type {
+                MarketOfferType::Sell => {
+                    if offer.remaining > 0 {
+                        let tier = self.tiers.get(&offer.player_id).copied().unwrap_or(VipTier::None);
+                        let capacity = self.storage.inbox_capacity(tier);
+                        let used: u64 = self.depot_inbox.get(&offer.player_id)
+                            .map(|inbox| inbox.values().map(|&count| count as u64).sum())
+                            .unwrap_or(0);
+                        if used + offer.remaining as u64 > capacity as u64 {
+                            // Keep the offer active until the player empties their inbox
+                            report.failed.push(offer.id);
+                            continue;
+                        }
+                        *self.depot_inbox
+                            .entry(offer.player_id)
+                            .or_default()
+                            .entry(offer.item_type_id)
+                            .or_insert(0) += offer.remaining;
+                        report.items_returned += offer.remaining as u64;
+                    }
+                }
+                MarketOfferType::Buy => {
+                    let refund = offer.remaining_value();
+                    if refund > 0 {
+                        if let Err(e) = bank.credit_for_sale(
+                            offer.player_id,
+                            refund,
+                            TransactionType::MarketRefund,
+                            "Expired market buy offer",
+                        ) {
+                            // Leave the offer active so the next sweep retries the refund
+                            tracing::warn!("Failed to refund expired market offer {}: {}", offer.id, e);
+                            report.failed.push(offer.id);
+                            continue;
+                        }
+                        report.coins_refunded += refund;
+                    }
+                }
+            }
+
+            offer.state = MarketOfferState::Expired;
+            report.expired.push(offer.id);
         }
+
+        report
+    }
+
+    /// Items waiting in a player's depot inbox (item type -> count)
+    pub fn depot_inbox(&self, player_id: Uuid) -> Option<&HashMap<u16, u32>> {
+        self.depot_inbox.get(&player_id)
+    }
+
+    /// Items a player's depot inbox can hold
+    pub fn inbox_capacity(&self, player_id: Uuid) -> u32 {
+        self.storage.inbox_capacity(self.player_tier(player_id))
+    }
+
+    /// Room left in a player's depot inbox
+    pub fn remaining_inbox_capacity(&self, player_id: Uuid) -> u32 {
+        let used: u64 = self.depot_inbox.get(&player_id)
+            .map(|inbox| inbox.values().map(|&count| count as u64).sum())
+            .unwrap_or(0);
+        (self.inbox_capacity(player_id) as u64).saturating_sub(used) as u32
+    }
+
+    /// Take all items from a player's depot inbox to move them into the depot
+    pub fn collect_depot_inbox(&mut self, player_id: Uuid) -> HashMap<u16, u32> {
+        self.depot_inbox.remove(&player_id).unwrap_or_default()
     }
 }
 
@@ -611,6 +884,69 @@ impl Default for MarketManager {
     }
 }
 
+/// Result of an offer expiry sweep
+#[derive(Debug, Clone, Default)]
+pub struct MarketExpiryReport {
+    /// Offers marked expired by this sweep
+    pub expired: Vec<Uuid>,
+    /// Offers whose refund failed or whose items did not fit the depot
+    /// inbox; they stay active and are retried
+    pub failed: Vec<Uuid>,
+    /// Total items returned to depot inboxes
+    pub items_returned: u64,
+    /// Total coins refunded to bank accounts
+    pub coins_refunded: u64,
+}
+
+/// Average price of an item over its last `window` trades (0 = every trade),
+/// or 0 when it was never traded
+pub fn average_trade_price(history: &[MarketHistory], item_type_id: u16, window: usize) -> u32 {
+    let window = if window == 0 { usize::MAX } else { window };
+    let (count, total) = history.iter()
+        .rev()
+        .filter(|h| h.item_type_id == item_type_id)
+        .take(window)
+        .fold((0u64, 0u64), |(count, total), h| (count + 1, total + h.price as u64));
+    total.checked_div(count).unwrap_or(0) as u32
+}
+
+/// Gold plus the market value of held items
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetWorth {
+    pub gold: u64,
+    pub items_value: u64,
+    pub total: u64,
+    /// Item value by category
+    pub by_category: HashMap<ItemCategory, u64>,
+    /// Held item types without a market price; they count as 0
+    pub unpriced: Vec<u16>,
+}
+
+impl NetWorth {
+    /// Value `holdings` (item type -> count) at `price` per unit
+    pub fn compute(
+        gold: u64,
+        holdings: &HashMap<u16, u32>,
+        price: impl Fn(u16) -> u32,
+        category: impl Fn(u16) -> ItemCategory,
+    ) -> Self {
+        let mut worth = NetWorth { gold, ..Default::default() };
+        for (&item_type_id, &count) in holdings {
+            let unit_price = price(item_type_id);
+            if unit_price == 0 {
+                worth.unpriced.push(item_type_id);
+                continue;
+            }
+            let value = unit_price as u64 * count as u64;
+            worth.items_value += value;
+            *worth.by_category.entry(category(item_type_id)).or_insert(0) += value;
+        }
+        worth.unpriced.sort_unstable();
+        worth.total = worth.gold + worth.items_value;
+        worth
+    }
+}
+
 /// Market statistics
 #[derive(Debug, Clone)]
 pub struct MarketStatistics {
@@ -636,6 +972,7 @@ pub enum TradeError {
     PriceMismatch,
     InsufficientAmount,
     InsufficientFunds,
+    InsufficientCapacity(Uuid),
     InventoryFull,
 }
 
@@ -652,6 +989,7 @@ impl std::fmt::Display for TradeError {
             TradeError::PriceMismatch => write!(f, "Price mismatch"),
             TradeError::InsufficientAmount => write!(f, "Insufficient amount"),
             TradeError::InsufficientFunds => write!(f, "Insufficient funds"),
+            TradeError::InsufficientCapacity(_) => write!(f, "Not enough capacity"),
             TradeError::InventoryFull => write!(f, "Inventory is full"),
         }
     }
@@ -738,4 +1076,179 @@ mod tests {
         let buy = market.get_offer(buy_id).unwrap();
         assert_eq!(buy.remaining, 5);
     }
+
+    #[test]
+    fn test_net_worth_uses_average_prices() {
+        let mut market = MarketManager::new();
+        market.set_item_category(2160, ItemCategory::Valuables);
+        market.set_item_category(3031, ItemCategory::Valuables);
+        market.set_item_category(3366, ItemCategory::Armor);
+        let buyer = Uuid::new_v4();
+        let seller = Uuid::new_v4();
+
+        let mut trade = |item_type_id: u16, price: u32| {
+            let buy = market.create_offer(MarketOffer::buy(buyer, "Buyer", item_type_id, 1, price));
+            let sell = market.create_offer(MarketOffer::sell(seller, "Seller", item_type_id, 1, price));
+            market.execute_trade(buy, sell, 1).unwrap();
+        };
+        trade(2160, 9_000);
+        trade(2160, 10_000);
+        trade(2160, 11_000);
+        trade(3031, 50);
+        trade(3366, 120_000);
+        assert_eq!(market.player_history(buyer).len(), 5);
+        assert_eq!(market.get_statistics(2160).average_price, 10_000);
+
+        let holdings = HashMap::from([(2160, 3), (3031, 100), (3366, 1), (5000, 7)]);
+        let worth = market.net_worth(25_000, &holdings, 0);
+        assert_eq!(worth.items_value, 3 * 10_000 + 100 * 50 + 120_000);
+        assert_eq!(worth.by_category[&ItemCategory::Valuables], 35_000);
+        assert_eq!(worth.by_category[&ItemCategory::Armor], 120_000);
+        assert_eq!(worth.total, 25_000 + 155_000);
+        assert_eq!(worth.unpriced, vec![5000]);
+
+        // A window only averages the most recent trades
+        let recent = market.net_worth(0, &HashMap::from([(2160, 1)]), 2);
+        assert_eq!(recent.items_value, 10_500);
+    }
+
+    #[test]
+    fn test_expired_sell_offer_returns_items() {
+        let mut market = MarketManager::new();
+        let mut bank = BankManager::new();
+        let seller = Uuid::new_v4();
+        let buyer = Uuid::new_v4();
+
+        let sell_id = market.create_offer(MarketOffer::sell(seller, "Seller", 2160, 10, 9500));
+        let buy_id = market.create_offer(MarketOffer::buy(buyer, "Buyer", 2160, 4, 10000));
+        market.execute_trade(buy_id, sell_id, 4).unwrap();
+
+        let expires_at = market.get_offer(sell_id).unwrap().expires_at;
+        assert!(market.expire_offers(expires_at, &mut bank).expired.is_empty());
+
+        let later = expires_at + Duration::seconds(1);
+        let report = market.expire_offers(later, &mut bank);
+        assert_eq!(report.expired, vec![sell_id]);
+        assert_eq!(report.items_returned, 6);
+        assert_eq!(market.get_offer(sell_id).unwrap().state, MarketOfferState::Expired);
+        assert_eq!(market.depot_inbox(seller).unwrap().get(&2160), Some(&6));
+
+        // Running the sweep again returns nothing twice
+        assert!(market.expire_offers(later, &mut bank).expired.is_empty());
+        assert_eq!(market.collect_depot_inbox(seller).get(&2160), Some(&6));
+        assert!(market.depot_inbox(seller).is_none());
+    }
+
+    #[test]
+    fn test_expired_buy_offer_refunds_coins() {
+        let mut market = MarketManager::new();
+        let mut bank = BankManager::new();
+        let buyer = Uuid::new_v4();
+
+        let buy_id = market.create_offer(MarketOffer::buy(buyer, "Buyer", 2160, 5, 10000));
+        let later = market.get_offer(buy_id).unwrap().expires_at + Duration::seconds(1);
+
+        let report = market.expire_offers(later, &mut bank);
+        assert_eq!(report.expired, vec![buy_id]);
+        assert_eq!(report.coins_refunded, 50000);
+        assert_eq!(bank.get_balance(buyer), 50000);
+        assert!(market.depot_inbox(buyer).is_none());
+
+        market.expire_offers(later, &mut bank);
+        assert_eq!(bank.get_balance(buyer), 50000);
+    }
+
+    #[test]
+    fn test_category_fees_differ_on_same_price() {
+        let mut fees = MarketFeeConfig::default();
+        fees.category_bp.insert(ItemCategory::TieredGear, 1000); // 10%
+        fees.category_bp.insert(ItemCategory::Consumables, 100); // 1%
+        let mut market = MarketManager::new().with_fees(fees);
+        market.set_item_category(3370, ItemCategory::TieredGear);
+        market.set_item_category(7618, ItemCategory::Consumables);
+
+        let seller = Uuid::new_v4();
+        let buyer = Uuid::new_v4();
+        let sell = market.create_offer(MarketOffer::sell(seller, "Seller", 3370, 1, 50000));
+        let buy = market.create_offer(MarketOffer::buy(buyer, "Buyer", 3370, 1, 50000));
+        assert_eq!(market.execute_trade(buy, sell, 1).unwrap().fee, 5000);
+
+        let sell = market.create_offer(MarketOffer::sell(seller, "Seller", 7618, 1, 50000));
+        let buy = market.create_offer(MarketOffer::buy(buyer, "Buyer", 7618, 1, 50000));
+        assert_eq!(market.execute_trade(buy, sell, 1).unwrap().fee, 500);
+
+        // Uncategorised items pay the default rate
+        assert_eq!(market.calculate_item_fee(2160, 50000), 1000);
+        assert_eq!(market.calculate_fee(50000), 1000);
+
+        // Auctions share the schedule and feed the same tally
+        let settlement = market.settle_auction(3370, 100000);
+        assert_eq!(settlement.fee, 10000);
+        assert_eq!(settlement.seller_proceeds, 90000);
+
+        let sinks = market.sinks();
+        assert_eq!(sinks.market_fees, 5500);
+        assert_eq!(sinks.auction_fees, 10000);
+        assert_eq!(sinks.by_category.get(&ItemCategory::TieredGear), Some(&15000));
+        assert_eq!(sinks.by_category.get(&ItemCategory::Consumables), Some(&500));
+        assert_eq!(sinks.total(), 15500);
+    }
+
+    fn storage() -> StorageCapacity {
+        StorageCapacity {
+            inbox: crate::vip::TierCapacity { free: 10, bronze: 50, silver: 50, gold: 100, platinum: 100 },
+            ..StorageCapacity::default()
+        }
+    }
+
+    #[test]
+    fn test_free_account_inbox_cap() {
+        let mut market = MarketManager::new().with_storage_capacity(storage());
+        let mut bank = BankManager::new();
+        let seller = Uuid::new_v4();
+        assert_eq!(market.remaining_inbox_capacity(seller), 10);
+
+        let small = market.create_offer(MarketOffer::sell(seller, "Seller", 2160, 8, 9500));
+        let later = market.get_offer(small).unwrap().expires_at + Duration::seconds(1);
+        assert_eq!(market.expire_offers(later, &mut bank).items_returned, 8);
+        assert_eq!(market.remaining_inbox_capacity(seller), 2);
+
+        // The next return does not fit, so the offer is held back
+        let large = market.create_offer(MarketOffer::sell(seller, "Seller", 3031, 5, 100));
+        let later = market.get_offer(large).unwrap().expires_at + Duration::seconds(1);
+        let report = market.expire_offers(later, &mut bank);
+        assert!(report.expired.is_empty());
+        assert_eq!(report.failed, vec![large]);
+        assert_eq!(market.get_offer(large).unwrap().state, MarketOfferState::Active);
+        assert_eq!(market.remaining_inbox_capacity(seller), 2);
+
+        // Emptying the inbox makes room for the held-back offer
+        market.collect_depot_inbox(seller);
+        let report = market.expire_offers(later, &mut bank);
+        assert_eq!(report.expired, vec![large]);
+        assert_eq!(market.remaining_inbox_capacity(seller), 5);
+    }
+
+    #[test]
+    fn test_premium_account_inbox_cap() {
+        let mut market = MarketManager::new().with_storage_capacity(storage());
+        let mut bank = BankManager::new();
+        let seller = Uuid::new_v4();
+        market.set_player_tier(seller, VipTier::Gold);
+        assert_eq!(market.inbox_capacity(seller), 100);
+
+        let first = market.create_offer(MarketOffer::sell(seller, "Seller", 2160, 8, 9500));
+        let second = market.create_offer(MarketOffer::sell(seller, "Seller", 3031, 5, 100));
+        let later = market.get_offer(second).unwrap().expires_at + Duration::seconds(1);
+
+        let report = market.expire_offers(later, &mut bank);
+        assert_eq!(report.items_returned, 13);
+        assert_eq!(report.expired.len(), 2);
+        assert!(report.expired.contains(&first));
+        assert!(report.failed.is_empty());
+        assert_eq!(market.remaining_inbox_capacity(seller), 87);
+
+        market.set_player_tier(seller, VipTier::None);
+        assert_eq!(market.remaining_inbox_capacity(seller), 0);
+    }
 }
@@ -169,6 +169,58 @@ impl VipTier {
     }
 }
 
+/// A limit that differs per VIP tier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TierCapacity {
+    pub free: u32,
+    pub bronze: u32,
+    pub silver: u32,
+    pub gold: u32,
+    pub platinum: u32,
+}
+
+impl TierCapacity {
+    /// Limit for `tier`
+    pub fn for_tier(&self, tier: VipTier) -> u32 {
+        match tier {
+            VipTier::None => self.free,
+            VipTier::Bronze => self.bronze,
+            VipTier::Silver => self.silver,
+            VipTier::Gold => self.gold,
+            VipTier::Platinum => self.platinum,
+        }
+    }
+}
+
+/// Depot and market inbox item capacity by VIP tier
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StorageCapacity {
+    /// Items a depot can hold
+    pub depot: TierCapacity,
+    /// Items the market inbox can hold before returns are held back
+    pub inbox: TierCapacity,
+}
+
+impl StorageCapacity {
+    pub fn depot_capacity(&self, tier: VipTier) -> u32 {
+        self.depot.for_tier(tier)
+    }
+
+    pub fn inbox_capacity(&self, tier: VipTier) -> u32 {
+        self.inbox.for_tier(tier)
+    }
+}
+
+impl Default for StorageCapacity {
+    fn default() -> Self {
+        Self {
+            depot: TierCapacity { free: 2000, bronze: 4000, silver: 6000, gold: 10000, platinum: 15000 },
+            inbox: TierCapacity { free: 500, bronze: 1000, silver: 2000, gold: 4000, platinum: 8000 },
+        }
+    }
+}
+
 /// VIP subscription status for an account
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VipStatus {
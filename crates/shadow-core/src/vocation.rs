@@ -0,0 +1,338 @@
+//! Vocation System
+//!
+//! Per-vocation stat gains (health, mana, capacity per level), regeneration
+//! rates, and promotion (Knight -> Elite Knight, etc.) with the multipliers
+//! that promoted vocations receive.
+
+use serde::{Deserialize, Serialize};
+use shadow_db::models::{Character, Vocation};
+use std::collections::HashMap;
+
+/// Stat gains and regeneration for a base vocation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VocationStats {
+    /// Health gained per level
+    pub health_per_level: u32,
+    /// Mana gained per level
+    pub mana_per_level: u32,
+    /// Capacity (oz) gained per level
+    pub capacity_per_level: u32,
+    /// Health regenerated per regen tick
+    pub health_regen: u32,
+    /// Mana regenerated per regen tick
+    pub mana_regen: u32,
+    /// Seconds between regen ticks
+    pub regen_interval_secs: u32,
+}
+
+impl VocationStats {
+    pub fn new(health_per_level: u32, mana_per_level: u32, capacity_per_level: u32) -> Self {
+        Self {
+            health_per_level,
+            mana_per_level,
+            capacity_per_level,
+            health_regen: 1,
+            mana_regen: 2,
+            regen_interval_secs: 6,
+        }
+    }
+
+    pub fn with_regen(mut self, health: u32, mana: u32, interval_secs: u32) -> Self {
+        self.health_regen = health;
+        self.mana_regen = mana;
+        self.regen_interval_secs = interval_secs;
+        self
+    }
+}
+
+/// Promotion requirements and the scaling applied to promoted vocations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromotionConfig {
+    /// Minimum level to be promoted
+    pub min_level: u32,
+    /// Whether a premium account is required
+    pub requires_premium: bool,
+    /// Gold cost of the promotion
+    pub cost: u64,
+    /// Multiplier on health/mana regenerated per tick
+    pub regen_multiplier: f64,
+    /// Multiplier on health gained per level
+    pub health_gain_multiplier: f64,
+    /// Multiplier on mana gained per level
+    pub mana_gain_multiplier: f64,
+    /// Multiplier on capacity gained per level
+    pub capacity_gain_multiplier: f64,
+}
+
+impl Default for PromotionConfig {
+    fn default() -> Self {
+        Self {
+            min_level: 20,
+            requires_premium: true,
+            cost: 20_000,
+            regen_multiplier: 2.0,
+            health_gain_multiplier: 1.0,
+            mana_gain_multiplier: 1.0,
+            capacity_gain_multiplier: 1.0,
+        }
+    }
+}
+
+/// Vocation table used for level scaling, regeneration and promotion
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VocationConfig {
+    /// Stats per base vocation
+    pub vocations: HashMap<Vocation, VocationStats>,
+    /// Promotion rules
+    pub promotion: PromotionConfig,
+    /// Health at level 1
+    pub base_health: u32,
+    /// Mana at level 1
+    pub base_mana: u32,
+    /// Capacity at level 1
+    pub base_capacity: u32,
+}
+
+impl Default for VocationConfig {
+    fn default() -> Self {
+        let mut vocations = HashMap::new();
+        vocations.insert(Vocation::None, VocationStats::new(5, 5, 10).with_regen(1, 2, 12));
+        vocations.insert(Vocation::Sorcerer, VocationStats::new(5, 30, 10).with_regen(1, 4, 6));
+        vocations.insert(Vocation::Druid, VocationStats::new(5, 30, 10).with_regen(1, 4, 6));
+        vocations.insert(Vocation::Paladin, VocationStats::new(10, 15, 20).with_regen(2, 3, 6));
+        vocations.insert(Vocation::Knight, VocationStats::new(15, 5, 25).with_regen(3, 1, 6));
+
+        Self {
+            vocations,
+            promotion: PromotionConfig::default(),
+            base_health: 150,
+            base_mana: 0,
+            base_capacity: 400,
+        }
+    }
+}
+
+impl VocationConfig {
+    /// Stats for a vocation (promoted vocations share their base vocation's table)
+    pub fn stats(&self, vocation: Vocation) -> Option<&VocationStats> {
+        self.vocations.get(&vocation.base_vocation())
+    }
+
+    /// Maximum health, mana and capacity for a vocation at a level
+    pub fn max_stats(&self, vocation: Vocation, level: u32) -> (u32, u32, u32) {
+        let Some(stats) = self.stats(vocation) else {
+            return (self.base_health, self.base_mana, self.base_capacity);
+        };
+
+        let (hp_mult, mana_mult, cap_mult) = if vocation.is_promoted() {
+            (
+                self.promotion.health_gain_multiplier,
+                self.promotion.mana_gain_multiplier,
+                self.promotion.capacity_gain_multiplier,
+            )
+        } else {
+            (1.0, 1.0, 1.0)
+        };
+
+        let levels = level.saturating_sub(1) as f64;
+        (
+            self.base_health + (levels * stats.health_per_level as f64 * hp_mult) as u32,
+            self.base_mana + (levels * stats.mana_per_level as f64 * mana_mult) as u32,
+            self.base_capacity + (levels * stats.capacity_per_level as f64 * cap_mult) as u32,
+        )
+    }
+
+    /// Health and mana regenerated per regen tick
+    pub fn regen_per_tick(&self, vocation: Vocation) -> (u32, u32) {
+        let Some(stats) = self.stats(vocation) else {
+            return (0, 0);
+        };
+
+        let multiplier = if vocation.is_promoted() {
+            self.promotion.regen_multiplier
+        } else {
+            1.0
+        };
+
+        (
+            (stats.health_regen as f64 * multiplier) as u32,
+            (stats.mana_regen as f64 * multiplier) as u32,
+        )
+    }
+
+    /// Seconds between regen ticks for a vocation
+    pub fn regen_interval_secs(&self, vocation: Vocation) -> u32 {
+        self.stats(vocation).map(|s| s.regen_interval_secs).unwrap_or(6)
+    }
+
+    /// Check whether a character meets the promotion requirements
+    pub fn can_promote(&self, character: &Character, premium: bool) -> Result<(), PromotionError> {
+        if character.vocation == Vocation::None {
+            return Err(PromotionError::NoVocation);
+        }
+        if character.promoted || character.vocation.is_promoted() {
+            return Err(PromotionError::AlreadyPromoted);
+        }
+        if (character.level as u32) < self.promotion.min_level {
+            return Err(PromotionError::LevelTooLow(self.promotion.min_level));
+        }
+        if self.promotion.requires_premium && !premium {
+            return Err(PromotionError::PremiumRequired);
+        }
+        if (character.balance.max(0) as u64) < self.promotion.cost {
+            return Err(PromotionError::InsufficientFunds(self.promotion.cost));
+        }
+        Ok(())
+    }
+
+    /// Promote a character, charging the cost and rescaling max stats.
+    /// Returns the new vocation.
+    pub fn promote(&self, character: &mut Character, premium: bool) -> Result<Vocation, PromotionError> {
+        self.can_promote(character, premium)?;
+
+        character.balance -= self.promotion.cost as i64;
+        character.vocation = character.vocation.promoted();
+        character.promoted = true;
+
+        let (health, mana, capacity) = self.max_stats(character.vocation, character.level as u32);
+        character.health_max = health as i32;
+        character.mana_max = mana as i32;
+        character.cap_max = capacity as i32;
+        character.health = character.health.min(character.health_max);
+        character.mana = character.mana.min(character.mana_max);
+
+        Ok(character.vocation)
+    }
+}
+
+/// Promotion errors
+#[derive(Debug, Clone, PartialEq)]
+pub enum PromotionError {
+    NoVocation,
+    AlreadyPromoted,
+    LevelTooLow(u32),
+    PremiumRequired,
+    InsufficientFunds(u64),
+}
+
+impl std::fmt::Display for PromotionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PromotionError::NoVocation => write!(f, "Character has no vocation to promote"),
+            PromotionError::AlreadyPromoted => write!(f, "Character is already promoted"),
+            PromotionError::LevelTooLow(level) => write!(f, "Promotion requires level {}", level),
+            PromotionError::PremiumRequired => write!(f, "Promotion requires a premium account"),
+            PromotionError::InsufficientFunds(cost) => write!(f, "Promotion costs {} gold", cost),
+        }
+    }
+}
+
+impl std::error::Error for PromotionError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use shadow_db::models::{Sex, SkullType};
+    use uuid::Uuid;
+
+    fn test_character(vocation: Vocation, level: i32) -> Character {
+        Character {
+            id: Uuid::new_v4(),
+            account_id: Uuid::new_v4(),
+            realm_id: Uuid::new_v4(),
+            name: "Test Character".to_string(),
+            vocation,
+            promoted: vocation.is_promoted(),
+            sex: Sex::Male,
+            level,
+            experience: 0,
+            health: 150,
+            health_max: 150,
+            mana: 0,
+            mana_max: 0,
+            capacity: 400,
+            cap_max: 400,
+            soul: 100,
+            stamina: 2520,
+            magic_level: 0,
+            magic_level_exp: 0,
+            pos_x: 1000,
+            pos_y: 1000,
+            pos_z: 7,
+            town_id: 1,
+            look_type: 128,
+            look_head: 78,
+            look_body: 68,
+            look_legs: 58,
+            look_feet: 76,
+            look_addons: 0,
+            look_mount: 0,
+            skull_type: SkullType::None,
+            skull_until: None,
+            frags: 0,
+            frag_time: None,
+            balance: 0,
+            bank_balance: 0,
+            guild_id: None,
+            guild_rank_id: None,
+            guild_nick: None,
+            house_id: None,
+            blessings: 0,
+            online: false,
+            last_login: None,
+            last_logout: None,
+            deletion_date: None,
+            deleted_by: None,
+            total_playtime: 0,
+            login_count: 0,
+            deaths: 0,
+            kills_players: 0,
+            kills_monsters: 0,
+            prey_wildcard: 0,
+            prey_bonus_rerolls: 0,
+            charm_points: 0,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_promotion_doubles_regen() {
+        let config = VocationConfig::default();
+        let mut knight = test_character(Vocation::Knight, 50);
+        knight.balance = 50_000;
+
+        assert_eq!(config.regen_per_tick(knight.vocation), (3, 1));
+
+        let vocation = config.promote(&mut knight, true).unwrap();
+        assert_eq!(vocation, Vocation::EliteKnight);
+        assert!(knight.promoted);
+        assert_eq!(knight.balance, 30_000);
+        assert_eq!(config.regen_per_tick(knight.vocation), (6, 2));
+        assert_eq!(knight.health_max, 150 + 49 * 15);
+        assert_eq!(knight.cap_max, 400 + 49 * 25);
+    }
+
+    #[test]
+    fn test_promotion_requirements() {
+        let config = VocationConfig::default();
+
+        let mut low = test_character(Vocation::Druid, 10);
+        low.balance = 50_000;
+        assert_eq!(config.promote(&mut low, true), Err(PromotionError::LevelTooLow(20)));
+        assert_eq!(low.vocation, Vocation::Druid);
+        assert_eq!(low.balance, 50_000);
+
+        let mut free = test_character(Vocation::Druid, 30);
+        free.balance = 50_000;
+        assert_eq!(config.promote(&mut free, false), Err(PromotionError::PremiumRequired));
+
+        let mut poor = test_character(Vocation::Druid, 30);
+        assert_eq!(config.promote(&mut poor, true), Err(PromotionError::InsufficientFunds(20_000)));
+
+        let mut promoted = test_character(Vocation::ElderDruid, 30);
+        promoted.balance = 50_000;
+        assert_eq!(config.promote(&mut promoted, true), Err(PromotionError::AlreadyPromoted));
+    }
+}
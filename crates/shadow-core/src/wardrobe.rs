@@ -0,0 +1,284 @@
+//! Wardrobe and addon quests
+//!
+//! The wardrobe holds the outfits a character owns and the addons unlocked
+//! for each. Addons are earned through addon quests: each quest lists the
+//! items to hand in and the creatures to kill, and `AddonProgress` keeps a
+//! character's counts towards every quest. When the last requirement of a
+//! quest is met the addon is added to the wardrobe. Addons can be earned
+//! before the outfit itself; they show once the outfit is owned.
+
+use serde::{Deserialize, Serialize};
+use shadow_world::creature::Outfit;
+use std::collections::{BTreeMap, HashMap};
+
+/// First addon flag of `look_addons`
+pub const FIRST_ADDON: u8 = 1;
+/// Second addon flag of `look_addons`
+pub const SECOND_ADDON: u8 = 2;
+
+/// Outfits and addons a character owns
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Wardrobe {
+    /// Owned outfits (look type -> addon flags)
+    outfits: BTreeMap<u16, u8>,
+    /// Addons earned for outfits not owned yet (look type -> addon flags)
+    pending_addons: BTreeMap<u16, u8>,
+}
+
+impl Wardrobe {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an outfit, along with any addons already earned for it
+    pub fn unlock_outfit(&mut self, look_type: u16) {
+        let pending = self.pending_addons.remove(&look_type).unwrap_or(0);
+        *self.outfits.entry(look_type).or_insert(0) |= pending;
+    }
+
+    /// Add an addon. Returns false if it was already unlocked.
+    pub fn grant_addon(&mut self, look_type: u16, addon: u8) -> bool {
+        if self.has_addon(look_type, addon) {
+            return false;
+        }
+        match self.outfits.get_mut(&look_type) {
+            Some(addons) => *addons |= addon,
+            None => *self.pending_addons.entry(look_type).or_insert(0) |= addon,
+        }
+        true
+    }
+
+    pub fn has_outfit(&self, look_type: u16) -> bool {
+        self.outfits.contains_key(&look_type)
+    }
+
+    /// Whether an addon is unlocked, owned outfit or not
+    pub fn has_addon(&self, look_type: u16, addon: u8) -> bool {
+        let flags = self.outfits.get(&look_type).or(self.pending_addons.get(&look_type));
+        flags.is_some_and(|flags| flags & addon == addon)
+    }
+
+    /// Addon flags of an owned outfit
+    pub fn addons(&self, look_type: u16) -> u8 {
+        self.outfits.get(&look_type).copied().unwrap_or(0)
+    }
+
+    /// Owned outfits with their addon flags
+    pub fn outfits(&self) -> impl Iterator<Item = (u16, u8)> + '_ {
+        self.outfits.iter().map(|(&look_type, &addons)| (look_type, addons))
+    }
+
+    /// Whether a character may wear `outfit`
+    pub fn can_wear(&self, outfit: &Outfit) -> bool {
+        self.has_outfit(outfit.look_type) && outfit.look_addons & !self.addons(outfit.look_type) == 0
+    }
+}
+
+/// Something an addon quest asks for
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AddonRequirement {
+    /// Items handed in to the quest NPC
+    Item { item_id: u16, count: u32 },
+    /// Creatures killed by the character
+    Kill { creature_name: String, count: u32 },
+}
+
+impl AddonRequirement {
+    fn count(&self) -> u32 {
+        match self {
+            AddonRequirement::Item { count, .. } | AddonRequirement::Kill { count, .. } => *count,
+        }
+    }
+}
+
+/// Requirements for one addon of one outfit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddonQuest {
+    pub look_type: u16,
+    /// `FIRST_ADDON` or `SECOND_ADDON`
+    pub addon: u8,
+    pub requirements: Vec<AddonRequirement>,
+}
+
+/// An addon unlocked by completing its quest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddonUnlock {
+    pub look_type: u16,
+    pub addon: u8,
+}
+
+/// A character's counts towards every addon quest
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AddonProgress {
+    /// Look type -> addon -> count per requirement
+    counts: HashMap<u16, HashMap<u8, Vec<u32>>>,
+}
+
+impl AddonProgress {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count a kill towards every unfinished quest asking for it
+    pub fn record_kill(&mut self, quests: &[AddonQuest], wardrobe: &mut Wardrobe, creature_name: &str) -> Vec<AddonUnlock> {
+        self.advance(quests, wardrobe, |requirement, remaining| match requirement {
+            AddonRequirement::Kill { creature_name: name, .. } if name.eq_ignore_ascii_case(creature_name) => remaining.min(1),
+            _ => 0,
+        })
+        .1
+    }
+
+    /// Hand in up to `available` of an item. Returns how many were taken,
+    /// which the caller removes from the character, and the addons unlocked.
+    pub fn deliver_item(
+        &mut self,
+        quests: &[AddonQuest],
+        wardrobe: &mut Wardrobe,
+        item_id: u16,
+        available: u32,
+    ) -> (u32, Vec<AddonUnlock>) {
+        let mut left = available;
+        self.advance(quests, wardrobe, |requirement, remaining| match requirement {
+            AddonRequirement::Item { item_id: id, .. } if *id == item_id => {
+                let taken = remaining.min(left);
+                left -= taken;
+                taken
+            }
+            _ => 0,
+        })
+    }
+
+    /// Count towards a requirement of a quest, 0 when not started
+    pub fn count(&self, look_type: u16, addon: u8, requirement: usize) -> u32 {
+        self.counts
+            .get(&look_type)
+            .and_then(|addons| addons.get(&addon))
+            .and_then(|counts| counts.get(requirement))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Apply `take` to every requirement of the unfinished quests, which
+    /// returns how much of the remaining count it covers. Quests that are
+    /// complete afterwards unlock their addon.
+    fn advance(
+        &mut self,
+        quests: &[AddonQuest],
+        wardrobe: &mut Wardrobe,
+        mut take: impl FnMut(&AddonRequirement, u32) -> u32,
+    ) -> (u32, Vec<AddonUnlock>) {
+        let mut total = 0;
+        let mut unlocked = Vec::new();
+
+        for quest in quests {
+            if wardrobe.has_addon(quest.look_type, quest.addon) {
+                self.forget(quest);
+                continue;
+            }
+
+            let counts = self.counts.entry(quest.look_type).or_default().entry(quest.addon).or_default();
+            counts.resize(quest.requirements.len(), 0);
+            for (requirement, count) in quest.requirements.iter().zip(counts.iter_mut()) {
+                let taken = take(requirement, requirement.count().saturating_sub(*count));
+                *count += taken;
+                total += taken;
+            }
+
+            let complete = quest.requirements.iter().zip(counts.iter()).all(|(r, &c)| c >= r.count());
+            if complete {
+                self.forget(quest);
+                wardrobe.grant_addon(quest.look_type, quest.addon);
+                unlocked.push(AddonUnlock { look_type: quest.look_type, addon: quest.addon });
+            } else if counts.iter().all(|&count| count == 0) {
+                self.forget(quest);
+            }
+        }
+
+        (total, unlocked)
+    }
+
+    fn forget(&mut self, quest: &AddonQuest) {
+        if let Some(addons) = self.counts.get_mut(&quest.look_type) {
+            addons.remove(&quest.addon);
+            if addons.is_empty() {
+                self.counts.remove(&quest.look_type);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CITIZEN: u16 = 128;
+
+    fn quests() -> Vec<AddonQuest> {
+        vec![
+            AddonQuest {
+                look_type: CITIZEN,
+                addon: FIRST_ADDON,
+                requirements: vec![AddonRequirement::Item { item_id: 5878, count: 100 }],
+            },
+            AddonQuest {
+                look_type: CITIZEN,
+                addon: SECOND_ADDON,
+                requirements: vec![
+                    AddonRequirement::Item { item_id: 5890, count: 100 },
+                    AddonRequirement::Kill { creature_name: "Chicken".to_string(), count: 3 },
+                ],
+            },
+        ]
+    }
+
+    #[test]
+    fn test_requirements_accumulate_across_deliveries() {
+        let quests = quests();
+        let mut wardrobe = Wardrobe::new();
+        let mut progress = AddonProgress::new();
+
+        assert_eq!(progress.deliver_item(&quests, &mut wardrobe, 5878, 40), (40, vec![]));
+        assert_eq!(progress.deliver_item(&quests, &mut wardrobe, 5890, 30).0, 30);
+        assert!(progress.record_kill(&quests, &mut wardrobe, "chicken").is_empty());
+        progress.record_kill(&quests, &mut wardrobe, "Rat");
+
+        assert_eq!(progress.count(CITIZEN, FIRST_ADDON, 0), 40);
+        assert_eq!(progress.count(CITIZEN, SECOND_ADDON, 0), 30);
+        assert_eq!(progress.count(CITIZEN, SECOND_ADDON, 1), 1);
+        assert!(!wardrobe.has_addon(CITIZEN, FIRST_ADDON));
+    }
+
+    #[test]
+    fn test_addon_unlocks_when_complete() {
+        let quests = quests();
+        let mut wardrobe = Wardrobe::new();
+        wardrobe.unlock_outfit(CITIZEN);
+        let mut progress = AddonProgress::new();
+
+        // Only what is still needed is taken
+        let unlock = AddonUnlock { look_type: CITIZEN, addon: FIRST_ADDON };
+        assert_eq!(progress.deliver_item(&quests, &mut wardrobe, 5878, 150), (100, vec![unlock]));
+        assert_eq!(wardrobe.addons(CITIZEN), FIRST_ADDON);
+        assert_eq!(progress.deliver_item(&quests, &mut wardrobe, 5878, 10).0, 0);
+
+        let outfit = Outfit { look_type: CITIZEN, look_addons: FIRST_ADDON | SECOND_ADDON, ..Default::default() };
+        assert!(!wardrobe.can_wear(&outfit));
+
+        progress.deliver_item(&quests, &mut wardrobe, 5890, 100);
+        for _ in 0..2 {
+            assert!(progress.record_kill(&quests, &mut wardrobe, "Chicken").is_empty());
+        }
+        let unlocked = progress.record_kill(&quests, &mut wardrobe, "Chicken");
+        assert_eq!(unlocked, vec![AddonUnlock { look_type: CITIZEN, addon: SECOND_ADDON }]);
+        assert!(wardrobe.can_wear(&outfit));
+
+        // Addons earned before the outfit show up once it is unlocked
+        let mut wardrobe = Wardrobe::new();
+        assert!(wardrobe.grant_addon(129, FIRST_ADDON));
+        assert!(!wardrobe.grant_addon(129, FIRST_ADDON));
+        wardrobe.unlock_outfit(129);
+        assert_eq!(wardrobe.addons(129), FIRST_ADDON);
+    }
+}
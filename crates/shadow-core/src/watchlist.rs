@@ -0,0 +1,350 @@
+//! Market and auction watchlists
+//!
+//! Players watch an item for sell offers at or below a price, or watch
+//! the character bazaar for characters of a vocation within a level band.
+//! The matcher runs on every new market offer and auction and produces a
+//! notification for each watch it satisfies; price watches only fire
+//! again when the price drops further.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::trade::{MarketOffer, MarketOfferType};
+
+/// What a watch looks for
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum WatchCondition {
+    /// Sell offers of an item at or below a price per piece
+    ItemPrice { item_type_id: u16, max_price: u32 },
+    /// Item auctions of an item, optionally at or below a minimum bid
+    ItemAuction { item_id: u16, max_bid: Option<u64> },
+    /// Character auctions of a vocation (promotions included) in a level band
+    CharacterAuction {
+        vocation: Option<String>,
+        min_level: u32,
+        max_level: u32,
+    },
+}
+
+impl WatchCondition {
+    /// Level band and price are sensible
+    pub fn is_valid(&self) -> bool {
+        match self {
+            WatchCondition::ItemPrice { max_price, .. } => *max_price > 0,
+            WatchCondition::ItemAuction { .. } => true,
+            WatchCondition::CharacterAuction { min_level, max_level, .. } => min_level <= max_level,
+        }
+    }
+
+    /// Whether a sell offer of an item at `price` per piece satisfies the condition
+    pub fn matches_item_price(&self, item_type_id: u16, price: u32) -> bool {
+        match *self {
+            WatchCondition::ItemPrice { item_type_id: watched, max_price } => watched == item_type_id && price <= max_price,
+            _ => false,
+        }
+    }
+
+    /// Whether a new character auction satisfies the condition
+    pub fn matches_character_auction(&self, listing: &CharacterListing) -> bool {
+        match self {
+            WatchCondition::CharacterAuction { vocation, min_level, max_level } => {
+                (*min_level..=*max_level).contains(&listing.level)
+                    && vocation.as_deref().is_none_or(|v| vocation_matches(v, &listing.vocation))
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether a new item auction satisfies the condition
+    pub fn matches_item_auction(&self, listing: &ItemListing) -> bool {
+        match *self {
+            WatchCondition::ItemAuction { item_id, max_bid } => {
+                item_id == listing.item_id && max_bid.is_none_or(|max| listing.min_bid <= max)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// A watchlist entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Watch {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub condition: WatchCondition,
+    pub created_at: DateTime<Utc>,
+    /// Price of the last offer that triggered a price watch
+    pub last_alert_price: Option<u32>,
+}
+
+/// A character put up for auction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CharacterListing {
+    pub auction_id: Uuid,
+    pub character_name: String,
+    pub level: u32,
+    pub vocation: String,
+    pub min_bid: u64,
+}
+
+/// An item put up for auction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemListing {
+    pub auction_id: Uuid,
+    pub item_id: u16,
+    pub item_name: String,
+    pub min_bid: u64,
+}
+
+/// Notification produced by a matching watch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchNotification {
+    pub account_id: Uuid,
+    pub watch_id: Uuid,
+    pub title: String,
+    pub message: String,
+    pub action_url: Option<String>,
+    pub data: serde_json::Value,
+}
+
+/// Watchlist errors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WatchlistError {
+    /// The account already has the maximum number of watches
+    LimitReached,
+    /// Level band or price is invalid
+    InvalidCondition,
+    NotFound,
+}
+
+impl std::fmt::Display for WatchlistError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WatchlistError::LimitReached => write!(f, "Watchlist is full"),
+            WatchlistError::InvalidCondition => write!(f, "Invalid watch condition"),
+            WatchlistError::NotFound => write!(f, "Watch not found"),
+        }
+    }
+}
+
+impl std::error::Error for WatchlistError {}
+
+/// Whether a price watch that last alerted at `last_alert_price` alerts
+/// again for `price`; only a further drop does
+pub fn is_new_price_alert(last_alert_price: Option<u32>, price: u32) -> bool {
+    last_alert_price.is_none_or(|last| price < last)
+}
+
+/// Whether `vocation` is `wanted` or one of its promotions
+fn vocation_matches(wanted: &str, vocation: &str) -> bool {
+    let normalize = |name: &str| name.to_ascii_lowercase().replace([' ', '_'], "");
+    normalize(vocation).ends_with(&normalize(wanted))
+}
+
+/// Watches of all accounts
+#[derive(Debug, Clone)]
+pub struct Watchlist {
+    watches: HashMap<Uuid, Watch>,
+    /// Maximum watches per account
+    max_per_account: usize,
+}
+
+impl Watchlist {
+    pub fn new(max_per_account: usize) -> Self {
+        Self {
+            watches: HashMap::new(),
+            max_per_account,
+        }
+    }
+
+    /// Add a watch for an account
+    pub fn add(&mut self, account_id: Uuid, condition: WatchCondition) -> Result<Uuid, WatchlistError> {
+        if !condition.is_valid() {
+            return Err(WatchlistError::InvalidCondition);
+        }
+        if self.for_account(account_id).len() >= self.max_per_account {
+            return Err(WatchlistError::LimitReached);
+        }
+
+        let watch = Watch {
+            id: Uuid::new_v4(),
+            account_id,
+            condition,
+            created_at: Utc::now(),
+            last_alert_price: None,
+        };
+        let id = watch.id;
+        self.watches.insert(id, watch);
+        Ok(id)
+    }
+
+    /// Restore a stored watch
+    pub fn insert(&mut self, watch: Watch) {
+        self.watches.insert(watch.id, watch);
+    }
+
+    /// Remove one of an account's watches
+    pub fn remove(&mut self, account_id: Uuid, watch_id: Uuid) -> Result<Watch, WatchlistError> {
+        match self.watches.get(&watch_id) {
+            Some(watch) if watch.account_id == account_id => Ok(self.watches.remove(&watch_id).unwrap()),
+            _ => Err(WatchlistError::NotFound),
+        }
+    }
+
+    pub fn get(&self, watch_id: Uuid) -> Option<&Watch> {
+        self.watches.get(&watch_id)
+    }
+
+    /// Watches of an account, oldest first
+    pub fn for_account(&self, account_id: Uuid) -> Vec<&Watch> {
+        let mut watches: Vec<_> = self.watches.values().filter(|w| w.account_id == account_id).collect();
+        watches.sort_by_key(|w| w.created_at);
+        watches
+    }
+
+    /// Match a new market offer. Only sell offers can satisfy price watches.
+    pub fn match_offer(&mut self, offer: &MarketOffer, item_name: &str) -> Vec<WatchNotification> {
+        if offer.offer_type != MarketOfferType::Sell {
+            return Vec::new();
+        }
+
+        let mut notifications = Vec::new();
+        for watch in self.watches.values_mut() {
+            if !watch.condition.matches_item_price(offer.item_type_id, offer.price)
+                || !is_new_price_alert(watch.last_alert_price, offer.price)
+            {
+                continue;
+            }
+            watch.last_alert_price = Some(offer.price);
+            notifications.push(WatchNotification {
+                account_id: watch.account_id,
+                watch_id: watch.id,
+                title: format!("{} price drop", item_name),
+                message: format!("{} is offered for {} gold each ({} pieces)", item_name, offer.price, offer.remaining),
+                action_url: Some(format!("/market/items/{}", offer.item_type_id)),
+                data: serde_json::json!({
+                    "offer_id": offer.id,
+                    "item_type_id": offer.item_type_id,
+                    "price": offer.price,
+                    "amount": offer.remaining,
+                }),
+            });
+        }
+        notifications
+    }
+
+    /// Match a new character auction
+    pub fn match_character_auction(&self, listing: &CharacterListing) -> Vec<WatchNotification> {
+        self.watches
+            .values()
+            .filter(|watch| watch.condition.matches_character_auction(listing))
+            .map(|watch| WatchNotification {
+                account_id: watch.account_id,
+                watch_id: watch.id,
+                title: "New character auction".to_string(),
+                message: format!(
+                    "{} (level {} {}) is up for auction from {} gold",
+                    listing.character_name, listing.level, listing.vocation, listing.min_bid
+                ),
+                action_url: Some(format!("/auctions/characters/{}", listing.auction_id)),
+                data: serde_json::json!({
+                    "auction_id": listing.auction_id,
+                    "level": listing.level,
+                    "vocation": listing.vocation,
+                }),
+            })
+            .collect()
+    }
+
+    /// Match a new item auction
+    pub fn match_item_auction(&self, listing: &ItemListing) -> Vec<WatchNotification> {
+        self.watches
+            .values()
+            .filter(|watch| watch.condition.matches_item_auction(listing))
+            .map(|watch| WatchNotification {
+                account_id: watch.account_id,
+                watch_id: watch.id,
+                title: "New item auction".to_string(),
+                message: format!("{} is up for auction from {} gold", listing.item_name, listing.min_bid),
+                action_url: Some(format!("/auctions/items/{}", listing.auction_id)),
+                data: serde_json::json!({
+                    "auction_id": listing.auction_id,
+                    "item_id": listing.item_id,
+                    "min_bid": listing.min_bid,
+                }),
+            })
+            .collect()
+    }
+}
+
+impl Default for Watchlist {
+    fn default() -> Self {
+        Self::new(20)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GOLDEN_HELMET: u16 = 3365;
+
+    #[test]
+    fn test_price_drop_alert() {
+        let mut watchlist = Watchlist::default();
+        let account = Uuid::new_v4();
+        let watch_id = watchlist
+            .add(account, WatchCondition::ItemPrice { item_type_id: GOLDEN_HELMET, max_price: 400_000 })
+            .unwrap();
+        let seller = Uuid::new_v4();
+
+        // Too expensive, wrong item and buy offers are ignored
+        let expensive = MarketOffer::sell(seller, "Seller", GOLDEN_HELMET, 1, 450_000);
+        assert!(watchlist.match_offer(&expensive, "golden helmet").is_empty());
+        let buy = MarketOffer::buy(seller, "Buyer", GOLDEN_HELMET, 1, 100_000);
+        assert!(watchlist.match_offer(&buy, "golden helmet").is_empty());
+
+        let cheap = MarketOffer::sell(seller, "Seller", GOLDEN_HELMET, 1, 390_000);
+        let notifications = watchlist.match_offer(&cheap, "golden helmet");
+        assert_eq!(notifications.len(), 1);
+        assert_eq!((notifications[0].account_id, notifications[0].watch_id), (account, watch_id));
+
+        // Only a further drop alerts again
+        let same = MarketOffer::sell(seller, "Seller", GOLDEN_HELMET, 1, 390_000);
+        assert!(watchlist.match_offer(&same, "golden helmet").is_empty());
+        let cheaper = MarketOffer::sell(seller, "Seller", GOLDEN_HELMET, 1, 380_000);
+        assert_eq!(watchlist.match_offer(&cheaper, "golden helmet").len(), 1);
+    }
+
+    #[test]
+    fn test_character_auction_watch() {
+        let mut watchlist = Watchlist::default();
+        let account = Uuid::new_v4();
+        watchlist
+            .add(account, WatchCondition::CharacterAuction { vocation: Some("knight".to_string()), min_level: 200, max_level: 300 })
+            .unwrap();
+        assert_eq!(
+            watchlist.add(account, WatchCondition::CharacterAuction { vocation: None, min_level: 300, max_level: 200 }),
+            Err(WatchlistError::InvalidCondition)
+        );
+
+        let listing = |level, vocation: &str| CharacterListing {
+            auction_id: Uuid::new_v4(),
+            character_name: "Bubble".to_string(),
+            level,
+            vocation: vocation.to_string(),
+            min_bid: 500,
+        };
+
+        // Promotions match their base vocation
+        let notifications = watchlist.match_character_auction(&listing(250, "Elite Knight"));
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].account_id, account);
+
+        assert!(watchlist.match_character_auction(&listing(150, "Knight")).is_empty());
+        assert!(watchlist.match_character_auction(&listing(250, "Druid")).is_empty());
+    }
+}
@@ -0,0 +1,5 @@
+-- Migration: Character sheet privacy
+-- Version: 008
+
+-- Whether the public character sheet (equipment and combat stats) is visible
+ALTER TABLE characters ADD COLUMN IF NOT EXISTS sheet_public BOOLEAN NOT NULL DEFAULT TRUE;
@@ -0,0 +1,15 @@
+-- Migration: Market and auction watchlist
+-- Version: 009
+
+-- Conditions players are alerted about; `condition` holds a tagged
+-- watch condition (item price, item auction or character auction)
+CREATE TABLE IF NOT EXISTS market_watchlist (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    account_id INTEGER NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    condition JSONB NOT NULL,
+    last_alert_price BIGINT,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_market_watchlist_account ON market_watchlist(account_id);
+CREATE INDEX IF NOT EXISTS idx_market_watchlist_kind ON market_watchlist((condition->>'kind'));
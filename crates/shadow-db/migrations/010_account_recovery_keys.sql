@@ -0,0 +1,13 @@
+-- Migration: Account recovery keys
+-- Version: 010
+
+-- One-time key that resets the password without email. Only the hash is
+-- stored; failed attempts lock the key for a while.
+CREATE TABLE IF NOT EXISTS account_recovery_keys (
+    account_id INTEGER PRIMARY KEY REFERENCES accounts(id) ON DELETE CASCADE,
+    key_hash VARCHAR(255) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    used_at TIMESTAMP WITH TIME ZONE,
+    failed_attempts INTEGER NOT NULL DEFAULT 0,
+    last_failed_at TIMESTAMP WITH TIME ZONE
+);
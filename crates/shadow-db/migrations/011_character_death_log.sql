@@ -0,0 +1,13 @@
+-- Migration: Character death log
+-- Version: 011
+
+-- Cause, experience lost and position of each death for the death log
+-- and death statistics
+ALTER TABLE character_deaths ADD COLUMN IF NOT EXISTS death_type VARCHAR(32) NOT NULL DEFAULT 'unknown';
+ALTER TABLE character_deaths ADD COLUMN IF NOT EXISTS experience_lost BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE character_deaths ADD COLUMN IF NOT EXISTS pos_x INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE character_deaths ADD COLUMN IF NOT EXISTS pos_y INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE character_deaths ADD COLUMN IF NOT EXISTS pos_z INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_deaths_character_time ON character_deaths(character_id, death_time DESC);
+CREATE INDEX IF NOT EXISTS idx_deaths_type ON character_deaths(death_type);
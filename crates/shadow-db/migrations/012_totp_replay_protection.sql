@@ -0,0 +1,6 @@
+-- Migration: TOTP replay protection
+-- Version: 012
+
+-- Time step of the last accepted 2FA code. Codes from this step or earlier
+-- are rejected, so an intercepted code cannot be used twice.
+ALTER TABLE accounts ADD COLUMN IF NOT EXISTS totp_last_used_step BIGINT;
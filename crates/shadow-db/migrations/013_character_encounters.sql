@@ -0,0 +1,17 @@
+-- Migration: Character encounter log
+-- Version: 013
+
+-- Combat log recording is opt-in; public logs are visible to everyone
+ALTER TABLE characters ADD COLUMN IF NOT EXISTS combat_log_enabled BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE characters ADD COLUMN IF NOT EXISTS combat_log_public BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Most recent encounter summaries (DPS/HPS breakdowns) of each character
+CREATE TABLE IF NOT EXISTS character_encounters (
+    id BIGSERIAL PRIMARY KEY,
+    character_id UUID NOT NULL REFERENCES characters(uuid) ON DELETE CASCADE,
+    started_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    ended_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    summary JSONB NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_encounters_character_time ON character_encounters(character_id, ended_at DESC);
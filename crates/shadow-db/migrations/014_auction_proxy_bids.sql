@@ -0,0 +1,18 @@
+-- Migration: Auction proxy bids
+-- Version: 014
+
+-- Highest amount each bidder lets the auction raise their bid to. When a
+-- new bid arrives every ceiling is re-resolved: the highest leads, the
+-- earliest placed on ties, one increment over the runner-up.
+CREATE TABLE IF NOT EXISTS auction_proxy_bids (
+    auction_id UUID NOT NULL,
+    auction_type auction_type NOT NULL,
+    bidder_id INTEGER NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    max_amount BIGINT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (auction_id, auction_type, bidder_id)
+);
+
+-- Standing bid lookup
+CREATE INDEX IF NOT EXISTS idx_auction_bids_standing
+    ON auction_bids(auction_id, auction_type) WHERE outbid_by IS NULL;
@@ -0,0 +1,7 @@
+-- Migration: Auction anti-snipe extensions
+-- Version: 015
+
+-- Seconds the end of an auction has been pushed back by late bids, so the
+-- total extension can be capped.
+ALTER TABLE character_auctions ADD COLUMN IF NOT EXISTS extended_secs INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE item_auctions ADD COLUMN IF NOT EXISTS extended_secs INTEGER NOT NULL DEFAULT 0;
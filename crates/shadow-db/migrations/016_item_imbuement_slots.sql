@@ -0,0 +1,8 @@
+-- Migration: Item imbuement slots
+-- Version: 016
+
+-- Equipment slot of an item type and the number of imbuement slots it has.
+-- A NULL slot count uses the default for the kind of item (3 for weapons,
+-- 2 for armors, 1 otherwise).
+ALTER TABLE items ADD COLUMN IF NOT EXISTS slot_type VARCHAR(20);
+ALTER TABLE items ADD COLUMN IF NOT EXISTS imbuement_slots SMALLINT;
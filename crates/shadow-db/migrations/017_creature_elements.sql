@@ -0,0 +1,7 @@
+-- Migration: Creature elemental modifiers
+-- Version: 017
+
+-- Elemental modifiers of a creature type, the same values the game server
+-- loads into the monster's elements: damage type -> percent of damage
+-- resisted. Negative values are weaknesses, 100 is an immunity.
+ALTER TABLE creatures ADD COLUMN IF NOT EXISTS elements JSONB NOT NULL DEFAULT '{}';
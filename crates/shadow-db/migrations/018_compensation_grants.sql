@@ -0,0 +1,17 @@
+-- Migration: Compensation grants
+-- Version: 018
+
+-- Items and coins support staff sent to a character, written in the same
+-- transaction that puts the items in its depot and the coins in its bank
+CREATE TABLE IF NOT EXISTS compensation_grants (
+    id UUID PRIMARY KEY,
+    actor_id UUID NOT NULL,
+    character_id INTEGER NOT NULL REFERENCES characters(id) ON DELETE CASCADE,
+    items JSONB NOT NULL DEFAULT '[]',
+    coins BIGINT NOT NULL DEFAULT 0,
+    reason TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_compensation_grants_actor ON compensation_grants(actor_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_compensation_grants_character ON compensation_grants(character_id);
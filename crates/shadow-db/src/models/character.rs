@@ -120,7 +120,7 @@ pub enum SkillType {
     Fishing,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "vocation", rename_all = "lowercase")]
 pub enum Vocation {
     None,
@@ -145,6 +145,16 @@ impl Vocation {
         }
     }
 
+    pub fn promoted(&self) -> Self {
+        match self {
+            Self::Sorcerer => Self::MasterSorcerer,
+            Self::Druid => Self::ElderDruid,
+            Self::Paladin => Self::RoyalPaladin,
+            Self::Knight => Self::EliteKnight,
+            _ => *self,
+        }
+    }
+
     pub fn is_promoted(&self) -> bool {
         matches!(
             self,
@@ -188,6 +198,19 @@ pub struct CharacterSpell {
     pub learned_at: DateTime<Utc>,
 }
 
+/// Items and coins granted to a character by support staff
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompensationGrant {
+    pub id: Uuid,
+    pub actor_id: Uuid,
+    pub character_id: Uuid,
+    /// Item type and count of each stack
+    pub items: Vec<(i32, i32)>,
+    pub coins: i64,
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+}
+
 /// Character death record
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct CharacterDeath {
@@ -204,6 +227,11 @@ pub struct CharacterDeath {
     pub pos_y: i32,
     pub pos_z: i32,
     pub died_at: DateTime<Utc>,
+    /// Cause of death: monster, player, environment, trap, condition, suicide, unknown
+    #[sqlx(default)]
+    pub death_type: String,
+    #[sqlx(default)]
+    pub experience_lost: i64,
 }
 
 /// Character outfit
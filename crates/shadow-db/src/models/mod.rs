@@ -17,7 +17,7 @@ pub mod stats;
 
 // Re-export commonly used models
 pub use account::{Account, AccountSession, AccountType};
-pub use character::{Character, CharacterSkill, CharacterSpell, CharacterDeath, Vocation, Sex, SkullType, SkillType};
+pub use character::{Character, CharacterSkill, CharacterSpell, CharacterDeath, CompensationGrant, Vocation, Sex, SkullType, SkillType};
 pub use guild::{Guild, GuildRank, GuildMember, GuildInvite};
 pub use house::{House, HouseAccess, HouseBid, HouseAccessType, HouseBidStatus, HouseTransfer, HouseTransferType};
 pub use market::{MarketOffer, MarketTransaction, MarketOfferType, MarketOfferStatus, MarketStats, CrossRealmOffer};
@@ -5,7 +5,7 @@ use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
 use crate::models::character::{
-    Character, CharacterSkill, CharacterSpell, CharacterDeath, CharacterStorage,
+    Character, CharacterSkill, CharacterSpell, CharacterDeath, CharacterStorage, CompensationGrant,
     Vocation, Sex, SkullType, SkillType,
 };
 use crate::{DbError, Result};
@@ -387,9 +387,10 @@ impl<'a> CharacterRepository<'a> {
             r#"
             INSERT INTO character_deaths (
                 id, character_id, realm_id, level, killed_by, is_player,
-                mostdamage_by, mostdamage_is_player, unjustified, time
+                mostdamage_by, mostdamage_is_player, unjustified, time,
+                pos_x, pos_y, pos_z, death_type, experience_lost
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
             RETURNING *
             "#
         )
@@ -403,6 +404,11 @@ impl<'a> CharacterRepository<'a> {
         .bind(death.mostdamage_is_player)
         .bind(death.unjustified)
         .bind(&death.died_at)
+        .bind(death.pos_x)
+        .bind(death.pos_y)
+        .bind(death.pos_z)
+        .bind(&death.death_type)
+        .bind(death.experience_lost)
         .fetch_one(self.pool)
         .await
         .map_err(|e| DbError::Query(e.to_string()))?;
@@ -438,6 +444,124 @@ impl<'a> CharacterRepository<'a> {
         Ok(result)
     }
 
+    /// Store a finished encounter summary, keeping only the `keep` most recent
+    pub async fn record_encounter(
+        &self,
+        character_id: Uuid,
+        started_at: DateTime<Utc>,
+        ended_at: DateTime<Utc>,
+        summary: &serde_json::Value,
+        keep: i64,
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await.map_err(|e| DbError::Query(e.to_string()))?;
+
+        // Characters that did not opt in are not recorded
+        sqlx::query(
+            r#"
+            INSERT INTO character_encounters (character_id, started_at, ended_at, summary)
+            SELECT uuid, $2, $3, $4 FROM characters WHERE uuid = $1 AND combat_log_enabled
+            "#
+        )
+        .bind(character_id)
+        .bind(started_at)
+        .bind(ended_at)
+        .bind(summary)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DbError::Query(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM character_encounters
+            WHERE character_id = $1 AND id NOT IN (
+                SELECT id FROM character_encounters
+                WHERE character_id = $1
+                ORDER BY ended_at DESC, id DESC
+                LIMIT $2
+            )
+            "#
+        )
+        .bind(character_id)
+        .bind(keep)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DbError::Query(e.to_string()))?;
+
+        tx.commit().await.map_err(|e| DbError::Query(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Whether a character exists
+    pub async fn exists(&self, character_id: Uuid) -> Result<bool> {
+        sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM characters WHERE uuid = $1)")
+            .bind(character_id)
+            .fetch_one(self.pool)
+            .await
+            .map_err(|e| DbError::Query(e.to_string()))
+    }
+
+    /// Deliver a compensation grant: the items go to the character's depot
+    /// in its home town, the coins to its bank balance, and the grant is
+    /// recorded, all in one transaction
+    pub async fn deliver_grant(&self, grant: &CompensationGrant) -> Result<()> {
+        let mut tx = self.pool.begin().await.map_err(|e| DbError::Query(e.to_string()))?;
+
+        let (character_id, town_id) = sqlx::query_as::<_, (i32, i32)>(
+            "SELECT id, town_id FROM characters WHERE uuid = $1 FOR UPDATE"
+        )
+        .bind(grant.character_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| DbError::Query(e.to_string()))?
+        .ok_or_else(|| DbError::NotFound(format!("Character {}", grant.character_id)))?;
+
+        for &(item_type, count) in &grant.items {
+            sqlx::query(
+                r#"
+                INSERT INTO player_depot_items (character_id, town_id, pid, sid, itemtype, count)
+                SELECT $1, $2, 0, COALESCE(MAX(sid), 0) + 1, $3, $4
+                FROM player_depot_items WHERE character_id = $1
+                "#
+            )
+            .bind(character_id)
+            .bind(town_id)
+            .bind(item_type)
+            .bind(count)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DbError::Query(e.to_string()))?;
+        }
+
+        if grant.coins > 0 {
+            sqlx::query("UPDATE characters SET bank_balance = bank_balance + $2 WHERE id = $1")
+                .bind(character_id)
+                .bind(grant.coins)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| DbError::Query(e.to_string()))?;
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO compensation_grants (id, actor_id, character_id, items, coins, reason, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#
+        )
+        .bind(grant.id)
+        .bind(grant.actor_id)
+        .bind(character_id)
+        .bind(sqlx::types::Json(&grant.items))
+        .bind(grant.coins)
+        .bind(&grant.reason)
+        .bind(grant.created_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DbError::Query(e.to_string()))?;
+
+        tx.commit().await.map_err(|e| DbError::Query(e.to_string()))?;
+        Ok(())
+    }
+
     /// Get/set storage value
     pub async fn get_storage(&self, character_id: Uuid, key: &str) -> Result<Option<i64>> {
         let result = sqlx::query_scalar::<_, i64>(
@@ -470,6 +594,19 @@ impl<'a> CharacterRepository<'a> {
         Ok(())
     }
 
+    /// Count of each item the character carries
+    pub async fn item_counts(&self, character_id: Uuid) -> Result<Vec<(i32, i64)>> {
+        let result = sqlx::query_as::<_, (i32, i64)>(
+            "SELECT item_id, SUM(count)::BIGINT FROM character_inventory WHERE character_id = $1 GROUP BY item_id"
+        )
+        .bind(character_id)
+        .fetch_all(self.pool)
+        .await
+        .map_err(|e| DbError::Query(e.to_string()))?;
+
+        Ok(result)
+    }
+
     /// Get highscores
     pub async fn get_highscores(
         &self,
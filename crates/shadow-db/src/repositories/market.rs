@@ -240,20 +240,60 @@ impl<'a> MarketRepository<'a> {
         Ok(offer)
     }
 
-    /// Expire old offers
+    /// Expire old offers and give back what they reserved in the same
+    /// transaction: the remaining items of a sell offer go to the seller's
+    /// inbox, the remaining gold of a buy offer back to the buyer's bank.
+    /// Returns the number of offers expired.
     pub async fn expire_offers(&self) -> Result<u64> {
-        let result = sqlx::query(
+        let mut tx = self.pool.begin().await.map_err(|e| DbError::Query(e.to_string()))?;
+
+        let expired = sqlx::query_as::<_, (Uuid, OfferType, i32, i32, i64)>(
             r#"
             UPDATE market_offers 
             SET state = 'expired', updated_at = NOW()
             WHERE state = 'active' AND expires_at < NOW()
+            RETURNING character_id, offer_type, item_type_id, amount, price
             "#
         )
-        .execute(self.pool)
+        .fetch_all(&mut *tx)
         .await
         .map_err(|e| DbError::Query(e.to_string()))?;
 
-        Ok(result.rows_affected())
+        for &(character_id, offer_type, item_type_id, amount, price) in &expired {
+            if amount <= 0 {
+                continue;
+            }
+            match offer_type {
+                MarketOfferType::Sell => {
+                    sqlx::query(
+                        r#"
+                        INSERT INTO player_inbox (character_id, pid, sid, itemtype, count)
+                        SELECT c.id, 0, COALESCE(MAX(i.sid), 0) + 1, $2, $3
+                        FROM characters c LEFT JOIN player_inbox i ON i.character_id = c.id
+                        WHERE c.uuid = $1
+                        GROUP BY c.id
+                        "#
+                    )
+                    .bind(character_id)
+                    .bind(item_type_id)
+                    .bind(amount)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| DbError::Query(e.to_string()))?;
+                }
+                MarketOfferType::Buy => {
+                    sqlx::query("UPDATE characters SET bank_balance = bank_balance + $2 WHERE uuid = $1")
+                        .bind(character_id)
+                        .bind(i64::from(amount) * price)
+                        .execute(&mut *tx)
+                        .await
+                        .map_err(|e| DbError::Query(e.to_string()))?;
+                }
+            }
+        }
+
+        tx.commit().await.map_err(|e| DbError::Query(e.to_string()))?;
+        Ok(expired.len() as u64)
     }
 
     /// Get market history for an item
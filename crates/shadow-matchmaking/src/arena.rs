@@ -1,13 +1,18 @@
 //! Arena Module
 //!
 //! Handles arena instances and match management.
+//!
+//! Non-participants can spectate a match. Spectators only ever see a
+//! delayed view without hidden participant details, have their own chat
+//! and can't take any action that affects the match.
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use shadow_combat::KillCredit;
 use std::collections::HashMap;
 use uuid::Uuid;
 
-use crate::{MatchParticipant, MatchResult, MatchStats, MatchType};
+use crate::{MatchParticipant, MatchResult, MatchStats, MatchType, MatchmakingError};
 
 /// Arena definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -63,6 +68,67 @@ impl Arena {
     }
 }
 
+/// Spectator settings of a match
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpectatorConfig {
+    /// Whether non-participants may spectate
+    pub enabled: bool,
+    /// Seconds spectators lag behind the live match
+    pub delay_secs: u32,
+    /// Maximum spectators at once
+    pub max_spectators: usize,
+    /// Whether spectators may chat with each other
+    pub chat_enabled: bool,
+}
+
+impl Default for SpectatorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            delay_secs: 0,
+            max_spectators: 50,
+            chat_enabled: true,
+        }
+    }
+}
+
+/// A character watching a match
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Spectator {
+    pub character_id: Uuid,
+    pub character_name: String,
+    pub joined_at: DateTime<Utc>,
+}
+
+/// Message in the spectator chat
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpectatorMessage {
+    pub sender: String,
+    pub message: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// A participant as spectators see them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpectatedParticipant {
+    pub character_name: String,
+    pub team: u8,
+    pub kills: u32,
+    pub deaths: u32,
+}
+
+/// What spectators see of a match at a point in time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpectatorView {
+    pub match_id: Uuid,
+    pub match_type: MatchType,
+    pub team1_score: u32,
+    pub team2_score: u32,
+    pub participants: Vec<SpectatedParticipant>,
+    /// Kill feed up to the delayed point in time
+    pub events: Vec<MatchEvent>,
+}
+
 /// An active arena match
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArenaMatch {
@@ -86,6 +152,15 @@ pub struct ArenaMatch {
     pub team2_score: u32,
     /// Match events log
     pub events: Vec<MatchEvent>,
+    /// Spectator settings
+    #[serde(default)]
+    pub spectator_config: SpectatorConfig,
+    /// Characters currently spectating
+    #[serde(default)]
+    pub spectators: HashMap<Uuid, Spectator>,
+    /// Spectator chat, never shown to participants
+    #[serde(default)]
+    pub spectator_chat: Vec<SpectatorMessage>,
 }
 
 impl ArenaMatch {
@@ -107,15 +182,28 @@ impl ArenaMatch {
             team1_score: 0,
             team2_score: 0,
             events: Vec::new(),
+            spectator_config: SpectatorConfig::default(),
+            spectators: HashMap::new(),
+            spectator_chat: Vec::new(),
         }
     }
 
-    /// Record a kill
+    /// Set spectator settings
+    pub fn with_spectator_config(mut self, config: SpectatorConfig) -> Self {
+        self.spectator_config = config;
+        self
+    }
+
+    /// Record a kill. Kills involving non-participants are ignored.
     pub fn record_kill(
         &mut self,
         killer_id: Uuid,
         victim_id: Uuid,
     ) {
+        if self.authorize_action(killer_id).is_err() || self.get_participant(victim_id).is_none() {
+            return;
+        }
+
         // Update killer stats
         if let Some(killer) = self.participants.iter_mut()
             .find(|p| p.character_id == killer_id)
@@ -152,6 +240,22 @@ impl ArenaMatch {
         });
     }
 
+    /// Record a kill from combat's kill credit. `character_id` maps
+    /// creature ids to participants; creatures outside the match are ignored.
+    pub fn record_kill_credit(&mut self, credit: &KillCredit, character_id: impl Fn(u32) -> Option<Uuid>) {
+        if let (Some(killer), Some(victim)) = (character_id(credit.killer), character_id(credit.victim)) {
+            self.record_kill(killer, victim);
+        }
+
+        for assist in credit.assists.iter().filter_map(|&id| character_id(id)) {
+            if let Some(participant) = self.participants.iter_mut()
+                .find(|p| p.character_id == assist)
+            {
+                participant.stats.assists += 1;
+            }
+        }
+    }
+
     /// Record damage
     pub fn record_damage(&mut self, dealer_id: Uuid, target_id: Uuid, amount: u64) {
         if let Some(dealer) = self.participants.iter_mut()
@@ -176,6 +280,114 @@ impl ArenaMatch {
         }
     }
 
+    /// Check that a character may act in the match (attack, heal, use
+    /// items). Spectators and other outsiders may not.
+    pub fn authorize_action(&self, character_id: Uuid) -> Result<(), MatchmakingError> {
+        if self.is_over() {
+            return Err(MatchmakingError::MatchNotFound);
+        }
+        match self.get_participant(character_id) {
+            Some(participant) if !participant.left_early => Ok(()),
+            _ => Err(MatchmakingError::NotAParticipant),
+        }
+    }
+
+    /// Start spectating the match
+    pub fn add_spectator(&mut self, character_id: Uuid, character_name: &str) -> Result<(), MatchmakingError> {
+        if !self.spectator_config.enabled {
+            return Err(MatchmakingError::SpectatingDisabled);
+        }
+        if self.is_over() {
+            return Err(MatchmakingError::MatchNotFound);
+        }
+        if self.get_participant(character_id).is_some() {
+            return Err(MatchmakingError::AlreadyInMatch);
+        }
+        if !self.spectators.contains_key(&character_id) && self.spectators.len() >= self.spectator_config.max_spectators {
+            return Err(MatchmakingError::SpectatorLimit);
+        }
+
+        self.spectators.insert(character_id, Spectator {
+            character_id,
+            character_name: character_name.to_string(),
+            joined_at: Utc::now(),
+        });
+        Ok(())
+    }
+
+    /// Stop spectating
+    pub fn remove_spectator(&mut self, character_id: Uuid) -> bool {
+        self.spectators.remove(&character_id).is_some()
+    }
+
+    /// Check if a character is spectating
+    pub fn is_spectator(&self, character_id: Uuid) -> bool {
+        self.spectators.contains_key(&character_id)
+    }
+
+    /// Post to the spectator chat
+    pub fn send_spectator_chat(&mut self, character_id: Uuid, message: &str) -> Result<(), MatchmakingError> {
+        if !self.spectator_config.chat_enabled {
+            return Err(MatchmakingError::SpectatingDisabled);
+        }
+        let spectator = self.spectators.get(&character_id).ok_or(MatchmakingError::NotAParticipant)?;
+        self.spectator_chat.push(SpectatorMessage {
+            sender: spectator.character_name.clone(),
+            message: message.to_string(),
+            timestamp: Utc::now(),
+        });
+        Ok(())
+    }
+
+    /// The match as spectators see it at `now`: only events older than the
+    /// spectator delay, and no stats beyond kills and deaths
+    pub fn spectator_view(&self, now: DateTime<Utc>) -> SpectatorView {
+        let cutoff = now - chrono::Duration::seconds(self.spectator_config.delay_secs as i64);
+        let events: Vec<MatchEvent> = self.events.iter().filter(|e| e.timestamp() <= cutoff).cloned().collect();
+
+        let mut participants: Vec<SpectatedParticipant> = self.participants.iter()
+            .map(|p| SpectatedParticipant {
+                character_name: p.character_name.clone(),
+                team: p.team,
+                kills: 0,
+                deaths: 0,
+            })
+            .collect();
+        let (mut team1_score, mut team2_score) = (0, 0);
+        for event in &events {
+            let MatchEvent::Kill { killer, victim, .. } = event else {
+                continue;
+            };
+            let killer_idx = self.participants.iter().position(|p| p.character_id == *killer);
+            let victim_idx = self.participants.iter().position(|p| p.character_id == *victim);
+            if let Some(i) = killer_idx {
+                participants[i].kills += 1;
+            }
+            if let Some(i) = victim_idx {
+                participants[i].deaths += 1;
+            }
+            if let (Some(k), Some(v)) = (killer_idx, victim_idx) {
+                let killer_team = self.participants[k].team;
+                if killer_team != self.participants[v].team {
+                    if killer_team == 0 {
+                        team1_score += 1;
+                    } else {
+                        team2_score += 1;
+                    }
+                }
+            }
+        }
+
+        SpectatorView {
+            match_id: self.id,
+            match_type: self.match_type,
+            team1_score,
+            team2_score,
+            participants,
+            events,
+        }
+    }
+
     /// Get participant by ID
     pub fn get_participant(&self, character_id: Uuid) -> Option<&MatchParticipant> {
         self.participants.iter().find(|p| p.character_id == character_id)
@@ -241,6 +453,18 @@ pub enum MatchEvent {
     },
 }
 
+impl MatchEvent {
+    /// When the event happened
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        match self {
+            MatchEvent::Kill { timestamp, .. }
+            | MatchEvent::Objective { timestamp, .. }
+            | MatchEvent::Disconnect { timestamp, .. }
+            | MatchEvent::Reconnect { timestamp, .. } => *timestamp,
+        }
+    }
+}
+
 /// Arena manager
 pub struct ArenaManager {
     /// All arenas
@@ -331,3 +555,73 @@ impl Default for ArenaManager {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn participant(name: &str, team: u8) -> MatchParticipant {
+        MatchParticipant {
+            character_id: Uuid::new_v4(),
+            character_name: name.to_string(),
+            team,
+            stats: MatchStats::default(),
+            rating_before: 1000,
+            rating_change: 0,
+            left_early: false,
+        }
+    }
+
+    fn duel() -> ArenaMatch {
+        ArenaMatch::new(Uuid::new_v4(), Uuid::new_v4(), MatchType::Duel, vec![participant("Alice", 0), participant("Bob", 1)])
+    }
+
+    #[test]
+    fn test_spectator_joins_active_match() {
+        let mut arena_match = duel().with_spectator_config(SpectatorConfig { delay_secs: 30, ..Default::default() });
+        let (alice, bob) = (arena_match.participants[0].character_id, arena_match.participants[1].character_id);
+        let viewer = Uuid::new_v4();
+
+        arena_match.add_spectator(viewer, "Viewer").unwrap();
+        assert!(arena_match.is_spectator(viewer));
+        assert!(matches!(arena_match.add_spectator(alice, "Alice"), Err(MatchmakingError::AlreadyInMatch)));
+
+        arena_match.send_spectator_chat(viewer, "go bob").unwrap();
+        assert!(matches!(arena_match.send_spectator_chat(alice, "hi"), Err(MatchmakingError::NotAParticipant)));
+        assert_eq!(arena_match.spectator_chat[0].sender, "Viewer");
+
+        // The kill only shows up once the delay has passed
+        arena_match.record_kill(bob, alice);
+        let now = Utc::now();
+        let view = arena_match.spectator_view(now);
+        assert!(view.events.is_empty());
+        assert_eq!(view.team2_score, 0);
+
+        let view = arena_match.spectator_view(now + chrono::Duration::seconds(31));
+        assert_eq!((view.team1_score, view.team2_score), (0, 1));
+        assert_eq!(view.participants[1].kills, 1);
+        assert_eq!(arena_match.team2_score, 1);
+    }
+
+    #[test]
+    fn test_spectator_cannot_act() {
+        let mut arena_match = duel();
+        let alice = arena_match.participants[0].character_id;
+        let viewer = Uuid::new_v4();
+        arena_match.add_spectator(viewer, "Viewer").unwrap();
+
+        assert!(arena_match.authorize_action(alice).is_ok());
+        assert!(matches!(arena_match.authorize_action(viewer), Err(MatchmakingError::NotAParticipant)));
+
+        // Combat actions credited to a spectator change nothing
+        arena_match.record_kill(viewer, alice);
+        arena_match.record_damage(viewer, alice, 500);
+        assert_eq!(arena_match.team1_score + arena_match.team2_score, 0);
+        assert!(arena_match.events.is_empty());
+        assert_eq!(arena_match.participants[0].stats.deaths, 0);
+
+        // Spectating can be turned off per match
+        let mut private = duel().with_spectator_config(SpectatorConfig { enabled: false, ..Default::default() });
+        assert!(matches!(private.add_spectator(viewer, "Viewer"), Err(MatchmakingError::SpectatingDisabled)));
+    }
+}
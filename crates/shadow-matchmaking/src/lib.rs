@@ -4,6 +4,7 @@
 //! and ranked play systems.
 
 pub mod arena;
+pub mod penalty;
 pub mod queue;
 pub mod rating;
 pub mod tournament;
@@ -14,10 +15,11 @@ use std::collections::HashMap;
 use thiserror::Error;
 use uuid::Uuid;
 
-pub use arena::{Arena, ArenaManager, ArenaMatch};
-pub use queue::{MatchmakingQueue, QueueEntry};
-pub use rating::{PlayerRating, RatingSystem};
-pub use tournament::{Tournament, TournamentManager};
+pub use arena::{Arena, ArenaManager, ArenaMatch, SpectatorConfig, SpectatorView};
+pub use penalty::{DodgePenaltyConfig, QueueDodgePenalty};
+pub use queue::{MatchmakingQueue, PartyMember, QueueEntry};
+pub use rating::{PlayerRating, RatingAlgorithm, RatingDecayConfig, RatingSystem};
+pub use tournament::{BracketMatch, BracketSide, SeedingMode, Tournament, TournamentManager};
 
 /// Matchmaking errors
 #[derive(Debug, Error)]
@@ -51,6 +53,15 @@ pub enum MatchmakingError {
     
     #[error("Cooldown active")]
     CooldownActive,
+
+    #[error("Spectating is disabled for this match")]
+    SpectatingDisabled,
+
+    #[error("Too many spectators")]
+    SpectatorLimit,
+
+    #[error("Not a participant of this match")]
+    NotAParticipant,
     
     #[error("Database error: {0}")]
     DatabaseError(String),
@@ -191,6 +202,12 @@ pub struct MatchmakingConfig {
     pub match_cooldown: u64,
     /// Enable cross-realm matching
     pub cross_realm: bool,
+    /// Queue cooldowns for leaving matches
+    #[serde(default)]
+    pub dodge_penalty: DodgePenaltyConfig,
+    /// Formula used to update ratings after a match
+    #[serde(default)]
+    pub rating_algorithm: RatingAlgorithm,
 }
 
 impl Default for MatchmakingConfig {
@@ -204,6 +221,8 @@ impl Default for MatchmakingConfig {
             min_level: 50,
             match_cooldown: 30,
             cross_realm: true,
+            dodge_penalty: DodgePenaltyConfig::default(),
+            rating_algorithm: RatingAlgorithm::default(),
         }
     }
 }
@@ -357,6 +376,8 @@ pub struct MatchmakingSystem {
     active_matches: HashMap<Uuid, ArenaMatch>,
     /// Player match history
     match_history: HashMap<Uuid, Vec<Uuid>>,
+    /// Queue cooldowns of players who left matches
+    dodge_penalty: QueueDodgePenalty,
 }
 
 impl MatchmakingSystem {
@@ -375,10 +396,11 @@ impl MatchmakingSystem {
         }
 
         Self {
+            dodge_penalty: QueueDodgePenalty::new(config.dodge_penalty.clone()),
+            ratings: RatingSystem::new().with_algorithm(config.rating_algorithm),
             config,
             queues,
             arenas: ArenaManager::new(),
-            ratings: RatingSystem::new(),
             tournaments: TournamentManager::new(),
             active_matches: HashMap::new(),
             match_history: HashMap::new(),
@@ -406,6 +428,10 @@ impl MatchmakingSystem {
             return Err(MatchmakingError::AlreadyInMatch);
         }
 
+        if self.dodge_penalty.cooldown_remaining(character_id, Utc::now()).is_some() {
+            return Err(MatchmakingError::CooldownActive);
+        }
+
         let queue = self.queues.get_mut(&match_type)
             .ok_or(MatchmakingError::NotInQueue)?;
 
@@ -414,6 +440,44 @@ impl MatchmakingSystem {
         queue.add_player(character_id, character_name, rating)
     }
 
+    /// Queue a pre-made team, leader first, to be placed on the same team
+    pub fn queue_party(
+        &mut self,
+        members: &[(Uuid, &str, u32)],
+        match_type: MatchType,
+    ) -> Result<(), MatchmakingError> {
+        if !self.config.enabled {
+            return Err(MatchmakingError::CooldownActive);
+        }
+
+        if members.iter().any(|&(_, _, level)| level < self.config.min_level) {
+            return Err(MatchmakingError::LevelRequirementNotMet);
+        }
+
+        if members.iter().any(|&(character_id, _, _)| self.is_in_match(character_id)) {
+            return Err(MatchmakingError::AlreadyInMatch);
+        }
+
+        let now = Utc::now();
+        if members.iter().any(|&(character_id, _, _)| self.dodge_penalty.cooldown_remaining(character_id, now).is_some()) {
+            return Err(MatchmakingError::CooldownActive);
+        }
+
+        let party = members
+            .iter()
+            .map(|&(character_id, character_name, _)| PartyMember {
+                character_id,
+                character_name: character_name.to_string(),
+                rating: self.ratings.get_rating(character_id).rating,
+            })
+            .collect();
+
+        let queue = self.queues.get_mut(&match_type)
+            .ok_or(MatchmakingError::NotInQueue)?;
+
+        queue.add_party(party)
+    }
+
     /// Remove a player from queue
     pub fn dequeue_player(
         &mut self,
@@ -459,6 +523,18 @@ impl MatchmakingSystem {
         new_matches
     }
 
+    /// Leave an active match. The player loses as a quitter when the
+    /// match ends and counts as having dodged it.
+    pub fn leave_match(&mut self, match_id: Uuid, character_id: Uuid) -> Result<(), MatchmakingError> {
+        let arena_match = self.active_matches.get_mut(&match_id)
+            .ok_or(MatchmakingError::MatchNotFound)?;
+        let participant = arena_match.participants.iter_mut()
+            .find(|p| p.character_id == character_id)
+            .ok_or(MatchmakingError::NotAParticipant)?;
+        participant.left_early = true;
+        Ok(())
+    }
+
     /// End a match and process results
     pub fn end_match(
         &mut self,
@@ -472,12 +548,18 @@ impl MatchmakingSystem {
         let rating_changes = self.ratings.process_match(&arena_match, result);
 
         // Record match in history
+        let now = Utc::now();
         for participant in &arena_match.participants {
             self.match_history
                 .entry(participant.character_id)
                 .or_insert_with(Vec::new)
                 .push(match_id);
+
+            if participant.left_early {
+                self.dodge_penalty.record_dodge(participant.character_id, now);
+            }
         }
+        self.dodge_penalty.decay(now);
 
         // Return arena
         self.arenas.release_arena(arena_match.arena_id);
@@ -485,6 +567,36 @@ impl MatchmakingSystem {
         Ok(rating_changes)
     }
 
+    /// Start spectating an active match
+    pub fn spectate(
+        &mut self,
+        match_id: Uuid,
+        character_id: Uuid,
+        character_name: &str,
+    ) -> Result<SpectatorView, MatchmakingError> {
+        if self.is_in_match(character_id) {
+            return Err(MatchmakingError::AlreadyInMatch);
+        }
+        let arena_match = self.active_matches.get_mut(&match_id)
+            .ok_or(MatchmakingError::MatchNotFound)?;
+
+        arena_match.add_spectator(character_id, character_name)?;
+        Ok(arena_match.spectator_view(Utc::now()))
+    }
+
+    /// Stop spectating a match
+    pub fn stop_spectating(&mut self, match_id: Uuid, character_id: Uuid) -> bool {
+        self.active_matches.get_mut(&match_id)
+            .is_some_and(|m| m.remove_spectator(character_id))
+    }
+
+    /// Decay the ratings of players who have stopped queuing. Meant to
+    /// run once a day; running it again the same day changes nothing.
+    pub fn decay_inactive(&mut self, config: &RatingDecayConfig) -> Vec<(Uuid, i32)> {
+        self.ratings.set_decay_floor(config.min_rating);
+        self.ratings.apply_decay(config.inactive_days, config.decay_points)
+    }
+
     /// Get player's current rank
     pub fn get_rank(&self, character_id: Uuid) -> Rank {
         let rating = self.ratings.get_rating(character_id);
@@ -0,0 +1,158 @@
+//! Queue Dodge Penalty Module
+//!
+//! Players who leave a match they were placed in get a queue cooldown.
+//! Each dodge within the rolling window escalates the cooldown to the next
+//! step; once a character has gone a full window without dodging, their
+//! record is dropped and the next dodge starts from the first step again.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Dodge penalty settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DodgePenaltyConfig {
+    /// Queue cooldown after the first, second, ... dodge in the window
+    /// (seconds); further dodges repeat the last step
+    pub cooldowns: Vec<i64>,
+    /// Dodges older than this stop counting (seconds)
+    pub window: i64,
+}
+
+impl Default for DodgePenaltyConfig {
+    fn default() -> Self {
+        Self {
+            cooldowns: vec![300, 900, 3600],
+            window: 86_400,
+        }
+    }
+}
+
+/// Recent dodges of one character
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DodgeRecord {
+    /// Dodges within the window, oldest first
+    pub dodges: Vec<DateTime<Utc>>,
+    /// Queueing is blocked until then
+    pub cooldown_until: Option<DateTime<Utc>>,
+}
+
+/// Tracks dodges and queue cooldowns per character
+#[derive(Debug, Clone, Default)]
+pub struct QueueDodgePenalty {
+    config: DodgePenaltyConfig,
+    records: HashMap<Uuid, DodgeRecord>,
+}
+
+impl QueueDodgePenalty {
+    /// Create a new penalty tracker
+    pub fn new(config: DodgePenaltyConfig) -> Self {
+        Self {
+            config,
+            records: HashMap::new(),
+        }
+    }
+
+    /// Record a dodge and start the cooldown it earns
+    pub fn record_dodge(&mut self, character_id: Uuid, now: DateTime<Utc>) -> Duration {
+        let window_start = now - Duration::seconds(self.config.window);
+        let record = self.records.entry(character_id).or_default();
+        record.dodges.retain(|&at| at > window_start);
+        record.dodges.push(now);
+
+        let step = (record.dodges.len() - 1).min(self.config.cooldowns.len().saturating_sub(1));
+        let cooldown = Duration::seconds(self.config.cooldowns.get(step).copied().unwrap_or(0));
+        record.cooldown_until = Some(now + cooldown);
+        cooldown
+    }
+
+    /// Time left before the character may queue again
+    pub fn cooldown_remaining(&self, character_id: Uuid, now: DateTime<Utc>) -> Option<Duration> {
+        let until = self.records.get(&character_id)?.cooldown_until?;
+        (until > now).then(|| until - now)
+    }
+
+    /// Dodges of the character still counting towards escalation
+    pub fn dodge_count(&self, character_id: Uuid, now: DateTime<Utc>) -> usize {
+        let window_start = now - Duration::seconds(self.config.window);
+        self.records
+            .get(&character_id)
+            .map_or(0, |record| record.dodges.iter().filter(|&&at| at > window_start).count())
+    }
+
+    /// Forget characters who have been clean for a full window
+    pub fn decay(&mut self, now: DateTime<Utc>) {
+        let window_start = now - Duration::seconds(self.config.window);
+        self.records.retain(|_, record| {
+            record.dodges.last().is_some_and(|&at| at > window_start)
+                || record.cooldown_until.is_some_and(|until| until > now)
+        });
+    }
+
+    /// Number of characters with a dodge record
+    pub fn tracked(&self) -> usize {
+        self.records.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MatchResult, MatchType, MatchmakingConfig, MatchmakingError, MatchmakingSystem};
+
+    #[test]
+    fn test_repeat_dodges_escalate_cooldown() {
+        let mut penalty = QueueDodgePenalty::new(DodgePenaltyConfig::default());
+        let character_id = Uuid::new_v4();
+        let now = Utc::now();
+
+        assert!(penalty.cooldown_remaining(character_id, now).is_none());
+        assert_eq!(penalty.record_dodge(character_id, now), Duration::minutes(5));
+        assert_eq!(penalty.cooldown_remaining(character_id, now + Duration::minutes(1)), Some(Duration::minutes(4)));
+
+        let later = now + Duration::minutes(10);
+        assert!(penalty.cooldown_remaining(character_id, later).is_none());
+        assert_eq!(penalty.record_dodge(character_id, later), Duration::minutes(15));
+        assert_eq!(penalty.record_dodge(character_id, later + Duration::minutes(20)), Duration::hours(1));
+        assert_eq!(penalty.record_dodge(character_id, later + Duration::hours(2)), Duration::hours(1));
+        assert_eq!(penalty.dodge_count(character_id, later + Duration::hours(2)), 4);
+    }
+
+    #[test]
+    fn test_clean_window_resets_escalation() {
+        let mut penalty = QueueDodgePenalty::new(DodgePenaltyConfig::default());
+        let character_id = Uuid::new_v4();
+        let now = Utc::now();
+        penalty.record_dodge(character_id, now);
+        penalty.record_dodge(character_id, now + Duration::hours(1));
+
+        // Still within the window of the last dodge
+        penalty.decay(now + Duration::hours(20));
+        assert_eq!(penalty.tracked(), 1);
+
+        let clean = now + Duration::hours(26);
+        penalty.decay(clean);
+        assert_eq!(penalty.tracked(), 0);
+        assert_eq!(penalty.record_dodge(character_id, clean), Duration::minutes(5));
+    }
+
+    #[test]
+    fn test_leaving_match_blocks_requeue() {
+        let mut system = MatchmakingSystem::new(MatchmakingConfig::default());
+        let (quitter, opponent) = (Uuid::new_v4(), Uuid::new_v4());
+        system.queue_player(quitter, "Eryn", 100, MatchType::Duel).unwrap();
+        system.queue_player(opponent, "Tarok", 100, MatchType::Duel).unwrap();
+        let arena_match = system.process_queues().pop().unwrap();
+
+        system.leave_match(arena_match.id, quitter).unwrap();
+        system.end_match(arena_match.id, MatchResult::Team2Win).unwrap();
+
+        assert!(matches!(
+            system.queue_player(quitter, "Eryn", 100, MatchType::Duel),
+            Err(MatchmakingError::CooldownActive)
+        ));
+        system.queue_player(opponent, "Tarok", 100, MatchType::Duel).unwrap();
+    }
+}
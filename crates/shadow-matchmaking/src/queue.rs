@@ -1,6 +1,14 @@
 //! Matchmaking Queue Module
 //!
 //! Handles player queuing and match creation logic.
+//!
+//! Players queue alone or as a premade party that must end up on the same
+//! team. A match is assembled around the entry that has waited longest:
+//! entries within its rating range are picked in queue order until their
+//! sizes add up to a full match, and the picked entries are then packed
+//! into teams so that the teams' average ratings are as close as possible.
+//! A selection whose best packing is still further apart than the
+//! configured rating range is passed over for the next one.
 
 use chrono::{DateTime, Duration, Utc};
 use std::collections::{HashMap, VecDeque};
@@ -8,7 +16,18 @@ use uuid::Uuid;
 
 use crate::{MatchType, MatchmakingConfig, MatchmakingError, MatchParticipant, MatchStats, QueueStats};
 
-/// A player in the queue
+/// Upper bound on the entry combinations tried per match attempt
+const MAX_SEARCH_STEPS: usize = 10_000;
+
+/// A party member queued along with the party leader
+#[derive(Debug, Clone)]
+pub struct PartyMember {
+    pub character_id: Uuid,
+    pub character_name: String,
+    pub rating: i32,
+}
+
+/// A player in the queue, with their party if they queued as one
 #[derive(Debug, Clone)]
 pub struct QueueEntry {
     /// Character ID
@@ -21,6 +40,8 @@ pub struct QueueEntry {
     pub joined_at: DateTime<Utc>,
     /// Team ID (for pre-made teams)
     pub team_id: Option<Uuid>,
+    /// Other members of a pre-made team, placed on the same team
+    pub party: Vec<PartyMember>,
 }
 
 impl QueueEntry {
@@ -32,9 +53,40 @@ impl QueueEntry {
             rating,
             joined_at: Utc::now(),
             team_id: None,
+            party: Vec::new(),
         }
     }
 
+    /// Create an entry for a pre-made team led by its first member
+    pub fn party(members: Vec<PartyMember>) -> Option<Self> {
+        let mut members = members.into_iter();
+        let leader = members.next()?;
+        let mut entry = Self::new(leader.character_id, &leader.character_name, leader.rating);
+        entry.team_id = Some(Uuid::new_v4());
+        entry.party = members.collect();
+        Some(entry)
+    }
+
+    /// Number of players in this entry
+    pub fn size(&self) -> usize {
+        1 + self.party.len()
+    }
+
+    /// Sum of the ratings of everyone in this entry
+    pub fn total_rating(&self) -> i32 {
+        self.rating + self.party.iter().map(|m| m.rating).sum::<i32>()
+    }
+
+    /// Average rating of everyone in this entry
+    pub fn average_rating(&self) -> i32 {
+        self.total_rating() / self.size() as i32
+    }
+
+    /// Everyone in this entry, leader first
+    pub fn character_ids(&self) -> impl Iterator<Item = Uuid> + '_ {
+        std::iter::once(self.character_id).chain(self.party.iter().map(|m| m.character_id))
+    }
+
     /// How long this player has been waiting
     pub fn wait_time(&self) -> Duration {
         Utc::now() - self.joined_at
@@ -48,6 +100,112 @@ impl QueueEntry {
     }
 }
 
+/// Picks queue entries for a match and packs them into balanced teams
+struct TeamSearch<'a> {
+    /// Entries in rating range of the anchor, in queue order
+    entries: Vec<&'a QueueEntry>,
+    team_size: usize,
+    team_count: usize,
+    players_needed: usize,
+    /// Largest allowed difference between team average ratings
+    max_spread: i32,
+    steps: usize,
+}
+
+impl TeamSearch<'_> {
+    /// Add entries after `next` to `picked` until the match is full and
+    /// can be packed. Returns the picked entries with their teams.
+    fn select(&mut self, next: usize, picked: &mut Vec<usize>, players: usize) -> Option<Vec<(usize, u8)>> {
+        self.steps += 1;
+        if players == self.players_needed {
+            let teams = self.pack(picked)?;
+            return Some(picked.iter().copied().zip(teams).collect());
+        }
+
+        for i in next..self.entries.len() {
+            if self.steps >= MAX_SEARCH_STEPS {
+                return None;
+            }
+            let size = self.entries[i].size();
+            if players + size > self.players_needed {
+                continue;
+            }
+            picked.push(i);
+            if let Some(found) = self.select(i + 1, picked, players + size) {
+                return Some(found);
+            }
+            picked.pop();
+        }
+        None
+    }
+
+    /// Most balanced packing of the picked entries into full teams, if
+    /// the teams end up within the allowed spread
+    fn pack(&self, picked: &[usize]) -> Option<Vec<u8>> {
+        // Placing the largest parties first runs into dead ends early
+        let mut order: Vec<usize> = (0..picked.len()).collect();
+        order.sort_by_key(|&k| std::cmp::Reverse(self.entries[picked[k]].size()));
+
+        let mut packing = Packing {
+            fill: vec![0; self.team_count],
+            rating: vec![0; self.team_count],
+            teams: vec![0; picked.len()],
+            best: None,
+        };
+        self.pack_from(picked, &order, 0, &mut packing);
+        packing
+            .best
+            .filter(|(spread, _)| *spread <= self.max_spread)
+            .map(|(_, teams)| teams)
+    }
+
+    fn pack_from(&self, picked: &[usize], order: &[usize], depth: usize, packing: &mut Packing) {
+        if packing.best.as_ref().is_some_and(|(spread, _)| *spread == 0) {
+            return;
+        }
+        let Some(&k) = order.get(depth) else {
+            let max = packing.rating.iter().max().copied().unwrap_or(0);
+            let min = packing.rating.iter().min().copied().unwrap_or(0);
+            let spread = (max - min) / self.team_size as i32;
+            if packing.best.as_ref().is_none_or(|(best, _)| spread < *best) {
+                packing.best = Some((spread, packing.teams.clone()));
+            }
+            return;
+        };
+
+        let entry = self.entries[picked[k]];
+        for team in 0..self.team_count {
+            if packing.fill[team] + entry.size() > self.team_size {
+                continue;
+            }
+            let empty = packing.fill[team] == 0;
+            packing.fill[team] += entry.size();
+            packing.rating[team] += entry.total_rating();
+            packing.teams[k] = team as u8;
+            self.pack_from(picked, order, depth + 1, packing);
+            packing.fill[team] -= entry.size();
+            packing.rating[team] -= entry.total_rating();
+
+            // Teams are filled in order, so every later team is empty too
+            if empty {
+                break;
+            }
+        }
+    }
+}
+
+/// Team assignment in progress
+struct Packing {
+    /// Players per team
+    fill: Vec<usize>,
+    /// Rating sum per team
+    rating: Vec<i32>,
+    /// Team of each picked entry
+    teams: Vec<u8>,
+    /// Smallest spread found and its assignment
+    best: Option<(i32, Vec<u8>)>,
+}
+
 /// Matchmaking queue for a specific match type
 pub struct MatchmakingQueue {
     /// Match type
@@ -82,24 +240,42 @@ impl MatchmakingQueue {
         character_name: &str,
         rating: i32,
     ) -> Result<(), MatchmakingError> {
-        if self.player_lookup.contains_key(&character_id) {
+        self.add_entry(QueueEntry::new(character_id, character_name, rating))
+    }
+
+    /// Add a pre-made team to the queue. The first member leads; the party
+    /// cannot be larger than a team.
+    pub fn add_party(&mut self, members: Vec<PartyMember>) -> Result<(), MatchmakingError> {
+        if members.len() > self.match_type.team_size() {
+            return Err(MatchmakingError::InvalidTeamSize);
+        }
+        let entry = QueueEntry::party(members).ok_or(MatchmakingError::InvalidTeamSize)?;
+        self.add_entry(entry)
+    }
+
+    fn add_entry(&mut self, entry: QueueEntry) -> Result<(), MatchmakingError> {
+        let mut ids: Vec<Uuid> = entry.character_ids().collect();
+        ids.sort();
+        ids.dedup();
+        if ids.len() != entry.size() {
+            return Err(MatchmakingError::InvalidTeamSize);
+        }
+        if ids.iter().any(|id| self.player_lookup.contains_key(id)) {
             return Err(MatchmakingError::AlreadyInQueue);
         }
 
-        let entry = QueueEntry::new(character_id, character_name, rating);
         let index = self.queue.len();
+        self.player_lookup.extend(ids.into_iter().map(|id| (id, index)));
         self.queue.push_back(entry);
-        self.player_lookup.insert(character_id, index);
 
         Ok(())
     }
 
-    /// Remove a player from the queue
+    /// Remove a player from the queue, along with their party
     pub fn remove_player(&mut self, character_id: Uuid) -> Result<(), MatchmakingError> {
         if let Some(&index) = self.player_lookup.get(&character_id) {
             self.queue.remove(index);
-            self.player_lookup.remove(&character_id);
-            
+
             // Rebuild lookup after removal
             self.rebuild_lookup();
             Ok(())
@@ -112,7 +288,7 @@ impl MatchmakingQueue {
     fn rebuild_lookup(&mut self) {
         self.player_lookup.clear();
         for (i, entry) in self.queue.iter().enumerate() {
-            self.player_lookup.insert(entry.character_id, i);
+            self.player_lookup.extend(entry.character_ids().map(|id| (id, i)));
         }
     }
 
@@ -122,73 +298,74 @@ impl MatchmakingQueue {
         let team_count = self.match_type.team_count();
         let players_needed = team_size * team_count;
 
-        if self.queue.len() < players_needed {
+        let players_queued: usize = self.queue.iter().map(|e| e.size()).sum();
+        if players_queued < players_needed {
             return None;
         }
 
-        // Find compatible players
-        let mut matched = Vec::new();
-        let mut matched_indices = Vec::new();
-
-        // Start with the player who has waited longest
-        if let Some(anchor) = self.queue.front() {
-            let anchor_rating = anchor.rating;
-            let range = anchor.expanded_range(config);
-
-            for (i, entry) in self.queue.iter().enumerate() {
-                let diff = (entry.rating - anchor_rating).abs();
-                if diff <= range {
-                    matched.push(entry.clone());
-                    matched_indices.push(i);
-
-                    if matched.len() >= players_needed {
-                        break;
-                    }
-                }
-            }
-        }
-
-        if matched.len() >= players_needed {
-            // Remove matched players from queue
-            matched_indices.sort_by(|a, b| b.cmp(a)); // Sort descending to remove from end first
-            for index in matched_indices {
-                if let Some(entry) = self.queue.remove(index) {
-                    self.total_wait_time = self.total_wait_time + entry.wait_time();
-                }
-            }
-            self.rebuild_lookup();
+        // Start with the entry that has waited longest
+        let anchor = self.queue.front()?;
+        let anchor_rating = anchor.average_rating();
+        let range = anchor.expanded_range(config);
+        let candidates: Vec<usize> = (0..self.queue.len())
+            .filter(|&i| (self.queue[i].average_rating() - anchor_rating).abs() <= range)
+            .collect();
 
-            // Create participants with team assignments
-            let participants = self.create_participants(matched, team_size);
+        // Single-player teams are already bounded by the anchor's range
+        let max_spread = if team_size > 1 { config.rating_range } else { i32::MAX };
+        let mut search = TeamSearch {
+            entries: candidates.iter().map(|&i| &self.queue[i]).collect(),
+            team_size,
+            team_count,
+            players_needed,
+            max_spread,
+            steps: 0,
+        };
+        let teams = search.select(1, &mut vec![0], self.queue[0].size())?;
 
-            // Update stats
-            self.matches_created += 1;
-            self.matches_in_last_hour.push(Utc::now());
+        // Remove matched entries from the queue, last first
+        let mut matched: Vec<(usize, u8)> = teams
+            .into_iter()
+            .map(|(picked, team)| (candidates[picked], team))
+            .collect();
+        matched.sort_by_key(|&(index, _)| std::cmp::Reverse(index));
 
-            return Some(participants);
+        let mut participants = Vec::with_capacity(players_needed);
+        for (index, team) in matched {
+            if let Some(entry) = self.queue.remove(index) {
+                self.total_wait_time += entry.wait_time();
+                participants.extend(Self::create_participants(entry, team));
+            }
         }
+        self.rebuild_lookup();
+        participants.sort_by_key(|p| p.team);
 
-        None
+        // Update stats
+        self.matches_created += 1;
+        self.matches_in_last_hour.push(Utc::now());
+
+        Some(participants)
     }
 
-    /// Create match participants from matched players
-    fn create_participants(
-        &self,
-        matched: Vec<QueueEntry>,
-        team_size: usize,
-    ) -> Vec<MatchParticipant> {
-        matched.into_iter().enumerate().map(|(i, entry)| {
-            let team = (i / team_size) as u8;
-            MatchParticipant {
-                character_id: entry.character_id,
-                character_name: entry.character_name,
+    /// Create match participants for an entry placed on `team`
+    fn create_participants(entry: QueueEntry, team: u8) -> Vec<MatchParticipant> {
+        let leader = PartyMember {
+            character_id: entry.character_id,
+            character_name: entry.character_name,
+            rating: entry.rating,
+        };
+        std::iter::once(leader)
+            .chain(entry.party)
+            .map(|member| MatchParticipant {
+                character_id: member.character_id,
+                character_name: member.character_name,
                 team,
                 stats: MatchStats::default(),
-                rating_before: entry.rating,
+                rating_before: member.rating,
                 rating_change: 0,
                 left_early: false,
-            }
-        }).collect()
+            })
+            .collect()
     }
 
     /// Get queue statistics
@@ -222,3 +399,64 @@ impl MatchmakingQueue {
         self.player_lookup.contains_key(&character_id)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn member(name: &str, rating: i32) -> PartyMember {
+        PartyMember { character_id: Uuid::new_v4(), character_name: name.to_string(), rating }
+    }
+
+    fn team_average(participants: &[MatchParticipant], team: u8) -> i32 {
+        let ratings: Vec<i32> = participants.iter().filter(|p| p.team == team).map(|p| p.rating_before).collect();
+        ratings.iter().sum::<i32>() / ratings.len() as i32
+    }
+
+    #[test]
+    fn test_party_and_solos_form_balanced_3v3() {
+        let config = MatchmakingConfig::default();
+        let mut queue = MatchmakingQueue::new(MatchType::Team3v3);
+        let duo = vec![member("Eryn", 1100), member("Tarok", 1000)];
+        let duo_ids: Vec<Uuid> = duo.iter().map(|m| m.character_id).collect();
+        queue.add_party(duo).unwrap();
+        for (name, rating) in [("Ash", 1050), ("Brin", 1000), ("Cael", 1080)] {
+            queue.add_player(Uuid::new_v4(), name, rating).unwrap();
+        }
+        assert!(queue.is_in_queue(duo_ids[1]));
+        assert!(queue.try_match(&config).is_none());
+
+        queue.add_player(Uuid::new_v4(), "Dorn", 1030).unwrap();
+        let participants = queue.try_match(&config).unwrap();
+        assert_eq!(participants.len(), 6);
+        assert_eq!(participants.iter().filter(|p| p.team == 0).count(), 3);
+
+        // The duo stays together and the teams are as even as possible
+        let duo_team = participants.iter().find(|p| p.character_id == duo_ids[0]).unwrap().team;
+        assert!(participants.iter().any(|p| p.character_id == duo_ids[1] && p.team == duo_team));
+        assert!((team_average(&participants, 0) - team_average(&participants, 1)).abs() <= 10);
+        assert_eq!(queue.queue_size(), 0);
+        assert!(!queue.is_in_queue(duo_ids[1]));
+    }
+
+    #[test]
+    fn test_oversized_party_rejected_and_unbalanced_teams_wait() {
+        let mut queue = MatchmakingQueue::new(MatchType::Team2v2);
+        let trio = vec![member("Eryn", 1000), member("Tarok", 1000), member("Ash", 1000)];
+        assert!(matches!(queue.add_party(trio), Err(MatchmakingError::InvalidTeamSize)));
+
+        // Both solos are in range of the duo, but together they are too weak
+        let config = MatchmakingConfig { rating_range: 100, ..Default::default() };
+        queue.add_party(vec![member("Eryn", 1500), member("Tarok", 1300)]).unwrap();
+        queue.add_player(Uuid::new_v4(), "Cael", 1300).unwrap();
+        queue.add_player(Uuid::new_v4(), "Dorn", 1290).unwrap();
+        assert!(queue.try_match(&config).is_none());
+
+        // A stronger solo makes an even match, leaving the weakest waiting
+        queue.add_player(Uuid::new_v4(), "Finn", 1380).unwrap();
+        let participants = queue.try_match(&config).unwrap();
+        assert_eq!(participants.len(), 4);
+        assert!(participants.iter().all(|p| p.character_name != "Dorn"));
+        assert_eq!(queue.queue_size(), 1);
+    }
+}
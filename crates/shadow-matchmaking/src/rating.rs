@@ -1,12 +1,18 @@
 //! Rating System Module
 //!
-//! Implements ELO-based rating calculations for competitive play.
-
+//! Implements rating calculations for competitive play. Realms pick
+//! either the ELO formula or Glicko-2, which also tracks how certain each
+//! rating is: players with few games have a high rating deviation and
+//! move quickly, established players with a low deviation move little.
+//! Ratings of players who stop playing decay towards a floor so that
+//! inactive players do not hold on to their rank indefinitely.
+
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
 
-use crate::{ArenaMatch, MatchResult, MatchType};
+use crate::{ArenaMatch, MatchResult, MatchType, Rank};
 
 /// Player rating information
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,6 +37,26 @@ pub struct PlayerRating {
     pub lose_streak: u32,
     /// Rating for specific match types
     pub type_ratings: HashMap<MatchType, i32>,
+    /// When the player last finished a match
+    #[serde(default)]
+    pub last_match_at: Option<DateTime<Utc>>,
+    /// When inactivity decay was last applied
+    #[serde(default)]
+    pub last_decay_at: Option<DateTime<Utc>>,
+    /// Glicko-2 rating deviation
+    #[serde(default = "default_rating_deviation")]
+    pub rating_deviation: f64,
+    /// Glicko-2 volatility
+    #[serde(default = "default_volatility")]
+    pub volatility: f64,
+}
+
+fn default_rating_deviation() -> f64 {
+    GLICKO_MAX_DEVIATION
+}
+
+fn default_volatility() -> f64 {
+    GLICKO_DEFAULT_VOLATILITY
 }
 
 impl PlayerRating {
@@ -47,6 +73,10 @@ impl PlayerRating {
             best_win_streak: 0,
             lose_streak: 0,
             type_ratings: HashMap::new(),
+            last_match_at: None,
+            last_decay_at: None,
+            rating_deviation: GLICKO_MAX_DEVIATION,
+            volatility: GLICKO_DEFAULT_VOLATILITY,
         }
     }
 
@@ -64,6 +94,7 @@ impl PlayerRating {
         self.games_played += 1;
         self.games_won += 1;
         self.win_streak += 1;
+        self.last_match_at = Some(Utc::now());
         self.lose_streak = 0;
 
         if self.win_streak > self.best_win_streak {
@@ -82,6 +113,7 @@ impl PlayerRating {
         self.games_lost += 1;
         self.lose_streak += 1;
         self.win_streak = 0;
+        self.last_match_at = Some(Utc::now());
     }
 
     /// Record a draw
@@ -89,19 +121,72 @@ impl PlayerRating {
         self.games_played += 1;
         self.win_streak = 0;
         self.lose_streak = 0;
+        self.last_match_at = Some(Utc::now());
+    }
+}
+
+/// Rating deviation of a new player, and the most any player can have
+pub const GLICKO_MAX_DEVIATION: f64 = 350.0;
+/// Rating deviation never drops below this, so ratings keep moving
+pub const GLICKO_MIN_DEVIATION: f64 = 30.0;
+/// Volatility of a new player
+pub const GLICKO_DEFAULT_VOLATILITY: f64 = 0.06;
+/// Glicko-2 system constant, limiting how fast volatility changes
+const GLICKO_TAU: f64 = 0.5;
+/// Conversion between the Glicko and Glicko-2 scales
+const GLICKO_SCALE: f64 = 173.7178;
+
+/// Formula used to update ratings after a match
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum RatingAlgorithm {
+    /// ELO with a base K-factor
+    Elo { k_factor: f64 },
+    /// Glicko-2 with rating deviation and volatility
+    Glicko2,
+}
+
+impl Default for RatingAlgorithm {
+    fn default() -> Self {
+        RatingAlgorithm::Elo { k_factor: 32.0 }
     }
 }
 
-/// Rating system using modified ELO
+/// Inactivity decay settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RatingDecayConfig {
+    /// Days without a match before decay starts
+    pub inactive_days: u32,
+    /// Rating lost per day of decay
+    pub decay_points: i32,
+    /// Decay never takes a player below this rating
+    pub min_rating: i32,
+}
+
+impl Default for RatingDecayConfig {
+    fn default() -> Self {
+        Self {
+            inactive_days: 28,
+            decay_points: 25,
+            min_rating: 1000,
+        }
+    }
+}
+
+/// Rating system using modified ELO or Glicko-2
 pub struct RatingSystem {
     /// Player ratings
     ratings: HashMap<Uuid, PlayerRating>,
+    /// Formula used by `process_match`
+    algorithm: RatingAlgorithm,
     /// Base K-factor for rating calculations
     k_factor: f64,
     /// New player K-factor boost
     new_player_boost: f64,
     /// Streak bonus multiplier
     streak_bonus: f64,
+    /// Lowest rating inactivity decay goes down to
+    decay_floor: i32,
 }
 
 impl RatingSystem {
@@ -109,10 +194,66 @@ impl RatingSystem {
     pub fn new() -> Self {
         Self {
             ratings: HashMap::new(),
+            algorithm: RatingAlgorithm::default(),
             k_factor: 32.0,
             new_player_boost: 1.5,
             streak_bonus: 0.1,
+            decay_floor: Rank::Bronze1.min_rating(),
+        }
+    }
+
+    /// Use a different rating formula
+    pub fn with_algorithm(mut self, algorithm: RatingAlgorithm) -> Self {
+        if let RatingAlgorithm::Elo { k_factor } = algorithm {
+            self.k_factor = k_factor;
         }
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// Formula used by `process_match`
+    pub fn algorithm(&self) -> RatingAlgorithm {
+        self.algorithm
+    }
+
+    /// Set the lowest rating inactivity decay goes down to
+    pub fn with_decay_floor(mut self, floor: i32) -> Self {
+        self.set_decay_floor(floor);
+        self
+    }
+
+    /// Set the lowest rating inactivity decay goes down to. Never below
+    /// the bottom of the lowest rank.
+    pub fn set_decay_floor(&mut self, floor: i32) {
+        self.decay_floor = floor.max(Rank::Bronze1.min_rating());
+    }
+
+    /// Reduce the rating of players without a match in `inactive_days`
+    /// by `decay_points`. Runs at most once per player per day.
+    pub fn apply_decay(&mut self, inactive_days: u32, decay_points: i32) -> Vec<(Uuid, i32)> {
+        self.apply_decay_at(inactive_days, decay_points, Utc::now())
+    }
+
+    /// Apply inactivity decay as of `now`
+    pub fn apply_decay_at(&mut self, inactive_days: u32, decay_points: i32, now: DateTime<Utc>) -> Vec<(Uuid, i32)> {
+        let cutoff = now - Duration::days(inactive_days as i64);
+        let floor = self.decay_floor;
+        let mut changes = Vec::new();
+
+        for rating in self.ratings.values_mut() {
+            let inactive = rating.last_match_at.is_some_and(|at| at <= cutoff);
+            let decayed_today = rating.last_decay_at.is_some_and(|at| at.date_naive() == now.date_naive());
+            if !inactive || decayed_today || rating.rating <= floor {
+                continue;
+            }
+
+            let decayed = (rating.rating - decay_points.max(0)).max(floor);
+            changes.push((rating.character_id, decayed - rating.rating));
+            rating.rating = decayed;
+            rating.last_decay_at = Some(now);
+        }
+
+        changes
     }
 
     /// Get or create a player's rating
@@ -193,6 +334,10 @@ impl RatingSystem {
         arena_match: &ArenaMatch,
         result: MatchResult,
     ) -> Vec<(Uuid, i32)> {
+        if self.algorithm == RatingAlgorithm::Glicko2 {
+            return self.process_match_glicko2(arena_match, result);
+        }
+
         let mut changes = Vec::new();
 
         match result {
@@ -290,6 +435,79 @@ impl RatingSystem {
         changes
     }
 
+    /// Process a completed match with Glicko-2. Every participant is rated
+    /// against the other team as a single opponent, with the team's average
+    /// rating and rating deviation.
+    fn process_match_glicko2(&mut self, arena_match: &ArenaMatch, result: MatchResult) -> Vec<(Uuid, i32)> {
+        let winning_team = match result {
+            MatchResult::Team1Win => Some(0),
+            MatchResult::Team2Win => Some(1),
+            MatchResult::Draw => None,
+            MatchResult::Cancelled | MatchResult::InProgress => return Vec::new(),
+        };
+
+        let team_average = |team: u8| {
+            let members: Vec<_> = arena_match.participants.iter().filter(|p| p.team == team).collect();
+            let count = members.len().max(1) as f64;
+            let rating = members.iter().map(|p| p.rating_before as f64).sum::<f64>() / count;
+            let deviation = members
+                .iter()
+                .map(|p| self.get_rating(p.character_id).rating_deviation)
+                .sum::<f64>()
+                / count;
+            (rating, if members.is_empty() { GLICKO_MAX_DEVIATION } else { deviation })
+        };
+        let teams = [team_average(0), team_average(1)];
+
+        let mut changes = Vec::new();
+        for participant in &arena_match.participants {
+            let (opponent_rating, opponent_deviation) = teams[usize::from(participant.team != 1)];
+            let score = match winning_team {
+                Some(team) if team == participant.team => 1.0,
+                Some(_) => 0.0,
+                None => 0.5,
+            };
+
+            let rating = self.get_rating_mut(participant.character_id);
+            let (new_rating, deviation, volatility) = glicko2_update(
+                participant.rating_before as f64,
+                rating.rating_deviation,
+                rating.volatility,
+                opponent_rating,
+                opponent_deviation,
+                score,
+            );
+            rating.rating_deviation = deviation;
+            rating.volatility = volatility;
+
+            let mut change = new_rating.round() as i32 - participant.rating_before;
+            match winning_team {
+                Some(team) if team == participant.team => {
+                    // No gain for quitters
+                    if participant.left_early {
+                        change = 0;
+                    }
+                    rating.record_win(change.max(0));
+                }
+                Some(_) => {
+                    // Extra penalty for leaving early
+                    if participant.left_early {
+                        change *= 2;
+                    }
+                    rating.record_loss(-change.min(0));
+                }
+                None => {
+                    rating.rating = (rating.rating + change).max(0);
+                    rating.peak_rating = rating.peak_rating.max(rating.rating);
+                    rating.record_draw();
+                }
+            }
+            changes.push((participant.character_id, change));
+        }
+
+        changes
+    }
+
     /// Get leaderboard for a match type
     pub fn get_leaderboard(&self, _match_type: MatchType, limit: usize) -> Vec<(Uuid, i32)> {
         let mut players: Vec<_> = self.ratings.iter()
@@ -307,6 +525,69 @@ impl RatingSystem {
     }
 }
 
+/// One Glicko-2 rating period of a single game. Returns the new rating,
+/// rating deviation and volatility.
+fn glicko2_update(
+    rating: f64,
+    deviation: f64,
+    volatility: f64,
+    opponent_rating: f64,
+    opponent_deviation: f64,
+    score: f64,
+) -> (f64, f64, f64) {
+    let mu = (rating - 1500.0) / GLICKO_SCALE;
+    let phi = deviation / GLICKO_SCALE;
+    let mu_j = (opponent_rating - 1500.0) / GLICKO_SCALE;
+    let phi_j = opponent_deviation / GLICKO_SCALE;
+
+    let g = 1.0 / (1.0 + 3.0 * phi_j.powi(2) / std::f64::consts::PI.powi(2)).sqrt();
+    let expected = 1.0 / (1.0 + (-g * (mu - mu_j)).exp());
+    let v = 1.0 / (g.powi(2) * expected * (1.0 - expected));
+    let delta = v * g * (score - expected);
+
+    // New volatility by the Illinois algorithm
+    let a = volatility.powi(2).ln();
+    let f = |x: f64| {
+        let ex = x.exp();
+        ex * (delta.powi(2) - phi.powi(2) - v - ex) / (2.0 * (phi.powi(2) + v + ex).powi(2))
+            - (x - a) / GLICKO_TAU.powi(2)
+    };
+    let mut big_a = a;
+    let mut big_b = if delta.powi(2) > phi.powi(2) + v {
+        (delta.powi(2) - phi.powi(2) - v).ln()
+    } else {
+        let mut k = 1.0;
+        while f(a - k * GLICKO_TAU) < 0.0 {
+            k += 1.0;
+        }
+        a - k * GLICKO_TAU
+    };
+    let (mut f_a, mut f_b) = (f(big_a), f(big_b));
+    while (big_b - big_a).abs() > 1e-6 {
+        let big_c = big_a + (big_a - big_b) * f_a / (f_b - f_a);
+        let f_c = f(big_c);
+        if f_c * f_b <= 0.0 {
+            big_a = big_b;
+            f_a = f_b;
+        } else {
+            f_a /= 2.0;
+        }
+        big_b = big_c;
+        f_b = f_c;
+    }
+    let new_volatility = (big_a / 2.0).exp();
+
+    let phi_star = (phi.powi(2) + new_volatility.powi(2)).sqrt();
+    let new_phi = 1.0 / (1.0 / phi_star.powi(2) + 1.0 / v).sqrt();
+    let new_mu = mu + new_phi.powi(2) * g * (score - expected);
+
+    (
+        GLICKO_SCALE * new_mu + 1500.0,
+        (GLICKO_SCALE * new_phi).clamp(GLICKO_MIN_DEVIATION, GLICKO_MAX_DEVIATION),
+        new_volatility,
+    )
+}
+
 impl Default for RatingSystem {
     fn default() -> Self {
         Self::new()
@@ -351,4 +632,88 @@ mod tests {
         assert_eq!(rating.win_streak, 0);
         assert_eq!(rating.lose_streak, 1);
     }
+
+    #[test]
+    fn test_active_player_does_not_decay() {
+        let mut system = RatingSystem::new();
+        let now = Utc::now();
+        let player = system.get_rating_mut(Uuid::new_v4());
+        player.record_win(200);
+        player.last_match_at = Some(now - Duration::days(3));
+
+        assert!(system.apply_decay_at(28, 25, now).is_empty());
+        // Never played, nothing to decay from
+        system.get_rating_mut(Uuid::new_v4());
+        assert!(system.apply_decay_at(28, 25, now).is_empty());
+    }
+
+    #[test]
+    fn test_inactive_player_decays_to_floor_once_per_day() {
+        let mut system = RatingSystem::new().with_decay_floor(-500);
+        let now = Utc::now();
+        let character_id = Uuid::new_v4();
+        let player = system.get_rating_mut(character_id);
+        player.record_loss(900);
+        player.last_match_at = Some(now - Duration::days(200));
+
+        assert_eq!(system.apply_decay_at(28, 60, now), vec![(character_id, -60)]);
+        assert!(system.apply_decay_at(28, 60, now).is_empty());
+        assert_eq!(system.get_rating(character_id).rating, 40);
+
+        for day in 1..=5 {
+            system.apply_decay_at(28, 60, now + Duration::days(day));
+        }
+        let rating = system.get_rating(character_id).rating;
+        assert_eq!(rating, Rank::Bronze1.min_rating());
+        assert_eq!(Rank::from_rating(rating), Rank::Bronze1);
+    }
+
+    fn duel(player: Uuid, opponent: Uuid, rating: i32) -> ArenaMatch {
+        let participant = |character_id, team| crate::MatchParticipant {
+            character_id,
+            character_name: String::new(),
+            team,
+            stats: Default::default(),
+            rating_before: rating,
+            rating_change: 0,
+            left_early: false,
+        };
+        ArenaMatch::new(Uuid::new_v4(), Uuid::new_v4(), MatchType::Duel, vec![participant(player, 0), participant(opponent, 1)])
+    }
+
+    #[test]
+    fn test_glicko2_newcomer_moves_more_than_veteran() {
+        let mut system = RatingSystem::new().with_algorithm(RatingAlgorithm::Glicko2);
+        let (newcomer, veteran, opponent) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let settled = system.get_rating_mut(veteran);
+        settled.rating_deviation = 50.0;
+        settled.games_played = 300;
+
+        let newcomer_gain = system.process_match(&duel(newcomer, opponent, 1000), MatchResult::Team1Win)[0].1;
+        system.get_rating_mut(opponent).rating = 1000;
+        let veteran_gain = system.process_match(&duel(veteran, opponent, 1000), MatchResult::Team1Win)[0].1;
+
+        assert!(newcomer_gain > 3 * veteran_gain, "{} vs {}", newcomer_gain, veteran_gain);
+        assert!(veteran_gain > 0);
+        assert_eq!(system.get_rating(newcomer).rating, 1000 + newcomer_gain);
+
+        // The newcomer's rating became more certain; the veteran's stays settled
+        assert!(system.get_rating(newcomer).rating_deviation < 300.0);
+        assert!(system.get_rating(veteran).rating_deviation < 55.0);
+    }
+
+    #[test]
+    fn test_glicko2_matches_reference_example() {
+        // Glickman's example, one game at a time against the 1400 opponent
+        let (rating, deviation, volatility) = glicko2_update(1500.0, 200.0, 0.06, 1400.0, 30.0, 1.0);
+        assert!((rating - 1563.6).abs() < 0.1, "{}", rating);
+        assert!((deviation - 175.4).abs() < 0.1, "{}", deviation);
+        assert!((volatility - 0.06).abs() < 1e-3);
+
+        // Losses lower the rating; draws between equals leave it
+        let (rating, ..) = glicko2_update(1500.0, 200.0, 0.06, 1400.0, 30.0, 0.0);
+        assert!(rating < 1500.0);
+        let (rating, ..) = glicko2_update(1500.0, 200.0, 0.06, 1500.0, 200.0, 0.5);
+        assert!((rating - 1500.0).abs() < 1e-9);
+    }
 }
@@ -1,10 +1,18 @@
 //! Tournament Module
 //!
 //! Handles tournament creation, brackets, and progression.
+//!
+//! Elimination brackets are laid out in full when the tournament starts.
+//! Every match knows where its winner (and, in double elimination, its
+//! loser) plays next, so reporting a result fills the next matches. When
+//! the field is not a power of two the top seeds get byes; a match left
+//! with a single player once all its feeds are in is a bye as well.
 
 use chrono::{DateTime, Duration, Utc};
+use rand::seq::SliceRandom;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 use crate::{MatchType, MatchResult, Rank};
@@ -37,6 +45,37 @@ pub enum TournamentStatus {
     Cancelled,
 }
 
+/// How participants are seeded into the bracket
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SeedingMode {
+    /// Shuffled
+    Random,
+    /// Highest registration rating first
+    #[default]
+    ByRating,
+    /// In the given order, top seed first
+    Manual(Vec<Uuid>),
+}
+
+/// Part of the bracket a match belongs to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BracketSide {
+    #[default]
+    Winners,
+    /// Losers bracket of a double elimination
+    Losers,
+    /// Winners bracket champion against losers bracket champion
+    GrandFinal,
+}
+
+/// A slot of a later match that a result feeds into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BracketSlot {
+    pub match_id: Uuid,
+    /// 1 or 2
+    pub slot: u8,
+}
+
 /// A tournament participant
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TournamentParticipant {
@@ -56,6 +95,10 @@ pub struct TournamentParticipant {
     pub losses: u32,
     /// Rating at time of registration
     pub registration_rating: i32,
+    /// Bracket stage at which the participant was knocked out; later
+    /// stages place higher
+    #[serde(default)]
+    pub eliminated_at: Option<u32>,
 }
 
 /// A bracket match
@@ -81,6 +124,18 @@ pub struct BracketMatch {
     pub completed_at: Option<DateTime<Utc>>,
     /// Is a bye match
     pub is_bye: bool,
+    /// Part of the bracket
+    #[serde(default)]
+    pub side: BracketSide,
+    /// Where the winner plays next
+    #[serde(default)]
+    pub winner_to: Option<BracketSlot>,
+    /// Where the loser plays next; without one the loser is out
+    #[serde(default)]
+    pub loser_to: Option<BracketSlot>,
+    /// Earlier matches that have yet to send a player here
+    #[serde(default)]
+    pub pending_feeds: u8,
 }
 
 impl BracketMatch {
@@ -97,6 +152,10 @@ impl BracketMatch {
             scheduled_at: None,
             completed_at: None,
             is_bye: false,
+            side: BracketSide::Winners,
+            winner_to: None,
+            loser_to: None,
+            pending_feeds: 0,
         }
     }
 
@@ -107,9 +166,10 @@ impl BracketMatch {
         self.winner.is_none()
     }
 
-    /// Check if match is complete
+    /// Check if match is complete. A bye without any player completes
+    /// without a winner.
     pub fn is_complete(&self) -> bool {
-        self.winner.is_some()
+        self.winner.is_some() || self.completed_at.is_some()
     }
 }
 
@@ -163,6 +223,9 @@ pub struct Tournament {
     pub bracket: Vec<BracketMatch>,
     /// Current round
     pub current_round: u32,
+    /// Seeding used when the tournament starts
+    #[serde(default)]
+    pub seeding: SeedingMode,
     /// Prizes by placement
     pub prizes: HashMap<u32, TournamentPrize>,
     /// Created by
@@ -198,6 +261,7 @@ impl Tournament {
             participants: HashMap::new(),
             bracket: Vec::new(),
             current_round: 0,
+            seeding: SeedingMode::default(),
             prizes: HashMap::new(),
             created_by: Uuid::nil(),
             created_at: now,
@@ -232,6 +296,7 @@ impl Tournament {
             wins: 0,
             losses: 0,
             registration_rating: rating,
+            eliminated_at: None,
         };
 
         self.participants.insert(character_id, participant);
@@ -249,23 +314,27 @@ impl Tournament {
             .ok_or("Not registered")
     }
 
-    /// Generate bracket based on seeding
-    pub fn generate_bracket(&mut self) {
-        // Seed players by rating
-        let mut seeded: Vec<_> = self.participants.values_mut().collect();
-        seeded.sort_by(|a, b| b.registration_rating.cmp(&a.registration_rating));
+    /// Seed the participants and lay out the bracket
+    pub fn generate_bracket(&mut self, seeding: SeedingMode) -> Result<(), &'static str> {
+        if self.participants.len() < 2 {
+            return Err("Not enough participants");
+        }
 
-        for (i, participant) in seeded.iter_mut().enumerate() {
-            participant.seed = (i + 1) as u32;
+        let order = self.seed_order(seeding)?;
+        for (i, character_id) in order.iter().enumerate() {
+            if let Some(participant) = self.participants.get_mut(character_id) {
+                participant.seed = (i + 1) as u32;
+            }
         }
 
         // Generate bracket matches based on format
+        self.bracket.clear();
         match self.format {
             TournamentFormat::SingleElimination => {
-                self.generate_single_elimination_bracket();
+                self.generate_elimination_bracket(&order, false);
             }
             TournamentFormat::DoubleElimination => {
-                self.generate_double_elimination_bracket();
+                self.generate_elimination_bracket(&order, true);
             }
             TournamentFormat::RoundRobin => {
                 self.generate_round_robin_bracket();
@@ -277,63 +346,106 @@ impl Tournament {
         }
 
         self.current_round = 1;
+        Ok(())
     }
 
-    /// Generate single elimination bracket
-    fn generate_single_elimination_bracket(&mut self) {
-        let count = self.participants.len();
-        let rounds = (count as f64).log2().ceil() as u32;
-        
-        // Find next power of 2
-        let bracket_size = 2_u32.pow(rounds);
-        
-        let mut seeded: Vec<_> = self.participants.values()
-            .map(|p| p.character_id)
-            .collect();
-        seeded.sort_by_key(|id| self.participants[id].seed);
-
-        // First round matches
-        let first_round_matches = bracket_size / 2;
-        for i in 0..first_round_matches {
-            let mut match_ = BracketMatch::new(1, i + 1);
-            
-            let p1_idx = i as usize;
-            let p2_idx = (bracket_size - 1 - i) as usize;
-
-            if p1_idx < seeded.len() {
-                match_.participant1 = Some(seeded[p1_idx]);
+    /// Participants from top seed down
+    fn seed_order(&self, seeding: SeedingMode) -> Result<Vec<Uuid>, &'static str> {
+        let mut order: Vec<Uuid> = self.participants.keys().copied().collect();
+        match seeding {
+            SeedingMode::Random => order.shuffle(&mut rand::thread_rng()),
+            SeedingMode::ByRating => {
+                order.sort_by_key(|id| {
+                    let participant = &self.participants[id];
+                    (Reverse(participant.registration_rating), participant.character_name.clone())
+                });
             }
-            if p2_idx < seeded.len() {
-                match_.participant2 = Some(seeded[p2_idx]);
+            SeedingMode::Manual(manual) => {
+                let listed: HashSet<&Uuid> = manual.iter().collect();
+                if manual.len() != order.len()
+                    || listed.len() != manual.len()
+                    || !manual.iter().all(|id| self.participants.contains_key(id))
+                {
+                    return Err("Manual seeding must list every participant once");
+                }
+                order = manual;
             }
+        }
+        Ok(order)
+    }
 
-            // Handle byes
-            if match_.participant1.is_some() && match_.participant2.is_none() {
-                match_.is_bye = true;
-                match_.winner = match_.participant1;
-            }
+    /// Lay out a single or double elimination bracket for `order`
+    fn generate_elimination_bracket(&mut self, order: &[Uuid], double: bool) {
+        let size = (order.len() as u32).next_power_of_two();
+        let rounds = size.trailing_zeros();
+        let new_match = |side: BracketSide, round: u32, number: u32| {
+            let mut bracket_match = BracketMatch::new(round, number);
+            bracket_match.side = side;
+            bracket_match.pending_feeds = if side == BracketSide::Winners && round == 1 { 0 } else { 2 };
+            bracket_match
+        };
 
-            self.bracket.push(match_);
+        let mut winners: Vec<Vec<BracketMatch>> = (1..=rounds)
+            .map(|round| (1..=size >> round).map(|n| new_match(BracketSide::Winners, round, n)).collect())
+            .collect();
+        let seeds = bracket_order(size);
+        for (i, bracket_match) in winners[0].iter_mut().enumerate() {
+            bracket_match.participant1 = order.get(seeds[2 * i] as usize - 1).copied();
+            bracket_match.participant2 = order.get(seeds[2 * i + 1] as usize - 1).copied();
         }
 
-        // Generate subsequent rounds (empty for now)
-        let mut matches_in_round = first_round_matches / 2;
-        for round in 2..=rounds {
-            for i in 0..matches_in_round {
-                let match_ = BracketMatch::new(round, i + 1);
-                self.bracket.push(match_);
+        // Losers bracket rounds alternate between halving the field and
+        // taking in the losers of the next winners round
+        let mut losers: Vec<Vec<BracketMatch>> = Vec::new();
+        let mut grand_final = None;
+        if double {
+            for round in 1..=2 * (rounds - 1) {
+                let count = size >> (round.div_ceil(2) + 1);
+                losers.push((1..=count).map(|n| new_match(BracketSide::Losers, round, n)).collect());
             }
-            matches_in_round /= 2;
+            grand_final = Some(new_match(BracketSide::GrandFinal, 1, 1));
         }
-    }
 
-    /// Generate double elimination bracket
-    fn generate_double_elimination_bracket(&mut self) {
-        // Start with winner's bracket
-        self.generate_single_elimination_bracket();
-        
-        // Loser's bracket would be generated as matches complete
-        // This is a simplified implementation
+        let slot = |bracket_match: &BracketMatch, slot: u8| Some(BracketSlot { match_id: bracket_match.id, slot });
+        for round in 0..winners.len() {
+            for i in 0..winners[round].len() {
+                let winner_to = match winners.get(round + 1) {
+                    Some(next) => slot(&next[i / 2], i as u8 % 2 + 1),
+                    None => grand_final.as_ref().and_then(|m| slot(m, 1)),
+                };
+                let loser_to = if !double {
+                    None
+                } else if losers.is_empty() {
+                    grand_final.as_ref().and_then(|m| slot(m, 2))
+                } else if round == 0 {
+                    slot(&losers[0][i / 2], i as u8 % 2 + 1)
+                } else {
+                    // Reversed, so players do not meet the same opponent again right away
+                    let target = &losers[2 * round - 1];
+                    slot(&target[target.len() - 1 - i], 2)
+                };
+                winners[round][i].winner_to = winner_to;
+                winners[round][i].loser_to = loser_to;
+            }
+        }
+        for round in 0..losers.len() {
+            for i in 0..losers[round].len() {
+                losers[round][i].winner_to = match losers.get(round + 1) {
+                    // Odd rounds feed the next round one to one, even rounds pair up
+                    Some(next) if round % 2 == 0 => slot(&next[i], 1),
+                    Some(next) => slot(&next[i / 2], i as u8 % 2 + 1),
+                    None => grand_final.as_ref().and_then(|m| slot(m, 2)),
+                };
+            }
+        }
+
+        let first_round: Vec<Uuid> = winners[0].iter().map(|m| m.id).collect();
+        self.bracket.extend(winners.into_iter().flatten());
+        self.bracket.extend(losers.into_iter().flatten());
+        self.bracket.extend(grand_final);
+        for match_id in first_round {
+            self.settle(match_id);
+        }
     }
 
     /// Generate round robin bracket
@@ -387,19 +499,56 @@ impl Tournament {
         self.current_round = round;
     }
 
+    /// Report a match by its result. Returns the matches that became
+    /// ready to play because of it.
+    pub fn report_result(&mut self, match_id: Uuid, result: MatchResult) -> Result<Vec<BracketMatch>, &'static str> {
+        let bracket_match = self.bracket.iter()
+            .find(|m| m.id == match_id)
+            .ok_or("Match not found")?;
+        let winner_id = match result {
+            MatchResult::Team1Win => bracket_match.participant1,
+            MatchResult::Team2Win => bracket_match.participant2,
+            _ => return Err("Bracket matches need a winner"),
+        }
+        .ok_or("Match is not ready")?;
+
+        let ready_before: HashSet<Uuid> = self.bracket.iter()
+            .filter(|m| m.is_ready())
+            .map(|m| m.id)
+            .collect();
+        self.report_match_result(match_id, winner_id)?;
+        if let Some(bracket_match) = self.bracket.iter_mut().find(|m| m.id == match_id) {
+            bracket_match.result = Some(result);
+        }
+        if self.is_complete() {
+            self.status = TournamentStatus::Completed;
+        }
+
+        Ok(self.bracket.iter()
+            .filter(|m| m.is_ready() && !ready_before.contains(&m.id))
+            .cloned()
+            .collect())
+    }
+
     /// Report a match result
     pub fn report_match_result(
         &mut self,
         match_id: Uuid,
         winner_id: Uuid,
     ) -> Result<(), &'static str> {
+        let losers_rounds = self.bracket.iter()
+            .filter(|m| m.side == BracketSide::Losers)
+            .map(|m| m.round)
+            .max()
+            .unwrap_or(0);
+
         // Extract match info first to avoid borrow conflicts
-        let (round, match_number, loser_id) = {
+        let (loser_id, stage, winner_to, loser_to, bracket_reset) = {
             let match_result = self.bracket.iter_mut()
                 .find(|m| m.id == match_id)
                 .ok_or("Match not found")?;
 
-            if match_result.winner.is_some() {
+            if match_result.is_complete() {
                 return Err("Match already has a result");
             }
 
@@ -409,6 +558,10 @@ impl Tournament {
                 return Err("Winner is not a participant in this match");
             }
 
+            if match_result.participant1.is_none() || match_result.participant2.is_none() {
+                return Err("Match is not ready");
+            }
+
             match_result.winner = Some(winner_id);
             match_result.completed_at = Some(Utc::now());
 
@@ -419,7 +572,17 @@ impl Tournament {
                 match_result.participant1
             };
 
-            (match_result.round, match_result.match_number, loser_id)
+            let stage = match match_result.side {
+                BracketSide::GrandFinal => losers_rounds + match_result.round,
+                _ => match_result.round,
+            };
+            // The winners bracket champion has not lost yet, so losing the
+            // grand final forces a second one
+            let bracket_reset = match_result.side == BracketSide::GrandFinal
+                && match_result.round == 1
+                && loser_id == match_result.participant1;
+
+            (loser_id, stage, match_result.winner_to, match_result.loser_to, bracket_reset)
         };
 
         // Update participant records
@@ -427,37 +590,86 @@ impl Tournament {
             winner.wins += 1;
         }
 
+        let elimination = matches!(
+            self.format,
+            TournamentFormat::SingleElimination | TournamentFormat::DoubleElimination
+        );
         if let Some(loser_id) = loser_id {
             if let Some(loser) = self.participants.get_mut(&loser_id) {
                 loser.losses += 1;
 
-                // Eliminate in single elimination
-                if self.format == TournamentFormat::SingleElimination {
+                if elimination && loser_to.is_none() && !bracket_reset {
                     loser.eliminated = true;
+                    loser.eliminated_at = Some(stage);
                 }
             }
         }
 
-        // Advance winner to next round
-        self.advance_winner(round, match_number, winner_id);
+        if bracket_reset {
+            let mut reset = BracketMatch::new(2, 1);
+            reset.side = BracketSide::GrandFinal;
+            reset.participant1 = loser_id;
+            reset.participant2 = Some(winner_id);
+            self.bracket.push(reset);
+            return Ok(());
+        }
+
+        // Advance winner and loser to their next matches
+        self.feed(winner_to, Some(winner_id));
+        self.feed(loser_to, loser_id);
 
         Ok(())
     }
 
-    /// Advance winner to next round
-    fn advance_winner(&mut self, current_round: u32, match_number: u32, winner_id: Uuid) {
-        let next_round = current_round + 1;
-        let next_match_number = (match_number + 1) / 2;
+    /// Put a player, or nobody when the feeding match was an empty bye,
+    /// into a later match
+    fn feed(&mut self, target: Option<BracketSlot>, player: Option<Uuid>) {
+        let Some(target) = target else {
+            return;
+        };
+        let Some(next_match) = self.bracket.iter_mut().find(|m| m.id == target.match_id) else {
+            return;
+        };
 
-        if let Some(next_match) = self.bracket.iter_mut()
-            .find(|m| m.round == next_round && m.match_number == next_match_number)
-        {
-            if match_number % 2 == 1 {
-                next_match.participant1 = Some(winner_id);
+        if player.is_some() {
+            if target.slot == 1 {
+                next_match.participant1 = player;
             } else {
-                next_match.participant2 = Some(winner_id);
+                next_match.participant2 = player;
             }
         }
+        next_match.pending_feeds = next_match.pending_feeds.saturating_sub(1);
+        self.settle(target.match_id);
+    }
+
+    /// Resolve a match that has all its feeds in but fewer than two
+    /// players: a lone player advances on a bye, an empty match is skipped
+    fn settle(&mut self, match_id: Uuid) {
+        let Some(bracket_match) = self.bracket.iter_mut().find(|m| m.id == match_id) else {
+            return;
+        };
+        if bracket_match.pending_feeds > 0
+            || bracket_match.is_complete()
+            || (bracket_match.participant1.is_some() && bracket_match.participant2.is_some())
+        {
+            return;
+        }
+
+        bracket_match.is_bye = true;
+        bracket_match.winner = bracket_match.participant1.or(bracket_match.participant2);
+        bracket_match.completed_at = Some(Utc::now());
+        let (winner, winner_to, loser_to) = (bracket_match.winner, bracket_match.winner_to, bracket_match.loser_to);
+        self.feed(winner_to, winner);
+        self.feed(loser_to, None);
+    }
+
+    /// Matches of one round, in bracket order
+    pub fn round_matchups(&self, side: BracketSide, round: u32) -> Vec<&BracketMatch> {
+        let mut matches: Vec<_> = self.bracket.iter()
+            .filter(|m| m.side == side && m.round == round)
+            .collect();
+        matches.sort_by_key(|m| m.match_number);
+        matches
     }
 
     /// Check if tournament is complete
@@ -492,6 +704,32 @@ impl Tournament {
         standings
     }
 
+    /// Placements of a finished elimination bracket: the champion first,
+    /// then everyone else by how far they got. Players knocked out at the
+    /// same stage share a placement.
+    pub fn final_standings(&self) -> Option<Vec<(u32, &TournamentParticipant)>> {
+        let elimination = matches!(
+            self.format,
+            TournamentFormat::SingleElimination | TournamentFormat::DoubleElimination
+        );
+        if !elimination || !self.is_complete() {
+            return None;
+        }
+
+        let mut participants: Vec<_> = self.participants.values().collect();
+        participants.sort_by_key(|p| (Reverse(p.eliminated_at.unwrap_or(u32::MAX)), p.seed));
+
+        let mut standings: Vec<(u32, &TournamentParticipant)> = Vec::with_capacity(participants.len());
+        for (i, participant) in participants.into_iter().enumerate() {
+            let placement = match standings.last() {
+                Some(&(placement, previous)) if previous.eliminated_at == participant.eliminated_at => placement,
+                _ => i as u32 + 1,
+            };
+            standings.push((placement, participant));
+        }
+        Some(standings)
+    }
+
     /// Get the winner
     pub fn get_winner(&self) -> Option<&TournamentParticipant> {
         if !self.is_complete() {
@@ -547,11 +785,8 @@ impl TournamentManager {
             return Err("Tournament cannot be started");
         }
 
-        if tournament.participants.len() < 2 {
-            return Err("Not enough participants");
-        }
-
-        tournament.generate_bracket();
+        let seeding = tournament.seeding.clone();
+        tournament.generate_bracket(seeding)?;
         tournament.status = TournamentStatus::InProgress;
 
         Ok(())
@@ -585,8 +820,8 @@ impl TournamentManager {
             // Auto-start tournament
             if tournament.status == TournamentStatus::Pending &&
                now >= tournament.start_time {
-                if tournament.participants.len() >= 2 {
-                    tournament.generate_bracket();
+                let seeding = tournament.seeding.clone();
+                if tournament.generate_bracket(seeding).is_ok() {
                     tournament.status = TournamentStatus::InProgress;
                 } else {
                     tournament.status = TournamentStatus::Cancelled;
@@ -607,3 +842,97 @@ impl Default for TournamentManager {
         Self::new()
     }
 }
+
+/// Seeds (1-based) in bracket order for a bracket of `size`, so that
+/// the top seeds can only meet in the last rounds
+fn bracket_order(size: u32) -> Vec<u32> {
+    let mut order = vec![1];
+    while (order.len() as u32) < size {
+        let sum = order.len() as u32 * 2 + 1;
+        order = order.iter().flat_map(|&seed| [seed, sum - seed]).collect();
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tournament(format: TournamentFormat, players: usize) -> (Tournament, Vec<Uuid>) {
+        let mut tournament = Tournament::new("Arena Cup", MatchType::Duel, format, 16, Utc::now() + Duration::days(1));
+        // Registered weakest first; seeded strongest first
+        let ids: Vec<Uuid> = (0..players).map(|_| Uuid::new_v4()).collect();
+        for (i, id) in ids.iter().enumerate() {
+            tournament.register(*id, &format!("Player {}", i), 1000 + i as i32 * 100).unwrap();
+        }
+        (tournament, ids.into_iter().rev().collect())
+    }
+
+    /// Play every ready match, the better seed winning
+    fn play_out(tournament: &mut Tournament) {
+        while let Some(next) = tournament.bracket.iter().find(|m| m.is_ready()) {
+            let seed = |id: Option<Uuid>| tournament.participants[&id.unwrap()].seed;
+            let result = if seed(next.participant1) < seed(next.participant2) {
+                MatchResult::Team1Win
+            } else {
+                MatchResult::Team2Win
+            };
+            tournament.report_result(next.id, result).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_six_player_single_elimination() {
+        let (mut tournament, seeds) = tournament(TournamentFormat::SingleElimination, 6);
+        tournament.generate_bracket(SeedingMode::ByRating).unwrap();
+
+        // The top two seeds get the byes
+        let first_round = tournament.round_matchups(BracketSide::Winners, 1);
+        assert_eq!(first_round.len(), 4);
+        let byes: Vec<_> = first_round.iter().filter(|m| m.is_bye).map(|m| m.winner.unwrap()).collect();
+        assert_eq!(byes, vec![seeds[0], seeds[1]]);
+        let played: Vec<_> = first_round.iter().filter(|m| !m.is_bye).map(|m| (m.participant1, m.participant2)).collect();
+        assert_eq!(played, vec![(Some(seeds[3]), Some(seeds[4])), (Some(seeds[2]), Some(seeds[5]))]);
+
+        // Seed 4 upsets seed 3 and meets seed 2 next
+        let upset = first_round[3].id;
+        let ready = tournament.report_result(upset, MatchResult::Team2Win).unwrap();
+        assert_eq!(ready.len(), 1);
+        assert_eq!((ready[0].round, ready[0].participant1, ready[0].participant2), (2, Some(seeds[1]), Some(seeds[5])));
+        assert!(matches!(tournament.report_result(upset, MatchResult::Team1Win), Err("Match already has a result")));
+
+        play_out(&mut tournament);
+        assert_eq!(tournament.status, TournamentStatus::Completed);
+        assert_eq!(tournament.get_winner().unwrap().character_id, seeds[0]);
+
+        let standings: Vec<(u32, Uuid)> = tournament.final_standings().unwrap().iter().map(|(place, p)| (*place, p.character_id)).collect();
+        assert_eq!(standings.iter().map(|s| s.0).collect::<Vec<_>>(), vec![1, 2, 3, 3, 5, 5]);
+        assert_eq!(standings[1].1, seeds[1]);
+        assert!(standings[4..].iter().any(|s| s.1 == seeds[2]));
+    }
+
+    #[test]
+    fn test_double_elimination_and_manual_seeding() {
+        let (mut tournament, seeds) = tournament(TournamentFormat::DoubleElimination, 5);
+        let bad = SeedingMode::Manual(seeds[..4].to_vec());
+        assert!(tournament.generate_bracket(bad).is_err());
+
+        // Seed the weakest player first
+        let manual: Vec<Uuid> = seeds.iter().rev().copied().collect();
+        tournament.generate_bracket(SeedingMode::Manual(manual.clone())).unwrap();
+        assert_eq!(tournament.participants[&manual[0]].seed, 1);
+
+        // The grand final goes to a second match when the losers bracket champion wins it
+        play_out(&mut tournament);
+        let finals = tournament.bracket.iter().filter(|m| m.side == BracketSide::GrandFinal).count();
+        let champion = tournament.get_winner().unwrap();
+        assert!(finals == 1 || champion.losses == 1);
+        assert_eq!(champion.character_id, manual[0]);
+
+        // Everyone but the champion lost twice
+        assert!(tournament.participants.values().filter(|p| p.character_id != manual[0]).all(|p| p.eliminated && p.losses == 2));
+        let standings = tournament.final_standings().unwrap();
+        assert_eq!((standings[0].0, standings[1].0), (1, 2));
+        assert_eq!(standings[1].1.character_id, manual[1]);
+    }
+}
@@ -633,6 +633,35 @@ pub fn build_text_message(message_type: TextMessageType, text: &str) -> NetworkM
     msg
 }
 
+/// Build magic effect packet
+pub fn build_magic_effect(position: Position, effect: u8) -> NetworkMessage {
+    let mut msg = NetworkMessage::new();
+    msg.put_u8(ServerPacketType::GraphicEffect as u8);
+    position.write(&mut msg);
+    msg.put_u8(effect);
+    msg
+}
+
+/// Build distance (projectile) effect packet
+pub fn build_distance_effect(from: Position, to: Position, effect: u8) -> NetworkMessage {
+    let mut msg = NetworkMessage::new();
+    msg.put_u8(ServerPacketType::MissileEffect as u8);
+    from.write(&mut msg);
+    to.write(&mut msg);
+    msg.put_u8(effect);
+    msg
+}
+
+/// Build animated text packet
+pub fn build_animated_text(position: Position, color: u8, text: &str) -> NetworkMessage {
+    let mut msg = NetworkMessage::new();
+    msg.put_u8(ServerPacketType::TextEffect as u8);
+    position.write(&mut msg);
+    msg.put_u8(color);
+    msg.put_string(text);
+    msg
+}
+
 #[derive(Debug, Clone, Copy)]
 #[repr(u8)]
 pub enum TextMessageType {
@@ -295,6 +295,17 @@ async fn authenticate_user(
         }
     }
 
+    // Check if account is banned
+    if let Some(ban_until) = &account.ban_until {
+        if *ban_until > chrono::Utc::now() {
+            return Err(format!(
+                "Your account has been banned until {}. Reason: {}",
+                ban_until.format("%Y-%m-%d"),
+                account.ban_reason.as_deref().unwrap_or("not given")
+            ));
+        }
+    }
+
     // Check if 2FA is required but token not provided
     if account.two_factor_enabled {
         // In a real implementation, we'd validate the 2FA token here
@@ -107,6 +107,16 @@ impl ProtocolVersion {
         self.version_number() >= 780
     }
 
+    /// Check if this version supports extended (32-bit id) sprite files
+    pub fn supports_extended_sprites(&self) -> bool {
+        self.version_number() >= 960
+    }
+
+    /// Check if this version supports imbuements
+    pub fn supports_imbuements(&self) -> bool {
+        self.version_number() >= 1100
+    }
+
     /// Check if this version supports the prey system
     pub fn supports_prey(&self) -> bool {
         self.version_number() >= 1100
@@ -3,6 +3,12 @@
 //! Configurable options for each realm.
 
 use serde::{Deserialize, Serialize};
+use shadow_combat::{AreaTargetPolicy, CombatLockConfig, ContributionConfig, LevelTable, NewCharacterProtectionConfig, RulesetFlags};
+use shadow_world::clock::GameClock;
+use shadow_world::corpse::CorpsePolicy;
+use shadow_world::house::HouseAcquisitionMode;
+use shadow_world::push::PushRules;
+use shadow_world::scaling::DifficultyScalingConfig;
 
 use crate::RealmType;
 
@@ -29,6 +35,9 @@ pub struct RealmConfig {
     pub world: WorldConfig,
     /// Custom features
     pub features: FeaturesConfig,
+    /// Classic rule toggles applied by combat, death and regeneration
+    #[serde(default)]
+    pub rules: RulesetFlags,
 }
 
 impl Default for RealmConfig {
@@ -44,6 +53,7 @@ impl Default for RealmConfig {
             death: DeathConfig::default(),
             world: WorldConfig::default(),
             features: FeaturesConfig::default(),
+            rules: RulesetFlags::modern(),
         }
     }
 }
@@ -100,6 +110,9 @@ pub struct EconomyConfig {
     pub allow_trading: bool,
     /// Minimum level to use market
     pub market_min_level: u32,
+    /// Whether free houses are auctioned or bought directly
+    #[serde(default)]
+    pub house_acquisition: HouseAcquisitionMode,
 }
 
 impl Default for EconomyConfig {
@@ -113,6 +126,7 @@ impl Default for EconomyConfig {
             max_gold_stack: 100_000_000_000, // 100 billion
             allow_trading: true,
             market_min_level: 20,
+            house_acquisition: HouseAcquisitionMode::Auction,
         }
     }
 }
@@ -138,6 +152,12 @@ pub struct ExperienceConfig {
     pub stamina_enabled: bool,
     /// Happy hour multiplier
     pub happy_hour_multiplier: f64,
+    /// Experience and loot split between non-party attackers
+    #[serde(default)]
+    pub pve_contribution: ContributionConfig,
+    /// Experience each level requires
+    #[serde(default)]
+    pub level_table: LevelTable,
 }
 
 impl Default for ExperienceConfig {
@@ -152,6 +172,8 @@ impl Default for ExperienceConfig {
             vip_bonus: 0.5,
             stamina_enabled: true,
             happy_hour_multiplier: 1.5,
+            pve_contribution: ContributionConfig::default(),
+            level_table: LevelTable::Retail,
         }
     }
 }
@@ -179,6 +201,18 @@ pub struct PvPConfig {
     pub black_skull_frags: u32,
     /// PvP damage reduction in safe areas
     pub safe_zone_reduction: f64,
+    /// Area spells also hit members of the caster's party
+    #[serde(default)]
+    pub party_friendly_fire: bool,
+    /// Who may push whom, push delay and protection zone rules
+    #[serde(default)]
+    pub push: PushRules,
+    /// How long PvP blocks logout and protection zone entry
+    #[serde(default)]
+    pub combat_lock: CombatLockConfig,
+    /// PvP and aggro protection of new characters
+    #[serde(default)]
+    pub new_character_protection: NewCharacterProtectionConfig,
 }
 
 impl Default for PvPConfig {
@@ -194,6 +228,10 @@ impl Default for PvPConfig {
             red_skull_frags: 3,
             black_skull_frags: 10,
             safe_zone_reduction: 0.5,
+            party_friendly_fire: false,
+            push: PushRules::default(),
+            combat_lock: CombatLockConfig::default(),
+            new_character_protection: NewCharacterProtectionConfig::default(),
         }
     }
 }
@@ -213,6 +251,9 @@ pub struct DeathConfig {
     pub blessings_enabled: bool,
     /// Amulet of loss enabled
     pub aol_enabled: bool,
+    /// Buy missing blessings from the bank on login, if affordable
+    #[serde(default)]
+    pub auto_bless: bool,
     /// Minimum level for full penalties
     pub min_penalty_level: u32,
     /// Death penalty reduction per blessing
@@ -230,6 +271,7 @@ impl Default for DeathConfig {
             container_drop: true,
             blessings_enabled: true,
             aol_enabled: true,
+            auto_bless: false,
             min_penalty_level: 20,
             blessing_reduction: 1.6,
             pvp_reduced_penalty: true,
@@ -256,6 +298,19 @@ pub struct WorldConfig {
     pub save_interval: u32,
     /// Maximum NPCs per area
     pub max_npcs_per_area: u32,
+    /// Game seconds per real second; only honoured on experimental realms
+    #[serde(default = "default_time_scale")]
+    pub time_scale: f64,
+    /// Corpse loot protection and decay
+    #[serde(default)]
+    pub corpse: CorpsePolicy,
+    /// Monsters scaling with the players fighting them
+    #[serde(default)]
+    pub difficulty_scaling: DifficultyScalingConfig,
+}
+
+fn default_time_scale() -> f64 {
+    1.0
 }
 
 impl Default for WorldConfig {
@@ -269,6 +324,9 @@ impl Default for WorldConfig {
             event_spawn_rate: 1.0,
             save_interval: 5,
             max_npcs_per_area: 100,
+            time_scale: default_time_scale(),
+            corpse: CorpsePolicy::default(),
+            difficulty_scaling: DifficultyScalingConfig::default(),
         }
     }
 }
@@ -306,6 +364,9 @@ pub struct FeaturesConfig {
     pub custom_spells: bool,
     /// Lua scripting enabled
     pub lua_scripts: bool,
+    /// Guilds on this realm may ally with guilds on other realms
+    #[serde(default)]
+    pub cross_realm_alliances: bool,
 }
 
 impl Default for FeaturesConfig {
@@ -326,6 +387,30 @@ impl Default for FeaturesConfig {
             forge: true,
             custom_spells: true,
             lua_scripts: true,
+            cross_realm_alliances: false,
+        }
+    }
+}
+
+impl RealmConfig {
+    /// Who area spells and runes may hit on this realm. Players are only
+    /// hit when PvP is enabled; party members only with friendly fire on.
+    pub fn area_policy(&self) -> AreaTargetPolicy {
+        let mut policy = if self.pvp.enabled && self.realm_type != RealmType::PvE {
+            AreaTargetPolicy::pvp(self.pvp.party_friendly_fire)
+        } else {
+            AreaTargetPolicy::pve()
+        };
+        policy.exclude_protection_zones = self.pvp.protection_zones;
+        policy
+    }
+
+    /// Game clock for scheduler, decay and resets. Time only runs faster
+    /// on experimental realms so live realms can't be misconfigured.
+    pub fn game_clock(&self) -> GameClock {
+        match self.realm_type {
+            RealmType::Experimental => GameClock::new(self.world.time_scale),
+            _ => GameClock::real_time(),
         }
     }
 }
@@ -395,6 +480,20 @@ impl RealmConfig {
                 daily_rewards: false,
                 ..Default::default()
             },
+            rules: RulesetFlags::retro(),
+            ..Default::default()
+        }
+    }
+
+    /// Test realm running a game day in `minutes_per_day` real minutes
+    pub fn experimental(minutes_per_day: u32) -> Self {
+        Self {
+            name: "Test Realm".to_string(),
+            realm_type: RealmType::Experimental,
+            world: WorldConfig {
+                time_scale: 1440.0 / minutes_per_day.max(1) as f64,
+                ..Default::default()
+            },
             ..Default::default()
         }
     }
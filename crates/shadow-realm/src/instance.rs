@@ -95,6 +95,27 @@ impl RealmInstance {
         self.info.status = RealmStatus::Maintenance;
     }
 
+    /// Lock the realm for maintenance. Online characters are saved and
+    /// disconnected; returns the saved characters.
+    pub fn begin_maintenance(&mut self, now: DateTime<Utc>) -> Vec<Uuid> {
+        self.accepting_connections = false;
+        let saved: Vec<Uuid> = self.online_players.keys().copied().collect();
+        self.last_save = now;
+
+        self.online_players.clear();
+        self.sessions.clear();
+        self.info.online_count = 0;
+        self.info.status = RealmStatus::Maintenance;
+        saved
+    }
+
+    /// Reopen the realm after maintenance
+    pub fn end_maintenance(&mut self, now: DateTime<Utc>) {
+        self.info.status = RealmStatus::Online;
+        self.info.last_online = Some(now);
+        self.accepting_connections = true;
+    }
+
     /// Add a player to the realm
     pub fn add_player(
         &mut self,
@@ -6,7 +6,9 @@
 
 pub mod config;
 pub mod instance;
+pub mod maintenance;
 pub mod manager;
+pub mod merge;
 pub mod transfer;
 
 use chrono::{DateTime, Utc};
@@ -17,8 +19,10 @@ use uuid::Uuid;
 
 pub use config::RealmConfig;
 pub use instance::RealmInstance;
+pub use maintenance::{MaintenanceConfig, MaintenanceEvent, MaintenanceScheduler, MaintenanceWindow};
 pub use manager::RealmManager;
-pub use transfer::CrossRealmTransfer;
+pub use merge::{MergeReport, RealmMerge, RealmSnapshot};
+pub use transfer::{CrossRealmTransfer, ExclusiveItemPolicy, TransferCandidate, TransferItem, TransferPreview, TransferRules};
 
 /// Realm errors
 #[derive(Debug, Error)]
@@ -38,9 +42,28 @@ pub enum RealmError {
     #[error("Transfer not allowed")]
     TransferNotAllowed,
     
+    #[error("Character was transferred recently, next transfer in {0} minutes")]
+    TransferCooldown(i64),
+    
+    #[error("Characters cannot transfer while their guild is at war")]
+    TransferDuringGuildWar,
+    
+    #[error("Characters cannot transfer while selling in an auction")]
+    TransferWithActiveAuction,
+    
+    #[error("Character holds {0} realm-exclusive items")]
+    TransferExclusiveItems(usize),
+    
+    
     #[error("Cross-realm feature disabled")]
     CrossRealmDisabled,
     
+    #[error("Realm must be in maintenance mode")]
+    MaintenanceRequired,
+
+    #[error("Maintenance window must end after it starts")]
+    InvalidMaintenanceWindow,
+    
     #[error("Configuration error: {0}")]
     ConfigError(String),
     
@@ -0,0 +1,245 @@
+//! Maintenance Scheduler
+//!
+//! Operators schedule maintenance windows per realm. Ahead of a window the
+//! scheduler broadcasts shutdown warnings at the configured lead times;
+//! when the window opens the realm saves and disconnects its players and
+//! goes into maintenance, and when it closes the realm comes back online.
+//!
+//! Each warning fires once. If several lead times pass between two ticks
+//! (a window scheduled at short notice, or a stalled tick) only the most
+//! urgent of them is broadcast.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{GlobalMessage, RealmError, RealmManager};
+
+/// Maintenance warning settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MaintenanceConfig {
+    /// Minutes before a window at which players are warned
+    pub warning_minutes: Vec<u32>,
+}
+
+impl Default for MaintenanceConfig {
+    fn default() -> Self {
+        Self {
+            warning_minutes: vec![30, 15, 5, 1],
+        }
+    }
+}
+
+/// A scheduled maintenance window
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaintenanceWindow {
+    pub realm_id: Uuid,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+}
+
+/// Something the scheduler did on a tick
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MaintenanceEvent {
+    /// Warning broadcast to the realm's players
+    Warning { realm_id: Uuid, message: GlobalMessage },
+    /// Realm entered maintenance after saving these characters
+    Started { realm_id: Uuid, saved_characters: Vec<Uuid> },
+    /// Realm is back online
+    Ended { realm_id: Uuid },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WindowState {
+    Pending,
+    Active,
+}
+
+#[derive(Debug, Clone)]
+struct ScheduledWindow {
+    window: MaintenanceWindow,
+    state: WindowState,
+    /// Index of the next warning in the descending lead times
+    next_warning: usize,
+}
+
+/// Runs maintenance windows against the realm manager
+#[derive(Debug, Clone, Default)]
+pub struct MaintenanceScheduler {
+    /// Lead times, longest first
+    warning_minutes: Vec<u32>,
+    windows: Vec<ScheduledWindow>,
+}
+
+impl MaintenanceScheduler {
+    /// Create a new scheduler
+    pub fn new(config: MaintenanceConfig) -> Self {
+        let mut warning_minutes = config.warning_minutes;
+        warning_minutes.sort_unstable_by(|a, b| b.cmp(a));
+        warning_minutes.dedup();
+        Self {
+            warning_minutes,
+            windows: Vec::new(),
+        }
+    }
+
+    /// Schedule a maintenance window
+    pub fn schedule(&mut self, window: MaintenanceWindow) -> Result<(), RealmError> {
+        if window.ends_at <= window.starts_at {
+            return Err(RealmError::InvalidMaintenanceWindow);
+        }
+        self.windows.push(ScheduledWindow {
+            window,
+            state: WindowState::Pending,
+            next_warning: 0,
+        });
+        Ok(())
+    }
+
+    /// Cancel a realm's windows that have not started yet
+    pub fn cancel(&mut self, realm_id: Uuid) {
+        self.windows
+            .retain(|w| w.window.realm_id != realm_id || w.state == WindowState::Active);
+    }
+
+    /// Scheduled and running windows
+    pub fn windows(&self) -> impl Iterator<Item = &MaintenanceWindow> {
+        self.windows.iter().map(|w| &w.window)
+    }
+
+    /// Fire due warnings and open or close windows. Windows of realms the
+    /// manager no longer knows are dropped.
+    pub fn tick(&mut self, manager: &mut RealmManager, now: DateTime<Utc>) -> Vec<MaintenanceEvent> {
+        let mut events = Vec::new();
+
+        self.windows.retain_mut(|scheduled| {
+            let realm_id = scheduled.window.realm_id;
+            let Some(realm) = manager.get_realm_mut(realm_id) else {
+                return false;
+            };
+
+            if scheduled.state == WindowState::Pending {
+                if now < scheduled.window.starts_at {
+                    let mut due = None;
+                    while let Some(&minutes) = self.warning_minutes.get(scheduled.next_warning) {
+                        if now < scheduled.window.starts_at - Duration::minutes(minutes as i64) {
+                            break;
+                        }
+                        due = Some(minutes);
+                        scheduled.next_warning += 1;
+                    }
+                    if let Some(minutes) = due {
+                        realm.broadcast(&format!("Server is going down for maintenance in {} minute(s).", minutes));
+                        events.push(MaintenanceEvent::Warning {
+                            realm_id,
+                            message: GlobalMessage::ShutdownWarning { minutes },
+                        });
+                    }
+                    return true;
+                }
+
+                let saved_characters = realm.begin_maintenance(now);
+                scheduled.state = WindowState::Active;
+                events.push(MaintenanceEvent::Started { realm_id, saved_characters });
+            }
+
+            if now >= scheduled.window.ends_at {
+                realm.end_maintenance(now);
+                events.push(MaintenanceEvent::Ended { realm_id });
+                return false;
+            }
+            true
+        });
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RealmConfig, RealmStatus};
+
+    fn setup() -> (RealmManager, Uuid, DateTime<Utc>) {
+        let mut manager = RealmManager::new();
+        let realm_id = manager.create_realm("Shadowfall", RealmConfig::default()).unwrap();
+        manager.start_realm(realm_id).unwrap();
+        (manager, realm_id, Utc::now())
+    }
+
+    fn warnings(events: &[MaintenanceEvent]) -> Vec<u32> {
+        events
+            .iter()
+            .filter_map(|e| match e {
+                MaintenanceEvent::Warning { message: GlobalMessage::ShutdownWarning { minutes }, .. } => Some(*minutes),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_warnings_fire_once_at_each_lead_time() {
+        let (mut manager, realm_id, now) = setup();
+        let mut scheduler = MaintenanceScheduler::new(MaintenanceConfig::default());
+        let starts_at = now + Duration::minutes(60);
+        scheduler
+            .schedule(MaintenanceWindow { realm_id, starts_at, ends_at: starts_at + Duration::minutes(30) })
+            .unwrap();
+
+        let mut fired = Vec::new();
+        for minute in 0..60 {
+            fired.extend(warnings(&scheduler.tick(&mut manager, now + Duration::minutes(minute))));
+        }
+        assert_eq!(fired, vec![30, 15, 5, 1]);
+
+        // Scheduled at short notice, the passed lead times collapse into one warning
+        let mut scheduler = MaintenanceScheduler::new(MaintenanceConfig::default());
+        let starts_at = now + Duration::minutes(10);
+        scheduler
+            .schedule(MaintenanceWindow { realm_id, starts_at, ends_at: starts_at + Duration::minutes(30) })
+            .unwrap();
+        assert_eq!(warnings(&scheduler.tick(&mut manager, now)), vec![15]);
+        assert!(scheduler.tick(&mut manager, now + Duration::minutes(1)).is_empty());
+
+        let backwards = MaintenanceWindow { realm_id, starts_at, ends_at: starts_at };
+        assert!(matches!(scheduler.schedule(backwards), Err(RealmError::InvalidMaintenanceWindow)));
+    }
+
+    #[test]
+    fn test_realm_locked_during_window_and_reopened_after() {
+        let (mut manager, realm_id, now) = setup();
+        let character_id = Uuid::new_v4();
+        let realm = manager.get_realm_mut(realm_id).unwrap();
+        realm.add_player(character_id, Uuid::new_v4(), "Eryn", 100, "Knight", "127.0.0.1").unwrap();
+
+        let mut scheduler = MaintenanceScheduler::new(MaintenanceConfig::default());
+        let starts_at = now + Duration::minutes(5);
+        let ends_at = starts_at + Duration::minutes(30);
+        scheduler.schedule(MaintenanceWindow { realm_id, starts_at, ends_at }).unwrap();
+
+        scheduler.tick(&mut manager, now);
+        assert_eq!(manager.get_realm(realm_id).unwrap().info.status, RealmStatus::Online);
+
+        let events = scheduler.tick(&mut manager, starts_at);
+        assert!(matches!(
+            &events[..],
+            [MaintenanceEvent::Started { saved_characters, .. }] if saved_characters == &vec![character_id]
+        ));
+        let realm = manager.get_realm_mut(realm_id).unwrap();
+        assert_eq!(realm.info.status, RealmStatus::Maintenance);
+        assert_eq!(realm.last_save, starts_at);
+        assert!(matches!(
+            realm.add_player(Uuid::new_v4(), Uuid::new_v4(), "Late", 8, "None", "127.0.0.1"),
+            Err(RealmError::Offline)
+        ));
+
+        assert!(scheduler.tick(&mut manager, ends_at - Duration::minutes(1)).is_empty());
+        let events = scheduler.tick(&mut manager, ends_at);
+        assert!(matches!(&events[..], [MaintenanceEvent::Ended { .. }]));
+        let realm = manager.get_realm(realm_id).unwrap();
+        assert_eq!(realm.info.status, RealmStatus::Online);
+        assert!(realm.accepting_connections);
+        assert_eq!(scheduler.windows().count(), 0);
+    }
+}
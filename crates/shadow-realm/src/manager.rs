@@ -33,6 +33,12 @@ impl RealmManager {
         name: &str,
         config: RealmConfig,
     ) -> Result<Uuid, RealmError> {
+        config
+            .experience
+            .level_table
+            .validate()
+            .map_err(|e| RealmError::ConfigError(e.to_string()))?;
+
         let instance = RealmInstance::new(name, config);
         let id = instance.info.id;
         
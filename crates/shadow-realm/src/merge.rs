@@ -0,0 +1,332 @@
+//! Realm Merge System
+//!
+//! Consolidates a low-population realm into a host realm. Characters,
+//! guilds and houses move over with their ids intact; name collisions are
+//! resolved by renaming the incoming side and house conflicts by evicting
+//! the incoming owner with a refund. Both realms must be in maintenance.
+//!
+//! The merge moves one entity at a time and removes it from the source only
+//! after it exists in the target, so an interrupted merge can simply be run
+//! again and picks up where it stopped.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use crate::{RealmError, RealmStatus};
+
+/// Character as seen by the merge
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MergeCharacter {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub name: String,
+    pub level: u32,
+    pub guild_id: Option<Uuid>,
+    /// Set when the character was renamed and gets a free name change
+    pub free_rename: bool,
+}
+
+/// Guild as seen by the merge
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MergeGuild {
+    pub id: Uuid,
+    pub name: String,
+    pub leader_id: Uuid,
+}
+
+/// House as seen by the merge; ids refer to the same house on both maps
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MergeHouse {
+    pub id: u32,
+    pub name: String,
+    pub owner_id: Option<Uuid>,
+    /// Prepaid rent returned to the owner if they lose the house
+    pub rent_balance: u64,
+}
+
+/// Everything a merge moves for one realm
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RealmSnapshot {
+    pub realm_id: Uuid,
+    pub name: String,
+    pub status: RealmStatus,
+    pub characters: Vec<MergeCharacter>,
+    pub guilds: Vec<MergeGuild>,
+    pub houses: Vec<MergeHouse>,
+    /// Evictions already refunded by earlier merge runs (house id, owner)
+    #[serde(default)]
+    pub settled_evictions: HashSet<(u32, Uuid)>,
+}
+
+impl RealmSnapshot {
+    pub fn new(realm_id: Uuid, name: &str, status: RealmStatus) -> Self {
+        Self {
+            realm_id,
+            name: name.to_string(),
+            status,
+            characters: Vec::new(),
+            guilds: Vec::new(),
+            houses: Vec::new(),
+            settled_evictions: HashSet::new(),
+        }
+    }
+}
+
+/// A renamed character or guild
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rename {
+    pub id: Uuid,
+    pub old_name: String,
+    pub new_name: String,
+}
+
+/// A source house owner who lost their house to a target owner
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HouseEviction {
+    pub house_id: u32,
+    pub owner_id: Uuid,
+    pub refund: u64,
+}
+
+/// What a merge run did
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MergeReport {
+    pub characters_moved: usize,
+    pub guilds_moved: usize,
+    pub houses_moved: usize,
+    /// Entities already present in the target from an earlier run
+    pub already_merged: usize,
+    pub renamed_characters: Vec<Rename>,
+    pub renamed_guilds: Vec<Rename>,
+    pub evictions: Vec<HouseEviction>,
+}
+
+/// Merges one realm into another
+pub struct RealmMerge;
+
+impl RealmMerge {
+    /// Move everything from `source` into `target`
+    pub fn run(source: &mut RealmSnapshot, target: &mut RealmSnapshot) -> Result<MergeReport, RealmError> {
+        if source.realm_id == target.realm_id {
+            return Err(RealmError::ConfigError("Cannot merge a realm into itself".to_string()));
+        }
+        if source.status != RealmStatus::Maintenance || target.status != RealmStatus::Maintenance {
+            return Err(RealmError::MaintenanceRequired);
+        }
+
+        let mut report = MergeReport::default();
+        Self::merge_guilds(source, target, &mut report);
+        Self::merge_characters(source, target, &mut report);
+        Self::merge_houses(source, target, &mut report);
+
+        tracing::info!(
+            "Merged realm {} into {}: {} characters, {} guilds, {} houses, {} renames, {} evictions",
+            source.name,
+            target.name,
+            report.characters_moved,
+            report.guilds_moved,
+            report.houses_moved,
+            report.renamed_characters.len() + report.renamed_guilds.len(),
+            report.evictions.len()
+        );
+        Ok(report)
+    }
+
+    fn merge_guilds(source: &mut RealmSnapshot, target: &mut RealmSnapshot, report: &mut MergeReport) {
+        let mut names: HashSet<String> = target.guilds.iter().map(|g| g.name.to_lowercase()).collect();
+        let merged: HashSet<Uuid> = target.guilds.iter().map(|g| g.id).collect();
+
+        for mut guild in std::mem::take(&mut source.guilds) {
+            if merged.contains(&guild.id) {
+                report.already_merged += 1;
+                continue;
+            }
+            if names.contains(&guild.name.to_lowercase()) {
+                let new_name = unique_name(&guild.name, &names);
+                report.renamed_guilds.push(Rename { id: guild.id, old_name: guild.name.clone(), new_name: new_name.clone() });
+                guild.name = new_name;
+            }
+            names.insert(guild.name.to_lowercase());
+            target.guilds.push(guild);
+            report.guilds_moved += 1;
+        }
+    }
+
+    fn merge_characters(source: &mut RealmSnapshot, target: &mut RealmSnapshot, report: &mut MergeReport) {
+        let mut names: HashSet<String> = target.characters.iter().map(|c| c.name.to_lowercase()).collect();
+        let merged: HashSet<Uuid> = target.characters.iter().map(|c| c.id).collect();
+
+        for mut character in std::mem::take(&mut source.characters) {
+            if merged.contains(&character.id) {
+                report.already_merged += 1;
+                continue;
+            }
+            if names.contains(&character.name.to_lowercase()) {
+                let new_name = unique_name(&character.name, &names);
+                report.renamed_characters.push(Rename {
+                    id: character.id,
+                    old_name: character.name.clone(),
+                    new_name: new_name.clone(),
+                });
+                character.name = new_name;
+                character.free_rename = true;
+            }
+            names.insert(character.name.to_lowercase());
+            target.characters.push(character);
+            report.characters_moved += 1;
+        }
+    }
+
+    fn merge_houses(source: &mut RealmSnapshot, target: &mut RealmSnapshot, report: &mut MergeReport) {
+        let target_houses: HashMap<u32, usize> = target.houses.iter().enumerate().map(|(i, h)| (h.id, i)).collect();
+
+        for house in std::mem::take(&mut source.houses) {
+            let Some(source_owner) = house.owner_id else {
+                continue;
+            };
+            match target_houses.get(&house.id).map(|&i| &mut target.houses[i]) {
+                Some(existing) if existing.owner_id == Some(source_owner) => report.already_merged += 1,
+                // The host realm's owner keeps the house
+                Some(existing) if existing.owner_id.is_some() => {
+                    if !target.settled_evictions.insert((house.id, source_owner)) {
+                        report.already_merged += 1;
+                        continue;
+                    }
+                    report.evictions.push(HouseEviction {
+                        house_id: house.id,
+                        owner_id: source_owner,
+                        refund: house.rent_balance,
+                    });
+                }
+                Some(existing) => {
+                    existing.owner_id = Some(source_owner);
+                    existing.rent_balance = house.rent_balance;
+                    report.houses_moved += 1;
+                }
+                None => {
+                    target.houses.push(house);
+                    report.houses_moved += 1;
+                }
+            }
+        }
+    }
+}
+
+/// First free variant of `name`, adding roman numerals ("Name II", "Name III", ...)
+/// since names may only contain letters and spaces
+fn unique_name(name: &str, taken: &HashSet<String>) -> String {
+    const NUMERALS: [&str; 9] = ["II", "III", "IV", "V", "VI", "VII", "VIII", "IX", "X"];
+    for numeral in NUMERALS {
+        let candidate = format!("{} {}", name, numeral);
+        if !taken.contains(&candidate.to_lowercase()) {
+            return candidate;
+        }
+    }
+    // Very unlikely; a free rename fixes it
+    let mut candidate = format!("{} Merged", name);
+    while taken.contains(&candidate.to_lowercase()) {
+        candidate.push_str(" X");
+    }
+    candidate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn character(name: &str, guild_id: Option<Uuid>) -> MergeCharacter {
+        MergeCharacter {
+            id: Uuid::new_v4(),
+            account_id: Uuid::new_v4(),
+            name: name.to_string(),
+            level: 100,
+            guild_id,
+            free_rename: false,
+        }
+    }
+
+    fn guild(name: &str) -> MergeGuild {
+        MergeGuild { id: Uuid::new_v4(), name: name.to_string(), leader_id: Uuid::new_v4() }
+    }
+
+    fn house(id: u32, owner_id: Option<Uuid>) -> MergeHouse {
+        MergeHouse { id, name: format!("House {}", id), owner_id, rent_balance: 5000 }
+    }
+
+    fn fixtures() -> (RealmSnapshot, RealmSnapshot) {
+        let mut source = RealmSnapshot::new(Uuid::new_v4(), "Shadowfall", RealmStatus::Maintenance);
+        let mut target = RealmSnapshot::new(Uuid::new_v4(), "Dawnhold", RealmStatus::Maintenance);
+
+        let knights = guild("Red Knights");
+        target.guilds.push(guild("Red Knights"));
+        target.characters.push(character("Eryn", None));
+        target.characters.push(character("Eryn II", None));
+        let owner = character("Tavi", None);
+        target.houses.push(house(1, Some(owner.id)));
+        target.houses.push(house(2, None));
+        target.characters.push(owner);
+
+        let evicted = character("Mora", Some(knights.id));
+        let mover = character("Eryn", Some(knights.id));
+        source.houses.push(house(1, Some(evicted.id)));
+        source.houses.push(house(2, Some(mover.id)));
+        source.houses.push(house(3, Some(mover.id)));
+        source.characters.push(evicted);
+        source.characters.push(mover);
+        source.characters.push(character("Tavi", None));
+        source.guilds.push(knights);
+        source.guilds.push(guild("Night Watch"));
+        (source, target)
+    }
+
+    #[test]
+    fn test_merge_resolves_collisions() {
+        let (mut source, mut target) = fixtures();
+        let report = RealmMerge::run(&mut source, &mut target).unwrap();
+
+        assert_eq!(report.characters_moved, 3);
+        assert_eq!(report.guilds_moved, 2);
+        assert_eq!(report.houses_moved, 2);
+        assert_eq!(target.characters.len(), 6);
+        assert_eq!(target.guilds.len(), 3);
+        assert!(source.characters.is_empty() && source.guilds.is_empty() && source.houses.is_empty());
+
+        // "Eryn" and "Eryn II" are taken on the host
+        let renamed: Vec<_> = report.renamed_characters.iter().map(|r| r.new_name.as_str()).collect();
+        assert_eq!(renamed, vec!["Eryn III", "Tavi II"]);
+        assert!(target.characters.iter().filter(|c| c.free_rename).count() == 2);
+        assert_eq!(report.renamed_guilds[0].new_name, "Red Knights II");
+
+        // House 1 stays with the host owner, the incoming owner is refunded
+        assert_eq!(report.evictions.len(), 1);
+        assert_eq!(report.evictions[0].house_id, 1);
+        assert_eq!(report.evictions[0].refund, 5000);
+        assert_eq!(target.houses.len(), 3);
+    }
+
+    #[test]
+    fn test_merge_is_restartable() {
+        let (mut source, mut target) = fixtures();
+        let pending = source.clone();
+        RealmMerge::run(&mut source, &mut target).unwrap();
+
+        // Re-running with the original source (as after a crash) changes nothing
+        let mut source = pending;
+        let characters = target.characters.clone();
+        let report = RealmMerge::run(&mut source, &mut target).unwrap();
+        assert_eq!(report.characters_moved + report.guilds_moved + report.houses_moved, 0);
+        assert!(report.renamed_characters.is_empty());
+        assert!(report.evictions.is_empty());
+        assert_eq!(report.already_merged, 3 + 2 + 3);
+        assert_eq!(target.characters, characters);
+    }
+
+    #[test]
+    fn test_merge_requires_maintenance() {
+        let (mut source, mut target) = fixtures();
+        target.status = RealmStatus::Online;
+        assert!(matches!(RealmMerge::run(&mut source, &mut target), Err(RealmError::MaintenanceRequired)));
+        assert_eq!(source.characters.len(), 3);
+    }
+}
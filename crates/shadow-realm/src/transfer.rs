@@ -1,10 +1,13 @@
 //! Cross-Realm Transfer System
 //!
-//! Handles transferring characters between realms.
+//! Handles transferring characters between realms. A transfer is refused
+//! while the character is on its transfer cooldown, at war or selling in an
+//! auction; items exclusive to the source realm are either stripped on
+//! transfer or block it, as configured.
 
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 use crate::RealmError;
@@ -28,6 +31,68 @@ pub enum TransferStatus {
     Failed,
 }
 
+/// What happens to realm-exclusive items on transfer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExclusiveItemPolicy {
+    /// Remove the items and let the transfer go through
+    Strip,
+    /// Refuse the transfer until the items are gone
+    Block,
+}
+
+/// Transfer guardrails
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TransferRules {
+    /// Hours between two transfers of the same character
+    pub cooldown_hours: u32,
+    /// Refuse transfers of characters whose guild is at war
+    pub block_during_guild_war: bool,
+    /// Refuse transfers of characters with a running auction
+    pub block_with_active_auction: bool,
+    pub exclusive_items: ExclusiveItemPolicy,
+}
+
+impl Default for TransferRules {
+    fn default() -> Self {
+        Self {
+            cooldown_hours: 168, // 1 week
+            block_during_guild_war: true,
+            block_with_active_auction: true,
+            exclusive_items: ExclusiveItemPolicy::Strip,
+        }
+    }
+}
+
+/// An item stack carried by a character
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferItem {
+    pub item_id: u16,
+    pub count: u16,
+}
+
+/// State of the character to transfer, gathered by the caller
+#[derive(Debug, Clone, Default)]
+pub struct TransferCandidate {
+    pub character_id: Uuid,
+    pub character_name: String,
+    /// The character's guild is at war
+    pub in_guild_war: bool,
+    /// Character or item auctions the character is selling in
+    pub active_auctions: u32,
+    /// Items carried, in depot and inbox
+    pub items: Vec<TransferItem>,
+}
+
+/// What a transfer would do to the character's items
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferPreview {
+    /// Realm-exclusive items removed on transfer
+    pub stripped: Vec<TransferItem>,
+    /// Item stacks that move over
+    pub kept: usize,
+}
+
 /// Character transfer request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TransferRequest {
@@ -57,6 +122,9 @@ pub struct TransferRequest {
     pub denial_reason: Option<String>,
     /// Admin notes
     pub notes: Option<String>,
+    /// Realm-exclusive items to remove when the transfer completes
+    #[serde(default)]
+    pub stripped_items: Vec<TransferItem>,
 }
 
 impl TransferRequest {
@@ -83,6 +151,7 @@ impl TransferRequest {
             paid: false,
             denial_reason: None,
             notes: None,
+            stripped_items: Vec::new(),
         }
     }
 
@@ -126,8 +195,10 @@ impl TransferRequest {
 pub struct CrossRealmTransfer {
     /// Pending transfer requests
     requests: HashMap<Uuid, TransferRequest>,
-    /// Transfer cooldown per character (hours)
-    cooldown_hours: u32,
+    /// Cooldown, war, auction and item guardrails
+    rules: TransferRules,
+    /// Items that may not leave their realm, by realm
+    exclusive_items: HashMap<Uuid, HashSet<u16>>,
     /// Last transfer time per character
     last_transfer: HashMap<Uuid, DateTime<Utc>>,
     /// Base transfer cost
@@ -143,7 +214,8 @@ impl CrossRealmTransfer {
     pub fn new() -> Self {
         Self {
             requests: HashMap::new(),
-            cooldown_hours: 168, // 1 week
+            rules: TransferRules::default(),
+            exclusive_items: HashMap::new(),
             last_transfer: HashMap::new(),
             base_cost: 750, // Premium currency
             enabled: true,
@@ -151,12 +223,60 @@ impl CrossRealmTransfer {
         }
     }
 
+    /// Set the transfer guardrails
+    pub fn with_rules(mut self, rules: TransferRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Set the items that may not leave a realm
+    pub fn set_exclusive_items(&mut self, realm_id: Uuid, item_ids: impl IntoIterator<Item = u16>) {
+        self.exclusive_items.insert(realm_id, item_ids.into_iter().collect());
+    }
+
+    /// Items a transfer out of `from_realm` would strip, without checking
+    /// whether the transfer is allowed
+    pub fn preview(&self, from_realm: Uuid, candidate: &TransferCandidate) -> TransferPreview {
+        let exclusive = self.exclusive_items.get(&from_realm);
+        let (stripped, kept): (Vec<TransferItem>, Vec<TransferItem>) = candidate
+            .items
+            .iter()
+            .partition(|item| exclusive.is_some_and(|ids| ids.contains(&item.item_id)));
+
+        TransferPreview {
+            stripped,
+            kept: kept.len(),
+        }
+    }
+
+    /// Check the guardrails for transferring `candidate` out of `from_realm`
+    pub fn check_transfer(
+        &self,
+        from_realm: Uuid,
+        candidate: &TransferCandidate,
+    ) -> Result<TransferPreview, RealmError> {
+        if let Some(remaining) = self.cooldown_remaining(candidate.character_id) {
+            return Err(RealmError::TransferCooldown(remaining.num_minutes().max(1)));
+        }
+        if self.rules.block_during_guild_war && candidate.in_guild_war {
+            return Err(RealmError::TransferDuringGuildWar);
+        }
+        if self.rules.block_with_active_auction && candidate.active_auctions > 0 {
+            return Err(RealmError::TransferWithActiveAuction);
+        }
+
+        let preview = self.preview(from_realm, candidate);
+        if self.rules.exclusive_items == ExclusiveItemPolicy::Block && !preview.stripped.is_empty() {
+            return Err(RealmError::TransferExclusiveItems(preview.stripped.len()));
+        }
+        Ok(preview)
+    }
+
     /// Request a character transfer
     pub fn request_transfer(
         &mut self,
         account_id: Uuid,
-        character_id: Uuid,
-        character_name: &str,
+        candidate: &TransferCandidate,
         from_realm: Uuid,
         to_realm: Uuid,
     ) -> Result<TransferRequest, RealmError> {
@@ -164,13 +284,8 @@ impl CrossRealmTransfer {
             return Err(RealmError::TransferNotAllowed);
         }
 
-        // Check cooldown
-        if let Some(last) = self.last_transfer.get(&character_id) {
-            let cooldown = Duration::hours(self.cooldown_hours as i64);
-            if Utc::now() - *last < cooldown {
-                return Err(RealmError::TransferNotAllowed);
-            }
-        }
+        let preview = self.check_transfer(from_realm, candidate)?;
+        let character_id = candidate.character_id;
 
         // Check for existing pending request
         let has_pending = self.requests.values()
@@ -181,14 +296,15 @@ impl CrossRealmTransfer {
             return Err(RealmError::TransferNotAllowed);
         }
 
-        let request = TransferRequest::new(
+        let mut request = TransferRequest::new(
             account_id,
             character_id,
-            character_name,
+            &candidate.character_name,
             from_realm,
             to_realm,
             self.base_cost,
         );
+        request.stripped_items = preview.stripped;
 
         let result = request.clone();
         self.requests.insert(request.id, request);
@@ -220,7 +336,7 @@ impl CrossRealmTransfer {
         request.status = TransferStatus::InProgress;
 
         // Actual transfer would happen here:
-        // 1. Save character data from source realm
+        // 1. Save character data from source realm, without stripped_items
         // 2. Transfer data to destination realm
         // 3. Update realm assignments in database
         // 4. Mark transfer complete
@@ -288,7 +404,7 @@ impl CrossRealmTransfer {
     /// Check if transfer is on cooldown
     pub fn is_on_cooldown(&self, character_id: Uuid) -> bool {
         if let Some(last) = self.last_transfer.get(&character_id) {
-            let cooldown = Duration::hours(self.cooldown_hours as i64);
+            let cooldown = Duration::hours(self.rules.cooldown_hours as i64);
             Utc::now() - *last < cooldown
         } else {
             false
@@ -298,7 +414,7 @@ impl CrossRealmTransfer {
     /// Get remaining cooldown
     pub fn cooldown_remaining(&self, character_id: Uuid) -> Option<Duration> {
         self.last_transfer.get(&character_id).and_then(|last| {
-            let cooldown = Duration::hours(self.cooldown_hours as i64);
+            let cooldown = Duration::hours(self.rules.cooldown_hours as i64);
             let elapsed = Utc::now() - *last;
             if elapsed < cooldown {
                 Some(cooldown - elapsed)
@@ -314,3 +430,65 @@ impl Default for CrossRealmTransfer {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(items: &[(u16, u16)]) -> TransferCandidate {
+        TransferCandidate {
+            character_id: Uuid::new_v4(),
+            character_name: "Eryn".to_string(),
+            items: items.iter().map(|&(item_id, count)| TransferItem { item_id, count }).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_transfer_blocked_during_active_auction() {
+        let mut transfers = CrossRealmTransfer::new();
+        let (account, from, to) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let mut eryn = candidate(&[]);
+        eryn.active_auctions = 1;
+
+        assert!(matches!(
+            transfers.request_transfer(account, &eryn, from, to),
+            Err(RealmError::TransferWithActiveAuction)
+        ));
+
+        // Once the auction ends the transfer goes through, then the cooldown applies
+        eryn.active_auctions = 0;
+        let request = transfers.request_transfer(account, &eryn, from, to).unwrap();
+        transfers.requests.get_mut(&request.id).unwrap().mark_paid();
+        transfers.process_transfer(request.id).unwrap();
+        assert!(matches!(
+            transfers.request_transfer(account, &eryn, to, from),
+            Err(RealmError::TransferCooldown(_))
+        ));
+    }
+
+    #[test]
+    fn test_exclusive_item_strip_preview() {
+        let mut transfers = CrossRealmTransfer::new();
+        let (from, to) = (Uuid::new_v4(), Uuid::new_v4());
+        transfers.set_exclusive_items(from, [9020, 9021]);
+        let eryn = candidate(&[(2160, 100), (9020, 1), (3031, 50), (9021, 2)]);
+
+        let preview = transfers.check_transfer(from, &eryn).unwrap();
+        assert_eq!(
+            preview.stripped,
+            vec![TransferItem { item_id: 9020, count: 1 }, TransferItem { item_id: 9021, count: 2 }]
+        );
+        assert_eq!(preview.kept, 2);
+        // Nothing is exclusive to the other realm
+        assert!(transfers.preview(to, &eryn).stripped.is_empty());
+
+        let request = transfers.request_transfer(Uuid::new_v4(), &eryn, from, to).unwrap();
+        assert_eq!(request.stripped_items, preview.stripped);
+
+        let mut strict = CrossRealmTransfer::new()
+            .with_rules(TransferRules { exclusive_items: ExclusiveItemPolicy::Block, ..Default::default() });
+        strict.set_exclusive_items(from, [9020]);
+        assert!(matches!(strict.check_transfer(from, &eryn), Err(RealmError::TransferExclusiveItems(1))));
+    }
+}
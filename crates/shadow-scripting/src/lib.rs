@@ -7,13 +7,15 @@ pub mod npc;
 pub mod dialog;
 pub mod shop;
 pub mod quest;
+pub mod progression;
 pub mod lua;
 pub mod actions;
 
 pub use npc::{Npc, NpcHandler, NpcManager};
 pub use dialog::{DialogHandler, DialogState, DialogResponse};
 pub use shop::{Shop, ShopItem, ShopHandler};
-pub use quest::{QuestScript, QuestTrigger};
+pub use quest::{QuestManager, QuestReward, QuestScript, QuestTrigger, RewardBinding};
+pub use progression::{ProgressionStage, ProgressionTrack, ProgressionTracker};
 pub use lua::LuaEngine;
 pub use actions::{ScriptAction, ActionContext};
 
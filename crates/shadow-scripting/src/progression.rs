@@ -0,0 +1,349 @@
+//! Long-term progression tracks
+//!
+//! Multi-stage grind quests such as retail's daily kill counters. Unlike a
+//! one-shot quest, a track keeps a counter per character that grows over
+//! many days, optionally capped per day. Stages unlock as the counter
+//! passes their threshold; a gated stage holds the counter at its
+//! threshold until the gate is passed (a boss, a ritual, ...). The final
+//! reward can be claimed once every stage is unlocked.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::quest::QuestReward;
+use crate::{Result, ScriptError};
+
+/// A stage of a progression track
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProgressionStage {
+    pub name: String,
+    /// Counter value that unlocks the stage
+    pub required: u64,
+    /// The counter stops at `required` until the gate is passed
+    pub gated: bool,
+    /// Reward given when the stage unlocks
+    pub reward: Option<QuestReward>,
+}
+
+impl ProgressionStage {
+    pub fn new(name: impl Into<String>, required: u64) -> Self {
+        Self {
+            name: name.into(),
+            required,
+            gated: false,
+            reward: None,
+        }
+    }
+
+    pub fn gated(mut self) -> Self {
+        self.gated = true;
+        self
+    }
+
+    pub fn reward(mut self, reward: QuestReward) -> Self {
+        self.reward = Some(reward);
+        self
+    }
+}
+
+/// A long-term progression track definition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProgressionTrack {
+    pub id: String,
+    pub name: String,
+    /// Counter increase allowed per day (0 = unlimited)
+    pub daily_cap: u32,
+    /// Stages in ascending order of `required`
+    pub stages: Vec<ProgressionStage>,
+    /// Reward for unlocking every stage
+    pub final_reward: QuestReward,
+}
+
+impl ProgressionTrack {
+    pub fn new(id: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            daily_cap: 0,
+            stages: Vec::new(),
+            final_reward: QuestReward::default(),
+        }
+    }
+
+    pub fn daily_cap(mut self, cap: u32) -> Self {
+        self.daily_cap = cap;
+        self
+    }
+
+    pub fn add_stage(mut self, stage: ProgressionStage) -> Self {
+        self.stages.push(stage);
+        self
+    }
+
+    pub fn final_reward(mut self, reward: QuestReward) -> Self {
+        self.final_reward = reward;
+        self
+    }
+}
+
+/// A character's state on one track
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrackProgress {
+    pub track_id: String,
+    /// Counter value
+    pub total: u64,
+    /// Day the daily counter belongs to
+    pub day: Option<NaiveDate>,
+    /// Counter increase on `day`
+    pub today: u32,
+    /// Number of stages unlocked
+    pub stages_unlocked: usize,
+    /// Number of gates passed
+    pub gates_passed: usize,
+    pub reward_claimed: bool,
+}
+
+impl TrackProgress {
+    pub fn new(track_id: impl Into<String>) -> Self {
+        Self {
+            track_id: track_id.into(),
+            total: 0,
+            day: None,
+            today: 0,
+            stages_unlocked: 0,
+            gates_passed: 0,
+            reward_claimed: false,
+        }
+    }
+}
+
+/// Result of adding progress
+#[derive(Debug, Clone, Default)]
+pub struct ProgressUpdate {
+    /// Amount actually added after the daily cap and gates
+    pub added: u64,
+    pub total: u64,
+    /// Indices of stages unlocked by this update
+    pub unlocked: Vec<usize>,
+    /// Every stage is unlocked
+    pub completed: bool,
+}
+
+/// Tracks long-term progression of all characters
+pub struct ProgressionTracker {
+    tracks: HashMap<String, ProgressionTrack>,
+    /// character_id -> track_id -> progress
+    progress: HashMap<Uuid, HashMap<String, TrackProgress>>,
+}
+
+impl ProgressionTracker {
+    pub fn new() -> Self {
+        Self {
+            tracks: HashMap::new(),
+            progress: HashMap::new(),
+        }
+    }
+
+    /// Register a track
+    pub fn register(&mut self, track: ProgressionTrack) {
+        self.tracks.insert(track.id.clone(), track);
+    }
+
+    pub fn get_track(&self, id: &str) -> Option<&ProgressionTrack> {
+        self.tracks.get(id)
+    }
+
+    /// A character's progress on a track
+    pub fn progress(&self, character_id: Uuid, track_id: &str) -> Option<&TrackProgress> {
+        self.progress.get(&character_id)?.get(track_id)
+    }
+
+    /// All tracks a character has progress on, e.g. for saving
+    pub fn character_progress(&self, character_id: Uuid) -> Vec<&TrackProgress> {
+        self.progress
+            .get(&character_id)
+            .map(|tracks| tracks.values().collect())
+            .unwrap_or_default()
+    }
+
+    /// Restore saved progress
+    pub fn load_progress(&mut self, character_id: Uuid, progress: TrackProgress) {
+        self.progress
+            .entry(character_id)
+            .or_default()
+            .insert(progress.track_id.clone(), progress);
+    }
+
+    /// Advance a character's counter on a track
+    pub fn add_progress(
+        &mut self,
+        character_id: Uuid,
+        track_id: &str,
+        amount: u32,
+        now: DateTime<Utc>,
+    ) -> Result<ProgressUpdate> {
+        let track = self
+            .tracks
+            .get(track_id)
+            .ok_or_else(|| ScriptError::NotFound(track_id.to_string()))?;
+        let progress = self
+            .progress
+            .entry(character_id)
+            .or_default()
+            .entry(track_id.to_string())
+            .or_insert_with(|| TrackProgress::new(track_id));
+
+        let day = now.date_naive();
+        if progress.day != Some(day) {
+            progress.day = Some(day);
+            progress.today = 0;
+        }
+
+        let mut amount = amount;
+        if track.daily_cap > 0 {
+            amount = amount.min(track.daily_cap.saturating_sub(progress.today));
+        }
+
+        // A closed gate holds the counter at its stage
+        let limit = track
+            .stages
+            .iter()
+            .filter(|s| s.gated)
+            .nth(progress.gates_passed)
+            .map_or(u64::MAX, |gate| gate.required);
+
+        let before = progress.total;
+        progress.total = progress.total.saturating_add(amount as u64).min(limit.max(before));
+        let added = progress.total - before;
+        progress.today += added as u32;
+
+        let mut unlocked = Vec::new();
+        while let Some(stage) = track.stages.get(progress.stages_unlocked) {
+            if progress.total < stage.required {
+                break;
+            }
+            unlocked.push(progress.stages_unlocked);
+            progress.stages_unlocked += 1;
+        }
+
+        Ok(ProgressUpdate {
+            added,
+            total: progress.total,
+            unlocked,
+            completed: progress.stages_unlocked == track.stages.len(),
+        })
+    }
+
+    /// Pass the next closed gate of a track, letting the counter go on
+    pub fn pass_gate(&mut self, character_id: Uuid, track_id: &str) -> Result<()> {
+        let track = self
+            .tracks
+            .get(track_id)
+            .ok_or_else(|| ScriptError::NotFound(track_id.to_string()))?;
+        let progress = self
+            .progress
+            .get_mut(&character_id)
+            .and_then(|tracks| tracks.get_mut(track_id))
+            .ok_or_else(|| ScriptError::Quest("No progress on track".to_string()))?;
+
+        let gate = track
+            .stages
+            .iter()
+            .filter(|s| s.gated)
+            .nth(progress.gates_passed)
+            .ok_or_else(|| ScriptError::Quest("No gate left".to_string()))?;
+        if progress.total < gate.required {
+            return Err(ScriptError::Quest(format!("{} is not reached yet", gate.name)));
+        }
+        progress.gates_passed += 1;
+        Ok(())
+    }
+
+    /// Claim the final reward of a completed track, once
+    pub fn claim_final_reward(&mut self, character_id: Uuid, track_id: &str) -> Result<QuestReward> {
+        let track = self
+            .tracks
+            .get(track_id)
+            .ok_or_else(|| ScriptError::NotFound(track_id.to_string()))?;
+        let progress = self
+            .progress
+            .get_mut(&character_id)
+            .and_then(|tracks| tracks.get_mut(track_id))
+            .filter(|p| p.stages_unlocked == track.stages.len())
+            .ok_or_else(|| ScriptError::Quest("Track is not completed".to_string()))?;
+
+        if progress.reward_claimed {
+            return Err(ScriptError::Quest("Reward already claimed".to_string()));
+        }
+        progress.reward_claimed = true;
+        Ok(track.final_reward.clone())
+    }
+}
+
+impl Default for ProgressionTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn day(d: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 5, d, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_daily_cap() {
+        let mut tracker = ProgressionTracker::new();
+        tracker.register(
+            ProgressionTrack::new("soul_war", "Soul War")
+                .daily_cap(10)
+                .add_stage(ProgressionStage::new("Taints", 100)),
+        );
+        let player = Uuid::new_v4();
+
+        assert_eq!(tracker.add_progress(player, "soul_war", 7, day(1)).unwrap().added, 7);
+        let update = tracker.add_progress(player, "soul_war", 7, day(1)).unwrap();
+        assert_eq!((update.added, update.total), (3, 10));
+        assert_eq!(tracker.add_progress(player, "soul_war", 5, day(1)).unwrap().added, 0);
+
+        // The cap resets the next day and the counter persists
+        let update = tracker.add_progress(player, "soul_war", 5, day(2)).unwrap();
+        assert_eq!((update.added, update.total), (5, 15));
+        assert!(!update.completed);
+        assert!(tracker.add_progress(player, "unknown", 1, day(2)).is_err());
+    }
+
+    #[test]
+    fn test_gated_stages_and_final_reward() {
+        let mut tracker = ProgressionTracker::new();
+        tracker.register(
+            ProgressionTrack::new("grind", "Grind")
+                .add_stage(ProgressionStage::new("First", 10).gated())
+                .add_stage(ProgressionStage::new("Second", 20))
+                .add_stage(ProgressionStage::new("Final", 30))
+                .final_reward(QuestReward::new().experience(1_000_000)),
+        );
+        let player = Uuid::new_v4();
+
+        // The gate holds the counter at the first stage
+        let update = tracker.add_progress(player, "grind", 25, day(1)).unwrap();
+        assert_eq!((update.added, update.total, update.unlocked), (10, 10, vec![0]));
+        assert!(tracker.claim_final_reward(player, "grind").is_err());
+
+        tracker.pass_gate(player, "grind").unwrap();
+        let update = tracker.add_progress(player, "grind", 25, day(1)).unwrap();
+        assert_eq!(update.unlocked, vec![1, 2]);
+        assert!(update.completed);
+
+        let reward = tracker.claim_final_reward(player, "grind").unwrap();
+        assert_eq!(reward.experience, 1_000_000);
+        assert!(tracker.claim_final_reward(player, "grind").is_err());
+        assert!(tracker.progress(player, "grind").unwrap().reward_claimed);
+    }
+}
@@ -3,7 +3,7 @@
 //! Handles quest definitions, triggers, and progression.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 /// Quest state
@@ -105,6 +105,21 @@ pub enum QuestTrigger {
     },
 }
 
+/// Who a reward can be claimed by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RewardBinding {
+    /// Every character may claim it
+    Character,
+    /// Only one character per account may claim it
+    Account,
+}
+
+impl Default for RewardBinding {
+    fn default() -> Self {
+        Self::Character
+    }
+}
+
 /// Quest reward
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QuestReward {
@@ -124,6 +139,9 @@ pub struct QuestReward {
     pub mount: Option<u16>,
     /// Storage values set
     pub storage: HashMap<u32, i32>,
+    /// Whether the reward is claimable per character or once per account
+    #[serde(default)]
+    pub binding: RewardBinding,
 }
 
 impl Default for QuestReward {
@@ -137,6 +155,7 @@ impl Default for QuestReward {
             addon: None,
             mount: None,
             storage: HashMap::new(),
+            binding: RewardBinding::Character,
         }
     }
 }
@@ -160,6 +179,11 @@ impl QuestReward {
         self.items.push(RewardItem { item_id, count });
         self
     }
+
+    pub fn binding(mut self, binding: RewardBinding) -> Self {
+        self.binding = binding;
+        self
+    }
 }
 
 /// Item given as reward
@@ -354,6 +378,8 @@ pub struct QuestManager {
     quests: HashMap<String, QuestScript>,
     /// Player progress (player_id -> quest_id -> progress)
     progress: HashMap<Uuid, HashMap<String, QuestProgress>>,
+    /// Account-bound rewards already claimed (account_id -> quest ids)
+    account_claims: HashMap<Uuid, HashSet<String>>,
 }
 
 impl QuestManager {
@@ -361,6 +387,7 @@ impl QuestManager {
         Self {
             quests: HashMap::new(),
             progress: HashMap::new(),
+            account_claims: HashMap::new(),
         }
     }
 
@@ -403,6 +430,54 @@ impl QuestManager {
         self.progress.get_mut(&player_id)?.get_mut(quest_id)
     }
 
+    /// Complete a quest in progress and hand out its final reward.
+    ///
+    /// Returns `None` instead of the reward when it is account-bound and
+    /// another character of `account_id` already claimed it; the quest
+    /// still counts as completed for this character.
+    pub fn complete_quest(
+        &mut self,
+        player_id: Uuid,
+        account_id: Uuid,
+        quest_id: &str,
+    ) -> Result<Option<QuestReward>, &'static str> {
+        let quest = self.quests.get(quest_id).ok_or("Quest not found")?;
+        let progress = self
+            .progress
+            .get_mut(&player_id)
+            .and_then(|p| p.get_mut(quest_id))
+            .filter(|p| p.state == QuestState::InProgress)
+            .ok_or("Quest not in progress")?;
+        progress.complete();
+
+        match quest.rewards.binding {
+            RewardBinding::Character => Ok(Some(quest.rewards.clone())),
+            RewardBinding::Account => {
+                let claimed = self
+                    .account_claims
+                    .entry(account_id)
+                    .or_default()
+                    .insert(quest_id.to_string());
+                Ok(claimed.then(|| quest.rewards.clone()))
+            }
+        }
+    }
+
+    /// Check if an account already claimed a quest's account-bound reward
+    pub fn is_claimed_by_account(&self, account_id: Uuid, quest_id: &str) -> bool {
+        self.account_claims
+            .get(&account_id)
+            .map_or(false, |claims| claims.contains(quest_id))
+    }
+
+    /// Restore an account-bound claim, e.g. when loading from the database
+    pub fn record_account_claim(&mut self, account_id: Uuid, quest_id: &str) {
+        self.account_claims
+            .entry(account_id)
+            .or_default()
+            .insert(quest_id.to_string());
+    }
+
     /// Check if player completed a quest
     pub fn is_completed(&self, player_id: Uuid, quest_id: &str) -> bool {
         self.get_progress(player_id, quest_id)
@@ -497,4 +572,46 @@ mod tests {
         let progress = manager.get_progress(player_id, "test").unwrap();
         assert_eq!(progress.state, QuestState::InProgress);
     }
+
+    #[test]
+    fn test_account_bound_reward_blocked_on_alt() {
+        let mut manager = QuestManager::new();
+        let account_id = Uuid::new_v4();
+        let (main, alt) = (Uuid::new_v4(), Uuid::new_v4());
+
+        manager.register(
+            QuestScript::new("annihilator", "Annihilator")
+                .rewards(QuestReward::new().item(2494, 1).binding(RewardBinding::Account)),
+        );
+
+        manager.start_quest(main, "annihilator").unwrap();
+        let reward = manager.complete_quest(main, account_id, "annihilator").unwrap();
+        assert_eq!(reward.unwrap().items.len(), 1);
+        assert!(manager.is_claimed_by_account(account_id, "annihilator"));
+
+        // The alt can finish the quest but gets nothing
+        manager.start_quest(alt, "annihilator").unwrap();
+        assert!(manager.complete_quest(alt, account_id, "annihilator").unwrap().is_none());
+        assert!(manager.is_completed(alt, "annihilator"));
+
+        // Another account is unaffected
+        let other = Uuid::new_v4();
+        manager.start_quest(other, "annihilator").unwrap();
+        assert!(manager.complete_quest(other, Uuid::new_v4(), "annihilator").unwrap().is_some());
+    }
+
+    #[test]
+    fn test_character_bound_reward_allowed_on_alt() {
+        let mut manager = QuestManager::new();
+        let account_id = Uuid::new_v4();
+        manager.register(QuestScript::new("rookgaard", "Rookgaard").rewards(QuestReward::new().gold(100)));
+
+        for character in [Uuid::new_v4(), Uuid::new_v4()] {
+            manager.start_quest(character, "rookgaard").unwrap();
+            let reward = manager.complete_quest(character, account_id, "rookgaard").unwrap();
+            assert_eq!(reward.unwrap().gold, 100);
+        }
+        assert!(!manager.is_claimed_by_account(account_id, "rookgaard"));
+        assert!(manager.complete_quest(Uuid::new_v4(), account_id, "rookgaard").is_err());
+    }
 }
@@ -0,0 +1,209 @@
+//! Access gates - quest/achievement-gated map areas
+//!
+//! An access gate is attached to a tile or teleport position and checks
+//! quest progress, item possession, level or vocation before letting a
+//! player step onto it. Players that fail any requirement are pushed back
+//! with the gate's denial message.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Character storage key holding the stage a quest is at
+pub fn quest_stage_key(quest_id: &str) -> String {
+    format!("quest.{}.stage", quest_id)
+}
+
+/// Character storage key set to 1 once a quest is completed
+pub fn quest_completed_key(quest_id: &str) -> String {
+    format!("quest.{}.completed", quest_id)
+}
+
+/// A single requirement of an access gate
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AccessRequirement {
+    /// Quest must be at least at the given stage
+    QuestStage { quest_id: String, stage: u8 },
+    /// Quest must be completed
+    QuestCompleted(String),
+    /// Player must carry at least `count` of an item
+    Item { item_id: u16, count: u32 },
+    /// Minimum player level
+    Level(u32),
+    /// Player vocation must be one of these (vocation ids)
+    Vocation(Vec<u8>),
+}
+
+/// Player state checked against gate requirements
+#[derive(Debug, Clone, Default)]
+pub struct AccessContext {
+    pub level: u32,
+    pub vocation: u8,
+    /// Current stage per quest id
+    pub quest_stages: HashMap<String, u8>,
+    /// Completed quest ids
+    pub completed_quests: HashSet<String>,
+    /// Item counts carried (item id -> count)
+    pub items: HashMap<u16, u32>,
+}
+
+impl AccessContext {
+    pub fn quest_stage(&self, quest_id: &str) -> u8 {
+        self.quest_stages.get(quest_id).copied().unwrap_or(0)
+    }
+
+    pub fn item_count(&self, item_id: u16) -> u32 {
+        self.items.get(&item_id).copied().unwrap_or(0)
+    }
+
+    fn meets(&self, requirement: &AccessRequirement) -> bool {
+        match requirement {
+            AccessRequirement::QuestStage { quest_id, stage } => {
+                self.completed_quests.contains(quest_id) || self.quest_stage(quest_id) >= *stage
+            }
+            AccessRequirement::QuestCompleted(quest_id) => self.completed_quests.contains(quest_id),
+            AccessRequirement::Item { item_id, count } => self.item_count(*item_id) >= *count,
+            AccessRequirement::Level(level) => self.level >= *level,
+            AccessRequirement::Vocation(vocations) => vocations.contains(&self.vocation),
+        }
+    }
+}
+
+/// Gate attached to a tile or teleport
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessGate {
+    /// All requirements must be met
+    pub requirements: Vec<AccessRequirement>,
+    /// Message sent to players that are denied entry
+    pub denial_message: String,
+}
+
+impl AccessRequirement {
+    /// Quest the requirement asks about, if any
+    pub fn quest_id(&self) -> Option<&str> {
+        match self {
+            AccessRequirement::QuestStage { quest_id, .. } | AccessRequirement::QuestCompleted(quest_id) => Some(quest_id),
+            _ => None,
+        }
+    }
+}
+
+impl AccessGate {
+    pub fn new(denial_message: impl Into<String>) -> Self {
+        Self {
+            requirements: Vec::new(),
+            denial_message: denial_message.into(),
+        }
+    }
+
+    pub fn require(mut self, requirement: AccessRequirement) -> Self {
+        self.requirements.push(requirement);
+        self
+    }
+
+    pub fn require_quest_stage(self, quest_id: impl Into<String>, stage: u8) -> Self {
+        self.require(AccessRequirement::QuestStage { quest_id: quest_id.into(), stage })
+    }
+
+    pub fn require_quest_completed(self, quest_id: impl Into<String>) -> Self {
+        self.require(AccessRequirement::QuestCompleted(quest_id.into()))
+    }
+
+    pub fn require_item(self, item_id: u16, count: u32) -> Self {
+        self.require(AccessRequirement::Item { item_id, count })
+    }
+
+    pub fn require_level(self, level: u32) -> Self {
+        self.require(AccessRequirement::Level(level))
+    }
+
+    pub fn require_vocation(self, vocations: Vec<u8>) -> Self {
+        self.require(AccessRequirement::Vocation(vocations))
+    }
+
+    /// Check a player against the gate
+    pub fn check(&self, ctx: &AccessContext) -> Result<(), AccessDenied> {
+        match self.requirements.iter().find(|r| !ctx.meets(r)) {
+            Some(requirement) => Err(AccessDenied {
+                message: self.denial_message.clone(),
+                requirement: requirement.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Entry denied by an access gate
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccessDenied {
+    /// Message to send to the player
+    pub message: String,
+    /// First requirement that was not met
+    pub requirement: AccessRequirement,
+}
+
+impl std::fmt::Display for AccessDenied {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for AccessDenied {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::Map;
+    use crate::position::Position;
+
+    #[test]
+    fn test_gate_blocks_until_quest_completed() {
+        let mut map = Map::new("Test".to_string());
+        let pos = Position::new(100, 100, 7);
+        map.set_access_gate(
+            pos,
+            AccessGate::new("You have not finished the Inquisition Quest.")
+                .require_quest_completed("inquisition"),
+        );
+
+        let mut player = AccessContext { level: 100, ..Default::default() };
+        player.quest_stages.insert("inquisition".to_string(), 5);
+
+        let denied = map.check_access(&pos, &player).unwrap_err();
+        assert_eq!(denied.message, "You have not finished the Inquisition Quest.");
+        assert_eq!(denied.requirement, AccessRequirement::QuestCompleted("inquisition".to_string()));
+
+        player.completed_quests.insert("inquisition".to_string());
+        assert!(map.check_access(&pos, &player).is_ok());
+
+        // Ungated tiles are always open
+        assert!(map.check_access(&Position::new(101, 100, 7), &AccessContext::default()).is_ok());
+        assert_eq!(map.gated_quest_ids(), HashSet::from(["inquisition".to_string()]));
+    }
+
+    #[test]
+    fn test_gate_requirements() {
+        let gate = AccessGate::new("You may not pass.")
+            .require_quest_stage("demon_oak", 2)
+            .require_item(2088, 1)
+            .require_level(120)
+            .require_vocation(vec![1, 2]);
+
+        let mut player = AccessContext { level: 150, vocation: 1, ..Default::default() };
+        player.quest_stages.insert("demon_oak".to_string(), 2);
+        player.items.insert(2088, 1);
+        assert!(gate.check(&player).is_ok());
+
+        let low = AccessContext { level: 80, ..player.clone() };
+        assert_eq!(gate.check(&low).unwrap_err().requirement, AccessRequirement::Level(120));
+
+        let knight = AccessContext { vocation: 4, ..player.clone() };
+        assert!(gate.check(&knight).is_err());
+
+        let mut no_key = player.clone();
+        no_key.items.clear();
+        assert!(matches!(
+            gate.check(&no_key).unwrap_err().requirement,
+            AccessRequirement::Item { item_id: 2088, .. }
+        ));
+    }
+}
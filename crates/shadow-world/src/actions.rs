@@ -103,6 +103,80 @@ impl ItemActionContext {
     }
 }
 
+/// Exhaust group of a usable item. Each group has its own timer, so a
+/// potion can be used right after eating but not right after another potion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ItemUseCategory {
+    /// Healing and mana potions, healing runes
+    Healing,
+    /// Utility consumables (e.g. haste potions, support runes)
+    Support,
+    Food,
+}
+
+/// Item-use exhaust durations, separate from spell cooldowns
+#[derive(Debug, Clone)]
+pub struct ItemExhaustConfig {
+    pub healing_ms: u64,
+    pub support_ms: u64,
+    pub food_ms: u64,
+}
+
+impl Default for ItemExhaustConfig {
+    fn default() -> Self {
+        Self {
+            healing_ms: 1000,
+            support_ms: 2000,
+            food_ms: 500,
+        }
+    }
+}
+
+impl ItemExhaustConfig {
+    pub fn duration_ms(&self, category: ItemUseCategory) -> u64 {
+        match category {
+            ItemUseCategory::Healing => self.healing_ms,
+            ItemUseCategory::Support => self.support_ms,
+            ItemUseCategory::Food => self.food_ms,
+        }
+    }
+}
+
+/// Per-player item-use exhaust timers
+#[derive(Debug, Default)]
+pub struct ItemExhaust {
+    config: ItemExhaustConfig,
+    /// (player, category) -> exhausted until (ms)
+    until: HashMap<(Uuid, ItemUseCategory), u64>,
+}
+
+impl ItemExhaust {
+    pub fn new(config: ItemExhaustConfig) -> Self {
+        Self {
+            config,
+            until: HashMap::new(),
+        }
+    }
+
+    /// Remaining exhaust in milliseconds, if any
+    pub fn remaining(&self, player_id: Uuid, category: ItemUseCategory, now_ms: u64) -> Option<u64> {
+        self.until
+            .get(&(player_id, category))
+            .filter(|&&until| until > now_ms)
+            .map(|&until| until - now_ms)
+    }
+
+    /// Start the exhaust for a category
+    pub fn trigger(&mut self, player_id: Uuid, category: ItemUseCategory, now_ms: u64) {
+        self.until.insert((player_id, category), now_ms + self.config.duration_ms(category));
+    }
+
+    /// Forget a player's timers (e.g. on logout)
+    pub fn clear_player(&mut self, player_id: Uuid) {
+        self.until.retain(|(id, _), _| *id != player_id);
+    }
+}
+
 /// Handler for specific item type actions
 pub trait ItemActionHandler: Send + Sync {
     /// Handle the item being used
@@ -123,6 +197,11 @@ pub trait ItemActionHandler: Send + Sync {
     fn unique_id(&self) -> Option<u16> {
         None
     }
+
+    /// Exhaust group the item belongs to, if using it causes exhaust
+    fn use_category(&self) -> Option<ItemUseCategory> {
+        None
+    }
 }
 
 /// Default handler for items with no special behavior
@@ -261,6 +340,10 @@ impl ItemActionHandler for PotionHandler {
     fn on_move(&self, _ctx: &ItemActionContext) -> ItemActionResult {
         ItemActionResult::Success
     }
+
+    fn use_category(&self) -> Option<ItemUseCategory> {
+        Some(ItemUseCategory::Healing)
+    }
 }
 
 /// Handler for runes
@@ -324,6 +407,11 @@ impl ItemActionHandler for RuneHandler {
     fn on_move(&self, _ctx: &ItemActionContext) -> ItemActionResult {
         ItemActionResult::Success
     }
+
+    fn use_category(&self) -> Option<ItemUseCategory> {
+        // Attack runes share the combat exhaust instead
+        (!self.aggressive).then_some(ItemUseCategory::Healing)
+    }
 }
 
 /// Handler for food items
@@ -355,6 +443,10 @@ impl ItemActionHandler for FoodHandler {
     fn on_move(&self, _ctx: &ItemActionContext) -> ItemActionResult {
         ItemActionResult::Success
     }
+
+    fn use_category(&self) -> Option<ItemUseCategory> {
+        Some(ItemUseCategory::Food)
+    }
 }
 
 /// Handler for doors
@@ -454,6 +546,30 @@ impl ItemActionRegistry {
         self.default.as_ref()
     }
 
+    /// Use an item, enforcing its exhaust group. A failed use does not
+    /// start the exhaust.
+    pub fn use_item(
+        &self,
+        item: &Item,
+        ctx: &ItemActionContext,
+        exhaust: &mut ItemExhaust,
+        now_ms: u64,
+    ) -> Result<ItemActionResult> {
+        let handler = self.get_handler(item);
+        let category = handler.use_category();
+        if let Some(category) = category {
+            if let Some(remaining) = exhaust.remaining(ctx.player_id, category, now_ms) {
+                return Err(WorldError::Exhausted(remaining));
+            }
+        }
+
+        let result = handler.on_use(ctx);
+        if let Some(category) = category.filter(|_| !matches!(result, ItemActionResult::Failed(_))) {
+            exhaust.trigger(ctx.player_id, category, now_ms);
+        }
+        Ok(result)
+    }
+
     /// Register default handlers
     pub fn register_defaults(&mut self) {
         // Rope (2120)
@@ -519,6 +635,30 @@ mod tests {
         // Handler should exist
     }
 
+    #[test]
+    fn test_potion_rejected_during_exhaust() {
+        let registry = ItemActionRegistry::default();
+        let mut exhaust = ItemExhaust::default();
+        let player = Uuid::new_v4();
+        let potion = Item::new(7618);
+        let ctx = ItemActionContext::new(player, 1, 7618, Position::new(100, 100, 7));
+
+        assert!(registry.use_item(&potion, &ctx, &mut exhaust, 10_000).is_ok());
+        assert!(matches!(
+            registry.use_item(&potion, &ctx, &mut exhaust, 10_400),
+            Err(WorldError::Exhausted(600))
+        ));
+
+        // Food has its own timer
+        let ham = Item::new(2671);
+        assert!(registry.use_item(&ham, &ctx, &mut exhaust, 10_400).is_ok());
+
+        // Other players and expired timers are unaffected
+        let other = ItemActionContext::new(Uuid::new_v4(), 2, 7618, Position::new(100, 100, 7));
+        assert!(registry.use_item(&potion, &other, &mut exhaust, 10_400).is_ok());
+        assert!(registry.use_item(&potion, &ctx, &mut exhaust, 11_000).is_ok());
+    }
+
     #[test]
     fn test_potion_handler() {
         let handler = PotionHandler::health_potion();
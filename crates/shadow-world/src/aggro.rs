@@ -0,0 +1,107 @@
+//! Monster aggro and sense ranges
+//!
+//! Each monster type decides how far it notices players, whether it notices
+//! them on other floors, and whether invisibility hides a player from it.
+//! The AI targeting step only considers players the monster can sense and
+//! picks the nearest one.
+
+use serde::{Deserialize, Serialize};
+
+use crate::creature::{ConditionType, Creature};
+
+/// How a monster type senses players
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AggroConfig {
+    /// Distance in tiles at which the monster notices players
+    pub range: u8,
+    /// Floors above or below its own on which the monster notices players
+    /// (0 = own floor only)
+    pub floor_range: u8,
+    /// The monster notices players under the invisible condition
+    pub sense_invisible: bool,
+}
+
+impl Default for AggroConfig {
+    fn default() -> Self {
+        Self {
+            range: 8,
+            floor_range: 0,
+            sense_invisible: false,
+        }
+    }
+}
+
+impl AggroConfig {
+    /// Whether `monster` notices `target`
+    pub fn can_sense(&self, monster: &Creature, target: &Creature) -> bool {
+        self.can_sense_within(monster, target, self.range)
+    }
+
+    /// Like `can_sense`, with the horizontal range overridden, e.g. when the
+    /// target is a protected new character
+    pub fn can_sense_within(&self, monster: &Creature, target: &Creature, range: u8) -> bool {
+        if !target.is_player() || !target.is_alive() || target.removed || !target.visible {
+            return false;
+        }
+        if target.has_condition(ConditionType::Invisible) && !self.sense_invisible {
+            return false;
+        }
+        let floors = (monster.position.z as i32 - target.position.z as i32).unsigned_abs();
+        floors <= self.floor_range as u32 && monster.position.distance_to(&target.position) <= range as u32
+    }
+
+    /// AI targeting step: the nearest player `monster` can sense, if any
+    pub fn select_target<'a>(
+        &self,
+        monster: &Creature,
+        candidates: impl IntoIterator<Item = &'a Creature>,
+    ) -> Option<&'a Creature> {
+        candidates
+            .into_iter()
+            .filter(|target| self.can_sense(monster, target))
+            .min_by_key(|target| monster.position.distance_to(&target.position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::creature::{Condition, CreatureType};
+    use crate::position::Position;
+
+    fn player(x: u16, y: u16, z: u8) -> Creature {
+        let mut player = Creature::new("Player".to_string(), CreatureType::Player, Position::new(x, y, z));
+        player.stats.health = 150;
+        player
+    }
+
+    #[test]
+    fn test_no_aggro_beyond_range() {
+        let monster = Creature::new("Rotworm".to_string(), CreatureType::Monster, Position::new(100, 100, 7));
+        let aggro = AggroConfig { range: 5, ..Default::default() };
+
+        let far = player(106, 100, 7);
+        assert!(!aggro.can_sense(&monster, &far));
+        assert!(aggro.select_target(&monster, [&far]).is_none());
+
+        let near = player(104, 103, 7);
+        let below = player(101, 100, 8);
+        assert_eq!(aggro.select_target(&monster, [&far, &below, &near]).map(|t| t.id), Some(near.id));
+
+        // Vertical aggro is opt-in
+        let vertical = AggroConfig { floor_range: 1, ..aggro };
+        assert_eq!(vertical.select_target(&monster, [&near, &below]).map(|t| t.id), Some(below.id));
+    }
+
+    #[test]
+    fn test_invisible_player_ignored_when_configured() {
+        let monster = Creature::new("Demon".to_string(), CreatureType::Monster, Position::new(100, 100, 7));
+        let mut target = player(102, 100, 7);
+        target.add_condition(Condition::new(ConditionType::Invisible, 60_000));
+
+        assert!(!AggroConfig::default().can_sense(&monster, &target));
+        let sees_invisible = AggroConfig { sense_invisible: true, ..Default::default() };
+        assert!(sees_invisible.can_sense(&monster, &target));
+    }
+}
@@ -0,0 +1,121 @@
+//! Game clock and time acceleration
+//!
+//! Test realms can run game time faster than wall-clock time so a whole
+//! "day" of decay, scheduled tasks and resets passes in minutes. Only game
+//! systems read the accelerated clock; authentication, sessions and other
+//! security checks keep using real wall-clock time.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Fastest supported acceleration (one game day per real minute)
+pub const MAX_TIME_SCALE: f64 = 1440.0;
+
+/// Maps real time to game time at a fixed rate
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct GameClock {
+    /// Game seconds per real second
+    scale: f64,
+    /// Real time the clock started at; game time equals it at that point
+    origin: DateTime<Utc>,
+}
+
+impl GameClock {
+    /// Clock running at `scale`, starting now. The scale is clamped to
+    /// `1.0..=MAX_TIME_SCALE`.
+    pub fn new(scale: f64) -> Self {
+        Self::starting_at(scale, Utc::now())
+    }
+
+    /// Clock running at `scale` whose game time matches `origin` at `origin`
+    pub fn starting_at(scale: f64, origin: DateTime<Utc>) -> Self {
+        let scale = if scale.is_finite() { scale.clamp(1.0, MAX_TIME_SCALE) } else { 1.0 };
+        Self { scale, origin }
+    }
+
+    /// Clock running at real speed
+    pub fn real_time() -> Self {
+        Self::new(1.0)
+    }
+
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    pub fn is_accelerated(&self) -> bool {
+        self.scale > 1.0
+    }
+
+    /// Current game time
+    pub fn now(&self) -> DateTime<Utc> {
+        self.game_time_at(Utc::now())
+    }
+
+    /// Current wall-clock time, for auth and security checks
+    pub fn wall_now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    /// Game time at the real time `real`
+    pub fn game_time_at(&self, real: DateTime<Utc>) -> DateTime<Utc> {
+        let elapsed = (real - self.origin).to_std().unwrap_or_default();
+        self.origin + chrono::Duration::from_std(self.game_elapsed(elapsed)).unwrap_or_default()
+    }
+
+    /// Game time that passes during `real` real time
+    pub fn game_elapsed(&self, real: Duration) -> Duration {
+        real.mul_f64(self.scale)
+    }
+
+    /// Real time it takes for `game` game time to pass
+    pub fn real_duration(&self, game: Duration) -> Duration {
+        game.div_f64(self.scale)
+    }
+}
+
+impl Default for GameClock {
+    fn default() -> Self {
+        Self::real_time()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::item::{DecayState, Item};
+
+    #[test]
+    fn test_accelerated_decay() {
+        let clock = GameClock::new(60.0);
+        assert_eq!(clock.real_duration(Duration::from_secs(3600)), Duration::from_secs(60));
+
+        // A 10 minute decay finishes after 10 real seconds
+        let mut item = Item::new(2148);
+        item.duration = Some(600_000);
+        item.decay_state = DecayState::Started;
+        assert!(!item.advance_decay(clock.game_elapsed(Duration::from_secs(9))));
+        assert!(item.advance_decay(clock.game_elapsed(Duration::from_secs(1))));
+
+        // Real time decays as before
+        let mut item = Item::new(2148);
+        item.duration = Some(600_000);
+        item.decay_state = DecayState::Started;
+        assert!(!item.advance_decay(GameClock::real_time().game_elapsed(Duration::from_secs(10))));
+        assert_eq!(item.duration, Some(590_000));
+    }
+
+    #[test]
+    fn test_game_time_and_scale_limits() {
+        let origin = Utc::now();
+        let clock = GameClock::starting_at(MAX_TIME_SCALE, origin);
+        let real = origin + chrono::Duration::minutes(1);
+        assert_eq!(clock.game_time_at(real), origin + chrono::Duration::days(1));
+        assert!(clock.wall_now() - origin < chrono::Duration::minutes(1));
+
+        assert_eq!(GameClock::new(0.5).scale(), 1.0);
+        assert_eq!(GameClock::new(f64::NAN).scale(), 1.0);
+        assert_eq!(GameClock::new(1e9).scale(), MAX_TIME_SCALE);
+        assert!(!GameClock::default().is_accelerated());
+    }
+}
@@ -0,0 +1,223 @@
+//! Monster corpse ownership and decay
+//!
+//! A fresh corpse can only be opened by the players who earned its loot
+//! (and, if the policy allows, their party members). After the protection
+//! window anyone may loot it, and once its decay runs out it is removed.
+//! Corpse lifetime is tracked through the corpse item's decay duration, so
+//! it follows the same game clock as every other decaying item.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use crate::item::{DecayState, Item};
+use crate::position::Position;
+
+/// Corpse protection and decay timings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorpsePolicy {
+    /// Seconds only the owners may open the corpse
+    pub protection_secs: u32,
+    /// Seconds the corpse stays lootable by anyone after protection ends
+    pub public_secs: u32,
+    /// Party members of the owners may open a protected corpse
+    pub party_loot: bool,
+}
+
+impl CorpsePolicy {
+    /// Total corpse lifetime in milliseconds
+    pub fn lifetime_ms(&self) -> u32 {
+        self.protection_secs.saturating_add(self.public_secs).saturating_mul(1000)
+    }
+}
+
+impl Default for CorpsePolicy {
+    fn default() -> Self {
+        Self {
+            protection_secs: 10,
+            public_secs: 290,
+            party_loot: true,
+        }
+    }
+}
+
+/// Lifecycle phase of a corpse
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CorpsePhase {
+    /// Only owners may open it
+    Protected,
+    /// Anyone may open it
+    Public,
+    /// Decay has run out; the corpse is gone
+    Decayed,
+}
+
+/// Why a corpse could not be opened
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CorpseAccessError {
+    NotFound,
+    NotOwner,
+}
+
+impl std::fmt::Display for CorpseAccessError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CorpseAccessError::NotFound => write!(f, "Corpse not found"),
+            CorpseAccessError::NotOwner => write!(f, "You are not the owner"),
+        }
+    }
+}
+
+impl std::error::Error for CorpseAccessError {}
+
+/// A monster corpse on the map
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Corpse {
+    /// Corpse container; its remaining duration is the corpse lifetime
+    pub item: Item,
+    pub position: Position,
+    /// Players who earned the loot
+    pub owners: HashSet<u32>,
+    /// Party members of the owners at the time of the kill
+    pub party_members: HashSet<u32>,
+}
+
+impl Corpse {
+    /// Game time elapsed since the corpse was created, in milliseconds
+    fn age_ms(&self, policy: &CorpsePolicy) -> u32 {
+        policy.lifetime_ms().saturating_sub(self.item.duration.unwrap_or(0))
+    }
+
+    pub fn phase(&self, policy: &CorpsePolicy) -> CorpsePhase {
+        if self.item.duration.unwrap_or(0) == 0 {
+            CorpsePhase::Decayed
+        } else if self.age_ms(policy) < policy.protection_secs.saturating_mul(1000) {
+            CorpsePhase::Protected
+        } else {
+            CorpsePhase::Public
+        }
+    }
+
+    /// Whether `player_id` may open the corpse right now
+    pub fn can_open(&self, player_id: u32, policy: &CorpsePolicy) -> bool {
+        match self.phase(policy) {
+            CorpsePhase::Protected => {
+                self.owners.contains(&player_id)
+                    || (policy.party_loot && self.party_members.contains(&player_id))
+            }
+            CorpsePhase::Public => true,
+            CorpsePhase::Decayed => false,
+        }
+    }
+}
+
+/// Tracks corpses by item unique id
+#[derive(Debug, Default)]
+pub struct CorpseManager {
+    policy: CorpsePolicy,
+    corpses: HashMap<u32, Corpse>,
+}
+
+impl CorpseManager {
+    pub fn new(policy: CorpsePolicy) -> Self {
+        Self {
+            policy,
+            corpses: HashMap::new(),
+        }
+    }
+
+    pub fn policy(&self) -> &CorpsePolicy {
+        &self.policy
+    }
+
+    /// Place a corpse and start its decay. `owners` are the loot-eligible
+    /// contributors of the kill. Returns the corpse id.
+    pub fn add_corpse(
+        &mut self,
+        mut item: Item,
+        position: Position,
+        owners: impl IntoIterator<Item = u32>,
+        party_members: impl IntoIterator<Item = u32>,
+    ) -> u32 {
+        item.duration = Some(self.policy.lifetime_ms());
+        item.decay_state = DecayState::Started;
+        let id = item.unique_id;
+        self.corpses.insert(id, Corpse {
+            item,
+            position,
+            owners: owners.into_iter().collect(),
+            party_members: party_members.into_iter().collect(),
+        });
+        id
+    }
+
+    pub fn get(&self, corpse_id: u32) -> Option<&Corpse> {
+        self.corpses.get(&corpse_id)
+    }
+
+    pub fn phase(&self, corpse_id: u32) -> Option<CorpsePhase> {
+        self.corpses.get(&corpse_id).map(|corpse| corpse.phase(&self.policy))
+    }
+
+    /// Open a corpse for looting
+    pub fn open(&mut self, corpse_id: u32, player_id: u32) -> Result<&mut Corpse, CorpseAccessError> {
+        let corpse = self.corpses.get_mut(&corpse_id).ok_or(CorpseAccessError::NotFound)?;
+        if !corpse.can_open(player_id, &self.policy) {
+            return Err(CorpseAccessError::NotOwner);
+        }
+        Ok(corpse)
+    }
+
+    /// Advance every corpse by `elapsed` game time and remove the ones
+    /// that decayed. Returns the removed corpses.
+    pub fn decay(&mut self, elapsed: Duration) -> Vec<Corpse> {
+        let decayed: Vec<u32> = self
+            .corpses
+            .iter_mut()
+            .filter_map(|(&id, corpse)| corpse.item.advance_decay(elapsed).then_some(id))
+            .collect();
+        decayed.into_iter().filter_map(|id| self.corpses.remove(&id)).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.corpses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.corpses.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_corpse_protection_then_public_then_decay() {
+        let mut manager = CorpseManager::new(CorpsePolicy { protection_secs: 10, public_secs: 20, party_loot: true });
+        let id = manager.add_corpse(Item::new(4240), Position::new(100, 100, 7), [1], [2]);
+
+        // Protected: owners and their party may loot, others may not
+        assert_eq!(manager.phase(id), Some(CorpsePhase::Protected));
+        assert!(manager.open(id, 1).is_ok());
+        assert!(manager.open(id, 2).is_ok());
+        assert_eq!(manager.open(id, 3).unwrap_err(), CorpseAccessError::NotOwner);
+
+        assert!(manager.decay(Duration::from_secs(10)).is_empty());
+        assert_eq!(manager.phase(id), Some(CorpsePhase::Public));
+        assert!(manager.open(id, 3).is_ok());
+
+        let removed = manager.decay(Duration::from_secs(20));
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].phase(manager.policy()), CorpsePhase::Decayed);
+        assert!(manager.is_empty());
+        assert_eq!(manager.open(id, 1).unwrap_err(), CorpseAccessError::NotFound);
+    }
+
+    #[test]
+    fn test_party_loot_can_be_disabled() {
+        let mut manager = CorpseManager::new(CorpsePolicy { party_loot: false, ..Default::default() });
+        let id = manager.add_corpse(Item::new(4240), Position::new(100, 100, 7), [1], [2]);
+        assert_eq!(manager.open(id, 2).unwrap_err(), CorpseAccessError::NotOwner);
+    }
+}
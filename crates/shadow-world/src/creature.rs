@@ -1,13 +1,18 @@
 //! Creature system - players, monsters, and NPCs
 
+use crate::aggro::AggroConfig;
 use crate::item::{DamageType, SkillType};
 use crate::position::{Direction, Position};
+use crate::scaling::{AppliedScale, DifficultyScale};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+/// Slowest step duration factor; faster grounds are treated as this
+pub const MIN_GROUND_SPEED: u16 = 150;
+
 static CREATURE_ID_COUNTER: AtomicU32 = AtomicU32::new(1);
 
 fn next_creature_id() -> u32 {
@@ -339,6 +344,12 @@ pub struct Creature {
     pub resistances: HashMap<DamageType, i32>,
     pub summon_master_id: Option<u32>,
     pub summons: Vec<u32>,
+    /// Speed bonus of the mount being ridden
+    pub mount_speed: u16,
+    /// Center of the spawn the creature belongs to, for leashing
+    pub spawn_position: Option<Position>,
+    /// Dynamic difficulty scaling in effect, if any
+    pub difficulty: Option<AppliedScale>,
 }
 
 impl Creature {
@@ -366,6 +377,9 @@ impl Creature {
             resistances: HashMap::new(),
             summon_master_id: None,
             summons: Vec::new(),
+            mount_speed: 0,
+            spawn_position: None,
+            difficulty: None,
         }
     }
 
@@ -404,7 +418,7 @@ impl Creature {
 
     /// Get current speed
     pub fn get_speed(&self) -> u16 {
-        let mut speed = self.stats.base_speed;
+        let mut speed = self.stats.base_speed.saturating_add(self.mount_speed);
 
         // Apply haste/paralyze
         for condition in &self.conditions {
@@ -429,7 +443,7 @@ impl Creature {
             return 1000;
         }
 
-        let ground_speed = ground_speed.max(150) as u32;
+        let ground_speed = ground_speed.max(MIN_GROUND_SPEED) as u32;
         let duration = (1000 * ground_speed) / speed;
         duration.max(50)
     }
@@ -458,10 +472,10 @@ impl Creature {
         self.conditions.iter().find(|c| c.condition_type == condition_type)
     }
 
-    /// Apply damage
-    pub fn apply_damage(&mut self, damage: i32, damage_type: DamageType) -> i32 {
-        let resistance = self.resistances.get(&damage_type).copied().unwrap_or(0);
-        let actual_damage = (damage as f64 * (100 - resistance) as f64 / 100.0).round() as i32;
+    /// Apply damage. Elemental modifiers from `resistances` are applied by
+    /// the combat pipeline before the damage gets here.
+    pub fn apply_damage(&mut self, damage: i32, _damage_type: DamageType) -> i32 {
+        let actual_damage = damage;
 
         if self.has_condition(ConditionType::ManaShield) {
             let mana_damage = actual_damage.min(self.stats.mana);
@@ -483,6 +497,25 @@ impl Creature {
         actual_heal
     }
 
+    /// Difficulty scaling in effect
+    pub fn difficulty_scale(&self) -> DifficultyScale {
+        self.difficulty.map(|applied| applied.scale).unwrap_or_default()
+    }
+
+    /// Scale the creature's health from its unscaled maximum, keeping its
+    /// health percentage. `DifficultyScale::NONE` restores the original.
+    pub fn apply_difficulty(&mut self, scale: DifficultyScale) {
+        let base_max_health = self.difficulty.map_or(self.stats.max_health, |applied| applied.base_max_health);
+        let max_health = ((base_max_health as f32 * scale.health).round() as i32).max(1);
+        if self.stats.max_health > 0 {
+            let health = self.stats.health as i64 * max_health as i64 / self.stats.max_health as i64;
+            // A living creature never drops to 0 by shrinking
+            self.stats.health = if self.stats.health > 0 { (health as i32).max(1) } else { 0 };
+        }
+        self.stats.max_health = max_health;
+        self.difficulty = (!scale.is_none()).then_some(AppliedScale { scale, base_max_health });
+    }
+
     /// Restore mana
     pub fn restore_mana(&mut self, amount: i32) -> i32 {
         let actual_restore = amount.min(self.stats.max_mana - self.stats.mana);
@@ -505,6 +538,27 @@ impl Creature {
         self.has_condition(ConditionType::InFight)
     }
 
+    /// Make `summon` one of this creature's summons. Returns false, leaving
+    /// both untouched, when the summon cap is reached or `summon` already
+    /// has a master.
+    pub fn try_add_summon(&mut self, summon: &mut Creature, limits: &SummonLimits) -> bool {
+        let cap = if self.is_player() { limits.max_player_summons } else { limits.max_monster_summons };
+        if self.summons.len() >= cap || summon.summon_master_id.is_some() || summon.id == self.id {
+            return false;
+        }
+        summon.summon_master_id = Some(self.id);
+        summon.spawn_position = None;
+        self.summons.push(summon.id);
+        true
+    }
+
+    /// Forget a summon, e.g. when it dies or disappears
+    pub fn remove_summon(&mut self, summon_id: u32) -> bool {
+        let before = self.summons.len();
+        self.summons.retain(|&id| id != summon_id);
+        self.summons.len() != before
+    }
+
     /// Turn to direction
     pub fn turn(&mut self, direction: Direction) {
         self.direction = direction;
@@ -568,6 +622,22 @@ impl Creature {
     }
 }
 
+/// Maximum active summons per master
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SummonLimits {
+    pub max_player_summons: usize,
+    pub max_monster_summons: usize,
+}
+
+impl Default for SummonLimits {
+    fn default() -> Self {
+        Self {
+            max_player_summons: 2,
+            max_monster_summons: 4,
+        }
+    }
+}
+
 impl Clone for Creature {
     fn clone(&self) -> Self {
         Self {
@@ -593,6 +663,9 @@ impl Clone for Creature {
             resistances: self.resistances.clone(),
             summon_master_id: self.summon_master_id,
             summons: self.summons.clone(),
+            mount_speed: self.mount_speed,
+            spawn_position: self.spawn_position,
+            difficulty: self.difficulty,
         }
     }
 }
@@ -621,6 +694,8 @@ pub struct Monster {
     pub hostile: bool,
     pub static_attack_chance: u8,
     pub flee_health: i32,
+    /// How the monster senses players
+    pub aggro: AggroConfig,
     pub attacks: Vec<MonsterAttack>,
     pub defenses: Vec<MonsterDefense>,
     pub elements: HashMap<DamageType, i32>,
@@ -652,6 +727,7 @@ impl Monster {
             hostile: true,
             static_attack_chance: 95,
             flee_health: 0,
+            aggro: AggroConfig::default(),
             attacks: Vec::new(),
             defenses: Vec::new(),
             elements: HashMap::new(),
@@ -673,6 +749,7 @@ impl Monster {
         creature.stats.base_speed = self.speed;
         creature.stats.experience = self.experience;
         creature.resistances = self.elements.clone();
+        creature.spawn_position = Some(position);
         creature
     }
 }
@@ -834,4 +911,28 @@ mod tests {
         creature.remove_condition(ConditionType::Poison);
         assert!(!creature.has_condition(ConditionType::Poison));
     }
+
+    #[test]
+    fn test_summon_cap() {
+        let position = Position::new(100, 100, 7);
+        let mut master = Creature::new("Demon".to_string(), CreatureType::Monster, position);
+        let limits = SummonLimits { max_player_summons: 2, max_monster_summons: 1 };
+
+        let mut first = Creature::new("Fire Elemental".to_string(), CreatureType::Summon, position);
+        let mut second = Creature::new("Fire Elemental".to_string(), CreatureType::Summon, position);
+        assert!(master.try_add_summon(&mut first, &limits));
+        assert_eq!(first.summon_master_id, Some(master.id));
+
+        // Requests beyond the cap are ignored
+        assert!(!master.try_add_summon(&mut second, &limits));
+        assert_eq!(master.summons, vec![first.id]);
+        assert!(second.summon_master_id.is_none());
+
+        // A dead summon frees its slot
+        assert!(master.remove_summon(first.id));
+        assert!(master.try_add_summon(&mut second, &limits));
+
+        let mut druid = Creature::new("Druid".to_string(), CreatureType::Player, position);
+        assert!(!druid.try_add_summon(&mut second, &limits));
+    }
 }
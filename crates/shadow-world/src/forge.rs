@@ -469,7 +469,9 @@ impl ForgeManager {
         }
     }
 
-    /// Fuse two items of same tier to guarantee upgrade
+    /// Fuse two items of the same type and tier. Dust, cores and gold
+    /// are spent either way; on success the first item gains a tier and
+    /// the second is consumed, on failure the second item loses a tier.
     pub fn fuse(
         &mut self,
         player_id: u32,
@@ -477,6 +479,22 @@ impl ForgeManager {
         item2_id: u32,
         player_gold: u64,
     ) -> ForgeResult {
+        let roll: f32 = rand::random::<f32>() * 100.0;
+        self.fuse_with_roll(player_id, item1_id, item2_id, player_gold, roll)
+    }
+
+    fn fuse_with_roll(
+        &mut self,
+        player_id: u32,
+        item1_id: u32,
+        item2_id: u32,
+        player_gold: u64,
+        roll: f32,
+    ) -> ForgeResult {
+        if item1_id == item2_id {
+            return ForgeResult::InvalidFusion;
+        }
+
         // Get both items
         let item1 = match self.items.get(&item1_id) {
             Some(i) => i.clone(),
@@ -498,34 +516,47 @@ impl ForgeManager {
             return ForgeResult::MaxTierReached;
         }
 
-        // Calculate cost (double normal)
         let req = item1.upgrade_requirements();
-        if player_gold < req.gold * 2 {
+        let resources = self.get_resources(player_id);
+        if player_gold < req.gold || !resources.can_afford(&req) {
             return ForgeResult::InsufficientResources {
-                needed: UpgradeRequirements {
-                    dust: req.dust * 2,
-                    cores: req.cores * 2,
-                    gold: req.gold * 2,
-                    success_rate: 100.0,
-                },
-                have_dust: 0,
-                have_cores: 0,
+                needed: req,
+                have_dust: resources.dust,
+                have_cores: resources.cores,
             };
         }
 
-        // Fusion always succeeds - item2 is consumed
+        // Materials are consumed whatever the outcome
+        self.get_resources_mut(player_id).spend(&req);
+
         let item1_mut = self.items.get_mut(&item1_id).unwrap();
-        item1_mut.tier += 1;
-        let new_tier = item1_mut.tier;
+        item1_mut.fusion_attempts += 1;
+        item1_mut.last_attempt = Some(Utc::now());
+
+        if roll <= req.success_rate {
+            item1_mut.tier += 1;
+            let new_tier = item1_mut.tier;
+
+            // Remove second item
+            self.items.remove(&item2_id);
 
-        // Remove second item
-        self.items.remove(&item2_id);
+            self.record_history(player_id, item1.item_type_id, ForgeAction::Fusion, ForgeResultType::Success);
 
-        self.record_history(player_id, item1.item_type_id, ForgeAction::Fusion, ForgeResultType::Success);
+            ForgeResult::Success {
+                new_tier,
+                bonuses: TierBonuses::for_tier(new_tier),
+            }
+        } else {
+            let current_tier = item1_mut.tier;
+            let item2_mut = self.items.get_mut(&item2_id).unwrap();
+            item2_mut.tier = item2_mut.tier.saturating_sub(1);
+
+            self.record_history(player_id, item1.item_type_id, ForgeAction::Fusion, ForgeResultType::Failure);
 
-        ForgeResult::Success {
-            new_tier,
-            bonuses: TierBonuses::for_tier(new_tier),
+            ForgeResult::Failure {
+                current_tier,
+                dust_lost: req.dust,
+            }
         }
     }
 
@@ -679,6 +710,52 @@ mod tests {
         assert_eq!(resources.slivers, 50);
     }
 
+    fn fusion_pair(manager: &mut ForgeManager, tier: u8) {
+        manager.register_item(1, 2400);
+        manager.register_item(2, 2400);
+        manager.get_item_mut(1).unwrap().tier = tier;
+        manager.get_item_mut(2).unwrap().tier = tier;
+        let resources = manager.get_resources_mut(7);
+        resources.dust = 1_000;
+        resources.cores = 3;
+    }
+
+    #[test]
+    fn test_successful_fusion_raises_tier() {
+        let mut manager = ForgeManager::new();
+        fusion_pair(&mut manager, 1);
+        let req = manager.get_item(1).unwrap().upgrade_requirements();
+
+        let result = manager.fuse_with_roll(7, 1, 2, req.gold, req.success_rate);
+        assert!(matches!(result, ForgeResult::Success { new_tier: 2, .. }));
+        assert_eq!(manager.get_item(1).unwrap().tier, 2);
+        assert!(manager.get_item(2).is_none());
+
+        let resources = manager.get_resources(7);
+        assert_eq!((resources.dust, resources.cores), (1_000 - req.dust, 3 - req.cores));
+        assert_eq!(manager.get_item(1).unwrap().bonuses().attack, 2);
+    }
+
+    #[test]
+    fn test_failed_fusion_consumes_materials() {
+        let mut manager = ForgeManager::new();
+        fusion_pair(&mut manager, 1);
+        let req = manager.get_item(1).unwrap().upgrade_requirements();
+
+        let result = manager.fuse_with_roll(7, 1, 2, req.gold, req.success_rate + 1.0);
+        assert!(matches!(result, ForgeResult::Failure { current_tier: 1, .. }));
+        assert_eq!(manager.get_item(1).unwrap().tier, 1);
+        assert_eq!(manager.get_item(2).unwrap().tier, 0);
+
+        let resources = manager.get_resources(7);
+        assert_eq!((resources.dust, resources.cores), (1_000 - req.dust, 3 - req.cores));
+
+        // Items of different tiers can't be fused, and nothing is spent
+        let result = manager.fuse_with_roll(7, 1, 2, req.gold, 0.0);
+        assert!(matches!(result, ForgeResult::InvalidFusion));
+        assert_eq!(manager.get_resources(7).dust, 1_000 - req.dust);
+    }
+
     #[test]
     fn test_forge_manager() {
         let mut manager = ForgeManager::new();
@@ -7,6 +7,7 @@ use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::info;
+use uuid::Uuid;
 
 /// House access levels
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -17,6 +18,49 @@ pub enum HouseAccessLevel {
     Owner = 3,
 }
 
+/// How free houses change hands on a realm
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HouseAcquisitionMode {
+    /// Players bid and the highest bidder wins when the auction ends
+    #[default]
+    Auction,
+    /// Players buy a free house at its fixed price
+    InstantBuy,
+}
+
+/// Why a house cannot be bought
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HousePurchaseError {
+    HouseNotFound,
+    AuctionOnly,
+    AlreadyOwned,
+    InAuction,
+    InsufficientFunds,
+    /// Guildhalls can only be bought by a guild
+    GuildhallOnly,
+    /// The house has no price
+    NotForSale,
+}
+
+impl std::fmt::Display for HousePurchaseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HousePurchaseError::HouseNotFound => write!(f, "House not found"),
+            HousePurchaseError::AuctionOnly => write!(f, "Houses on this realm are sold by auction"),
+            HousePurchaseError::AlreadyOwned => write!(f, "This house already has an owner"),
+            HousePurchaseError::InAuction => write!(f, "This house is being auctioned"),
+            HousePurchaseError::InsufficientFunds => write!(f, "You do not have enough gold in your bank account"),
+            HousePurchaseError::GuildhallOnly => write!(f, "Only a guild can buy a guildhall"),
+            HousePurchaseError::NotForSale => write!(f, "This house is not for sale"),
+        }
+    }
+}
+
+impl std::error::Error for HousePurchaseError {}
+
+/// Months of rent a house costs when its price is derived
+const PRICE_IN_MONTHS_OF_RENT: u64 = 12;
+
 /// House definition
 #[derive(Debug, Clone)]
 pub struct House {
@@ -25,6 +69,8 @@ pub struct House {
     pub owner_id: Option<u32>,
     pub paid_until: Option<i64>,
     pub rent: u64,
+    /// Fixed price when houses are bought instantly
+    pub price: u64,
     pub size: u32,
     pub beds: u8,
     pub town_id: u32,
@@ -36,6 +82,9 @@ pub struct House {
     pub sub_owner_list: String,
     pub guild_id: Option<u32>,
     pub guild_rank: Option<u32>,
+    /// Guildhalls are owned by a guild instead of a player
+    pub guildhall: bool,
+    pub owner_guild: Option<Uuid>,
     pub transfer_to: Option<u32>,
     pub transfer_price: Option<u64>,
     pub bid_end: Option<i64>,
@@ -51,6 +100,7 @@ impl House {
             owner_id: None,
             paid_until: None,
             rent: 0,
+            price: 0,
             size: 0,
             beds: 0,
             town_id: 0,
@@ -62,6 +112,8 @@ impl House {
             sub_owner_list: String::new(),
             guild_id: None,
             guild_rank: None,
+            guildhall: false,
+            owner_guild: None,
             transfer_to: None,
             transfer_price: None,
             bid_end: None,
@@ -72,7 +124,7 @@ impl House {
 
     /// Check if house has an owner
     pub fn has_owner(&self) -> bool {
-        self.owner_id.is_some()
+        self.owner_id.is_some() || self.owner_guild.is_some()
     }
 
     /// Check if player owns the house
@@ -141,6 +193,22 @@ impl House {
         self.doors.iter_mut().find(|d| d.position == *pos)
     }
 
+    /// Resolve whether a player may open the door at `pos`.
+    ///
+    /// Owner > sub-owner > guest > door list, denying by default. Guests only
+    /// pass unrestricted doors; restricted doors require the door's own list.
+    pub fn can_open_door(&self, pos: &Position, player_id: u32) -> bool {
+        let Some(door) = self.get_door(pos) else {
+            return false;
+        };
+
+        match self.get_access_level(player_id) {
+            HouseAccessLevel::Owner | HouseAccessLevel::SubOwner => true,
+            HouseAccessLevel::Guest if !door.restricted => true,
+            _ => door.has_access(player_id),
+        }
+    }
+
     /// Check if house is up for auction
     pub fn is_auction(&self) -> bool {
         self.bid_end.is_some()
@@ -151,6 +219,19 @@ impl House {
         self.transfer_to.is_some()
     }
 
+    /// Price of a house loaded without one: a year of its rent, or of the
+    /// rent its size and beds would cost. Zero for a house with neither.
+    pub fn derived_price(&self) -> u64 {
+        let rent = if self.rent > 0 {
+            self.rent
+        } else if self.size > 0 {
+            Self::calculate_rent(self.size, self.beds)
+        } else {
+            0
+        };
+        rent.saturating_mul(PRICE_IN_MONTHS_OF_RENT)
+    }
+
     /// Calculate rent based on size
     pub fn calculate_rent(size: u32, beds: u8) -> u64 {
         // Base rent + per sqm + per bed
@@ -168,6 +249,8 @@ pub struct HouseDoor {
     pub door_id: u8,
     pub access_list: HashSet<u32>,
     pub locked: bool,
+    /// Restricted doors ignore the house guest list and only admit their own list
+    pub restricted: bool,
 }
 
 impl HouseDoor {
@@ -177,6 +260,14 @@ impl HouseDoor {
             door_id,
             access_list: HashSet::new(),
             locked: true,
+            restricted: false,
+        }
+    }
+
+    pub fn restricted(position: Position, door_id: u8) -> Self {
+        Self {
+            restricted: true,
+            ..Self::new(position, door_id)
         }
     }
 
@@ -208,7 +299,10 @@ impl HouseManager {
     }
 
     /// Add a house
-    pub fn add_house(&mut self, house: House) {
+    pub fn add_house(&mut self, mut house: House) {
+        if house.price == 0 {
+            house.price = house.derived_price();
+        }
         let id = house.id;
         for pos in &house.tiles {
             self.position_map.insert(*pos, id);
@@ -239,6 +333,22 @@ impl HouseManager {
         id.and_then(move |id| self.houses.get_mut(&id))
     }
 
+    /// Check whether a player may open the house door at a position.
+    /// Returns None if there is no house door there.
+    pub fn can_open_door(&self, pos: &Position, player_id: u32) -> Option<bool> {
+        self.houses
+            .values()
+            .find(|h| h.get_door(pos).is_some())
+            .map(|h| h.can_open_door(pos, player_id))
+    }
+
+    /// Check whether a player may step onto a position. Non-house tiles are open.
+    pub fn can_enter(&self, pos: &Position, player_id: u32) -> bool {
+        self.get_at_position(pos)
+            .map(|h| h.has_access(player_id))
+            .unwrap_or(true)
+    }
+
     /// Get houses owned by player
     pub fn get_player_houses(&self, player_id: u32) -> Vec<&House> {
         self.houses
@@ -259,7 +369,7 @@ impl HouseManager {
     pub fn get_available_houses(&self) -> Vec<&House> {
         self.houses
             .values()
-            .filter(|h| h.owner_id.is_none())
+            .filter(|h| !h.has_owner())
             .collect()
     }
 
@@ -286,10 +396,47 @@ impl HouseManager {
         }
     }
 
+    /// Buy a free house at its fixed price. `debit` takes the price from
+    /// the buyer's bank account and returns false if they cannot afford it;
+    /// ownership only changes after a successful debit.
+    pub fn instant_buy(
+        &mut self,
+        house_id: u32,
+        buyer_id: u32,
+        mode: HouseAcquisitionMode,
+        debit: impl FnOnce(u64) -> bool,
+    ) -> std::result::Result<u64, HousePurchaseError> {
+        if mode != HouseAcquisitionMode::InstantBuy {
+            return Err(HousePurchaseError::AuctionOnly);
+        }
+        let house = self.houses.get(&house_id).ok_or(HousePurchaseError::HouseNotFound)?;
+        if house.guildhall {
+            return Err(HousePurchaseError::GuildhallOnly);
+        }
+        if house.has_owner() {
+            return Err(HousePurchaseError::AlreadyOwned);
+        }
+        if house.is_auction() {
+            return Err(HousePurchaseError::InAuction);
+        }
+
+        let price = house.price;
+        if price == 0 {
+            return Err(HousePurchaseError::NotForSale);
+        }
+        if !debit(price) {
+            return Err(HousePurchaseError::InsufficientFunds);
+        }
+        self.transfer_ownership(house_id, buyer_id);
+        info!("House {} bought by player {} for {} gold", house_id, buyer_id, price);
+        Ok(price)
+    }
+
     /// Remove house ownership
     pub fn remove_ownership(&mut self, house_id: u32) -> bool {
         if let Some(house) = self.houses.get_mut(&house_id) {
             house.owner_id = None;
+            house.owner_guild = None;
             house.paid_until = None;
             house.access_list.clear();
             house.guest_list.clear();
@@ -364,4 +511,78 @@ mod tests {
         assert!(house.contains_position(&pos));
         assert_eq!(house.size, 1);
     }
+
+    #[test]
+    fn test_door_access_resolution() {
+        let mut house = House::new(1, "Test House".to_string());
+        house.owner_id = Some(100);
+        house.set_access(200, HouseAccessLevel::Guest);
+        house.set_access(300, HouseAccessLevel::SubOwner);
+
+        let main_door = Position::new(100, 100, 7);
+        let vault_door = Position::new(102, 100, 7);
+        house.add_door(HouseDoor::new(main_door, 1));
+        let mut vault = HouseDoor::restricted(vault_door, 2);
+        vault.grant_access(400);
+        house.add_door(vault);
+
+        // Guest passes the main door but not the restricted one
+        assert!(house.can_open_door(&main_door, 200));
+        assert!(!house.can_open_door(&vault_door, 200));
+
+        // Owner and sub-owner pass every door
+        assert!(house.can_open_door(&vault_door, 100));
+        assert!(house.can_open_door(&vault_door, 300));
+
+        // Door list grants a specific door only; strangers are denied
+        assert!(house.can_open_door(&vault_door, 400));
+        assert!(!house.can_open_door(&main_door, 400));
+        assert!(!house.can_open_door(&main_door, 500));
+
+        let mut manager = HouseManager::new();
+        house.add_tile(Position::new(101, 100, 7));
+        manager.add_house(house);
+        assert_eq!(manager.can_open_door(&vault_door, 200), Some(false));
+        assert_eq!(manager.can_open_door(&Position::new(1, 1, 7), 200), None);
+        assert!(manager.can_enter(&Position::new(101, 100, 7), 200));
+        assert!(!manager.can_enter(&Position::new(101, 100, 7), 500));
+    }
+
+    #[test]
+    fn test_instant_buy_requires_mode() {
+        let mut manager = HouseManager::new();
+        let mut house = House::new(1, "Test House".to_string());
+        house.price = 50_000;
+        manager.add_house(house);
+
+        let result = manager.instant_buy(1, 100, HouseAcquisitionMode::Auction, |_| true);
+        assert_eq!(result, Err(HousePurchaseError::AuctionOnly));
+
+        let result = manager.instant_buy(1, 100, HouseAcquisitionMode::InstantBuy, |_| false);
+        assert_eq!(result, Err(HousePurchaseError::InsufficientFunds));
+        assert!(!manager.get(1).unwrap().has_owner());
+    }
+
+    #[test]
+    fn test_instant_buy_price_derived_on_load() {
+        let mut manager = HouseManager::new();
+        let mut house = House::new(1, "Test House".to_string());
+        house.add_tile(Position::new(100, 100, 7));
+        house.add_tile(Position::new(101, 100, 7));
+        house.beds = 1;
+        manager.add_house(house);
+        let mut rented = House::new(2, "Rented House".to_string());
+        rented.rent = 1_000;
+        manager.add_house(rented);
+        // Nothing to price it by
+        manager.add_house(House::new(3, "Empty House".to_string()));
+
+        assert_eq!(manager.get(1).unwrap().price, (500 + 2 * 10 + 100) * 12);
+        assert_eq!(manager.get(2).unwrap().price, 12_000);
+
+        let result = manager.instant_buy(3, 100, HouseAcquisitionMode::InstantBuy, |_| panic!("charged for a house without a price"));
+        assert_eq!(result, Err(HousePurchaseError::NotForSale));
+        assert!(!manager.get(3).unwrap().has_owner());
+        assert_eq!(manager.instant_buy(2, 100, HouseAcquisitionMode::InstantBuy, |_| true), Ok(12_000));
+    }
 }
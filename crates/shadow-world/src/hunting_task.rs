@@ -655,6 +655,18 @@ impl TaskManager {
             .collect()
     }
 
+    /// Record a kill for every player credited by the kill credit tracker
+    pub fn record_credited_kill(&mut self, credited: &[u32], race_id: u16) -> Vec<(u32, TaskCompletionEvent)> {
+        credited
+            .iter()
+            .flat_map(|&player_id| {
+                self.record_kill(player_id, race_id)
+                    .into_iter()
+                    .map(move |event| (player_id, event))
+            })
+            .collect()
+    }
+
     /// Complete task and claim rewards
     pub fn complete_task(&mut self, player_id: u32, task_id: u32) -> Option<TaskRewards> {
         let task = self.tasks.get(&task_id)?.clone();
@@ -11,6 +11,8 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use chrono::{DateTime, Utc};
 
+use crate::item::{SlotType, WeaponType};
+
 /// Imbuement tiers - each tier is more powerful and expensive
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ImbuementTier {
@@ -23,6 +25,9 @@ pub enum ImbuementTier {
 }
 
 impl ImbuementTier {
+    /// All tiers, cheapest first
+    pub const ALL: [ImbuementTier; 3] = [Self::Basic, Self::Intricate, Self::Powerful];
+
     /// Get tier multiplier for effects
     pub fn effect_multiplier(&self) -> f32 {
         match self {
@@ -149,6 +154,35 @@ pub enum ImbuementType {
 }
 
 impl ImbuementType {
+    /// All imbuement types
+    pub const ALL: [ImbuementType; 25] = [
+        Self::Scorch,
+        Self::Frost,
+        Self::Electrify,
+        Self::Venom,
+        Self::Reap,
+        Self::LichShroud,
+        Self::SnakeSkin,
+        Self::CloudFabric,
+        Self::QuaraScale,
+        Self::DragonHide,
+        Self::DemonPresence,
+        Self::Swiftness,
+        Self::Vampirism,
+        Self::Void,
+        Self::Strike,
+        Self::Slash,
+        Self::Chop,
+        Self::Bash,
+        Self::Precision,
+        Self::Epiphany,
+        Self::Blockade,
+        Self::Featherweight,
+        Self::SwiftnessBoots,
+        Self::FeatherweightBackpack,
+        Self::Vibrancy,
+    ];
+
     /// Get the category of this imbuement
     pub fn category(&self) -> ImbuementCategory {
         match self {
@@ -169,6 +203,19 @@ impl ImbuementType {
         }
     }
 
+    /// Weapon whose skill a skill boost raises
+    pub fn boosted_weapon(&self) -> Option<WeaponType> {
+        match self {
+            Self::Slash => Some(WeaponType::Sword),
+            Self::Chop => Some(WeaponType::Axe),
+            Self::Bash => Some(WeaponType::Club),
+            Self::Precision => Some(WeaponType::Distance),
+            Self::Epiphany => Some(WeaponType::Wand),
+            Self::Blockade => Some(WeaponType::Shield),
+            _ => None,
+        }
+    }
+
     /// Get the base effect value at Basic tier
     pub fn base_effect_value(&self) -> i32 {
         match self {
@@ -352,6 +399,50 @@ impl ImbuementSlotType {
             ],
         }
     }
+
+    /// Imbuement slot type of an item, from its equipment slot and weapon type
+    pub fn for_item(slot: Option<SlotType>, weapon: Option<WeaponType>) -> Option<Self> {
+        match weapon {
+            Some(WeaponType::Shield) => return Some(Self::Shield),
+            Some(WeaponType::Ammunition) => return None,
+            Some(_) => return Some(Self::Weapon),
+            None => {}
+        }
+        match slot? {
+            SlotType::Head => Some(Self::Helmet),
+            SlotType::Armor => Some(Self::Armor),
+            SlotType::Feet => Some(Self::Boots),
+            SlotType::Backpack => Some(Self::Backpack),
+            _ => None,
+        }
+    }
+
+    /// Slots an item has when its item type does not set a count
+    pub fn default_slot_count(&self) -> u8 {
+        match self {
+            Self::Weapon => 3,
+            Self::Armor => 2,
+            Self::Helmet | Self::Shield | Self::Boots | Self::Backpack => 1,
+        }
+    }
+
+    /// Imbuements this slot accepts. On weapons and shields a skill boost
+    /// must raise the skill the item is used with.
+    pub fn eligible_imbuements(&self, weapon: Option<WeaponType>) -> Vec<ImbuementType> {
+        let categories = self.allowed_categories();
+        ImbuementType::ALL
+            .into_iter()
+            .filter(|imbuement| categories.contains(&imbuement.category()))
+            .filter(|imbuement| {
+                imbuement.category() != ImbuementCategory::SkillBoost
+                    || match self {
+                        Self::Weapon => weapon.is_some() && imbuement.boosted_weapon() == weapon,
+                        Self::Shield => *imbuement == ImbuementType::Blockade,
+                        _ => true,
+                    }
+            })
+            .collect()
+    }
 }
 
 /// Imbuement shrine location
@@ -540,4 +631,26 @@ mod tests {
 
         assert!(matches!(result, ImbuementResult::Success(_)));
     }
+
+    #[test]
+    fn test_eligible_imbuements_follow_item_type() {
+        let sword = ImbuementSlotType::for_item(Some(SlotType::Right), Some(WeaponType::Sword)).unwrap();
+        assert_eq!(sword, ImbuementSlotType::Weapon);
+        let eligible = sword.eligible_imbuements(Some(WeaponType::Sword));
+        for imbuement in [ImbuementType::Scorch, ImbuementType::Vampirism, ImbuementType::Strike, ImbuementType::Slash] {
+            assert!(eligible.contains(&imbuement));
+        }
+        assert!(!eligible.contains(&ImbuementType::Chop));
+        assert!(!eligible.contains(&ImbuementType::LichShroud));
+
+        let shield = ImbuementSlotType::for_item(Some(SlotType::Left), Some(WeaponType::Shield)).unwrap();
+        let eligible = shield.eligible_imbuements(Some(WeaponType::Shield));
+        assert!(eligible.contains(&ImbuementType::Blockade) && eligible.contains(&ImbuementType::DragonHide));
+        assert!(!eligible.contains(&ImbuementType::Epiphany));
+
+        let boots = ImbuementSlotType::for_item(Some(SlotType::Feet), None).unwrap();
+        assert_eq!(boots.eligible_imbuements(None), vec![ImbuementType::SwiftnessBoots, ImbuementType::Vibrancy]);
+        assert_eq!(ImbuementSlotType::for_item(Some(SlotType::Ring), None), None);
+        assert_eq!(ImbuementSlotType::for_item(Some(SlotType::Ammo), Some(WeaponType::Ammunition)), None);
+    }
 }
@@ -149,6 +149,35 @@ impl Item {
         self.attributes.get(key)
     }
 
+    /// Combat stat of this instance: a rolled value stored on the item,
+    /// otherwise the item type's value
+    pub fn stat(&self, stat: ItemStat) -> Option<i32> {
+        match self.attributes.get(stat.key()) {
+            Some(ItemAttribute::Integer(value)) => Some(*value as i32),
+            _ => self.get_type().and_then(|item_type| stat.of_type(item_type)),
+        }
+    }
+
+    /// Store a rolled combat stat on this instance
+    pub fn set_stat(&mut self, stat: ItemStat, value: i32) {
+        self.set_attribute(stat.key(), ItemAttribute::Integer(value as i64));
+    }
+
+    /// Advance a started decay by `elapsed` game time. Returns true once
+    /// the remaining duration (in milliseconds) has run out.
+    pub fn advance_decay(&mut self, elapsed: std::time::Duration) -> bool {
+        if self.decay_state != DecayState::Started {
+            return false;
+        }
+        let Some(remaining) = self.duration else {
+            return false;
+        };
+        let elapsed_ms = u32::try_from(elapsed.as_millis()).unwrap_or(u32::MAX);
+        let remaining = remaining.saturating_sub(elapsed_ms);
+        self.duration = Some(remaining);
+        remaining == 0
+    }
+
     /// Check if item can be stacked with another
     pub fn can_stack_with(&self, other: &Item) -> bool {
         self.item_type_id == other.item_type_id
@@ -166,6 +195,37 @@ pub enum ItemAttribute {
     Boolean(bool),
 }
 
+/// Combat stats an item instance can override, e.g. with a loot roll
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ItemStat {
+    Attack,
+    Defense,
+    ExtraDefense,
+    Armor,
+}
+
+impl ItemStat {
+    /// Attribute key the stat is stored under
+    pub fn key(&self) -> &'static str {
+        match self {
+            ItemStat::Attack => "attack",
+            ItemStat::Defense => "defense",
+            ItemStat::ExtraDefense => "extradefense",
+            ItemStat::Armor => "armor",
+        }
+    }
+
+    /// Value of the stat on the item type
+    pub fn of_type(&self, item_type: &ItemType) -> Option<i32> {
+        match self {
+            ItemStat::Attack => item_type.attack,
+            ItemStat::Defense => item_type.defense,
+            ItemStat::ExtraDefense => item_type.extra_defense,
+            ItemStat::Armor => item_type.armor,
+        }
+    }
+}
+
 /// Item decay state
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DecayState {
@@ -203,6 +263,8 @@ pub struct ItemType {
     pub max_write_once_length: Option<u16>,
     pub light_level: Option<u8>,
     pub light_color: Option<u8>,
+    /// Automap color index (0-215)
+    pub minimap_color: Option<u16>,
     pub level_requirement: Option<u16>,
     pub vocation_mask: Option<u32>,
     pub speed_boost: Option<i32>,
@@ -252,6 +314,7 @@ impl ItemType {
             max_write_once_length: None,
             light_level: None,
             light_color: None,
+            minimap_color: None,
             level_requirement: None,
             vocation_mask: None,
             speed_boost: None,
@@ -337,7 +400,7 @@ pub enum ItemGroup {
 }
 
 /// Equipment slot types
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum SlotType {
     Head,
     Necklace,
@@ -552,6 +615,11 @@ impl ItemLoader {
     pub fn all(&self) -> &HashMap<u16, ItemType> {
         &self.items
     }
+
+    /// Add an item type
+    pub fn add(&mut self, item_type: ItemType) {
+        self.items.insert(item_type.id, item_type);
+    }
 }
 
 // Global item types registry (populated at server start)
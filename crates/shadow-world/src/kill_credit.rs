@@ -0,0 +1,188 @@
+//! Kill credit attribution
+//!
+//! Decides which players a monster kill counts for. Tasks, bestiary, prey
+//! and kill statistics all ask the same tracker so a kill is never
+//! credited differently by different systems. Damage dealt by a summon is
+//! recorded for its master, and with party sharing enabled the members of
+//! a credited player's party are credited as well.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::creature::Creature;
+
+/// Who is credited for a kill
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KillCreditPolicy {
+    /// Only the player who dealt the most damage
+    TopDamage,
+    /// Only the player who landed the killing blow
+    LastHit,
+    /// Every player who dealt enough damage
+    AllContributors,
+}
+
+/// Kill credit configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KillCreditConfig {
+    pub policy: KillCreditPolicy,
+    /// Credit the party members of every credited player
+    pub share_with_party: bool,
+    /// Share of the total damage a contributor needs (0.0 - 1.0)
+    pub min_damage_share: f32,
+}
+
+impl Default for KillCreditConfig {
+    fn default() -> Self {
+        Self {
+            policy: KillCreditPolicy::AllContributors,
+            share_with_party: true,
+            min_damage_share: 0.0,
+        }
+    }
+}
+
+/// Damage dealt to one creature, by credited owner
+#[derive(Debug, Clone, Default)]
+pub struct KillCreditTracker {
+    /// Owner creature id -> damage, in order of first hit
+    damage: Vec<(u32, u64)>,
+    index: HashMap<u32, usize>,
+    last_hit: Option<u32>,
+}
+
+impl KillCreditTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record damage from `attacker`; summon damage counts for the master
+    pub fn record_damage(&mut self, attacker: &Creature, damage: u32) {
+        self.record_damage_by(attacker.summon_master_id.unwrap_or(attacker.id), damage);
+    }
+
+    /// Record damage for an owner id directly
+    pub fn record_damage_by(&mut self, owner_id: u32, damage: u32) {
+        match self.index.get(&owner_id) {
+            Some(&i) => self.damage[i].1 += damage as u64,
+            None => {
+                self.index.insert(owner_id, self.damage.len());
+                self.damage.push((owner_id, damage as u64));
+            }
+        }
+        self.last_hit = Some(owner_id);
+    }
+
+    /// Damage recorded for an owner
+    pub fn damage_by(&self, owner_id: u32) -> u64 {
+        self.index.get(&owner_id).map(|&i| self.damage[i].1).unwrap_or(0)
+    }
+
+    /// Damage per owner, in order of first hit
+    pub fn contributors(&self) -> &[(u32, u64)] {
+        &self.damage
+    }
+
+    /// Owner who dealt the most damage; ties go to the earlier attacker
+    pub fn top_damage(&self) -> Option<u32> {
+        self.damage
+            .iter()
+            .fold(None, |best: Option<(u32, u64)>, &(id, dmg)| match best {
+                Some((_, top)) if top >= dmg => best,
+                _ => Some((id, dmg)),
+            })
+            .map(|(id, _)| id)
+    }
+
+    /// Owner of the last recorded hit
+    pub fn last_hit(&self) -> Option<u32> {
+        self.last_hit
+    }
+
+    /// Players credited for the kill. `party_members` returns the party
+    /// members of a player eligible for shared credit (e.g. in range).
+    pub fn credited(&self, config: &KillCreditConfig, party_members: impl Fn(u32) -> Vec<u32>) -> Vec<u32> {
+        let total: u64 = self.damage.iter().map(|(_, d)| d).sum();
+        let min_damage = (total as f64 * config.min_damage_share.clamp(0.0, 1.0) as f64).ceil() as u64;
+
+        let base: Vec<u32> = match config.policy {
+            KillCreditPolicy::TopDamage => self.top_damage().into_iter().collect(),
+            KillCreditPolicy::LastHit => self.last_hit.into_iter().collect(),
+            KillCreditPolicy::AllContributors => self
+                .damage
+                .iter()
+                .filter(|(_, d)| *d > 0 && *d >= min_damage)
+                .map(|(id, _)| *id)
+                .collect(),
+        };
+
+        let mut credited = Vec::new();
+        for id in base {
+            let shared = if config.share_with_party { party_members(id) } else { Vec::new() };
+            for member in std::iter::once(id).chain(shared) {
+                if !credited.contains(&member) {
+                    credited.push(member);
+                }
+            }
+        }
+        credited
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::creature::CreatureType;
+    use crate::position::Position;
+
+    fn player(name: &str) -> Creature {
+        Creature::new(name.to_string(), CreatureType::Player, Position::new(100, 100, 7))
+    }
+
+    #[test]
+    fn test_party_shared_credit() {
+        let (knight, druid, outsider) = (player("Knight"), player("Druid"), player("Outsider"));
+        let party = [knight.id, druid.id];
+        let party_members = |id: u32| if party.contains(&id) { party.to_vec() } else { Vec::new() };
+
+        let mut tracker = KillCreditTracker::new();
+        tracker.record_damage(&knight, 900);
+        tracker.record_damage(&outsider, 50);
+
+        // The druid only healed but shares the knight's credit
+        let top = KillCreditConfig { policy: KillCreditPolicy::TopDamage, ..Default::default() };
+        assert_eq!(tracker.credited(&top, party_members), vec![knight.id, druid.id]);
+
+        let solo = KillCreditConfig { share_with_party: false, ..top.clone() };
+        assert_eq!(tracker.credited(&solo, party_members), vec![knight.id]);
+
+        let all = KillCreditConfig::default();
+        assert_eq!(tracker.credited(&all, party_members), vec![knight.id, druid.id, outsider.id]);
+
+        let significant = KillCreditConfig { min_damage_share: 0.1, ..Default::default() };
+        assert_eq!(tracker.credited(&significant, party_members), vec![knight.id, druid.id]);
+
+        let last = KillCreditConfig { policy: KillCreditPolicy::LastHit, ..Default::default() };
+        assert_eq!(tracker.credited(&last, party_members), vec![outsider.id]);
+    }
+
+    #[test]
+    fn test_summon_kill_credited_to_master() {
+        let sorcerer = player("Sorcerer");
+        let mut summon = Creature::new("Fire Elemental".to_string(), CreatureType::Summon, sorcerer.position);
+        summon.summon_master_id = Some(sorcerer.id);
+
+        let mut tracker = KillCreditTracker::new();
+        tracker.record_damage(&sorcerer, 100);
+        tracker.record_damage(&summon, 300);
+
+        assert_eq!(tracker.damage_by(sorcerer.id), 400);
+        assert_eq!(tracker.damage_by(summon.id), 0);
+
+        let no_party = |_| Vec::new();
+        for policy in [KillCreditPolicy::TopDamage, KillCreditPolicy::LastHit, KillCreditPolicy::AllContributors] {
+            let config = KillCreditConfig { policy, ..Default::default() };
+            assert_eq!(tracker.credited(&config, no_party), vec![sorcerer.id]);
+        }
+    }
+}
@@ -3,40 +3,62 @@
 //! Handles all world-related systems: maps, tiles, creatures, items,
 //! spawns, pathfinding, and spatial queries.
 
+pub mod access;
 pub mod actions;
+pub mod aggro;
+pub mod clock;
+pub mod corpse;
 pub mod creature;
 pub mod forge;
 pub mod house;
 pub mod hunting_task;
 pub mod imbuement;
 pub mod item;
+pub mod kill_credit;
 pub mod map;
+pub mod minimap;
+pub mod mount;
 pub mod npc;
 pub mod otb;
 pub mod otbm;
 pub mod pathfinding;
 pub mod position;
+pub mod push;
+pub mod raid;
+pub mod scaling;
 pub mod spawn;
+pub mod spawn_loader;
 pub mod store;
 pub mod tile;
 pub mod town;
 
 // Re-exports
-pub use actions::{ItemActionRegistry, ItemActionHandler, ItemActionResult, ItemActionContext};
-pub use creature::{Creature, CreatureType, Monster, MonsterLoader};
+pub use access::{quest_completed_key, quest_stage_key, AccessContext, AccessDenied, AccessGate, AccessRequirement};
+pub use actions::{ItemActionRegistry, ItemActionHandler, ItemActionResult, ItemActionContext, ItemExhaust, ItemExhaustConfig, ItemUseCategory};
+pub use aggro::AggroConfig;
+pub use clock::{GameClock, MAX_TIME_SCALE};
+pub use corpse::{Corpse, CorpseAccessError, CorpseManager, CorpsePhase, CorpsePolicy};
+pub use creature::{Creature, CreatureType, Monster, MonsterLoader, SummonLimits};
 pub use forge::{ForgeManager, ForgeableItem, ForgeClassification, ForgeResult, TierBonuses};
-pub use house::{House, HouseManager};
+pub use house::{House, HouseAcquisitionMode, HouseManager, HousePurchaseError};
 pub use hunting_task::{TaskManager, HuntingTask, TaskDifficulty, TaskRank, PlayerTaskProgress};
-pub use imbuement::{ImbuementManager, ImbuementType, ImbuementTier, ActiveImbuement};
+pub use imbuement::{ImbuementManager, ImbuementSlotType, ImbuementType, ImbuementTier, ActiveImbuement};
 pub use item::{Item, ItemLoader, ItemType};
+pub use kill_credit::{KillCreditConfig, KillCreditPolicy, KillCreditTracker};
 pub use map::{Map, MapLayer};
+pub use minimap::{MinimapColors, MinimapImage};
+pub use mount::{MountAbility, MountError, MountRegistry, MountStamina, MountType};
 pub use npc::{Npc, NpcLoader};
 pub use otb::OtbLoader;
 pub use otbm::OtbmLoader;
 pub use pathfinding::{Pathfinder, PathResult};
 pub use position::{Direction, Position};
-pub use spawn::{SpawnManager, SpawnPoint};
-pub use store::{StoreManager, StoreOffer, StoreCategory, CoinBalance, PurchaseResult};
+pub use push::{PushError, PushResolver, PushRules, PushTargets};
+pub use raid::{RaidAction, RaidDefinition, RaidError, RaidManager, RaidOutcome, RaidRecovery, RaidSpawn, RaidState, RaidTrigger, RaidWave};
+pub use scaling::{AppliedScale, DifficultyScale, DifficultyScalingConfig, ScalingBasis};
+pub use spawn::{BoostArea, SpawnBoost, SpawnManager, SpawnPoint};
+pub use spawn_loader::{SpawnIssue, SpawnIssueSeverity, SpawnLoadReport, SpawnLoader, SpawnValidationConfig};
+pub use store::{StoreManager, StoreOffer, StoreCategory, CoinBalance, PurchaseResult, OutfitPreview, OutfitPreviewError};
 pub use tile::{SharedTile, Tile, TileFlags};
 pub use town::{Town, TownManager};
 
@@ -75,6 +97,9 @@ pub enum WorldError {
 
     #[error("XML parse error: {0}")]
     XmlParse(String),
+
+    #[error("You are exhausted ({0}ms remaining)")]
+    Exhausted(u64),
 }
 
 /// World dimensions
@@ -1,10 +1,11 @@
 //! Map management - handles the game world grid and sectors
 
+use crate::access::{AccessContext, AccessDenied, AccessGate};
 use crate::item::Item;
 use crate::position::{Direction, Position};
 use crate::tile::{SharedTile, Tile, TileFlags};
 use crate::{Result, WorldError};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
@@ -166,6 +167,8 @@ pub struct Map {
     waypoints: HashMap<String, Position>,
     /// House tiles (position -> house_id)
     house_tiles: HashMap<Position, u32>,
+    /// Access gates on tiles and teleports
+    access_gates: HashMap<Position, AccessGate>,
 }
 
 impl Map {
@@ -186,6 +189,7 @@ impl Map {
             layers,
             waypoints: HashMap::new(),
             house_tiles: HashMap::new(),
+            access_gates: HashMap::new(),
         }
     }
 
@@ -426,6 +430,38 @@ impl Map {
             .collect()
     }
 
+    /// Attach an access gate to a tile or teleport
+    pub fn set_access_gate(&mut self, pos: Position, gate: AccessGate) {
+        self.access_gates.insert(pos, gate);
+    }
+
+    /// Remove the access gate from a position
+    pub fn remove_access_gate(&mut self, pos: &Position) -> Option<AccessGate> {
+        self.access_gates.remove(pos)
+    }
+
+    /// Get the access gate at a position
+    pub fn get_access_gate(&self, pos: &Position) -> Option<&AccessGate> {
+        self.access_gates.get(pos)
+    }
+
+    /// Quests any access gate asks about, to load from character storage
+    pub fn gated_quest_ids(&self) -> HashSet<String> {
+        self.access_gates
+            .values()
+            .flat_map(|gate| gate.requirements.iter())
+            .filter_map(|requirement| requirement.quest_id().map(str::to_string))
+            .collect()
+    }
+
+    /// Check whether a player may enter a position
+    pub fn check_access(&self, pos: &Position, ctx: &AccessContext) -> std::result::Result<(), AccessDenied> {
+        match self.access_gates.get(pos) {
+            Some(gate) => gate.check(ctx),
+            None => Ok(()),
+        }
+    }
+
     /// Get layer for a floor
     pub fn get_layer(&self, floor: u8) -> Option<&MapLayer> {
         self.layers.get(floor as usize)
@@ -500,6 +536,7 @@ impl Map {
         }
         self.waypoints.clear();
         self.house_tiles.clear();
+        self.access_gates.clear();
     }
 
     /// Log map statistics
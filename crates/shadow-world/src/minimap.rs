@@ -0,0 +1,274 @@
+//! Minimap/automap export
+//!
+//! Renders a floor of the map as an image using each tile's ground item
+//! minimap color, the same 216-color palette the client automap uses.
+//! Tiles that do not exist or whose ground has no minimap color stay
+//! transparent, so the exported PNGs can be layered over each other.
+
+use std::collections::HashMap;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+use crate::item::ItemType;
+use crate::map::{Map, SECTOR_SIZE};
+use crate::{Result, WorldError};
+
+/// Minimap color index of each item type
+#[derive(Debug, Clone, Default)]
+pub struct MinimapColors {
+    colors: HashMap<u16, u8>,
+}
+
+impl MinimapColors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Collect the colors of all item types that have one
+    pub fn from_item_types<'a>(items: impl IntoIterator<Item = &'a ItemType>) -> Self {
+        let mut colors = Self::new();
+        for item in items {
+            if let Some(color) = item.minimap_color {
+                colors.insert(item.id, color);
+            }
+        }
+        colors
+    }
+
+    /// Set the color of an item type, e.g. from the client DAT/appearances
+    pub fn insert(&mut self, item_id: u16, color: u16) {
+        if let Ok(color) = u8::try_from(color) {
+            self.colors.insert(item_id, color);
+        }
+    }
+
+    pub fn get(&self, item_id: u16) -> Option<u8> {
+        self.colors.get(&item_id).copied()
+    }
+}
+
+/// RGB of a minimap palette index (6x6x6 color cube)
+pub fn palette_rgb(color: u8) -> [u8; 3] {
+    let color = color.min(215);
+    [(color / 36) % 6 * 51, (color / 6) % 6 * 51, color % 6 * 51]
+}
+
+/// A rendered floor
+#[derive(Debug, Clone)]
+pub struct MinimapImage {
+    pub floor: u8,
+    /// World coordinates of the top-left pixel
+    pub origin_x: u16,
+    pub origin_y: u16,
+    pub width: u32,
+    pub height: u32,
+    /// Palette index per pixel, row-major; `None` for unexplored tiles
+    pixels: Vec<Option<u8>>,
+}
+
+impl MinimapImage {
+    fn new(floor: u8, origin_x: u16, origin_y: u16, width: u32, height: u32) -> Self {
+        Self {
+            floor,
+            origin_x,
+            origin_y,
+            width,
+            height,
+            pixels: vec![None; width as usize * height as usize],
+        }
+    }
+
+    fn index(&self, x: u16, y: u16) -> Option<usize> {
+        let (dx, dy) = (x.checked_sub(self.origin_x)? as u32, y.checked_sub(self.origin_y)? as u32);
+        (dx < self.width && dy < self.height).then(|| (dy * self.width + dx) as usize)
+    }
+
+    /// Palette index at world coordinates
+    pub fn color_at(&self, x: u16, y: u16) -> Option<u8> {
+        self.index(x, y).and_then(|i| self.pixels[i])
+    }
+
+    /// RGBA at world coordinates; fully transparent when unexplored
+    pub fn pixel(&self, x: u16, y: u16) -> [u8; 4] {
+        match self.color_at(x, y) {
+            Some(color) => {
+                let [r, g, b] = palette_rgb(color);
+                [r, g, b, 255]
+            }
+            None => [0; 4],
+        }
+    }
+
+    /// Number of tiles with a color
+    pub fn explored(&self) -> usize {
+        self.pixels.iter().filter(|p| p.is_some()).count()
+    }
+
+    /// Encode as an RGBA PNG
+    pub fn encode_png(&self) -> Result<Vec<u8>> {
+        let mut data = Vec::with_capacity(self.pixels.len() * 4);
+        for color in &self.pixels {
+            match color {
+                Some(color) => {
+                    data.extend_from_slice(&palette_rgb(*color));
+                    data.push(255);
+                }
+                None => data.extend_from_slice(&[0; 4]),
+            }
+        }
+        if data.is_empty() {
+            // PNG has no empty images; write a single transparent pixel
+            data.extend_from_slice(&[0; 4]);
+        }
+
+        let mut out = Vec::new();
+        {
+            let mut encoder = png::Encoder::new(&mut out, self.width.max(1), self.height.max(1));
+            encoder.set_color(png::ColorType::Rgba);
+            encoder.set_depth(png::BitDepth::Eight);
+            let mut writer = encoder
+                .write_header()
+                .map_err(|e| WorldError::Map(format!("minimap encode: {}", e)))?;
+            writer
+                .write_image_data(&data)
+                .map_err(|e| WorldError::Map(format!("minimap encode: {}", e)))?;
+        }
+        Ok(out)
+    }
+
+    /// Write the image as a PNG file
+    pub fn write_png(&self, path: impl AsRef<Path>) -> Result<()> {
+        let png = self.encode_png()?;
+        let mut file = BufWriter::new(std::fs::File::create(path)?);
+        std::io::Write::write_all(&mut file, &png)?;
+        Ok(())
+    }
+}
+
+impl Map {
+    /// Render a whole floor, cropped to the sectors that hold tiles
+    pub async fn export_minimap(&self, floor: u8, colors: &MinimapColors) -> MinimapImage {
+        let Some(layer) = self.get_layer(floor) else {
+            return MinimapImage::new(floor, 0, 0, 0, 0);
+        };
+        let bounds = layer.sectors().map(|(&key, _)| key).fold(None, |bounds: Option<(u16, u16, u16, u16)>, (sx, sy)| {
+            Some(match bounds {
+                Some((min_x, min_y, max_x, max_y)) => (min_x.min(sx), min_y.min(sy), max_x.max(sx), max_y.max(sy)),
+                None => (sx, sy, sx, sy),
+            })
+        });
+        let Some((min_x, min_y, max_x, max_y)) = bounds else {
+            return MinimapImage::new(floor, 0, 0, 0, 0);
+        };
+
+        let width = (max_x - min_x + 1) as u32 * SECTOR_SIZE as u32;
+        let height = (max_y - min_y + 1) as u32 * SECTOR_SIZE as u32;
+        self.export_minimap_region(floor, min_x * SECTOR_SIZE, min_y * SECTOR_SIZE, width, height, colors)
+            .await
+    }
+
+    /// Render a rectangle of a floor starting at world coordinates `x`, `y`
+    pub async fn export_minimap_region(
+        &self,
+        floor: u8,
+        x: u16,
+        y: u16,
+        width: u32,
+        height: u32,
+        colors: &MinimapColors,
+    ) -> MinimapImage {
+        let mut image = MinimapImage::new(floor, x, y, width, height);
+        let Some(layer) = self.get_layer(floor) else {
+            return image;
+        };
+
+        for (_, sector) in layer.sectors() {
+            let sector = sector.read().await;
+            for (&(local_x, local_y), tile) in sector.tiles() {
+                let pos = sector.to_world(local_x, local_y);
+                let Some(index) = image.index(pos.x, pos.y) else {
+                    continue;
+                };
+                let tile = tile.read().await;
+                image.pixels[index] = tile.get_ground().and_then(|ground| colors.get(ground.item_type_id));
+            }
+        }
+        image
+    }
+
+    /// Write one `floor_NN.png` per floor that has tiles into `dir`
+    pub async fn write_minimap_pngs(&self, dir: impl AsRef<Path>, colors: &MinimapColors) -> Result<Vec<PathBuf>> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+
+        let mut written = Vec::new();
+        for floor in 0..=crate::MAP_MAX_Z {
+            let image = self.export_minimap(floor, colors).await;
+            if image.explored() == 0 {
+                continue;
+            }
+            let path = dir.join(format!("floor_{:02}.png", floor));
+            image.write_png(&path)?;
+            written.push(path);
+        }
+        Ok(written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::position::Position;
+
+    const GRASS: u16 = 4526;
+    const WATER: u16 = 4608;
+    const UNMAPPED: u16 = 100;
+
+    fn colors() -> MinimapColors {
+        let mut grass = ItemType::new(GRASS);
+        grass.minimap_color = Some(24);
+        let mut water = ItemType::new(WATER);
+        water.minimap_color = Some(40);
+        MinimapColors::from_item_types([&grass, &water, &ItemType::new(UNMAPPED)])
+    }
+
+    #[tokio::test]
+    async fn test_export_region_matches_ground_colors() {
+        let mut map = Map::new("Test".to_string());
+        map.create_tile(Position::new(100, 100, 7), GRASS).await;
+        map.create_tile(Position::new(101, 100, 7), WATER).await;
+        map.create_tile(Position::new(100, 101, 7), UNMAPPED).await;
+
+        let image = map.export_minimap_region(7, 100, 100, 3, 2, &colors()).await;
+        assert_eq!((image.width, image.height), (3, 2));
+        assert_eq!(image.pixel(100, 100), [0, 204, 0, 255]);
+        assert_eq!(image.pixel(101, 100), [51, 0, 204, 255]);
+
+        // Grounds without a color and missing tiles are transparent
+        assert_eq!(image.pixel(100, 101), [0; 4]);
+        assert_eq!(image.pixel(102, 101), [0; 4]);
+        assert_eq!(image.explored(), 2);
+
+        // The PNG round-trips the same pixels
+        let png = image.encode_png().unwrap();
+        let mut reader = png::Decoder::new(png.as_slice()).read_info().unwrap();
+        let mut buf = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut buf).unwrap();
+        assert_eq!(&buf[0..4], &[0, 204, 0, 255]);
+        assert_eq!(&buf[4..8], &[51, 0, 204, 255]);
+        assert_eq!(&buf[12..16], &[0; 4]);
+    }
+
+    #[tokio::test]
+    async fn test_export_floor_crops_to_sectors() {
+        let mut map = Map::new("Test".to_string());
+        map.create_tile(Position::new(40, 20, 7), GRASS).await;
+        map.create_tile(Position::new(70, 20, 7), WATER).await;
+
+        let image = map.export_minimap(7, &colors()).await;
+        assert_eq!((image.origin_x, image.origin_y), (32, 16));
+        assert_eq!((image.width, image.height), (48, 16));
+        assert_eq!(image.color_at(70, 20), Some(40));
+        assert_eq!(map.export_minimap(6, &colors()).await.explored(), 0);
+    }
+}
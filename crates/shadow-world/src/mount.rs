@@ -0,0 +1,253 @@
+//! Mounts - speed bonuses and mount mechanics
+//!
+//! A mount is more than the `look_mount` of an outfit: riding one adds its
+//! speed bonus to the rider's movement speed. Some mounts tire: each step
+//! uses mount stamina and an exhausted mount keeps carrying the rider but
+//! loses its bonus until it has rested. Mounts may also grant abilities
+//! such as crossing shallow water.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::creature::{Creature, MIN_GROUND_SPEED};
+
+/// Special mount abilities
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MountAbility {
+    /// Walk over shallow water
+    WaterWalking,
+    /// Take no damage from fire, poison and energy fields
+    FieldImmunity,
+}
+
+/// Stamina of a tiring mount
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MountStamina {
+    pub max: u32,
+    /// Stamina used per step
+    pub per_step: u32,
+    /// Stamina recovered per second while not ridden
+    pub regen_per_second: u32,
+}
+
+/// Mount definition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MountType {
+    pub id: u16,
+    /// Looktype shown by the client
+    pub client_id: u16,
+    pub name: String,
+    pub speed_bonus: u16,
+    pub premium: bool,
+    pub stamina: Option<MountStamina>,
+    pub abilities: Vec<MountAbility>,
+}
+
+impl MountType {
+    pub fn new(id: u16, client_id: u16, name: impl Into<String>, speed_bonus: u16) -> Self {
+        Self {
+            id,
+            client_id,
+            name: name.into(),
+            speed_bonus,
+            premium: true,
+            stamina: None,
+            abilities: Vec::new(),
+        }
+    }
+
+    pub fn with_stamina(mut self, stamina: MountStamina) -> Self {
+        self.stamina = Some(stamina);
+        self
+    }
+
+    pub fn with_ability(mut self, ability: MountAbility) -> Self {
+        self.abilities.push(ability);
+        self
+    }
+}
+
+/// Extra movement speed in tiles per second a speed bonus allows on the
+/// fastest ground; used as the anti-cheat speed allowance
+pub fn speed_bonus_tiles_per_second(speed_bonus: u16) -> f64 {
+    speed_bonus as f64 / MIN_GROUND_SPEED as f64
+}
+
+/// Why a creature cannot mount
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MountError {
+    UnknownMount,
+    /// The mount is resting and cannot be ridden yet
+    Exhausted,
+}
+
+impl std::fmt::Display for MountError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MountError::UnknownMount => write!(f, "Unknown mount"),
+            MountError::Exhausted => write!(f, "Your mount is too exhausted to ride"),
+        }
+    }
+}
+
+impl std::error::Error for MountError {}
+
+/// Mount definitions and the stamina of mounts in use
+#[derive(Debug, Clone, Default)]
+pub struct MountRegistry {
+    mounts: HashMap<u16, MountType>,
+    /// Creature id -> mount id being ridden
+    riders: HashMap<u32, u16>,
+    /// (creature id, mount id) -> remaining stamina
+    stamina: HashMap<(u32, u16), u32>,
+}
+
+impl MountRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, mount: MountType) {
+        self.mounts.insert(mount.id, mount);
+    }
+
+    pub fn get(&self, id: u16) -> Option<&MountType> {
+        self.mounts.get(&id)
+    }
+
+    /// Mount by client looktype
+    pub fn get_by_client_id(&self, client_id: u16) -> Option<&MountType> {
+        self.mounts.values().find(|m| m.client_id == client_id)
+    }
+
+    /// Mount a creature is riding
+    pub fn ridden_by(&self, creature_id: u32) -> Option<&MountType> {
+        self.riders.get(&creature_id).and_then(|id| self.mounts.get(id))
+    }
+
+    /// Whether a creature's current mount grants an ability
+    pub fn has_ability(&self, creature_id: u32, ability: MountAbility) -> bool {
+        self.ridden_by(creature_id).is_some_and(|m| m.abilities.contains(&ability))
+    }
+
+    /// Remaining stamina of a creature's mount; `None` for untiring mounts
+    pub fn stamina(&self, creature_id: u32, mount_id: u16) -> Option<u32> {
+        let max = self.mounts.get(&mount_id)?.stamina?.max;
+        Some(self.stamina.get(&(creature_id, mount_id)).copied().unwrap_or(max))
+    }
+
+    /// Ride a mount, applying its speed bonus. Returns the bonus.
+    pub fn mount(&mut self, creature: &mut Creature, mount_id: u16) -> Result<u16, MountError> {
+        let mount = self.mounts.get(&mount_id).ok_or(MountError::UnknownMount)?;
+        if self.stamina(creature.id, mount_id) == Some(0) {
+            return Err(MountError::Exhausted);
+        }
+
+        creature.outfit.look_mount = mount.client_id;
+        creature.mount_speed = mount.speed_bonus;
+        self.riders.insert(creature.id, mount_id);
+        Ok(mount.speed_bonus)
+    }
+
+    /// Get off the mount and lose its speed bonus
+    pub fn dismount(&mut self, creature: &mut Creature) {
+        creature.outfit.look_mount = 0;
+        creature.mount_speed = 0;
+        self.riders.remove(&creature.id);
+    }
+
+    /// Use stamina for one step. Returns false once the mount is exhausted
+    /// and its speed bonus is gone.
+    pub fn on_step(&mut self, creature: &mut Creature) -> bool {
+        let Some(&mount_id) = self.riders.get(&creature.id) else {
+            return true;
+        };
+        let Some(stamina) = self.mounts.get(&mount_id).and_then(|m| m.stamina) else {
+            return true;
+        };
+
+        let left = self.stamina.entry((creature.id, mount_id)).or_insert(stamina.max);
+        *left = left.saturating_sub(stamina.per_step);
+        if *left == 0 {
+            creature.mount_speed = 0;
+            return false;
+        }
+        true
+    }
+
+    /// Recover stamina of mounts the creature is not riding
+    pub fn rest(&mut self, creature_id: u32, elapsed_ms: u64) {
+        let riding = self.riders.get(&creature_id).copied();
+        let mounts = &self.mounts;
+        self.stamina.retain(|&(owner, mount_id), left| {
+            if owner != creature_id || Some(mount_id) == riding {
+                return true;
+            }
+            let Some(stamina) = mounts.get(&mount_id).and_then(|m| m.stamina) else {
+                return false;
+            };
+            let regen = (stamina.regen_per_second as u64 * elapsed_ms / 1000).min(u32::MAX as u64) as u32;
+            *left = left.saturating_add(regen);
+            // Fully rested mounts need no entry
+            *left < stamina.max
+        });
+    }
+
+    /// Forget a creature's riding state, e.g. on logout
+    pub fn clear_creature(&mut self, creature_id: u32) {
+        self.riders.remove(&creature_id);
+        self.stamina.retain(|&(owner, _), _| owner != creature_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::creature::CreatureType;
+    use crate::position::Position;
+
+    fn rider() -> Creature {
+        let mut creature = Creature::new("Rider".to_string(), CreatureType::Player, Position::new(100, 100, 7));
+        creature.stats.base_speed = 220;
+        creature
+    }
+
+    #[test]
+    fn test_mount_speed_and_dismount() {
+        let mut registry = MountRegistry::new();
+        registry.add(MountType::new(1, 368, "Widow Queen", 10).with_ability(MountAbility::FieldImmunity));
+        let mut creature = rider();
+
+        assert_eq!(registry.mount(&mut creature, 1), Ok(10));
+        assert_eq!(creature.get_speed(), 230);
+        assert_eq!(creature.outfit.look_mount, 368);
+        assert!(registry.has_ability(creature.id, MountAbility::FieldImmunity));
+
+        registry.dismount(&mut creature);
+        assert_eq!(creature.get_speed(), 220);
+        assert!(!creature.outfit.has_mount());
+        assert!(registry.ridden_by(creature.id).is_none());
+        assert_eq!(registry.mount(&mut creature, 99), Err(MountError::UnknownMount));
+    }
+
+    #[test]
+    fn test_mount_stamina_exhaustion_and_rest() {
+        let mut registry = MountRegistry::new();
+        let stamina = MountStamina { max: 30, per_step: 10, regen_per_second: 5 };
+        registry.add(MountType::new(2, 369, "Racing Bird", 20).with_stamina(stamina));
+        let mut creature = rider();
+
+        registry.mount(&mut creature, 2).unwrap();
+        assert!(registry.on_step(&mut creature));
+        assert!(registry.on_step(&mut creature));
+        assert!(!registry.on_step(&mut creature));
+        assert_eq!(creature.get_speed(), 220);
+
+        registry.dismount(&mut creature);
+        assert_eq!(registry.mount(&mut creature, 2), Err(MountError::Exhausted));
+
+        registry.rest(creature.id, 4_000);
+        assert_eq!(registry.stamina(creature.id, 2), Some(20));
+        assert_eq!(registry.mount(&mut creature, 2), Ok(20));
+    }
+}
@@ -322,6 +322,16 @@ impl OtbLoader {
                         self.position += data_len;
                     }
                 }
+                ATTR_MINIMAPCOLOR => {
+                    if data_len >= 2 {
+                        item.minimap_color = Some(self.read_u16()?);
+                        if data_len > 2 {
+                            self.position += data_len - 2;
+                        }
+                    } else {
+                        self.position += data_len;
+                    }
+                }
                 ATTR_WRITEABLE | ATTR_WRITEABLE2 | ATTR_WRITEABLE3 => {
                     if data_len >= 2 {
                         item.max_text_length = Some(self.read_u16()?);
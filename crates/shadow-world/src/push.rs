@@ -0,0 +1,246 @@
+//! Creature pushing
+//!
+//! A creature standing next to another may push it one tile onto a free
+//! neighbouring tile. Realms decide who may push whom, how long a pusher
+//! waits between pushes and whether protection zones block pushing. Every
+//! push attempt should also be passed to anti-cheat as a push action so
+//! push spam (e.g. trapping players) is detected.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::creature::{Creature, CreatureType};
+use crate::map::Map;
+use crate::position::Position;
+
+/// Which creatures a player may push
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PushTargets {
+    /// Players and monsters
+    #[default]
+    Everyone,
+    /// Monsters and summons, but no players
+    NonPlayers,
+    /// Nobody
+    Nobody,
+}
+
+/// Push configuration of a realm
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PushRules {
+    /// Who players may push
+    pub player_targets: PushTargets,
+    /// Whether monsters may push players out of their way
+    pub monsters_push_players: bool,
+    /// Minimum time between two pushes of the same creature, in milliseconds
+    pub push_delay_ms: u64,
+    /// Creatures standing in a protection zone can't be pushed
+    pub blocked_in_pz: bool,
+}
+
+impl Default for PushRules {
+    fn default() -> Self {
+        Self {
+            player_targets: PushTargets::Everyone,
+            monsters_push_players: false,
+            push_delay_ms: 1_000,
+            blocked_in_pz: false,
+        }
+    }
+}
+
+/// Why a push was refused
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushError {
+    /// The rules don't allow pushing this creature
+    NotAllowed,
+    /// Pusher, target and destination aren't neighbours
+    OutOfReach,
+    /// The target stands in a protection zone
+    ProtectionZone,
+    /// The pusher pushed too recently
+    TooSoon { remaining_ms: u64 },
+    /// The destination tile is blocked or occupied
+    DestinationBlocked,
+}
+
+impl std::fmt::Display for PushError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PushError::NotAllowed => write!(f, "You can't push this creature"),
+            PushError::OutOfReach => write!(f, "Creature is out of reach"),
+            PushError::ProtectionZone => write!(f, "You can't push creatures in a protection zone"),
+            PushError::TooSoon { .. } => write!(f, "You are exhausted"),
+            PushError::DestinationBlocked => write!(f, "There is not enough room"),
+        }
+    }
+}
+
+impl std::error::Error for PushError {}
+
+/// Applies push rules and tracks push delays
+#[derive(Debug, Clone, Default)]
+pub struct PushResolver {
+    rules: PushRules,
+    /// Pusher creature id -> time of its last push
+    last_push: HashMap<u32, u64>,
+}
+
+impl PushResolver {
+    pub fn new(rules: PushRules) -> Self {
+        Self {
+            rules,
+            last_push: HashMap::new(),
+        }
+    }
+
+    pub fn rules(&self) -> &PushRules {
+        &self.rules
+    }
+
+    /// Whether the rules let `pusher` push `target` at all
+    pub fn may_push(&self, pusher: &Creature, target: &Creature) -> bool {
+        let target_is_player = target.creature_type == CreatureType::Player;
+        match pusher.creature_type {
+            CreatureType::Player => match self.rules.player_targets {
+                PushTargets::Everyone => target.creature_type != CreatureType::Npc,
+                PushTargets::NonPlayers => matches!(target.creature_type, CreatureType::Monster | CreatureType::Summon),
+                PushTargets::Nobody => false,
+            },
+            CreatureType::Monster => !target_is_player || self.rules.monsters_push_players,
+            CreatureType::Npc | CreatureType::Summon => false,
+        }
+    }
+
+    /// Check a push of `target` onto `to`. `target_in_pz` is whether the
+    /// target's tile is a protection zone.
+    pub fn check(
+        &self,
+        pusher: &Creature,
+        target: &Creature,
+        to: &Position,
+        target_in_pz: bool,
+        now: u64,
+    ) -> Result<(), PushError> {
+        if !self.may_push(pusher, target) {
+            return Err(PushError::NotAllowed);
+        }
+        if !pusher.position.is_adjacent(&target.position) || !target.position.is_adjacent(to) || *to == pusher.position {
+            return Err(PushError::OutOfReach);
+        }
+        if target_in_pz && self.rules.blocked_in_pz {
+            return Err(PushError::ProtectionZone);
+        }
+        if let Some(&last) = self.last_push.get(&pusher.id) {
+            let elapsed = now.saturating_sub(last);
+            if elapsed < self.rules.push_delay_ms {
+                return Err(PushError::TooSoon { remaining_ms: self.rules.push_delay_ms - elapsed });
+            }
+        }
+        Ok(())
+    }
+
+    /// Record a successful push
+    pub fn record(&mut self, pusher_id: u32, now: u64) {
+        self.last_push.insert(pusher_id, now);
+    }
+
+    /// Forget a creature, e.g. on logout or death
+    pub fn clear_creature(&mut self, creature_id: u32) {
+        self.last_push.remove(&creature_id);
+    }
+}
+
+impl Map {
+    /// Push `target` onto `to`, checking the push rules and the tiles
+    pub async fn push_creature(
+        &self,
+        resolver: &mut PushResolver,
+        pusher: &Creature,
+        target: &mut Creature,
+        to: Position,
+        now: u64,
+    ) -> Result<(), PushError> {
+        let target_in_pz = match self.get_tile(&target.position).await {
+            Some(tile) => tile.read().await.flags.is_protection_zone(),
+            None => false,
+        };
+        resolver.check(pusher, target, &to, target_in_pz, now)?;
+
+        match self.get_tile(&to).await {
+            Some(tile) => {
+                let tile = tile.read().await;
+                if !tile.is_walkable() || tile.has_creatures() {
+                    return Err(PushError::DestinationBlocked);
+                }
+            }
+            None => return Err(PushError::DestinationBlocked),
+        }
+
+        self.move_creature(&target.position, &to, target.id)
+            .await
+            .map_err(|_| PushError::DestinationBlocked)?;
+        target.position = to;
+        resolver.record(pusher.id, now);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tile::TileFlags;
+
+    async fn setup(rules: PushRules) -> (Map, PushResolver, Creature, Creature) {
+        let mut map = Map::new("Test".to_string());
+        for x in 100..=103 {
+            map.create_tile(Position::new(x, 100, 7), 4526).await;
+        }
+        let pusher = Creature::new("Pusher".to_string(), CreatureType::Player, Position::new(100, 100, 7));
+        let target = Creature::new("Target".to_string(), CreatureType::Player, Position::new(101, 100, 7));
+        map.add_creature(&pusher.position, pusher.id).await.unwrap();
+        map.add_creature(&target.position, target.id).await.unwrap();
+        (map, PushResolver::new(rules), pusher, target)
+    }
+
+    #[tokio::test]
+    async fn test_push_with_delay() {
+        let (map, mut resolver, pusher, mut target) = setup(PushRules::default()).await;
+
+        map.push_creature(&mut resolver, &pusher, &mut target, Position::new(102, 100, 7), 0).await.unwrap();
+        assert_eq!(target.position, Position::new(102, 100, 7));
+        assert!(map.get_tile(&Position::new(102, 100, 7)).await.unwrap().read().await.has_creatures());
+
+        // Following up right away is refused until the delay has passed
+        let mut pusher = pusher;
+        pusher.position = Position::new(101, 100, 7);
+        let to = Position::new(103, 100, 7);
+        assert_eq!(
+            map.push_creature(&mut resolver, &pusher, &mut target, to, 400).await,
+            Err(PushError::TooSoon { remaining_ms: 600 })
+        );
+        map.push_creature(&mut resolver, &pusher, &mut target, to, 1_000).await.unwrap();
+
+        // Rules can forbid pushing players entirely
+        let strict = PushResolver::new(PushRules { player_targets: PushTargets::NonPlayers, ..Default::default() });
+        assert!(!strict.may_push(&pusher, &target));
+    }
+
+    #[tokio::test]
+    async fn test_push_blocked_in_protection_zone() {
+        let rules = PushRules { blocked_in_pz: true, ..Default::default() };
+        let (map, mut resolver, pusher, mut target) = setup(rules).await;
+        map.get_tile(&target.position).await.unwrap().write().await.flags.set(TileFlags::PROTECTION_ZONE);
+
+        let to = Position::new(102, 100, 7);
+        assert_eq!(
+            map.push_creature(&mut resolver, &pusher, &mut target, to, 0).await,
+            Err(PushError::ProtectionZone)
+        );
+        assert_eq!(target.position, Position::new(101, 100, 7));
+
+        // Without the rule the same push goes through
+        let mut lenient = PushResolver::new(PushRules::default());
+        map.push_creature(&mut lenient, &pusher, &mut target, to, 0).await.unwrap();
+    }
+}
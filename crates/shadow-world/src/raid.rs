@@ -0,0 +1,389 @@
+//! Raids - scheduled waves of monsters
+//!
+//! A raid announces itself and spawns waves of monsters around configured
+//! locations, each wave a number of seconds after the raid started. It
+//! completes once every wave has spawned and all of its monsters are dead,
+//! or expires after `duration_secs`, when the survivors are removed. Raids
+//! are started by the event scheduler or by an admin.
+//!
+//! The manager only decides what should happen; the engine performs the
+//! returned actions and reports the creatures it spawned back. Running
+//! raids are persisted as `RaidState`, so after a restart a raid either
+//! resumes its remaining waves or is cancelled.
+
+use crate::spawn::BoostArea;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::info;
+use uuid::Uuid;
+
+/// Monsters one wave spawns in an area
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RaidSpawn {
+    pub monster: String,
+    pub count: u16,
+    /// Monsters are placed at random within the area
+    pub area: BoostArea,
+}
+
+/// A wave of a raid
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RaidWave {
+    /// Seconds after the raid started
+    pub delay_secs: u32,
+    /// Broadcast when the wave spawns
+    pub announcement: Option<String>,
+    pub spawns: Vec<RaidSpawn>,
+}
+
+/// Raid configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RaidDefinition {
+    pub name: String,
+    /// Broadcast when the raid starts
+    pub announcement: Option<String>,
+    /// Waves in spawn order
+    pub waves: Vec<RaidWave>,
+    /// Seconds after which the raid ends and remaining monsters are removed
+    pub duration_secs: u32,
+    /// Broadcast when all raid monsters were defeated
+    pub completion_message: Option<String>,
+}
+
+/// Who started a raid
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RaidTrigger {
+    Scheduled,
+    Admin(String),
+}
+
+/// How a raid ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RaidOutcome {
+    /// Every monster was defeated
+    Completed,
+    /// The raid ran out of time
+    Expired,
+    Cancelled,
+}
+
+/// What to do with raids that were running when the server stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RaidRecovery {
+    /// Spawn the remaining waves on the original schedule. Monsters of
+    /// waves spawned before the restart did not survive it.
+    #[default]
+    Resume,
+    Cancel,
+}
+
+/// Persisted progress of a running raid
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RaidState {
+    pub id: Uuid,
+    pub raid: String,
+    pub trigger: RaidTrigger,
+    pub started_at: DateTime<Utc>,
+    /// The start announcement was made
+    pub announced: bool,
+    /// Index of the next wave to spawn
+    pub next_wave: usize,
+}
+
+/// Work for the engine, in the order it should be done
+#[derive(Debug, Clone, PartialEq)]
+pub enum RaidAction {
+    Announce { raid_id: Uuid, message: String },
+    /// Spawn monsters, then report them with `RaidManager::record_spawned`
+    Spawn { raid_id: Uuid, wave: usize, spawn: RaidSpawn },
+    /// Remove the raid's surviving monsters
+    Cleanup { raid_id: Uuid, creatures: Vec<u32> },
+    Finished { raid_id: Uuid, outcome: RaidOutcome },
+}
+
+/// Why a raid could not be started or cancelled
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RaidError {
+    UnknownRaid(String),
+    /// The raid is already running
+    AlreadyRunning(String),
+    NotRunning(Uuid),
+}
+
+impl std::fmt::Display for RaidError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RaidError::UnknownRaid(name) => write!(f, "Unknown raid: {}", name),
+            RaidError::AlreadyRunning(name) => write!(f, "Raid {} is already running", name),
+            RaidError::NotRunning(id) => write!(f, "No running raid {}", id),
+        }
+    }
+}
+
+impl std::error::Error for RaidError {}
+
+#[derive(Debug)]
+struct ActiveRaid {
+    state: RaidState,
+    /// Living monsters of the raid
+    creatures: Vec<u32>,
+}
+
+/// Runs raids and tracks their monsters
+#[derive(Debug, Default)]
+pub struct RaidManager {
+    definitions: HashMap<String, RaidDefinition>,
+    active: HashMap<Uuid, ActiveRaid>,
+}
+
+impl RaidManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&mut self, definition: RaidDefinition) {
+        self.definitions.insert(definition.name.to_lowercase(), definition);
+    }
+
+    pub fn definition(&self, name: &str) -> Option<&RaidDefinition> {
+        self.definitions.get(&name.to_lowercase())
+    }
+
+    /// Start a raid. Its announcement and first waves follow on the next tick.
+    pub fn start(&mut self, name: &str, trigger: RaidTrigger, now: DateTime<Utc>) -> Result<Uuid, RaidError> {
+        let definition = self.definition(name).ok_or_else(|| RaidError::UnknownRaid(name.to_string()))?;
+        let key = definition.name.clone();
+        if self.active.values().any(|raid| raid.state.raid == key) {
+            return Err(RaidError::AlreadyRunning(key));
+        }
+
+        let id = Uuid::new_v4();
+        info!("Raid {} started ({:?})", key, trigger);
+        self.active.insert(id, ActiveRaid {
+            state: RaidState { id, raid: key, trigger, started_at: now, announced: false, next_wave: 0 },
+            creatures: Vec::new(),
+        });
+        Ok(id)
+    }
+
+    /// Track creatures the engine spawned for a raid
+    pub fn record_spawned(&mut self, raid_id: Uuid, creatures: impl IntoIterator<Item = u32>) {
+        if let Some(raid) = self.active.get_mut(&raid_id) {
+            raid.creatures.extend(creatures);
+        }
+    }
+
+    /// A creature died; returns the raid it belonged to
+    pub fn record_death(&mut self, creature_id: u32) -> Option<Uuid> {
+        let raid = self.active.values_mut().find(|raid| raid.creatures.contains(&creature_id))?;
+        raid.creatures.retain(|&id| id != creature_id);
+        Some(raid.state.id)
+    }
+
+    pub fn is_running(&self, raid_id: Uuid) -> bool {
+        self.active.contains_key(&raid_id)
+    }
+
+    /// Spawn due waves and end finished raids
+    pub fn tick(&mut self, now: DateTime<Utc>) -> Vec<RaidAction> {
+        let mut actions = Vec::new();
+        let mut finished = Vec::new();
+
+        for raid in self.active.values_mut() {
+            let Some(definition) = self.definitions.get(&raid.state.raid.to_lowercase()) else {
+                finished.push((raid.state.id, RaidOutcome::Cancelled));
+                continue;
+            };
+            let id = raid.state.id;
+            let elapsed = now - raid.state.started_at;
+
+            if elapsed >= Duration::seconds(definition.duration_secs as i64) {
+                finished.push((id, RaidOutcome::Expired));
+                continue;
+            }
+            // Checked before spawning, so the last wave's monsters are
+            // recorded before the raid can count as defeated
+            if raid.state.next_wave >= definition.waves.len() && raid.creatures.is_empty() {
+                if let Some(message) = &definition.completion_message {
+                    actions.push(RaidAction::Announce { raid_id: id, message: message.clone() });
+                }
+                finished.push((id, RaidOutcome::Completed));
+                continue;
+            }
+
+            if !raid.state.announced {
+                raid.state.announced = true;
+                if let Some(message) = &definition.announcement {
+                    actions.push(RaidAction::Announce { raid_id: id, message: message.clone() });
+                }
+            }
+            while let Some(wave) = definition.waves.get(raid.state.next_wave) {
+                if elapsed < Duration::seconds(wave.delay_secs as i64) {
+                    break;
+                }
+                if let Some(message) = &wave.announcement {
+                    actions.push(RaidAction::Announce { raid_id: id, message: message.clone() });
+                }
+                for spawn in &wave.spawns {
+                    actions.push(RaidAction::Spawn { raid_id: id, wave: raid.state.next_wave, spawn: spawn.clone() });
+                }
+                raid.state.next_wave += 1;
+            }
+        }
+
+        for (id, outcome) in finished {
+            actions.extend(self.finish(id, outcome));
+        }
+        actions
+    }
+
+    /// End a running raid early, removing its monsters
+    pub fn cancel(&mut self, raid_id: Uuid) -> Result<Vec<RaidAction>, RaidError> {
+        if !self.active.contains_key(&raid_id) {
+            return Err(RaidError::NotRunning(raid_id));
+        }
+        Ok(self.finish(raid_id, RaidOutcome::Cancelled))
+    }
+
+    fn finish(&mut self, raid_id: Uuid, outcome: RaidOutcome) -> Vec<RaidAction> {
+        let mut actions = Vec::new();
+        if let Some(raid) = self.active.remove(&raid_id) {
+            info!("Raid {} ended: {:?}", raid.state.raid, outcome);
+            if !raid.creatures.is_empty() {
+                actions.push(RaidAction::Cleanup { raid_id, creatures: raid.creatures });
+            }
+            actions.push(RaidAction::Finished { raid_id, outcome });
+        }
+        actions
+    }
+
+    /// Progress of every running raid, for persisting
+    pub fn snapshot(&self) -> Vec<RaidState> {
+        self.active.values().map(|raid| raid.state.clone()).collect()
+    }
+
+    /// Restore raids persisted before a restart. Raids that are cancelled,
+    /// unknown or past their duration are reported as finished.
+    pub fn restore(&mut self, states: Vec<RaidState>, recovery: RaidRecovery, now: DateTime<Utc>) -> Vec<RaidAction> {
+        let mut actions = Vec::new();
+        for state in states {
+            let outcome = match self.definition(&state.raid) {
+                None => Some(RaidOutcome::Cancelled),
+                Some(definition) if now - state.started_at >= Duration::seconds(definition.duration_secs as i64) => {
+                    Some(RaidOutcome::Expired)
+                }
+                Some(_) if recovery == RaidRecovery::Cancel => Some(RaidOutcome::Cancelled),
+                Some(_) => None,
+            };
+
+            match outcome {
+                Some(outcome) => actions.push(RaidAction::Finished { raid_id: state.id, outcome }),
+                None => {
+                    info!("Raid {} resumed at wave {}", state.raid, state.next_wave);
+                    self.active.insert(state.id, ActiveRaid { state, creatures: Vec::new() });
+                }
+            }
+        }
+        actions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::position::Position;
+
+    fn spawn(monster: &str, count: u16) -> RaidSpawn {
+        RaidSpawn {
+            monster: monster.to_string(),
+            count,
+            area: BoostArea { center: Position::new(100, 100, 7), radius: 5 },
+        }
+    }
+
+    fn orc_raid() -> RaidDefinition {
+        RaidDefinition {
+            name: "Orc Invasion".to_string(),
+            announcement: Some("Orcs are approaching the city!".to_string()),
+            waves: vec![
+                RaidWave { delay_secs: 0, announcement: None, spawns: vec![spawn("Orc", 2)] },
+                RaidWave {
+                    delay_secs: 60,
+                    announcement: Some("The warlord has arrived!".to_string()),
+                    spawns: vec![spawn("Orc Warrior", 1), spawn("Orc Warlord", 1)],
+                },
+            ],
+            duration_secs: 1800,
+            completion_message: Some("The orcs have been driven back.".to_string()),
+        }
+    }
+
+    fn spawned(actions: &[RaidAction]) -> Vec<&str> {
+        actions
+            .iter()
+            .filter_map(|action| match action {
+                RaidAction::Spawn { spawn, .. } => Some(spawn.monster.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_raid_spawns_waves_and_completes() {
+        let mut manager = RaidManager::new();
+        manager.register(orc_raid());
+        let start = Utc::now();
+        let id = manager.start("orc invasion", RaidTrigger::Scheduled, start).unwrap();
+        assert_eq!(
+            manager.start("Orc Invasion", RaidTrigger::Admin("GM".to_string()), start),
+            Err(RaidError::AlreadyRunning("Orc Invasion".to_string()))
+        );
+
+        let actions = manager.tick(start);
+        assert!(matches!(&actions[0], RaidAction::Announce { message, .. } if message.starts_with("Orcs")));
+        assert_eq!(spawned(&actions), vec!["Orc"]);
+        manager.record_spawned(id, [1, 2]);
+
+        assert!(manager.tick(start + Duration::seconds(30)).is_empty());
+        let actions = manager.tick(start + Duration::seconds(60));
+        assert_eq!(spawned(&actions), vec!["Orc Warrior", "Orc Warlord"]);
+        manager.record_spawned(id, [3, 4]);
+
+        for creature in 1..=4 {
+            assert_eq!(manager.record_death(creature), Some(id));
+        }
+        let actions = manager.tick(start + Duration::seconds(90));
+        assert_eq!(actions.last(), Some(&RaidAction::Finished { raid_id: id, outcome: RaidOutcome::Completed }));
+        assert!(!manager.is_running(id));
+    }
+
+    #[test]
+    fn test_raid_recovers_or_cancels_after_restart() {
+        let mut manager = RaidManager::new();
+        manager.register(orc_raid());
+        let start = Utc::now();
+        let id = manager.start("Orc Invasion", RaidTrigger::Scheduled, start).unwrap();
+        manager.tick(start);
+        manager.record_spawned(id, [1, 2]);
+        let saved = manager.snapshot();
+
+        // Resumed raids continue with the wave that was still due
+        let mut restarted = RaidManager::new();
+        restarted.register(orc_raid());
+        assert!(restarted.restore(saved.clone(), RaidRecovery::Resume, start + Duration::seconds(45)).is_empty());
+        let actions = restarted.tick(start + Duration::seconds(60));
+        assert_eq!(spawned(&actions), vec!["Orc Warrior", "Orc Warlord"]);
+
+        let mut cancelled = RaidManager::new();
+        cancelled.register(orc_raid());
+        let actions = cancelled.restore(saved, RaidRecovery::Cancel, start + Duration::seconds(45));
+        assert_eq!(actions, vec![RaidAction::Finished { raid_id: id, outcome: RaidOutcome::Cancelled }]);
+        assert!(!cancelled.is_running(id));
+
+        // An expired raid removes its survivors
+        let actions = manager.tick(start + Duration::seconds(1800));
+        assert_eq!(actions[0], RaidAction::Cleanup { raid_id: id, creatures: vec![1, 2] });
+        assert_eq!(actions[1], RaidAction::Finished { raid_id: id, outcome: RaidOutcome::Expired });
+    }
+}
@@ -0,0 +1,190 @@
+//! Dynamic difficulty scaling - monsters grow with the group fighting them
+//!
+//! A realm can let monsters scale with the players around them: either
+//! with the number of players within range, or with their average level.
+//! Each step past the baseline adds a share of health, damage and loot,
+//! up to a cap. The spawn manager rescales a monster when it spawns and
+//! whenever the group near it changes; the monster's unscaled health is
+//! kept, so when players leave the monster shrinks back, with the health
+//! it already lost carried over as a percentage.
+
+use serde::{Deserialize, Serialize};
+
+/// What a monster's difficulty is measured against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScalingBasis {
+    /// One step per player in range beyond the first
+    NearbyPlayers,
+    /// One step per `level_step` levels of the average level in range
+    PartyLevel,
+}
+
+/// Dynamic difficulty settings of a realm
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DifficultyScalingConfig {
+    pub enabled: bool,
+    pub basis: ScalingBasis,
+    /// Players within this many tiles of the monster count
+    pub radius: u32,
+    /// Levels per step with `ScalingBasis::PartyLevel`
+    pub level_step: u16,
+    /// Steps past this are ignored
+    pub max_steps: u32,
+    /// Extra health per step (0.5 = +50%)
+    pub health_per_step: f32,
+    /// Extra damage per step
+    pub damage_per_step: f32,
+    /// Extra loot chance per step
+    pub loot_per_step: f32,
+}
+
+impl Default for DifficultyScalingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            basis: ScalingBasis::NearbyPlayers,
+            radius: 8,
+            level_step: 50,
+            max_steps: 4,
+            health_per_step: 0.5,
+            damage_per_step: 0.1,
+            loot_per_step: 0.1,
+        }
+    }
+}
+
+impl DifficultyScalingConfig {
+    /// Steps earned by the levels of the players in range
+    pub fn steps(&self, player_levels: &[u16]) -> u32 {
+        if !self.enabled || player_levels.is_empty() {
+            return 0;
+        }
+        let steps = match self.basis {
+            ScalingBasis::NearbyPlayers => player_levels.len() as u32 - 1,
+            ScalingBasis::PartyLevel => {
+                let average = player_levels.iter().map(|&level| level as u32).sum::<u32>() / player_levels.len() as u32;
+                average / self.level_step.max(1) as u32
+            }
+        };
+        steps.min(self.max_steps)
+    }
+
+    /// Scale of a monster fought by the players in range
+    pub fn scale(&self, player_levels: &[u16]) -> DifficultyScale {
+        let steps = self.steps(player_levels) as f32;
+        DifficultyScale {
+            health: 1.0 + self.health_per_step * steps,
+            damage: 1.0 + self.damage_per_step * steps,
+            loot: 1.0 + self.loot_per_step * steps,
+        }
+    }
+}
+
+/// Multipliers applied to a scaled monster
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DifficultyScale {
+    pub health: f32,
+    pub damage: f32,
+    pub loot: f32,
+}
+
+impl DifficultyScale {
+    /// No scaling
+    pub const NONE: DifficultyScale = DifficultyScale { health: 1.0, damage: 1.0, loot: 1.0 };
+
+    pub fn is_none(&self) -> bool {
+        *self == Self::NONE
+    }
+
+    /// Damage of a hit dealt by the scaled monster
+    pub fn scale_damage(&self, damage: i32) -> i32 {
+        (damage as f32 * self.damage).round() as i32
+    }
+}
+
+impl Default for DifficultyScale {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+/// Scaling applied to a creature, with the health it had before
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AppliedScale {
+    pub scale: DifficultyScale,
+    pub base_max_health: i32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::creature::{MonsterLoader, Monster};
+    use crate::position::Position;
+    use crate::spawn::SpawnManager;
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    fn scaled_spawns(basis: ScalingBasis) -> SpawnManager {
+        SpawnManager::new(Arc::new(RwLock::new(MonsterLoader::new()))).with_difficulty_scaling(DifficultyScalingConfig {
+            enabled: true,
+            basis,
+            ..Default::default()
+        })
+    }
+
+    fn dragon() -> Monster {
+        let mut dragon = Monster::new("Dragon".to_string());
+        dragon.health = 1000;
+        dragon.max_health = 1000;
+        dragon
+    }
+
+    #[test]
+    fn test_health_scales_with_nearby_players_and_reverts() {
+        let spawns = scaled_spawns(ScalingBasis::NearbyPlayers);
+        let lair = Position::new(100, 100, 7);
+        let mut creature = dragon().spawn(lair);
+
+        // Alone, the monster stays as loaded
+        assert!(!spawns.rescale(&mut creature, &[(Position::new(102, 100, 7), 80)]));
+        assert_eq!(creature.stats.max_health, 1000);
+
+        // Three players in range, one on another floor and one too far
+        let group = [
+            (Position::new(101, 100, 7), 80),
+            (Position::new(100, 103, 7), 90),
+            (Position::new(99, 99, 7), 100),
+            (Position::new(100, 100, 6), 100),
+            (Position::new(120, 100, 7), 100),
+        ];
+        assert!(spawns.rescale(&mut creature, &group));
+        assert_eq!((creature.stats.health, creature.stats.max_health), (2000, 2000));
+        assert_eq!(creature.difficulty_scale().scale_damage(100), 120);
+
+        // Wounded to half, then the group shrinks back to one
+        creature.apply_damage(1000, crate::item::DamageType::Physical);
+        assert!(spawns.rescale(&mut creature, &group[..1]));
+        assert_eq!((creature.stats.health, creature.stats.max_health), (500, 1000));
+        assert!(creature.difficulty_scale().is_none());
+        assert!(creature.difficulty.is_none());
+    }
+
+    #[test]
+    fn test_party_level_steps_are_capped() {
+        let config = DifficultyScalingConfig { enabled: true, basis: ScalingBasis::PartyLevel, ..Default::default() };
+        assert_eq!(config.steps(&[40, 45]), 0);
+        assert_eq!(config.steps(&[100, 120]), 2);
+        assert_eq!(config.steps(&[800]), 4);
+        assert_eq!(config.scale(&[150]).scale_damage(100), 130);
+
+        // Disabled realms never scale
+        let disabled = DifficultyScalingConfig { basis: ScalingBasis::PartyLevel, ..Default::default() };
+        assert!(disabled.scale(&[800, 800]).is_none());
+
+        let spawns = scaled_spawns(ScalingBasis::PartyLevel);
+        let mut creature = dragon().spawn(Position::new(100, 100, 7));
+        spawns.rescale(&mut creature, &[(Position::new(101, 100, 7), 100)]);
+        assert_eq!(creature.stats.max_health, 2000);
+    }
+}
@@ -1,13 +1,25 @@
 //! Spawn system - manages creature spawning
+//!
+//! Events can boost spawns for their duration: respawn times in an area
+//! shrink and extra spawn points appear. Boosts are kept apart from the
+//! loaded spawn points, so ending an event restores the base spawns as
+//! they were.
+//!
+//! With dynamic difficulty enabled, spawned monsters are rescaled to the
+//! players around them (see `scaling`).
 
 use crate::creature::{Creature, CreatureType, Monster, MonsterLoader};
 use crate::position::Position;
+use crate::scaling::DifficultyScalingConfig;
+use crate::spawn_loader::{SpawnLoadReport, SpawnLoader, SpawnValidationConfig};
 use crate::Result;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
+use uuid::Uuid;
 
 /// Spawn point configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -52,12 +64,18 @@ impl SpawnPoint {
 
     /// Check if spawn needs to create new creatures
     pub fn needs_spawn(&self, current_time: u64) -> bool {
+        self.needs_spawn_after(current_time, self.interval as u64 * 1000)
+    }
+
+    /// Check if spawn needs to create new creatures, waiting `interval_ms`
+    /// since the last spawn
+    fn needs_spawn_after(&self, current_time: u64, interval_ms: u64) -> bool {
         if !self.active {
             return false;
         }
 
         // Check interval
-        if current_time < self.last_spawn + (self.interval as u64 * 1000) {
+        if current_time < self.last_spawn + interval_ms {
             return false;
         }
 
@@ -111,12 +129,65 @@ pub struct SpawnMonster {
     pub spawned: u8,
 }
 
+/// Area a spawn boost applies to
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BoostArea {
+    pub center: Position,
+    /// Distance from the center, on the center's floor
+    pub radius: u32,
+}
+
+impl BoostArea {
+    pub fn contains(&self, position: &Position) -> bool {
+        self.center.in_range(position, self.radius)
+    }
+}
+
+/// Spawn modifiers of a running realm event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpawnBoost {
+    /// The realm event the boost belongs to
+    pub event_id: Uuid,
+    /// Boosted area; the whole map when unset
+    pub area: Option<BoostArea>,
+    /// Respawn interval multiplier, e.g. 0.5 halves respawn times
+    pub respawn_factor: f64,
+    /// Spawn points that only exist during the event
+    pub extra_spawns: Vec<SpawnPoint>,
+    /// When the event ends and the boost is reverted
+    pub ends_at: DateTime<Utc>,
+}
+
+impl SpawnBoost {
+    fn applies_to(&self, position: &Position) -> bool {
+        self.area.is_none_or(|area| area.contains(position))
+    }
+}
+
+/// Respawn interval in milliseconds with the strongest covering boost applied
+fn boosted_interval(boosts: &[SpawnBoost], spawn: &SpawnPoint) -> u64 {
+    let factor = boosts
+        .iter()
+        .filter(|b| b.applies_to(&spawn.position))
+        .map(|b| b.respawn_factor)
+        .fold(1.0, f64::min);
+    (spawn.interval as f64 * 1000.0 * factor) as u64
+}
+
 /// Spawn manager handles all spawn points
 pub struct SpawnManager {
     /// All spawn points
     spawns: Vec<SpawnPoint>,
+    /// Running event boosts
+    boosts: Vec<SpawnBoost>,
+    /// Extra spawn points of running boosts, by event
+    event_spawns: Vec<(Uuid, SpawnPoint)>,
     /// Monster loader reference
     monster_loader: Arc<RwLock<MonsterLoader>>,
+    /// Distance monsters may chase away from their spawn center
+    leash_range: u32,
+    /// Dynamic difficulty of the realm
+    difficulty: DifficultyScalingConfig,
     /// Spawn interval check (milliseconds)
     check_interval: u64,
     /// Last check time
@@ -127,12 +198,78 @@ impl SpawnManager {
     pub fn new(monster_loader: Arc<RwLock<MonsterLoader>>) -> Self {
         Self {
             spawns: Vec::new(),
+            boosts: Vec::new(),
+            event_spawns: Vec::new(),
             monster_loader,
+            leash_range: 30,
+            difficulty: DifficultyScalingConfig::default(),
             check_interval: 1000, // Check every second
             last_check: 0,
         }
     }
 
+    /// Set how far monsters may stray from their spawn center
+    pub fn with_leash_range(mut self, leash_range: u32) -> Self {
+        self.leash_range = leash_range;
+        self
+    }
+
+    /// Set the realm's dynamic difficulty scaling
+    pub fn with_difficulty_scaling(mut self, config: DifficultyScalingConfig) -> Self {
+        self.difficulty = config;
+        self
+    }
+
+    pub fn difficulty_scaling(&self) -> &DifficultyScalingConfig {
+        &self.difficulty
+    }
+
+    /// Rescale a spawned monster to the players near it, given as
+    /// (position, level). Call on spawn and whenever players come or go;
+    /// with nobody left in range the monster returns to its base stats.
+    /// Returns true if the scale changed.
+    pub fn rescale(&self, creature: &mut Creature, players: &[(Position, u16)]) -> bool {
+        if creature.is_summon() || creature.creature_type != CreatureType::Monster {
+            return false;
+        }
+        let levels: Vec<u16> = players
+            .iter()
+            .filter(|(position, _)| position.in_range(&creature.position, self.difficulty.radius))
+            .map(|&(_, level)| level)
+            .collect();
+        let scale = self.difficulty.scale(&levels);
+        if scale == creature.difficulty_scale() {
+            return false;
+        }
+
+        debug!("Rescaling {} for {} nearby players ({:?})", creature.name, levels.len(), scale);
+        creature.apply_difficulty(scale);
+        true
+    }
+
+    /// Return a spawned monster that strayed beyond the leash range (or
+    /// to another floor) to its spawn center and drop its targets.
+    /// Summons follow their master and are never leashed. Returns true if
+    /// the creature was moved.
+    pub fn enforce_leash(&self, creature: &mut Creature) -> bool {
+        if creature.is_summon() || creature.creature_type != CreatureType::Monster {
+            return false;
+        }
+        let Some(home) = creature.spawn_position else {
+            return false;
+        };
+        if creature.position.in_range(&home, self.leash_range) {
+            return false;
+        }
+
+        debug!("Leashing {} back to its spawn at {:?}", creature.name, home);
+        creature.position = home;
+        creature.path.clear();
+        creature.set_attack_target(None);
+        creature.set_follow_target(None);
+        true
+    }
+
     /// Add a spawn point
     pub fn add_spawn(&mut self, spawn: SpawnPoint) {
         self.spawns.push(spawn);
@@ -145,14 +282,71 @@ impl SpawnManager {
         self.spawns.len() != initial_len
     }
 
-    /// Get spawn at position
+    /// Get spawn at position, including event spawn points
     pub fn get_spawn(&self, position: &Position) -> Option<&SpawnPoint> {
-        self.spawns.iter().find(|s| s.position == *position)
+        self.spawns
+            .iter()
+            .chain(self.event_spawns.iter().map(|(_, spawn)| spawn))
+            .find(|s| s.position == *position)
     }
 
-    /// Get mutable spawn at position
+    /// Get mutable spawn at position, including event spawn points
     pub fn get_spawn_mut(&mut self, position: &Position) -> Option<&mut SpawnPoint> {
-        self.spawns.iter_mut().find(|s| s.position == *position)
+        self.spawns
+            .iter_mut()
+            .chain(self.event_spawns.iter_mut().map(|(_, spawn)| spawn))
+            .find(|s| s.position == *position)
+    }
+
+    /// Start an event's spawn boost. Starting the same event again
+    /// replaces its previous boost.
+    pub fn start_boost(&mut self, mut boost: SpawnBoost) -> Vec<u32> {
+        let replaced = self.end_boost(boost.event_id);
+        boost.respawn_factor = boost.respawn_factor.clamp(0.1, 1.0);
+        for spawn in boost.extra_spawns.drain(..) {
+            self.event_spawns.push((boost.event_id, spawn));
+        }
+        info!("Spawn boost for event {} started", boost.event_id);
+        self.boosts.push(boost);
+        replaced
+    }
+
+    /// End an event's spawn boost. Returns the creatures spawned by its
+    /// extra spawn points, which should be removed from the map.
+    pub fn end_boost(&mut self, event_id: Uuid) -> Vec<u32> {
+        let before = self.boosts.len();
+        self.boosts.retain(|b| b.event_id != event_id);
+
+        let mut creatures = Vec::new();
+        self.event_spawns.retain(|(id, spawn)| {
+            if *id == event_id {
+                creatures.extend_from_slice(&spawn.spawned_creatures);
+                false
+            } else {
+                true
+            }
+        });
+        if self.boosts.len() != before {
+            info!("Spawn boost for event {} ended", event_id);
+        }
+        creatures
+    }
+
+    /// End every boost whose event is over at `now`
+    pub fn expire_boosts(&mut self, now: DateTime<Utc>) -> Vec<u32> {
+        let expired: Vec<Uuid> = self.boosts.iter().filter(|b| b.ends_at <= now).map(|b| b.event_id).collect();
+        expired.into_iter().flat_map(|event_id| self.end_boost(event_id)).collect()
+    }
+
+    /// Running spawn boosts
+    pub fn boosts(&self) -> &[SpawnBoost] {
+        &self.boosts
+    }
+
+    /// Respawn interval of a spawn point in milliseconds, with the
+    /// strongest boost covering it applied
+    pub fn effective_interval(&self, spawn: &SpawnPoint) -> u64 {
+        boosted_interval(&self.boosts, spawn)
     }
 
     /// Get all spawns
@@ -170,9 +364,14 @@ impl SpawnManager {
 
         let mut requests = Vec::new();
         let monster_loader = self.monster_loader.read().await;
-
-        for spawn in &mut self.spawns {
-            if !spawn.needs_spawn(current_time) {
+        let boosts = &self.boosts;
+        let spawns = self.spawns
+            .iter_mut()
+            .chain(self.event_spawns.iter_mut().map(|(_, spawn)| spawn));
+
+        for spawn in spawns {
+            let interval_ms = boosted_interval(boosts, spawn);
+            if !spawn.needs_spawn_after(current_time, interval_ms) {
                 continue;
             }
 
@@ -229,11 +428,22 @@ impl SpawnManager {
         }
     }
 
-    /// Load spawns from XML file
-    pub fn load_xml(&mut self, path: &str) -> Result<()> {
+    /// Load spawns from XML file, adding every spawn that passed validation.
+    /// Invalid entries are skipped and listed in the returned report.
+    pub async fn load_xml(&mut self, path: &str, config: SpawnValidationConfig) -> Result<SpawnLoadReport> {
         info!("Loading spawns from: {}", path);
-        // XML parsing implementation
-        Ok(())
+        let report = {
+            let monster_loader = self.monster_loader.read().await;
+            SpawnLoader::new(&monster_loader, config).load_file(path)?
+        };
+
+        for issue in &report.issues {
+            warn!("{}: {}", path, issue);
+        }
+        self.spawns.extend(report.spawns.iter().cloned());
+        info!("Loaded {} spawns ({} issues)", report.spawns.len(), report.issues.len());
+
+        Ok(report)
     }
 
     /// Get total spawn count
@@ -348,4 +558,97 @@ mod tests {
         assert!(!positions.is_empty());
         assert!(positions.contains(&Position::new(100, 100, 7)));
     }
+
+    fn manager() -> SpawnManager {
+        let mut monsters = MonsterLoader::new();
+        monsters.add(Monster::new("Rat".to_string()));
+        let mut manager = SpawnManager::new(Arc::new(RwLock::new(monsters)));
+        let mut spawn = SpawnPoint::new(Position::new(100, 100, 7), 2, 60);
+        spawn.add_monster("Rat".to_string(), 1);
+        manager.add_spawn(spawn);
+        manager
+    }
+
+    #[test]
+    fn test_leash_return() {
+        let manager = manager().with_leash_range(10);
+        let home = Position::new(100, 100, 7);
+        let mut rat = Monster::new("Rat".to_string()).spawn(home);
+        rat.set_attack_target(Some(42));
+
+        rat.position = Position::new(108, 100, 7);
+        assert!(!manager.enforce_leash(&mut rat));
+        assert_eq!(rat.combat.attack_target, Some(42));
+
+        rat.position = Position::new(111, 100, 7);
+        assert!(manager.enforce_leash(&mut rat));
+        assert_eq!(rat.position, home);
+        assert_eq!(rat.combat.attack_target, None);
+
+        // Summons aren't leashed to a spawn
+        let mut summon = Creature::new("Rat".to_string(), CreatureType::Summon, Position::new(200, 200, 7));
+        summon.spawn_position = Some(home);
+        assert!(!manager.enforce_leash(&mut summon));
+    }
+
+    fn boost(event_id: Uuid) -> SpawnBoost {
+        let mut extra = SpawnPoint::new(Position::new(105, 100, 7), 1, 30);
+        extra.add_monster("Rat".to_string(), 2);
+        SpawnBoost {
+            event_id,
+            area: Some(BoostArea { center: Position::new(100, 100, 7), radius: 10 }),
+            respawn_factor: 0.5,
+            extra_spawns: vec![extra],
+            ends_at: Utc::now() + chrono::Duration::hours(2),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_event_boost_speeds_up_respawns() {
+        let mut manager = manager();
+        let base = Position::new(100, 100, 7);
+        manager.tick(60_000).await;
+        manager.record_spawn(&base, "Rat", 1);
+        manager.record_death(&base, "Rat", 1);
+
+        // Without a boost the rat respawns 60 seconds after the last spawn
+        assert!(manager.tick(90_000).await.is_empty());
+
+        let event_id = Uuid::new_v4();
+        manager.start_boost(boost(event_id));
+        assert_eq!(manager.effective_interval(manager.get_spawn(&base).unwrap()), 30_000);
+
+        // Halved respawn time, and the event spawn point spawns as well
+        let requests = manager.tick(91_000).await;
+        assert_eq!(requests.iter().filter(|r| r.spawn_position == base).count(), 1);
+        assert_eq!(requests.iter().filter(|r| r.spawn_position == Position::new(105, 100, 7)).count(), 2);
+
+        // Spawns outside the area aren't boosted
+        let far = SpawnPoint::new(Position::new(500, 500, 7), 2, 60);
+        assert_eq!(manager.effective_interval(&far), 60_000);
+    }
+
+    #[tokio::test]
+    async fn test_boost_reverts_after_event() {
+        let mut manager = manager();
+        let base = Position::new(100, 100, 7);
+        let event_id = Uuid::new_v4();
+        manager.start_boost(boost(event_id));
+        manager.tick(60_000).await;
+        manager.record_spawn(&Position::new(105, 100, 7), "Rat", 7);
+
+        // Not over yet
+        assert!(manager.expire_boosts(Utc::now()).is_empty());
+        assert_eq!(manager.boosts().len(), 1);
+
+        // Event creatures are handed back for removal and base spawns are untouched
+        let removed = manager.expire_boosts(Utc::now() + chrono::Duration::hours(3));
+        assert_eq!(removed, vec![7]);
+        assert!(manager.boosts().is_empty());
+        assert!(manager.get_spawn(&Position::new(105, 100, 7)).is_none());
+        assert_eq!(manager.spawn_count(), 1);
+        let spawn = manager.get_spawn(&base).unwrap();
+        assert_eq!(spawn.interval, 60);
+        assert_eq!(manager.effective_interval(spawn), 60_000);
+    }
 }
@@ -0,0 +1,309 @@
+//! Spawn file loader and validator
+//!
+//! Parses spawn XML files into spawn points and validates each entry:
+//! monster names must resolve through the `MonsterLoader`, spawn centers
+//! and monster positions must lie inside the map, and radii and respawn
+//! intervals must be sane. Bad entries are skipped and reported instead of
+//! aborting the whole load; only a malformed document is a hard error.
+//! Spawns and issues are produced in document order, so the same file
+//! always yields the same result.
+
+use crate::creature::MonsterLoader;
+use crate::position::Position;
+use crate::spawn::SpawnPoint;
+use crate::{Result, WorldError, MAP_MAX_X, MAP_MAX_Y, MAP_MAX_Z};
+
+/// Validation limits
+#[derive(Debug, Clone)]
+pub struct SpawnValidationConfig {
+    /// Map width in tiles
+    pub map_width: u16,
+    /// Map height in tiles
+    pub map_height: u16,
+    /// Largest accepted spawn radius; larger radii are clamped
+    pub max_radius: u8,
+    /// Shortest accepted respawn interval in seconds
+    pub min_interval: u32,
+    /// Longest respawn interval before it is reported as suspicious
+    pub max_interval: u32,
+}
+
+impl Default for SpawnValidationConfig {
+    fn default() -> Self {
+        Self {
+            map_width: MAP_MAX_X,
+            map_height: MAP_MAX_Y,
+            max_radius: 30,
+            min_interval: 1,
+            max_interval: 24 * 60 * 60,
+        }
+    }
+}
+
+impl SpawnValidationConfig {
+    pub fn with_map_size(mut self, width: u16, height: u16) -> Self {
+        self.map_width = width;
+        self.map_height = height;
+        self
+    }
+
+    fn in_bounds(&self, x: i64, y: i64, z: i64) -> bool {
+        (0..self.map_width as i64).contains(&x)
+            && (0..self.map_height as i64).contains(&y)
+            && (0..=MAP_MAX_Z as i64).contains(&z)
+    }
+}
+
+/// How serious a spawn issue is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpawnIssueSeverity {
+    /// The entry was loaded, adjusted, or harmlessly skipped
+    Warning,
+    /// The entry was invalid and dropped
+    Error,
+}
+
+/// A problem found while loading a spawn file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpawnIssue {
+    pub severity: SpawnIssueSeverity,
+    /// Line in the spawn file
+    pub line: u32,
+    pub message: String,
+}
+
+impl std::fmt::Display for SpawnIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let severity = match self.severity {
+            SpawnIssueSeverity::Warning => "warning",
+            SpawnIssueSeverity::Error => "error",
+        };
+        write!(f, "line {}: {}: {}", self.line, severity, self.message)
+    }
+}
+
+/// Result of loading a spawn file
+#[derive(Debug, Clone, Default)]
+pub struct SpawnLoadReport {
+    /// Spawn points that passed validation
+    pub spawns: Vec<SpawnPoint>,
+    /// Problems found, in document order
+    pub issues: Vec<SpawnIssue>,
+}
+
+impl SpawnLoadReport {
+    pub fn warnings(&self) -> impl Iterator<Item = &SpawnIssue> {
+        self.issues.iter().filter(|i| i.severity == SpawnIssueSeverity::Warning)
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &SpawnIssue> {
+        self.issues.iter().filter(|i| i.severity == SpawnIssueSeverity::Error)
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    fn warn(&mut self, line: u32, message: String) {
+        self.issues.push(SpawnIssue { severity: SpawnIssueSeverity::Warning, line, message });
+    }
+
+    fn error(&mut self, line: u32, message: String) {
+        self.issues.push(SpawnIssue { severity: SpawnIssueSeverity::Error, line, message });
+    }
+}
+
+/// Loads and validates spawn files
+pub struct SpawnLoader<'a> {
+    monsters: &'a MonsterLoader,
+    config: SpawnValidationConfig,
+}
+
+impl<'a> SpawnLoader<'a> {
+    pub fn new(monsters: &'a MonsterLoader, config: SpawnValidationConfig) -> Self {
+        Self { monsters, config }
+    }
+
+    /// Load a spawn XML file
+    pub fn load_file(&self, path: &str) -> Result<SpawnLoadReport> {
+        let xml = std::fs::read_to_string(path)?;
+        self.parse(&xml)
+    }
+
+    /// Parse spawn XML. Fails only if the document itself is malformed.
+    pub fn parse(&self, xml: &str) -> Result<SpawnLoadReport> {
+        let doc = roxmltree::Document::parse(xml).map_err(|e| WorldError::XmlParse(e.to_string()))?;
+        let root = doc.root_element();
+        if !root.has_tag_name("spawns") {
+            return Err(WorldError::XmlParse(format!(
+                "expected <spawns> root element, found <{}>",
+                root.tag_name().name()
+            )));
+        }
+
+        let mut report = SpawnLoadReport::default();
+        for node in root.children().filter(|n| n.has_tag_name("spawn")) {
+            if let Some(spawn) = self.parse_spawn(&doc, node, &mut report) {
+                report.spawns.push(spawn);
+            }
+        }
+        Ok(report)
+    }
+
+    fn parse_spawn(
+        &self,
+        doc: &roxmltree::Document,
+        node: roxmltree::Node,
+        report: &mut SpawnLoadReport,
+    ) -> Option<SpawnPoint> {
+        let line = doc.text_pos_at(node.range().start).row;
+
+        let (Some(x), Some(y), Some(z)) = (
+            int_attr(node, "centerx"),
+            int_attr(node, "centery"),
+            int_attr(node, "centerz"),
+        ) else {
+            report.error(line, "spawn is missing centerx/centery/centerz".to_string());
+            return None;
+        };
+        if !self.config.in_bounds(x, y, z) {
+            report.error(line, format!("spawn center ({}, {}, {}) is outside the map", x, y, z));
+            return None;
+        }
+        let center = Position::new(x as u16, y as u16, z as u8);
+
+        let radius = int_attr(node, "radius").unwrap_or(1);
+        let radius = if radius < 0 {
+            report.error(line, format!("spawn at {:?} has negative radius {}", center, radius));
+            return None;
+        } else if radius > self.config.max_radius as i64 {
+            report.warn(
+                line,
+                format!("spawn at {:?} radius {} clamped to {}", center, radius, self.config.max_radius),
+            );
+            self.config.max_radius
+        } else {
+            radius as u8
+        };
+
+        let mut monsters: Vec<(String, u8)> = Vec::new();
+        let mut interval: Option<u32> = None;
+
+        for child in node.children().filter(|n| n.has_tag_name("monster")) {
+            let line = doc.text_pos_at(child.range().start).row;
+
+            let Some(name) = child.attribute("name").filter(|n| !n.trim().is_empty()) else {
+                report.error(line, "monster entry is missing a name".to_string());
+                continue;
+            };
+            let Some(monster) = self.monsters.get(name) else {
+                report.warn(line, format!("unknown monster '{}' skipped", name));
+                continue;
+            };
+
+            let (dx, dy) = (int_attr(child, "x").unwrap_or(0), int_attr(child, "y").unwrap_or(0));
+            let (mx, my, mz) = (x + dx, y + dy, int_attr(child, "z").unwrap_or(z));
+            if !self.config.in_bounds(mx, my, mz) {
+                report.error(line, format!("'{}' at ({}, {}, {}) is outside the map", name, mx, my, mz));
+                continue;
+            }
+            if dx.abs() > radius as i64 || dy.abs() > radius as i64 {
+                report.warn(line, format!("'{}' is placed outside the spawn radius", name));
+            }
+
+            let spawn_time = int_attr(child, "spawntime").unwrap_or(60);
+            if spawn_time < self.config.min_interval as i64 {
+                report.error(line, format!("'{}' has invalid spawn time {}s", name, spawn_time));
+                continue;
+            }
+            if spawn_time > self.config.max_interval as i64 {
+                report.warn(line, format!("'{}' has unusually long spawn time {}s", name, spawn_time));
+            }
+            let spawn_time = spawn_time.min(u32::MAX as i64) as u32;
+            interval = Some(interval.map_or(spawn_time, |i| i.min(spawn_time)));
+
+            match monsters.iter_mut().find(|(n, _)| n.eq_ignore_ascii_case(&monster.name)) {
+                Some((_, count)) => *count = count.saturating_add(1),
+                None => monsters.push((monster.name.clone(), 1)),
+            }
+        }
+
+        let Some(interval) = interval else {
+            report.warn(line, format!("spawn at {:?} has no valid monsters and was skipped", center));
+            return None;
+        };
+
+        let mut spawn = SpawnPoint::new(center, radius, interval);
+        for (name, count) in monsters {
+            spawn.add_monster(name, count);
+        }
+        Some(spawn)
+    }
+}
+
+fn int_attr(node: roxmltree::Node, name: &str) -> Option<i64> {
+    node.attribute(name).and_then(|v| v.trim().parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::creature::Monster;
+
+    fn monsters() -> MonsterLoader {
+        let mut loader = MonsterLoader::new();
+        loader.add(Monster::new("Rat".to_string()));
+        loader.add(Monster::new("Cave Rat".to_string()));
+        loader
+    }
+
+    #[test]
+    fn test_valid_spawn_file() {
+        let xml = r#"<?xml version="1.0"?>
+<spawns>
+    <spawn centerx="100" centery="100" centerz="7" radius="3">
+        <monster name="Rat" x="1" y="0" z="7" spawntime="60" />
+        <monster name="rat" x="-1" y="1" z="7" spawntime="90" />
+        <monster name="Cave Rat" x="0" y="2" z="7" spawntime="120" />
+    </spawn>
+</spawns>"#;
+        let monsters = monsters();
+        let report = SpawnLoader::new(&monsters, SpawnValidationConfig::default()).parse(xml).unwrap();
+
+        assert!(report.issues.is_empty());
+        assert_eq!(report.spawns.len(), 1);
+        let spawn = &report.spawns[0];
+        assert_eq!(spawn.position, Position::new(100, 100, 7));
+        assert_eq!((spawn.radius, spawn.interval), (3, 60));
+        let counts: Vec<_> = spawn.monsters.iter().map(|m| (m.name.as_str(), m.count)).collect();
+        assert_eq!(counts, vec![("Rat", 2), ("Cave Rat", 1)]);
+    }
+
+    #[test]
+    fn test_unknown_monster_is_a_warning() {
+        let xml = r#"<spawns>
+    <spawn centerx="100" centery="100" centerz="7" radius="3">
+        <monster name="Rat" x="0" y="0" z="7" spawntime="60" />
+        <monster name="Rat Kingg" x="1" y="1" z="7" spawntime="60" />
+    </spawn>
+    <spawn centerx="70000" centery="100" centerz="7" radius="3">
+        <monster name="Rat" x="0" y="0" z="7" spawntime="60" />
+    </spawn>
+</spawns>"#;
+        let monsters = monsters();
+        let config = SpawnValidationConfig::default().with_map_size(1000, 1000);
+        let report = SpawnLoader::new(&monsters, config).parse(xml).unwrap();
+
+        // The good monster still loads; the typo is reported, not fatal
+        assert_eq!(report.spawns.len(), 1);
+        assert_eq!(report.spawns[0].monsters.len(), 1);
+        let warnings: Vec<_> = report.warnings().collect();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].line, 4);
+        assert!(warnings[0].message.contains("Rat Kingg"));
+
+        // The out-of-bounds spawn is dropped with an error
+        assert_eq!(report.errors().count(), 1);
+        assert!(SpawnLoader::new(&monsters, SpawnValidationConfig::default()).parse("<spawns").is_err());
+    }
+}
@@ -4,13 +4,21 @@
 //! services, and premium time using Tibia Coins.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
+use crate::creature::Outfit;
+
 /// Store currency
 pub type TibiaCoins = u32;
 
+/// Highest valid outfit color index
+pub const MAX_OUTFIT_COLOR: u8 = 132;
+
+/// Base outfits every character can preview (citizen, hunter, mage, knight)
+const BASE_LOOK_TYPES: [u16; 8] = [128, 129, 130, 131, 136, 137, 138, 139];
+
 /// Store category
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum StoreCategory {
@@ -330,6 +338,10 @@ pub struct StoreManager {
     purchase_counts: HashMap<u32, HashMap<u32, u32>>,
     /// Player owned items (outfits, mounts)
     player_owned: HashMap<u32, PlayerOwnedStore>,
+    /// Outfit look types known to the store
+    look_types: HashSet<u16>,
+    /// Mount ids known to the store
+    mount_types: HashSet<u16>,
 }
 
 /// Player's owned store items
@@ -357,6 +369,8 @@ impl StoreManager {
             transactions: Vec::new(),
             purchase_counts: HashMap::new(),
             player_owned: HashMap::new(),
+            look_types: BASE_LOOK_TYPES.into_iter().collect(),
+            mount_types: HashSet::new(),
         };
 
         // Initialize default offers
@@ -487,10 +501,90 @@ impl StoreManager {
     pub fn add_offer(&mut self, offer: StoreOffer) {
         let id = offer.id;
         let category = offer.category;
+        for reward in &offer.rewards {
+            match reward {
+                StoreReward::Outfit { look_type, .. } => {
+                    self.look_types.insert(*look_type);
+                }
+                StoreReward::Mount(mount_id) => {
+                    self.mount_types.insert(*mount_id);
+                }
+                _ => {}
+            }
+        }
         self.offers.insert(id, offer);
         self.offers_by_category.entry(category).or_default().push(id);
     }
 
+    /// Register an outfit look type that can be previewed
+    pub fn register_look_type(&mut self, look_type: u16) {
+        self.look_types.insert(look_type);
+    }
+
+    /// Register a mount that can be previewed
+    pub fn register_mount(&mut self, mount_id: u16) {
+        self.mount_types.insert(mount_id);
+    }
+
+    /// Validate an outfit/mount combination and compose its preview
+    pub fn preview_outfit(&self, outfit: &Outfit) -> Result<OutfitPreview, OutfitPreviewError> {
+        if outfit.is_item() {
+            return Err(OutfitPreviewError::ItemAppearance);
+        }
+        if !self.look_types.contains(&outfit.look_type) {
+            return Err(OutfitPreviewError::UnknownLookType(outfit.look_type));
+        }
+        if outfit.look_addons > 3 {
+            return Err(OutfitPreviewError::InvalidAddons(outfit.look_addons));
+        }
+
+        let colors = [
+            ("head", outfit.look_head),
+            ("body", outfit.look_body),
+            ("legs", outfit.look_legs),
+            ("feet", outfit.look_feet),
+        ];
+        validate_colors(&colors)?;
+
+        if outfit.has_mount() {
+            if !self.mount_types.contains(&outfit.look_mount) {
+                return Err(OutfitPreviewError::UnknownMount(outfit.look_mount));
+            }
+            let mount_colors = [
+                ("mount_head", outfit.look_mount_head),
+                ("mount_body", outfit.look_mount_body),
+                ("mount_legs", outfit.look_mount_legs),
+                ("mount_feet", outfit.look_mount_feet),
+            ];
+            validate_colors(&mount_colors)?;
+        }
+
+        let mut appearance = format!(
+            "outfit:{}:{}:{}-{}-{}-{}",
+            outfit.look_type,
+            outfit.look_addons,
+            outfit.look_head,
+            outfit.look_body,
+            outfit.look_legs,
+            outfit.look_feet
+        );
+        if outfit.has_mount() {
+            appearance.push_str(&format!(
+                "/mount:{}:{}-{}-{}-{}",
+                outfit.look_mount,
+                outfit.look_mount_head,
+                outfit.look_mount_body,
+                outfit.look_mount_legs,
+                outfit.look_mount_feet
+            ));
+        }
+
+        Ok(OutfitPreview {
+            outfit: *outfit,
+            appearance,
+        })
+    }
+
     /// Get offer by ID
     pub fn get_offer(&self, offer_id: u32) -> Option<&StoreOffer> {
         self.offers.get(&offer_id)
@@ -659,6 +753,47 @@ impl StoreManager {
     }
 }
 
+fn validate_colors(colors: &[(&'static str, u8)]) -> Result<(), OutfitPreviewError> {
+    match colors.iter().find(|(_, value)| *value > MAX_OUTFIT_COLOR) {
+        Some((part, value)) => Err(OutfitPreviewError::ColorOutOfRange { part, value: *value }),
+        None => Ok(()),
+    }
+}
+
+/// A validated outfit ready to be shown in the store preview
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutfitPreview {
+    pub outfit: Outfit,
+    /// Composed appearance reference used by the client renderer
+    pub appearance: String,
+}
+
+/// Outfit preview validation errors
+#[derive(Debug, Clone, PartialEq)]
+pub enum OutfitPreviewError {
+    UnknownLookType(u16),
+    UnknownMount(u16),
+    InvalidAddons(u8),
+    ColorOutOfRange { part: &'static str, value: u8 },
+    ItemAppearance,
+}
+
+impl std::fmt::Display for OutfitPreviewError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownLookType(look_type) => write!(f, "Unknown outfit look type {}", look_type),
+            Self::UnknownMount(mount_id) => write!(f, "Unknown mount {}", mount_id),
+            Self::InvalidAddons(addons) => write!(f, "Invalid addon flags {}", addons),
+            Self::ColorOutOfRange { part, value } => {
+                write!(f, "Color {} for {} is out of range (0-{})", value, part, MAX_OUTFIT_COLOR)
+            }
+            Self::ItemAppearance => write!(f, "Item appearances cannot be previewed as outfits"),
+        }
+    }
+}
+
+impl std::error::Error for OutfitPreviewError {}
+
 impl Default for StoreManager {
     fn default() -> Self {
         Self::new()
@@ -710,4 +845,39 @@ mod tests {
         let result = store.purchase(1, 1, None);
         assert!(matches!(result, PurchaseResult::InsufficientCoins { .. }));
     }
+
+    #[test]
+    fn test_preview_rejects_out_of_range_color() {
+        let store = StoreManager::new();
+        let outfit = Outfit::with_colors(128, 78, 133, 58, 76);
+
+        assert_eq!(
+            store.preview_outfit(&outfit).unwrap_err(),
+            OutfitPreviewError::ColorOutOfRange { part: "body", value: 133 }
+        );
+        assert_eq!(
+            store.preview_outfit(&Outfit::new(9999)).unwrap_err(),
+            OutfitPreviewError::UnknownLookType(9999)
+        );
+    }
+
+    #[test]
+    fn test_valid_outfit_preview() {
+        let mut store = StoreManager::new();
+        store.register_mount(368);
+
+        let outfit = Outfit::with_colors(128, 78, 68, 58, 132)
+            .with_addons(3)
+            .with_mount(368)
+            .with_mount_colors(0, 10, 20, 30);
+        let preview = store.preview_outfit(&outfit).unwrap();
+        assert_eq!(preview.appearance, "outfit:128:3:78-68-58-132/mount:368:0-10-20-30");
+
+        // Unknown mounts are rejected even with a valid outfit
+        let unknown_mount = Outfit::new(128).with_mount(1);
+        assert_eq!(
+            store.preview_outfit(&unknown_mount).unwrap_err(),
+            OutfitPreviewError::UnknownMount(1)
+        );
+    }
 }